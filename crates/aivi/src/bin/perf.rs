@@ -4,7 +4,8 @@ use std::path::PathBuf;
 use std::time::Instant;
 
 use aivi::{
-    check_modules, check_types, desugar_modules, file_diagnostics_have_errors, lower_kernel,
+    check_modules, check_types, desugar_modules, embedded_stdlib_modules,
+    file_diagnostics_have_errors, lower_kernel, run_native, Module,
 };
 
 #[derive(Debug, Clone)]
@@ -12,6 +13,11 @@ struct Fixture {
     name: String,
     path: PathBuf,
     contents: String,
+    /// Fixtures importing stdlib modules (e.g. `aivi.ui`) are checked together with the
+    /// embedded stdlib, so their resolve/typecheck/lower timings include it.
+    uses_stdlib: bool,
+    /// Fixtures exporting `main` also get it run, timed as `run_ns`.
+    runs_main: bool,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -21,9 +27,16 @@ struct FixtureMetrics {
     resolve_ns: u128,
     typecheck_ns: u128,
     lower_ns: u128,
+    /// Only measured for fixtures that define `main`; excluded from `total_ns`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    run_ns: u128,
     total_ns: u128,
 }
 
+fn is_zero(value: &u128) -> bool {
+    *value == 0
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct PerfReport {
     fixtures: BTreeMap<String, FixtureMetrics>,
@@ -55,8 +68,11 @@ fn load_fixtures() -> Vec<Fixture> {
             .unwrap_or("fixture")
             .to_string();
         let contents = fs::read_to_string(&path).expect("read fixture");
+        let (modules, _) = aivi::parse_modules(&path, &contents);
         fixtures.push(Fixture {
             name,
+            uses_stdlib: imports_stdlib(&modules),
+            runs_main: exports_main(&modules),
             path,
             contents,
         });
@@ -65,6 +81,26 @@ fn load_fixtures() -> Vec<Fixture> {
     fixtures
 }
 
+/// The implicit prelude import alone does not count as using the stdlib.
+fn imports_stdlib(modules: &[Module]) -> bool {
+    let defined = |name: &str| modules.iter().any(|module| module.name.name == name);
+    modules.iter().any(|module| {
+        module.uses.iter().any(|use_decl| {
+            let name = use_decl.module.name.as_str();
+            name != "aivi.prelude" && !defined(name)
+        })
+    })
+}
+
+fn exports_main(modules: &[Module]) -> bool {
+    modules.iter().any(|module| {
+        module
+            .exports
+            .iter()
+            .any(|export| export.name.name == "main")
+    })
+}
+
 fn median(samples: &mut [u128]) -> u128 {
     samples.sort_unstable();
     samples[samples.len() / 2]
@@ -76,6 +112,7 @@ fn measure_fixture(fixture: &Fixture, iters: usize) -> FixtureMetrics {
     let mut resolve = Vec::with_capacity(iters);
     let mut typecheck = Vec::with_capacity(iters);
     let mut lower = Vec::with_capacity(iters);
+    let mut run = Vec::with_capacity(iters);
     let mut total = Vec::with_capacity(iters);
 
     for i in 0..=iters {
@@ -87,24 +124,43 @@ fn measure_fixture(fixture: &Fixture, iters: usize) -> FixtureMetrics {
             panic!("parse diagnostics for {}: {parse_diags:?}", fixture.name);
         }
 
+        let modules = if fixture.uses_stdlib {
+            let mut all = embedded_stdlib_modules();
+            all.extend(modules);
+            all
+        } else {
+            modules
+        };
         let mut diags = check_modules(&modules);
         let t2 = Instant::now();
+        // Like `aivi check`, only the fixture's own diagnostics count.
+        diags.retain(|diag| !diag.path.starts_with("<embedded:"));
         if file_diagnostics_have_errors(&diags) {
             panic!("resolver diagnostics for {}: {diags:?}", fixture.name);
         }
 
         diags.extend(check_types(&modules));
         let t3 = Instant::now();
+        diags.retain(|diag| !diag.path.starts_with("<embedded:"));
         if file_diagnostics_have_errors(&diags) {
             panic!("typecheck diagnostics for {}: {diags:?}", fixture.name);
         }
 
         let hir = desugar_modules(&modules);
+        let program = fixture.runs_main.then(|| hir.clone());
         let _kernel = lower_kernel(hir);
         let t4 = Instant::now();
 
+        if let Some(program) = program {
+            if let Err(err) = run_native(program) {
+                panic!("running {} failed: {err}", fixture.name);
+            }
+            run.push((Instant::now() - t4).as_nanos());
+        }
+
         if i == 0 {
             // Warmup: don't record the first iteration.
+            run.clear();
             continue;
         }
         parse.push((t1 - t0).as_nanos());
@@ -120,6 +176,7 @@ fn measure_fixture(fixture: &Fixture, iters: usize) -> FixtureMetrics {
         resolve_ns: median(&mut resolve),
         typecheck_ns: median(&mut typecheck),
         lower_ns: median(&mut lower),
+        run_ns: if run.is_empty() { 0 } else { median(&mut run) },
         total_ns: median(&mut total),
     }
 }
@@ -128,7 +185,10 @@ fn build_report() -> PerfReport {
     let fixtures = load_fixtures();
     let mut out = BTreeMap::new();
     for fixture in fixtures {
-        let iters = if fixture.contents.len() < 4 * 1024 {
+        let iters = if fixture.uses_stdlib {
+            // Each iteration re-checks the whole stdlib.
+            3
+        } else if fixture.contents.len() < 4 * 1024 {
             25
         } else {
             7
@@ -208,6 +268,13 @@ fn main() {
                         metrics.total_ns, max, base.total_ns, max_multiplier
                     ));
                 }
+                let max_run = (base.run_ns as f64) * max_multiplier;
+                if base.run_ns > 0 && (metrics.run_ns as f64) > max_run {
+                    failures.push(format!(
+                        "{name}: run_ns {} > allowed {:.0} (baseline {} * {})",
+                        metrics.run_ns, max_run, base.run_ns, max_multiplier
+                    ));
+                }
             }
 
             if !failures.is_empty() {
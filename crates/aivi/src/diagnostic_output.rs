@@ -0,0 +1,268 @@
+//! Machine-readable renderings of diagnostics (`aivi check --format short|json|sarif`) and the
+//! `--deny` / `--allow` rules applied before reporting them.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::diagnostics::{
+    diagnostic_fixes, Diagnostic, DiagnosticFix, DiagnosticSeverity, FileDiagnostic, Span,
+};
+use crate::error_codes::{error_code_docs_url, error_code_info};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiagnosticFormat {
+    /// Source frames on stderr, as rendered by `render_diagnostics`.
+    #[default]
+    Human,
+    /// One `path:line:column: severity[CODE] message` line per diagnostic.
+    Short,
+    Json,
+    /// SARIF 2.1.0, as consumed by code-scanning services.
+    Sarif,
+}
+
+impl DiagnosticFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "human" => Some(Self::Human),
+            "short" => Some(Self::Short),
+            "json" => Some(Self::Json),
+            "sarif" => Some(Self::Sarif),
+            _ => None,
+        }
+    }
+}
+
+/// Per-code severity overrides. Entries are diagnostic codes or `warnings` for every warning;
+/// a code entry wins over `warnings`. Errors are never affected.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticFilter {
+    /// Warnings reported as errors.
+    pub deny: Vec<String>,
+    /// Warnings that are not reported at all.
+    pub allow: Vec<String>,
+}
+
+impl DiagnosticFilter {
+    pub fn apply(&self, diagnostics: Vec<FileDiagnostic>) -> Vec<FileDiagnostic> {
+        diagnostics
+            .into_iter()
+            .filter_map(|mut diag| {
+                if diag.diagnostic.severity == DiagnosticSeverity::Error {
+                    return Some(diag);
+                }
+                match self.denies(&diag.diagnostic.code) {
+                    Some(true) => {
+                        diag.diagnostic.severity = DiagnosticSeverity::Error;
+                        Some(diag)
+                    }
+                    Some(false) => None,
+                    None => Some(diag),
+                }
+            })
+            .collect()
+    }
+
+    /// `Some(true)` when the warning `code` is denied, `Some(false)` when it is allowed.
+    fn denies(&self, code: &str) -> Option<bool> {
+        let matches =
+            |rules: &[String], name: &str| rules.iter().any(|rule| rule.eq_ignore_ascii_case(name));
+        if matches(&self.deny, code) {
+            Some(true)
+        } else if matches(&self.allow, code) {
+            Some(false)
+        } else if matches(&self.deny, "warnings") {
+            Some(true)
+        } else if matches(&self.allow, "warnings") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn render_diagnostics_short(diagnostics: &[FileDiagnostic]) -> String {
+    let mut output = String::new();
+    for diag in diagnostics {
+        let start = &diag.diagnostic.span.start;
+        output.push_str(&format!(
+            "{}:{}:{}: {}[{}] {}\n",
+            diag.path,
+            start.line,
+            start.column,
+            severity_name(diag.diagnostic.severity),
+            diag.diagnostic.code,
+            diag.diagnostic.message
+        ));
+    }
+    output
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    path: &'a str,
+    #[serde(flatten)]
+    diagnostic: &'a Diagnostic,
+    fixes: Vec<DiagnosticFix>,
+}
+
+pub fn diagnostics_to_json(diagnostics: &[FileDiagnostic]) -> String {
+    let mut sources = SourceCache::default();
+    let entries: Vec<JsonDiagnostic> = diagnostics
+        .iter()
+        .map(|diag| JsonDiagnostic {
+            path: &diag.path,
+            diagnostic: &diag.diagnostic,
+            fixes: sources.fixes(diag),
+        })
+        .collect();
+    let errors = diagnostics
+        .iter()
+        .filter(|diag| diag.diagnostic.severity == DiagnosticSeverity::Error)
+        .count();
+    let report = json!({
+        "diagnostics": entries,
+        "errors": errors,
+        "warnings": diagnostics.len() - errors,
+    });
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+pub fn diagnostics_to_sarif(diagnostics: &[FileDiagnostic]) -> String {
+    let mut sources = SourceCache::default();
+    let mut rule_indices = BTreeMap::new();
+    for diag in diagnostics {
+        rule_indices.insert(diag.diagnostic.code.as_str(), 0);
+    }
+    let rules: Vec<Value> = rule_indices
+        .iter_mut()
+        .enumerate()
+        .map(|(index, (code, slot))| {
+            *slot = index;
+            let mut rule = json!({ "id": code, "helpUri": error_code_docs_url(code) });
+            if let Some(info) = error_code_info(code) {
+                rule["shortDescription"] = json!({ "text": info.title });
+                rule["fullDescription"] = json!({ "text": info.explanation });
+            }
+            rule
+        })
+        .collect();
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|diag| {
+            let location = |span: &Span| {
+                json!({
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": sarif_uri(&diag.path) },
+                        "region": sarif_region(span),
+                    }
+                })
+            };
+            let related: Vec<Value> = diag
+                .diagnostic
+                .labels
+                .iter()
+                .map(|label| {
+                    let mut related = location(&label.span);
+                    related["message"] = json!({ "text": label.message });
+                    related
+                })
+                .collect();
+            let fixes: Vec<Value> = sources
+                .fixes(diag)
+                .into_iter()
+                .map(|fix| {
+                    let replacements: Vec<Value> = fix
+                        .edits
+                        .iter()
+                        .map(|edit| {
+                            json!({
+                                "deletedRegion": sarif_region(&edit.span),
+                                "insertedContent": { "text": edit.new_text },
+                            })
+                        })
+                        .collect();
+                    json!({
+                        "description": { "text": fix.title },
+                        "artifactChanges": [{
+                            "artifactLocation": { "uri": sarif_uri(&diag.path) },
+                            "replacements": replacements,
+                        }],
+                    })
+                })
+                .collect();
+            let mut result = json!({
+                "ruleId": diag.diagnostic.code,
+                "ruleIndex": rule_indices[diag.diagnostic.code.as_str()],
+                "level": severity_name(diag.diagnostic.severity),
+                "message": { "text": diag.diagnostic.message },
+                "locations": [location(&diag.diagnostic.span)],
+            });
+            if !related.is_empty() {
+                result["relatedLocations"] = Value::Array(related);
+            }
+            if !fixes.is_empty() {
+                result["fixes"] = Value::Array(fixes);
+            }
+            result
+        })
+        .collect();
+    let report = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "aivi",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://mendrik.github.io/aivi/",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+fn severity_name(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::Error => "error",
+        DiagnosticSeverity::Warning => "warning",
+    }
+}
+
+fn sarif_uri(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// SARIF regions are 1-based with an exclusive end column.
+fn sarif_region(span: &Span) -> Value {
+    json!({
+        "startLine": span.start.line,
+        "startColumn": span.start.column,
+        "endLine": span.end.line,
+        "endColumn": span.end.column + 1,
+    })
+}
+
+/// Sources read on demand to compute quick fixes; embedded modules have none.
+#[derive(Default)]
+struct SourceCache {
+    sources: HashMap<String, Option<String>>,
+}
+
+impl SourceCache {
+    fn fixes(&mut self, diag: &FileDiagnostic) -> Vec<DiagnosticFix> {
+        let source = self
+            .sources
+            .entry(diag.path.clone())
+            .or_insert_with(|| std::fs::read_to_string(&diag.path).ok());
+        match source {
+            Some(source) => diagnostic_fixes(source, &diag.diagnostic),
+            None => Vec::new(),
+        }
+    }
+}
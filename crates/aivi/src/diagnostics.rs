@@ -7,13 +7,13 @@ pub enum DiagnosticSeverity {
     Warning,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct Position {
     pub line: usize,
     pub column: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct Span {
     pub start: Position,
     pub end: Position,
@@ -34,7 +34,7 @@ pub struct Diagnostic {
     pub labels: Vec<DiagnosticLabel>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FileDiagnostic {
     pub path: String,
     pub diagnostic: Diagnostic,
@@ -46,6 +46,113 @@ pub fn file_diagnostics_have_errors(diagnostics: &[FileDiagnostic]) -> bool {
         .any(|diag| diag.diagnostic.severity == DiagnosticSeverity::Error)
 }
 
+/// A text edit of a quick fix. `span` is replaced by `new_text`; a span ending one column before
+/// its start is an insertion point.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticEdit {
+    pub span: Span,
+    pub new_text: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticFix {
+    pub title: String,
+    pub is_preferred: bool,
+    pub edits: Vec<DiagnosticEdit>,
+}
+
+/// Quick fixes for `diagnostic`, computed against the `source` it was reported for.
+pub fn diagnostic_fixes(source: &str, diagnostic: &Diagnostic) -> Vec<DiagnosticFix> {
+    let fix = |title: String, edit: DiagnosticEdit| DiagnosticFix {
+        title,
+        is_preferred: true,
+        edits: vec![edit],
+    };
+    match diagnostic.code.as_str() {
+        "E1001" => {
+            let line = diagnostic.span.end.line;
+            let column = source
+                .split('\n')
+                .nth(line.saturating_sub(1))
+                .map(|text| text.chars().count())
+                .unwrap_or(0);
+            vec![fix(
+                "Insert missing closing quote".to_string(),
+                insert_at(line, column + 1, "\""),
+            )]
+        }
+        "E1002" => vec![fix(
+            "Remove unmatched closing delimiter".to_string(),
+            DiagnosticEdit {
+                span: diagnostic.span.clone(),
+                new_text: String::new(),
+            },
+        )],
+        "E1004" => {
+            let Some(close) = unclosed_delimiter(&diagnostic.message).and_then(closing_for) else {
+                return Vec::new();
+            };
+            let line = source.matches('\n').count() + 1;
+            let column = source
+                .rsplit('\n')
+                .next()
+                .map(|text| text.chars().count())
+                .unwrap_or(0);
+            vec![fix(
+                format!("Insert missing '{close}'"),
+                insert_at(line, column + 1, &close.to_string()),
+            )]
+        }
+        // Typed holes list the bindings that fit as `candidate: name : Type` labels.
+        "E3200" => diagnostic
+            .labels
+            .iter()
+            .filter_map(|label| label.message.strip_prefix("candidate: "))
+            .filter_map(|candidate| candidate.split(" : ").next())
+            .map(|name| DiagnosticFix {
+                title: format!("Fill hole with `{name}`"),
+                is_preferred: false,
+                edits: vec![DiagnosticEdit {
+                    span: diagnostic.span.clone(),
+                    new_text: name.to_string(),
+                }],
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn insert_at(line: usize, column: usize, text: &str) -> DiagnosticEdit {
+    DiagnosticEdit {
+        span: Span {
+            start: Position { line, column },
+            end: Position {
+                line,
+                column: column - 1,
+            },
+        },
+        new_text: text.to_string(),
+    }
+}
+
+fn unclosed_delimiter(message: &str) -> Option<char> {
+    let start = message.find('\'')?;
+    let mut chars = message[start + 1..].chars();
+    let open = chars.next()?;
+    (chars.next()? == '\'').then_some(open)
+}
+
+fn closing_for(open: char) -> Option<char> {
+    match open {
+        '{' => Some('}'),
+        '(' => Some(')'),
+        '[' => Some(']'),
+        _ => None,
+    }
+}
+
 pub fn render_diagnostics(path: &str, diagnostics: &[Diagnostic]) -> String {
     let mut output = String::new();
     let source = std::fs::read_to_string(path).ok();
@@ -85,6 +192,10 @@ fn render_diagnostic_with_source(
         }
     }
     for label in &diagnostic.labels {
+        if label.span == diagnostic.span {
+            output.push_str(&format!("note: {}\n", label.message));
+            continue;
+        }
         let pos = &label.span.start;
         output.push_str(&format!(
             "note: {} at {}:{}:{}\n",
@@ -0,0 +1,855 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::surface::{
+    ClassDecl, Decorator, DomainDecl, DomainItem, Expr, InstanceDecl, Literal, Module, ModuleItem,
+    ScopeItemKind, SpannedName, TypeAlias, TypeCtor, TypeExpr,
+};
+use crate::typecheck::infer_value_types;
+use crate::AiviError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        }
+    }
+}
+
+/// A generated documentation file, relative to the output directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocPage {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDoc {
+    pub name: String,
+    pub doc: Option<String>,
+    pub deprecated: Option<String>,
+    pub values: Vec<ValueDoc>,
+    pub types: Vec<TypeDoc>,
+    pub classes: Vec<ClassDoc>,
+    pub instances: Vec<String>,
+    pub domains: Vec<DomainDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueDoc {
+    pub name: String,
+    pub signature: Option<String>,
+    pub doc: Option<String>,
+    pub deprecated: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDoc {
+    pub header: String,
+    pub constructors: Vec<String>,
+    pub doc: Option<String>,
+    pub deprecated: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassDoc {
+    pub header: String,
+    pub supers: Vec<String>,
+    pub members: Vec<(String, String)>,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainDoc {
+    pub name: String,
+    pub over: String,
+    pub operators: Vec<ValueDoc>,
+    pub literals: Vec<String>,
+    pub doc: Option<String>,
+}
+
+/// A `>>> expr` line in a doc comment, optionally followed by the expected result on the next
+/// line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Doctest {
+    pub item: String,
+    pub expr: String,
+    pub expected: Option<String>,
+}
+
+/// Renders one page per user module plus an index page.
+///
+/// `sources` maps each user module's path to the text it was parsed from. Embedded stdlib modules
+/// are skipped; doc comments are the `//` blocks directly above a declaration (the same convention
+/// the LSP uses for hovers).
+pub fn generate_docs(
+    modules: &[Module],
+    sources: &HashMap<String, String>,
+    format: DocFormat,
+) -> Result<Vec<DocPage>, AiviError> {
+    let (_, inferred) = infer_value_types(modules);
+    let mut docs = Vec::new();
+    for module in modules {
+        if module.path.starts_with("<embedded:") {
+            continue;
+        }
+        let source = sources.get(&module.path).ok_or_else(|| {
+            AiviError::InvalidPath(format!(
+                "no source text for module {} ({})",
+                module.name.name, module.path
+            ))
+        })?;
+        docs.push(module_doc(module, source, inferred.get(&module.name.name)));
+    }
+    docs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut pages = Vec::new();
+    pages.push(DocPage {
+        path: PathBuf::from(format!("index.{}", format.extension())),
+        contents: match format {
+            DocFormat::Markdown => render_index_markdown(&docs),
+            DocFormat::Html => render_index_html(&docs),
+        },
+    });
+    for doc in &docs {
+        pages.push(DocPage {
+            path: PathBuf::from(format!("{}.{}", doc.name, format.extension())),
+            contents: match format {
+                DocFormat::Markdown => render_module_markdown(doc),
+                DocFormat::Html => render_module_html(doc),
+            },
+        });
+    }
+    Ok(pages)
+}
+
+pub fn module_doc(
+    module: &Module,
+    source: &str,
+    inferred: Option<&HashMap<String, String>>,
+) -> ModuleDoc {
+    let lines: Vec<&str> = source.lines().collect();
+    let exported: HashSet<&str> = module
+        .exports
+        .iter()
+        .filter(|item| item.kind == ScopeItemKind::Value)
+        .map(|item| item.name.name.as_str())
+        .collect();
+    let exported_domains: HashSet<&str> = module
+        .exports
+        .iter()
+        .filter(|item| item.kind == ScopeItemKind::Domain)
+        .map(|item| item.name.name.as_str())
+        .collect();
+    let is_public = |name: &str| module.exports.is_empty() || exported.contains(name);
+
+    let mut signatures = HashMap::new();
+    for item in &module.items {
+        if let ModuleItem::TypeSig(sig) = item {
+            signatures.insert(sig.name.name.clone(), sig);
+        }
+    }
+
+    let mut doc = ModuleDoc {
+        name: module.name.name.clone(),
+        doc: doc_block_above(&lines, first_line(&module.annotations, &module.name)),
+        deprecated: deprecated_message(&module.annotations),
+        values: Vec::new(),
+        types: Vec::new(),
+        classes: Vec::new(),
+        instances: Vec::new(),
+        domains: Vec::new(),
+    };
+
+    let mut seen_values = HashSet::new();
+    for item in &module.items {
+        match item {
+            ModuleItem::Def(def) => {
+                if !is_public(&def.name.name) || !seen_values.insert(def.name.name.clone()) {
+                    continue;
+                }
+                let sig = signatures.get(&def.name.name);
+                let start = match sig {
+                    Some(sig) => first_line(&sig.decorators, &sig.name),
+                    None => first_line(&def.decorators, &def.name),
+                };
+                let signature = sig
+                    .map(|sig| type_expr_to_string(&sig.ty))
+                    .or_else(|| inferred.and_then(|types| types.get(&def.name.name).cloned()));
+                let deprecated = deprecated_message(&def.decorators)
+                    .or_else(|| sig.and_then(|sig| deprecated_message(&sig.decorators)));
+                doc.values.push(ValueDoc {
+                    name: def.name.name.clone(),
+                    signature,
+                    doc: doc_block_above(&lines, start),
+                    deprecated,
+                });
+            }
+            ModuleItem::TypeSig(_) => {}
+            ModuleItem::TypeDecl(decl) => {
+                let ctor_public = decl
+                    .constructors
+                    .iter()
+                    .any(|ctor| exported.contains(ctor.name.name.as_str()));
+                if !is_public(&decl.name.name) && !ctor_public {
+                    continue;
+                }
+                doc.types.push(TypeDoc {
+                    header: format!("type {}{}", decl.name.name, format_params(&decl.params)),
+                    constructors: decl.constructors.iter().map(format_type_ctor).collect(),
+                    doc: doc_block_above(&lines, first_line(&decl.decorators, &decl.name)),
+                    deprecated: deprecated_message(&decl.decorators),
+                });
+            }
+            ModuleItem::TypeAlias(alias) => {
+                if !is_public(&alias.name.name) {
+                    continue;
+                }
+                doc.types.push(TypeDoc {
+                    header: format_type_alias(alias),
+                    constructors: Vec::new(),
+                    doc: doc_block_above(&lines, first_line(&alias.decorators, &alias.name)),
+                    deprecated: deprecated_message(&alias.decorators),
+                });
+            }
+            ModuleItem::EffectDecl(effect) => {
+                if !is_public(&effect.name.name) {
+                    continue;
+                }
+                doc.types.push(TypeDoc {
+                    header: format!("effect {}", effect.name.name),
+                    constructors: effect
+                        .operations
+                        .iter()
+                        .map(|op| format!("{}: {}", op.name.name, type_expr_to_string(&op.ty)))
+                        .collect(),
+                    doc: doc_block_above(&lines, first_line(&effect.decorators, &effect.name)),
+                    deprecated: deprecated_message(&effect.decorators),
+                });
+            }
+            ModuleItem::ClassDecl(class_decl) => {
+                if !is_public(&class_decl.name.name) {
+                    continue;
+                }
+                doc.classes.push(class_doc(class_decl, &lines));
+            }
+            ModuleItem::InstanceDecl(instance) => {
+                doc.instances.push(format_instance_decl(instance));
+            }
+            ModuleItem::DomainDecl(domain) => {
+                if !module.exports.is_empty()
+                    && !exported_domains.contains(domain.name.name.as_str())
+                {
+                    continue;
+                }
+                doc.domains.push(domain_doc(domain, &lines));
+            }
+        }
+    }
+    doc
+}
+
+/// Collects `>>>` examples from the doc comments of exported values.
+pub fn collect_doctests(module: &Module, source: &str) -> Vec<Doctest> {
+    let doc = module_doc(module, source, None);
+    let mut out = Vec::new();
+    for value in &doc.values {
+        let Some(text) = value.doc.as_deref() else {
+            continue;
+        };
+        let lines: Vec<&str> = text.lines().collect();
+        let mut index = 0;
+        while index < lines.len() {
+            let Some(expr) = lines[index].trim().strip_prefix(">>>") else {
+                index += 1;
+                continue;
+            };
+            let expected = lines
+                .get(index + 1)
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty() && !line.starts_with(">>>"))
+                .map(|line| line.to_string());
+            index += if expected.is_some() { 2 } else { 1 };
+            out.push(Doctest {
+                item: value.name.clone(),
+                expr: expr.trim().to_string(),
+                expected,
+            });
+        }
+    }
+    out
+}
+
+/// Builds a module whose `@test` definitions run the given doctests against `module_name`.
+///
+/// Examples without an expected line must evaluate to an `Effect`; the others compare with
+/// `assertEq`. Returns the generated source and the qualified names of its tests.
+pub fn doctest_module_source(module_name: &str, doctests: &[Doctest]) -> (String, Vec<String>) {
+    let test_module = format!("{module_name}.doctests");
+    let mut source =
+        format!("module {test_module}\n\nuse aivi.testing (assertEq)\nuse {module_name}\n");
+    let mut names = Vec::new();
+    let mut counters: HashMap<&str, usize> = HashMap::new();
+    for doctest in doctests {
+        let counter = counters.entry(doctest.item.as_str()).or_default();
+        *counter += 1;
+        let base: String = doctest
+            .item
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let name = format!("doctest_{base}_{counter}");
+        let body = match &doctest.expected {
+            Some(expected) => format!("assertEq ({}) ({})", doctest.expr, expected),
+            None => doctest.expr.clone(),
+        };
+        source.push_str(&format!("\n@test\n{name} = {body}\n"));
+        names.push(format!("{test_module}.{name}"));
+    }
+    (source, names)
+}
+
+fn class_doc(class_decl: &ClassDecl, lines: &[&str]) -> ClassDoc {
+    let params = class_decl
+        .params
+        .iter()
+        .map(type_expr_to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    ClassDoc {
+        header: if params.is_empty() {
+            format!("class {}", class_decl.name.name)
+        } else {
+            format!("class {} {}", class_decl.name.name, params)
+        },
+        supers: class_decl.supers.iter().map(type_expr_to_string).collect(),
+        members: class_decl
+            .members
+            .iter()
+            .map(|member| (member.name.name.clone(), type_expr_to_string(&member.ty)))
+            .collect(),
+        doc: doc_block_above(lines, first_line(&class_decl.decorators, &class_decl.name)),
+    }
+}
+
+fn domain_doc(domain: &DomainDecl, lines: &[&str]) -> DomainDoc {
+    let mut signatures = HashMap::new();
+    for item in &domain.items {
+        if let DomainItem::TypeSig(sig) = item {
+            signatures.insert(sig.name.name.clone(), sig);
+        }
+    }
+    let mut operators = Vec::new();
+    let mut literals = Vec::new();
+    let mut seen = HashSet::new();
+    for item in &domain.items {
+        match item {
+            DomainItem::Def(def) => {
+                if !seen.insert(def.name.name.clone()) {
+                    continue;
+                }
+                let sig = signatures.get(&def.name.name);
+                let start = match sig {
+                    Some(sig) => first_line(&sig.decorators, &sig.name),
+                    None => first_line(&def.decorators, &def.name),
+                };
+                operators.push(ValueDoc {
+                    name: def.name.name.clone(),
+                    signature: sig.map(|sig| type_expr_to_string(&sig.ty)),
+                    doc: doc_block_above(lines, start),
+                    deprecated: deprecated_message(&def.decorators),
+                });
+            }
+            DomainItem::LiteralDef(def) => literals.push(def.name.name.clone()),
+            DomainItem::TypeSig(_) | DomainItem::TypeAlias(_) => {}
+        }
+    }
+    DomainDoc {
+        name: domain.name.name.clone(),
+        over: type_expr_to_string(&domain.over),
+        operators,
+        literals,
+        doc: doc_block_above(lines, first_line(&domain.decorators, &domain.name)),
+    }
+}
+
+fn first_line(decorators: &[Decorator], name: &SpannedName) -> usize {
+    decorators
+        .iter()
+        .map(|decorator| decorator.span.start.line)
+        .chain(std::iter::once(name.span.start.line))
+        .min()
+        .unwrap_or(name.span.start.line)
+}
+
+/// `line` is 1-based; collects the contiguous `//` block ending on the line above it.
+fn doc_block_above(lines: &[&str], line: usize) -> Option<String> {
+    let mut index = line.checked_sub(2)?;
+    let mut docs = Vec::new();
+    loop {
+        let current = lines.get(index)?.trim_start();
+        let Some(body) = current.strip_prefix("//") else {
+            break;
+        };
+        docs.push(body.strip_prefix(' ').unwrap_or(body).to_string());
+        if index == 0 {
+            break;
+        }
+        index -= 1;
+    }
+    docs.reverse();
+    (!docs.is_empty()).then(|| docs.join("\n"))
+}
+
+fn deprecated_message(decorators: &[Decorator]) -> Option<String> {
+    decorators
+        .iter()
+        .find(|decorator| decorator.name.name == "deprecated")
+        .map(|decorator| match &decorator.arg {
+            Some(Expr::Literal(Literal::String { text, .. })) => text.clone(),
+            _ => String::new(),
+        })
+}
+
+fn format_params(params: &[SpannedName]) -> String {
+    params.iter().map(|p| format!(" {}", p.name)).collect()
+}
+
+fn format_type_ctor(ctor: &TypeCtor) -> String {
+    let mut out = ctor.name.name.clone();
+    for arg in &ctor.args {
+        out.push(' ');
+        match arg {
+            TypeExpr::Func { .. } | TypeExpr::Apply { .. } => {
+                out.push_str(&format!("({})", type_expr_to_string(arg)))
+            }
+            _ => out.push_str(&type_expr_to_string(arg)),
+        }
+    }
+    out
+}
+
+fn format_type_alias(alias: &TypeAlias) -> String {
+    format!(
+        "type {}{} = {}",
+        alias.name.name,
+        format_params(&alias.params),
+        type_expr_to_string(&alias.aliased)
+    )
+}
+
+fn format_instance_decl(instance: &InstanceDecl) -> String {
+    let mut out = instance.name.name.clone();
+    for param in &instance.params {
+        out.push(' ');
+        match param {
+            TypeExpr::Apply { args, .. } if !args.is_empty() => {
+                out.push_str(&format!("({})", type_expr_to_string(param)))
+            }
+            _ => out.push_str(&type_expr_to_string(param)),
+        }
+    }
+    out
+}
+
+pub(crate) fn type_expr_to_string(expr: &TypeExpr) -> String {
+    match expr {
+        TypeExpr::Name(name) => name.name.clone(),
+        TypeExpr::And { items, .. } => items
+            .iter()
+            .map(type_expr_to_string)
+            .collect::<Vec<_>>()
+            .join(" with "),
+        TypeExpr::Apply { base, args, .. } => {
+            let mut out = match **base {
+                TypeExpr::Func { .. } => format!("({})", type_expr_to_string(base)),
+                _ => type_expr_to_string(base),
+            };
+            for arg in args {
+                out.push(' ');
+                match arg {
+                    TypeExpr::Func { .. } => {
+                        out.push_str(&format!("({})", type_expr_to_string(arg)))
+                    }
+                    TypeExpr::Apply { args, .. } if !args.is_empty() => {
+                        out.push_str(&format!("({})", type_expr_to_string(arg)))
+                    }
+                    _ => out.push_str(&type_expr_to_string(arg)),
+                }
+            }
+            out
+        }
+        TypeExpr::Func { params, result, .. } => {
+            let params = params
+                .iter()
+                .map(|param| match param {
+                    TypeExpr::Func { .. } => format!("({})", type_expr_to_string(param)),
+                    _ => type_expr_to_string(param),
+                })
+                .collect::<Vec<_>>();
+            let mut parts = params;
+            parts.push(type_expr_to_string(result));
+            parts.join(" -> ")
+        }
+        TypeExpr::Record { fields, rest, .. } => {
+            let fields = fields
+                .iter()
+                .map(|(name, ty)| format!("{}: {}", name.name, type_expr_to_string(ty)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            match rest {
+                Some(rest) => format!("{{ {fields} | {} }}", rest.name),
+                None => format!("{{ {fields} }}"),
+            }
+        }
+        TypeExpr::Tuple { items, .. } => format!(
+            "({})",
+            items
+                .iter()
+                .map(type_expr_to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TypeExpr::Star { .. } | TypeExpr::Unknown { .. } => "*".to_string(),
+    }
+}
+
+fn render_index_markdown(docs: &[ModuleDoc]) -> String {
+    let mut out = String::from("# API reference\n\n");
+    for doc in docs {
+        out.push_str(&format!("- [`{}`]({}.md)", doc.name, doc.name));
+        if let Some(summary) = doc.doc.as_deref().and_then(|d| d.lines().next()) {
+            out.push_str(&format!(" — {summary}"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_module_markdown(doc: &ModuleDoc) -> String {
+    let mut out = format!("# Module `{}`\n\n", doc.name);
+    push_deprecated_markdown(&mut out, doc.deprecated.as_deref());
+    push_doc_markdown(&mut out, doc.doc.as_deref());
+
+    if !doc.values.is_empty() {
+        out.push_str("## Values\n\n");
+        for value in &doc.values {
+            push_value_markdown(&mut out, value, "###");
+        }
+    }
+    if !doc.types.is_empty() {
+        out.push_str("## Types\n\n");
+        for ty in &doc.types {
+            out.push_str(&format!("### `{}`\n\n", ty.header));
+            push_deprecated_markdown(&mut out, ty.deprecated.as_deref());
+            if !ty.constructors.is_empty() {
+                out.push_str("| Constructor |\n| --- |\n");
+                for ctor in &ty.constructors {
+                    out.push_str(&format!("| {} |\n", table_code(ctor)));
+                }
+                out.push('\n');
+            }
+            push_doc_markdown(&mut out, ty.doc.as_deref());
+        }
+    }
+    if !doc.classes.is_empty() {
+        out.push_str("## Classes\n\n");
+        for class in &doc.classes {
+            out.push_str(&format!("### `{}`\n\n", class.header));
+            if !class.supers.is_empty() {
+                out.push_str(&format!(
+                    "Superclasses: {}\n\n",
+                    class
+                        .supers
+                        .iter()
+                        .map(|s| format!("`{s}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            push_doc_markdown(&mut out, class.doc.as_deref());
+            if !class.members.is_empty() {
+                out.push_str("| Member | Type |\n| --- | --- |\n");
+                for (name, ty) in &class.members {
+                    out.push_str(&format!("| {} | {} |\n", table_code(name), table_code(ty)));
+                }
+                out.push('\n');
+            }
+        }
+    }
+    if !doc.instances.is_empty() {
+        out.push_str("## Instances\n\n| Instance |\n| --- |\n");
+        for instance in &doc.instances {
+            out.push_str(&format!("| {} |\n", table_code(instance)));
+        }
+        out.push('\n');
+    }
+    if !doc.domains.is_empty() {
+        out.push_str("## Domains\n\n");
+        for domain in &doc.domains {
+            out.push_str(&format!(
+                "### `domain {}` over `{}`\n\n",
+                domain.name, domain.over
+            ));
+            push_doc_markdown(&mut out, domain.doc.as_deref());
+            for op in &domain.operators {
+                push_value_markdown(&mut out, op, "####");
+            }
+            if !domain.literals.is_empty() {
+                out.push_str(&format!(
+                    "Literals: {}\n\n",
+                    domain
+                        .literals
+                        .iter()
+                        .map(|l| format!("`{l}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// A code span for a Markdown table cell. `|` ends the cell even inside backticks (row types
+/// such as `{ name: Text | r }`), so it is escaped.
+fn table_code(text: &str) -> String {
+    format!("`{}`", text.replace('|', "\\|"))
+}
+
+fn push_value_markdown(out: &mut String, value: &ValueDoc, heading: &str) {
+    match &value.signature {
+        Some(sig) => out.push_str(&format!("{heading} `{} : {}`\n\n", value.name, sig)),
+        None => out.push_str(&format!("{heading} `{}`\n\n", value.name)),
+    }
+    push_deprecated_markdown(out, value.deprecated.as_deref());
+    push_doc_markdown(out, value.doc.as_deref());
+}
+
+/// Emits doc text, turning runs of `>>>` examples (and their expected lines) into fenced blocks.
+fn push_doc_markdown(out: &mut String, doc: Option<&str>) {
+    let Some(text) = doc else {
+        return;
+    };
+    let mut in_example = false;
+    let mut expect_result = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let is_example =
+            trimmed.starts_with(">>>") || (expect_result && !trimmed.is_empty() && in_example);
+        if is_example && !in_example {
+            out.push_str("```aivi\n");
+            in_example = true;
+        } else if !is_example && in_example {
+            out.push_str("```\n");
+            in_example = false;
+        }
+        expect_result = trimmed.starts_with(">>>");
+        out.push_str(line);
+        out.push('\n');
+    }
+    if in_example {
+        out.push_str("```\n");
+    }
+    out.push('\n');
+}
+
+fn push_deprecated_markdown(out: &mut String, deprecated: Option<&str>) {
+    match deprecated {
+        Some("") => out.push_str("> **Deprecated.**\n\n"),
+        Some(message) => out.push_str(&format!("> **Deprecated:** {message}\n\n")),
+        None => {}
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5}code{background:#f4f4f4;padding:0 .2em}table{border-collapse:collapse}td,th{border:1px solid #ddd;padding:.2em .6em}.deprecated{color:#a33}pre{white-space:pre-wrap}";
+
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>\n{}</body></html>\n",
+        escape_html(title),
+        HTML_STYLE,
+        body
+    )
+}
+
+fn render_index_html(docs: &[ModuleDoc]) -> String {
+    let mut body = String::from("<h1>API reference</h1>\n<ul>\n");
+    for doc in docs {
+        body.push_str(&format!(
+            "<li><a href=\"{}.html\"><code>{}</code></a>",
+            escape_html(&doc.name),
+            escape_html(&doc.name)
+        ));
+        if let Some(summary) = doc.doc.as_deref().and_then(|d| d.lines().next()) {
+            body.push_str(&format!(" — {}", escape_html(summary)));
+        }
+        body.push_str("</li>\n");
+    }
+    body.push_str("</ul>\n");
+    html_page("API reference", &body)
+}
+
+fn render_module_html(doc: &ModuleDoc) -> String {
+    let mut body = format!("<h1>Module <code>{}</code></h1>\n", escape_html(&doc.name));
+    push_deprecated_html(&mut body, doc.deprecated.as_deref());
+    push_doc_html(&mut body, doc.doc.as_deref());
+
+    if !doc.values.is_empty() {
+        body.push_str("<h2>Values</h2>\n");
+        for value in &doc.values {
+            push_value_html(&mut body, value, "h3");
+        }
+    }
+    if !doc.types.is_empty() {
+        body.push_str("<h2>Types</h2>\n");
+        for ty in &doc.types {
+            body.push_str(&format!(
+                "<h3><code>{}</code></h3>\n",
+                escape_html(&ty.header)
+            ));
+            push_deprecated_html(&mut body, ty.deprecated.as_deref());
+            if !ty.constructors.is_empty() {
+                body.push_str("<table><tr><th>Constructor</th></tr>\n");
+                for ctor in &ty.constructors {
+                    body.push_str(&format!(
+                        "<tr><td><code>{}</code></td></tr>\n",
+                        escape_html(ctor)
+                    ));
+                }
+                body.push_str("</table>\n");
+            }
+            push_doc_html(&mut body, ty.doc.as_deref());
+        }
+    }
+    if !doc.classes.is_empty() {
+        body.push_str("<h2>Classes</h2>\n");
+        for class in &doc.classes {
+            body.push_str(&format!(
+                "<h3><code>{}</code></h3>\n",
+                escape_html(&class.header)
+            ));
+            if !class.supers.is_empty() {
+                body.push_str(&format!(
+                    "<p>Superclasses: {}</p>\n",
+                    class
+                        .supers
+                        .iter()
+                        .map(|s| format!("<code>{}</code>", escape_html(s)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            push_doc_html(&mut body, class.doc.as_deref());
+            if !class.members.is_empty() {
+                body.push_str("<table><tr><th>Member</th><th>Type</th></tr>\n");
+                for (name, ty) in &class.members {
+                    body.push_str(&format!(
+                        "<tr><td><code>{}</code></td><td><code>{}</code></td></tr>\n",
+                        escape_html(name),
+                        escape_html(ty)
+                    ));
+                }
+                body.push_str("</table>\n");
+            }
+        }
+    }
+    if !doc.instances.is_empty() {
+        body.push_str("<h2>Instances</h2>\n<table><tr><th>Instance</th></tr>\n");
+        for instance in &doc.instances {
+            body.push_str(&format!(
+                "<tr><td><code>{}</code></td></tr>\n",
+                escape_html(instance)
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+    if !doc.domains.is_empty() {
+        body.push_str("<h2>Domains</h2>\n");
+        for domain in &doc.domains {
+            body.push_str(&format!(
+                "<h3><code>domain {}</code> over <code>{}</code></h3>\n",
+                escape_html(&domain.name),
+                escape_html(&domain.over)
+            ));
+            push_doc_html(&mut body, domain.doc.as_deref());
+            for op in &domain.operators {
+                push_value_html(&mut body, op, "h4");
+            }
+            if !domain.literals.is_empty() {
+                body.push_str(&format!(
+                    "<p>Literals: {}</p>\n",
+                    domain
+                        .literals
+                        .iter()
+                        .map(|l| format!("<code>{}</code>", escape_html(l)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+    }
+    body.push_str("<p><a href=\"index.html\">Index</a></p>\n");
+    html_page(&doc.name, &body)
+}
+
+fn push_value_html(body: &mut String, value: &ValueDoc, tag: &str) {
+    let title = match &value.signature {
+        Some(sig) => format!("{} : {}", value.name, sig),
+        None => value.name.clone(),
+    };
+    body.push_str(&format!(
+        "<{tag} id=\"{}\"><code>{}</code></{tag}>\n",
+        escape_html(&value.name),
+        escape_html(&title)
+    ));
+    push_deprecated_html(body, value.deprecated.as_deref());
+    push_doc_html(body, value.doc.as_deref());
+}
+
+fn push_deprecated_html(body: &mut String, deprecated: Option<&str>) {
+    match deprecated {
+        Some("") => body.push_str("<p class=\"deprecated\"><strong>Deprecated.</strong></p>\n"),
+        Some(message) => body.push_str(&format!(
+            "<p class=\"deprecated\"><strong>Deprecated:</strong> {}</p>\n",
+            escape_html(message)
+        )),
+        None => {}
+    }
+}
+
+fn push_doc_html(body: &mut String, doc: Option<&str>) {
+    if let Some(text) = doc {
+        body.push_str(&format!("<pre>{}</pre>\n", escape_html(text)));
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+pub fn write_doc_pages(out_dir: &Path, pages: &[DocPage]) -> std::io::Result<()> {
+    std::fs::create_dir_all(out_dir)?;
+    for page in pages {
+        std::fs::write(out_dir.join(&page.path), &page.contents)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,800 @@
+//! Registry of every diagnostic code the compiler emits, with a long-form explanation.
+//!
+//! `aivi explain <code>` prints an entry, the LSP links each diagnostic to its entry on the
+//! documentation site, and `specs/07_tools/05_error_codes.md` is generated from this table.
+
+use serde::Serialize;
+
+/// Documentation page that renders [`ERROR_CODES`]; every code has an `#e1234` anchor.
+pub const ERROR_CODES_DOCS_URL: &str =
+    "https://mendrik.github.io/aivi/07_tools/05_error_codes.html";
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ErrorCodeInfo {
+    pub code: &'static str,
+    pub title: &'static str,
+    pub explanation: &'static str,
+    /// Source that triggers the diagnostic.
+    pub bad: &'static str,
+    /// The same source, fixed.
+    pub good: &'static str,
+    pub fixes: &'static [&'static str],
+}
+
+/// Looks a code up case-insensitively (`e3100` finds `E3100`).
+pub fn error_code_info(code: &str) -> Option<&'static ErrorCodeInfo> {
+    ERROR_CODES
+        .iter()
+        .find(|info| info.code.eq_ignore_ascii_case(code.trim()))
+}
+
+pub fn error_code_docs_url(code: &str) -> String {
+    format!("{ERROR_CODES_DOCS_URL}#{}", code.to_ascii_lowercase())
+}
+
+impl ErrorCodeInfo {
+    /// Plain-text rendering used by `aivi explain`.
+    pub fn render_text(&self) -> String {
+        let mut out = format!("{}: {}\n\n{}\n", self.code, self.title, self.explanation);
+        out.push_str("\nExample:\n\n");
+        out.push_str(&indent(self.bad));
+        out.push_str("\nFixed:\n\n");
+        out.push_str(&indent(self.good));
+        out.push_str("\nHow to fix:\n");
+        for fix in self.fixes {
+            out.push_str(&format!("  - {fix}\n"));
+        }
+        out.push_str(&format!("\nSee also: {}\n", error_code_docs_url(self.code)));
+        out
+    }
+
+    /// Markdown rendering used by the docs page and LSP hovers.
+    pub fn render_markdown(&self) -> String {
+        let mut out = format!("{}\n\nExample:\n\n", self.explanation);
+        out.push_str(&fence(self.bad));
+        out.push_str("\nFixed:\n\n");
+        out.push_str(&fence(self.good));
+        out.push_str("\nHow to fix:\n\n");
+        for fix in self.fixes {
+            out.push_str(&format!("- {fix}\n"));
+        }
+        out
+    }
+}
+
+/// The generated `specs/07_tools/05_error_codes.md`.
+pub fn error_codes_markdown() -> String {
+    let mut out = String::from(
+        "# Error Codes\n\n\
+         <!-- Generated from `crates/aivi/src/error_codes.rs`; run `AIVI_BLESS=1 cargo test -p aivi --test error_codes` after editing it. -->\n\n\
+         Every diagnostic carries a code. `E` codes are errors and `W` codes are warnings; the first\n\
+         digit names the phase: `1` lexing and parsing, `2` modules, names and i18n catalogs, `3` types.\n\
+         `aivi explain <code>` prints the same text in a terminal.\n",
+    );
+    for info in ERROR_CODES {
+        out.push_str(&format!(
+            "\n## {}: {} {{#{}}}\n\n",
+            info.code,
+            info.title,
+            info.code.to_ascii_lowercase()
+        ));
+        out.push_str(&info.render_markdown());
+    }
+    out
+}
+
+fn indent(text: &str) -> String {
+    text.lines().map(|line| format!("    {line}\n")).collect()
+}
+
+fn fence(text: &str) -> String {
+    format!("```aivi\n{text}\n```\n")
+}
+
+pub const ERROR_CODES: &[ErrorCodeInfo] = &[
+    ErrorCodeInfo {
+        code: "E1000",
+        title: "Unexpected character",
+        explanation: "The lexer found a character that is not part of AIVI syntax outside of a text \
+            literal or comment.",
+        bad: "total = price $ 2",
+        good: "total = price * 2",
+        fixes: &[
+            "Remove the character or replace it with the intended operator.",
+            "Put free-form text inside a `\"...\"` literal or a `//` comment.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1001",
+        title: "Unterminated string literal",
+        explanation: "A text literal was opened with `\"` but the line ended before the closing quote.",
+        bad: "greeting = \"hello",
+        good: "greeting = \"hello\"",
+        fixes: &["Add the closing `\"`.", "Escape quotes inside the text as `\\\"`."],
+    },
+    ErrorCodeInfo {
+        code: "E1002",
+        title: "Unmatched closing delimiter",
+        explanation: "A `)`, `]` or `}` appears without a matching opening delimiter.",
+        bad: "total = (1 + 2))",
+        good: "total = (1 + 2)",
+        fixes: &["Remove the extra closing delimiter.", "Add the missing opening delimiter."],
+    },
+    ErrorCodeInfo {
+        code: "E1003",
+        title: "Mismatched delimiters",
+        explanation: "A delimiter was closed by a different kind than the one that opened it, such as \
+            `(` closed by `]`.",
+        bad: "xs = [1, 2)",
+        good: "xs = [1, 2]",
+        fixes: &["Close each delimiter with its counterpart: `()`, `[]`, `{}`."],
+    },
+    ErrorCodeInfo {
+        code: "E1004",
+        title: "Unclosed delimiter",
+        explanation: "The file ended while a `(`, `[` or `{` was still open.",
+        bad: "config = { port: 8080",
+        good: "config = { port: 8080 }",
+        fixes: &["Add the missing closing delimiter."],
+    },
+    ErrorCodeInfo {
+        code: "E1005",
+        title: "Unterminated sigil literal",
+        explanation: "A sigil such as `~r/.../` or `~u(...)` was opened but its closing delimiter never \
+            appeared.",
+        bad: "pattern = ~r/[a-z]+",
+        good: "pattern = ~r/[a-z]+/",
+        fixes: &["Add the closing delimiter that matches the opening one."],
+    },
+    ErrorCodeInfo {
+        code: "E1006",
+        title: "Semicolon",
+        explanation: "AIVI separates statements and bindings with newlines; `;` is not part of the \
+            syntax.",
+        bad: "x = 1; y = 2",
+        good: "x = 1\ny = 2",
+        fixes: &["Replace each `;` with a newline."],
+    },
+    ErrorCodeInfo {
+        code: "E1500",
+        title: "Expected a keyword or name",
+        explanation: "The parser expected a specific keyword or name at this point, for example a domain \
+            name after `domain` or an alias after `as`.",
+        bad: "use aivi.text as",
+        good: "use aivi.text as text",
+        fixes: &["Add the missing keyword or name named in the message."],
+    },
+    ErrorCodeInfo {
+        code: "E1501",
+        title: "Expected a symbol",
+        explanation: "The parser expected a specific symbol, such as `=`, `=>` or `:`, at this point.",
+        bad: "ages = ~map{ \"ada\" 36 }",
+        good: "ages = ~map{ \"ada\" => 36 }",
+        fixes: &["Insert the symbol named in the message."],
+    },
+    ErrorCodeInfo {
+        code: "E1502",
+        title: "Misplaced decorator",
+        explanation: "Decorators at the top of a file are only allowed directly before the `module` \
+            declaration.",
+        bad: "@no_prelude\nx = 1\nmodule app.main",
+        good: "@no_prelude\nmodule app.main\n\nx = 1",
+        fixes: &["Move the decorator onto the line before `module`."],
+    },
+    ErrorCodeInfo {
+        code: "E1503",
+        title: "Missing decorator name",
+        explanation: "An `@` must be followed by the decorator's name.",
+        bad: "@\nf = x => x",
+        good: "@debug()\nf = x => x",
+        fixes: &["Write the decorator name after `@`, or remove the `@`."],
+    },
+    ErrorCodeInfo {
+        code: "E1504",
+        title: "Decorator on the same line",
+        explanation: "A decorator must be written on its own line, above the item it decorates.",
+        bad: "@deprecated \"use total\" sum = 1",
+        good: "@deprecated \"use total\"\nsum = 1",
+        fixes: &["Break the line after the decorator."],
+    },
+    ErrorCodeInfo {
+        code: "E1506",
+        title: "Unknown decorator",
+        explanation: "The decorator is not one the compiler knows. Decorators are a closed set; they \
+            cannot be defined in user code.",
+        bad: "@memoize\nf = x => x",
+        good: "f = x => x",
+        fixes: &[
+            "Check the spelling against the documented decorators (`@static`, `@debug`, `@deprecated`, `@no_prelude`, ...).",
+            "Remove the decorator.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1507",
+        title: "Decorator on `export` or `use`",
+        explanation: "Decorators apply to definitions; `export` and `use` lines cannot carry them.",
+        bad: "@deprecated \"old\"\nexport total",
+        good: "export total\n\n@deprecated \"old\"\ntotal = 1",
+        fixes: &["Move the decorator onto the definition itself."],
+    },
+    ErrorCodeInfo {
+        code: "E1508",
+        title: "Implicit module body is not last",
+        explanation: "A `module name` line without `=` makes the rest of the file its body, so nothing \
+            can follow it at the top level.",
+        bad: "module app.a\nx = 1\nmodule app.b = {\n  y = 2\n}",
+        good: "module app.a\nx = 1",
+        fixes: &["Put each module in its own file."],
+    },
+    ErrorCodeInfo {
+        code: "E1509",
+        title: "Missing `=` before module body",
+        explanation: "An explicit module body in braces is introduced with `=`.",
+        bad: "module app.main {\n  x = 1\n}",
+        good: "module app.main = {\n  x = 1\n}",
+        fixes: &["Write `module name = { ... }`, or drop the braces and use an implicit body."],
+    },
+    ErrorCodeInfo {
+        code: "E1510",
+        title: "Invalid URL sigil or decorator argument",
+        explanation: "Either a `~u(...)` sigil does not contain a URL, or a decorator argument is missing \
+            or has the wrong form (for example `@deprecated` needs a text literal).",
+        bad: "home = ~u(not a url)\n\n@deprecated 42\nold = 1",
+        good: "home = ~u(https://example.com)\n\n@deprecated \"use new\"\nold = 1",
+        fixes: &[
+            "Write a full URL with a scheme inside `~u(...)`.",
+            "Pass the argument form the decorator documents.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1511",
+        title: "Invalid date-time sigil or missing decorator argument",
+        explanation: "Either a `~t(...)` / `~dt(...)` sigil does not contain an ISO-8601 date-time, or a \
+            decorator that needs an argument (such as `@deprecated`) has none.",
+        bad: "start = ~dt(yesterday)\n\n@deprecated\nold = 1",
+        good: "start = ~dt(2024-05-01T09:30:00Z)\n\n@deprecated \"use new\"\nold = 1",
+        fixes: &[
+            "Use the `YYYY-MM-DDTHH:MM:SS` form with an optional offset.",
+            "Give the decorator its argument.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1512",
+        title: "Invalid date sigil or unexpected `@no_prelude` argument",
+        explanation: "Either a `~d(...)` sigil does not contain an ISO-8601 date, or `@no_prelude` was \
+            given an argument.",
+        bad: "due = ~d(31/12/2024)",
+        good: "due = ~d(2024-12-31)",
+        fixes: &["Use the `YYYY-MM-DD` form.", "Write `@no_prelude` without arguments."],
+    },
+    ErrorCodeInfo {
+        code: "E1513",
+        title: "Invalid sigil or unexpected decorator argument",
+        explanation: "Either the text after `~` is not a well-formed sigil, or a decorator that takes no \
+            argument was given one.",
+        bad: "@static 1\nconfig = 1",
+        good: "@static\nconfig = 1",
+        fixes: &[
+            "Write sigils as `~tag` followed by a delimited body, e.g. `~r/.../`.",
+            "Remove the decorator argument.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1514",
+        title: "Invalid i18n key sigil or misplaced decorator",
+        explanation: "Either a `~k\"...\"` key is not a dotted identifier path, or a decorator is applied \
+            to an item it does not support (`@static` needs a value without parameters, `@debug` a \
+            function).",
+        bad: "title = ~k\"Home Title\"",
+        good: "title = ~k\"home.title\"",
+        fixes: &[
+            "Use lowercase dotted key segments such as `app.home.title`.",
+            "Move the decorator onto a definition of the supported kind.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1515",
+        title: "Invalid i18n message sigil or unreadable `@static` file",
+        explanation: "Either a `~m\"...\"` message has malformed placeholders, or a `@static` definition \
+            could not read the file it embeds.",
+        bad: "welcome = ~m\"Hello {name\"",
+        good: "welcome = ~m\"Hello {name}\"",
+        fixes: &[
+            "Close every `{placeholder}` in the message.",
+            "Check that the `@static` path exists relative to the source file.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1516",
+        title: "Several `module` declarations",
+        explanation: "A file holds exactly one module.",
+        bad: "module app.a\nx = 1\n\nmodule app.b\ny = 2",
+        good: "module app.a\nx = 1",
+        fixes: &["Move the second module into its own file."],
+    },
+    ErrorCodeInfo {
+        code: "E1517",
+        title: "Missing `module` declaration",
+        explanation: "Every source file starts with `module name` (optionally preceded by module \
+            decorators).",
+        bad: "x = 1",
+        good: "module app.main\n\nx = 1",
+        fixes: &["Add a `module` line at the top of the file."],
+    },
+    ErrorCodeInfo {
+        code: "E1520",
+        title: "Unterminated escape sequence",
+        explanation: "A text literal ends with a lone `\\`.",
+        bad: "path = \"C:\\",
+        good: "path = \"C:\\\\\"",
+        fixes: &["Write `\\\\` for a literal backslash."],
+    },
+    ErrorCodeInfo {
+        code: "E1521",
+        title: "Unknown escape sequence",
+        explanation: "Text literals recognize `\\n`, `\\r`, `\\t`, `\\\\`, `\\\"`, `\\{` and `\\}`; \
+            any other character after a backslash is an error.",
+        bad: "path = \"C:\\data\"",
+        good: "path = \"C:\\\\data\"",
+        fixes: &["Write `\\\\` for a literal backslash."],
+    },
+    ErrorCodeInfo {
+        code: "E1522",
+        title: "Unterminated text interpolation",
+        explanation: "A `{` inside a text literal starts an interpolation that must be closed by `}` \
+            before the text ends.",
+        bad: "greeting = \"Hello {name\"",
+        good: "greeting = \"Hello {name}\"",
+        fixes: &["Close the interpolation with `}`.", "Write `\\{` for a literal brace."],
+    },
+    ErrorCodeInfo {
+        code: "E1523",
+        title: "Unexpected tokens in text interpolation",
+        explanation: "An interpolation holds a single expression; something followed it before the `}`.",
+        bad: "line = \"{count ,} items\"",
+        good: "line = \"{count} items\"",
+        fixes: &["Keep one expression between `{` and `}`."],
+    },
+    ErrorCodeInfo {
+        code: "E1524",
+        title: "Missing comma between list items",
+        explanation: "List items written on one line are separated by commas. Items on separate lines \
+            need no comma. Two values side by side usually parse as a function application, so a \
+            missing comma often surfaces as a type error (`E3000`) instead.",
+        bad: "xs = [1 2]",
+        good: "xs = [1, 2, 3]",
+        fixes: &["Insert `,` between the items, or put each item on its own line."],
+    },
+    ErrorCodeInfo {
+        code: "E1525",
+        title: "Missing comma between record fields",
+        explanation: "Record fields written on one line are separated by commas. Because `1 y` parses \
+            as an application, a missing comma often surfaces as an unknown name (`E2005`) instead.",
+        bad: "point = { x: 1 y: 2 }",
+        good: "point = { x: 1, y: 2 }",
+        fixes: &["Insert `,` between the fields, or put each field on its own line."],
+    },
+    ErrorCodeInfo {
+        code: "E1526",
+        title: "Missing comma between map entries",
+        explanation: "Entries of a `~map{ ... }` literal written on one line are separated by commas. A \
+            missing comma often surfaces as a type error instead, because the value swallows the next \
+            key as an argument.",
+        bad: "ages = ~map{ \"ada\" => 36 \"alan\" => 41 }",
+        good: "ages = ~map{ \"ada\" => 36, \"alan\" => 41 }",
+        fixes: &["Insert `,` between the entries, or put each entry on its own line."],
+    },
+    ErrorCodeInfo {
+        code: "E1527",
+        title: "Missing comma between set entries",
+        explanation: "Entries of a `~set[ ... ]` literal written on one line are separated by commas. \
+            Two values side by side usually parse as an application, so a missing comma often \
+            surfaces as a type error instead.",
+        bad: "tags = ~set[\"a\" \"b\"]",
+        good: "tags = ~set[\"a\", \"b\"]",
+        fixes: &["Insert `,` between the entries, or put each entry on its own line."],
+    },
+    ErrorCodeInfo {
+        code: "E1528",
+        title: "Type signature shares a line",
+        explanation: "A type signature ends at the end of its line; the definition goes on the next one.",
+        bad: "inc : Int -> Int inc = x => x + 1",
+        good: "inc : Int -> Int\ninc = x => x + 1",
+        fixes: &["Break the line after the signature."],
+    },
+    ErrorCodeInfo {
+        code: "E1529",
+        title: "Empty brackets",
+        explanation: "An index or bracketed expression needs an expression between the brackets.",
+        bad: "first = xs[]",
+        good: "first = xs[0]",
+        fixes: &["Write the index expression inside the brackets."],
+    },
+    ErrorCodeInfo {
+        code: "E1530",
+        title: "Result `or` arm does not match `Err`",
+        explanation: "Arms of a Result fallback (`result or | Err ... => ...`) only handle the error \
+            case, so every arm must match `Err ...` at the top level.",
+        bad: "msg =\n  res or\n    | Ok m  => m\n    | Err _ => \"boom\"",
+        good: "msg =\n  res or\n    | Err NotFound m => m\n    | Err _          => \"boom\"",
+        fixes: &[
+            "Match only `Err ...` patterns in the arms.",
+            "Use `?` with full arms when you also need the `Ok` case.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1531",
+        title: "Result `or` lacks a catch-all arm",
+        explanation: "A Result fallback must handle every error, so its last arm is `| Err _ => ...`.",
+        bad: "msg =\n  res or\n    | Err NotFound m => m",
+        good: "msg =\n  res or\n    | Err NotFound m => m\n    | Err _          => \"boom\"",
+        fixes: &["Add a final `| Err _ => ...` arm."],
+    },
+    ErrorCodeInfo {
+        code: "E1532",
+        title: "Effect `or` arm matches `Err`",
+        explanation: "Arms of an effect fallback match the error value itself, not a `Result`, so the \
+            leading `Err` is not written.",
+        bad: "readme = effect {\n  txt <- load (file.read \"README.md\") or\n    | NotFound _ => \"(missing)\"\n    | Err _      => \"(other)\"\n  pure txt\n}",
+        good: "readme = effect {\n  txt <- load (file.read \"README.md\") or\n    | NotFound _ => \"(missing)\"\n    | _          => \"(other)\"\n  pure txt\n}",
+        fixes: &["Drop the `Err` around each pattern."],
+    },
+    ErrorCodeInfo {
+        code: "E1533",
+        title: "`loop` outside `generate`",
+        explanation: "`loop` builds a generator and is only available inside `generate { ... }`.",
+        bad: "count = effect {\n  loop i = 0 => {\n    recurse (i + 1)\n  }\n}",
+        good: "count = generate {\n  loop i = 0 => {\n    yield i\n    recurse (i + 1)\n  }\n}",
+        fixes: &["Move the loop into a `generate` block, or use recursion."],
+    },
+    ErrorCodeInfo {
+        code: "E1534",
+        title: "`yield` outside `generate` or `resource`",
+        explanation: "`yield` produces an element of a generator or the value of a resource; it has no \
+            meaning in other blocks.",
+        bad: "xs = {\n  yield 1\n}",
+        good: "xs = generate {\n  yield 1\n}",
+        fixes: &["Use a `generate { ... }` or `resource { ... }` block."],
+    },
+    ErrorCodeInfo {
+        code: "E1535",
+        title: "`recurse` outside `generate`",
+        explanation: "`recurse` continues the enclosing `loop` and only exists inside `generate` blocks.",
+        bad: "next = {\n  recurse 1\n}",
+        good: "next = generate {\n  loop i = 0 => {\n    yield i\n    recurse (i + 1)\n  }\n}",
+        fixes: &["Use `recurse` inside a `loop` of a `generate` block."],
+    },
+    ErrorCodeInfo {
+        code: "E1536",
+        title: "`<-` outside an effect block",
+        explanation: "`x <- e` binds the result of running an effect (or the items of a generator), so \
+            it needs an `effect`, `generate` or `resource` block. Plain blocks use `=`.",
+        bad: "main = {\n  line <- readLine\n  line\n}",
+        good: "main = effect {\n  line <- readLine\n  pure line\n}",
+        fixes: &["Turn the block into `effect { ... }`.", "Use `=` for a pure binding."],
+    },
+    ErrorCodeInfo {
+        code: "E1537",
+        title: "Malformed record pattern field",
+        explanation: "After a field name, a record pattern expects `:` (sub-pattern), `@` (alias), `,` \
+            or the closing `}`.",
+        bad: "area = { width * 2 } => width",
+        good: "area = { width } => width * 2",
+        fixes: &["Keep computations out of the pattern and move them into the body."],
+    },
+    ErrorCodeInfo {
+        code: "E1538",
+        title: "Missing comma between record pattern fields",
+        explanation: "Fields of a record pattern on one line are separated by commas.",
+        bad: "f = { a: x b: y } => x",
+        good: "f = { a: x, b: y } => x",
+        fixes: &["Insert `,` between the fields."],
+    },
+    ErrorCodeInfo {
+        code: "E1539",
+        title: "Parameters before `=`",
+        explanation: "Functions are values: parameters are written as a lambda after `=`, not after the \
+            name.",
+        bad: "add x y = x + y",
+        good: "add = x y => x + y",
+        fixes: &["Move the parameters after `=` and add `=>`."],
+    },
+    ErrorCodeInfo {
+        code: "E1540",
+        title: "Missing row variable",
+        explanation: "In a record type, `|` introduces a row variable naming the remaining fields.",
+        bad: "getName : { name: Text | } -> Text",
+        good: "getName : { name: Text | r } -> Text",
+        fixes: &["Name the row variable after `|`, or remove the `|`."],
+    },
+    ErrorCodeInfo {
+        code: "E1541",
+        title: "Missing class in `deriving`",
+        explanation: "A `deriving (...)` clause lists class names separated by commas.",
+        bad: "Color = Red | Green deriving (Setoid,)",
+        good: "Color = Red | Green deriving (Setoid)",
+        fixes: &["Remove the trailing comma or add the missing class name."],
+    },
+    ErrorCodeInfo {
+        code: "E1542",
+        title: "Class cannot be derived",
+        explanation: "Only `Setoid`, `Ord`, `ToText` and `ToJson` have derivation rules, and `Ord` \
+            additionally needs `Setoid`.",
+        bad: "Color = Red | Green deriving (Eq)",
+        good: "Color = Red | Green deriving (Setoid)",
+        fixes: &[
+            "Derive one of the supported classes.",
+            "Write the instance by hand for other classes.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1543",
+        title: "Type does not support deriving",
+        explanation: "Deriving works on ADTs and closed record types whose fields can be compared or \
+            printed. It is rejected for aliases of other types, for function-typed fields, for type \
+            parameters without an instance and for classes that already have an instance.",
+        bad: "Name = Text deriving (Setoid)",
+        good: "Name = Named Text | Anonymous deriving (Setoid)",
+        fixes: &[
+            "Wrap the type in a constructor.",
+            "Write the instance by hand.",
+            "Remove the duplicate class or the explicit instance.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E1544",
+        title: "Malformed effect declaration",
+        explanation: "An effect is declared as `effect Name = { operation: Type, ... }`; the operation \
+            record is closed, so it cannot name a row variable.",
+        bad: "effect Log = { info: Text -> Unit | r }",
+        good: "effect Log = { info: Text -> Unit }",
+        fixes: &["List every operation explicitly in braces."],
+    },
+    ErrorCodeInfo {
+        code: "E1545",
+        title: "Missing handler after `with`",
+        explanation: "`handle e with` is followed by a record of operation implementations.",
+        bad: "result = handle program with",
+        good: "result = handle program with { info: msg => pure Unit }",
+        fixes: &["Write the handler record after `with`."],
+    },
+    ErrorCodeInfo {
+        code: "E1546",
+        title: "`opaque` on a non-alias",
+        explanation: "`opaque` hides the representation of a type alias outside its module. ADTs are \
+            already abstract when their constructors are not exported.",
+        bad: "opaque type Color = Red | Green",
+        good: "Color = Red | Green",
+        fixes: &["Remove `opaque` and export only the type name, not its constructors."],
+    },
+    ErrorCodeInfo {
+        code: "E1547",
+        title: "Typed hole in a pattern",
+        explanation: "A `?name` typed hole stands for an expression that is still to be written, so it \
+            cannot bind a value in a pattern.",
+        bad: "first = xs => xs ?\n  | [?head, ...] => Some head\n  | []            => None",
+        good: "first = xs => xs ?\n  | [head, ...] => Some head\n  | []           => None",
+        fixes: &["Use a plain name, or `_` to ignore the value."],
+    },
+    ErrorCodeInfo {
+        code: "E1548",
+        title: "Invalid functional dependency",
+        explanation: "A `| From -> To` clause on a class head may only name the class's own \
+            parameters, and needs at least one on each side of the arrow.",
+        bad: "class Convert A B | A -> C = {\n  convert: A -> B\n}",
+        good: "class Convert A B | A -> B = {\n  convert: A -> B\n}",
+        fixes: &["Name parameters declared after the class name on both sides of `->`."],
+    },
+    ErrorCodeInfo {
+        code: "E1600",
+        title: "Malformed HTML sigil",
+        explanation: "The body of a `~html~> ... <~html` sigil is not well-formed: a tag, attribute or \
+            `{ splice }` is unterminated or mismatched.",
+        bad: "view = ~html~> <div>{ title }</span> <~html",
+        good: "view = ~html~> <div>{ title }</div> <~html",
+        fixes: &["Close every tag, quoted attribute and `{splice}`."],
+    },
+    ErrorCodeInfo {
+        code: "E2000",
+        title: "Duplicate module",
+        explanation: "Two files declare the same module name.",
+        bad: "// a.aivi\nmodule app.util\n\n// b.aivi\nmodule app.util",
+        good: "// a.aivi\nmodule app.util\n\n// b.aivi\nmodule app.text",
+        fixes: &["Rename one of the modules."],
+    },
+    ErrorCodeInfo {
+        code: "E2001",
+        title: "Duplicate export",
+        explanation: "A name is listed twice in the module's `export` lines.",
+        bad: "export total, total",
+        good: "export total",
+        fixes: &["Remove the repeated name."],
+    },
+    ErrorCodeInfo {
+        code: "E2002",
+        title: "Unknown module",
+        explanation: "A `use` line names a module that is neither in the project, its dependencies nor \
+            the standard library.",
+        bad: "use app.utils",
+        good: "use app.util",
+        fixes: &["Check the module name.", "Add the package that provides it."],
+    },
+    ErrorCodeInfo {
+        code: "E2003",
+        title: "Name not exported",
+        explanation: "A `use` line imports a name that the module does not export.",
+        bad: "use aivi.text (shout)",
+        good: "use aivi.text (toUpper)",
+        fixes: &["Import an exported name.", "Export the name from its module."],
+    },
+    ErrorCodeInfo {
+        code: "E2004",
+        title: "Cyclic module dependency",
+        explanation: "Modules import each other in a cycle. Module initialization needs a dependency \
+            order, so imports must form a DAG.",
+        bad: "// a.aivi\nmodule app.a\nuse app.b\n\n// b.aivi\nmodule app.b\nuse app.a",
+        good: "// a.aivi\nmodule app.a\nuse app.shared\n\n// b.aivi\nmodule app.b\nuse app.shared",
+        fixes: &["Move the shared definitions into a third module both can import."],
+    },
+    ErrorCodeInfo {
+        code: "E2005",
+        title: "Unknown name",
+        explanation: "The name is not defined in the module, imported, or part of the prelude. Names from \
+            other languages such as `return`, `null` or `while` get a specific hint.",
+        bad: "inc = x => return (x + 1)",
+        good: "inc = x => x + 1",
+        fixes: &[
+            "Check the spelling.",
+            "Import the name with `use`, or define it.",
+            "Replace constructs from other languages with their AIVI form.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E2010",
+        title: "`@debug` on a non-function",
+        explanation: "`@debug` traces calls, so it only applies to definitions whose value is a lambda.",
+        bad: "@debug()\nlimit = 10",
+        good: "@debug()\nclamp = x => min x 10",
+        fixes: &["Remove the decorator or apply it to a function."],
+    },
+    ErrorCodeInfo {
+        code: "E2011",
+        title: "Malformed `@debug` arguments",
+        explanation: "`@debug` takes a parenthesized list of trace options, or `()` for the defaults.",
+        bad: "@debug \"pipes\"\nf = x => x",
+        good: "@debug(pipes, args)\nf = x => x",
+        fixes: &["Write `@debug(...)` with bare option names."],
+    },
+    ErrorCodeInfo {
+        code: "E2012",
+        title: "Unknown `@debug` option",
+        explanation: "The options of `@debug` are `pipes`, `args`, `return` and `time`.",
+        bad: "@debug(pipes, nope)\nf = x => x",
+        good: "@debug(pipes, time)\nf = x => x",
+        fixes: &["Use one of the documented options."],
+    },
+    ErrorCodeInfo {
+        code: "E2013",
+        title: "Exported signature leaks an opaque type",
+        explanation: "An exported value or alias mentions an opaque type that the module keeps private, \
+            so importers would hold values of a type they cannot name.",
+        bad: "export userId\n\nopaque type UserId = Text\n\nuserId : Text -> UserId\nuserId = raw => raw",
+        good: "export UserId, userId\n\nopaque type UserId = Text\n\nuserId : Text -> UserId\nuserId = raw => raw",
+        fixes: &["Export the opaque type too.", "Stop exporting the value."],
+    },
+    ErrorCodeInfo {
+        code: "E2602",
+        title: "Catalog placeholders disagree",
+        explanation: "A translation uses different `{placeholders}` than the same key in the source-locale \
+            catalog, so rendering it would miss or invent arguments.",
+        bad: "# en.properties\ngreeting = Hello {name}\n\n# de.properties\ngreeting = Hallo {user}",
+        good: "# en.properties\ngreeting = Hello {name}\n\n# de.properties\ngreeting = Hallo {name}",
+        fixes: &["Use the placeholder names of the source-locale message."],
+    },
+    ErrorCodeInfo {
+        code: "E2603",
+        title: "Malformed catalog",
+        explanation: "An i18n catalog cannot be read: its file name is not a locale tag, a line is not \
+            `key = message`, a message is malformed, or a key is defined twice.",
+        bad: "# locales/english.properties\ngreeting = Hello\ngreeting = Hi",
+        good: "# locales/en.properties\ngreeting = Hello",
+        fixes: &[
+            "Name catalogs after their locale (`en.properties`, `de-AT.properties`).",
+            "Keep one definition per key.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E3000",
+        title: "Type error",
+        explanation: "The typechecker could not make the program's types agree: a value has a different \
+            type than its context expects, a name or field does not exist, a class instance is missing, \
+            or an effect is used where it is not handled. The message names the specific problem.",
+        bad: "count : Int\ncount = \"three\"",
+        good: "count : Int\ncount = 3",
+        fixes: &[
+            "Compare the expected and found types in the message and convert the value.",
+            "Add the missing instance, field or handler.",
+            "Add type signatures to narrow down where the types diverge.",
+        ],
+    },
+    ErrorCodeInfo {
+        code: "E3100",
+        title: "Non-exhaustive match",
+        explanation: "A match, multi-clause function or lambda pattern does not cover every value of the \
+            scrutinee's type. The message lists example values that are not matched.",
+        bad: "describe = opt => opt ?\n  | Some x => x",
+        good: "describe = opt => opt ?\n  | Some x => x\n  | None   => \"nothing\"",
+        fixes: &["Add arms for the listed cases.", "Add a final `_` arm."],
+    },
+    ErrorCodeInfo {
+        code: "E3200",
+        title: "Typed hole",
+        explanation: "`?name` marks an expression that is still to be written. The typechecker reports \
+            the type expected at the hole, the local bindings in scope with their types, and bindings \
+            whose type fits; editors offer each fitting binding as a quick fix.",
+        bad: "use aivi.math (sum)\n\ntotal : List Float -> Float\ntotal = ?fold",
+        good: "use aivi.math (sum)\n\ntotal : List Float -> Float\ntotal = sum",
+        fixes: &["Replace the hole with an expression of the reported type."],
+    },
+    ErrorCodeInfo {
+        code: "W2100",
+        title: "Unused import",
+        explanation: "A name imported with `use` is never referenced.",
+        bad: "use aivi.text (toUpper, toLower)\n\nshout = toUpper",
+        good: "use aivi.text (toUpper)\n\nshout = toUpper",
+        fixes: &["Remove the import."],
+    },
+    ErrorCodeInfo {
+        code: "W2101",
+        title: "Unused binding",
+        explanation: "A private definition is never referenced and not exported.",
+        bad: "export main\n\nhelper = 1\nmain = pure Unit",
+        good: "export main\n\nmain = pure Unit",
+        fixes: &["Remove the definition, export it, or use it."],
+    },
+    ErrorCodeInfo {
+        code: "W2102",
+        title: "Recursive binding shadows an outer name",
+        explanation: "A function bound in a block whose body mentions its own name is recursive, so \
+            that name refers to the new binding rather than to the outer one it shadows. Wrapping an \
+            outer function under the same name therefore calls itself forever.",
+        bad: "main = effect {\n  println = msg => println \"> {msg}\"\n  println \"hi\"\n}",
+        good: "main = effect {\n  say = msg => println \"> {msg}\"\n  say \"hi\"\n}",
+        fixes: &["Give the wrapper a new name, or rename the recursive function if the shadowing was accidental."],
+    },
+    ErrorCodeInfo {
+        code: "W2500",
+        title: "Deprecated name",
+        explanation: "The referenced definition is marked `@deprecated`; the message carries the \
+            author's note.",
+        bad: "@deprecated \"use total\"\nsum = 1\n\nx = sum",
+        good: "total = 1\n\nx = total",
+        fixes: &["Follow the deprecation note, usually by switching to the replacement."],
+    },
+    ErrorCodeInfo {
+        code: "W2600",
+        title: "Missing translation",
+        explanation: "A `~k\"...\"` key used in source has no (or an empty) entry in one of the project's \
+            catalogs.",
+        bad: "title = ~k\"home.title\"\n\n# de.properties has no home.title",
+        good: "title = ~k\"home.title\"\n\n# de.properties\nhome.title = Startseite",
+        fixes: &["Add the key to each listed catalog; `aivi i18n extract` writes the stubs."],
+    },
+    ErrorCodeInfo {
+        code: "W2601",
+        title: "Unused catalog key",
+        explanation: "A catalog defines a key that no `~k` sigil in the project uses.",
+        bad: "# en.properties\nold.title = Old",
+        good: "# en.properties\nhome.title = Home",
+        fixes: &["Delete the entry, or use the key in source."],
+    },
+    ErrorCodeInfo {
+        code: "W3101",
+        title: "Unreachable match arm",
+        explanation: "Earlier arms (or function clauses) already match every value this one matches, so \
+            it never runs.",
+        bad: "sign = n => n ?\n  | _ => 1\n  | 0 => 0",
+        good: "sign = n => n ?\n  | 0 => 0\n  | _ => 1",
+        fixes: &["Remove the arm or move it before the arms that shadow it."],
+    },
+];
@@ -633,7 +633,9 @@
         } else if !starts_with_pipe {
             pipe_block_base_indent = None;
         }
-        let effective_indent = if starts_with_pipe && should_indent_pipe {
+        // A `deriving (...)` clause on its own line continues the type declaration above it.
+        let starts_with_deriving = state.tokens[first_idx].text == "deriving";
+        let effective_indent = if (starts_with_pipe && should_indent_pipe) || starts_with_deriving {
             // Avoid allocations in the hot path unless we actually need extra indentation.
             format!("{base_indent}{pipe_block_extra}")
         } else {
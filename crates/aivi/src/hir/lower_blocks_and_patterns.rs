@@ -1,3 +1,39 @@
+/// Lowers the items of a block, turning each recursive group of function bindings into a single
+/// `LetRec`.
+fn lower_block_items_ctx(
+    items: Vec<BlockItem>,
+    surface_kind: &BlockKind,
+    hir_kind: &HirBlockKind,
+    id_gen: &mut IdGen,
+    ctx: &mut LowerCtx<'_>,
+) -> Vec<HirBlockItem> {
+    let groups = recursive_binding_groups(&items);
+    let mut lowered = Vec::new();
+    let mut bindings = Vec::new();
+    for (idx, item) in items.into_iter().enumerate() {
+        let Some(group) = groups.iter().find(|group| group.contains(&idx)) else {
+            lowered.push(lower_block_item_ctx(item, surface_kind, hir_kind, id_gen, ctx));
+            continue;
+        };
+        if let BlockItem::Let {
+            pattern: Pattern::Ident(name),
+            expr,
+            ..
+        } = item
+        {
+            bindings.push(HirRecBinding {
+                name: name.name,
+                expr: lower_expr_ctx(expr, id_gen, ctx, false),
+            });
+        }
+        if idx + 1 == group.end {
+            lowered.push(HirBlockItem::LetRec {
+                bindings: std::mem::take(&mut bindings),
+            });
+        }
+    }
+    lowered
+}
 
 fn lower_block_item_ctx(
     item: BlockItem,
@@ -163,6 +199,9 @@ fn contains_placeholder(expr: &Expr) -> bool {
         Expr::Binary { left, right, .. } => {
             contains_placeholder(left) || contains_placeholder(right)
         }
+        Expr::Handle { body, handler, .. } => {
+            contains_placeholder(body) || contains_placeholder(handler)
+        }
         Expr::Block { items, .. } => items.iter().any(|item| match item {
             BlockItem::Bind { expr, .. } => contains_placeholder(expr),
             BlockItem::Let { expr, .. } => contains_placeholder(expr),
@@ -337,6 +376,15 @@ fn desugar_placeholder_lambdas(expr: Expr) -> Expr {
             right: Box::new(desugar_placeholder_lambdas(*right)),
             span,
         },
+        Expr::Handle {
+            body,
+            handler,
+            span,
+        } => Expr::Handle {
+            body: Box::new(desugar_placeholder_lambdas(*body)),
+            handler: Box::new(desugar_placeholder_lambdas(*handler)),
+            span,
+        },
         Expr::Block { kind, items, span } => Expr::Block {
             kind,
             items: items
@@ -410,6 +458,7 @@ fn desugar_placeholder_lambdas(expr: Expr) -> Expr {
         | Expr::Match { span, .. }
         | Expr::If { span, .. }
         | Expr::Binary { span, .. }
+        | Expr::Handle { span, .. }
         | Expr::Block { span, .. }
         | Expr::Raw { span, .. } => span.clone(),
     };
@@ -611,6 +660,15 @@ fn replace_holes_inner(expr: Expr, counter: &mut u32, params: &mut Vec<String>)
             right: Box::new(replace_holes_inner(*right, counter, params)),
             span,
         },
+        Expr::Handle {
+            body,
+            handler,
+            span,
+        } => Expr::Handle {
+            body: Box::new(replace_holes_inner(*body, counter, params)),
+            handler: Box::new(replace_holes_inner(*handler, counter, params)),
+            span,
+        },
         Expr::Block { kind, items, span } => Expr::Block {
             kind,
             items: items
@@ -226,6 +226,17 @@ fn lower_expr_inner_ctx(expr: Expr, id_gen: &mut IdGen, ctx: &mut LowerCtx<'_>,
             let body = lower_expr_ctx(*body, id_gen, ctx, false);
             lower_lambda_hir(params, body, id_gen)
         }
+        Expr::Handle { body, handler, .. } => HirExpr::Call {
+            id: id_gen.next(),
+            func: Box::new(HirExpr::Var {
+                id: id_gen.next(),
+                name: "__handle".to_string(),
+            }),
+            args: vec![
+                lower_expr_ctx(*handler, id_gen, ctx, false),
+                lower_expr_ctx(*body, id_gen, ctx, false),
+            ],
+        },
         Expr::Match {
             scrutinee, arms, ..
         } => {
@@ -339,10 +350,7 @@ fn lower_expr_inner_ctx(expr: Expr, id_gen: &mut IdGen, ctx: &mut LowerCtx<'_>,
             HirExpr::Block {
                 id: id_gen.next(),
                 block_kind: block_kind.clone(),
-                items: items
-                    .into_iter()
-                    .map(|item| lower_block_item_ctx(item, &kind, &block_kind, id_gen, ctx))
-                    .collect(),
+                items: lower_block_items_ctx(items, &kind, &block_kind, id_gen, ctx),
             }
         }
         Expr::Raw { text, .. } => HirExpr::Raw {
@@ -376,6 +384,7 @@ fn surface_expr_span(expr: &Expr) -> crate::diagnostics::Span {
         | Expr::Match { span, .. }
         | Expr::If { span, .. }
         | Expr::Binary { span, .. }
+        | Expr::Handle { span, .. }
         | Expr::Block { span, .. }
         | Expr::Raw { span, .. } => span.clone(),
     }
@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 use crate::surface::{
-    BlockItem, BlockKind, Decorator, Def, DomainItem, Expr, Module, ModuleItem, Pattern, TextPart,
+    expr_mentions, recursive_binding_groups, BlockItem, BlockKind, ClassDecl, Decorator, Def,
+    DomainItem, EffectOperation, Expr, InstanceDecl, ListItem, Literal, Module, ModuleItem,
+    Pattern, SpannedName, TextPart, TypeExpr,
 };
 use std::cell::Cell;
+use std::collections::HashSet;
 
 thread_local! {
     static DEBUG_TRACE_OVERRIDE: Cell<Option<bool>> = const { Cell::new(None) };
@@ -252,11 +255,32 @@ pub enum HirBlockKind {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum HirBlockItem {
-    Bind { pattern: HirPattern, expr: HirExpr },
-    Filter { expr: HirExpr },
-    Yield { expr: HirExpr },
-    Recurse { expr: HirExpr },
-    Expr { expr: HirExpr },
+    Bind {
+        pattern: HirPattern,
+        expr: HirExpr,
+    },
+    Filter {
+        expr: HirExpr,
+    },
+    Yield {
+        expr: HirExpr,
+    },
+    Recurse {
+        expr: HirExpr,
+    },
+    Expr {
+        expr: HirExpr,
+    },
+    /// Mutually recursive function bindings: every name is in scope in every body.
+    LetRec {
+        bindings: Vec<HirRecBinding>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HirRecBinding {
+    pub name: String,
+    pub expr: HirExpr,
 }
 
 pub fn desugar_modules(modules: &[Module]) -> HirProgram {
@@ -264,6 +288,7 @@ pub fn desugar_modules(modules: &[Module]) -> HirProgram {
     let debug_trace = debug_trace_enabled();
     let mut id_gen = IdGen::default();
     let mut hir_modules = Vec::new();
+    let mut default_defs = instance_default_defs(modules);
     for (module_index, module) in modules.iter().enumerate() {
         if trace {
             eprintln!(
@@ -278,7 +303,9 @@ pub fn desugar_modules(modules: &[Module]) -> HirProgram {
         } else {
             None
         };
-        let defs = collect_surface_defs(module)
+        let mut surface_defs = collect_surface_defs(module);
+        surface_defs.append(&mut default_defs[module_index]);
+        let defs = surface_defs
             .into_iter()
             .map(|def| {
                 let name = def.name.name.clone();
@@ -336,12 +363,278 @@ fn collect_surface_defs(module: &Module) -> Vec<Def> {
                     }
                 }
             }
+            ModuleItem::EffectDecl(effect) => {
+                defs.extend(effect.operations.iter().map(effect_operation_def));
+            }
             _ => {}
         }
     }
     defs
 }
 
+/// Default method bodies each module's instances pick up, indexed like `modules`.
+///
+/// Mirrors the type checker: a member the instance leaves out comes from an instance of the
+/// superclass that declares it when one exists for the same head, and from the class's (or a
+/// superclass's) default otherwise. Class methods dispatch on their arguments at runtime, so a
+/// default that also uses a return-polymorphic method like `of` gets this instance's definitions
+/// of the members it mentions passed in, rather than whichever instance answers first.
+fn instance_default_defs(modules: &[Module]) -> Vec<Vec<Def>> {
+    let instances: Vec<&InstanceDecl> = modules
+        .iter()
+        .flat_map(|module| module.items.iter())
+        .filter_map(|item| match item {
+            ModuleItem::InstanceDecl(instance) => Some(instance),
+            _ => None,
+        })
+        .collect();
+    let head = |instance: &InstanceDecl| {
+        instance
+            .params
+            .iter()
+            .map(crate::doc_gen::type_expr_to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    modules
+        .iter()
+        .map(|module| {
+            let mut defs = Vec::new();
+            for item in &module.items {
+                let ModuleItem::InstanceDecl(instance) = item else {
+                    continue;
+                };
+                let hierarchy = class_hierarchy(modules, module, &instance.name.name);
+                let Some(class_decl) = hierarchy.first() else {
+                    continue;
+                };
+                let instance_head = head(instance);
+                // Instances for the same head, in this class or its superclasses.
+                let siblings: Vec<(&ClassDecl, &InstanceDecl)> = instances
+                    .iter()
+                    .filter(|other| head(other) == instance_head)
+                    .filter_map(|other| {
+                        hierarchy
+                            .iter()
+                            .find(|class_decl| class_decl.name.name == other.name.name)
+                            .map(|class_decl| (*class_decl, *other))
+                    })
+                    .collect();
+                let defined = |name: &str| {
+                    siblings
+                        .iter()
+                        .find_map(|(_, other)| other.defs.iter().find(|def| def.name.name == name))
+                };
+
+                let mut seen = HashSet::new();
+                for default in hierarchy.iter().flat_map(|class_decl| &class_decl.defaults) {
+                    let name = default.name.name.as_str();
+                    if !seen.insert(name) || instance.defs.iter().any(|def| def.name.name == name) {
+                        continue;
+                    }
+                    let from_super = siblings.iter().any(|(sibling_class, _)| {
+                        sibling_class.name.name != class_decl.name.name
+                            && sibling_class.members.iter().any(|m| m.name.name == name)
+                    });
+                    if from_super {
+                        continue;
+                    }
+
+                    let Some(expr) = default_binding(name, &hierarchy, &defined, &mut Vec::new())
+                    else {
+                        continue;
+                    };
+                    defs.push(Def {
+                        decorators: Vec::new(),
+                        name: default.name.clone(),
+                        params: Vec::new(),
+                        expr,
+                        span: default.span.clone(),
+                    });
+                }
+            }
+            defs
+        })
+        .collect()
+}
+
+/// The nearest default for `name` in `hierarchy`, applied to this instance's definitions of the
+/// members it mentions. Members the instance also leaves to a default are bound the same way;
+/// `visiting` keeps mutually defined defaults from expanding forever.
+fn default_binding<'a>(
+    name: &str,
+    hierarchy: &[&'a ClassDecl],
+    defined: &dyn Fn(&str) -> Option<&'a Def>,
+    visiting: &mut Vec<String>,
+) -> Option<Expr> {
+    let default = hierarchy
+        .iter()
+        .flat_map(|class_decl| &class_decl.defaults)
+        .find(|default| default.name.name == name)?;
+    let default_expr = def_as_expr(default);
+    visiting.push(name.to_string());
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    for member in hierarchy.iter().flat_map(|class_decl| &class_decl.members) {
+        let member_name = member.name.name.as_str();
+        if member_name == name
+            || !member_name.starts_with(|ch: char| ch.is_ascii_alphabetic())
+            || params.iter().any(|param| match param {
+                Pattern::Ident(bound) => bound.name == member_name,
+                _ => false,
+            })
+            || !expr_mentions(&default_expr, &[member_name])
+        {
+            continue;
+        }
+        let arg = match defined(member_name) {
+            Some(def) => Some(def_as_expr(def)),
+            None if !visiting.iter().any(|v| v == member_name) => {
+                default_binding(member_name, hierarchy, defined, visiting)
+            }
+            None => None,
+        };
+        if let Some(arg) = arg {
+            params.push(Pattern::Ident(member.name.clone()));
+            args.push(arg);
+        }
+    }
+    visiting.pop();
+    if params.is_empty() {
+        return Some(default_expr);
+    }
+    let span = default.span.clone();
+    Some(Expr::Call {
+        func: Box::new(Expr::Lambda {
+            params,
+            body: Box::new(default_expr),
+            span: span.clone(),
+        }),
+        args,
+        span,
+    })
+}
+
+/// `class_name` followed by its superclasses, nearest first. The module's own classes shadow
+/// same-named classes elsewhere.
+fn class_hierarchy<'a>(
+    modules: &'a [Module],
+    module: &'a Module,
+    class_name: &str,
+) -> Vec<&'a ClassDecl> {
+    let find = |name: &str| {
+        std::iter::once(module)
+            .chain(modules.iter())
+            .flat_map(|module| module.items.iter())
+            .find_map(|item| match item {
+                ModuleItem::ClassDecl(class_decl) if class_decl.name.name == name => {
+                    Some(class_decl)
+                }
+                _ => None,
+            })
+    };
+    let mut hierarchy: Vec<&ClassDecl> = Vec::new();
+    let mut pending = vec![class_name.to_string()];
+    let mut visited = HashSet::new();
+    while !pending.is_empty() {
+        let name = pending.remove(0);
+        if !visited.insert(name.clone()) {
+            continue;
+        }
+        let Some(class_decl) = find(&name) else {
+            continue;
+        };
+        hierarchy.push(class_decl);
+        for super_expr in &class_decl.supers {
+            push_class_names(super_expr, &mut pending);
+        }
+    }
+    hierarchy
+}
+
+fn push_class_names(ty: &TypeExpr, out: &mut Vec<String>) {
+    match ty {
+        TypeExpr::And { items, .. } => {
+            for item in items {
+                push_class_names(item, out);
+            }
+        }
+        TypeExpr::Name(name) => out.push(name.name.clone()),
+        TypeExpr::Apply { base, .. } => push_class_names(base, out),
+        _ => {}
+    }
+}
+
+fn def_as_expr(def: &Def) -> Expr {
+    if def.params.is_empty() {
+        def.expr.clone()
+    } else {
+        Expr::Lambda {
+            params: def.params.clone(),
+            body: Box::new(def.expr.clone()),
+            span: def.span.clone(),
+        }
+    }
+}
+
+/// `log = a0 => __perform "log" [a0]`: performing an operation asks the innermost handler for it.
+fn effect_operation_def(op: &EffectOperation) -> Def {
+    let span = op.span.clone();
+    let mut arity = 0;
+    let mut ty = &op.ty;
+    while let TypeExpr::Func { params, result, .. } = ty {
+        arity += params.len();
+        ty = result;
+    }
+    let params: Vec<SpannedName> = (0..arity)
+        .map(|index| SpannedName {
+            name: format!("__arg{index}"),
+            span: span.clone(),
+        })
+        .collect();
+    let perform = Expr::Call {
+        func: Box::new(Expr::Ident(SpannedName {
+            name: "__perform".to_string(),
+            span: span.clone(),
+        })),
+        args: vec![
+            Expr::Literal(Literal::String {
+                text: op.name.name.clone(),
+                span: span.clone(),
+            }),
+            Expr::List {
+                items: params
+                    .iter()
+                    .map(|param| ListItem {
+                        expr: Expr::Ident(param.clone()),
+                        spread: false,
+                        span: span.clone(),
+                    })
+                    .collect(),
+                span: span.clone(),
+            },
+        ],
+        span: span.clone(),
+    };
+    let expr = if params.is_empty() {
+        perform
+    } else {
+        Expr::Lambda {
+            params: params.into_iter().map(Pattern::Ident).collect(),
+            body: Box::new(perform),
+            span: span.clone(),
+        }
+    };
+    Def {
+        decorators: Vec::new(),
+        name: op.name.clone(),
+        params: Vec::new(),
+        expr,
+        span,
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct DebugParams {
     pipes: bool,
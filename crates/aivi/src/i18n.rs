@@ -12,7 +12,42 @@ pub struct LocaleParts {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MessagePart {
     Lit(String),
-    Hole { name: String, ty: Option<String> },
+    Hole {
+        name: String,
+        ty: Option<String>,
+    },
+    /// `#` inside a plural arm: the number being pluralized.
+    Pound,
+    /// `{name, plural, ...}` or `{name, selectordinal, ...}` (`ordinal = true`).
+    Plural {
+        name: String,
+        ty: Option<String>,
+        ordinal: bool,
+        arms: Vec<MessageArm>,
+    },
+    /// `{name, select, ...}`.
+    Select {
+        name: String,
+        ty: Option<String>,
+        arms: Vec<MessageArm>,
+    },
+    /// `{name, number}`, `{name, date, long}`, ...: locale-formatted value.
+    Format {
+        name: String,
+        ty: Option<String>,
+        /// `number`, `date` or `time`.
+        format: String,
+        /// `integer`, `percent`, `currency/EUR`, or `short`/`medium`/`long` for dates.
+        style: Option<String>,
+    },
+}
+
+/// One `selector {message}` arm of a plural/select placeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageArm {
+    /// `=N`, a CLDR plural category, or a select key.
+    pub selector: String,
+    pub parts: Vec<MessagePart>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -124,67 +159,305 @@ pub fn parse_message_template(text: &str) -> Result<ParsedMessage, String> {
     // - Literal text.
     // - Escapes: '{{' -> '{', '}}' -> '}'.
     // - Placeholder: '{name}' or '{name:Type}' where name is identifier-ish.
-    let mut parts = Vec::new();
-    let mut buf = String::new();
+    // - ICU-style forms: '{name, plural, one {...} other {...}}', '{name, selectordinal, ...}'
+    //   and '{name, select, key {...} other {...}}'. Arms nest messages; inside an arm a single
+    //   '}' closes the arm, and inside plural arms '#' stands for the number.
+    // - Formatted values: '{name, number}', '{name, number, integer|percent|currency/EUR}',
+    //   '{name, date|time}' and '{name, date|time, short|medium|long}'.
+    let mut parser = TemplateParser {
+        chars: text.chars().collect(),
+        pos: 0,
+    };
+    let parts = parser.parse_parts(false, false)?;
+    Ok(ParsedMessage { parts })
+}
 
-    let mut chars = text.chars().peekable();
-    while let Some(ch) = chars.next() {
-        match ch {
-            '{' => {
-                if matches!(chars.peek(), Some('{')) {
-                    chars.next();
+struct TemplateParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl TemplateParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        self.chars.get(self.pos + 1).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(ch) if ch.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn take_until(&mut self, stops: &[char]) -> String {
+        let mut out = String::new();
+        while let Some(ch) = self.peek() {
+            if stops.contains(&ch) {
+                break;
+            }
+            out.push(ch);
+            self.pos += 1;
+        }
+        out
+    }
+
+    fn parse_parts(&mut self, in_arm: bool, in_plural: bool) -> Result<Vec<MessagePart>, String> {
+        let mut parts = Vec::new();
+        let mut buf = String::new();
+        while let Some(ch) = self.peek() {
+            match ch {
+                '{' if self.peek_next() == Some('{') => {
+                    self.pos += 2;
                     buf.push('{');
-                    continue;
                 }
-                if !buf.is_empty() {
-                    parts.push(MessagePart::Lit(std::mem::take(&mut buf)));
+                '{' => {
+                    if !buf.is_empty() {
+                        parts.push(MessagePart::Lit(std::mem::take(&mut buf)));
+                    }
+                    self.pos += 1;
+                    parts.push(self.parse_placeholder(in_plural)?);
+                }
+                '}' if in_arm => break,
+                '}' if self.peek_next() == Some('}') => {
+                    self.pos += 2;
+                    buf.push('}');
                 }
-                let mut inner = String::new();
-                let mut closed = false;
-                for next in chars.by_ref() {
-                    if next == '}' {
-                        closed = true;
-                        break;
+                '}' => return Err("unexpected '}' (use '}}' to escape)".to_string()),
+                '#' if in_plural => {
+                    if !buf.is_empty() {
+                        parts.push(MessagePart::Lit(std::mem::take(&mut buf)));
                     }
-                    inner.push(next);
+                    self.pos += 1;
+                    parts.push(MessagePart::Pound);
                 }
-                if !closed {
-                    return Err("unclosed '{' in message template".to_string());
+                other => {
+                    self.pos += 1;
+                    buf.push(other);
                 }
-                let inner = inner.trim();
-                if inner.is_empty() {
-                    return Err("empty placeholder '{}'".to_string());
+            }
+        }
+        if !buf.is_empty() {
+            parts.push(MessagePart::Lit(buf));
+        }
+        Ok(parts)
+    }
+
+    fn parse_placeholder(&mut self, in_plural: bool) -> Result<MessagePart, String> {
+        let head = self.take_until(&['}', ',']);
+        if self.peek().is_none() {
+            return Err("unclosed '{' in message template".to_string());
+        }
+        let head = head.trim();
+        if head.is_empty() {
+            return Err(if self.peek() == Some('}') {
+                "empty placeholder '{}'".to_string()
+            } else {
+                "placeholder name is empty".to_string()
+            });
+        }
+        let (name, ty) = match head.split_once(':') {
+            Some((n, t)) => (n.trim(), Some(t.trim())),
+            None => (head, None),
+        };
+        validate_placeholder_name(name)?;
+        let ty = ty
+            .filter(|t| !t.is_empty())
+            .map(|t| validate_placeholder_type(t).map(|_| t.to_string()))
+            .transpose()?;
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(MessagePart::Hole {
+                name: name.to_string(),
+                ty,
+            });
+        }
+
+        // ICU-style form: `{name, kind, arms...}` or `{name, format[, style]}`.
+        self.pos += 1;
+        let kind = self.take_until(&['}', ',']);
+        let kind = kind.trim();
+        if matches!(kind, "number" | "date" | "time") {
+            return self.parse_format(name, ty, kind);
+        }
+        if self.peek() != Some(',') {
+            return Err(format!("placeholder '{name}' is missing arms for '{kind}'"));
+        }
+        self.pos += 1;
+        match kind {
+            "plural" | "selectordinal" => {
+                if let Some(ty) = ty.as_deref() {
+                    if !matches!(ty, "Int" | "Float" | "Decimal") {
+                        return Err(format!(
+                            "{kind} placeholder '{name}' must be numeric, got {ty}"
+                        ));
+                    }
                 }
-                let (name, ty) = match inner.split_once(':') {
-                    Some((n, t)) => (n.trim(), Some(t.trim())),
-                    None => (inner, None),
-                };
-                validate_placeholder_name(name)?;
-                let ty = ty
-                    .filter(|t| !t.is_empty())
-                    .map(|t| validate_placeholder_type(t).map(|_| t.to_string()))
-                    .transpose()?;
-                parts.push(MessagePart::Hole {
+                let arms = self.parse_arms(name, kind, true)?;
+                Ok(MessagePart::Plural {
                     name: name.to_string(),
                     ty,
-                });
+                    ordinal: kind == "selectordinal",
+                    arms,
+                })
             }
-            '}' => {
-                if matches!(chars.peek(), Some('}')) {
-                    chars.next();
-                    buf.push('}');
-                    continue;
+            "select" => {
+                if let Some(ty) = ty.as_deref() {
+                    if ty != "Text" {
+                        return Err(format!(
+                            "select placeholder '{name}' must be Text, got {ty}"
+                        ));
+                    }
                 }
-                return Err("unexpected '}' (use '}}' to escape)".to_string());
+                let arms = self.parse_arms(name, kind, in_plural)?;
+                Ok(MessagePart::Select {
+                    name: name.to_string(),
+                    ty,
+                    arms,
+                })
             }
-            other => buf.push(other),
+            other => Err(format!(
+                "unsupported placeholder format '{other}' (expected plural, select, selectordinal, number, date or time)"
+            )),
         }
     }
-    if !buf.is_empty() {
-        parts.push(MessagePart::Lit(buf));
+
+    fn parse_format(
+        &mut self,
+        name: &str,
+        ty: Option<String>,
+        format: &str,
+    ) -> Result<MessagePart, String> {
+        let style = match self.peek() {
+            Some(',') => {
+                self.pos += 1;
+                let style = self.take_until(&['}', ',']);
+                Some(style.trim().to_string())
+            }
+            _ => None,
+        };
+        if self.peek() != Some('}') {
+            return Err(format!("unclosed {format} placeholder '{name}'"));
+        }
+        self.pos += 1;
+        let (allowed_types, style_ok): (&[&str], bool) = match format {
+            "number" => (
+                &["Int", "Float", "Decimal"],
+                style.as_deref().is_none_or(|style| {
+                    matches!(style, "integer" | "percent")
+                        || style.strip_prefix("currency/").is_some_and(|code| {
+                            code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic())
+                        })
+                }),
+            ),
+            _ => (
+                &["DateTime"],
+                style
+                    .as_deref()
+                    .is_none_or(|style| matches!(style, "short" | "medium" | "long")),
+            ),
+        };
+        if let Some(ty) = ty.as_deref() {
+            if !allowed_types.contains(&ty) {
+                return Err(format!(
+                    "{format} placeholder '{name}' must be {}, got {ty}",
+                    allowed_types.join(" or ")
+                ));
+            }
+        }
+        if !style_ok {
+            return Err(format!(
+                "unsupported {format} style '{}' in placeholder '{name}'",
+                style.unwrap_or_default()
+            ));
+        }
+        Ok(MessagePart::Format {
+            name: name.to_string(),
+            ty,
+            format: format.to_string(),
+            style,
+        })
     }
 
-    Ok(ParsedMessage { parts })
+    fn parse_arms(
+        &mut self,
+        name: &str,
+        kind: &str,
+        in_plural: bool,
+    ) -> Result<Vec<MessageArm>, String> {
+        let mut arms: Vec<MessageArm> = Vec::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                None => return Err(format!("unclosed {kind} placeholder '{name}'")),
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(_) => {}
+            }
+            let selector = self.take_until(&['{', '}']);
+            let selector = selector.trim().to_string();
+            if self.peek() != Some('{') {
+                return Err(format!(
+                    "{kind} placeholder '{name}': expected '{{' after selector '{selector}'"
+                ));
+            }
+            validate_arm_selector(kind, &selector)?;
+            if arms.iter().any(|arm| arm.selector == selector) {
+                return Err(format!(
+                    "{kind} placeholder '{name}': duplicate selector '{selector}'"
+                ));
+            }
+            self.pos += 1;
+            let parts = self.parse_parts(true, in_plural)?;
+            if self.peek() != Some('}') {
+                return Err(format!(
+                    "{kind} placeholder '{name}': unclosed arm '{selector}'"
+                ));
+            }
+            self.pos += 1;
+            arms.push(MessageArm { selector, parts });
+        }
+        if !arms.iter().any(|arm| arm.selector == "other") {
+            return Err(format!(
+                "{kind} placeholder '{name}' requires an 'other' arm"
+            ));
+        }
+        Ok(arms)
+    }
+}
+
+fn validate_arm_selector(kind: &str, selector: &str) -> Result<(), String> {
+    if selector.is_empty() {
+        return Err(format!("{kind} arm is missing a selector"));
+    }
+    if kind == "select" {
+        if !selector
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+        {
+            return Err(format!("invalid select key '{selector}'"));
+        }
+        return Ok(());
+    }
+    if let Some(exact) = selector.strip_prefix('=') {
+        if exact.is_empty() || !exact.chars().all(|ch| ch.is_ascii_digit()) {
+            return Err(format!(
+                "invalid {kind} selector '{selector}': expected '=' followed by digits"
+            ));
+        }
+        return Ok(());
+    }
+    if PLURAL_CATEGORIES.contains(&selector) {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid {kind} selector '{selector}': expected one of zero, one, two, few, many, other or '=N'"
+        ))
+    }
 }
 
 fn validate_placeholder_name(name: &str) -> Result<(), String> {
@@ -214,6 +487,164 @@ fn validate_placeholder_type(ty: &str) -> Result<(), String> {
     }
 }
 
+/// CLDR plural categories accepted as plural/selectordinal selectors.
+pub const PLURAL_CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// CLDR plural operands, derived from the decimal text of a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluralOperands {
+    /// Integer digits of the absolute value.
+    pub i: u64,
+    /// Number of visible fraction digits (with trailing zeros).
+    pub v: usize,
+    /// Visible fraction digits as an integer (with trailing zeros).
+    pub f: u64,
+}
+
+impl PluralOperands {
+    /// Parses operands from a plain decimal rendering such as `-12`, `1.50` or `3.25`.
+    pub fn from_decimal_text(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('-');
+        let (int_part, frac_part) = text.split_once('.').unwrap_or((text, ""));
+        if int_part.is_empty() || !int_part.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        if !frac_part.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        // Only the low digits matter for the modulo-based rules.
+        let low = |digits: &str| {
+            let start = digits.len().saturating_sub(18);
+            digits[start..].parse::<u64>().unwrap_or(0)
+        };
+        Some(Self {
+            i: low(int_part),
+            v: frac_part.len(),
+            f: low(frac_part),
+        })
+    }
+
+    fn integer(&self) -> Option<u64> {
+        (self.f == 0).then_some(self.i)
+    }
+}
+
+/// Returns the CLDR plural category for `operands` in `language`.
+///
+/// Rules are bundled for the locales we ship; other languages use the CLDR root rule (`other`).
+pub fn plural_category(language: &str, operands: PluralOperands, ordinal: bool) -> &'static str {
+    let PluralOperands { i, v, .. } = operands;
+    let n = operands.integer();
+    if ordinal {
+        return match language {
+            "en" => match (i % 10, i % 100) {
+                _ if n.is_none() => "other",
+                (1, r) if r != 11 => "one",
+                (2, r) if r != 12 => "two",
+                (3, r) if r != 13 => "few",
+                _ => "other",
+            },
+            "fr" if n == Some(1) => "one",
+            "it" if matches!(n, Some(11 | 8 | 80 | 800)) => "many",
+            "sv" if n.is_some() && matches!(i % 10, 1 | 2) && !matches!(i % 100, 11 | 12) => "one",
+            _ => "other",
+        };
+    }
+    let million = v == 0 && i != 0 && i % 1_000_000 == 0;
+    match language {
+        "en" | "de" | "nl" | "sv" | "nb" | "no" | "fi" | "et" | "ca" | "gl" => {
+            if i == 1 && v == 0 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        // `n = 1 or t != 0 and i = 0,1`: 1, 1.0, 0.5 and 1.5 are all `one`.
+        "da" => {
+            if i <= 1 && (n == Some(1) || n.is_none()) {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        "it" => match () {
+            _ if i == 1 && v == 0 => "one",
+            _ if million => "many",
+            _ => "other",
+        },
+        "es" => match () {
+            _ if n == Some(1) => "one",
+            _ if million => "many",
+            _ => "other",
+        },
+        "fr" | "pt" => match () {
+            _ if i <= 1 => "one",
+            _ if million => "many",
+            _ => "other",
+        },
+        "ru" | "uk" => match (v, i % 10, i % 100) {
+            (0, 1, r) if r != 11 => "one",
+            (0, 2..=4, r) if !(12..=14).contains(&r) => "few",
+            (0, _, _) => "many",
+            _ => "other",
+        },
+        "pl" => match (v, i % 10, i % 100) {
+            (0, _, _) if i == 1 => "one",
+            (0, 2..=4, r) if !(12..=14).contains(&r) => "few",
+            (0, _, _) => "many",
+            _ => "other",
+        },
+        "cs" | "sk" => match () {
+            _ if v != 0 => "many",
+            _ if i == 1 => "one",
+            _ if (2..=4).contains(&i) => "few",
+            _ => "other",
+        },
+        "ar" => match n {
+            Some(0) => "zero",
+            Some(1) => "one",
+            Some(2) => "two",
+            Some(n) if (3..=10).contains(&(n % 100)) => "few",
+            Some(n) if (11..=99).contains(&(n % 100)) => "many",
+            _ => "other",
+        },
+        _ => "other",
+    }
+}
+
+/// Picks the plural arm for a number given as decimal text: exact `=N` arms win over the
+/// locale's plural category, which falls back to `other`.
+pub fn select_plural_arm<'a>(
+    arms: &'a [MessageArm],
+    language: &str,
+    number: &str,
+    ordinal: bool,
+) -> Option<&'a MessageArm> {
+    let other = || arms.iter().find(|arm| arm.selector == "other");
+    let Some(operands) = PluralOperands::from_decimal_text(number) else {
+        return other();
+    };
+    if !number.trim().starts_with('-') {
+        if let Some(n) = operands.integer() {
+            let exact = format!("={n}");
+            if let Some(arm) = arms.iter().find(|arm| arm.selector == exact) {
+                return Some(arm);
+            }
+        }
+    }
+    let category = plural_category(language, operands, ordinal);
+    arms.iter()
+        .find(|arm| arm.selector == category)
+        .or_else(other)
+}
+
+/// Picks the select arm matching `key`, falling back to `other`.
+pub fn select_arm<'a>(arms: &'a [MessageArm], key: &str) -> Option<&'a MessageArm> {
+    arms.iter()
+        .find(|arm| arm.selector == key)
+        .or_else(|| arms.iter().find(|arm| arm.selector == "other"))
+}
+
 pub fn escape_sigil_string_body(text: &str) -> Cow<'_, str> {
     // `~tag"..."` uses the sigil string body verbatim with backslash escapes preserved.
     // Keep the escaping minimal and ASCII-friendly.
@@ -273,4 +704,109 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn message_parses_plural_and_select_arms() {
+        let msg = parse_message_template("{n, plural, =0 {none} one {# {what}} other {# {what}s}}")
+            .unwrap();
+        let [MessagePart::Plural {
+            name,
+            ordinal,
+            arms,
+            ..
+        }] = msg.parts.as_slice()
+        else {
+            panic!("expected a single plural part, got {:?}", msg.parts);
+        };
+        assert_eq!(name, "n");
+        assert!(!ordinal);
+        assert_eq!(
+            arms.iter()
+                .map(|arm| arm.selector.as_str())
+                .collect::<Vec<_>>(),
+            vec!["=0", "one", "other"]
+        );
+        assert_eq!(
+            arms[1].parts,
+            vec![
+                MessagePart::Pound,
+                MessagePart::Lit(" ".to_string()),
+                MessagePart::Hole {
+                    name: "what".to_string(),
+                    ty: None
+                }
+            ]
+        );
+
+        let msg = parse_message_template("{g, select, f {her} other {their}} # }}").unwrap();
+        assert!(matches!(&msg.parts[0], MessagePart::Select { arms, .. } if arms.len() == 2));
+        assert_eq!(msg.parts[1], MessagePart::Lit(" # }".to_string()));
+    }
+
+    #[test]
+    fn message_rejects_malformed_plural_forms() {
+        assert!(parse_message_template("{n, plural, one {x}}").is_err());
+        assert!(parse_message_template("{n, plural, lots {x} other {y}}").is_err());
+        assert!(parse_message_template("{n, plural, one {x} one {y} other {z}}").is_err());
+        assert!(parse_message_template("{n, plural, other {x}").is_err());
+        assert!(parse_message_template("{n:Text, plural, other {x}}").is_err());
+        assert!(parse_message_template("{n, choice, other {x}}").is_err());
+    }
+
+    #[test]
+    fn message_parses_format_placeholders() {
+        let msg = parse_message_template("{total, number, currency/EUR} on {when:DateTime, date}")
+            .unwrap();
+        assert_eq!(
+            msg.parts,
+            vec![
+                MessagePart::Format {
+                    name: "total".to_string(),
+                    ty: None,
+                    format: "number".to_string(),
+                    style: Some("currency/EUR".to_string()),
+                },
+                MessagePart::Lit(" on ".to_string()),
+                MessagePart::Format {
+                    name: "when".to_string(),
+                    ty: Some("DateTime".to_string()),
+                    format: "date".to_string(),
+                    style: None,
+                },
+            ]
+        );
+        assert!(parse_message_template("{n, number, roman}").is_err());
+        assert!(parse_message_template("{n:Text, number}").is_err());
+        assert!(parse_message_template("{d, time, full}").is_err());
+        assert!(parse_message_template("{d:Int, date}").is_err());
+    }
+
+    #[test]
+    fn plural_rules_follow_cldr() {
+        let cat = |lang: &str, n: &str| {
+            plural_category(lang, PluralOperands::from_decimal_text(n).unwrap(), false)
+        };
+        assert_eq!(cat("en", "1"), "one");
+        assert_eq!(cat("en", "1.0"), "other");
+        assert_eq!(cat("da", "1.0"), "one");
+        assert_eq!(cat("da", "0.5"), "one");
+        assert_eq!(cat("da", "0"), "other");
+        assert_eq!(cat("da", "2.5"), "other");
+        assert_eq!(cat("fr", "1.5"), "one");
+        assert_eq!(cat("ru", "21"), "one");
+        assert_eq!(cat("ru", "24"), "few");
+        assert_eq!(cat("ru", "14"), "many");
+        assert_eq!(cat("pl", "22"), "few");
+        assert_eq!(cat("pl", "25"), "many");
+        assert_eq!(cat("cs", "3"), "few");
+        assert_eq!(cat("ar", "105"), "few");
+        assert_eq!(cat("ja", "1"), "other");
+
+        let ord = |lang: &str, n: &str| {
+            plural_category(lang, PluralOperands::from_decimal_text(n).unwrap(), true)
+        };
+        assert_eq!(ord("en", "22"), "two");
+        assert_eq!(ord("en", "13"), "other");
+        assert_eq!(ord("it", "8"), "many");
+    }
 }
@@ -0,0 +1,497 @@
+//! Catalog tooling behind `aivi i18n extract` and `aivi i18n check`.
+//!
+//! Sources are scanned token-wise for `~k"..."` keys; a `~m"..."` template written right after a
+//! key on the same line (e.g. `(~k"app.title", ~m"Welcome")`) becomes that key's source text.
+//! Catalogs are `<locale>.properties` files in one directory.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::diagnostics::{Diagnostic, DiagnosticSeverity, FileDiagnostic, Position, Span};
+use crate::i18n::{parse_locale_tag, parse_message_template, validate_key_text, MessagePart};
+use crate::i18n_codegen::parse_properties_line;
+use crate::lexer::lex;
+use crate::surface::parse_sigil_text;
+use crate::AiviError;
+
+/// A `~k` key used in source code.
+#[derive(Debug, Clone)]
+pub struct I18nKeyUsage {
+    pub key: String,
+    /// The `~m` template written next to the key, if any.
+    pub default_message: Option<String>,
+    pub path: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct I18nCatalog {
+    /// Normalized locale tag taken from the file name (`i18n/de-AT.properties`).
+    pub locale: String,
+    pub path: String,
+    pub entries: Vec<I18nCatalogEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct I18nCatalogEntry {
+    pub key: String,
+    pub message: String,
+    pub line: usize,
+}
+
+impl I18nCatalog {
+    fn entry(&self, key: &str) -> Option<&I18nCatalogEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    /// Present with a non-empty message; `key =` marks a key as untranslated.
+    fn translates(&self, key: &str) -> bool {
+        self.entry(key)
+            .is_some_and(|entry| !entry.message.trim().is_empty())
+    }
+}
+
+pub fn collect_i18n_key_usages(path: &str, text: &str) -> Vec<I18nKeyUsage> {
+    let (tokens, _) = lex(text);
+    let tokens: Vec<_> = tokens
+        .into_iter()
+        .filter(|token| !matches!(token.kind.as_str(), "whitespace" | "comment"))
+        .collect();
+    let sigil = |idx: usize, tag: &str| {
+        let token = tokens.get(idx).filter(|token| token.kind == "sigil")?;
+        let (found, body, _) = parse_sigil_text(&token.text)?;
+        (found == tag).then_some((token, body))
+    };
+
+    let mut usages = Vec::new();
+    for idx in 0..tokens.len() {
+        let Some((token, key)) = sigil(idx, "k") else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        if validate_key_text(&key).is_err() {
+            // Reported by the parser (E1514).
+            continue;
+        }
+        let mut next = idx + 1;
+        if tokens
+            .get(next)
+            .is_some_and(|token| token.kind == "symbol" && token.text == ",")
+        {
+            next += 1;
+        }
+        let default_message = sigil(next, "m")
+            .filter(|(message, _)| message.span.start.line == token.span.end.line)
+            .map(|(_, body)| body);
+        usages.push(I18nKeyUsage {
+            key,
+            default_message,
+            path: path.to_string(),
+            span: token.span.clone(),
+        });
+    }
+    usages
+}
+
+/// Loads every `<locale>.properties` catalog in `dir` (sorted by file name). Malformed lines are
+/// skipped and reported as `E2603`.
+pub fn load_i18n_catalogs(
+    dir: &Path,
+) -> Result<(Vec<I18nCatalog>, Vec<FileDiagnostic>), AiviError> {
+    let mut paths = Vec::new();
+    if dir.is_dir() {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "properties") {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+
+    let mut catalogs = Vec::new();
+    let mut diagnostics = Vec::new();
+    for path in paths {
+        let path_text = path.to_string_lossy().to_string();
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let locale = match parse_locale_tag(&stem) {
+            Ok(locale) => locale.tag,
+            Err(msg) => {
+                diagnostics.push(catalog_diagnostic(
+                    &path_text,
+                    1,
+                    "E2603",
+                    DiagnosticSeverity::Error,
+                    format!("catalog file name must be a locale tag: {msg}"),
+                ));
+                continue;
+            }
+        };
+        let text = std::fs::read_to_string(&path)?;
+        catalogs.push(parse_i18n_catalog(
+            &locale,
+            &path_text,
+            &text,
+            &mut diagnostics,
+        ));
+    }
+    Ok((catalogs, diagnostics))
+}
+
+pub fn parse_i18n_catalog(
+    locale: &str,
+    path: &str,
+    text: &str,
+    diagnostics: &mut Vec<FileDiagnostic>,
+) -> I18nCatalog {
+    let mut entries: Vec<I18nCatalogEntry> = Vec::new();
+    for (idx, raw_line) in text.lines().enumerate() {
+        match parse_properties_line(raw_line) {
+            Ok(Some(entry)) => {
+                if entries.iter().any(|existing| existing.key == entry.key) {
+                    diagnostics.push(catalog_diagnostic(
+                        path,
+                        idx + 1,
+                        "E2603",
+                        DiagnosticSeverity::Error,
+                        format!("duplicate key '{}'", entry.key),
+                    ));
+                    continue;
+                }
+                entries.push(I18nCatalogEntry {
+                    key: entry.key,
+                    message: entry.message,
+                    line: idx + 1,
+                });
+            }
+            Ok(None) => {}
+            Err(msg) => diagnostics.push(catalog_diagnostic(
+                path,
+                idx + 1,
+                "E2603",
+                DiagnosticSeverity::Error,
+                msg,
+            )),
+        }
+    }
+    I18nCatalog {
+        locale: locale.to_string(),
+        path: path.to_string(),
+        entries,
+    }
+}
+
+/// `W2600` at each `~k` usage whose key is missing (or empty) in one of the catalogs.
+pub fn missing_i18n_translations(
+    usages: &[I18nKeyUsage],
+    catalogs: &[I18nCatalog],
+) -> Vec<FileDiagnostic> {
+    let mut diagnostics = Vec::new();
+    for usage in usages {
+        let missing: Vec<&str> = catalogs
+            .iter()
+            .filter(|catalog| !catalog.translates(&usage.key))
+            .map(|catalog| catalog.locale.as_str())
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+        diagnostics.push(FileDiagnostic {
+            path: usage.path.clone(),
+            diagnostic: Diagnostic {
+                code: "W2600".to_string(),
+                severity: DiagnosticSeverity::Warning,
+                message: format!(
+                    "missing translation for '{}' in {}",
+                    usage.key,
+                    missing.join(", ")
+                ),
+                span: usage.span.clone(),
+                labels: Vec::new(),
+            },
+        });
+    }
+    diagnostics
+}
+
+/// Full consistency check: missing translations (`W2600`), keys no source uses (`W2601`) and
+/// placeholders that disagree with the source-locale catalog (`E2602`).
+pub fn check_i18n_catalogs(
+    usages: &[I18nKeyUsage],
+    catalogs: &[I18nCatalog],
+    source_locale: &str,
+) -> Vec<FileDiagnostic> {
+    let mut diagnostics = missing_i18n_translations(usages, catalogs);
+
+    let used: BTreeSet<&str> = usages.iter().map(|usage| usage.key.as_str()).collect();
+    for catalog in catalogs {
+        for entry in &catalog.entries {
+            if !used.contains(entry.key.as_str()) {
+                diagnostics.push(catalog_diagnostic(
+                    &catalog.path,
+                    entry.line,
+                    "W2601",
+                    DiagnosticSeverity::Warning,
+                    format!(
+                        "key '{}' in the {} catalog is not used by any source",
+                        entry.key, catalog.locale
+                    ),
+                ));
+            }
+        }
+    }
+
+    let reference = catalogs
+        .iter()
+        .find(|catalog| catalog.locale == normalize_locale(source_locale))
+        .or_else(|| catalogs.first());
+    let Some(reference) = reference else {
+        return diagnostics;
+    };
+    for catalog in catalogs {
+        if std::ptr::eq(catalog, reference) {
+            continue;
+        }
+        for entry in &catalog.entries {
+            let Some(expected) = reference.entry(&entry.key) else {
+                continue;
+            };
+            if entry.message.trim().is_empty() || expected.message.trim().is_empty() {
+                continue;
+            }
+            let expected_sig = placeholder_signature(&expected.message);
+            let found_sig = placeholder_signature(&entry.message);
+            let mut problems = Vec::new();
+            for (name, expected_ty) in &expected_sig {
+                match found_sig.get(name) {
+                    None => problems.push(format!("missing placeholder '{name}'")),
+                    Some(found_ty) => {
+                        if let (Some(expected_ty), Some(found_ty)) = (expected_ty, found_ty) {
+                            if expected_ty != found_ty {
+                                problems.push(format!(
+                                    "placeholder '{name}' is {found_ty}, expected {expected_ty}"
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+            for name in found_sig.keys() {
+                if !expected_sig.contains_key(name) {
+                    problems.push(format!("unknown placeholder '{name}'"));
+                }
+            }
+            for problem in problems {
+                diagnostics.push(catalog_diagnostic(
+                    &catalog.path,
+                    entry.line,
+                    "E2602",
+                    DiagnosticSeverity::Error,
+                    format!(
+                        "'{}' in the {} catalog: {problem} (compared with {})",
+                        entry.key, catalog.locale, reference.locale
+                    ),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Appends keys used in sources but absent from `existing` catalog text. The source locale gets
+/// the `~m` template written next to the key; other locales get an empty (untranslated) value.
+/// Returns the new text and the number of keys added.
+pub fn merge_i18n_catalog(
+    existing: &str,
+    usages: &[I18nKeyUsage],
+    is_source_locale: bool,
+) -> (String, usize) {
+    let present: BTreeSet<String> = existing
+        .lines()
+        .filter_map(|line| parse_properties_line(line).ok().flatten())
+        .map(|entry| entry.key)
+        .collect();
+    let mut additions: BTreeMap<&str, Option<&str>> = BTreeMap::new();
+    for usage in usages {
+        if present.contains(&usage.key) {
+            continue;
+        }
+        let slot = additions.entry(usage.key.as_str()).or_insert(None);
+        if slot.is_none() {
+            *slot = usage.default_message.as_deref();
+        }
+    }
+
+    let mut out = existing.to_string();
+    if additions.is_empty() {
+        return (out, 0);
+    }
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    for (key, message) in &additions {
+        match message.filter(|_| is_source_locale) {
+            Some(message) => out.push_str(&format!("{key} = {}\n", message.replace('\n', "\\n"))),
+            None => out.push_str(&format!("{key} =\n")),
+        }
+    }
+    (out, additions.len())
+}
+
+fn normalize_locale(tag: &str) -> String {
+    parse_locale_tag(tag)
+        .map(|locale| locale.tag)
+        .unwrap_or_else(|_| tag.to_string())
+}
+
+/// Placeholder name -> declared (or implied) type.
+fn placeholder_signature(message: &str) -> BTreeMap<String, Option<String>> {
+    fn collect(parts: &[MessagePart], out: &mut BTreeMap<String, Option<String>>) {
+        for part in parts {
+            let (name, ty) = match part {
+                MessagePart::Lit(_) | MessagePart::Pound => continue,
+                MessagePart::Hole { name, ty } => (name, ty.clone()),
+                MessagePart::Plural { name, ty, arms, .. } => {
+                    for arm in arms {
+                        collect(&arm.parts, out);
+                    }
+                    (name, ty.clone())
+                }
+                MessagePart::Select { name, ty, arms } => {
+                    for arm in arms {
+                        collect(&arm.parts, out);
+                    }
+                    (name, ty.clone().or_else(|| Some("Text".to_string())))
+                }
+                MessagePart::Format {
+                    name, ty, format, ..
+                } => match format.as_str() {
+                    "number" => (name, ty.clone()),
+                    _ => (name, Some("DateTime".to_string())),
+                },
+            };
+            let slot = out.entry(name.clone()).or_insert(None);
+            if slot.is_none() {
+                *slot = ty;
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    if let Ok(parsed) = parse_message_template(message) {
+        collect(&parsed.parts, &mut out);
+    }
+    out
+}
+
+fn catalog_diagnostic(
+    path: &str,
+    line: usize,
+    code: &str,
+    severity: DiagnosticSeverity,
+    message: String,
+) -> FileDiagnostic {
+    FileDiagnostic {
+        path: path.to_string(),
+        diagnostic: Diagnostic {
+            code: code.to_string(),
+            severity,
+            message,
+            span: Span {
+                start: Position { line, column: 1 },
+                end: Position { line, column: 1 },
+            },
+            labels: Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usages_pair_keys_with_adjacent_templates() {
+        let source = r#"module app.main
+entries = [(~k"app.title", ~m"Welcome, {name:Text}!"), (~k"app.bye", ~m"Bye")]
+label = t bundle ~k"app.count"
+  ~m"unrelated"
+"#;
+        let usages = collect_i18n_key_usages("main.aivi", source);
+        let found: Vec<_> = usages
+            .iter()
+            .map(|usage| (usage.key.as_str(), usage.default_message.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("app.title", Some("Welcome, {name:Text}!")),
+                ("app.bye", Some("Bye")),
+                ("app.count", None),
+            ]
+        );
+        assert_eq!(usages[2].span.start.line, 3);
+    }
+
+    #[test]
+    fn check_reports_missing_unused_and_mismatched_entries() {
+        let usages =
+            collect_i18n_key_usages("main.aivi", "x = [~k\"app.title\", ~k\"app.count\"]\n");
+        let mut diagnostics = Vec::new();
+        let en = parse_i18n_catalog(
+            "en",
+            "i18n/en.properties",
+            "app.title = Hi {name:Text}\napp.count = {n, plural, one {# item} other {# items}}\napp.old = Old\n",
+            &mut diagnostics,
+        );
+        let de = parse_i18n_catalog(
+            "de",
+            "i18n/de.properties",
+            "app.title = Hallo {name:Int}\napp.count =\n",
+            &mut diagnostics,
+        );
+        assert!(diagnostics.is_empty());
+
+        let diagnostics = check_i18n_catalogs(&usages, &[en, de], "en");
+        let summary: Vec<_> = diagnostics
+            .iter()
+            .map(|d| {
+                (
+                    d.diagnostic.code.as_str(),
+                    d.path.as_str(),
+                    d.diagnostic.span.start.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("W2600", "main.aivi", 1),
+                ("W2601", "i18n/en.properties", 3),
+                ("E2602", "i18n/de.properties", 1),
+            ]
+        );
+        assert!(diagnostics[0]
+            .diagnostic
+            .message
+            .contains("'app.count' in de"));
+    }
+
+    #[test]
+    fn merge_appends_only_missing_keys() {
+        let usages = collect_i18n_key_usages(
+            "main.aivi",
+            "x = [(~k\"app.b\", ~m\"B {n}\"), (~k\"app.a\", ~m\"A\")]\n",
+        );
+        let (text, added) = merge_i18n_catalog("# header\napp.a = Eh\n", &usages, true);
+        assert_eq!(added, 1);
+        assert_eq!(text, "# header\napp.a = Eh\napp.b = B {n}\n");
+
+        let (text, added) = merge_i18n_catalog("", &usages, false);
+        assert_eq!(added, 2);
+        assert_eq!(text, "app.a =\napp.b =\n");
+    }
+}
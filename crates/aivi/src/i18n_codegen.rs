@@ -14,24 +14,31 @@ pub fn parse_properties_catalog(text: &str) -> Result<Vec<PropertiesEntry>, Stri
     let mut entries = Vec::new();
     for (idx, raw_line) in text.lines().enumerate() {
         let line_no = idx + 1;
-        let line = raw_line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
+        if let Some(entry) =
+            parse_properties_line(raw_line).map_err(|msg| format!("line {line_no}: {msg}"))?
+        {
+            entries.push(entry);
         }
-        let (k, v) =
-            split_kv(line).ok_or_else(|| format!("line {line_no}: expected 'key = value'"))?;
-        let key = k.trim().to_string();
-        let message =
-            unescape_properties_value(v.trim()).map_err(|msg| format!("line {line_no}: {msg}"))?;
-
-        validate_key_text(&key).map_err(|msg| format!("line {line_no}: {msg}"))?;
-        parse_message_template(&message).map_err(|msg| format!("line {line_no}: {msg}"))?;
-
-        entries.push(PropertiesEntry { key, message });
     }
     Ok(entries)
 }
 
+/// Parses one catalog line; blank lines and `#` comments yield `None`.
+pub(crate) fn parse_properties_line(raw_line: &str) -> Result<Option<PropertiesEntry>, String> {
+    let line = raw_line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let (k, v) = split_kv(line).ok_or_else(|| "expected 'key = value'".to_string())?;
+    let key = k.trim().to_string();
+    let message = unescape_properties_value(v.trim())?;
+
+    validate_key_text(&key)?;
+    parse_message_template(&message)?;
+
+    Ok(Some(PropertiesEntry { key, message }))
+}
+
 pub fn generate_i18n_module_from_properties(
     module_name: &str,
     locale_tag: &str,
@@ -127,7 +134,7 @@ pub fn generate_i18n_module_from_properties(
     out.push_str("  Map.get (keyText k) bundle.entries ?\n");
     out.push_str("    | None => keyText k\n");
     out.push_str("    | Some msg =>\n");
-    out.push_str("      (i18n.render msg args) ?\n");
+    out.push_str("      (i18n.renderIn locale msg args) ?\n");
     out.push_str("        | Ok txt => txt\n");
     out.push_str("        | Err _  => keyText k\n");
 
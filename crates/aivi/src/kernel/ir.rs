@@ -257,6 +257,16 @@ pub enum KernelBlockItem {
     Expr {
         expr: KernelExpr,
     },
+    /// Mutually recursive function bindings: every name is in scope in every body.
+    LetRec {
+        bindings: Vec<KernelRecBinding>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KernelRecBinding {
+    pub name: String,
+    pub expr: KernelExpr,
 }
 
 struct IdGen {
@@ -539,6 +549,18 @@ fn lower_generate_block(items: Vec<HirBlockItem>, id_gen: &mut IdGen) -> KernelE
             // Unsupported for now
             gen_empty(id_gen)
         }
+        HirBlockItem::LetRec { bindings } => {
+            // The rest of the generator is evaluated in the scope of the recursive bindings.
+            let next = lower_generate_block(rest, id_gen);
+            KernelExpr::Block {
+                id: id_gen.next(),
+                block_kind: KernelBlockKind::Plain,
+                items: vec![
+                    lower_block_item(HirBlockItem::LetRec { bindings }, id_gen),
+                    KernelBlockItem::Expr { expr: next },
+                ],
+            }
+        }
     }
 }
 
@@ -197,6 +197,15 @@ fn lower_block_item(item: HirBlockItem, id_gen: &mut IdGen) -> KernelBlockItem {
         HirBlockItem::Expr { expr } => KernelBlockItem::Expr {
             expr: lower_expr(expr, id_gen),
         },
+        HirBlockItem::LetRec { bindings } => KernelBlockItem::LetRec {
+            bindings: bindings
+                .into_iter()
+                .map(|binding| KernelRecBinding {
+                    name: binding.name,
+                    expr: lower_expr(binding.expr, id_gen),
+                })
+                .collect(),
+        },
     }
 }
 
@@ -370,6 +379,11 @@ fn find_max_id_expr(expr: &HirExpr, max: &mut u32) {
                     | HirBlockItem::Expr { expr } => {
                         find_max_id_expr(expr, max);
                     }
+                    HirBlockItem::LetRec { bindings } => {
+                        for binding in bindings {
+                            find_max_id_expr(&binding.expr, max);
+                        }
+                    }
                 }
             }
         }
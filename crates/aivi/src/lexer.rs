@@ -162,7 +162,12 @@ pub fn lex(content: &str) -> (Vec<CstToken>, Vec<Diagnostic>) {
             }
         }
 
-        if is_ident_start(ch) {
+        // `?name` is a typed hole; it lexes as one identifier so it can stand wherever a name can.
+        let is_hole = ch == '?'
+            && chars
+                .get(index + 1)
+                .is_some_and(|next| next.is_ascii_lowercase() || *next == '_');
+        if is_ident_start(ch) || is_hole {
             let start = index;
             let start_col = col;
             index += 1;
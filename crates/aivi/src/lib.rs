@@ -1,9 +1,14 @@
 mod cst;
+mod diagnostic_output;
 mod diagnostics;
+mod doc_gen;
+mod error_codes;
 mod formatter;
 mod hir;
 mod i18n;
+mod i18n_catalog;
 mod i18n_codegen;
+mod i18n_format;
 mod kernel;
 pub mod lexer;
 mod mcp;
@@ -20,16 +25,33 @@ pub mod syntax;
 mod typecheck;
 mod workspace;
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 pub use cst::{CstBundle, CstFile, CstToken};
+pub use diagnostic_output::{
+    diagnostics_to_json, diagnostics_to_sarif, render_diagnostics_short, DiagnosticFilter,
+    DiagnosticFormat,
+};
 pub use diagnostics::{
-    file_diagnostics_have_errors, render_diagnostics, Diagnostic, DiagnosticLabel,
-    DiagnosticSeverity, FileDiagnostic, Position, Span,
+    diagnostic_fixes, file_diagnostics_have_errors, render_diagnostics, Diagnostic, DiagnosticEdit,
+    DiagnosticFix, DiagnosticLabel, DiagnosticSeverity, FileDiagnostic, Position, Span,
+};
+pub use doc_gen::{
+    collect_doctests, doctest_module_source, generate_docs, module_doc, write_doc_pages, ClassDoc,
+    DocFormat, DocPage, Doctest, DomainDoc, ModuleDoc, TypeDoc, ValueDoc,
+};
+pub use error_codes::{
+    error_code_docs_url, error_code_info, error_codes_markdown, ErrorCodeInfo, ERROR_CODES,
+    ERROR_CODES_DOCS_URL,
 };
 pub use formatter::{format_text, format_text_with_options, FormatOptions};
 pub use hir::{HirModule, HirProgram};
+pub use i18n_catalog::{
+    check_i18n_catalogs, collect_i18n_key_usages, load_i18n_catalogs, merge_i18n_catalog,
+    missing_i18n_translations, parse_i18n_catalog, I18nCatalog, I18nCatalogEntry, I18nKeyUsage,
+};
 pub use i18n_codegen::{
     generate_i18n_module_from_properties, parse_properties_catalog, PropertiesEntry,
 };
@@ -44,9 +66,13 @@ pub use mcp::{
 };
 pub use native_rust_backend::{emit_native_rust_source, emit_native_rust_source_lib};
 pub use pm::{
-    collect_aivi_sources, edit_cargo_toml_dependencies, ensure_aivi_dependency, read_aivi_toml,
-    validate_publish_preflight, write_scaffold, AiviCargoMetadata, AiviToml, CargoDepSpec,
-    CargoDepSpecParseError, CargoManifestEdits, ProjectKind,
+    collect_aivi_sources, edit_cargo_toml_dependencies, ensure_aivi_dependency, hash_aivi_sources,
+    locked_cargo_source_args, read_aivi_lock, read_aivi_toml, resolve_aivi_dependencies,
+    update_aivi_lock, validate_publish_preflight, vendor_cargo_dependencies,
+    verify_locked_dependencies, write_aivi_lock, write_scaffold, AiviCargoMetadata, AiviLock,
+    AiviLockPackage, AiviToml, AiviTomlI18n, CargoDepSpec, CargoDepSpecParseError,
+    CargoManifestEdits, ProjectKind, ResolvedAiviDependency, AIVI_LOCK_FILE,
+    DEFAULT_CARGO_VENDOR_DIR, DEFAULT_VENDOR_DIR,
 };
 pub use resolver::check_modules;
 pub use runtime::{run_native, run_native_with_fuel, run_test_suite, TestFailure, TestReport};
@@ -56,9 +82,9 @@ pub use rustc_backend::{build_with_rustc, emit_rustc_source};
 pub use stdlib::{embedded_stdlib_modules, embedded_stdlib_source};
 pub use surface::{
     parse_modules, parse_modules_from_tokens, BlockItem, BlockKind, ClassDecl, Decorator, Def,
-    DomainDecl, DomainItem, Expr, InstanceDecl, ListItem, Literal, MatchArm, Module, ModuleItem,
-    PathSegment, Pattern, RecordField, RecordPatternField, SpannedName, TextPart, TypeAlias,
-    TypeCtor, TypeDecl, TypeExpr, TypeSig, UseDecl,
+    DomainDecl, DomainItem, EffectDecl, EffectOperation, Expr, InstanceDecl, ListItem, Literal,
+    MatchArm, Module, ModuleItem, PathSegment, Pattern, RecordField, RecordPatternField,
+    SpannedName, TextPart, TypeAlias, TypeCtor, TypeDecl, TypeExpr, TypeSig, UseDecl,
 };
 pub use typecheck::{check_types, elaborate_expected_coercions, infer_value_types};
 
@@ -192,6 +218,72 @@ pub fn desugar_target_typed(target: &str) -> Result<HirProgram, AiviError> {
     Ok(hir::desugar_modules(&stdlib_modules))
 }
 
+pub fn doc_target(target: &str, format: DocFormat) -> Result<Vec<DocPage>, AiviError> {
+    let diagnostics = load_module_diagnostics(target)?;
+    if file_diagnostics_have_errors(&diagnostics) {
+        return Err(AiviError::Diagnostics);
+    }
+    let paths = workspace::expand_target(target)?;
+    let mut sources = HashMap::new();
+    let mut modules = stdlib::embedded_stdlib_modules();
+    for path in paths {
+        let content = fs::read_to_string(&path)?;
+        let (mut file_modules, _) = parse_modules(&path, &content);
+        for module in &file_modules {
+            sources.insert(module.path.clone(), content.clone());
+        }
+        modules.append(&mut file_modules);
+    }
+    doc_gen::generate_docs(&modules, &sources, format)
+}
+
+/// Runs the `>>>` examples found in doc comments as `@test` definitions.
+pub fn run_doctests(target: &str) -> Result<TestReport, AiviError> {
+    let paths = workspace::expand_target(target)?;
+    let mut modules = Vec::new();
+    let mut doctest_modules = Vec::new();
+    let mut test_names = Vec::new();
+    for path in &paths {
+        let content = fs::read_to_string(path)?;
+        let (mut parsed, diagnostics) = parse_modules(path.as_path(), &content);
+        if file_diagnostics_have_errors(&diagnostics) {
+            return Err(AiviError::Diagnostics);
+        }
+        for module in &parsed {
+            let doctests = doc_gen::collect_doctests(module, &content);
+            if doctests.is_empty() {
+                continue;
+            }
+            let (source, mut names) = doc_gen::doctest_module_source(&module.name.name, &doctests);
+            let doctest_path = PathBuf::from(format!("<doctest:{}>", module.name.name));
+            let (mut generated, diagnostics) = parse_modules(&doctest_path, &source);
+            if let Some(diag) = diagnostics
+                .iter()
+                .find(|diag| diag.diagnostic.severity == DiagnosticSeverity::Error)
+            {
+                return Err(AiviError::InvalidCommand(format!(
+                    "invalid doctest in {}: {}",
+                    module.name.name, diag.diagnostic.message
+                )));
+            }
+            doctest_modules.append(&mut generated);
+            test_names.append(&mut names);
+        }
+        modules.append(&mut parsed);
+    }
+    if test_names.is_empty() {
+        return Ok(TestReport {
+            passed: 0,
+            failed: 0,
+            failures: Vec::new(),
+        });
+    }
+    let mut all_modules = stdlib::embedded_stdlib_modules();
+    all_modules.append(&mut modules);
+    all_modules.append(&mut doctest_modules);
+    run_test_suite(hir::desugar_modules(&all_modules), &test_names)
+}
+
 pub fn kernel_target(target: &str) -> Result<kernel::KernelProgram, AiviError> {
     let hir = desugar_target_typed(target)?;
     Ok(kernel::lower_hir(hir))
@@ -1,10 +1,10 @@
 use aivi::{
     check_modules, check_types, collect_mcp_manifest, compile_rust_native, compile_rust_native_lib,
-    desugar_target, embedded_stdlib_source, ensure_aivi_dependency,
-    format_target, kernel_target, load_module_diagnostics, load_modules, parse_target,
-    render_diagnostics, run_native,
-    rust_ir_target, serve_mcp_stdio_with_policy, validate_publish_preflight, write_scaffold,
-    AiviError, CargoDepSpec, McpPolicy, ProjectKind,
+    desugar_target, diagnostics_to_json, diagnostics_to_sarif, embedded_stdlib_source,
+    ensure_aivi_dependency, format_target, kernel_target, load_module_diagnostics, load_modules,
+    parse_target, render_diagnostics, render_diagnostics_short, run_native, rust_ir_target,
+    serve_mcp_stdio_with_policy, validate_publish_preflight, write_scaffold, AiviError,
+    CargoDepSpec, DiagnosticFilter, DiagnosticFormat, McpPolicy, ProjectKind,
 };
 use sha2::{Digest, Sha256};
 use std::env;
@@ -45,6 +45,7 @@ fn run() -> Result<(), AiviError> {
         "init" | "new" => cmd_init(&rest),
         "clean" => cmd_clean(&rest),
         "install" => cmd_install(&rest),
+        "vendor" => cmd_vendor(&rest),
         "search" => cmd_search(&rest),
         "package" => cmd_package(&rest),
         "publish" => cmd_publish(&rest),
@@ -79,13 +80,20 @@ fn run() -> Result<(), AiviError> {
         "check" => {
             let (debug_trace, rest) = consume_debug_trace_flag(&rest);
             let (check_stdlib, rest) = consume_check_stdlib_flag(&rest);
+            let (lock_mode, rest) = consume_lock_mode_flags(&rest);
+            let (output, rest) = consume_check_output_flags(&rest)?;
             maybe_enable_debug_trace(debug_trace);
             let Some(target) = rest.first() else {
                 print_help();
                 return Ok(());
             };
             let mut diagnostics = load_module_diagnostics(target)?;
-            let modules = load_modules(target)?;
+            let mut modules = load_modules(target)?;
+            if lock_mode.locked {
+                let (mut vendored, mut vendored_diags) = load_vendored_modules()?;
+                modules.append(&mut vendored);
+                diagnostics.append(&mut vendored_diags);
+            }
             diagnostics.extend(check_modules(&modules));
             if !aivi::file_diagnostics_have_errors(&diagnostics) {
                 diagnostics.extend(check_types(&modules));
@@ -93,13 +101,21 @@ fn run() -> Result<(), AiviError> {
             if !check_stdlib {
                 diagnostics.retain(|diag| !diag.path.starts_with("<embedded:"));
             }
+            let diagnostics = output.filter.apply(diagnostics);
             let has_errors = aivi::file_diagnostics_have_errors(&diagnostics);
-            for diag in &diagnostics {
-                let rendered =
-                    render_diagnostics(&diag.path, std::slice::from_ref(&diag.diagnostic));
-                if !rendered.is_empty() {
-                    eprintln!("{rendered}");
+            match output.format {
+                DiagnosticFormat::Human => {
+                    for diag in &diagnostics {
+                        let rendered =
+                            render_diagnostics(&diag.path, std::slice::from_ref(&diag.diagnostic));
+                        if !rendered.is_empty() {
+                            eprintln!("{rendered}");
+                        }
+                    }
                 }
+                DiagnosticFormat::Short => print!("{}", render_diagnostics_short(&diagnostics)),
+                DiagnosticFormat::Json => println!("{}", diagnostics_to_json(&diagnostics)),
+                DiagnosticFormat::Sarif => println!("{}", diagnostics_to_sarif(&diagnostics)),
             }
             if has_errors {
                 Err(AiviError::Diagnostics)
@@ -238,8 +254,10 @@ fn run() -> Result<(), AiviError> {
             }
             _ => Ok(()),
         },
+        "doc" => cmd_doc(&rest),
         "mcp" => cmd_mcp(&rest),
         "i18n" => cmd_i18n(&rest),
+        "explain" => cmd_explain(&rest),
         _ => {
             print_help();
             Err(AiviError::InvalidCommand(command))
@@ -308,7 +326,7 @@ Fix:\n\
 
 fn print_help() {
     println!(
-        "aivi\n\nUSAGE:\n  aivi <COMMAND>\n\nCOMMANDS:\n  init <name> [--bin|--lib] [--edition 2024] [--language-version 0.1] [--force]\n  new <name> ... (alias of init)\n  search <query>\n  install <spec> [--no-fetch]\n  package [--allow-dirty] [--no-verify] [-- <cargo args...>]\n  publish [--dry-run] [--allow-dirty] [--no-verify] [-- <cargo args...>]\n  build [--release] [-- <cargo args...>]\n  run [--release] [-- <cargo args...>]\n  clean [--all]\n\n  parse <path|dir/...>\n  check [--debug-trace] [--check-stdlib] <path|dir/...>\n  fmt <path>\n  desugar [--debug-trace] <path|dir/...>\n  kernel [--debug-trace] <path|dir/...>\n  rust-ir [--debug-trace] <path|dir/...>\n  lsp\n  build <path|dir/...> [--debug-trace] [--target rust|rust-native|rustc] [--out <dir|path>] [-- <rustc args...>]\n  run <path|dir/...> [--debug-trace] [--target native]\n  mcp serve <path|dir/...> [--allow-effects]\n  i18n gen <catalog.properties> --locale <tag> --module <name> --out <file>\n\n  -h, --help"
+        "aivi\n\nUSAGE:\n  aivi <COMMAND>\n\nCOMMANDS:\n  init <name> [--bin|--lib] [--edition 2024] [--language-version 0.1] [--force]\n  new <name> ... (alias of init)\n  search <query>\n  install <spec> [--no-fetch]\n  vendor [--dir <dir>] [--cargo-dir <dir>] [--offline]\n  package [--allow-dirty] [--no-verify] [-- <cargo args...>]\n  publish [--dry-run] [--allow-dirty] [--no-verify] [-- <cargo args...>]\n  build [--release] [--locked|--offline] [-- <cargo args...>]\n  run [--release] [--locked|--offline] [-- <cargo args...>]\n  clean [--all]\n\n  parse <path|dir/...>\n  check [--debug-trace] [--check-stdlib] [--locked|--offline] [--format human|short|json|sarif] [--deny <code|warnings>]... [--allow <code|warnings>]... <path|dir/...>\n  fmt <path>\n  desugar [--debug-trace] <path|dir/...>\n  kernel [--debug-trace] <path|dir/...>\n  rust-ir [--debug-trace] <path|dir/...>\n  doc <path|dir/...> [--format md|html] [--out <dir>] [--test]\n  lsp\n  build <path|dir/...> [--debug-trace] [--target rust|rust-native|rustc] [--out <dir|path>] [-- <rustc args...>]\n  run <path|dir/...> [--debug-trace] [--target native]\n  mcp serve <path|dir/...> [--allow-effects]\n  i18n gen <catalog.properties> --locale <tag> --module <name> --out <file>\n  i18n extract <path|dir/...> [--catalogs <dir>] [--source-locale <tag>] [--locale <tag>]...\n  i18n check <path|dir/...> [--catalogs <dir>] [--source-locale <tag>]\n  explain [--json] [<code>]\n\n  -h, --help"
     );
 }
 
@@ -341,6 +359,116 @@ fn cmd_mcp(args: &[String]) -> Result<(), AiviError> {
     }
 }
 
+fn cmd_doc(args: &[String]) -> Result<(), AiviError> {
+    let mut target = None;
+    let mut format = aivi::DocFormat::Markdown;
+    let mut out_dir = PathBuf::from("target/aivi-doc");
+    let mut run_tests = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match iter.next().map(String::as_str) {
+                    Some("md") | Some("markdown") => aivi::DocFormat::Markdown,
+                    Some("html") => aivi::DocFormat::Html,
+                    Some(other) => {
+                        return Err(AiviError::InvalidCommand(format!(
+                            "unsupported doc format {other}"
+                        )))
+                    }
+                    None => {
+                        return Err(AiviError::InvalidCommand(
+                            "--format expects a value".to_string(),
+                        ))
+                    }
+                };
+            }
+            "--out" => {
+                let Some(value) = iter.next() else {
+                    return Err(AiviError::InvalidCommand(
+                        "--out expects a value".to_string(),
+                    ));
+                };
+                out_dir = PathBuf::from(value);
+            }
+            "--test" => run_tests = true,
+            value if !value.starts_with('-') && target.is_none() => {
+                target = Some(value.to_string());
+            }
+            other => {
+                return Err(AiviError::InvalidCommand(format!(
+                    "unexpected doc argument {other}"
+                )));
+            }
+        }
+    }
+    let Some(target) = target else {
+        print_help();
+        return Ok(());
+    };
+
+    if run_tests {
+        let report = aivi::run_doctests(&target)?;
+        for failure in &report.failures {
+            eprintln!("FAIL {}: {}", failure.name, failure.message);
+        }
+        println!("doctests: {} passed, {} failed", report.passed, report.failed);
+        return if report.failed > 0 {
+            Err(AiviError::Diagnostics)
+        } else {
+            Ok(())
+        };
+    }
+
+    let pages = aivi::doc_target(&target, format)?;
+    aivi::write_doc_pages(&out_dir, &pages)?;
+    println!("{}", out_dir.display());
+    Ok(())
+}
+
+fn cmd_explain(args: &[String]) -> Result<(), AiviError> {
+    let mut json = false;
+    let mut code = None;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            value if !value.starts_with('-') && code.is_none() => code = Some(value),
+            other => {
+                return Err(AiviError::InvalidCommand(format!(
+                    "unexpected explain argument {other}"
+                )));
+            }
+        }
+    }
+
+    let Some(code) = code else {
+        if json {
+            let output = serde_json::to_string_pretty(aivi::ERROR_CODES)
+                .map_err(|err| AiviError::Io(std::io::Error::other(err)))?;
+            println!("{output}");
+        } else {
+            for info in aivi::ERROR_CODES {
+                println!("{}  {}", info.code, info.title);
+            }
+        }
+        return Ok(());
+    };
+    let Some(info) = aivi::error_code_info(code) else {
+        return Err(AiviError::InvalidCommand(format!(
+            "unknown error code {code} (run `aivi explain` to list every code)"
+        )));
+    };
+    if json {
+        let output = serde_json::to_string_pretty(info)
+            .map_err(|err| AiviError::Io(std::io::Error::other(err)))?;
+        println!("{output}");
+    } else {
+        print!("{}", info.render_text());
+    }
+    Ok(())
+}
+
 fn cmd_i18n(args: &[String]) -> Result<(), AiviError> {
     let Some(subcommand) = args.first() else {
         print_help();
@@ -348,6 +476,8 @@ fn cmd_i18n(args: &[String]) -> Result<(), AiviError> {
     };
     match subcommand.as_str() {
         "gen" => cmd_i18n_gen(&args[1..]),
+        "extract" => cmd_i18n_extract(&args[1..]),
+        "check" => cmd_i18n_check(&args[1..]),
         other => Err(AiviError::InvalidCommand(format!("i18n {other}"))),
     }
 }
@@ -416,6 +546,138 @@ fn cmd_i18n_gen(args: &[String]) -> Result<(), AiviError> {
     Ok(())
 }
 
+struct I18nArgs {
+    target: String,
+    catalogs: PathBuf,
+    source_locale: String,
+    locales: Vec<String>,
+}
+
+fn parse_i18n_args(command: &str, args: &[String]) -> Result<I18nArgs, AiviError> {
+    // Defaults come from the `[i18n]` section of `./aivi.toml`, when present.
+    let config = if Path::new("aivi.toml").is_file() {
+        aivi::read_aivi_toml(Path::new("aivi.toml"))?.i18n
+    } else {
+        aivi::AiviTomlI18n::default()
+    };
+    let mut target = None;
+    let mut catalogs = PathBuf::from(config.catalogs);
+    let mut source_locale = config.source_locale;
+    let mut locales = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next().cloned().ok_or_else(|| {
+                AiviError::InvalidCommand(format!("i18n {command} {flag} requires a value"))
+            })
+        };
+        match arg.as_str() {
+            "--catalogs" => catalogs = PathBuf::from(value("--catalogs")?),
+            "--source-locale" => source_locale = value("--source-locale")?,
+            "--locale" if command == "extract" => locales.push(value("--locale")?),
+            value if !value.starts_with('-') && target.is_none() => {
+                target = Some(value.to_string());
+            }
+            other => {
+                return Err(AiviError::InvalidCommand(format!(
+                    "unexpected i18n {command} argument {other}"
+                )));
+            }
+        }
+    }
+    let Some(target) = target else {
+        return Err(AiviError::InvalidCommand(format!(
+            "i18n {command} requires <path|dir/...>"
+        )));
+    };
+    Ok(I18nArgs {
+        target,
+        catalogs,
+        source_locale,
+        locales,
+    })
+}
+
+fn collect_i18n_target_usages(target: &str) -> Result<Vec<aivi::I18nKeyUsage>, AiviError> {
+    let mut usages = Vec::new();
+    for path in aivi::resolve_target(target)? {
+        let text = std::fs::read_to_string(&path)?;
+        usages.extend(aivi::collect_i18n_key_usages(
+            &path.to_string_lossy(),
+            &text,
+        ));
+    }
+    Ok(usages)
+}
+
+fn cmd_i18n_extract(args: &[String]) -> Result<(), AiviError> {
+    let args = parse_i18n_args("extract", args)?;
+    let usages = collect_i18n_target_usages(&args.target)?;
+
+    // Merge into every existing catalog plus the requested (and source) locales.
+    let mut locales = std::collections::BTreeSet::new();
+    if args.catalogs.is_dir() {
+        for entry in std::fs::read_dir(&args.catalogs)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "properties") {
+                if let Some(stem) = path.file_stem() {
+                    locales.insert(stem.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    locales.insert(args.source_locale.clone());
+    locales.extend(args.locales);
+
+    std::fs::create_dir_all(&args.catalogs)?;
+    for locale in locales {
+        let path = args.catalogs.join(format!("{locale}.properties"));
+        let exists = path.is_file();
+        let existing = if exists {
+            std::fs::read_to_string(&path)?
+        } else {
+            String::new()
+        };
+        let (merged, added) =
+            aivi::merge_i18n_catalog(&existing, &usages, locale == args.source_locale);
+        if added > 0 || !exists {
+            std::fs::write(&path, merged)?;
+        }
+        println!("{}: {added} key(s) added", path.display());
+    }
+    Ok(())
+}
+
+fn cmd_i18n_check(args: &[String]) -> Result<(), AiviError> {
+    let args = parse_i18n_args("check", args)?;
+    let usages = collect_i18n_target_usages(&args.target)?;
+    let (catalogs, mut diagnostics) = aivi::load_i18n_catalogs(&args.catalogs)?;
+    if catalogs.is_empty() && diagnostics.is_empty() {
+        return Err(AiviError::InvalidCommand(format!(
+            "no <locale>.properties catalogs found in {}",
+            args.catalogs.display()
+        )));
+    }
+    diagnostics.extend(aivi::check_i18n_catalogs(
+        &usages,
+        &catalogs,
+        &args.source_locale,
+    ));
+    for diag in &diagnostics {
+        let rendered = render_diagnostics(&diag.path, std::slice::from_ref(&diag.diagnostic));
+        if !rendered.is_empty() {
+            eprintln!("{rendered}");
+        }
+    }
+    // Warnings count too: the command exists to keep catalogs in sync.
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(AiviError::Diagnostics)
+    }
+}
+
 fn cmd_mcp_serve(target: &str, allow_effects: bool) -> Result<(), AiviError> {
     let mut diagnostics = load_module_diagnostics(target)?;
     let modules = load_modules(target)?;
@@ -547,6 +809,85 @@ fn consume_check_stdlib_flag(args: &[String]) -> (bool, Vec<String>) {
     (enabled, out)
 }
 
+/// How `aivi check` reports its diagnostics.
+#[derive(Debug, Clone, Default)]
+struct CheckOutput {
+    format: DiagnosticFormat,
+    filter: DiagnosticFilter,
+}
+
+fn consume_check_output_flags(args: &[String]) -> Result<(CheckOutput, Vec<String>), AiviError> {
+    let mut output = CheckOutput::default();
+    let mut out = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let flag = arg.as_str();
+        if !matches!(flag, "--format" | "--deny" | "--allow") {
+            out.push(arg.clone());
+            continue;
+        }
+        let Some(value) = iter.next() else {
+            return Err(AiviError::InvalidCommand(format!("{flag} expects a value")));
+        };
+        match flag {
+            "--format" => {
+                output.format = DiagnosticFormat::parse(value).ok_or_else(|| {
+                    AiviError::InvalidCommand(format!("unsupported diagnostics format {value}"))
+                })?;
+            }
+            "--deny" => output.filter.deny.push(value.clone()),
+            _ => output.filter.allow.push(value.clone()),
+        }
+    }
+    Ok((output, out))
+}
+
+/// `--locked` requires every dependency to be pinned and vendored in `aivi.lock`;
+/// `--offline` additionally forbids Cargo from touching the network.
+#[derive(Debug, Clone, Copy, Default)]
+struct LockMode {
+    locked: bool,
+    offline: bool,
+}
+
+fn consume_lock_mode_flags(args: &[String]) -> (LockMode, Vec<String>) {
+    let mut mode = LockMode::default();
+    let mut out = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--locked" => mode.locked = true,
+            "--offline" => {
+                mode.locked = true;
+                mode.offline = true;
+            }
+            _ => out.push(arg.clone()),
+        }
+    }
+    (mode, out)
+}
+
+fn load_vendored_modules() -> Result<(Vec<aivi::Module>, Vec<aivi::FileDiagnostic>), AiviError> {
+    let root = env::current_dir()?;
+    let language_version = if root.join("aivi.toml").exists() {
+        aivi::read_aivi_toml(&root.join("aivi.toml"))?
+            .project
+            .language_version
+    } else {
+        None
+    };
+    let mut modules = Vec::new();
+    let mut diagnostics = Vec::new();
+    for dir in aivi::verify_locked_dependencies(&root, language_version.as_deref())? {
+        for path in aivi::collect_aivi_sources(&dir)? {
+            let content = std::fs::read_to_string(&path)?;
+            let (mut parsed, mut file_diags) = aivi::parse_modules(&path, &content);
+            modules.append(&mut parsed);
+            diagnostics.append(&mut file_diags);
+        }
+    }
+    Ok((modules, diagnostics))
+}
+
 struct Spinner {
     stop: Arc<AtomicBool>,
     message: Arc<Mutex<String>>,
@@ -658,13 +999,20 @@ fn load_checked_modules_with_progress(target: &str) -> Result<Vec<aivi::Module>,
     Err(AiviError::Diagnostics)
 }
 
-fn load_checked_modules(target: &str) -> Result<Vec<aivi::Module>, AiviError> {
+fn load_checked_modules(
+    target: &str,
+    (mut extra, mut extra_diags): (Vec<aivi::Module>, Vec<aivi::FileDiagnostic>),
+) -> Result<Vec<aivi::Module>, AiviError> {
     let mut diagnostics = load_module_diagnostics(target)?;
-    let modules = load_modules(target)?;
+    let mut modules = load_modules(target)?;
+    modules.append(&mut extra);
+    diagnostics.append(&mut extra_diags);
     diagnostics.extend(check_modules(&modules));
     if !aivi::file_diagnostics_have_errors(&diagnostics) {
         diagnostics.extend(check_types(&modules));
     }
+    // Like `aivi check` without `--check-stdlib`: the embedded stdlib is not required to typecheck.
+    diagnostics.retain(|diag| !diag.path.starts_with("<embedded:"));
     if !aivi::file_diagnostics_have_errors(&diagnostics) {
         return Ok(modules);
     }
@@ -188,6 +188,94 @@ fn cmd_install(args: &[String]) -> Result<(), AiviError> {
         return Err(err);
     }
 
+    let aivi_lock_path = root.join(aivi::AIVI_LOCK_FILE);
+    let original_aivi_lock = std::fs::read_to_string(&aivi_lock_path).ok();
+    if let Err(err) =
+        aivi::update_aivi_lock(&root, cfg.project.language_version.as_deref(), None, false)
+    {
+        restore_install_manifest(
+            &cargo_toml_path,
+            &original,
+            &cargo_lock_path,
+            &original_lock,
+        );
+        restore_install_manifest_file(&aivi_lock_path, &original_aivi_lock);
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+fn restore_install_manifest_file(path: &Path, original: &Option<String>) {
+    match original {
+        Some(contents) => {
+            let _ = std::fs::write(path, contents);
+        }
+        None => {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn cmd_vendor(args: &[String]) -> Result<(), AiviError> {
+    let mut dir = aivi::DEFAULT_VENDOR_DIR.to_string();
+    let mut cargo_dir = aivi::DEFAULT_CARGO_VENDOR_DIR.to_string();
+    let mut offline = false;
+
+    let mut iter = args.iter().cloned();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--dir" => {
+                let Some(value) = iter.next() else {
+                    return Err(AiviError::InvalidCommand(
+                        "--dir expects a value".to_string(),
+                    ));
+                };
+                dir = value;
+            }
+            "--cargo-dir" => {
+                let Some(value) = iter.next() else {
+                    return Err(AiviError::InvalidCommand(
+                        "--cargo-dir expects a value".to_string(),
+                    ));
+                };
+                cargo_dir = value;
+            }
+            "--offline" => offline = true,
+            _ if arg.starts_with('-') => {
+                return Err(AiviError::InvalidCommand(format!("unknown flag {arg}")))
+            }
+            _ => {
+                return Err(AiviError::InvalidCommand(format!(
+                    "unexpected argument {arg}"
+                )))
+            }
+        }
+    }
+
+    let root = env::current_dir()?;
+    if !root.join("aivi.toml").exists() || !root.join("Cargo.toml").exists() {
+        return Err(AiviError::Config(
+            "vendor expects a directory containing aivi.toml and Cargo.toml".to_string(),
+        ));
+    }
+    let cfg = aivi::read_aivi_toml(&root.join("aivi.toml"))?;
+    let lock = aivi::update_aivi_lock(
+        &root,
+        cfg.project.language_version.as_deref(),
+        Some(Path::new(&dir)),
+        offline,
+    )?;
+    for pkg in &lock.packages {
+        println!(
+            "vendored {} {} -> {}",
+            pkg.name,
+            pkg.version,
+            pkg.vendored.as_deref().unwrap_or("")
+        );
+    }
+    aivi::vendor_cargo_dependencies(&root, Path::new(&cargo_dir), offline)?;
+    println!("vendored crates -> {cargo_dir}");
     Ok(())
 }
 
@@ -198,14 +286,7 @@ fn restore_install_manifest(
     original_lock: &Option<String>,
 ) {
     let _ = std::fs::write(cargo_toml_path, original);
-    match original_lock {
-        Some(contents) => {
-            let _ = std::fs::write(cargo_lock_path, contents);
-        }
-        None => {
-            let _ = std::fs::remove_file(cargo_lock_path);
-        }
-    }
+    restore_install_manifest_file(cargo_lock_path, original_lock);
 }
 
 fn cmd_package(args: &[String]) -> Result<(), AiviError> {
@@ -350,15 +431,27 @@ fn should_use_project_pipeline(args: &[String]) -> bool {
 fn cmd_project_build(args: &[String]) -> Result<(), AiviError> {
     let root = env::current_dir()?;
     let cfg = aivi::read_aivi_toml(&root.join("aivi.toml"))?;
-    let (release_flag, cargo_args) = parse_project_args(args)?;
-    let release = release_flag || cfg.build.cargo_profile == "release";
-    generate_project_rust(&root, &cfg)?;
+    let opts = parse_project_args(args)?;
+    let release = opts.release || cfg.build.cargo_profile == "release";
+    let vendored = if opts.lock_mode.locked {
+        load_vendored_modules()?
+    } else {
+        Default::default()
+    };
+    generate_project_rust(&root, &cfg, vendored)?;
     let mut cmd = Command::new("cargo");
     cmd.arg("build");
     if release {
         cmd.arg("--release");
     }
-    cmd.args(cargo_args);
+    if opts.lock_mode.locked {
+        cmd.arg("--locked");
+        cmd.args(aivi::locked_cargo_source_args(&root)?);
+    }
+    if opts.lock_mode.offline {
+        cmd.arg("--offline");
+    }
+    cmd.args(opts.cargo_args);
     let status = cmd.current_dir(&root).status()?;
     if !status.success() {
         return Err(AiviError::Cargo("cargo build failed".to_string()));
@@ -369,15 +462,27 @@ fn cmd_project_build(args: &[String]) -> Result<(), AiviError> {
 fn cmd_project_run(args: &[String]) -> Result<(), AiviError> {
     let root = env::current_dir()?;
     let cfg = aivi::read_aivi_toml(&root.join("aivi.toml"))?;
-    let (release_flag, cargo_args) = parse_project_args(args)?;
-    let release = release_flag || cfg.build.cargo_profile == "release";
-    generate_project_rust(&root, &cfg)?;
+    let opts = parse_project_args(args)?;
+    let release = opts.release || cfg.build.cargo_profile == "release";
+    let vendored = if opts.lock_mode.locked {
+        load_vendored_modules()?
+    } else {
+        Default::default()
+    };
+    generate_project_rust(&root, &cfg, vendored)?;
     let mut cmd = Command::new("cargo");
     cmd.arg("run");
     if release {
         cmd.arg("--release");
     }
-    cmd.args(cargo_args);
+    if opts.lock_mode.locked {
+        cmd.arg("--locked");
+        cmd.args(aivi::locked_cargo_source_args(&root)?);
+    }
+    if opts.lock_mode.offline {
+        cmd.arg("--offline");
+    }
+    cmd.args(opts.cargo_args);
     let status = cmd.current_dir(&root).status()?;
     if !status.success() {
         return Err(AiviError::Cargo("cargo run failed".to_string()));
@@ -385,7 +490,13 @@ fn cmd_project_run(args: &[String]) -> Result<(), AiviError> {
     Ok(())
 }
 
-fn parse_project_args(args: &[String]) -> Result<(bool, Vec<String>), AiviError> {
+struct ProjectArgs {
+    release: bool,
+    lock_mode: LockMode,
+    cargo_args: Vec<String>,
+}
+
+fn parse_project_args(args: &[String]) -> Result<ProjectArgs, AiviError> {
     let mut before = Vec::new();
     let mut after = Vec::new();
     let mut saw_sep = false;
@@ -401,6 +512,7 @@ fn parse_project_args(args: &[String]) -> Result<(bool, Vec<String>), AiviError>
         }
     }
 
+    let (lock_mode, before) = consume_lock_mode_flags(&before);
     let mut release = false;
     for arg in before {
         match arg.as_str() {
@@ -409,10 +521,20 @@ fn parse_project_args(args: &[String]) -> Result<(bool, Vec<String>), AiviError>
         }
     }
 
-    Ok((release, after))
+    Ok(ProjectArgs {
+        release,
+        lock_mode,
+        cargo_args: after,
+    })
 }
 
-fn generate_project_rust(project_root: &Path, cfg: &aivi::AiviToml) -> Result<(), AiviError> {
+/// Checks the project entry together with `vendored` (the `--locked` dependency modules) and
+/// writes the generated Rust crate.
+fn generate_project_rust(
+    project_root: &Path,
+    cfg: &aivi::AiviToml,
+    vendored: (Vec<aivi::Module>, Vec<aivi::FileDiagnostic>),
+) -> Result<(), AiviError> {
     let aivi_toml_path = project_root.join("aivi.toml");
     let cargo_toml_path = project_root.join("Cargo.toml");
     if !aivi_toml_path.exists() || !cargo_toml_path.exists() {
@@ -426,8 +548,14 @@ fn generate_project_rust(project_root: &Path, cfg: &aivi::AiviToml) -> Result<()
         .to_str()
         .ok_or_else(|| AiviError::InvalidPath(entry_path.display().to_string()))?;
 
-    let _modules = load_checked_modules(entry_str)?;
-    let program = aivi::desugar_target_typed(entry_str)?;
+    let mut modules = load_checked_modules(entry_str, vendored)?;
+    // Same gate as `desugar_target_typed`: coercions are only elaborated for resolver-clean input.
+    if check_modules(&modules).is_empty()
+        && aivi::file_diagnostics_have_errors(&aivi::elaborate_expected_coercions(&mut modules))
+    {
+        return Err(AiviError::Diagnostics);
+    }
+    let program = aivi::desugar_modules(&modules);
 
     let gen_dir = project_root.join(&cfg.build.gen_dir);
     let src_out = gen_dir.join("src");
@@ -314,12 +314,17 @@ fn schema_for_type(expr: &TypeExpr) -> serde_json::Value {
                 _ => schema_unknown(),
             }
         }
-        TypeExpr::Record { fields, .. } => {
+        TypeExpr::Record { fields, rest, .. } => {
             let map: BTreeMap<String, TypeExpr> = fields
                 .iter()
                 .map(|(name, ty)| (name.name.clone(), ty.clone()))
                 .collect();
-            schema_for_record_map(&map)
+            let mut schema = schema_for_record_map(&map);
+            // A row variable (`{ name: Text | r }`) admits any further fields.
+            if rest.is_some() {
+                schema["additionalProperties"] = serde_json::Value::Bool(true);
+            }
+            schema
         }
         TypeExpr::Tuple { items, .. } => {
             let prefix: Vec<serde_json::Value> = items.iter().map(schema_for_type).collect();
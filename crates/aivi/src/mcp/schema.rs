@@ -18,7 +18,7 @@ fn tool_input_schema(sig: Option<&TypeSig>, def: Option<&Def>) -> serde_json::Va
         return serde_json::json!({ "type": "object" });
     }
 
-    fn def_param_patterns<'a>(def: &'a Def) -> Vec<&'a Pattern> {
+    fn def_param_patterns(def: &Def) -> Vec<&Pattern> {
         if !def.params.is_empty() {
             return def.params.iter().collect();
         }
@@ -119,6 +119,7 @@ fn expr_is_effectful(expr: &Expr) -> bool {
             expr_is_effectful(func) || args.iter().any(expr_is_effectful)
         }
         Expr::Lambda { body, .. } => expr_is_effectful(body),
+        Expr::Handle { .. } => true,
         Expr::Match {
             scrutinee, arms, ..
         } => {
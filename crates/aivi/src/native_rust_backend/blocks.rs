@@ -1,4 +1,4 @@
-use crate::rust_ir::{RustIrBlockItem, RustIrBlockKind};
+use crate::rust_ir::{RustIrBlockItem, RustIrBlockKind, RustIrRecBinding};
 use crate::AiviError;
 
 use super::expr::emit_expr;
@@ -41,6 +41,13 @@ pub(super) fn emit_block(
                             s.push_str("aivi_ok(Value::Unit)\n");
                         }
                     }
+                    RustIrBlockItem::LetRec { bindings } => {
+                        s.push_str(&emit_let_rec(bindings, indent + 1, &mut tmp_id)?);
+                        if last {
+                            s.push_str(&ind2);
+                            s.push_str("aivi_ok(Value::Unit)\n");
+                        }
+                    }
                     RustIrBlockItem::Expr { expr } => {
                         let expr_code = emit_expr(expr, indent + 1)?;
                         s.push_str(&ind2);
@@ -119,6 +126,13 @@ pub(super) fn emit_block(
                             s.push_str("Ok(Value::Unit)\n");
                         }
                     }
+                    RustIrBlockItem::LetRec { bindings } => {
+                        s.push_str(&emit_let_rec(bindings, indent + 4, &mut tmp_id)?);
+                        if last {
+                            s.push_str(&"    ".repeat(indent + 4));
+                            s.push_str("Ok(Value::Unit)\n");
+                        }
+                    }
                     RustIrBlockItem::Expr { expr } => {
                         let expr_code = emit_expr(expr, indent + 3)?;
                         s.push_str(&"    ".repeat(indent + 4));
@@ -349,6 +363,15 @@ fn emit_generate_materialize_items(
             s.push_str(&ind);
             s.push_str("Ok(())\n");
         }
+        RustIrBlockItem::LetRec { bindings } => {
+            s.push_str(&emit_let_rec(bindings, indent, tmp_id)?);
+            s.push_str(&emit_generate_materialize_items(
+                &items[1..],
+                out_ident,
+                indent,
+                tmp_id,
+            )?);
+        }
         RustIrBlockItem::Recurse { .. } => {
             // Unsupported for now.
             s.push_str(&emit_generate_materialize_items(
@@ -430,6 +453,9 @@ fn emit_resource_acquire(
                 s.push_str(&ind);
                 s.push_str("}\n");
             }
+            RustIrBlockItem::LetRec { bindings } => {
+                s.push_str(&emit_let_rec(bindings, indent, tmp_id)?);
+            }
             RustIrBlockItem::Yield { expr } => {
                 let expr_code = emit_expr(expr, indent)?;
                 s.push_str(&ind);
@@ -498,6 +524,9 @@ fn emit_resource_cleanup(
                 s.push_str(&ind);
                 s.push_str("}\n");
             }
+            RustIrBlockItem::LetRec { bindings } => {
+                s.push_str(&emit_let_rec(bindings, indent, tmp_id)?);
+            }
             RustIrBlockItem::Yield { .. } => {
                 return Err(AiviError::Codegen(
                     "yield is not supported in resource cleanup".to_string(),
@@ -514,3 +543,49 @@ fn emit_resource_cleanup(
     s.push_str("Ok(Value::Unit)\n");
     Ok(s)
 }
+
+/// Emits a recursive group as statements. Every name is first bound to a forwarder that looks the
+/// real closure up in a shared slot, so the bodies can capture the group before it is evaluated.
+fn emit_let_rec(
+    bindings: &[RustIrRecBinding],
+    indent: usize,
+    tmp_id: &mut usize,
+) -> Result<String, AiviError> {
+    let ind = "    ".repeat(indent);
+    let ind2 = "    ".repeat(indent + 1);
+    let slot = format!("__rec{tmp_id}");
+    let values = format!("__rec_vals{tmp_id}");
+    *tmp_id += 1;
+    let mut s = String::new();
+    s.push_str(&ind);
+    s.push_str(&format!(
+        "let {slot}: Arc<std::sync::OnceLock<Vec<Value>>> = Arc::new(std::sync::OnceLock::new());\n"
+    ));
+    for (i, binding) in bindings.iter().enumerate() {
+        let rust_name = rust_local_name(&binding.name);
+        s.push_str(&ind);
+        s.push_str(&format!("let {rust_name} = {{\n"));
+        s.push_str(&ind2);
+        s.push_str(&format!("let {slot} = {slot}.clone();\n"));
+        s.push_str(&ind2);
+        s.push_str(&format!(
+            "Value::Closure(Arc::new(aivi_native_runtime::ClosureValue {{ func: Arc::new(move |arg: Value, rt: &mut Runtime| match {slot}.get() {{ Some(values) => rt.apply(values[{i}].clone(), arg), None => Err(RuntimeError::Message(\"recursive binding used before its definition\".to_string())) }}) }}))\n"
+        ));
+        s.push_str(&ind);
+        s.push_str("};\n");
+    }
+    let mut codes = Vec::new();
+    for binding in bindings {
+        codes.push(format!("({})?", emit_expr(&binding.expr, indent)?));
+    }
+    s.push_str(&ind);
+    s.push_str(&format!("let {values} = vec![{}];\n", codes.join(", ")));
+    s.push_str(&ind);
+    s.push_str(&format!("let _ = {slot}.set({values}.clone());\n"));
+    for (i, binding) in bindings.iter().enumerate() {
+        let rust_name = rust_local_name(&binding.name);
+        s.push_str(&ind);
+        s.push_str(&format!("let {rust_name} = {values}[{i}].clone();\n"));
+    }
+    Ok(s)
+}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::i18n::{parse_message_template, validate_key_text, MessagePart};
+use crate::i18n::{parse_message_template, validate_key_text, MessageArm, MessagePart};
 use crate::rust_ir::{RustIrExpr, RustIrPathSegment, RustIrRecordField};
 use crate::AiviError;
 
@@ -105,13 +105,15 @@ pub(super) fn emit_expr(expr: &RustIrExpr, indent: usize) -> Result<String, Aivi
             let body_code = emit_expr(body, indent + 1)?;
             let ind = "    ".repeat(indent);
             let ind2 = "    ".repeat(indent + 1);
+            // Captures are cloned before the `move` so sibling closures can share a local, and
+            // again per call so the closure stays `Fn`.
             let mut capture_lines = String::new();
             for name in captured {
                 let rust_name = rust_local_name(&name);
                 capture_lines.push_str(&format!("{ind2}let {rust_name} = {rust_name}.clone();\n"));
             }
             format!(
-                "aivi_ok(Value::Closure(Arc::new(aivi_native_runtime::ClosureValue {{ func: Arc::new(move |{param_name}: Value, rt: &mut Runtime| {{\n{capture_lines}{ind2}{body_code}\n{ind}}}) }})))"
+                "{{\n{capture_lines}{ind2}aivi_ok(Value::Closure(Arc::new(aivi_native_runtime::ClosureValue {{ func: Arc::new(move |{param_name}: Value, rt: &mut Runtime| {{\n{capture_lines}{ind2}{body_code}\n{ind}}}) }})))\n{ind}}}"
             )
         }
         RustIrExpr::App { func, arg, .. } => {
@@ -282,59 +284,100 @@ pub(super) fn emit_expr(expr: &RustIrExpr, indent: usize) -> Result<String, Aivi
 fn emit_i18n_message_parts(parts: &[MessagePart], indent: usize) -> String {
     let ind = "    ".repeat(indent);
     let ind2 = "    ".repeat(indent + 1);
-    let ind3 = "    ".repeat(indent + 2);
 
     let mut out = String::new();
-    out.push_str("{\n");
-    out.push_str(&ind2);
-    out.push_str("let mut items: Vec<Value> = Vec::new();\n");
+    out.push_str("Value::List(Arc::new(vec![\n");
     for part in parts {
-        match part {
-            MessagePart::Lit(text) => {
-                out.push_str(&ind2);
-                out.push_str("items.push(Value::Record(Arc::new(HashMap::from([\n");
-                out.push_str(&ind3);
-                out.push_str(&format!(
-                    "(\"kind\".to_string(), Value::Text({:?}.to_string())),\n",
-                    "lit"
-                ));
-                out.push_str(&ind3);
-                out.push_str(&format!(
-                    "(\"text\".to_string(), Value::Text({text:?}.to_string())),\n"
-                ));
-                out.push_str(&ind2);
-                out.push_str("]))));\n");
-            }
-            MessagePart::Hole { name, ty } => {
-                let ty_code = match ty {
-                    Some(t) => format!(
-                        "Value::Constructor {{ name: \"Some\".to_string(), args: vec![Value::Text({t:?}.to_string())] }}"
-                    ),
-                    None => "Value::Constructor { name: \"None\".to_string(), args: Vec::new() }"
-                        .to_string(),
-                };
-                out.push_str(&ind2);
-                out.push_str("items.push(Value::Record(Arc::new(HashMap::from([\n");
-                out.push_str(&ind3);
-                out.push_str(&format!(
-                    "(\"kind\".to_string(), Value::Text({:?}.to_string())),\n",
-                    "hole"
-                ));
-                out.push_str(&ind3);
-                out.push_str(&format!(
-                    "(\"name\".to_string(), Value::Text({name:?}.to_string())),\n"
-                ));
-                out.push_str(&ind3);
-                out.push_str(&format!("(\"ty\".to_string(), {ty_code}),\n"));
-                out.push_str(&ind2);
-                out.push_str("]))));\n");
-            }
+        out.push_str(&ind2);
+        out.push_str(&emit_i18n_message_part(part, indent + 1));
+        out.push_str(",\n");
+    }
+    out.push_str(&ind);
+    out.push_str("]))");
+    out
+}
+
+fn emit_i18n_message_part(part: &MessagePart, indent: usize) -> String {
+    let text = |value: &str| format!("Value::Text({value:?}.to_string())");
+    let ty_code = |ty: &Option<String>| {
+        match ty {
+        Some(t) => format!(
+            "Value::Constructor {{ name: \"Some\".to_string(), args: vec![Value::Text({t:?}.to_string())] }}"
+        ),
+        None => "Value::Constructor { name: \"None\".to_string(), args: Vec::new() }".to_string(),
+    }
+    };
+    let arms_code = |arms: &[MessageArm]| {
+        let ind = "    ".repeat(indent + 1);
+        let ind2 = "    ".repeat(indent + 2);
+        let mut out = String::from("Value::List(Arc::new(vec![\n");
+        for arm in arms {
+            out.push_str(&ind2);
+            out.push_str(&emit_i18n_record(
+                &[
+                    ("selector", text(&arm.selector)),
+                    ("parts", emit_i18n_message_parts(&arm.parts, indent + 3)),
+                ],
+                indent + 2,
+            ));
+            out.push_str(",\n");
         }
+        out.push_str(&ind);
+        out.push_str("]))");
+        out
+    };
+    let fields = match part {
+        MessagePart::Lit(value) => vec![("kind", text("lit")), ("text", text(value))],
+        MessagePart::Hole { name, ty } => vec![
+            ("kind", text("hole")),
+            ("name", text(name)),
+            ("ty", ty_code(ty)),
+        ],
+        MessagePart::Pound => vec![("kind", text("pound"))],
+        MessagePart::Plural {
+            name,
+            ty,
+            ordinal,
+            arms,
+        } => vec![
+            ("kind", text("plural")),
+            ("name", text(name)),
+            ("ty", ty_code(ty)),
+            ("ordinal", format!("Value::Bool({ordinal})")),
+            ("arms", arms_code(arms)),
+        ],
+        MessagePart::Select { name, ty, arms } => vec![
+            ("kind", text("select")),
+            ("name", text(name)),
+            ("ty", ty_code(ty)),
+            ("arms", arms_code(arms)),
+        ],
+        MessagePart::Format {
+            name,
+            ty,
+            format,
+            style,
+        } => vec![
+            ("kind", text("format")),
+            ("name", text(name)),
+            ("ty", ty_code(ty)),
+            ("format", text(format)),
+            ("style", ty_code(style)),
+        ],
+    };
+    emit_i18n_record(&fields, indent)
+}
+
+fn emit_i18n_record(fields: &[(&str, String)], indent: usize) -> String {
+    let ind = "    ".repeat(indent);
+    let ind2 = "    ".repeat(indent + 1);
+    let mut out = String::from("Value::Record(Arc::new(HashMap::from([\n");
+    for (key, value) in fields {
+        out.push_str(&ind2);
+        out.push_str(&format!("({key:?}.to_string(), {value}),\n"));
     }
-    out.push_str(&ind2);
-    out.push_str("Value::List(Arc::new(items))\n");
     out.push_str(&ind);
-    out.push('}');
+    out.push_str("])))");
     out
 }
 
@@ -53,6 +53,12 @@ pub(super) fn collect_free_locals_in_items(items: &[RustIrBlockItem]) -> Vec<Str
             | RustIrBlockItem::Expr { expr } => {
                 collect_free_locals_in_expr(expr, &mut bound, &mut out);
             }
+            RustIrBlockItem::LetRec { bindings } => {
+                bound.extend(bindings.iter().map(|binding| binding.name.clone()));
+                for binding in bindings {
+                    collect_free_locals_in_expr(&binding.expr, &mut bound, &mut out);
+                }
+            }
         }
     }
 
@@ -196,6 +202,12 @@ pub(super) fn collect_free_locals_in_expr(
                     | RustIrBlockItem::Expr { expr } => {
                         collect_free_locals_in_expr(expr, bound, out);
                     }
+                    RustIrBlockItem::LetRec { bindings } => {
+                        bound.extend(bindings.iter().map(|binding| binding.name.clone()));
+                        for binding in bindings {
+                            collect_free_locals_in_expr(&binding.expr, bound, out);
+                        }
+                    }
                 }
             }
             bound.truncate(before);
@@ -1,5 +1,6 @@
 use crate::AiviError;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use toml_edit::{value, DocumentMut, Item, Table};
@@ -16,6 +17,8 @@ pub struct AiviToml {
     pub project: AiviTomlProject,
     #[serde(default)]
     pub build: AiviTomlBuild,
+    #[serde(default)]
+    pub i18n: AiviTomlI18n,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -46,6 +49,31 @@ impl Default for AiviTomlBuild {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiviTomlI18n {
+    #[serde(default = "default_i18n_catalogs")]
+    pub catalogs: String,
+    #[serde(default = "default_i18n_source_locale")]
+    pub source_locale: String,
+}
+
+impl Default for AiviTomlI18n {
+    fn default() -> Self {
+        Self {
+            catalogs: default_i18n_catalogs(),
+            source_locale: default_i18n_source_locale(),
+        }
+    }
+}
+
+fn default_i18n_catalogs() -> String {
+    "i18n".to_string()
+}
+
+fn default_i18n_source_locale() -> String {
+    "en".to_string()
+}
+
 fn default_gen_dir() -> String {
     "target/aivi-gen".to_string()
 }
@@ -624,3 +652,360 @@ fn expected_cargo_entry_for_project(entry: &str) -> String {
         entry.to_string()
     }
 }
+
+pub const AIVI_LOCK_FILE: &str = "aivi.lock";
+pub const DEFAULT_VENDOR_DIR: &str = "vendor/aivi";
+pub const DEFAULT_CARGO_VENDOR_DIR: &str = "vendor/cargo";
+const AIVI_LOCK_VERSION: u32 = 1;
+
+/// Contents of `aivi.lock`: the project's language version plus one entry per AIVI dependency,
+/// pinned by a content hash of its `.aivi` sources.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AiviLock {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_version: Option<String>,
+    /// Directory holding the `cargo vendor` copy of the project's registry and git crates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cargo_vendored: Option<String>,
+    #[serde(default, rename = "package")]
+    pub packages: Vec<AiviLockPackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AiviLockPackage {
+    pub name: String,
+    pub version: String,
+    pub language_version: String,
+    pub source: String,
+    pub sources_sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendored: Option<String>,
+}
+
+/// An AIVI library dependency as reported by `cargo metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedAiviDependency {
+    pub name: String,
+    pub version: String,
+    pub language_version: String,
+    pub source: String,
+    pub src_dir: PathBuf,
+}
+
+pub fn read_aivi_lock(path: &Path) -> Result<AiviLock, AiviError> {
+    let text = std::fs::read_to_string(path)?;
+    toml::from_str(&text)
+        .map_err(|err| AiviError::Config(format!("failed to parse {}: {err}", path.display())))
+}
+
+pub fn write_aivi_lock(path: &Path, lock: &AiviLock) -> Result<(), AiviError> {
+    let body = toml::to_string_pretty(lock)
+        .map_err(|err| AiviError::Config(format!("failed to serialize aivi.lock: {err}")))?;
+    std::fs::write(
+        path,
+        format!("# This file is generated by `aivi install` / `aivi vendor`. Do not edit.\n{body}"),
+    )?;
+    Ok(())
+}
+
+/// Hashes every `.aivi` file below `src_dir` (relative path and contents, in sorted order).
+pub fn hash_aivi_sources(src_dir: &Path) -> Result<String, AiviError> {
+    let mut hasher = Sha256::new();
+    for path in collect_aivi_sources(src_dir)? {
+        let rel = path.strip_prefix(src_dir).unwrap_or(&path);
+        hasher.update(rel.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0u8]);
+        hasher.update(std::fs::read(&path)?);
+        hasher.update([0u8]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+pub fn resolve_aivi_dependencies(
+    root: &Path,
+    offline: bool,
+) -> Result<Vec<ResolvedAiviDependency>, AiviError> {
+    let mut cmd = std::process::Command::new("cargo");
+    cmd.arg("metadata").arg("--format-version").arg("1");
+    if offline {
+        cmd.arg("--offline");
+    }
+    let output = cmd.current_dir(root).output()?;
+    if !output.status.success() {
+        return Err(AiviError::Cargo(format!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    #[derive(serde::Deserialize)]
+    struct CargoMetadata {
+        packages: Vec<CargoMetadataPackage>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct CargoMetadataPackage {
+        name: String,
+        version: String,
+        source: Option<String>,
+        manifest_path: String,
+        metadata: serde_json::Value,
+    }
+
+    let metadata: CargoMetadata = serde_json::from_slice(&output.stdout)
+        .map_err(|err| AiviError::Cargo(format!("failed to parse cargo metadata: {err}")))?;
+
+    let root_manifest = root.join("Cargo.toml").canonicalize().ok();
+    let mut deps = Vec::new();
+    for pkg in metadata.packages {
+        let manifest_path = PathBuf::from(&pkg.manifest_path);
+        if root_manifest.is_some() && manifest_path.canonicalize().ok() == root_manifest {
+            continue;
+        }
+        let Some(aivi) = parse_aivi_cargo_metadata(&pkg.metadata) else {
+            continue;
+        };
+        if aivi.kind != ProjectKind::Lib {
+            continue;
+        }
+        let pkg_dir = manifest_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        let src_dir = aivi
+            .entry
+            .as_deref()
+            .and_then(|entry| Path::new(entry).parent())
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map(|parent| pkg_dir.join(parent))
+            .unwrap_or_else(|| pkg_dir.join("src"));
+        // Path dependencies are recorded relative to the project, so the lock holds on any checkout.
+        let source = pkg
+            .source
+            .unwrap_or_else(|| format!("path+{}", relative_lock_path(root, &pkg_dir)));
+        deps.push(ResolvedAiviDependency {
+            name: pkg.name,
+            version: pkg.version,
+            language_version: aivi.language_version,
+            source,
+            src_dir,
+        });
+    }
+    deps.sort_by(|a, b| a.name.cmp(&b.name).then(a.version.cmp(&b.version)));
+    Ok(deps)
+}
+
+/// Resolves the project's AIVI dependencies and rewrites `aivi.lock`.
+///
+/// When `vendor_dir` is set, dependency sources are copied there first and the lock records the
+/// vendored location, so `--locked`/`--offline` builds never need to consult Cargo.
+pub fn update_aivi_lock(
+    root: &Path,
+    language_version: Option<&str>,
+    vendor_dir: Option<&Path>,
+    offline: bool,
+) -> Result<AiviLock, AiviError> {
+    let deps = resolve_aivi_dependencies(root, offline)?;
+    let lock_path = root.join(AIVI_LOCK_FILE);
+    let previous = if lock_path.exists() {
+        read_aivi_lock(&lock_path).ok()
+    } else {
+        None
+    };
+    let mut packages = Vec::new();
+    for dep in deps {
+        let sources_sha256 = hash_aivi_sources(&dep.src_dir)?;
+        let vendored = match vendor_dir {
+            Some(vendor_dir) => {
+                let rel = vendor_dir.join(&dep.name);
+                let out = root.join(&rel);
+                if out.exists() {
+                    std::fs::remove_dir_all(&out)?;
+                }
+                for path in collect_aivi_sources(&dep.src_dir)? {
+                    let target = out.join(path.strip_prefix(&dep.src_dir).unwrap_or(&path));
+                    if let Some(parent) = target.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::copy(&path, &target)?;
+                }
+                std::fs::create_dir_all(&out)?;
+                Some(normalize_lock_path(&rel))
+            }
+            // Keep an existing vendored copy as long as it still matches the resolved sources.
+            None => previous.as_ref().and_then(|lock| {
+                lock.packages
+                    .iter()
+                    .find(|pkg| pkg.name == dep.name && pkg.sources_sha256 == sources_sha256)
+                    .and_then(|pkg| pkg.vendored.clone())
+                    .filter(|vendored| root.join(vendored).is_dir())
+            }),
+        };
+        packages.push(AiviLockPackage {
+            name: dep.name,
+            version: dep.version,
+            language_version: dep.language_version,
+            source: dep.source,
+            sources_sha256,
+            vendored,
+        });
+    }
+
+    let cargo_vendored = previous
+        .and_then(|lock| lock.cargo_vendored)
+        .filter(|dir| root.join(dir).is_dir());
+    let lock = AiviLock {
+        version: AIVI_LOCK_VERSION,
+        language_version: language_version.map(|v| v.to_string()),
+        cargo_vendored,
+        packages,
+    };
+    write_aivi_lock(&lock_path, &lock)?;
+    Ok(lock)
+}
+
+/// Runs `cargo vendor` into `dir` and records it in `aivi.lock`, so `--locked` builds can replace
+/// crates.io (and git sources) with the vendored copy.
+pub fn vendor_cargo_dependencies(root: &Path, dir: &Path, offline: bool) -> Result<(), AiviError> {
+    let mut cmd = std::process::Command::new("cargo");
+    cmd.arg("vendor").arg("--versioned-dirs").arg("--quiet");
+    if offline {
+        cmd.arg("--offline");
+    }
+    let output = cmd.arg(dir).current_dir(root).output()?;
+    if !output.status.success() {
+        return Err(AiviError::Cargo(format!(
+            "cargo vendor failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    // `cargo vendor` skips the directory when there is nothing to vendor; source replacement
+    // still needs it to exist.
+    std::fs::create_dir_all(root.join(dir))?;
+
+    let lock_path = root.join(AIVI_LOCK_FILE);
+    let mut lock = read_aivi_lock(&lock_path)?;
+    lock.cargo_vendored = Some(normalize_lock_path(dir));
+    write_aivi_lock(&lock_path, &lock)
+}
+
+/// Cargo `--config` arguments that replace crates.io with the directory recorded in `aivi.lock`.
+///
+/// Returns no arguments when the project never vendored its crates.
+pub fn locked_cargo_source_args(root: &Path) -> Result<Vec<String>, AiviError> {
+    let lock = read_aivi_lock(&root.join(AIVI_LOCK_FILE))?;
+    let Some(vendored) = lock.cargo_vendored else {
+        return Ok(Vec::new());
+    };
+    let dir = root.join(&vendored);
+    if !dir.is_dir() {
+        return Err(AiviError::Config(format!(
+            "vendored crates are missing at {} (run `aivi vendor`)",
+            dir.display()
+        )));
+    }
+    // `--config` takes TOML, so the path is written as a TOML string rather than a Rust literal.
+    let dir = toml::Value::String(dir.canonicalize()?.to_string_lossy().into_owned());
+    Ok(vec![
+        "--config".to_string(),
+        "source.crates-io.replace-with=\"aivi-vendored\"".to_string(),
+        "--config".to_string(),
+        format!("source.aivi-vendored.directory={dir}"),
+    ])
+}
+
+/// Checks `aivi.lock` against the vendored sources and returns the vendored source directories.
+///
+/// Fails when a dependency is not vendored, its sources changed since locking, or the lock was
+/// written for a different language version.
+pub fn verify_locked_dependencies(
+    root: &Path,
+    language_version: Option<&str>,
+) -> Result<Vec<PathBuf>, AiviError> {
+    let lock_path = root.join(AIVI_LOCK_FILE);
+    if !lock_path.exists() {
+        return Err(AiviError::Config(format!(
+            "missing {} in {} (run `aivi vendor` first)",
+            AIVI_LOCK_FILE,
+            root.display()
+        )));
+    }
+    let lock = read_aivi_lock(&lock_path)?;
+    if lock.version != AIVI_LOCK_VERSION {
+        return Err(AiviError::Config(format!(
+            "unsupported {AIVI_LOCK_FILE} version {}",
+            lock.version
+        )));
+    }
+    if let (Some(required), Some(locked)) = (language_version, lock.language_version.as_deref()) {
+        if required != locked {
+            return Err(AiviError::Config(format!(
+                "{AIVI_LOCK_FILE} was written for language_version {locked}, but project uses {required}"
+            )));
+        }
+    }
+
+    let mut dirs = Vec::new();
+    for pkg in &lock.packages {
+        let Some(vendored) = pkg.vendored.as_deref() else {
+            return Err(AiviError::Config(format!(
+                "dependency {} is not vendored (run `aivi vendor`)",
+                pkg.name
+            )));
+        };
+        let dir = root.join(vendored);
+        if !dir.is_dir() {
+            return Err(AiviError::Config(format!(
+                "vendored sources for {} are missing at {}",
+                pkg.name,
+                dir.display()
+            )));
+        }
+        let actual = hash_aivi_sources(&dir)?;
+        if actual != pkg.sources_sha256 {
+            return Err(AiviError::Config(format!(
+                "vendored sources for {} do not match {AIVI_LOCK_FILE} (expected sha256 {}, found {actual})",
+                pkg.name, pkg.sources_sha256
+            )));
+        }
+        dirs.push(dir);
+    }
+    Ok(dirs)
+}
+
+fn normalize_lock_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// `path` relative to `root`, with `..` steps where it lies outside; absolute only when the two
+/// share no prefix (e.g. different drives).
+fn relative_lock_path(root: &Path, path: &Path) -> String {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let root_parts: Vec<_> = root.components().collect();
+    let path_parts: Vec<_> = path.components().collect();
+    let common = root_parts
+        .iter()
+        .zip(&path_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return normalize_lock_path(&path);
+    }
+    let mut rel = PathBuf::new();
+    for _ in common..root_parts.len() {
+        rel.push("..");
+    }
+    for part in &path_parts[common..] {
+        rel.push(part);
+    }
+    if rel.as_os_str().is_empty() {
+        rel.push(".");
+    }
+    normalize_lock_path(&rel)
+}
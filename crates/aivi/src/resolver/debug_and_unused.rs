@@ -24,6 +24,7 @@ fn check_debug_decorators(def: &Def, diagnostics: &mut Vec<FileDiagnostic>, modu
             | Expr::Match { span, .. }
             | Expr::If { span, .. }
             | Expr::Binary { span, .. }
+            | Expr::Handle { span, .. }
             | Expr::Block { span, .. }
             | Expr::Raw { span, .. } => span.clone(),
         }
@@ -128,7 +129,7 @@ fn check_expr(
             }
         }
         Expr::Ident(name) => {
-            if name.name == "_" {
+            if name.name == "_" || name.is_typed_hole() {
                 return;
             }
             if is_constructor_name(&name.name) {
@@ -242,9 +243,46 @@ fn check_expr(
             check_expr(left, scope, diagnostics, module, allow_unknown);
             check_expr(right, scope, diagnostics, module, allow_unknown);
         }
+        Expr::Handle { body, handler, .. } => {
+            check_expr(body, scope, diagnostics, module, allow_unknown);
+            check_expr(handler, scope, diagnostics, module, allow_unknown);
+        }
         Expr::Block { items, .. } => {
             let mut block_scope = scope.clone();
-            for item in items {
+            let groups = recursive_binding_groups(items);
+            for (idx, item) in items.iter().enumerate() {
+                // Names of a recursive group are visible in all of its bodies.
+                if let Some(group) = groups.iter().find(|group| group.start == idx) {
+                    let bindings: Vec<_> = items[group.clone()]
+                        .iter()
+                        .filter_map(function_binding)
+                        .collect();
+                    for (name, _) in &bindings {
+                        let shadows =
+                            block_scope.contains_key(&name.name) || is_builtin_name(&name.name);
+                        let mentioned = bindings
+                            .iter()
+                            .any(|(_, body)| expr_mentions(body, &[name.name.as_str()]));
+                        if shadows && mentioned {
+                            diagnostics.push(file_diag(
+                                module,
+                                Diagnostic {
+                                    code: "W2102".to_string(),
+                                    severity: DiagnosticSeverity::Warning,
+                                    message: format!(
+                                        "recursive binding '{0}' shadows an outer '{0}'; '{0}' in its body refers to itself",
+                                        name.name
+                                    ),
+                                    span: name.span.clone(),
+                                    labels: Vec::new(),
+                                },
+                            ));
+                        }
+                    }
+                    for (name, _) in bindings {
+                        block_scope.insert(name.name.clone(), None);
+                    }
+                }
                 match item {
                     BlockItem::Bind { pattern, expr, .. } => {
                         check_expr(expr, &mut block_scope, diagnostics, module, allow_unknown);
@@ -411,6 +449,7 @@ fn is_builtin_name(name: &str) -> bool {
             | "Stream"
             | "Listener"
             | "Connection"
+            | "UdpSocket"
             | "Some"
             | "None"
             | "Ok"
@@ -558,6 +597,36 @@ x = 1
         );
     }
 
+    #[test]
+    fn exported_signatures_cannot_leak_hidden_opaque_types() {
+        let source = r#"
+module test.opaque
+export userId, Name
+
+opaque type UserId = Text
+type Name = { id: UserId }
+
+userId : Text -> UserId
+userId = raw => raw
+"#;
+        let (modules, diags) =
+            crate::surface::parse_modules(std::path::Path::new("test.aivi"), source);
+        assert!(diags.is_empty(), "unexpected parse diagnostics: {diags:?}");
+        let diags = check_modules(&modules);
+        let leaks: Vec<&str> = diags
+            .iter()
+            .filter(|d| d.diagnostic.code == "E2013")
+            .map(|d| d.diagnostic.message.as_str())
+            .collect();
+        assert_eq!(
+            leaks,
+            [
+                "exported 'Name' mentions opaque type 'UserId', which is not exported",
+                "exported 'userId' mentions opaque type 'UserId', which is not exported",
+            ]
+        );
+    }
+
     #[test]
     fn warns_on_unused_imports_and_private_bindings() {
         let source = r#"
@@ -584,6 +653,46 @@ x = 1
         assert!(codes.contains(&"W2101"), "expected W2101, got: {codes:?}");
     }
 
+    #[test]
+    fn warns_when_a_recursive_binding_shadows_an_outer_name() {
+        let source = r#"
+module test.shadowing
+export main, twice
+
+use aivi
+
+twice = f x => f (f x)
+
+main = effect {
+  println = msg => println "> {msg}"
+  apply = apply => apply 1
+  twice = f => f 0
+  size = xs => xs ?
+    | [] => 0
+    | [_, ...rest] => 1 + size rest
+  _ <- println "{apply (x => x)} {twice (x => x)} {size [1]}"
+  pure Unit
+}
+"#;
+        let (mut modules, diags) =
+            crate::surface::parse_modules(std::path::Path::new("test.aivi"), source);
+        assert!(diags.is_empty(), "unexpected parse diagnostics: {diags:?}");
+
+        let mut all = crate::stdlib::embedded_stdlib_modules();
+        all.append(&mut modules);
+        let diags = check_modules(&all);
+
+        let shadowing: Vec<_> = diags
+            .iter()
+            .filter(|d| d.path == "test.aivi" && d.diagnostic.code == "W2102")
+            .map(|d| d.diagnostic.message.as_str())
+            .collect();
+        assert_eq!(
+            shadowing,
+            vec!["recursive binding 'println' shadows an outer 'println'; 'println' in its body refers to itself"]
+        );
+    }
+
     #[test]
     fn does_not_warn_for_domain_import_used_via_operators() {
         let source = r#"
@@ -2,7 +2,8 @@ use std::collections::{HashMap, HashSet};
 
 use crate::diagnostics::{Diagnostic, DiagnosticSeverity, FileDiagnostic};
 use crate::surface::{
-    BlockItem, Decorator, Def, DomainItem, Expr, Literal, Module, ModuleItem, Pattern, TextPart,
+    expr_mentions, function_binding, recursive_binding_groups, BlockItem, Decorator, Def,
+    DomainItem, Expr, Literal, Module, ModuleItem, Pattern, ScopeItemKind, SpannedName, TextPart,
     TypeAlias, TypeDecl, TypeExpr, TypeSig,
 };
 
@@ -31,6 +32,7 @@ pub fn check_modules(modules: &[Module]) -> Vec<FileDiagnostic> {
         check_duplicate_exports(module, &mut diagnostics);
         check_uses(module, &module_map, &mut diagnostics);
         check_defs(module, &module_map, &mut diagnostics);
+        check_opaque_exports(module, &mut diagnostics);
         check_unused_imports_and_bindings(module, &mut diagnostics);
     }
 
@@ -115,38 +117,38 @@ fn check_unused_imports_and_bindings(module: &Module, diagnostics: &mut Vec<File
     }
 }
 
-fn collect_used_names(module: &Module) -> HashSet<String> {
-    fn collect_type_expr(expr: &TypeExpr, out: &mut HashSet<String>) {
-        match expr {
-            TypeExpr::Name(name) => {
-                out.insert(name.name.clone());
-            }
-            TypeExpr::And { items, .. } | TypeExpr::Tuple { items, .. } => {
-                for item in items {
-                    collect_type_expr(item, out);
-                }
+fn collect_type_expr(expr: &TypeExpr, out: &mut HashSet<String>) {
+    match expr {
+        TypeExpr::Name(name) => {
+            out.insert(name.name.clone());
+        }
+        TypeExpr::And { items, .. } | TypeExpr::Tuple { items, .. } => {
+            for item in items {
+                collect_type_expr(item, out);
             }
-            TypeExpr::Apply { base, args, .. } => {
-                collect_type_expr(base, out);
-                for arg in args {
-                    collect_type_expr(arg, out);
-                }
+        }
+        TypeExpr::Apply { base, args, .. } => {
+            collect_type_expr(base, out);
+            for arg in args {
+                collect_type_expr(arg, out);
             }
-            TypeExpr::Func { params, result, .. } => {
-                for param in params {
-                    collect_type_expr(param, out);
-                }
-                collect_type_expr(result, out);
+        }
+        TypeExpr::Func { params, result, .. } => {
+            for param in params {
+                collect_type_expr(param, out);
             }
-            TypeExpr::Record { fields, .. } => {
-                for (_label, ty) in fields {
-                    collect_type_expr(ty, out);
-                }
+            collect_type_expr(result, out);
+        }
+        TypeExpr::Record { fields, .. } => {
+            for (_label, ty) in fields {
+                collect_type_expr(ty, out);
             }
-            TypeExpr::Star { .. } | TypeExpr::Unknown { .. } => {}
         }
+        TypeExpr::Star { .. } | TypeExpr::Unknown { .. } => {}
     }
+}
 
+fn collect_used_names(module: &Module) -> HashSet<String> {
     fn collect_pattern_uses(pattern: &Pattern, out: &mut HashSet<String>) {
         match pattern {
             Pattern::Constructor { name, args, .. } => {
@@ -255,6 +257,10 @@ fn collect_used_names(module: &Module) -> HashSet<String> {
                 collect_expr(left, out);
                 collect_expr(right, out);
             }
+            Expr::Handle { body, handler, .. } => {
+                collect_expr(body, out);
+                collect_expr(handler, out);
+            }
             Expr::Block { items, .. } => {
                 for item in items {
                     match item {
@@ -291,6 +297,11 @@ fn collect_used_names(module: &Module) -> HashSet<String> {
             ModuleItem::Def(def) => {
                 collect_expr(&def.expr, &mut out);
             }
+            ModuleItem::EffectDecl(effect) => {
+                for op in &effect.operations {
+                    collect_type_expr(&op.ty, &mut out);
+                }
+            }
             ModuleItem::DomainDecl(domain) => {
                 for domain_item in &domain.items {
                     match domain_item {
@@ -309,20 +320,32 @@ fn collect_used_names(module: &Module) -> HashSet<String> {
                 }
             }
             ModuleItem::InstanceDecl(instance) => {
+                out.insert(instance.name.name.clone());
                 for def in &instance.defs {
                     collect_expr(&def.expr, &mut out);
                 }
             }
-            _ => {}
+            ModuleItem::ClassDecl(class_decl) => {
+                for super_expr in &class_decl.supers {
+                    collect_type_expr(super_expr, &mut out);
+                }
+                for member in &class_decl.members {
+                    collect_type_expr(&member.ty, &mut out);
+                }
+                for def in &class_decl.defaults {
+                    collect_expr(&def.expr, &mut out);
+                }
+            }
         }
     }
     out
 }
 
 fn check_duplicate_exports(module: &Module, diagnostics: &mut Vec<FileDiagnostic>) {
-    let mut seen: HashSet<&str> = HashSet::new();
+    // `export domain Path` and `export Path` name different things.
+    let mut seen: HashSet<(ScopeItemKind, &str)> = HashSet::new();
     for export in &module.exports {
-        if !seen.insert(export.name.name.as_str()) {
+        if !seen.insert((export.kind, export.name.name.as_str())) {
             diagnostics.push(file_diag(
                 module,
                 Diagnostic {
@@ -337,6 +360,62 @@ fn check_duplicate_exports(module: &Module, diagnostics: &mut Vec<FileDiagnostic
     }
 }
 
+/// Exported signatures and aliases may only mention opaque types the module exports too;
+/// otherwise importers could hold values of a type they cannot name.
+fn check_opaque_exports(module: &Module, diagnostics: &mut Vec<FileDiagnostic>) {
+    let exported: HashSet<&str> = module
+        .exports
+        .iter()
+        .filter(|export| export.kind == ScopeItemKind::Value)
+        .map(|export| export.name.name.as_str())
+        .collect();
+    let hidden: HashSet<&str> = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::TypeAlias(alias) if alias.opaque => Some(alias.name.name.as_str()),
+            _ => None,
+        })
+        .filter(|name| !exported.contains(name))
+        .collect();
+    if hidden.is_empty() {
+        return;
+    }
+    for item in &module.items {
+        let (name, ty) = match item {
+            ModuleItem::TypeSig(sig) => (&sig.name, &sig.ty),
+            ModuleItem::TypeAlias(alias) if !alias.opaque => (&alias.name, &alias.aliased),
+            _ => continue,
+        };
+        if !exported.contains(name.name.as_str()) {
+            continue;
+        }
+        let mut mentioned = HashSet::new();
+        collect_type_expr(ty, &mut mentioned);
+        let mut leaked: Vec<&str> = hidden
+            .iter()
+            .copied()
+            .filter(|hidden| mentioned.contains(*hidden))
+            .collect();
+        leaked.sort_unstable();
+        for opaque in leaked {
+            diagnostics.push(file_diag(
+                module,
+                Diagnostic {
+                    code: "E2013".to_string(),
+                    severity: DiagnosticSeverity::Error,
+                    message: format!(
+                        "exported '{}' mentions opaque type '{opaque}', which is not exported",
+                        name.name
+                    ),
+                    span: name.span.clone(),
+                    labels: Vec::new(),
+                },
+            ));
+        }
+    }
+}
+
 fn check_uses(
     module: &Module,
     module_map: &HashMap<String, &Module>,
@@ -421,15 +500,15 @@ fn check_defs(
                     }
                 }
                 for item in &target.items {
-                    if let ModuleItem::ClassDecl(class_decl) = item {
-                        if !exported.contains(class_decl.name.name.as_str()) {
+                    if let Some((owner, members)) = item_members(item) {
+                        if !exported.contains(owner.name.as_str()) {
                             continue;
                         }
-                        for member in &class_decl.members {
-                            scope.insert(member.name.name.clone(), None);
+                        for member in members {
+                            scope.insert(member.name.clone(), None);
                             if use_decl.alias.is_some() {
                                 scope.insert(
-                                    format!("{}.{}", use_decl.module.name, member.name.name),
+                                    format!("{}.{}", use_decl.module.name, member.name),
                                     None,
                                 );
                             }
@@ -467,15 +546,15 @@ fn check_defs(
                             }
                             if exported.contains(item.name.name.as_str()) {
                                 for module_item in &target.items {
-                                    if let ModuleItem::ClassDecl(class_decl) = module_item {
-                                        if class_decl.name.name == item.name.name {
-                                            for member in &class_decl.members {
-                                                scope.insert(member.name.name.clone(), None);
+                                    if let Some((owner, members)) = item_members(module_item) {
+                                        if owner.name == item.name.name {
+                                            for member in members {
+                                                scope.insert(member.name.clone(), None);
                                                 if use_decl.alias.is_some() {
                                                     scope.insert(
                                                         format!(
                                                             "{}.{}",
-                                                            use_decl.module.name, member.name.name
+                                                            use_decl.module.name, member.name
                                                         ),
                                                         None,
                                                     );
@@ -537,6 +616,11 @@ fn check_defs(
                     check_def(def, &scope, diagnostics, module, allow_unknown);
                 }
             }
+            ModuleItem::ClassDecl(class_decl) => {
+                for def in &class_decl.defaults {
+                    check_def(def, &scope, diagnostics, module, allow_unknown);
+                }
+            }
             ModuleItem::DomainDecl(domain) => {
                 for domain_item in &domain.items {
                     match domain_item {
@@ -569,6 +653,11 @@ fn collect_value_defs(item: &ModuleItem, scope: &mut HashMap<String, Option<Stri
                 scope.insert(member.name.name.clone(), None);
             }
         }
+        ModuleItem::EffectDecl(effect) => {
+            for op in &effect.operations {
+                scope.insert(op.name.name.clone(), None);
+            }
+        }
         ModuleItem::InstanceDecl(instance) => {
             for def in &instance.defs {
                 scope.insert(def.name.name.clone(), deprecated_message(&def.decorators));
@@ -593,6 +682,25 @@ fn collect_value_defs(item: &ModuleItem, scope: &mut HashMap<String, Option<Stri
     }
 }
 
+/// Class members and effect operations come into scope together with their owner's name.
+fn item_members(item: &ModuleItem) -> Option<(&SpannedName, Vec<&SpannedName>)> {
+    match item {
+        ModuleItem::ClassDecl(class_decl) => Some((
+            &class_decl.name,
+            class_decl
+                .members
+                .iter()
+                .map(|member| &member.name)
+                .collect(),
+        )),
+        ModuleItem::EffectDecl(effect) => Some((
+            &effect.name,
+            effect.operations.iter().map(|op| &op.name).collect(),
+        )),
+        _ => None,
+    }
+}
+
 fn deprecated_message(decorators: &[Decorator]) -> Option<String> {
     decorators
         .iter()
@@ -143,6 +143,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                 func: Arc::new(move |runtime| {
                     let cancel = CancelToken::child(runtime.cancel.clone());
                     let mut child = Runtime::new(ctx.clone(), cancel.clone());
+                    child.effect_handlers = runtime.effect_handlers.clone();
                     let result = child.run_effect_value(effect.clone());
                     cancel.cancel();
                     result
@@ -168,6 +169,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                         ctx.clone(),
                         left_cancel.clone(),
                         tx.clone(),
+                        runtime.effect_handlers.clone(),
                     );
                     spawn_effect(
                         1,
@@ -175,6 +177,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                         ctx.clone(),
                         right_cancel.clone(),
                         tx.clone(),
+                        runtime.effect_handlers.clone(),
                     );
 
                     let mut left_result = None;
@@ -241,6 +244,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                         ctx.clone(),
                         left_cancel.clone(),
                         tx.clone(),
+                        runtime.effect_handlers.clone(),
                     );
                     spawn_effect(
                         1,
@@ -248,6 +252,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                         ctx.clone(),
                         right_cancel.clone(),
                         tx.clone(),
+                        runtime.effect_handlers.clone(),
                     );
 
                     let mut cancelled = false;
@@ -299,7 +304,14 @@ pub(crate) fn build_concurrent_record() -> Value {
                         .unwrap_or_else(|| runtime.cancel.clone());
                     let cancel = CancelToken::child(parent);
                     let (tx, _rx) = mpsc::channel();
-                    spawn_effect(0, effect_value.clone(), ctx.clone(), cancel, tx);
+                    spawn_effect(
+                        0,
+                        effect_value.clone(),
+                        ctx.clone(),
+                        cancel,
+                        tx,
+                        runtime.effect_handlers.clone(),
+                    );
                     Ok(Value::Unit)
                 }),
             };
@@ -315,9 +327,11 @@ fn spawn_effect(
     ctx: Arc<RuntimeContext>,
     cancel: Arc<CancelToken>,
     sender: mpsc::Sender<(usize, Result<Value, RuntimeError>)>,
+    handlers: Vec<Arc<std::collections::HashMap<String, Value>>>,
 ) {
     std::thread::spawn(move || {
         let mut runtime = Runtime::new(ctx, cancel);
+        runtime.effect_handlers = handlers;
         let result = runtime.run_effect_value(effect);
         let _ = sender.send((id, result));
     });
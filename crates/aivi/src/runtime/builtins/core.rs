@@ -239,6 +239,72 @@ pub(crate) fn register_builtins(env: &Env) {
         }),
     );
 
+    env.set(
+        "__handle".to_string(),
+        builtin("__handle", 2, |mut args, _| {
+            let body = args.pop().unwrap();
+            let handler = match args.pop().unwrap() {
+                Value::Record(fields) => fields,
+                _ => {
+                    return Err(RuntimeError::Message(
+                        "with expects a record of effect operation clauses".to_string(),
+                    ))
+                }
+            };
+            let effect = EffectValue::Thunk {
+                func: std::sync::Arc::new(move |runtime| {
+                    runtime.effect_handlers.push(handler.clone());
+                    let result = runtime.run_effect_value(body.clone());
+                    runtime.effect_handlers.pop();
+                    result
+                }),
+            };
+            Ok(Value::Effect(std::sync::Arc::new(effect)))
+        }),
+    );
+
+    env.set(
+        "__perform".to_string(),
+        builtin("__perform", 2, |mut args, _| {
+            let op_args = match args.pop().unwrap() {
+                Value::List(items) => items,
+                _ => {
+                    return Err(RuntimeError::Message(
+                        "__perform expects a List".to_string(),
+                    ))
+                }
+            };
+            let op = match args.pop().unwrap() {
+                Value::Text(op) => op,
+                _ => return Err(RuntimeError::Message("__perform expects Text".to_string())),
+            };
+            let effect = EffectValue::Thunk {
+                func: std::sync::Arc::new(move |runtime| {
+                    let Some(index) = runtime
+                        .effect_handlers
+                        .iter()
+                        .rposition(|handler| handler.contains_key(&op))
+                    else {
+                        return Err(RuntimeError::Message(format!(
+                            "unhandled effect operation '{op}'"
+                        )));
+                    };
+                    let clause = runtime.effect_handlers[index][&op].clone();
+                    // The clause runs under the handlers outside its own, so performing the same
+                    // operation again reaches the next enclosing handler.
+                    let inner = runtime.effect_handlers.split_off(index);
+                    let result = op_args
+                        .iter()
+                        .try_fold(clause, |clause, arg| runtime.apply(clause, arg.clone()))
+                        .and_then(|effect| runtime.run_effect_value(effect));
+                    runtime.effect_handlers.extend(inner);
+                    result
+                }),
+            };
+            Ok(Value::Effect(std::sync::Arc::new(effect)))
+        }),
+    );
+
     env.set(
         "print".to_string(),
         builtin("print", 1, |mut args, _| {
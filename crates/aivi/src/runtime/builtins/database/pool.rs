@@ -1,7 +1,9 @@
 use std::sync::{Condvar, Mutex};
 use std::time::{Duration, Instant};
 
-use super::util::{expect_int, make_err, make_ok};
+use super::util::{
+    backoff_policy, backoff_wait, expect_int, make_err, make_ok, span_millis, BackoffPolicy,
+};
 
 #[derive(Clone, Copy, Debug)]
 enum QueuePolicy {
@@ -9,12 +11,6 @@ enum QueuePolicy {
     Lifo,
 }
 
-#[derive(Clone, Copy, Debug)]
-enum BackoffPolicy {
-    Fixed(Duration),
-    Exponential { base: Duration, max: Duration },
-}
-
 #[derive(Clone)]
 struct ConnEntry {
     conn: Value,
@@ -70,14 +66,6 @@ fn result_err(value: Value) -> Value {
     make_err(value)
 }
 
-fn span_millis(value: Value, ctx: &str) -> Result<i64, RuntimeError> {
-    let fields = expect_record(value, ctx)?;
-    let millis = fields
-        .get("millis")
-        .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects Span.millis")))?;
-    expect_int(millis.clone(), ctx)
-}
-
 fn option_span_millis(value: Value, ctx: &str) -> Result<Option<i64>, RuntimeError> {
     match value {
         Value::Constructor { name, args } if name == "None" && args.is_empty() => Ok(None),
@@ -91,34 +79,6 @@ fn option_span_millis(value: Value, ctx: &str) -> Result<Option<i64>, RuntimeErr
     }
 }
 
-fn backoff_policy(value: Value, ctx: &str) -> Result<BackoffPolicy, RuntimeError> {
-    match value {
-        Value::Constructor { name, args } if name == "Fixed" && args.len() == 1 => {
-            let ms = span_millis(args[0].clone(), ctx)?;
-            Ok(BackoffPolicy::Fixed(Duration::from_millis(ms.max(0) as u64)))
-        }
-        Value::Constructor { name, args } if name == "Exponential" && args.len() == 1 => {
-            let rec = expect_record(args[0].clone(), ctx)?;
-            let base = rec
-                .get("base")
-                .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects Exponential.base")))?;
-            let max = rec
-                .get("max")
-                .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects Exponential.max")))?;
-            let base_ms = span_millis(base.clone(), ctx)?;
-            let max_ms = span_millis(max.clone(), ctx)?;
-            Ok(BackoffPolicy::Exponential {
-                base: Duration::from_millis(base_ms.max(0) as u64),
-                max: Duration::from_millis(max_ms.max(0) as u64),
-            })
-        }
-        other => Err(RuntimeError::Message(format!(
-            "{ctx} expects BackoffPolicy (Fixed|Exponential), got {}",
-            crate::runtime::format_value(&other)
-        ))),
-    }
-}
-
 fn queue_policy(value: Value, ctx: &str) -> Result<QueuePolicy, RuntimeError> {
     match value {
         Value::Constructor { name, args } if args.is_empty() => match name.as_str() {
@@ -173,17 +133,6 @@ fn health_check_effect(
     }
 }
 
-fn backoff_wait(policy: BackoffPolicy, attempt: usize) -> Duration {
-    match policy {
-        BackoffPolicy::Fixed(d) => d,
-        BackoffPolicy::Exponential { base, max } => {
-            let factor = 1u64 << (attempt.min(16) as u32);
-            let dur = base.saturating_mul(factor as u32);
-            std::cmp::min(dur, max)
-        }
-    }
-}
-
 fn pop_idle(state: &mut PoolState) -> Option<ConnEntry> {
     match state.queue_policy {
         QueuePolicy::Fifo => {
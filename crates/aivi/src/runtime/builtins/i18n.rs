@@ -4,9 +4,16 @@ use std::sync::Arc;
 use im::HashMap as ImHashMap;
 
 use super::util::{builtin, expect_record, expect_text, list_value, make_err, make_ok, make_some};
-use crate::i18n::{parse_locale_tag, parse_message_template, validate_key_text, MessagePart};
+use crate::i18n::{
+    parse_locale_tag, parse_message_template, select_arm, select_plural_arm, validate_key_text,
+    MessageArm, MessagePart,
+};
+use crate::i18n_format::{
+    format_currency, format_date, format_date_time, format_integer, format_number, format_percent,
+    format_time, locale_formats, DateStyle, FormatNumber, LocaleFormats,
+};
 use crate::runtime::values::KeyValue;
-use crate::runtime::{format_value, RuntimeError, Value};
+use crate::runtime::{format_value, i18n_message_parts_value, RuntimeError, Value};
 
 pub(super) fn build_i18n_record() -> Value {
     let mut fields = HashMap::new();
@@ -53,33 +60,99 @@ pub(super) fn build_i18n_record() -> Value {
         builtin("i18n.render", 2, |mut args, _| {
             let args_rec = expect_record(args.pop().unwrap(), "i18n.render")?;
             let msg = expect_record(args.pop().unwrap(), "i18n.render")?;
-            let body = match msg.get("body") {
-                Some(Value::Text(text)) => text.clone(),
+            let locale = RenderLocale {
+                language: DEFAULT_PLURAL_LANGUAGE,
+                formats: None,
+            };
+            render_message(&msg, &args_rec, locale, "i18n.render")
+        }),
+    );
+
+    fields.insert(
+        "renderIn".to_string(),
+        builtin("i18n.renderIn", 3, |mut args, _| {
+            let args_rec = expect_record(args.pop().unwrap(), "i18n.renderIn")?;
+            let msg = expect_record(args.pop().unwrap(), "i18n.renderIn")?;
+            let locale = expect_record(args.pop().unwrap(), "i18n.renderIn")?;
+            let language = match locale.get("language") {
+                Some(Value::Text(language)) => language.clone(),
                 _ => {
                     return Err(RuntimeError::Message(
-                        "i18n.render expects Message with field 'body : Text'".to_string(),
+                        "i18n.renderIn expects Locale with field 'language : Text'".to_string(),
                     ))
                 }
             };
+            let formats = locale_formats(&locale_tag(&locale, "i18n.renderIn")?);
+            render_message(
+                &msg,
+                &args_rec,
+                RenderLocale {
+                    language: &language,
+                    formats: Some(formats),
+                },
+                "i18n.renderIn",
+            )
+        }),
+    );
 
-            let parts = if let Some(Value::List(items)) = msg.get("parts") {
-                // Trusted compilation output from `i18n.message`.
-                Some(items.iter().cloned().collect::<Vec<_>>())
-            } else {
-                None
-            };
+    for (name, format) in [
+        (
+            "formatNumber",
+            format_number as fn(&LocaleFormats, FormatNumber) -> String,
+        ),
+        ("formatInt", format_integer),
+        ("formatDecimal", format_number),
+        ("formatPercent", format_percent),
+    ] {
+        let ctx = format!("i18n.{name}");
+        fields.insert(
+            name.to_string(),
+            builtin(&ctx.clone(), 2, move |mut args, _| {
+                let number = expect_number(args.pop().unwrap(), &ctx)?;
+                let locale = expect_record(args.pop().unwrap(), &ctx)?;
+                let formats = locale_formats(&locale_tag(&locale, &ctx)?);
+                Ok(Value::Text(format(formats, number)))
+            }),
+        );
+    }
 
-            let rendered = match parts {
-                Some(items) => render_parts(&items, &args_rec),
-                None => match parse_message_template(&body) {
-                    Ok(parsed) => render_parsed(&parsed.parts, &args_rec),
-                    Err(msg) => return Ok(make_err(Value::Text(msg))),
-                },
-            }?;
-            Ok(make_ok(Value::Text(rendered)))
+    fields.insert(
+        "formatCurrency".to_string(),
+        builtin("i18n.formatCurrency", 3, |mut args, _| {
+            let number = expect_number(args.pop().unwrap(), "i18n.formatCurrency")?;
+            let code = expect_text(args.pop().unwrap(), "i18n.formatCurrency")?;
+            let locale = expect_record(args.pop().unwrap(), "i18n.formatCurrency")?;
+            let formats = locale_formats(&locale_tag(&locale, "i18n.formatCurrency")?);
+            Ok(Value::Text(format_currency(formats, &code, number)))
         }),
     );
 
+    for (name, format) in [
+        (
+            "formatDate",
+            format_date as fn(&LocaleFormats, DateStyle, &str) -> Result<String, String>,
+        ),
+        ("formatTime", format_time),
+        ("formatDateTime", format_date_time),
+    ] {
+        let ctx = format!("i18n.{name}");
+        fields.insert(
+            name.to_string(),
+            builtin(&ctx.clone(), 3, move |mut args, _| {
+                let value = args.pop().unwrap();
+                let style = expect_date_style(args.pop().unwrap(), &ctx)?;
+                let locale = expect_record(args.pop().unwrap(), &ctx)?;
+                let formats = locale_formats(&locale_tag(&locale, &ctx)?);
+                let Value::DateTime(text) = value else {
+                    return Err(RuntimeError::Message(format!("{ctx} expects DateTime")));
+                };
+                format(formats, style, &text)
+                    .map(Value::Text)
+                    .map_err(|msg| RuntimeError::Message(format!("{ctx}: {msg}")))
+            }),
+        );
+    }
+
     fields.insert(
         "bundleFromProperties".to_string(),
         builtin("i18n.bundleFromProperties", 2, |mut args, _| {
@@ -140,132 +213,329 @@ fn locale_value(parts: crate::i18n::LocaleParts) -> Value {
 }
 
 fn message_value(body: String, parts: &[MessagePart]) -> Value {
-    let parts_value = list_value(
-        parts
-            .iter()
-            .map(|part| match part {
-                MessagePart::Lit(text) => Value::Record(Arc::new(HashMap::from([
-                    ("kind".to_string(), Value::Text("lit".to_string())),
-                    ("text".to_string(), Value::Text(text.clone())),
-                ]))),
-                MessagePart::Hole { name, ty } => Value::Record(Arc::new(HashMap::from([
-                    ("kind".to_string(), Value::Text("hole".to_string())),
-                    ("name".to_string(), Value::Text(name.clone())),
-                    (
-                        "ty".to_string(),
-                        match ty {
-                            Some(t) => make_some(Value::Text(t.clone())),
-                            None => Value::Constructor {
-                                name: "None".to_string(),
-                                args: Vec::new(),
-                            },
-                        },
-                    ),
-                ]))),
-            })
-            .collect(),
-    );
-
     Value::Record(Arc::new(HashMap::from([
         ("tag".to_string(), Value::Text("m".to_string())),
         ("body".to_string(), Value::Text(body)),
         ("flags".to_string(), Value::Text(String::new())),
-        ("parts".to_string(), parts_value),
+        ("parts".to_string(), i18n_message_parts_value(parts)),
     ])))
 }
 
-fn render_parts(
-    parts: &[Value],
-    args: &std::collections::HashMap<String, Value>,
-) -> Result<String, RuntimeError> {
+fn locale_tag(locale: &HashMap<String, Value>, ctx: &str) -> Result<String, RuntimeError> {
+    match (locale.get("tag"), locale.get("language")) {
+        (Some(Value::Text(tag)), _) | (None, Some(Value::Text(tag))) => Ok(tag.clone()),
+        _ => Err(RuntimeError::Message(format!(
+            "{ctx} expects Locale with field 'tag : Text'"
+        ))),
+    }
+}
+
+fn format_number_of(value: &Value) -> Option<FormatNumber> {
+    match value {
+        Value::Int(n) => Some(FormatNumber::Int(*n)),
+        Value::Float(n) => Some(FormatNumber::Float(*n)),
+        Value::Decimal(n) => Some(FormatNumber::Decimal(*n)),
+        _ => None,
+    }
+}
+
+fn expect_number(value: Value, ctx: &str) -> Result<FormatNumber, RuntimeError> {
+    format_number_of(&value).ok_or_else(|| {
+        RuntimeError::Message(format!(
+            "{ctx} expects a number, got {}",
+            value_type_name(&value)
+        ))
+    })
+}
+
+fn expect_date_style(value: Value, ctx: &str) -> Result<DateStyle, RuntimeError> {
+    match &value {
+        Value::Constructor { name, args } if args.is_empty() => DateStyle::parse(name),
+        _ => None,
+    }
+    .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects DateStyle")))
+}
+
+/// Plural rules used by `i18n.render`, which has no locale to go by.
+const DEFAULT_PLURAL_LANGUAGE: &str = "en";
+
+/// Locale conventions applied while rendering a message.
+#[derive(Clone, Copy)]
+struct RenderLocale<'a> {
+    language: &'a str,
+    /// `None` for `i18n.render`: typed holes keep their locale-neutral text form.
+    formats: Option<&'static LocaleFormats>,
+}
+
+fn render_message(
+    msg: &HashMap<String, Value>,
+    args: &HashMap<String, Value>,
+    locale: RenderLocale,
+    ctx: &str,
+) -> Result<Value, RuntimeError> {
+    let body = match msg.get("body") {
+        Some(Value::Text(text)) => text.clone(),
+        _ => {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects Message with field 'body : Text'"
+            )))
+        }
+    };
+
+    let parts = match msg.get("parts") {
+        // Trusted compilation output from `i18n.message`.
+        Some(Value::List(items)) => decode_parts(items, ctx)?,
+        _ => match parse_message_template(&body) {
+            Ok(parsed) => parsed.parts,
+            Err(msg) => return Ok(make_err(Value::Text(msg))),
+        },
+    };
     let mut out = String::new();
-    for part in parts {
-        let rec = expect_record(part.clone(), "i18n.render")?;
-        let kind = match rec.get("kind") {
-            Some(Value::Text(k)) => k.as_str(),
-            _ => {
-                return Err(RuntimeError::Message(
-                    "i18n.render expects compiled message parts".to_string(),
-                ))
+    render_parsed(&parts, args, locale, None, &mut out)?;
+    Ok(make_ok(Value::Text(out)))
+}
+
+fn decode_parts(parts: &[Value], ctx: &str) -> Result<Vec<MessagePart>, RuntimeError> {
+    parts.iter().map(|part| decode_part(part, ctx)).collect()
+}
+
+fn decode_part(part: &Value, ctx: &str) -> Result<MessagePart, RuntimeError> {
+    let rec = expect_record(part.clone(), ctx)?;
+    let text_field = |field: &str, kind: &str| match rec.get(field) {
+        Some(Value::Text(text)) => Ok(text.clone()),
+        _ => Err(RuntimeError::Message(format!(
+            "{ctx} expects {kind} part with '{field} : Text'"
+        ))),
+    };
+    let ty = match rec.get("ty") {
+        Some(Value::Constructor { name, args }) if name == "Some" && args.len() == 1 => {
+            match &args[0] {
+                Value::Text(t) => Some(t.clone()),
+                _ => None,
             }
+        }
+        _ => None,
+    };
+    let arms = || -> Result<Vec<MessageArm>, RuntimeError> {
+        let Some(Value::List(items)) = rec.get("arms") else {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects arms : List in plural/select parts"
+            )));
         };
-        match kind {
-            "lit" => match rec.get("text") {
-                Some(Value::Text(text)) => out.push_str(text),
-                _ => {
-                    return Err(RuntimeError::Message(
-                        "i18n.render expects lit part with 'text : Text'".to_string(),
-                    ))
+        items
+            .iter()
+            .map(|item| {
+                let arm = expect_record(item.clone(), ctx)?;
+                match (arm.get("selector"), arm.get("parts")) {
+                    (Some(Value::Text(selector)), Some(Value::List(parts))) => Ok(MessageArm {
+                        selector: selector.clone(),
+                        parts: decode_parts(parts, ctx)?,
+                    }),
+                    _ => Err(RuntimeError::Message(format!(
+                        "{ctx} expects arms with 'selector : Text' and 'parts : List'"
+                    ))),
                 }
-            },
-            "hole" => {
-                let name = match rec.get("name") {
-                    Some(Value::Text(name)) => name.as_str(),
-                    _ => {
-                        return Err(RuntimeError::Message(
-                            "i18n.render expects hole part with 'name : Text'".to_string(),
-                        ))
-                    }
-                };
-                let expected_ty = match rec.get("ty") {
-                    Some(Value::Constructor { name, args })
-                        if name == "Some" && args.len() == 1 =>
-                    {
-                        match &args[0] {
-                            Value::Text(t) => Some(t.as_str()),
-                            _ => None,
-                        }
-                    }
-                    _ => None,
-                };
-                let value = args.get(name).ok_or_else(|| {
-                    RuntimeError::Message(format!("missing message arg '{name}'"))
-                })?;
-                if let Some(ty) = expected_ty {
-                    if !matches_type(ty, value) {
-                        return Err(RuntimeError::Message(format!(
-                            "type mismatch for arg '{name}': expected {ty}, got {}",
-                            value_type_name(value)
-                        )));
+            })
+            .collect()
+    };
+    let kind = match rec.get("kind") {
+        Some(Value::Text(k)) => k.as_str(),
+        _ => {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects compiled message parts"
+            )))
+        }
+    };
+    match kind {
+        "lit" => Ok(MessagePart::Lit(text_field("text", kind)?)),
+        "hole" => Ok(MessagePart::Hole {
+            name: text_field("name", kind)?,
+            ty,
+        }),
+        "pound" => Ok(MessagePart::Pound),
+        "plural" => Ok(MessagePart::Plural {
+            name: text_field("name", kind)?,
+            ty,
+            ordinal: matches!(rec.get("ordinal"), Some(Value::Bool(true))),
+            arms: arms()?,
+        }),
+        "select" => Ok(MessagePart::Select {
+            name: text_field("name", kind)?,
+            ty,
+            arms: arms()?,
+        }),
+        "format" => Ok(MessagePart::Format {
+            name: text_field("name", kind)?,
+            ty,
+            format: text_field("format", kind)?,
+            style: match rec.get("style") {
+                Some(Value::Constructor { name, args }) if name == "Some" && args.len() == 1 => {
+                    match &args[0] {
+                        Value::Text(style) => Some(style.clone()),
+                        _ => None,
                     }
                 }
-                out.push_str(&format_value(value));
-            }
-            other => {
-                return Err(RuntimeError::Message(format!(
-                    "i18n.render: unknown part kind '{other}'"
-                )))
-            }
-        }
+                _ => None,
+            },
+        }),
+        other => Err(RuntimeError::Message(format!(
+            "{ctx}: unknown part kind '{other}'"
+        ))),
     }
-    Ok(out)
 }
 
 fn render_parsed(
     parts: &[MessagePart],
-    args: &std::collections::HashMap<String, Value>,
-) -> Result<String, RuntimeError> {
-    let mut out = String::new();
+    args: &HashMap<String, Value>,
+    locale: RenderLocale,
+    pound: Option<&Value>,
+    out: &mut String,
+) -> Result<(), RuntimeError> {
     for part in parts {
         match part {
             MessagePart::Lit(text) => out.push_str(text),
             MessagePart::Hole { name, ty } => {
-                let value = args.get(name).ok_or_else(|| {
-                    RuntimeError::Message(format!("missing message arg '{name}'"))
-                })?;
-                if let Some(ty) = ty.as_deref() {
-                    if !matches_type(ty, value) {
+                let value = message_arg(args, name, ty.as_deref())?;
+                match (locale.formats, ty.as_deref(), value) {
+                    (Some(formats), Some(_), Value::DateTime(text)) => out
+                        .push_str(&format_date_time(formats, DateStyle::Medium, text).map_err(
+                            |msg| RuntimeError::Message(format!("arg '{name}': {msg}")),
+                        )?),
+                    (Some(formats), Some(_), value) => match format_number_of(value) {
+                        Some(number) => out.push_str(&format_number(formats, number)),
+                        None => out.push_str(&format_value(value)),
+                    },
+                    _ => out.push_str(&format_value(value)),
+                }
+            }
+            MessagePart::Pound => {
+                if let Some(value) = pound {
+                    match (locale.formats, format_number_of(value)) {
+                        (Some(formats), Some(number)) => {
+                            out.push_str(&format_number(formats, number))
+                        }
+                        _ => out.push_str(&format_value(value)),
+                    }
+                }
+            }
+            MessagePart::Plural {
+                name,
+                ty,
+                ordinal,
+                arms,
+            } => {
+                let value = message_arg(args, name, ty.as_deref())?;
+                let number = match value {
+                    Value::Int(n) => n.to_string(),
+                    Value::Float(n) => n.to_string(),
+                    Value::Decimal(n) => n.to_string(),
+                    other => {
                         return Err(RuntimeError::Message(format!(
-                            "type mismatch for arg '{name}': expected {ty}, got {}",
-                            value_type_name(value)
-                        )));
+                            "type mismatch for arg '{name}': expected a number, got {}",
+                            value_type_name(other)
+                        )))
                     }
+                };
+                if let Some(arm) = select_plural_arm(arms, locale.language, &number, *ordinal) {
+                    render_parsed(&arm.parts, args, locale, Some(value), out)?;
                 }
-                out.push_str(&format_value(value));
+            }
+            MessagePart::Select { name, ty, arms } => {
+                let value = message_arg(args, name, ty.as_deref())?;
+                let key = match value {
+                    Value::Text(text) => text.clone(),
+                    Value::Bool(flag) => flag.to_string(),
+                    Value::Constructor { name, args } if args.is_empty() => name.clone(),
+                    other => {
+                        return Err(RuntimeError::Message(format!(
+                            "type mismatch for arg '{name}': expected Text, got {}",
+                            value_type_name(other)
+                        )))
+                    }
+                };
+                if let Some(arm) = select_arm(arms, &key) {
+                    render_parsed(&arm.parts, args, locale, pound, out)?;
+                }
+            }
+            MessagePart::Format {
+                name,
+                ty,
+                format,
+                style,
+            } => {
+                let value = message_arg(args, name, ty.as_deref())?;
+                let formats = locale
+                    .formats
+                    .unwrap_or_else(|| locale_formats(DEFAULT_PLURAL_LANGUAGE));
+                out.push_str(&format_message_value(
+                    formats,
+                    format,
+                    style.as_deref(),
+                    name,
+                    value,
+                )?);
             }
         }
     }
-    Ok(out)
+    Ok(())
+}
+
+fn format_message_value(
+    formats: &LocaleFormats,
+    format: &str,
+    style: Option<&str>,
+    name: &str,
+    value: &Value,
+) -> Result<String, RuntimeError> {
+    let mismatch = |expected: &str| {
+        RuntimeError::Message(format!(
+            "type mismatch for arg '{name}': expected {expected}, got {}",
+            value_type_name(value)
+        ))
+    };
+    if format == "number" {
+        let number = format_number_of(value).ok_or_else(|| mismatch("a number"))?;
+        return Ok(match style {
+            Some("integer") => format_integer(formats, number),
+            Some("percent") => format_percent(formats, number),
+            Some(style) => match style.strip_prefix("currency/") {
+                Some(code) => format_currency(formats, code, number),
+                None => format_number(formats, number),
+            },
+            None => format_number(formats, number),
+        });
+    }
+    let Value::DateTime(text) = value else {
+        return Err(mismatch("DateTime"));
+    };
+    let style = style
+        .and_then(DateStyle::parse)
+        .unwrap_or(DateStyle::Medium);
+    let formatted = if format == "time" {
+        format_time(formats, style, text)
+    } else {
+        format_date(formats, style, text)
+    };
+    formatted.map_err(|msg| RuntimeError::Message(format!("arg '{name}': {msg}")))
+}
+
+fn message_arg<'a>(
+    args: &'a HashMap<String, Value>,
+    name: &str,
+    ty: Option<&str>,
+) -> Result<&'a Value, RuntimeError> {
+    let value = args
+        .get(name)
+        .ok_or_else(|| RuntimeError::Message(format!("missing message arg '{name}'")))?;
+    if let Some(ty) = ty {
+        if !matches_type(ty, value) {
+            return Err(RuntimeError::Message(format!(
+                "type mismatch for arg '{name}': expected {ty}, got {}",
+                value_type_name(value)
+            )));
+        }
+    }
+    Ok(value)
 }
 
 fn matches_type(expected: &str, value: &Value) -> bool {
@@ -313,6 +583,7 @@ fn value_type_name(value: &Value) -> &'static str {
         Value::FileHandle(_) => "FileHandle",
         Value::Listener(_) => "Listener",
         Value::Connection(_) => "Connection",
+        Value::UdpSocket(_) => "UdpSocket",
         Value::Stream(_) => "Stream",
         Value::HttpServer(_) => "HttpServer",
         Value::WebSocket(_) => "WebSocket",
@@ -23,6 +23,8 @@ mod url_http;
 mod util;
 
 pub(crate) use core::register_builtins;
+pub(crate) use sockets::tls_server_settings_from_value;
+pub(crate) use streams::{channel_stream, collect_stream, next_chunk, stream_value};
 pub(crate) use util::builtin;
 
 #[cfg(test)]
@@ -1,10 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::io::{Read, Write};
 use std::sync::{Arc, Mutex};
 
 use super::sockets::connection_from_value;
-use super::util::{builtin, expect_int};
-use crate::runtime::values::{StreamHandle, StreamState};
+use super::util::{builtin, expect_bytes, expect_int};
+use crate::runtime::values::{ChannelRecv, StreamHandle, StreamState};
 use crate::runtime::{EffectValue, RuntimeError, Value};
 
 const DEFAULT_STREAM_CHUNK: usize = 4096;
@@ -22,7 +22,21 @@ fn stream_from_value(value: Value, ctx: &str) -> Result<Arc<StreamHandle>, Runti
     }
 }
 
-fn next_chunk(handle: &Arc<StreamHandle>) -> Result<Option<Vec<u8>>, RuntimeError> {
+pub(crate) fn stream_value(state: StreamState) -> Value {
+    Value::Stream(Arc::new(StreamHandle {
+        state: Mutex::new(state),
+    }))
+}
+
+/// A stream of the values received on `receiver`, ending when the channel is closed.
+pub(crate) fn channel_stream(
+    receiver: Arc<ChannelRecv>,
+    encode: fn(Value) -> Result<Vec<u8>, RuntimeError>,
+) -> Value {
+    stream_value(StreamState::Channel { receiver, encode })
+}
+
+pub(crate) fn next_chunk(handle: &Arc<StreamHandle>) -> Result<Option<Vec<u8>>, RuntimeError> {
     let mut guard = handle
         .state
         .lock()
@@ -63,7 +77,43 @@ fn next_chunk(handle: &Arc<StreamHandle>) -> Result<Option<Vec<u8>>, RuntimeErro
                 }
             }
         },
+        StreamState::Buffered { chunks } => Ok(chunks.pop_front()),
+        StreamState::Channel { receiver, encode } => {
+            let receiver = receiver
+                .inner
+                .receiver
+                .lock()
+                .map_err(|_| RuntimeError::Message("channel poisoned".to_string()))?;
+            match receiver.recv() {
+                Ok(value) => encode(value).map(Some),
+                Err(_) => Ok(None),
+            }
+        }
+        StreamState::Body { source } => source()
+            .map(|chunk| chunk.map(|bytes| bytes.to_vec()))
+            .map_err(|err| RuntimeError::Error(stream_error_value(err.message))),
+    }
+}
+
+/// Reads the whole stream, failing once it yields more than `limit` bytes.
+pub(crate) fn collect_stream(
+    handle: &Arc<StreamHandle>,
+    limit: usize,
+) -> Result<Vec<u8>, RuntimeError> {
+    let mut out = Vec::new();
+    while let Some(chunk) = next_chunk(handle)? {
+        if out.len() + chunk.len() > limit {
+            return Err(RuntimeError::Error(stream_error_value(format!(
+                "stream exceeds {limit} bytes"
+            ))));
+        }
+        out.extend_from_slice(&chunk);
     }
+    Ok(out)
+}
+
+fn channel_chunk(value: Value) -> Result<Vec<u8>, RuntimeError> {
+    expect_bytes(value, "streams.fromChannel").map(|bytes| bytes.as_ref().clone())
 }
 
 pub(super) fn build_streams_record() -> Value {
@@ -125,5 +175,71 @@ pub(super) fn build_streams_record() -> Value {
             Ok(Value::Stream(Arc::new(handle)))
         }),
     );
+    fields.insert(
+        "fromChunks".to_string(),
+        builtin("streams.fromChunks", 1, |mut args, _| {
+            let Value::List(items) = args.pop().unwrap() else {
+                return Err(RuntimeError::Message(
+                    "streams.fromChunks expects List Bytes".to_string(),
+                ));
+            };
+            let chunks = items
+                .iter()
+                .map(|item| {
+                    expect_bytes(item.clone(), "streams.fromChunks")
+                        .map(|bytes| bytes.as_ref().clone())
+                })
+                .collect::<Result<VecDeque<_>, _>>()?;
+            Ok(stream_value(StreamState::Buffered { chunks }))
+        }),
+    );
+    fields.insert(
+        "fromChannel".to_string(),
+        builtin("streams.fromChannel", 1, |mut args, _| {
+            let Value::ChannelRecv(receiver) = args.pop().unwrap() else {
+                return Err(RuntimeError::Message(
+                    "streams.fromChannel expects a recv handle".to_string(),
+                ));
+            };
+            Ok(channel_stream(receiver, channel_chunk))
+        }),
+    );
+    fields.insert(
+        "readChunk".to_string(),
+        builtin("streams.readChunk", 1, |mut args, _| {
+            let stream = stream_from_value(args.pop().unwrap(), "streams.readChunk")?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| {
+                    Ok(match next_chunk(&stream)? {
+                        Some(chunk) => Value::Constructor {
+                            name: "Some".to_string(),
+                            args: vec![Value::Bytes(Arc::new(chunk))],
+                        },
+                        None => Value::Constructor {
+                            name: "None".to_string(),
+                            args: Vec::new(),
+                        },
+                    })
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "collect".to_string(),
+        builtin("streams.collect", 2, |mut args, _| {
+            let stream = stream_from_value(args.pop().unwrap(), "streams.collect")?;
+            let limit = expect_int(args.pop().unwrap(), "streams.collect")?;
+            let limit = usize::try_from(limit).map_err(|_| {
+                RuntimeError::Message("streams.collect expects a non-negative limit".to_string())
+            })?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| {
+                    collect_stream(&stream, limit).map(|bytes| Value::Bytes(Arc::new(bytes)))
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
     Value::Record(Arc::new(fields))
 }
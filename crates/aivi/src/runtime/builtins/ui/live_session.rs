@@ -0,0 +1,456 @@
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::runtime::values::ChannelRecv;
+
+/// How often subscription threads re-check their cancel token while idle.
+const SUB_POLL: Duration = Duration::from_millis(25);
+
+/// A live view as started by `ui.live` or `ui.liveApp`.
+///
+/// `ui.live` has `commands: false`: `update` returns the next model. `ui.liveApp` has
+/// `commands: true`: `update` returns `(model, Cmd msg)` and `init_cmd` runs when a browser
+/// session connects.
+#[derive(Clone)]
+struct LiveApp {
+    model: Value,
+    init_cmd: Option<Value>,
+    view: Value,
+    update: Value,
+    subscriptions: Option<Value>,
+    commands: bool,
+}
+
+impl LiveApp {
+    fn from_init(
+        init: Value,
+        view: Value,
+        update: Value,
+        subscriptions: Value,
+        runtime: &mut Runtime,
+    ) -> Result<Self, RuntimeError> {
+        let (model, init_cmd) = expect_model_and_cmd(runtime.force_value(init)?, "init")?;
+        Ok(Self {
+            model,
+            init_cmd: Some(init_cmd),
+            view,
+            update,
+            subscriptions: Some(subscriptions),
+            commands: true,
+        })
+    }
+}
+
+enum SessionEvent {
+    ClientText(String),
+    ClientClosed,
+    ClientFailed(String),
+    /// A message produced by a finished `Cmd`.
+    Dispatch(Value),
+    /// An interval subscription fired.
+    Tick(String),
+    /// A channel subscription received `Result Closed a`.
+    Received(String, Value),
+    Failed(RuntimeError),
+}
+
+enum SubSource {
+    Every(u64),
+    Receive(Arc<ChannelRecv>),
+}
+
+struct RunningSub {
+    cancel: Arc<CancelToken>,
+    /// `msg` for intervals, `Result Closed a -> msg` for channel receives. Replaced whenever
+    /// `subscriptions` is re-evaluated so handlers always see the latest closure.
+    handler: Value,
+}
+
+struct LiveSession {
+    ctx: Arc<RuntimeContext>,
+    cancel: Arc<CancelToken>,
+    socket: Arc<WebSocketHandle>,
+    events: mpsc::Sender<SessionEvent>,
+    app: LiveApp,
+    vnode: Value,
+    handlers: HashMap<i64, UiHandler>,
+    subs: HashMap<String, RunningSub>,
+}
+
+fn run_ws_session(
+    ctx: Arc<RuntimeContext>,
+    socket: WebSocketHandle,
+    app: LiveApp,
+) -> Result<(), RuntimeError> {
+    // Every command and subscription of this browser session runs under `cancel`, so they all
+    // stop once the session ends, whether the client disconnected or `update` failed.
+    let cancel = CancelToken::root();
+    let mut runtime = Runtime::new(ctx.clone(), cancel.clone());
+    let socket = Arc::new(socket);
+    let (events, inbox) = mpsc::channel();
+    spawn_socket_reader(socket.clone(), events.clone());
+
+    let vnode = runtime.apply(app.view.clone(), app.model.clone())?;
+    let handlers = collect_handlers(&vnode, "root");
+    let mut session = LiveSession {
+        ctx,
+        cancel: cancel.clone(),
+        socket: socket.clone(),
+        events,
+        app,
+        vnode,
+        handlers,
+        subs: HashMap::new(),
+    };
+
+    let result = session.run(&mut runtime, inbox);
+    cancel.cancel();
+    let closed = socket
+        .close()
+        .map_err(|err| RuntimeError::Message(err.message));
+    result.and(closed)
+}
+
+impl LiveSession {
+    fn run(
+        &mut self,
+        runtime: &mut Runtime,
+        inbox: mpsc::Receiver<SessionEvent>,
+    ) -> Result<(), RuntimeError> {
+        // No need to send an init message: the initial HTML is delivered via HTTP.
+        if let Some(cmd) = self.app.init_cmd.take() {
+            self.run_cmd(cmd, runtime)?;
+        }
+        self.sync_subscriptions(runtime)?;
+
+        while let Ok(event) = inbox.recv() {
+            let msg = match event {
+                SessionEvent::ClientText(text) if is_hydrate_request(&text) => {
+                    self.send_root()?;
+                    continue;
+                }
+                SessionEvent::ClientText(text) => match self.client_msg(&text, runtime)? {
+                    Some(msg) => msg,
+                    None => continue,
+                },
+                SessionEvent::ClientClosed => break,
+                SessionEvent::ClientFailed(message) => return Err(RuntimeError::Message(message)),
+                SessionEvent::Dispatch(msg) => msg,
+                SessionEvent::Tick(key) => match self.subs.get(&key) {
+                    Some(sub) => sub.handler.clone(),
+                    None => continue,
+                },
+                SessionEvent::Received(key, result) => match self.subs.get(&key) {
+                    Some(sub) => runtime.apply(sub.handler.clone(), result)?,
+                    None => continue,
+                },
+                SessionEvent::Failed(err) => return Err(err),
+            };
+            self.dispatch(msg, runtime)?;
+        }
+        Ok(())
+    }
+
+    fn client_msg(&self, text: &str, runtime: &mut Runtime) -> Result<Option<Value>, RuntimeError> {
+        let event = decode_event_raw(text).map_err(RuntimeError::Message)?;
+        let Some(handler) = self.handlers.get(&event.id()).cloned() else {
+            return Ok(None);
+        };
+        // The event kind is part of the handler id, so a mismatch only comes from a stale
+        // or hand-crafted client message and is ignored.
+        let (f, arg) = match (handler, event) {
+            (UiHandler::Click(msg), DecodedEvent::Click(_))
+            | (UiHandler::Focus(msg), DecodedEvent::Focus(_))
+            | (UiHandler::Blur(msg), DecodedEvent::Blur(_)) => return Ok(Some(msg)),
+            (UiHandler::Input(f), DecodedEvent::Input(_, value))
+            | (UiHandler::Change(f), DecodedEvent::Change(_, value)) => (f, Value::Text(value)),
+            (UiHandler::Check(f), DecodedEvent::Check(_, checked)) => (f, Value::Bool(checked)),
+            (UiHandler::KeyDown(f), DecodedEvent::KeyDown(_, key)) => (f, key_event_value(key)),
+            (UiHandler::Submit(f), DecodedEvent::Submit(_, fields)) => {
+                (f, form_fields_value(fields))
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(runtime.apply(f, arg)?))
+    }
+
+    fn dispatch(&mut self, msg: Value, runtime: &mut Runtime) -> Result<(), RuntimeError> {
+        // update : msg -> model -> model            (ui.live)
+        // update : msg -> model -> (model, Cmd msg) (ui.liveApp)
+        let update_applied = runtime.apply(self.app.update.clone(), msg)?;
+        let updated = runtime.apply(update_applied, self.app.model.clone())?;
+        let cmd = if self.app.commands {
+            let (model, cmd) = expect_model_and_cmd(runtime.force_value(updated)?, "update")?;
+            self.app.model = model;
+            Some(cmd)
+        } else {
+            self.app.model = updated;
+            None
+        };
+
+        let new_vnode = runtime.apply(self.app.view.clone(), self.app.model.clone())?;
+        let mut ops = Vec::new();
+        diff_vnode(&self.vnode, &new_vnode, "root", &mut ops);
+        self.send_ops(ops)?;
+        self.handlers = collect_handlers(&new_vnode, "root");
+        self.vnode = new_vnode;
+
+        if let Some(cmd) = cmd {
+            self.run_cmd(cmd, runtime)?;
+            self.sync_subscriptions(runtime)?;
+        }
+        Ok(())
+    }
+
+    fn send_ops(&self, ops: Vec<Value>) -> Result<(), RuntimeError> {
+        if ops.is_empty() {
+            return Ok(());
+        }
+        let payload = serde_json::json!({
+            "t": "patch",
+            "ops": patch_ops_to_json_value(&Value::List(Arc::new(ops)))?
+        });
+        let text =
+            serde_json::to_string(&payload).map_err(|e| RuntimeError::Message(e.to_string()))?;
+        self.socket
+            .send(AiviWsMessage::TextMsg(text))
+            .map_err(|err| RuntimeError::Message(err.message))
+    }
+
+    /// A hydrating static page may show an older view than this session's, so it gets the
+    /// current one as a whole.
+    fn send_root(&self) -> Result<(), RuntimeError> {
+        let (html, _handlers) = render_vnode(&self.vnode, "root");
+        self.send_ops(vec![Value::Constructor {
+            name: "Replace".to_string(),
+            args: vec![Value::Text("root".to_string()), Value::Text(html)],
+        }])
+    }
+
+    /// Starts every effect in `cmd` on its own thread; results come back as `Dispatch` events.
+    fn run_cmd(&self, cmd: Value, runtime: &mut Runtime) -> Result<(), RuntimeError> {
+        match runtime.force_value(cmd)? {
+            Value::Constructor { name, .. } if name == "CmdNone" => Ok(()),
+            Value::Constructor { name, args } if name == "CmdBatch" && args.len() == 1 => {
+                let Value::List(items) = runtime.force_value(args[0].clone())? else {
+                    return Err(RuntimeError::Message(
+                        "ui.cmdBatch expects a List of Cmd".to_string(),
+                    ));
+                };
+                for item in items.iter() {
+                    self.run_cmd(item.clone(), runtime)?;
+                }
+                Ok(())
+            }
+            Value::Constructor { name, mut args } if name == "CmdPerform" && args.len() == 2 => {
+                let effect = args.pop().unwrap();
+                let to_msg = args.pop().unwrap();
+                let ctx = self.ctx.clone();
+                let cancel = CancelToken::child(self.cancel.clone());
+                let events = self.events.clone();
+                std::thread::spawn(move || {
+                    let mut runtime = Runtime::new(ctx, cancel);
+                    let event = match perform_cmd(&mut runtime, effect, to_msg) {
+                        Ok(msg) => SessionEvent::Dispatch(msg),
+                        Err(RuntimeError::Cancelled) => return,
+                        Err(err) => SessionEvent::Failed(err),
+                    };
+                    let _ = events.send(event);
+                });
+                Ok(())
+            }
+            _ => Err(RuntimeError::Message(
+                "ui.liveApp expects update to return a Cmd".to_string(),
+            )),
+        }
+    }
+
+    /// Re-evaluates `subscriptions` against the current model. Subscriptions that are still
+    /// requested keep running, new ones start and missing ones are cancelled.
+    fn sync_subscriptions(&mut self, runtime: &mut Runtime) -> Result<(), RuntimeError> {
+        let Some(subscriptions) = self.app.subscriptions.clone() else {
+            return Ok(());
+        };
+        let requested = runtime.apply(subscriptions, self.app.model.clone())?;
+        let Value::List(items) = runtime.force_value(requested)? else {
+            return Err(RuntimeError::Message(
+                "ui.liveApp expects subscriptions to return a List of Sub".to_string(),
+            ));
+        };
+
+        let mut wanted = HashMap::new();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for item in items.iter() {
+            let (base, source, handler) = match runtime.force_value(item.clone())? {
+                Value::Constructor { name, mut args } if name == "SubEvery" && args.len() == 2 => {
+                    let handler = args.pop().unwrap();
+                    let Value::Int(millis) = args.pop().unwrap() else {
+                        return Err(RuntimeError::Message(
+                            "ui.subEvery expects Int milliseconds".to_string(),
+                        ));
+                    };
+                    (
+                        format!("every:{millis}"),
+                        SubSource::Every(millis as u64),
+                        handler,
+                    )
+                }
+                Value::Constructor { name, mut args }
+                    if name == "SubReceive" && args.len() == 2 =>
+                {
+                    let handler = args.pop().unwrap();
+                    let Value::ChannelRecv(receiver) = args.pop().unwrap() else {
+                        return Err(RuntimeError::Message(
+                            "ui.subReceive expects a recv handle".to_string(),
+                        ));
+                    };
+                    (
+                        format!("recv:{:p}", Arc::as_ptr(&receiver.inner)),
+                        SubSource::Receive(receiver),
+                        handler,
+                    )
+                }
+                _ => {
+                    return Err(RuntimeError::Message(
+                        "ui.liveApp expects subscriptions to return a List of Sub".to_string(),
+                    ))
+                }
+            };
+            // Identical subscriptions are told apart by position so each keeps its own timer.
+            let count = seen.entry(base.clone()).or_insert(0);
+            let key = format!("{base}#{count}");
+            *count += 1;
+            wanted.insert(key, (source, handler));
+        }
+
+        self.subs.retain(|key, sub| {
+            let keep = wanted.contains_key(key);
+            if !keep {
+                sub.cancel.cancel();
+            }
+            keep
+        });
+        for (key, (source, handler)) in wanted {
+            if let Some(sub) = self.subs.get_mut(&key) {
+                sub.handler = handler;
+                continue;
+            }
+            let cancel = CancelToken::child(self.cancel.clone());
+            spawn_subscription(key.clone(), source, cancel.clone(), self.events.clone());
+            self.subs.insert(key, RunningSub { cancel, handler });
+        }
+        Ok(())
+    }
+}
+
+fn perform_cmd(runtime: &mut Runtime, effect: Value, to_msg: Value) -> Result<Value, RuntimeError> {
+    let result = match runtime.run_effect_value(effect) {
+        Ok(value) => Value::Constructor {
+            name: "Ok".to_string(),
+            args: vec![value],
+        },
+        Err(RuntimeError::Error(err)) => Value::Constructor {
+            name: "Err".to_string(),
+            args: vec![err],
+        },
+        Err(err) => return Err(err),
+    };
+    runtime.apply(to_msg, result)
+}
+
+fn is_hydrate_request(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text)
+        .is_ok_and(|value| value.get("t").and_then(|t| t.as_str()) == Some("hydrate"))
+}
+
+fn expect_model_and_cmd(value: Value, what: &str) -> Result<(Value, Value), RuntimeError> {
+    match value {
+        Value::Tuple(mut items) if items.len() == 2 => {
+            let cmd = items.pop().unwrap();
+            let model = items.pop().unwrap();
+            Ok((model, cmd))
+        }
+        _ => Err(RuntimeError::Message(format!(
+            "ui.liveApp expects {what} to be (model, Cmd msg)"
+        ))),
+    }
+}
+
+fn spawn_socket_reader(socket: Arc<WebSocketHandle>, events: mpsc::Sender<SessionEvent>) {
+    std::thread::spawn(move || loop {
+        let event = match socket.recv() {
+            Ok(AiviWsMessage::TextMsg(text)) => SessionEvent::ClientText(text),
+            Ok(AiviWsMessage::Close) => SessionEvent::ClientClosed,
+            Ok(_) => continue,
+            Err(err) => SessionEvent::ClientFailed(err.message),
+        };
+        let last = !matches!(event, SessionEvent::ClientText(_));
+        if events.send(event).is_err() || last {
+            return;
+        }
+    });
+}
+
+fn spawn_subscription(
+    key: String,
+    source: SubSource,
+    cancel: Arc<CancelToken>,
+    events: mpsc::Sender<SessionEvent>,
+) {
+    std::thread::spawn(move || match source {
+        SubSource::Every(millis) => {
+            let period = Duration::from_millis(millis.max(1));
+            let mut next = Instant::now() + period;
+            while !cancel.is_cancelled() {
+                let now = Instant::now();
+                if now < next {
+                    std::thread::sleep((next - now).min(SUB_POLL));
+                    continue;
+                }
+                if events.send(SessionEvent::Tick(key.clone())).is_err() {
+                    return;
+                }
+                // Skip missed ticks instead of bursting after a slow update.
+                next += period;
+                if next <= now {
+                    next = now + period;
+                }
+            }
+        }
+        SubSource::Receive(receiver) => {
+            while !cancel.is_cancelled() {
+                let received = match receiver.inner.receiver.lock() {
+                    Ok(guard) => guard.recv_timeout(SUB_POLL),
+                    Err(_) => return,
+                };
+                let (result, closed) = match received {
+                    Ok(value) => (
+                        Value::Constructor {
+                            name: "Ok".to_string(),
+                            args: vec![value],
+                        },
+                        false,
+                    ),
+                    Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => (
+                        Value::Constructor {
+                            name: "Err".to_string(),
+                            args: vec![Value::Constructor {
+                                name: "Closed".to_string(),
+                                args: Vec::new(),
+                            }],
+                        },
+                        true,
+                    ),
+                };
+                if events
+                    .send(SessionEvent::Received(key.clone(), result))
+                    .is_err()
+                    || closed
+                {
+                    return;
+                }
+            }
+        }
+    });
+}
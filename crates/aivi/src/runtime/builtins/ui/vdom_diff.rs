@@ -74,11 +74,21 @@ fn diff_vnode(old: &Value, new: &Value, node_id: &str, out: &mut Vec<Value>) {
             }
 
             diff_attrs(&oa[1], &na[1], node_id, out);
+            diff_props(&oa[1], &na[1], node_id, out);
 
-            // If child segment lists differ, replace (simpler, still correct).
             let oseg = child_segments(&oa[2]);
             let nseg = child_segments(&na[2]);
-            if oseg != nseg {
+            if oseg == nseg {
+                if let (Value::List(oc), Value::List(nc)) = (&oa[2], &na[2]) {
+                    for ((ochild, nchild), seg) in oc.iter().zip(nc.iter()).zip(&nseg) {
+                        let child_id = format!("{}/{}", node_id, seg);
+                        diff_vnode(ochild, nchild, &child_id, out);
+                    }
+                }
+                return;
+            }
+            // Duplicate keys make child ids ambiguous; replace the subtree in that case.
+            if !all_unique(&oseg) || !all_unique(&nseg) {
                 let (html, _handlers) = render_vnode(new, node_id);
                 out.push(Value::Constructor {
                     name: "Replace".to_string(),
@@ -86,19 +96,140 @@ fn diff_vnode(old: &Value, new: &Value, node_id: &str, out: &mut Vec<Value>) {
                 });
                 return;
             }
-
             if let (Value::List(oc), Value::List(nc)) = (&oa[2], &na[2]) {
-                for (idx, (ochild, nchild)) in oc.iter().zip(nc.iter()).enumerate() {
-                    let seg = child_segment(nchild, idx);
-                    let child_id = format!("{}/{}", node_id, seg);
-                    diff_vnode(ochild, nchild, &child_id, out);
-                }
+                diff_children(oc, &oseg, nc, &nseg, node_id, out);
             }
         }
         _ => {}
     }
 }
 
+/// Reconciles children by segment (`k:<key>` for keyed children, the index otherwise).
+///
+/// Children missing from `new` are removed first. The children that survive keep their
+/// node ids, so only those outside the longest increasing subsequence of their old
+/// positions are moved; new children are inserted. Positions are walked from the end so
+/// every insert/move lands before a sibling that is already in its final place, and each
+/// op's index is the child position at the time the op is applied.
+fn diff_children(
+    old: &[Value],
+    oseg: &[String],
+    new: &[Value],
+    nseg: &[String],
+    node_id: &str,
+    out: &mut Vec<Value>,
+) {
+    let child_id = |seg: &str| format!("{node_id}/{seg}");
+    let new_index: HashMap<&str, usize> = nseg
+        .iter()
+        .enumerate()
+        .map(|(idx, seg)| (seg.as_str(), idx))
+        .collect();
+
+    // `current` mirrors the client's child order while ops are emitted.
+    let mut current: Vec<&str> = Vec::with_capacity(nseg.len());
+    for seg in oseg {
+        if new_index.contains_key(seg.as_str()) {
+            current.push(seg);
+        } else {
+            out.push(Value::Constructor {
+                name: "RemoveChild".to_string(),
+                args: vec![Value::Text(node_id.to_string()), Value::Text(child_id(seg))],
+            });
+        }
+    }
+
+    let kept_index: HashMap<&str, usize> = current
+        .iter()
+        .enumerate()
+        .map(|(idx, seg)| (*seg, idx))
+        .collect();
+    let kept_in_new_order: Vec<usize> = nseg
+        .iter()
+        .filter_map(|seg| kept_index.get(seg.as_str()).copied())
+        .collect();
+    let stable: HashSet<usize> = longest_increasing_subsequence(&kept_in_new_order)
+        .into_iter()
+        .map(|idx| kept_in_new_order[idx])
+        .collect();
+
+    for (idx, seg) in nseg.iter().enumerate().rev() {
+        let anchor = nseg.get(idx + 1).map(String::as_str);
+        match kept_index.get(seg.as_str()) {
+            Some(old_pos) if stable.contains(old_pos) => continue,
+            Some(_) => {
+                current.retain(|s| *s != seg.as_str());
+                let index = anchor_position(&current, anchor);
+                current.insert(index, seg);
+                out.push(Value::Constructor {
+                    name: "MoveChild".to_string(),
+                    args: vec![
+                        Value::Text(node_id.to_string()),
+                        Value::Text(child_id(seg)),
+                        Value::Int(index as i64),
+                    ],
+                });
+            }
+            None => {
+                let index = anchor_position(&current, anchor);
+                current.insert(index, seg);
+                let (html, _handlers) = render_vnode(&new[idx], &child_id(seg));
+                out.push(Value::Constructor {
+                    name: "InsertChild".to_string(),
+                    args: vec![
+                        Value::Text(node_id.to_string()),
+                        Value::Int(index as i64),
+                        Value::Text(html),
+                    ],
+                });
+            }
+        }
+    }
+
+    let old_by_seg: HashMap<&str, &Value> =
+        oseg.iter().map(String::as_str).zip(old.iter()).collect();
+    for (nchild, seg) in new.iter().zip(nseg) {
+        if let Some(ochild) = old_by_seg.get(seg.as_str()) {
+            diff_vnode(ochild, nchild, &child_id(seg), out);
+        }
+    }
+}
+
+fn anchor_position(current: &[&str], anchor: Option<&str>) -> usize {
+    anchor
+        .and_then(|anchor| current.iter().position(|seg| *seg == anchor))
+        .unwrap_or(current.len())
+}
+
+/// Indices (into `items`) of one longest strictly increasing subsequence.
+fn longest_increasing_subsequence(items: &[usize]) -> Vec<usize> {
+    // `tails[len]` is the index of the smallest tail of an increasing run of length `len + 1`.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut prev: Vec<Option<usize>> = vec![None; items.len()];
+    for (idx, item) in items.iter().enumerate() {
+        let len = tails.partition_point(|&tail| items[tail] < *item);
+        prev[idx] = len.checked_sub(1).map(|p| tails[p]);
+        if len == tails.len() {
+            tails.push(idx);
+        } else {
+            tails[len] = idx;
+        }
+    }
+    let mut out = Vec::with_capacity(tails.len());
+    let mut cursor = tails.last().copied();
+    while let Some(idx) = cursor {
+        out.push(idx);
+        cursor = prev[idx];
+    }
+    out.reverse();
+    out
+}
+
+fn all_unique(segments: &[String]) -> bool {
+    let mut seen = HashSet::with_capacity(segments.len());
+    segments.iter().all(|seg| seen.insert(seg.as_str()))
+}
+
 fn child_segments(children: &Value) -> Vec<String> {
     let Value::List(items) = children else {
         return Vec::new();
@@ -125,7 +256,7 @@ fn attrs_to_map(attrs: &Value, node_id: &str) -> HashMap<String, String> {
         handlers: HashMap::new(),
     };
     // Reuse the attribute renderer to get canonical text values.
-    let s = render_attrs(attrs, node_id, &mut state);
+    let s = render_attrs(&without_props(attrs), node_id, &mut state);
     // Parse ` key="value"` chunks back into a map (safe: all keys are ASCII).
     let mut map = HashMap::new();
     let mut i = 0usize;
@@ -165,6 +296,70 @@ fn attrs_to_map(attrs: &Value, node_id: &str) -> HashMap<String, String> {
     map
 }
 
+fn is_prop_attr(attr: &Value) -> bool {
+    matches!(attr, Value::Constructor { name, args } if (name == "Value" || name == "Checked") && args.len() == 1)
+}
+
+fn without_props(attrs: &Value) -> Value {
+    match attrs {
+        Value::List(items) => Value::List(Arc::new(
+            items
+                .iter()
+                .filter(|attr| !is_prop_attr(attr))
+                .cloned()
+                .collect(),
+        )),
+        other => other.clone(),
+    }
+}
+
+/// DOM properties (`value`, `checked`) keyed by property name, as their patch text.
+fn props_to_map(attrs: &Value) -> HashMap<&'static str, String> {
+    let mut map = HashMap::new();
+    let Value::List(items) = attrs else {
+        return map;
+    };
+    for attr in items.iter() {
+        match attr {
+            Value::Constructor { name, args } if name == "Value" && args.len() == 1 => {
+                if let Value::Text(text) = &args[0] {
+                    map.insert("value", text.clone());
+                }
+            }
+            Value::Constructor { name, args } if name == "Checked" && args.len() == 1 => {
+                if let Value::Bool(checked) = &args[0] {
+                    map.insert("checked", checked.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    map
+}
+
+// Properties are patched instead of set as attributes: once the user has typed into an
+// input, its `value` attribute no longer reflects what is shown.
+fn diff_props(old: &Value, new: &Value, node_id: &str, out: &mut Vec<Value>) {
+    let old_map = props_to_map(old);
+    let new_map = props_to_map(new);
+    for name in ["checked", "value"] {
+        let value = match (old_map.get(name), new_map.get(name)) {
+            (old, Some(new)) if old != Some(new) => new.clone(),
+            (Some(_), None) if name == "checked" => "false".to_string(),
+            (Some(_), None) => String::new(),
+            _ => continue,
+        };
+        out.push(Value::Constructor {
+            name: "SetProp".to_string(),
+            args: vec![
+                Value::Text(node_id.to_string()),
+                Value::Text(name.to_string()),
+                Value::Text(value),
+            ],
+        });
+    }
+}
+
 fn diff_attrs(old: &Value, new: &Value, node_id: &str, out: &mut Vec<Value>) {
     let old_map = attrs_to_map(old, node_id);
     let new_map = attrs_to_map(new, node_id);
@@ -230,6 +425,23 @@ fn patch_ops_to_json_value(value: &Value) -> Result<serde_json::Value, RuntimeEr
             ("RemoveAttr", [Value::Text(id), Value::Text(name)]) => {
                 out.push(serde_json::json!({"op":"removeAttr","id":id,"name":name}));
             }
+            ("InsertChild", [Value::Text(id), Value::Int(index), Value::Text(html)]) => {
+                out.push(serde_json::json!({"op":"insertChild","id":id,"index":index,"html":html}));
+            }
+            ("MoveChild", [Value::Text(id), Value::Text(child), Value::Int(index)]) => {
+                out.push(serde_json::json!({"op":"moveChild","id":id,"child":child,"index":index}));
+            }
+            ("RemoveChild", [Value::Text(id), Value::Text(child)]) => {
+                out.push(serde_json::json!({"op":"removeChild","id":id,"child":child}));
+            }
+            ("SetProp", [Value::Text(id), Value::Text(name), Value::Text(value)]) => {
+                let value = if name == "checked" {
+                    serde_json::Value::Bool(value == "true")
+                } else {
+                    serde_json::Value::String(value.clone())
+                };
+                out.push(serde_json::json!({"op":"setProp","id":id,"name":name,"value":value}));
+            }
             _ => {
                 return Err(RuntimeError::Message(
                     "ui.patchToJson got invalid PatchOp".to_string(),
@@ -240,22 +452,75 @@ fn patch_ops_to_json_value(value: &Value) -> Result<serde_json::Value, RuntimeEr
     Ok(serde_json::Value::Array(out))
 }
 
+struct KeyInfo {
+    key: String,
+    ctrl: bool,
+    shift: bool,
+    alt: bool,
+    meta: bool,
+}
+
 enum DecodedEvent {
     Click(i64),
     Input(i64, String),
+    Change(i64, String),
+    Check(i64, bool),
+    KeyDown(i64, KeyInfo),
+    Submit(i64, Vec<(String, String)>),
+    Focus(i64),
+    Blur(i64),
+}
+
+impl DecodedEvent {
+    fn id(&self) -> i64 {
+        match self {
+            DecodedEvent::Click(id)
+            | DecodedEvent::Input(id, _)
+            | DecodedEvent::Change(id, _)
+            | DecodedEvent::Check(id, _)
+            | DecodedEvent::KeyDown(id, _)
+            | DecodedEvent::Submit(id, _)
+            | DecodedEvent::Focus(id)
+            | DecodedEvent::Blur(id) => *id,
+        }
+    }
+}
+
+fn key_event_value(key: KeyInfo) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert("key".to_string(), Value::Text(key.key));
+    fields.insert("ctrl".to_string(), Value::Bool(key.ctrl));
+    fields.insert("shift".to_string(), Value::Bool(key.shift));
+    fields.insert("alt".to_string(), Value::Bool(key.alt));
+    fields.insert("meta".to_string(), Value::Bool(key.meta));
+    Value::Record(Arc::new(fields))
+}
+
+fn form_fields_value(fields: Vec<(String, String)>) -> Value {
+    let fields = fields
+        .into_iter()
+        .map(|(name, value)| (name, Value::Text(value)))
+        .collect();
+    Value::Record(Arc::new(fields))
 }
 
 fn decode_event(text: &str) -> Result<Value, String> {
     let event = decode_event_raw(text)?;
-    Ok(match event {
-        DecodedEvent::Click(id) => Value::Constructor {
-            name: "Click".to_string(),
-            args: vec![Value::Int(id)],
-        },
-        DecodedEvent::Input(id, value) => Value::Constructor {
-            name: "Input".to_string(),
-            args: vec![Value::Int(id), Value::Text(value)],
-        },
+    let (name, args) = match event {
+        DecodedEvent::Click(id) => ("Click", vec![Value::Int(id)]),
+        DecodedEvent::Input(id, value) => ("Input", vec![Value::Int(id), Value::Text(value)]),
+        DecodedEvent::Change(id, value) => ("Change", vec![Value::Int(id), Value::Text(value)]),
+        DecodedEvent::Check(id, checked) => ("Check", vec![Value::Int(id), Value::Bool(checked)]),
+        DecodedEvent::KeyDown(id, key) => ("KeyDown", vec![Value::Int(id), key_event_value(key)]),
+        DecodedEvent::Submit(id, fields) => {
+            ("Submit", vec![Value::Int(id), form_fields_value(fields)])
+        }
+        DecodedEvent::Focus(id) => ("Focus", vec![Value::Int(id)]),
+        DecodedEvent::Blur(id) => ("Blur", vec![Value::Int(id)]),
+    };
+    Ok(Value::Constructor {
+        name: name.to_string(),
+        args,
     })
 }
 
@@ -273,15 +538,51 @@ fn decode_event_raw(text: &str) -> Result<DecodedEvent, String> {
         .get("id")
         .and_then(|v| v.as_i64())
         .ok_or_else(|| "event.id must be an int".to_string())?;
+    let text_field = |name: &str| {
+        obj.get(name)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| format!("event.{name} must be a string"))
+    };
+    // Modifier flags are optional and default to `False`.
+    let flag = |name: &str| obj.get(name).and_then(|v| v.as_bool()).unwrap_or(false);
     match t {
         "click" => Ok(DecodedEvent::Click(id)),
-        "input" => {
-            let value = obj
-                .get("value")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| "event.value must be a string".to_string())?;
-            Ok(DecodedEvent::Input(id, value.to_string()))
+        "input" => Ok(DecodedEvent::Input(id, text_field("value")?)),
+        "change" => Ok(DecodedEvent::Change(id, text_field("value")?)),
+        "check" => {
+            let checked = obj
+                .get("checked")
+                .and_then(|v| v.as_bool())
+                .ok_or_else(|| "event.checked must be a bool".to_string())?;
+            Ok(DecodedEvent::Check(id, checked))
+        }
+        "keydown" => Ok(DecodedEvent::KeyDown(
+            id,
+            KeyInfo {
+                key: text_field("key")?,
+                ctrl: flag("ctrl"),
+                shift: flag("shift"),
+                alt: flag("alt"),
+                meta: flag("meta"),
+            },
+        )),
+        "submit" => {
+            let fields = obj
+                .get("fields")
+                .and_then(|v| v.as_object())
+                .ok_or_else(|| "event.fields must be an object".to_string())?;
+            let mut out = Vec::with_capacity(fields.len());
+            for (name, value) in fields {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| format!("event.fields.{name} must be a string"))?;
+                out.push((name.clone(), value.to_string()));
+            }
+            Ok(DecodedEvent::Submit(id, out))
         }
+        "focus" => Ok(DecodedEvent::Focus(id)),
+        "blur" => Ok(DecodedEvent::Blur(id)),
         _ => Err("unknown event type".to_string()),
     }
 }
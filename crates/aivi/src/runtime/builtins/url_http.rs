@@ -214,6 +214,7 @@ pub(super) fn build_http_client_record(mode: HttpClientMode) -> Value {
             Ok(Value::Effect(Arc::new(effect)))
         }),
     );
+    fields.insert("client".to_string(), client_builtin(mode));
     Value::Record(Arc::new(fields))
 }
 
@@ -352,3 +353,5 @@ fn http_error_record(message: String) -> Value {
     fields.insert("message".to_string(), Value::Text(message));
     Value::Record(Arc::new(fields))
 }
+
+include!("url_http/client.rs");
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use num_bigint::BigInt;
 use num_rational::BigRational;
@@ -168,3 +169,58 @@ pub(super) fn expect_decimal(value: Value, ctx: &str) -> Result<Decimal, Runtime
         _ => Err(RuntimeError::Message(format!("{ctx} expects Decimal"))),
     }
 }
+
+#[derive(Clone, Copy, Debug)]
+pub(super) enum BackoffPolicy {
+    Fixed(Duration),
+    Exponential { base: Duration, max: Duration },
+}
+
+pub(super) fn span_millis(value: Value, ctx: &str) -> Result<i64, RuntimeError> {
+    let fields = expect_record(value, ctx)?;
+    let millis = fields
+        .get("millis")
+        .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects Span.millis")))?;
+    expect_int(millis.clone(), ctx)
+}
+
+pub(super) fn backoff_policy(value: Value, ctx: &str) -> Result<BackoffPolicy, RuntimeError> {
+    match value {
+        Value::Constructor { name, args } if name == "Fixed" && args.len() == 1 => {
+            let ms = span_millis(args[0].clone(), ctx)?;
+            Ok(BackoffPolicy::Fixed(
+                Duration::from_millis(ms.max(0) as u64),
+            ))
+        }
+        Value::Constructor { name, args } if name == "Exponential" && args.len() == 1 => {
+            let rec = expect_record(args[0].clone(), ctx)?;
+            let base = rec
+                .get("base")
+                .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects Exponential.base")))?;
+            let max = rec
+                .get("max")
+                .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects Exponential.max")))?;
+            let base_ms = span_millis(base.clone(), ctx)?;
+            let max_ms = span_millis(max.clone(), ctx)?;
+            Ok(BackoffPolicy::Exponential {
+                base: Duration::from_millis(base_ms.max(0) as u64),
+                max: Duration::from_millis(max_ms.max(0) as u64),
+            })
+        }
+        other => Err(RuntimeError::Message(format!(
+            "{ctx} expects BackoffPolicy (Fixed|Exponential), got {}",
+            crate::runtime::format_value(&other)
+        ))),
+    }
+}
+
+pub(super) fn backoff_wait(policy: BackoffPolicy, attempt: usize) -> Duration {
+    match policy {
+        BackoffPolicy::Fixed(d) => d,
+        BackoffPolicy::Exponential { base, max } => {
+            let factor = 1u64 << (attempt.min(16) as u32);
+            let dur = base.saturating_mul(factor as u32);
+            std::cmp::min(dur, max)
+        }
+    }
+}
@@ -9,7 +9,7 @@ use url::Url;
 
 use crate::hir::{
     HirBlockItem, HirExpr, HirListItem, HirLiteral, HirMatchArm, HirPathSegment, HirPattern,
-    HirProgram, HirRecordField, HirTextPart,
+    HirProgram, HirRecBinding, HirRecordField, HirTextPart,
 };
 use crate::i18n::{parse_message_template, validate_key_text, MessagePart};
 use crate::AiviError;
@@ -74,6 +74,8 @@ struct Runtime {
     fuel: Option<u64>,
     rng_state: u64,
     debug_stack: Vec<DebugFrame>,
+    /// Handlers installed by `with`, innermost last; each maps operation names to clauses.
+    effect_handlers: Vec<Arc<HashMap<String, Value>>>,
 }
 
 #[derive(Clone)]
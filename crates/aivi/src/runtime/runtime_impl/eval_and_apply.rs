@@ -59,6 +59,12 @@ impl Runtime {
                         last_value = Value::Unit;
                     }
                 }
+                HirBlockItem::LetRec { bindings } => {
+                    self.bind_recursive(bindings, &local_env)?;
+                    if last {
+                        last_value = Value::Unit;
+                    }
+                }
                 HirBlockItem::Expr { expr } => {
                     last_value = self.eval_expr(expr, &local_env)?;
                     if !last {
@@ -77,6 +83,20 @@ impl Runtime {
         Ok(last_value)
     }
 
+    /// Binds a recursive group in `env` itself: the closures capture that scope, so every name
+    /// of the group is visible from every body once the group is bound.
+    fn bind_recursive(
+        &mut self,
+        bindings: &[HirRecBinding],
+        env: &Env,
+    ) -> Result<(), RuntimeError> {
+        for binding in bindings {
+            let value = self.eval_expr(&binding.expr, env)?;
+            env.set(binding.name.clone(), value);
+        }
+        Ok(())
+    }
+
     fn eval_generate_block(
         &mut self,
         items: &[HirBlockItem],
@@ -158,6 +178,9 @@ impl Runtime {
                     let sub_items = self.generator_to_list(sub)?;
                     out.extend(sub_items);
                 }
+                HirBlockItem::LetRec { bindings } => {
+                    self.bind_recursive(bindings, &local_env)?;
+                }
                 HirBlockItem::Recurse { .. } => {
                     // Unsupported for now
                 }
@@ -369,6 +392,9 @@ impl Runtime {
         }
         let op_name = format!("({})", op);
         if let Some(op_value) = env.get(&op_name) {
+            if let Value::MultiClause(clauses) = self.force_value(op_value.clone())? {
+                return self.apply_operator_clauses(clauses, left, right);
+            }
             let applied = self.apply(op_value, left)?;
             return self.apply(applied, right);
         }
@@ -377,6 +403,36 @@ impl Runtime {
         )))
     }
 
+    /// Every domain's definition of an operator shares one global. Like the type checker, take
+    /// the first definition that accepts both operands, without evaluating the rest (which may
+    /// not terminate on operands of another carrier).
+    fn apply_operator_clauses(
+        &mut self,
+        clauses: Vec<Value>,
+        left: Value,
+        right: Value,
+    ) -> Result<Value, RuntimeError> {
+        let mut match_failures = 0;
+        let mut last_error = None;
+        for clause in clauses {
+            let result = self
+                .apply(clause, left.clone())
+                .and_then(|partial| self.apply(partial, right.clone()));
+            match result {
+                Ok(value) => return Ok(value),
+                Err(RuntimeError::Message(message)) if is_match_failure_message(&message) => {
+                    match_failures += 1;
+                }
+                Err(RuntimeError::Cancelled) => return Err(RuntimeError::Cancelled),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        if match_failures > 0 && last_error.is_none() {
+            return Err(RuntimeError::Message("non-exhaustive match".to_string()));
+        }
+        Err(last_error.unwrap_or_else(|| RuntimeError::Message("no matching clause".to_string())))
+    }
+
     fn run_effect_value(&mut self, value: Value) -> Result<Value, RuntimeError> {
         self.check_cancelled()?;
         match value {
@@ -417,8 +473,7 @@ impl Runtime {
                     let value = self.eval_expr(expr, &local_env)?;
                     match value {
                         Value::Resource(resource) => {
-                            let (res_value, cleanup) =
-                                self.acquire_resource(resource, &local_env)?;
+                            let (res_value, cleanup) = self.acquire_resource(resource)?;
                             let bindings = collect_pattern_bindings(pattern, &res_value)
                                 .ok_or_else(|| {
                                     RuntimeError::Message(
@@ -454,6 +509,9 @@ impl Runtime {
                         }
                     }
                 }
+                HirBlockItem::LetRec { bindings } => self
+                    .bind_recursive(bindings, &local_env)
+                    .map(|()| Value::Unit),
                 HirBlockItem::Expr { expr } => {
                     let value = self.eval_expr(expr, &local_env)?;
                     if last {
@@ -11,6 +11,7 @@ impl Runtime {
             fuel: None,
             rng_state: seed ^ 0x9E37_79B9_7F4A_7C15,
             debug_stack: Vec::new(),
+            effect_handlers: Vec::new(),
         }
     }
 
@@ -526,6 +527,7 @@ impl Runtime {
                 }
                 crate::hir::HirBlockKind::Resource => {
                     Ok(Value::Resource(Arc::new(ResourceValue {
+                        env: env.clone(),
                         items: Arc::new(items.clone()),
                     })))
                 }
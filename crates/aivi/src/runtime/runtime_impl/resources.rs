@@ -2,9 +2,9 @@ impl Runtime {
     fn acquire_resource(
         &mut self,
         resource: Arc<ResourceValue>,
-        env: &Env,
     ) -> Result<(Value, Value), RuntimeError> {
-        let local_env = Env::new(Some(env.clone()));
+        // Resource blocks see the scope they were written in, not the one they are bound in.
+        let local_env = Env::new(Some(resource.env.clone()));
         let items = resource.items.as_ref();
         let mut yielded = None;
         let mut cleanup_start = None;
@@ -32,6 +32,9 @@ impl Runtime {
                         }
                     }
                 }
+                HirBlockItem::LetRec { bindings } => {
+                    self.bind_recursive(bindings, &local_env)?;
+                }
                 HirBlockItem::Yield { expr } => {
                     let value = self.eval_expr(expr, &local_env)?;
                     yielded = Some(value);
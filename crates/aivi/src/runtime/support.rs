@@ -387,6 +387,7 @@ fn debug_value_to_json(value: &Value, depth: usize) -> serde_json::Value {
         Value::FileHandle(_) => debug_summary_json(value),
         Value::Listener(_) => debug_summary_json(value),
         Value::Connection(_) => debug_summary_json(value),
+        Value::UdpSocket(_) => debug_summary_json(value),
         Value::Stream(_) => debug_summary_json(value),
         Value::HttpServer(_) => debug_summary_json(value),
         Value::WebSocket(_) => debug_summary_json(value),
@@ -437,6 +438,7 @@ fn debug_summary_json(value: &Value) -> serde_json::Value {
         Value::FileHandle(_) => ("File", None),
         Value::Listener(_) => ("Listener", None),
         Value::Connection(_) => ("Connection", None),
+        Value::UdpSocket(_) => ("UdpSocket", None),
         Value::Stream(_) => ("Stream", None),
         Value::HttpServer(_) => ("HttpServer", None),
         Value::WebSocket(_) => ("WebSocket", None),
@@ -520,6 +522,7 @@ fn format_value(value: &Value) -> String {
         Value::FileHandle(_) => "<file>".to_string(),
         Value::Listener(_) => "<listener>".to_string(),
         Value::Connection(_) => "<connection>".to_string(),
+        Value::UdpSocket(_) => "<udp-socket>".to_string(),
         Value::Stream(_) => "<stream>".to_string(),
         Value::HttpServer(_) => "<http-server>".to_string(),
         Value::WebSocket(_) => "<websocket>".to_string(),
@@ -579,33 +582,78 @@ fn url_to_record(url: &Url) -> HashMap<String, Value> {
 }
 
 fn i18n_message_parts_value(parts: &[MessagePart]) -> Value {
-    let mut out = Vec::with_capacity(parts.len());
-    for part in parts {
-        match part {
-            MessagePart::Lit(text) => {
-                out.push(Value::Record(Arc::new(HashMap::from([
-                    ("kind".to_string(), Value::Text("lit".to_string())),
-                    ("text".to_string(), Value::Text(text.clone())),
-                ]))));
-            }
-            MessagePart::Hole { name, ty } => {
-                let ty_value = match ty {
-                    Some(t) => Value::Constructor {
-                        name: "Some".to_string(),
-                        args: vec![Value::Text(t.clone())],
-                    },
-                    None => Value::Constructor {
-                        name: "None".to_string(),
-                        args: Vec::new(),
-                    },
-                };
-                out.push(Value::Record(Arc::new(HashMap::from([
-                    ("kind".to_string(), Value::Text("hole".to_string())),
-                    ("name".to_string(), Value::Text(name.clone())),
-                    ("ty".to_string(), ty_value),
-                ]))));
-            }
-        }
-    }
-    Value::List(Arc::new(out))
+    Value::List(Arc::new(
+        parts.iter().map(i18n_message_part_value).collect(),
+    ))
+}
+
+fn i18n_message_part_value(part: &MessagePart) -> Value {
+    let text = |value: &str| Value::Text(value.to_string());
+    let ty_value = |ty: &Option<String>| match ty {
+        Some(t) => Value::Constructor {
+            name: "Some".to_string(),
+            args: vec![Value::Text(t.clone())],
+        },
+        None => Value::Constructor {
+            name: "None".to_string(),
+            args: Vec::new(),
+        },
+    };
+    let arms_value = |arms: &[crate::i18n::MessageArm]| {
+        Value::List(Arc::new(
+            arms.iter()
+                .map(|arm| {
+                    Value::Record(Arc::new(HashMap::from([
+                        ("selector".to_string(), text(&arm.selector)),
+                        ("parts".to_string(), i18n_message_parts_value(&arm.parts)),
+                    ])))
+                })
+                .collect(),
+        ))
+    };
+    let fields = match part {
+        MessagePart::Lit(value) => vec![("kind", text("lit")), ("text", text(value))],
+        MessagePart::Hole { name, ty } => vec![
+            ("kind", text("hole")),
+            ("name", text(name)),
+            ("ty", ty_value(ty)),
+        ],
+        MessagePart::Pound => vec![("kind", text("pound"))],
+        MessagePart::Plural {
+            name,
+            ty,
+            ordinal,
+            arms,
+        } => vec![
+            ("kind", text("plural")),
+            ("name", text(name)),
+            ("ty", ty_value(ty)),
+            ("ordinal", Value::Bool(*ordinal)),
+            ("arms", arms_value(arms)),
+        ],
+        MessagePart::Select { name, ty, arms } => vec![
+            ("kind", text("select")),
+            ("name", text(name)),
+            ("ty", ty_value(ty)),
+            ("arms", arms_value(arms)),
+        ],
+        MessagePart::Format {
+            name,
+            ty,
+            format,
+            style,
+        } => vec![
+            ("kind", text("format")),
+            ("name", text(name)),
+            ("ty", ty_value(ty)),
+            ("format", text(format)),
+            ("style", ty_value(style)),
+        ],
+    };
+    Value::Record(Arc::new(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    ))
 }
@@ -90,6 +90,31 @@ fn cleanups_run_even_when_cancelled() {
     assert!(ran.load(Ordering::SeqCst));
 }
 
+#[test]
+fn resource_blocks_capture_their_scope() {
+    let source = r#"
+module test.resource_scope
+export main
+
+counter = start => resource {
+  value <- pure (start + 1)
+  yield value
+}
+
+main = effect {
+  value <- counter 41
+  pure value
+}
+"#;
+    let mut runtime = runtime_from_source(source);
+    let main = runtime.ctx.globals.get("main").expect("main");
+    let main = expect_ok(runtime.force_value(main), "main");
+    match expect_ok(runtime.run_effect_value(main), "run main") {
+        Value::Int(value) => assert_eq!(value, 42),
+        _ => panic!("expected Int"),
+    }
+}
+
 #[test]
 fn text_interpolation_evaluates() {
     let source = r#"
@@ -166,6 +191,1004 @@ rendered = i18n.render m { name: "Alice" } or "ERR"
     assert!(matches!(rendered, Value::Text(t) if t == "Hello, Alice!"));
 }
 
+#[test]
+fn i18n_plural_and_select_forms_render() {
+    let source = r#"
+module test.i18nPlural
+items = ~m"{count, plural, =0 {no items} one {# item} other {# items}}"
+place = ~m"{n, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}"
+files = ~m"{count, plural, one {# файл} few {# файла} many {# файлов} other {# файла}}"
+invite = ~m"{who, select, she {She} other {They}} invited {guests, plural, one {one guest} other {# guests}}."
+ru = { language: "ru", region: None, variants: [], tag: "ru" }
+zero = i18n.render items { count: 0 } or "ERR"
+one = i18n.render items { count: 1 } or "ERR"
+many = i18n.render items { count: 5 } or "ERR"
+third = i18n.render place { n: 23 } or "ERR"
+eleventh = i18n.render place { n: 11 } or "ERR"
+ruFew = i18n.renderIn ru files { count: 22 } or "ERR"
+ruMany = i18n.renderIn ru files { count: 11 } or "ERR"
+invited = i18n.render invite { who: "she", guests: 3 } or "ERR"
+"#;
+    let mut runtime = runtime_from_source(source);
+
+    for (name, want) in [
+        ("zero", "no items"),
+        ("one", "1 item"),
+        ("many", "5 items"),
+        ("third", "23rd"),
+        ("eleventh", "11th"),
+        ("ruFew", "22 файла"),
+        ("ruMany", "11 файлов"),
+        ("invited", "She invited 3 guests."),
+    ] {
+        let value = runtime.ctx.globals.get(name).unwrap();
+        let value = expect_ok(runtime.force_value(value), name);
+        assert!(
+            matches!(&value, Value::Text(t) if t == want),
+            "{name}: expected {want:?}, got {}",
+            format_value(&value)
+        );
+    }
+}
+
+#[test]
+fn i18n_messages_format_numbers_and_dates_per_locale() {
+    let source = r#"
+module test.i18nFormat
+total = ~m"Total: {amount:Float} ({share, number, percent})"
+price = ~m"{amount, number, currency/EUR}"
+due = ~m"Due {when, date, long} at {when, time, short}"
+stamp = ~m"{when:DateTime}"
+de = { language: "de", region: Some "DE", variants: [], tag: "de-DE" }
+en = { language: "en", region: None, variants: [], tag: "en" }
+deTotal = i18n.renderIn de total { amount: 1234.5, share: 0.25 } or "ERR"
+enTotal = i18n.renderIn en total { amount: 1234.5, share: 0.25 } or "ERR"
+neutralTotal = i18n.render total { amount: 1234.5, share: 0.25 } or "ERR"
+dePrice = i18n.renderIn de price { amount: 1234.5 } or "ERR"
+deDue = i18n.renderIn de due { when: 2024-03-05T14:07:09Z } or "ERR"
+enStamp = i18n.renderIn en stamp { when: 2024-03-05T14:07:09Z } or "ERR"
+"#;
+    let mut runtime = runtime_from_source(source);
+
+    for (name, want) in [
+        ("deTotal", "Total: 1.234,5 (25\u{a0}%)"),
+        ("enTotal", "Total: 1,234.5 (25%)"),
+        ("neutralTotal", "Total: 1234.5 (25%)"),
+        ("dePrice", "1.234,50\u{a0}€"),
+        ("deDue", "Due 5. März 2024 at 14:07"),
+        ("enStamp", "Mar 5, 2024, 2:07:09 PM"),
+    ] {
+        let value = runtime.ctx.globals.get(name).unwrap();
+        let value = expect_ok(runtime.force_value(value), name);
+        assert!(
+            matches!(&value, Value::Text(t) if t == want),
+            "{name}: expected {want:?}, got {}",
+            format_value(&value)
+        );
+    }
+}
+
+#[test]
+fn calendar_zones_follow_dst_rules() {
+    let source = r#"
+module test.calendarZones
+use aivi.calendar
+
+newYork = timeZone "America/New_York" or utc
+standup = fromLocal newYork { date: { year: 2024, month: 3, day: 9 }, time: { hour: 9, minute: 0, second: 0 } }
+nextStandup = zonedText (calendar.shift standup (Day 1))
+dayLater = zonedText (calendar.shift standup (Hour 24))
+skipped = zonedText (fromLocal newYork { date: { year: 2024, month: 3, day: 10 }, time: { hour: 2, minute: 30, second: 0 } })
+repeated = zonedText (fromLocal newYork { date: { year: 2024, month: 11, day: 3 }, time: { hour: 1, minute: 30, second: 0 } })
+utcStandup = instantText (toInstant (calendar.shift standup (Day 1)))
+tokyo = zonedText (atZone (timeZone "Asia/Tokyo" or utc) 2024-05-21T12:00:00Z)
+fixed = zonedText (atZone (timeZone "+05:30" or utc) 2024-05-21T12:00:00Z)
+reparsed = (parseZoned "2024-03-10T03:30:00-04:00[America/New_York]") ?
+  | Ok value => instantText (toInstant value)
+  | Err msg => msg
+badOffset = (parseZoned "2024-03-10T03:30:00-05:00[America/New_York]") ?
+  | Ok _ => "accepted"
+  | Err _ => "rejected"
+badZone = (timeZone "Mars/Olympus") ?
+  | Ok _ => "accepted"
+  | Err msg => msg
+instant = instantText (calendar.shift (parseInstant "2024-05-21T14:00:00+02:00" or 2000-01-01T00:00:00Z) (Hour 36))
+closing = timeText (calendar.shift { hour: 23, minute: 30, second: 0 } (Hour 1))
+opening = timeText (calendar.shift { hour: 0, minute: 15, second: 0 } (Minute (-30)))
+local = (parseLocalDateTime "2025-01-31T23:30:00") ?
+  | Ok value => localDateTimeText (calendar.shift (calendar.shift value (Minute 45)) (Month 1))
+  | Err msg => msg
+date = dateText (calendar.shift (calendar.shift { year: 2025, month: 1, day: 31 } (Month 1)) (Hour 25))
+"#;
+    let mut runtime = runtime_from_source_with_stdlib(source);
+
+    for (name, want) in [
+        ("nextStandup", "2024-03-10T09:00:00-04:00[America/New_York]"),
+        ("dayLater", "2024-03-10T10:00:00-04:00[America/New_York]"),
+        ("skipped", "2024-03-10T03:30:00-04:00[America/New_York]"),
+        ("repeated", "2024-11-03T01:30:00-04:00[America/New_York]"),
+        ("utcStandup", "2024-03-10T13:00:00Z"),
+        ("tokyo", "2024-05-21T21:00:00+09:00[Asia/Tokyo]"),
+        ("fixed", "2024-05-21T17:30:00+05:30"),
+        ("reparsed", "2024-03-10T07:30:00Z"),
+        ("badOffset", "rejected"),
+        ("badZone", "unknown time zone: Mars/Olympus"),
+        ("instant", "2024-05-23T00:00:00Z"),
+        ("closing", "00:30:00"),
+        ("opening", "23:45:00"),
+        ("local", "2025-03-01T00:15:00"),
+        ("date", "2025-03-01"),
+    ] {
+        let value = runtime.ctx.globals.get(name).unwrap();
+        let value = expect_ok(runtime.force_value(value), name);
+        assert!(
+            matches!(&value, Value::Text(t) if t == want),
+            "{name}: expected {want:?}, got {}",
+            format_value(&value)
+        );
+    }
+}
+
+#[test]
+fn calendar_operators_shift_each_carrier() {
+    let source = r#"
+module test.calendarOperators
+use aivi
+use aivi.calendar
+
+leapDay = dateText ({ year: 2024, month: 1, day: 31 } + 1m)
+dayBefore = dateText ({ year: 2024, month: 3, day: 1 } - 1d)
+later = instantText (fromDateTime 2024-05-21T14:00:00Z + 36h)
+closing = timeText ({ hour: 23, minute: 30, second: 0 } + 1h)
+zonedLater = zonedText (atZone (timeZone "Asia/Tokyo" or utc) 2024-05-21T12:00:00Z + 1d)
+"#;
+    let mut runtime = runtime_from_source_with_stdlib(source);
+
+    for (name, want) in [
+        ("leapDay", "2024-02-29"),
+        ("dayBefore", "2024-02-29"),
+        ("later", "2024-05-23T02:00:00Z"),
+        ("closing", "00:30:00"),
+        ("zonedLater", "2024-05-22T21:00:00+09:00[Asia/Tokyo]"),
+    ] {
+        let value = runtime.ctx.globals.get(name).unwrap();
+        let value = expect_ok(runtime.force_value(value), name);
+        assert!(
+            matches!(&value, Value::Text(t) if t == want),
+            "{name}: expected {want:?}, got {}",
+            format_value(&value)
+        );
+    }
+}
+
+/// Reads one server frame (servers never mask) and returns its opcode and payload.
+fn read_ws_frame(stream: &mut std::net::TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
+    use std::io::Read;
+    let mut head = [0u8; 2];
+    stream.read_exact(&mut head)?;
+    let len = match head[1] & 0x7f {
+        126 => {
+            let mut ext = [0u8; 2];
+            stream.read_exact(&mut ext)?;
+            u16::from_be_bytes(ext) as usize
+        }
+        127 => {
+            let mut ext = [0u8; 8];
+            stream.read_exact(&mut ext)?;
+            u64::from_be_bytes(ext) as usize
+        }
+        len => len as usize,
+    };
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload)?;
+    Ok((head[0] & 0x0f, payload))
+}
+
+#[test]
+fn ui_diff_reconciles_keyed_children() {
+    let source = r#"
+module test.keyed_diff
+row = key label => Keyed key (Element "li" [] [TextNode label])
+list = rows => Element "ul" [] rows
+
+reordered = ui.patchToJson (ui.diff (list [row "a" "a", row "b" "b", row "c" "c", row "d" "d"]) (list [row "d" "d", row "a" "a", row "c" "c!", row "e" "e"]))
+appended = ui.patchToJson (ui.diff (list [TextNode "x"]) (list [TextNode "x", TextNode "y"]))
+"#;
+    let mut runtime = runtime_from_source(source);
+    let mut ops = |name: &str| -> Vec<serde_json::Value> {
+        let value = runtime.ctx.globals.get(name).expect(name);
+        match expect_ok(runtime.force_value(value), name) {
+            Value::Text(json) => serde_json::from_str(&json).expect("patch json"),
+            _ => panic!("expected Text for {name}"),
+        }
+    };
+
+    let reordered = ops("reordered");
+    let summary: Vec<(String, String)> = reordered
+        .iter()
+        .map(|op| {
+            let kind = op["op"].as_str().unwrap_or_default().to_string();
+            let target = match kind.as_str() {
+                "insertChild" => format!("{}", op["index"]),
+                "moveChild" => format!("{}@{}", op["child"].as_str().unwrap(), op["index"]),
+                "removeChild" => op["child"].as_str().unwrap().to_string(),
+                _ => op["id"].as_str().unwrap().to_string(),
+            };
+            (kind, target)
+        })
+        .collect();
+    let expected = [
+        ("removeChild", "root/k:b"),
+        ("insertChild", "3"),
+        ("moveChild", "root/k:d@0"),
+        ("setText", "root/k:c/0"),
+    ];
+    assert_eq!(
+        summary,
+        expected
+            .iter()
+            .map(|(kind, target)| (kind.to_string(), target.to_string()))
+            .collect::<Vec<_>>()
+    );
+    let inserted = reordered[1]["html"].as_str().expect("html");
+    assert!(
+        inserted.contains("data-aivi-node=\"root/k:e\""),
+        "{inserted}"
+    );
+
+    let appended = ops("appended");
+    assert_eq!(appended.len(), 1, "{appended:?}");
+    assert_eq!(appended[0]["op"], "insertChild");
+    assert_eq!(appended[0]["index"], 1);
+}
+
+#[test]
+fn ui_static_site_renders_audits_and_writes_pages() {
+    let out_dir = std::env::temp_dir().join(format!("aivi-ssg-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&out_dir);
+    let source = r#"
+module test.static_site
+export main
+
+head = title => { title: title, meta: [{ name: "description", content: "a \"b\"" }], links: [{ rel: "stylesheet", href: "/site.css" }], styles: [{ selector: ".hero", style: { color: "red" } }, { selector: "</style>", style: { color: "blue" } }] }
+home = { route: "/", head: head "A & <B>", body: Element "main" [Class "hero", Attr "onclick" "go()"] [TextNode "1 < 2", Element "a" [Attr "href" " JavaScript:alert(1)"] [TextNode "x"]] }
+docs = { route: "/docs/intro", head: head "Intro", body: TextNode "intro" }
+feed = { route: "/feed.xml", head: head "Feed", body: TextNode "feed" }
+
+static = ui.renderPage None home
+hydrated = ui.renderPage (Some "/live") docs
+issues = ui.auditPage home
+main = ui.buildSite { outDir: "OUT_DIR", hydrate: Some "/live" } [home, docs, feed]
+"#
+    .replace("OUT_DIR", &out_dir.to_string_lossy());
+    let mut runtime = runtime_from_source(&source);
+    let mut global = |name: &str| {
+        let value = runtime.ctx.globals.get(name).expect(name);
+        expect_ok(runtime.force_value(value), name)
+    };
+    let text = |value: Value| match value {
+        Value::Text(text) => text,
+        _ => panic!("expected Text"),
+    };
+
+    let html = text(global("static"));
+    assert!(html.starts_with("<!doctype html>"), "{html}");
+    assert!(html.contains("<title>A &amp; &lt;B&gt;</title>"), "{html}");
+    assert!(
+        html.contains(r#"<meta name="description" content="a &quot;b&quot;">"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<link rel="stylesheet" href="/site.css">"#),
+        "{html}"
+    );
+    assert!(
+        html.contains("<style>.hero { color: red }</style>"),
+        "{html}"
+    );
+    assert!(html.contains("1 &lt; 2"), "{html}");
+    assert!(!html.contains("<script"), "{html}");
+    let hydrated = text(global("hydrated"));
+    assert!(hydrated.contains("<script>"), "{hydrated}");
+    assert!(hydrated.contains("\"/live\""), "{hydrated}");
+
+    let Value::List(issues) = global("issues") else {
+        panic!("expected List for issues");
+    };
+    let messages: Vec<String> = issues
+        .iter()
+        .map(|issue| match issue {
+            Value::Record(fields) => text(fields.get("message").cloned().expect("message")),
+            _ => panic!("expected audit record"),
+        })
+        .collect();
+    assert_eq!(messages.len(), 3, "{messages:?}");
+    assert!(
+        messages[0].contains("`</style>` is dropped"),
+        "{messages:?}"
+    );
+    assert!(messages[1].contains("`onclick`"), "{messages:?}");
+    assert!(messages[2].contains("script URL"), "{messages:?}");
+
+    let main = global("main");
+    let report = expect_ok(runtime.run_effect_value(main), "build site");
+    let Value::Record(report) = report else {
+        panic!("expected site report");
+    };
+    let Some(Value::List(files)) = report.get("files") else {
+        panic!("expected files");
+    };
+    let files: Vec<String> = files.iter().cloned().map(text).collect();
+    assert_eq!(files, ["index.html", "docs/intro/index.html", "feed.xml"]);
+    let Some(Value::List(audit)) = report.get("audit") else {
+        panic!("expected audit");
+    };
+    // The shared head's unsafe selector is reported once per page.
+    assert_eq!(audit.len(), 5);
+    let written = std::fs::read_to_string(out_dir.join("docs/intro/index.html")).expect("page");
+    assert!(written.contains("<title>Intro</title>"), "{written}");
+    assert!(written.contains("t:'hydrate'"), "{written}");
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn live_app_runs_commands_and_stops_dropped_subscriptions() {
+    use std::io::{Read, Write};
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port")
+        .port();
+    let source = r#"
+module test.live_app
+export main
+
+view = model => Element "span" [] [TextNode "ticks {model}"]
+
+step = msg model => msg ?
+  | Loaded (Ok n) => (n, ui.cmdNone)
+  | Loaded (Err _) => (model, ui.cmdNone)
+  | Tick => (model + 1, ui.cmdNone)
+
+ticking = model => if model < 3 then [ui.subEvery 10 Tick] else []
+
+main = ui.liveApp { address: "127.0.0.1:PORT", path: "/", title: "Ticks" } (0, ui.cmdPerform Loaded (pure 1)) view step ticking
+"#
+    .replace("PORT", &port.to_string());
+    let mut runtime = runtime_from_source(&source);
+    let main = runtime.ctx.globals.get("main").expect("main");
+    let main = expect_ok(runtime.force_value(main), "main");
+    let server = match expect_ok(runtime.run_effect_value(main), "start server") {
+        Value::HttpServer(server) => server,
+        _ => panic!("expected server"),
+    };
+
+    // The server binds its listener in the background.
+    let mut stream = (0..100)
+        .find_map(|_| {
+            std::net::TcpStream::connect(("127.0.0.1", port))
+                .map_err(|_| std::thread::sleep(Duration::from_millis(20)))
+                .ok()
+        })
+        .expect("connect");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .expect("timeout");
+    write!(
+        stream,
+        "GET /ws HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n"
+    )
+    .expect("handshake");
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        let mut byte = [0u8; 1];
+        stream.read_exact(&mut byte).expect("handshake response");
+        response.push(byte[0]);
+    }
+    assert!(String::from_utf8_lossy(&response).starts_with("HTTP/1.1 101"));
+
+    // The init command and the interval subscription both patch the text; the subscription is
+    // dropped once the model reaches 3, so no patch follows.
+    loop {
+        let (opcode, payload) = read_ws_frame(&mut stream).expect("patch frame");
+        assert_eq!(opcode, 1, "expected a text frame");
+        if String::from_utf8_lossy(&payload).contains("ticks 3") {
+            break;
+        }
+    }
+    stream
+        .set_read_timeout(Some(Duration::from_millis(200)))
+        .expect("timeout");
+    assert!(
+        read_ws_frame(&mut stream).is_err(),
+        "expected no patches after the subscription stopped"
+    );
+
+    // Masked close frame with an empty payload.
+    stream
+        .write_all(&[0x88, 0x80, 0, 0, 0, 0])
+        .expect("close frame");
+    server.stop().expect("stop server");
+}
+
+#[test]
+fn http_server_routes_and_middleware() {
+    use std::io::{Read, Write};
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port")
+        .port();
+    let dir = std::env::temp_dir().join(format!("aivi-http-static-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("static dir");
+    let page = format!("hello {}", "static ".repeat(300));
+    std::fs::write(dir.join("page.txt"), &page).expect("static file");
+
+    let source = r#"
+module test.http_routing
+export main, cookieHeader, badCookie
+
+describe = value => value ?
+  | IntParam n => "int {n}"
+  | TextParam text => "text {text}"
+
+firstParam = params => params ?
+  | [p] => describe p.value
+  | _ => "none"
+
+staticPath = params => params ?
+  | [{ name: _, value: TextParam path }] => path
+  | _ => ""
+
+empty = Buffered (text.toBytes Utf8 "")
+
+reply = headers => pure (Http { status: 200, headers: headers, body: empty })
+
+showUser = params req => reply [{ name: "x-id", value: firstParam params }]
+echoQuery = params req => reply (httpServer.query req)
+echoCookies = params req => reply (httpServer.cookies req)
+echoForm = params req => httpServer.form req ?
+  | Ok fields => reply fields
+  | Err e => pure (Http { status: 400, headers: [], body: empty })
+files = params req => httpServer.serveStatic "DIR" (staticPath params) req
+
+routes = [
+  { method: "GET", pattern: "/users/:id:Int", handler: showUser },
+  { method: "GET", pattern: "/echo", handler: echoQuery },
+  { method: "GET", pattern: "/cookies", handler: echoCookies },
+  { method: "POST", pattern: "/form", handler: echoForm },
+  { method: "GET", pattern: "/static/*file", handler: files }
+]
+
+corsConfig = { origins: ["https://app.example"], methods: ["GET", "POST"], headers: [], credentials: False, maxAge: Some 600 }
+
+app = req => httpServer.cors corsConfig (httpServer.compression (httpServer.route routes)) req
+
+main = httpServer.listen { address: "127.0.0.1:PORT" } app
+
+cookieHeader = httpServer.setCookie { name: "sid", value: "a b;c", path: Some "/", domain: None, maxAge: Some 60, httpOnly: True, secure: True, sameSite: Some "lax" }
+badCookie = httpServer.setCookie { name: "bad name", value: "x", path: None, domain: None, maxAge: None, httpOnly: False, secure: False, sameSite: None }
+"#
+    .replace("PORT", &port.to_string())
+    .replace("DIR", &dir.to_string_lossy());
+    let mut runtime = runtime_from_source(&source);
+
+    let cookie = runtime
+        .ctx
+        .globals
+        .get("cookieHeader")
+        .expect("cookieHeader");
+    match expect_ok(runtime.force_value(cookie), "cookieHeader") {
+        Value::Constructor { name, args } if name == "Ok" => {
+            let Value::Record(header) = &args[0] else {
+                panic!("expected Header");
+            };
+            assert!(matches!(
+                header.get("value"),
+                Some(Value::Text(value))
+                    if value == "sid=a%20b%3Bc; Path=/; Max-Age=60; SameSite=Lax; HttpOnly; Secure"
+            ));
+        }
+        other => panic!("expected Ok header, got {}", format_value(&other)),
+    }
+    let bad = runtime.ctx.globals.get("badCookie").expect("badCookie");
+    assert!(matches!(
+        expect_ok(runtime.force_value(bad), "badCookie"),
+        Value::Constructor { name, .. } if name == "Err"
+    ));
+
+    let main = runtime.ctx.globals.get("main").expect("main");
+    let main = expect_ok(runtime.force_value(main), "main");
+    let server = match expect_ok(runtime.run_effect_value(main), "start server") {
+        Value::HttpServer(server) => server,
+        _ => panic!("expected server"),
+    };
+    let request = |head: &str, body: &str| -> String {
+        // The server binds its listener in the background.
+        let mut stream = (0..100)
+            .find_map(|_| {
+                std::net::TcpStream::connect(("127.0.0.1", port))
+                    .map_err(|_| std::thread::sleep(Duration::from_millis(20)))
+                    .ok()
+            })
+            .expect("connect");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .expect("timeout");
+        write!(
+            stream,
+            "{head}\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .expect("request");
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).expect("response");
+        String::from_utf8_lossy(&response).to_lowercase()
+    };
+
+    let user = request("GET /users/42 HTTP/1.1", "");
+    assert!(user.starts_with("http/1.1 200"), "{user}");
+    assert!(user.contains("x-id: int 42"), "{user}");
+    let not_int = request("GET /users/abc HTTP/1.1", "");
+    assert!(not_int.starts_with("http/1.1 404"), "{not_int}");
+    let wrong_method = request("DELETE /users/42 HTTP/1.1", "");
+    assert!(wrong_method.starts_with("http/1.1 405"), "{wrong_method}");
+    assert!(wrong_method.contains("allow: get, head"), "{wrong_method}");
+
+    let echo = request(
+        "GET /echo?q=a%20b&page=2 HTTP/1.1\r\nOrigin: https://app.example",
+        "",
+    );
+    assert!(
+        echo.contains("q: a b") && echo.contains("page: 2"),
+        "{echo}"
+    );
+    assert!(
+        echo.contains("access-control-allow-origin: https://app.example"),
+        "{echo}"
+    );
+    let cookies = request(
+        "GET /cookies HTTP/1.1\r\nCookie: sid=abc; theme=\"dark\"",
+        "",
+    );
+    assert!(
+        cookies.contains("sid: abc") && cookies.contains("theme: dark"),
+        "{cookies}"
+    );
+    let form = request(
+        "POST /form HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded",
+        "name=Ada+L&lang=aivi",
+    );
+    assert!(
+        form.contains("name: ada l") && form.contains("lang: aivi"),
+        "{form}"
+    );
+    let preflight = request(
+        "OPTIONS /form HTTP/1.1\r\nOrigin: https://app.example\r\nAccess-Control-Request-Method: POST",
+        "",
+    );
+    assert!(preflight.starts_with("http/1.1 204"), "{preflight}");
+    assert!(
+        preflight.contains("access-control-allow-methods: get, post")
+            && preflight.contains("access-control-max-age: 600"),
+        "{preflight}"
+    );
+
+    let range = request("GET /static/page.txt HTTP/1.1\r\nRange: bytes=0-4", "");
+    assert!(range.starts_with("http/1.1 206"), "{range}");
+    assert!(
+        range.contains(&format!("content-range: bytes 0-4/{}", page.len())),
+        "{range}"
+    );
+    assert!(range.ends_with("\r\n\r\nhello"), "{range}");
+    let etag = range
+        .lines()
+        .find_map(|line| line.strip_prefix("etag: "))
+        .expect("etag")
+        .trim()
+        .to_string();
+    let cached = request(
+        &format!("GET /static/page.txt HTTP/1.1\r\nIf-None-Match: {etag}"),
+        "",
+    );
+    assert!(cached.starts_with("http/1.1 304"), "{cached}");
+    let gzipped = request("GET /static/page.txt HTTP/1.1\r\nAccept-Encoding: gzip", "");
+    assert!(gzipped.starts_with("http/1.1 200"), "{gzipped}");
+    assert!(gzipped.contains("content-encoding: gzip"), "{gzipped}");
+    assert!(gzipped.contains(&format!("etag: w/{etag}")), "{gzipped}");
+    let escape = request("GET /static/../secret HTTP/1.1", "");
+    assert!(escape.starts_with("http/1.1 404"), "{escape}");
+
+    server.stop().expect("stop server");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn http_server_streams_bodies() {
+    use std::io::{Read, Write};
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port")
+        .port();
+    let dir = std::env::temp_dir().join(format!("aivi-http-stream-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("static dir");
+    let large = "0123456789".repeat(20_000);
+    std::fs::write(dir.join("large.txt"), &large).expect("static file");
+
+    let source = r#"
+module test.http_streaming
+export main
+
+bytes = value => text.toBytes Utf8 value
+
+respond = body => pure (Http { status: 200, headers: [], body: body })
+
+decoded = result => result ?
+  | Ok value => value
+  | Err _ => ""
+
+echo = params req => respond req.body
+size = params req => effect {
+  body <- httpServer.readBody 64 req
+  pure (Http { status: 200, headers: [{ name: "x-size", value: "{text.length (decoded (text.fromBytes Utf8 body))}" }], body: Buffered body })
+}
+chunked = params req => respond (Streamed (streams.fromChunks [bytes "ab", bytes "cd"]))
+events = params req => effect {
+  (tx, rx) <- channel.make Unit
+  _ <- channel.send tx { event: Some "tick", data: "one\ntwo", id: Some "1", retry: None }
+  _ <- channel.send tx { event: None, data: "done", id: None, retry: Some 500 }
+  _ <- channel.close tx
+  pure (Http { status: 200, headers: [{ name: "content-type", value: "text/event-stream" }], body: Streamed (httpServer.sseStream rx) })
+}
+files = params req => httpServer.serveStatic "DIR" "large.txt" req
+
+routes = [
+  { method: "POST", pattern: "/echo", handler: echo },
+  { method: "POST", pattern: "/size", handler: size },
+  { method: "GET", pattern: "/chunked", handler: chunked },
+  { method: "GET", pattern: "/events", handler: events },
+  { method: "GET", pattern: "/large", handler: files }
+]
+
+main = httpServer.listenWith { address: "127.0.0.1:PORT" } { maxBodyBytes: 64, streamRequestBodies: True } (httpServer.route routes)
+"#
+    .replace("PORT", &port.to_string())
+    .replace("DIR", &dir.to_string_lossy());
+    let mut runtime = runtime_from_source(&source);
+    let main = runtime.ctx.globals.get("main").expect("main");
+    let main = expect_ok(runtime.force_value(main), "main");
+    let server = match expect_ok(runtime.run_effect_value(main), "start server") {
+        Value::HttpServer(server) => server,
+        _ => panic!("expected server"),
+    };
+    let request = |head: &str, body: &str| -> String {
+        let mut stream = (0..100)
+            .find_map(|_| {
+                std::net::TcpStream::connect(("127.0.0.1", port))
+                    .map_err(|_| std::thread::sleep(Duration::from_millis(20)))
+                    .ok()
+            })
+            .expect("connect");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .expect("timeout");
+        write!(
+            stream,
+            "{head}\r\nHost: localhost\r\nConnection: close\r\n\r\n{body}"
+        )
+        .expect("request");
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).expect("response");
+        String::from_utf8_lossy(&response).into_owned()
+    };
+
+    // A chunked request body streams through the handler into a chunked response.
+    let echo = request(
+        "POST /echo HTTP/1.1\r\nTransfer-Encoding: chunked",
+        "5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n",
+    );
+    assert!(echo.starts_with("HTTP/1.1 200"), "{echo}");
+    assert!(echo.contains("transfer-encoding: chunked"), "{echo}");
+    assert!(echo.contains("hello") && echo.contains(" world"), "{echo}");
+
+    let chunked = request("GET /chunked HTTP/1.1", "");
+    assert!(
+        chunked.ends_with("\r\n\r\n2\r\nab\r\n2\r\ncd\r\n0\r\n\r\n"),
+        "{chunked}"
+    );
+
+    let events = request("GET /events HTTP/1.1", "");
+    assert!(
+        events.contains("content-type: text/event-stream"),
+        "{events}"
+    );
+    assert!(
+        events.contains("event: tick\nid: 1\ndata: one\ndata: two\n\n"),
+        "{events}"
+    );
+    assert!(events.contains("retry: 500\ndata: done\n\n"), "{events}");
+
+    let size = request(
+        "POST /size HTTP/1.1\r\nTransfer-Encoding: chunked",
+        "3\r\nabc\r\n0\r\n\r\n",
+    );
+    assert!(size.contains("x-size: 3"), "{size}");
+    // Declared lengths over the limit are rejected before the handler runs; undeclared ones
+    // fail while the body is read.
+    let declared = request(
+        "POST /size HTTP/1.1\r\nContent-Length: 100",
+        &"x".repeat(100),
+    );
+    assert!(declared.starts_with("HTTP/1.1 413"), "{declared}");
+    let oversized = format!("64\r\n{}\r\n0\r\n\r\n", "x".repeat(100));
+    let streamed = request(
+        "POST /size HTTP/1.1\r\nTransfer-Encoding: chunked",
+        &oversized,
+    );
+    assert!(streamed.starts_with("HTTP/1.1 500"), "{streamed}");
+    assert!(streamed.contains("exceeds 64 bytes"), "{streamed}");
+
+    let file = request("GET /large HTTP/1.1", "");
+    assert!(
+        file.contains(&format!("content-length: {}", large.len())),
+        "{}",
+        &file[..200]
+    );
+    assert!(file.ends_with(&large[large.len() - 100..]));
+    assert_eq!(
+        file.len() - file.find("\r\n\r\n").expect("head") - 4,
+        large.len()
+    );
+
+    server.stop().expect("stop server");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn tls_terminates_http_and_socket_connections() {
+    use aivi_http_server::net::NetStream;
+    use aivi_http_server::tls;
+    use aivi_http_server::TlsClientSettings;
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+    use std::io::{Read, Write};
+
+    let dir = std::env::temp_dir().join(format!("aivi-tls-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("cert dir");
+    let ca_key = KeyPair::generate().expect("ca key");
+    let mut ca_params = CertificateParams::new(Vec::<String>::new()).expect("ca params");
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca = ca_params.self_signed(&ca_key).expect("ca");
+    std::fs::write(dir.join("ca.pem"), ca.pem()).expect("ca.pem");
+    for (name, subject) in [("server", "localhost"), ("client", "client.test")] {
+        let key = KeyPair::generate().expect("key");
+        let cert = CertificateParams::new(vec![subject.to_string()])
+            .and_then(|params| params.signed_by(&key, &ca, &ca_key))
+            .expect("cert");
+        std::fs::write(dir.join(format!("{name}.pem")), cert.pem()).expect("cert pem");
+        std::fs::write(dir.join(format!("{name}-key.pem")), key.serialize_pem()).expect("key pem");
+    }
+    let free_port = || {
+        std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("free port")
+            .port()
+    };
+    let (http_port, socket_port) = (free_port(), free_port());
+
+    let source = r#"
+module test.tls
+export main, fetch, secureListener, echoOnce
+
+serverTls = clientCa => { certFile: "DIR/server.pem", keyFile: "DIR/server-key.pem", clientCaFile: clientCa }
+
+hello = req => pure (Http { status: 200, headers: [], body: Buffered (text.toBytes Utf8 "hello over tls") })
+
+main = httpServer.listen { address: "127.0.0.1:HTTP_PORT", tls: Some (serverTls None) } hello
+
+fetch = name request => effect {
+  conn <- sockets.connectTls { host: "127.0.0.1", port: HTTP_PORT } { serverName: Some name, caFile: Some "DIR/ca.pem", clientCert: None }
+  _ <- sockets.send conn request
+  streams.collect 65536 (streams.fromSocket conn)
+}
+
+secureListener = sockets.listenTls { host: "127.0.0.1", port: SOCKET_PORT } (serverTls (Some "DIR/ca.pem"))
+
+echoOnce = listener => effect {
+  conn <- sockets.accept listener
+  bytes <- sockets.recv conn
+  _ <- sockets.send conn bytes
+  _ <- sockets.close conn
+  pure bytes
+}
+"#
+    .replace("DIR", &dir.to_string_lossy())
+    .replace("HTTP_PORT", &http_port.to_string())
+    .replace("SOCKET_PORT", &socket_port.to_string());
+    let mut runtime = runtime_from_source(&source);
+    let global = |runtime: &mut Runtime, name: &str| {
+        let value = runtime.ctx.globals.get(name).expect(name);
+        expect_ok(runtime.force_value(value), name)
+    };
+    let main = global(&mut runtime, "main");
+    let server = match expect_ok(runtime.run_effect_value(main), "start server") {
+        Value::HttpServer(server) => server,
+        _ => panic!("expected server"),
+    };
+
+    let certs = dir.clone();
+    let connect = move |port: u16, identity: bool| {
+        let settings = TlsClientSettings {
+            server_name: Some("localhost".to_string()),
+            ca_file: Some(certs.join("ca.pem")),
+            identity: identity.then(|| (certs.join("client.pem"), certs.join("client-key.pem"))),
+        };
+        let config = Arc::new(tls::client_config(&settings).expect("client config"));
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+        (0..100)
+            .find_map(|_| {
+                NetStream::connect_tls(addr, "127.0.0.1", config.clone(), &settings)
+                    .map_err(|_| std::thread::sleep(Duration::from_millis(20)))
+                    .ok()
+            })
+            .expect("tls connect")
+    };
+    let http_request = "GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+    // HTTPS from a plain rustls client.
+    let mut stream = connect(http_port, false);
+    stream.write_all(http_request.as_bytes()).expect("request");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("response");
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.ends_with("hello over tls"), "{response}");
+
+    // `connectTls` verifies the certificate against the SNI name, not the IP it dialled.
+    let request = Value::Bytes(Arc::new(http_request.as_bytes().to_vec()));
+    let fetch = global(&mut runtime, "fetch");
+    let fetch_as = |runtime: &mut Runtime, name: &str| {
+        let applied = runtime.apply(fetch.clone(), Value::Text(name.to_string()))?;
+        let applied = runtime.apply(applied, request.clone())?;
+        runtime.run_effect_value(applied)
+    };
+    match expect_ok(fetch_as(&mut runtime, "localhost"), "fetch") {
+        Value::Bytes(bytes) => {
+            let text = String::from_utf8_lossy(&bytes);
+            assert!(text.ends_with("hello over tls"), "{text}");
+        }
+        _ => panic!("expected bytes"),
+    }
+    assert!(fetch_as(&mut runtime, "other.test").is_err());
+
+    // The socket listener requires a client certificate issued by the CA.
+    let listener = global(&mut runtime, "secureListener");
+    let listener = expect_ok(runtime.run_effect_value(listener), "listenTls");
+    let echo_once = global(&mut runtime, "echoOnce");
+    let echo = |runtime: &mut Runtime| {
+        let applied = runtime.apply(echo_once.clone(), listener.clone())?;
+        runtime.run_effect_value(applied)
+    };
+    let anonymous_connect = connect.clone();
+    let anonymous = std::thread::spawn(move || {
+        let mut stream = anonymous_connect(socket_port, false);
+        let _ = stream.write_all(b"ping");
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).map(|_| reply)
+    });
+    assert!(echo(&mut runtime).is_err());
+    assert!(anonymous.join().expect("anonymous client").is_err());
+
+    let authenticated = std::thread::spawn(move || {
+        let mut stream = connect(socket_port, true);
+        stream.write_all(b"ping").expect("send");
+        let mut reply = [0u8; 4];
+        stream.read_exact(&mut reply).expect("reply");
+        reply
+    });
+    match expect_ok(echo(&mut runtime), "echo") {
+        Value::Bytes(bytes) => assert_eq!(bytes.as_slice(), b"ping"),
+        _ => panic!("expected bytes"),
+    }
+    assert_eq!(&authenticated.join().expect("client"), b"ping");
+
+    server.stop().expect("stop server");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn sockets_speak_udp_and_unix_streams_and_accept_is_cancellable() {
+    let dir = std::env::temp_dir().join(format!("aivi-sockets-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("socket dir");
+    let socket_path = dir.join("echo.sock");
+    let udp_port = std::net::UdpSocket::bind("127.0.0.1:0")
+        .and_then(|socket| socket.local_addr())
+        .expect("free port")
+        .port();
+    let source = r#"
+module test.sockets
+export udpRoundTrip, unixListener, unixEcho, unixClient, tcpListener, acceptOnce
+
+udpRoundTrip = effect {
+  server <- sockets.bind { host: "127.0.0.1", port: UDP_PORT }
+  client <- sockets.bind { host: "127.0.0.1", port: 0 }
+  _ <- sockets.sendTo client { host: "127.0.0.1", port: UDP_PORT } (text.toBytes Utf8 "gauge:1|c")
+  datagram <- sockets.recvFrom server
+  pure datagram.data
+}
+
+unixListener = sockets.listenUnix "PATH"
+
+unixEcho = listener => effect {
+  conn <- sockets.accept listener
+  bytes <- sockets.recv conn
+  _ <- sockets.send conn bytes
+  _ <- sockets.close conn
+  pure bytes
+}
+
+unixClient = effect {
+  conn <- sockets.connectUnix "PATH"
+  _ <- sockets.send conn (text.toBytes Utf8 "ipc")
+  bytes <- sockets.recv conn
+  _ <- sockets.close conn
+  pure bytes
+}
+
+tcpListener = sockets.listen { host: "127.0.0.1", port: 0 }
+
+acceptOnce = listener => sockets.accept listener
+"#
+    .replace("PATH", &socket_path.to_string_lossy())
+    .replace("UDP_PORT", &udp_port.to_string());
+    let mut runtime = runtime_from_source(&source);
+    let global = |runtime: &mut Runtime, name: &str| {
+        let value = runtime.ctx.globals.get(name).expect(name);
+        expect_ok(runtime.force_value(value), name)
+    };
+
+    let udp = global(&mut runtime, "udpRoundTrip");
+    match expect_ok(runtime.run_effect_value(udp), "udp") {
+        Value::Bytes(bytes) => assert_eq!(bytes.as_slice(), b"gauge:1|c"),
+        _ => panic!("expected bytes"),
+    }
+
+    let listener = global(&mut runtime, "unixListener");
+    let listener = expect_ok(runtime.run_effect_value(listener), "listenUnix");
+    let client_ctx = runtime.ctx.clone();
+    let client = std::thread::spawn(move || {
+        let mut runtime = Runtime::new(client_ctx, CancelToken::root());
+        let client = global(&mut runtime, "unixClient");
+        match expect_ok(runtime.run_effect_value(client), "unix client") {
+            Value::Bytes(bytes) => bytes.as_slice().to_vec(),
+            _ => panic!("expected bytes"),
+        }
+    });
+    let echo = global(&mut runtime, "unixEcho");
+    let echo = expect_ok(runtime.apply(echo, listener), "apply echo");
+    match expect_ok(runtime.run_effect_value(echo), "unix echo") {
+        Value::Bytes(bytes) => assert_eq!(bytes.as_slice(), b"ipc"),
+        _ => panic!("expected bytes"),
+    }
+    assert_eq!(client.join().expect("unix client"), b"ipc");
+
+    let listener = global(&mut runtime, "tcpListener");
+    let listener = expect_ok(runtime.run_effect_value(listener), "listen");
+    let accept = global(&mut runtime, "acceptOnce");
+    let accept = expect_ok(runtime.apply(accept, listener), "apply accept");
+    let cancel = runtime.cancel.clone();
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        cancel.cancel();
+    });
+    assert!(matches!(
+        runtime.run_effect_value(accept),
+        Err(RuntimeError::Cancelled)
+    ));
+    canceller.join().expect("canceller");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn structured_sigils_evaluate_to_map_and_set() {
     let source = r#"
@@ -594,6 +1617,128 @@ fn collections_map_set_queue_heap() {
     }
 }
 
+#[test]
+fn list_pattern_with_bare_rest_matches_longer_lists() {
+    let source = r#"
+module test.listRest
+export first
+
+first =
+  | [] => 0
+  | [x, ...] => x
+
+value = first [7, 8, 9]
+"#;
+    let mut runtime = runtime_from_source(source);
+    let value = runtime.ctx.globals.get("value").expect("value");
+    let value = expect_ok(runtime.force_value(value), "first");
+    assert!(matches!(value, Value::Int(7)));
+}
+
+#[test]
+fn derived_instances_dispatch_per_type() {
+    let source = r#"
+module test.deriving
+
+Color = Red | Green | Blue deriving (Setoid, Ord, ToText, ToJson)
+
+Shape = Circle Int | Tinted Color Shape | Dot
+  deriving (Setoid, Ord, ToText, ToJson)
+
+Point = { x: Int, label: Text } deriving (Setoid, Ord, ToJson)
+
+sameShape = equals (Tinted Red Dot) (Tinted Red Dot)
+sameColor = equals Red Blue
+colorOrder = lte Green Blue
+shapeOrder = lte (Tinted Red (Circle 2)) (Tinted Red (Circle 1))
+pointOrder = lte { x: 1, label: "a" } { x: 1, label: "b" }
+shapeText = toText (Tinted Blue (Circle 2))
+shapeJson = toJson (Tinted Blue Dot)
+pointJson = toJson { x: 1, label: "say \"hi\"" }
+"#;
+    let mut runtime = runtime_from_source(source);
+    let mut get = |name: &str| {
+        let value = runtime.ctx.globals.get(name).expect(name);
+        expect_ok(runtime.force_value(value), name)
+    };
+    assert!(matches!(get("sameShape"), Value::Bool(true)));
+    assert!(matches!(get("sameColor"), Value::Bool(false)));
+    assert!(matches!(get("colorOrder"), Value::Bool(true)));
+    assert!(matches!(get("shapeOrder"), Value::Bool(false)));
+    assert!(matches!(get("pointOrder"), Value::Bool(true)));
+    assert!(matches!(get("shapeText"), Value::Text(text) if text == "Tinted Blue (Circle 2)"));
+    assert!(matches!(
+        get("shapeJson"),
+        Value::Text(text) if text == r#"{"tag":"Tinted","values":["Blue","Dot"]}"#
+    ));
+    assert!(matches!(
+        get("pointJson"),
+        Value::Text(text) if text == r#"{"x":1,"label":"say \"hi\""}"#
+    ));
+}
+
+#[test]
+fn effect_handlers_answer_operations() {
+    let source = r#"
+module test.user_effects
+
+effect Counter = {
+  bump: Int -> Int
+  base: Int
+}
+
+total = effect {
+  start <- base
+  once <- bump start
+  twice <- bump once
+  pure (once + twice)
+}
+
+counted = total with {
+  bump: n => pure (n + 1)
+  base: pure 10
+}
+
+inner = effect {
+  value <- bump 1
+  pure value
+}
+
+nested = (inner with {
+  bump: n => bump (n + 1)
+  base: pure 0
+}) with {
+  bump: n => pure (n * 100)
+  base: pure 0
+}
+
+orphan = bump 1
+"#;
+    let mut runtime = runtime_from_source(source);
+    let mut run = |name: &str| {
+        let value = runtime.ctx.globals.get(name).expect(name);
+        let value = expect_ok(runtime.force_value(value), name);
+        runtime.run_effect_value(value)
+    };
+    assert!(matches!(
+        expect_ok(run("counted"), "counted"),
+        Value::Int(23)
+    ));
+    assert!(matches!(
+        expect_ok(run("nested"), "nested"),
+        Value::Int(200)
+    ));
+    match run("orphan") {
+        Err(RuntimeError::Message(message)) => {
+            assert!(
+                message.contains("unhandled effect operation 'bump'"),
+                "{message}"
+            )
+        }
+        _ => panic!("expected an unhandled operation error"),
+    }
+}
+
 #[test]
 fn list_core_ops() {
     let globals = Env::new(None);
@@ -905,6 +2050,204 @@ fn linalg_dot_and_graph_shortest_path() {
     ));
 }
 
+#[test]
+fn http_client_follows_redirects_retries_and_keeps_cookies() {
+    use aivi_http_server::{AiviRequest, AiviResponse, Body, ServerReply};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let flaky = Arc::new(AtomicUsize::new(0));
+    let post_flaky = Arc::new(AtomicUsize::new(0));
+    let handler: aivi_http_server::Handler = {
+        let (flaky, post_flaky) = (flaky.clone(), post_flaky.clone());
+        Arc::new(move |req: AiviRequest| {
+            let header = |name: &str| {
+                req.headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default()
+            };
+            let reply = |status: u16, headers: Vec<(&str, String)>, body: &[u8]| {
+                ServerReply::Http(AiviResponse {
+                    status,
+                    headers: headers
+                        .into_iter()
+                        .map(|(name, value)| (name.to_string(), value))
+                        .collect(),
+                    body: Body::from(body.to_vec()),
+                })
+            };
+            let response = match req.path.as_str() {
+                "/echo" => reply(
+                    200,
+                    vec![("x-content-type", header("content-type"))],
+                    req.body
+                        .as_full()
+                        .map(|bytes| bytes.as_ref())
+                        .unwrap_or(b""),
+                ),
+                "/redirect" => reply(302, vec![("location", "/final".to_string())], b""),
+                "/final" => reply(200, Vec::new(), b"final"),
+                "/flaky" if flaky.fetch_add(1, Ordering::SeqCst) < 2 => {
+                    reply(503, Vec::new(), b"busy")
+                }
+                "/flaky" => reply(200, Vec::new(), b"recovered"),
+                "/post-flaky" => {
+                    post_flaky.fetch_add(1, Ordering::SeqCst);
+                    reply(503, Vec::new(), b"busy")
+                }
+                "/login" => reply(
+                    302,
+                    vec![
+                        ("set-cookie", "session=abc; Path=/".to_string()),
+                        ("location", "/whoami".to_string()),
+                    ],
+                    b"",
+                ),
+                "/whoami" => reply(200, Vec::new(), header("cookie").as_bytes()),
+                "/slow" => {
+                    std::thread::sleep(Duration::from_millis(1000));
+                    reply(200, Vec::new(), b"late")
+                }
+                _ => reply(404, Vec::new(), b"not found"),
+            };
+            Box::pin(async move { Ok(response) })
+        })
+    };
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port");
+    let server = aivi_http_server::start_server(addr, handler).expect("start server");
+    let base = format!("http://{addr}");
+
+    let source = r#"
+module test.httpClient
+export main
+
+use aivi
+use aivi.text
+use aivi.net.http
+use aivi.database.pool (Fixed)
+
+toUrl = raw => (url.parse raw) ?
+  | Ok u => pure u
+  | Err e => fail e
+
+send = c method path body => effect {
+  u <- toUrl "BASE{path}"
+  execute c ((request method u) <| { body: body })
+}
+
+main = effect {
+  c <- client (defaultClientConfig <| { timeout: { millis: 2000 }, retry: { maxRetries: 2, backoff: Fixed { millis: 10 } } })
+  plain <- client (defaultClientConfig <| { cookies: False })
+  form <- send c "POST" "/echo" (FormBody [{ name: "a b", value: "c&d" }])
+  bytes <- send c "PUT" "/echo" (BytesBody (toBytes Utf8 "raw"))
+  multipart <- send c "POST" "/echo" (MultipartBody [{ name: "file", fileName: Some "a.txt", contentType: Some "text/plain", data: toBytes Utf8 "hello" }])
+  redirected <- send c "GET" "/redirect" NoBody
+  flaky <- send c "GET" "/flaky" NoBody
+  postFlaky <- send c "POST" "/post-flaky" NoBody
+  login <- send c "GET" "/login" NoBody
+  anonymous <- send plain "GET" "/login" NoBody
+  home <- toUrl "BASE/"
+  jar <- cookiesFor c home
+  u <- toUrl "BASE/slow"
+  slow <- execute c ((request "POST" u) <| { timeout: Some { millis: 200 } })
+  pure { form: form, bytes: bytes, multipart: multipart, redirected: redirected, flaky: flaky, postFlaky: postFlaky, login: login, anonymous: anonymous, jar: jar, slow: slow }
+}
+"#
+    .replace("BASE", &base);
+    let mut runtime = runtime_from_source_with_stdlib(&source);
+    let main = runtime.ctx.globals.get("main").expect("main");
+    let main = expect_ok(runtime.force_value(main), "evaluate main");
+    let result = match expect_ok(runtime.run_effect_value(main), "run main") {
+        Value::Record(fields) => fields,
+        other => panic!("expected record, got {}", format_value(&other)),
+    };
+
+    let response = |name: &str| match result.get(name) {
+        Some(Value::Constructor { name: tag, args }) if tag == "Ok" => match &args[0] {
+            Value::Record(fields) => fields.clone(),
+            other => panic!("{name}: expected response, got {}", format_value(other)),
+        },
+        Some(other) => panic!("{name}: expected Ok, got {}", format_value(other)),
+        None => panic!("{name}: missing"),
+    };
+    let status = |name: &str| match response(name).get("status") {
+        Some(Value::Int(status)) => *status,
+        _ => panic!("{name}: missing status"),
+    };
+    let body = |name: &str| match response(name).get("body") {
+        Some(Value::Bytes(bytes)) => String::from_utf8_lossy(bytes).into_owned(),
+        _ => panic!("{name}: expected Bytes body"),
+    };
+    let header = |name: &str, header: &str| match response(name).get("headers") {
+        Some(Value::List(items)) => items
+            .iter()
+            .find_map(|item| match item {
+                Value::Record(fields) => match (fields.get("name"), fields.get("value")) {
+                    (Some(Value::Text(key)), Some(Value::Text(value))) if key == header => {
+                        Some(value.clone())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap_or_default(),
+        _ => panic!("{name}: expected headers"),
+    };
+
+    assert_eq!(body("form"), "a+b=c%26d");
+    assert_eq!(
+        header("form", "x-content-type"),
+        "application/x-www-form-urlencoded"
+    );
+    assert_eq!(body("bytes"), "raw");
+    assert_eq!(
+        header("bytes", "x-content-type"),
+        "application/octet-stream"
+    );
+    let boundary = header("multipart", "x-content-type")
+        .strip_prefix("multipart/form-data; boundary=")
+        .expect("multipart content type")
+        .to_string();
+    assert_eq!(
+        body("multipart"),
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\nContent-Type: text/plain\r\n\r\nhello\r\n--{boundary}--\r\n"
+        )
+    );
+
+    assert_eq!(body("redirected"), "final");
+    match response("redirected").get("url") {
+        Some(Value::Record(url)) => {
+            assert!(matches!(url.get("path"), Some(Value::Text(path)) if path == "/final"))
+        }
+        _ => panic!("expected final url"),
+    }
+
+    // GET is retried past two 503s; POST is not retried and its 503 is still a response.
+    assert_eq!(body("flaky"), "recovered");
+    assert_eq!(flaky.load(Ordering::SeqCst), 3);
+    assert_eq!(status("postFlaky"), 503);
+    assert_eq!(post_flaky.load(Ordering::SeqCst), 1);
+
+    // The cookie set on the redirect hop is sent to the redirect target.
+    assert_eq!(body("login"), "session=abc");
+    assert_eq!(body("anonymous"), "");
+    match result.get("jar") {
+        Some(Value::List(items)) => assert_eq!(items.len(), 1),
+        _ => panic!("expected cookie list"),
+    }
+
+    match result.get("slow") {
+        Some(Value::Constructor { name, .. }) => assert_eq!(name, "Err"),
+        _ => panic!("expected timeout error"),
+    }
+
+    server.stop().expect("stop server");
+}
+
 #[test]
 fn https_rejects_non_https_urls() {
     let globals = Env::new(None);
@@ -986,3 +2329,59 @@ fn regex_compile_and_match() {
         .unwrap_or_else(|_| panic!("test value"));
     assert!(matches!(verdict, Value::Bool(true)));
 }
+
+#[test]
+fn recursive_block_bindings_see_each_other() {
+    let source = r#"
+module test.local_recursion
+
+sumTo = limit => {
+  go = n => if n > limit then 0 else n + go (n + 1)
+  go 1
+}
+
+shadowed = {
+  n = 1
+  n = n + 1
+  n
+}
+
+parity = effect {
+  isEven = n => if n == 0 then True else isOdd (n - 1)
+  isOdd = n => if n == 0 then False else isEven (n - 1)
+  pure (isEven 10, isOdd 7)
+}
+
+partials = generate {
+  upTo = n => if n == 0 then 0 else n + upTo (n - 1)
+  yield upTo 3
+  yield upTo 4
+}
+"#;
+    let mut runtime = runtime_from_source(source);
+    let mut global = |name: &str| {
+        let value = runtime.ctx.globals.get(name).expect(name);
+        expect_ok(runtime.force_value(value), name)
+    };
+    let sum_to = global("sumTo");
+    let shadowed = global("shadowed");
+    let parity = global("parity");
+    let partials = global("partials");
+    assert!(matches!(
+        expect_ok(runtime.apply(sum_to, Value::Int(10)), "sumTo 10"),
+        Value::Int(55)
+    ));
+    assert!(matches!(shadowed, Value::Int(2)));
+    let Value::Tuple(parity) = expect_ok(runtime.run_effect_value(parity), "parity") else {
+        panic!("expected a tuple");
+    };
+    assert!(matches!(
+        parity.as_slice(),
+        [Value::Bool(true), Value::Bool(true)]
+    ));
+    let partials = expect_ok(runtime.generator_to_list(partials), "partials");
+    assert!(matches!(
+        partials.as_slice(),
+        [Value::Int(6), Value::Int(10)]
+    ));
+}
@@ -1,6 +1,5 @@
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-use std::net::{TcpListener, TcpStream};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::sync::atomic::AtomicBool;
 use std::sync::{mpsc, Arc, Mutex};
 
@@ -11,7 +10,8 @@ use regex::Regex;
 use rust_decimal::Decimal;
 
 use crate::hir::{HirBlockItem, HirExpr};
-use aivi_http_server::{ServerHandle, WebSocketHandle};
+use aivi_http_server::net::{NetListener, NetStream};
+use aivi_http_server::{ChunkSource, ServerHandle, WebSocketHandle};
 
 use super::environment::Env;
 use super::{Runtime, RuntimeError};
@@ -51,8 +51,9 @@ pub(super) enum Value {
     ChannelSend(Arc<ChannelSend>),
     ChannelRecv(Arc<ChannelRecv>),
     FileHandle(Arc<Mutex<std::fs::File>>),
-    Listener(Arc<TcpListener>),
-    Connection(Arc<Mutex<TcpStream>>),
+    Listener(Arc<NetListener>),
+    Connection(Arc<Mutex<NetStream>>),
+    UdpSocket(Arc<std::net::UdpSocket>),
     Stream(Arc<StreamHandle>),
     HttpServer(Arc<ServerHandle>),
     WebSocket(Arc<WebSocketHandle>),
@@ -87,6 +88,7 @@ pub(super) enum EffectValue {
 }
 
 pub(super) struct ResourceValue {
+    pub(super) env: Env,
     pub(super) items: Arc<Vec<HirBlockItem>>,
 }
 
@@ -117,7 +119,7 @@ pub(super) struct StreamHandle {
 
 pub(super) enum StreamState {
     Socket {
-        stream: Arc<Mutex<TcpStream>>,
+        stream: Arc<Mutex<NetStream>>,
         chunk_size: usize,
     },
     Chunks {
@@ -125,6 +127,15 @@ pub(super) enum StreamState {
         size: usize,
         buffer: Vec<u8>,
     },
+    /// Chunks that are already in memory.
+    Buffered { chunks: VecDeque<Vec<u8>> },
+    /// Values received from a channel until it is closed, each encoded into one chunk.
+    Channel {
+        receiver: Arc<ChannelRecv>,
+        encode: fn(Value) -> Result<Vec<u8>, RuntimeError>,
+    },
+    /// A streamed HTTP request or response body.
+    Body { source: ChunkSource },
 }
 
 #[derive(Clone, Eq, PartialEq, Hash)]
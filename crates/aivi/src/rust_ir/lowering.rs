@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::kernel::{
     KernelBlockItem, KernelBlockKind, KernelDef, KernelExpr, KernelMatchArm, KernelModule,
-    KernelPathSegment, KernelPattern, KernelProgram, KernelRecordField,
+    KernelPathSegment, KernelPattern, KernelProgram, KernelRecBinding, KernelRecordField,
 };
 use crate::AiviError;
 
@@ -283,6 +283,16 @@ pub enum RustIrBlockItem {
     Expr {
         expr: RustIrExpr,
     },
+    /// Mutually recursive function bindings: every name is in scope in every body.
+    LetRec {
+        bindings: Vec<RustIrRecBinding>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RustIrRecBinding {
+    pub name: String,
+    pub expr: RustIrExpr,
 }
 
 pub fn lower_kernel(program: KernelProgram) -> Result<RustIrProgram, AiviError> {
@@ -129,6 +129,18 @@ fn collect_unbound_vars_in_kernel_expr(
                     | crate::kernel::KernelBlockItem::Expr { expr } => {
                         collect_unbound_vars_in_kernel_expr(expr, globals, locals, bound, out);
                     }
+                    crate::kernel::KernelBlockItem::LetRec { bindings } => {
+                        bound.extend(bindings.iter().map(|binding| binding.name.clone()));
+                        for binding in bindings {
+                            collect_unbound_vars_in_kernel_expr(
+                                &binding.expr,
+                                globals,
+                                locals,
+                                bound,
+                                out,
+                            );
+                        }
+                    }
                 }
             }
             bound.truncate(before);
@@ -423,6 +435,21 @@ fn rewrite_implicit_field_vars(
                             expr: rewrite_implicit_field_vars(expr, implicit_param, unbound),
                         }
                     }
+                    crate::kernel::KernelBlockItem::LetRec { bindings } => {
+                        crate::kernel::KernelBlockItem::LetRec {
+                            bindings: bindings
+                                .into_iter()
+                                .map(|binding| KernelRecBinding {
+                                    name: binding.name,
+                                    expr: rewrite_implicit_field_vars(
+                                        binding.expr,
+                                        implicit_param,
+                                        unbound,
+                                    ),
+                                })
+                                .collect(),
+                        }
+                    }
                 })
                 .collect(),
         },
@@ -467,6 +494,19 @@ fn lower_block_item(
         KernelBlockItem::Expr { expr } => Ok(RustIrBlockItem::Expr {
             expr: lower_expr(expr, globals, locals)?,
         }),
+        KernelBlockItem::LetRec { bindings } => {
+            locals.extend(bindings.iter().map(|binding| binding.name.clone()));
+            let bindings = bindings
+                .into_iter()
+                .map(|binding| {
+                    Ok(RustIrRecBinding {
+                        name: binding.name,
+                        expr: lower_expr(binding.expr, globals, locals)?,
+                    })
+                })
+                .collect::<Result<_, AiviError>>()?;
+            Ok(RustIrBlockItem::LetRec { bindings })
+        }
     }
 }
 
@@ -642,6 +682,8 @@ fn resolve_builtin(name: &str) -> Option<BuiltinName> {
             | "attempt"
             | "load"
             | "bind"
+            | "__handle"
+            | "__perform"
             | "print"
             | "println"
             | "map"
@@ -79,6 +79,12 @@ fn emit_plain_block(items: &[RustIrBlockItem], indent: usize) -> Result<String,
                     "filter/yield/recurse are not supported by the rustc backend yet".to_string(),
                 ))
             }
+            RustIrBlockItem::LetRec { .. } => {
+                return Err(AiviError::Codegen(
+                    "recursive block bindings are not supported by the rustc backend yet"
+                        .to_string(),
+                ))
+            }
         }
     }
 
@@ -117,6 +123,11 @@ fn emit_plain_block(items: &[RustIrBlockItem], indent: usize) -> Result<String,
                 "filter/yield/recurse are not supported by the rustc backend yet".to_string(),
             ))
         }
+        RustIrBlockItem::LetRec { .. } => {
+            return Err(AiviError::Codegen(
+                "recursive block bindings are not supported by the rustc backend yet".to_string(),
+            ))
+        }
     }
 
     s.push_str(&ind);
@@ -176,6 +187,12 @@ fn emit_effect_block(items: &[RustIrBlockItem], indent: usize) -> Result<String,
                     "filter/yield/recurse are not supported by the rustc backend yet".to_string(),
                 ))
             }
+            RustIrBlockItem::LetRec { .. } => {
+                return Err(AiviError::Codegen(
+                    "recursive block bindings are not supported by the rustc backend yet"
+                        .to_string(),
+                ))
+            }
         }
     }
 
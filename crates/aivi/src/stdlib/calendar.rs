@@ -4,14 +4,25 @@ pub const SOURCE: &str = r#"
 @no_prelude
 module aivi.calendar
 export Date, DateTime, EndOfMonth
+export Time, LocalDateTime, Instant, TimeZone, ZonedDateTime
 export isLeapYear, daysInMonth, endOfMonth
 export addDays, addMonths, addYears, negateDelta
-export now
+export now, nowIn, fromDateTime, toDateTime
+export utc, timeZone, atZone, fromLocal, toInstant, withZone
+export parseDate, parseTime, parseLocalDateTime, parseInstant, parseZoned
+export dateText, timeText, localDateTimeText, instantText, zonedText
 export domain Calendar
 
 use aivi
 
 Date = { year: Int, month: Int, day: Int }
+Time = { hour: Int, minute: Int, second: Int }
+LocalDateTime = { date: Date, time: Time }
+// An instant is stored as a UTC `DateTime`; `fromDateTime` turns literals such as
+// `2024-05-21T12:00:00Z` into instants.
+opaque type Instant = DateTime
+TimeZone = { id: Text }
+ZonedDateTime = { dateTime: LocalDateTime, zone: TimeZone, offsetSeconds: Int }
 type EndOfMonth = EndOfMonth
 
 isLeapYear : Date -> Bool
@@ -37,25 +48,124 @@ negateDelta = delta => delta ?
   | Day n => Day (-n)
   | Month n => Month (-n)
   | Year n => Year (-n)
+  | Hour n => Hour (-n)
+  | Minute n => Minute (-n)
   | End => End
 
-now : Effect DateTime
+now : Effect Text Instant
 now = clock.now Unit
 
+fromDateTime : DateTime -> Instant
+fromDateTime = value => value
+
+toDateTime : Instant -> DateTime
+toDateTime = value => value
+
+nowIn : TimeZone -> Effect Text ZonedDateTime
+nowIn = zone => effect {
+  instant <- now
+  pure (atZone zone instant)
+}
+
+// Zone ids are IANA names from the bundled tz database (`Europe/Paris`), `UTC`, or fixed
+// offsets such as `+05:30`.
+utc : TimeZone
+utc = { id: "UTC" }
+
+timeZone : Text -> Result Text TimeZone
+timeZone = id => calendar.timeZone id
+
+atZone : TimeZone -> Instant -> ZonedDateTime
+atZone = zone instant => calendar.atZone zone instant
+
+// Wall-clock times repeated by a DST fall-back take the earlier offset; times skipped by a
+// spring-forward gap move forward by the length of the gap.
+fromLocal : TimeZone -> LocalDateTime -> ZonedDateTime
+fromLocal = zone local => calendar.fromLocal zone local
+
+toInstant : ZonedDateTime -> Instant
+toInstant = value => calendar.toInstant value
+
+withZone : TimeZone -> ZonedDateTime -> ZonedDateTime
+withZone = zone value => atZone zone (toInstant value)
+
+parseDate : Text -> Result Text Date
+parseDate = text => calendar.parseDate text
+
+parseTime : Text -> Result Text Time
+parseTime = text => calendar.parseTime text
+
+parseLocalDateTime : Text -> Result Text LocalDateTime
+parseLocalDateTime = text => calendar.parseLocalDateTime text
+
+parseInstant : Text -> Result Text Instant
+parseInstant = text => calendar.parseInstant text
+
+// Accepts RFC 3339 with an optional zone suffix: `2024-03-10T03:30:00-04:00[America/New_York]`.
+parseZoned : Text -> Result Text ZonedDateTime
+parseZoned = text => calendar.parseZoned text
+
+dateText : Date -> Text
+dateText = value => calendar.dateText value
+
+timeText : Time -> Text
+timeText = value => calendar.timeText value
+
+localDateTimeText : LocalDateTime -> Text
+localDateTimeText = value => calendar.localDateTimeText value
+
+instantText : Instant -> Text
+instantText = value => calendar.instantText value
+
+zonedText : ZonedDateTime -> Text
+zonedText = value => calendar.zonedText value
+
 domain Calendar over Date = {
-  type Delta = Day Int | Month Int | Year Int | End EndOfMonth
+  type Delta = Day Int | Month Int | Year Int | Hour Int | Minute Int | End
 
+  // Day, month and year deltas move the wall clock; hour and minute deltas are elapsed time.
   (+) : Date -> Delta -> Date
-  (+) = date (Day n) => addDays date n
-  (+) = date (Month n) => addMonths date n
-  (+) = date (Year n) => addYears date n
-  (+) = date End => endOfMonth date
+  (+) = value delta => calendar.shift value delta
 
   (-) : Date -> Delta -> Date
-  (-) = date delta => date + (negateDelta delta)
+  (-) = value delta => calendar.shift value (negateDelta delta)
 
   1d = Day 1
   1m = Month 1
   1y = Year 1
+  1h = Hour 1
+  1min = Minute 1
   eom = End
+}
+
+domain Calendar over Time = {
+  (+) : Time -> Delta -> Time
+  (+) = value delta => calendar.shift value delta
+
+  (-) : Time -> Delta -> Time
+  (-) = value delta => calendar.shift value (negateDelta delta)
+}
+
+domain Calendar over LocalDateTime = {
+  (+) : LocalDateTime -> Delta -> LocalDateTime
+  (+) = value delta => calendar.shift value delta
+
+  (-) : LocalDateTime -> Delta -> LocalDateTime
+  (-) = value delta => calendar.shift value (negateDelta delta)
+}
+
+domain Calendar over ZonedDateTime = {
+  (+) : ZonedDateTime -> Delta -> ZonedDateTime
+  (+) = value delta => calendar.shift value delta
+
+  (-) : ZonedDateTime -> Delta -> ZonedDateTime
+  (-) = value delta => calendar.shift value (negateDelta delta)
+}
+
+domain Calendar over Instant = {
+  (+) : Instant -> Delta -> Instant
+  (+) = value delta => calendar.shift value delta
+
+  (-) : Instant -> Delta -> Instant
+  (-) = value delta => calendar.shift value (negateDelta delta)
 }"#;
@@ -3,8 +3,11 @@ pub const MODULE_NAME: &str = "aivi.i18n";
 pub const SOURCE: &str = r#"
 @no_prelude
 module aivi.i18n
-export Locale, Key, Message, Bundle, Catalog
-export parseLocale, key, message, render
+export Locale, Key, Message, Bundle, Catalog, DateStyle
+export Short, Medium, Long
+export parseLocale, key, message, render, renderIn
+export formatNumber, formatInt, formatDecimal, formatPercent, formatCurrency
+export formatDate, formatTime, formatDateTime
 export bundleFromProperties, bundleFromPropertiesFile
 export keyText, messageText
 export tResult, tOpt, t, tWithFallback
@@ -14,10 +17,11 @@ export tCatalog, tCatalogWithDefault
 use aivi
 
 type Locale = { language: Text, region: Option Text, variants: List Text, tag: Text }
-type Key = { tag: Text, body: Text, flags: Text }
-type Message = { tag: Text, body: Text, flags: Text }
+opaque type Key = { tag: Text, body: Text, flags: Text }
+opaque type Message = { tag: Text, body: Text, flags: Text }
 type Bundle = { locale: Locale, entries: Map Text Message }
 type Catalog = Map Text Bundle
+type DateStyle = Short | Medium | Long
 
 parseLocale : Text -> Result Text Locale
 parseLocale = tag => i18n.parseLocale tag
@@ -28,9 +32,42 @@ key = text => i18n.key text
 message : Text -> Result Text Message
 message = text => i18n.message text
 
+// `render` uses English plural rules and leaves typed values unformatted; `renderIn` formats
+// `{n:Decimal}`-style placeholders and picks plural rules from the locale.
 render : Message -> {} -> Result Text Text
 render = msg args => i18n.render msg args
 
+renderIn : Locale -> Message -> {} -> Result Text Text
+renderIn = locale msg args => i18n.renderIn locale msg args
+
+// Locale-aware formatting uses the bundled CLDR data for `locale.tag`, falling back to the
+// language and then to `en` when the locale is not compiled in.
+formatNumber : Locale -> Float -> Text
+formatNumber = locale n => i18n.formatNumber locale n
+
+formatInt : Locale -> Int -> Text
+formatInt = locale n => i18n.formatInt locale n
+
+formatDecimal : Locale -> Decimal -> Text
+formatDecimal = locale n => i18n.formatDecimal locale n
+
+// `0.25` renders as `25%`.
+formatPercent : Locale -> Float -> Text
+formatPercent = locale n => i18n.formatPercent locale n
+
+// `currency` is an ISO 4217 code such as `EUR`.
+formatCurrency : Locale -> Text -> Decimal -> Text
+formatCurrency = locale currency amount => i18n.formatCurrency locale currency amount
+
+formatDate : Locale -> DateStyle -> DateTime -> Text
+formatDate = locale style when => i18n.formatDate locale style when
+
+formatTime : Locale -> DateStyle -> DateTime -> Text
+formatTime = locale style when => i18n.formatTime locale style when
+
+formatDateTime : Locale -> DateStyle -> DateTime -> Text
+formatDateTime = locale style when => i18n.formatDateTime locale style when
+
 bundleFromProperties : Locale -> Text -> Result Text Bundle
 bundleFromProperties = locale props => i18n.bundleFromProperties locale props
 
@@ -52,7 +89,7 @@ tResult : Bundle -> Key -> {} -> Result Text Text
 tResult = bundle k args =>
   Map.get (keyText k) bundle.entries ?
     | None => Err (text.concat ["missing key: ", keyText k])
-    | Some msg => render msg args
+    | Some msg => renderIn bundle.locale msg args
 
 tOpt : Bundle -> Key -> {} -> Option Text
 tOpt = bundle k args =>
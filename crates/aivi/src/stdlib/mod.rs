@@ -44,6 +44,7 @@ mod testing;
 mod text;
 mod ui;
 mod ui_layout;
+mod ui_ssg;
 mod units;
 mod url;
 mod vector;
@@ -234,6 +235,10 @@ const EMBEDDED_MODULES: &[EmbeddedModule] = &[
         name: ui::MODULE_NAME,
         source: ui::SOURCE,
     },
+    EmbeddedModule {
+        name: ui_ssg::MODULE_NAME,
+        source: ui_ssg::SOURCE,
+    },
 ];
 
 pub fn embedded_stdlib_modules() -> Vec<Module> {
@@ -5,9 +5,15 @@ pub const SOURCE: &str = r#"
 module aivi.net.http
 export Header, Request, Response, Error
 export get, post, fetch
+export Client, ClientConfig, RetryPolicy, ClientRequest, ClientResponse
+export RequestBody, FormField, Part
+export NoBody, BytesBody, TextBody, FormBody, MultipartBody
+export client, execute, cookiesFor, request, defaultClientConfig, noRetry
 
 use aivi
 use aivi.url (Url)
+use aivi.duration (Span)
+use aivi.database.pool (BackoffPolicy, Fixed)
 
 Header = { name: Text, value: Text }
 Request = { method: Text, url: Url, headers: List Header, body: Option Text }
@@ -22,4 +28,76 @@ post = url body => http.post url body
 
 fetch : Request -> Effect Text (Result Error Response)
 fetch = request => http.fetch request
+
+// Idempotent requests (GET, HEAD, OPTIONS, PUT, DELETE, TRACE) are retried after transport
+// errors and 429/502/503/504 responses, waiting between attempts as `backoff` says.
+RetryPolicy = { maxRetries: Int, backoff: BackoffPolicy }
+
+ClientConfig = {
+  timeout: Span
+  maxRedirects: Int
+  retry: RetryPolicy
+  cookies: Bool
+  userAgent: Option Text
+}
+
+FormField = { name: Text, value: Text }
+Part = { name: Text, fileName: Option Text, contentType: Option Text, data: Bytes }
+
+// The content type follows from the body unless a `content-type` header is given.
+type RequestBody =
+  | NoBody
+  | BytesBody Bytes
+  | TextBody Text
+  | FormBody (List FormField)
+  | MultipartBody (List Part)
+
+ClientRequest = {
+  method: Text
+  url: Url
+  headers: List Header
+  body: RequestBody
+  timeout: Option Span
+}
+
+// `url` is where the response came from after following redirects.
+ClientResponse = { status: Int, headers: List Header, body: Bytes, url: Url }
+
+// Client is represented as a record of effectful operations.
+Client = {
+  send: ClientRequest -> Effect Text (Result Error ClientResponse)
+  cookies: Url -> Effect Text (List Header)
+  close: Unit -> Effect Text Unit
+}
+
+noRetry : RetryPolicy
+noRetry = { maxRetries: 0, backoff: Fixed { millis: 0 } }
+
+defaultClientConfig : ClientConfig
+defaultClientConfig = {
+  timeout: { millis: 30000 }
+  maxRedirects: 5
+  retry: noRetry
+  cookies: True
+  userAgent: None
+}
+
+// Connections are kept alive and reused until the resource is released.
+client : ClientConfig -> Resource Text Client
+client = config => resource {
+  c <- http.client config
+  yield c
+  _ <- c.close Unit
+}
+
+request : Text -> Url -> ClientRequest
+request = method url => { method: method, url: url, headers: [], body: NoBody, timeout: None }
+
+// Unlike `fetch`, error statuses are returned as responses; only transport failures and
+// timeouts are `Err`.
+execute : Client -> ClientRequest -> Effect Text (Result Error ClientResponse)
+execute = c req => c.send req
+
+cookiesFor : Client -> Url -> Effect Text (List Header)
+cookiesFor = c url => c.cookies url
 "#;
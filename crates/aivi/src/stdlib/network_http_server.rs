@@ -3,17 +3,36 @@ pub const MODULE_NAME: &str = "aivi.net.http_server";
 pub const SOURCE: &str = r#"
 @no_prelude
 module aivi.net.http_server
-export Header, Request, Response, ServerConfig
+export Header, Request, Response, ServerConfig, ServerOptions, Body
 export HttpError, WsError, WsMessage, ServerReply
+export Http, Ws, TextMsg, BinaryMsg, Ping, Pong, Close, Buffered, Streamed
 export Server, WebSocket
-export listen, stop, wsRecv, wsSend, wsClose
+export listen, listenWith, listenHttps, defaultServerOptions, stop, wsRecv, wsSend, wsClose
+export emptyBody, textBody, readBody, SseEvent, sseEvent, sse
+export Handler, Middleware, Route, PathParam, ParamValue, Field, CorsConfig, SetCookie
+export IntParam, TextParam
+export route, onGet, onPost, onPut, onDelete, router, staticDir
+export param, paramInt
+export middleware, logging, cors, compression, auth, bearerToken
+export header, requestPath, query, queryParam, cookies, cookie, form, lookupField
+export setCookie, sessionCookie
 
 use aivi
+use aivi.text (Utf8)
+use aivi.net.sockets (TlsServerConfig)
 
 Header = { name: Text, value: Text }
-Request = { method: Text, path: Text, headers: List Header, body: List Int, remoteAddr: Option Text }
-Response = { status: Int, headers: List Header, body: List Int }
+
+// `Streamed` request bodies arrive when `streamRequestBodies` is set; `Streamed` response bodies
+// are sent with chunked transfer encoding as the stream produces them.
+type Body = Buffered Bytes | Streamed (Stream Bytes)
+
+Request = { method: Text, path: Text, headers: List Header, body: Body, remoteAddr: Option Text }
+Response = { status: Int, headers: List Header, body: Body }
 ServerConfig = { address: Text }
+
+// Requests with larger bodies get a 413, or a failing body stream once the limit is passed.
+ServerOptions = { maxBodyBytes: Int, streamRequestBodies: Bool }
 HttpError = { message: Text }
 WsError = { message: Text }
 
@@ -22,7 +41,26 @@ type ServerReply = Http Response | Ws (WebSocket -> Effect WsError Unit)
 
 listen : ServerConfig -> (Request -> Effect HttpError ServerReply) -> Resource HttpError Server
 listen = config handler => resource {
-  server <- httpServer.listen config handler
+  server <- httpServer.listen { address: config.address, tls: None } handler
+  yield server
+  _ <- httpServer.stop server
+}
+
+// 16 MiB bodies, buffered before the handler runs. `listen` uses these.
+defaultServerOptions : ServerOptions
+defaultServerOptions = { maxBodyBytes: 16777216, streamRequestBodies: False }
+
+listenWith : ServerConfig -> ServerOptions -> (Request -> Effect HttpError ServerReply) -> Resource HttpError Server
+listenWith = config options handler => resource {
+  server <- httpServer.listenWith { address: config.address, tls: None } options handler
+  yield server
+  _ <- httpServer.stop server
+}
+
+// Every connection is HTTPS; ALPN offers HTTP/2 and HTTP/1.1.
+listenHttps : ServerConfig -> TlsServerConfig -> ServerOptions -> (Request -> Effect HttpError ServerReply) -> Resource HttpError Server
+listenHttps = config tls options handler => resource {
+  server <- httpServer.listenWith { address: config.address, tls: Some tls } options handler
   yield server
   _ <- httpServer.stop server
 }
@@ -38,4 +76,171 @@ wsSend = socket msg => httpServer.ws_send socket msg
 
 wsClose : WebSocket -> Effect WsError Unit
 wsClose = socket => httpServer.ws_close socket
+
+// Bodies
+
+emptyBody : Body
+emptyBody = Buffered (text.toBytes Utf8 "")
+
+textBody : Text -> Body
+textBody = value => Buffered (text.toBytes Utf8 value)
+
+// The whole request body, buffered or streamed, failing when it is larger than `limit` bytes.
+// A streamed body can only be read once.
+readBody : Int -> Request -> Effect HttpError Bytes
+readBody = limit req => httpServer.readBody limit req
+
+// Server-Sent Events
+
+SseEvent = { event: Option Text, data: Text, id: Option Text, retry: Option Int }
+
+sseEvent : Text -> SseEvent
+sseEvent = data => { event: None, data: data, id: None, retry: None }
+
+// A `text/event-stream` response sending every event received on the channel until its sender
+// is closed.
+sse : Recv SseEvent -> Response
+sse = events => { status: 200, headers: [{ name: "content-type", value: "text/event-stream" }, { name: "cache-control", value: "no-cache" }], body: Streamed (httpServer.sseStream events) }
+
+// Routing
+
+Handler = Request -> Effect HttpError ServerReply
+Middleware = Handler -> Handler
+
+type ParamValue = IntParam Int | TextParam Text
+PathParam = { name: Text, value: ParamValue }
+
+// `pattern` segments: `users` matches literally, `:name` captures a segment, `:name:Int` only
+// matches an integer and `*name` (last) captures the rest of the path. `method` `*` matches any.
+Route = { method: Text, pattern: Text, handler: List PathParam -> Handler }
+
+route : Text -> Text -> (List PathParam -> Handler) -> Route
+route = method pattern handler => { method: method, pattern: pattern, handler: handler }
+
+// `GET` routes also answer `HEAD`. `patch` is a keyword, so use `route "PATCH"`.
+onGet : Text -> (List PathParam -> Handler) -> Route
+onGet = pattern handler => route "GET" pattern handler
+
+onPost : Text -> (List PathParam -> Handler) -> Route
+onPost = pattern handler => route "POST" pattern handler
+
+onPut : Text -> (List PathParam -> Handler) -> Route
+onPut = pattern handler => route "PUT" pattern handler
+
+onDelete : Text -> (List PathParam -> Handler) -> Route
+onDelete = pattern handler => route "DELETE" pattern handler
+
+// Runs the first route matching the request's method and path. Paths no route matches get a
+// 404; paths that only match other methods get a 405 with an `Allow` header.
+router : List Route -> Handler
+router = routes req => httpServer.route routes req
+
+// Serves files below `root` for `GET {prefix}/...`, with ETag, Last-Modified and Range support.
+staticDir : Text -> Text -> Route
+staticDir = prefix root => onGet "{prefix}/*file" (staticFile root)
+
+staticFile : Text -> List PathParam -> Handler
+staticFile = root params req => httpServer.serveStatic root (paramOr "" "file" params) req
+
+param : Text -> List PathParam -> Option Text
+param = name params => params ?
+  | [] => None
+  | [p, ...rest] => if p.name == name then Some (paramText p.value) else param name rest
+
+// `Some` only for parameters declared as `:name:Int`.
+paramInt : Text -> List PathParam -> Option Int
+paramInt = name params => params ?
+  | [] => None
+  | [p, ...rest] => if p.name == name then paramIntValue p.value else paramInt name rest
+
+paramOr : Text -> Text -> List PathParam -> Text
+paramOr = fallback name params => param name params ?
+  | Some value => value
+  | None => fallback
+
+paramText : ParamValue -> Text
+paramText = value => value ?
+  | IntParam n => "{n}"
+  | TextParam text => text
+
+paramIntValue : ParamValue -> Option Int
+paramIntValue = value => value ?
+  | IntParam n => Some n
+  | TextParam _ => None
+
+// Middleware
+
+// The first middleware in the list is the outermost one.
+middleware : List Middleware -> Handler -> Handler
+middleware = layers handler => layers ?
+  | [] => handler
+  | [layer, ...rest] => layer (middleware rest handler)
+
+// Writes one structured log line per request, in the `aivi.log` JSON format.
+logging : Middleware
+logging = next req => httpServer.logging next req
+
+// `origins` may contain `*`. Empty `headers` allows whatever the preflight asks for.
+CorsConfig = { origins: List Text, methods: List Text, headers: List Text, credentials: Bool, maxAge: Option Int }
+
+// Answers preflight requests and adds CORS headers to the responses of allowed origins.
+cors : CorsConfig -> Middleware
+cors = config next req => httpServer.cors config next req
+
+// Gzips textual 200 responses of 1 KiB or more when the client sends `Accept-Encoding: gzip`.
+compression : Middleware
+compression = next req => httpServer.compression next req
+
+// Rejects requests `check` does not accept with 401 and a `Bearer` challenge.
+auth : (Request -> Effect HttpError Bool) -> Middleware
+auth = check next req => effect {
+  allowed <- check req
+  if allowed then next req else pure (Http { status: 401, headers: [{ name: "www-authenticate", value: "Bearer" }], body: emptyBody })
+}
+
+bearerToken : Request -> Option Text
+bearerToken = req => httpServer.bearerToken req
+
+// Request parsing
+
+Field = { name: Text, value: Text }
+
+// Case-insensitive lookup of the first header with that name.
+header : Text -> Request -> Option Text
+header = name req => httpServer.header name req
+
+// `Request.path` without the query string.
+requestPath : Request -> Text
+requestPath = req => httpServer.requestPath req
+
+query : Request -> List Field
+query = req => httpServer.query req
+
+queryParam : Text -> Request -> Option Text
+queryParam = name req => lookupField name (query req)
+
+cookies : Request -> List Field
+cookies = req => httpServer.cookies req
+
+cookie : Text -> Request -> Option Text
+cookie = name req => lookupField name (cookies req)
+
+// Decodes an `application/x-www-form-urlencoded` body.
+form : Request -> Result HttpError (List Field)
+form = req => httpServer.form req
+
+lookupField : Text -> List Field -> Option Text
+lookupField = name fields => fields ?
+  | [] => None
+  | [field, ...rest] => if field.name == name then Some field.value else lookupField name rest
+
+SetCookie = { name: Text, value: Text, path: Option Text, domain: Option Text, maxAge: Option Int, httpOnly: Bool, secure: Bool, sameSite: Option Text }
+
+// A `Set-Cookie` response header. The value is percent-encoded; `cookie` decodes it again.
+setCookie : SetCookie -> Result HttpError Header
+setCookie = c => httpServer.setCookie c
+
+// An `HttpOnly`, `SameSite=Lax` cookie for the whole site that lasts until the browser closes.
+sessionCookie : Text -> Text -> SetCookie
+sessionCookie = name value => { name: name, value: value, path: Some "/", domain: None, maxAge: None, httpOnly: True, secure: False, sameSite: Some "Lax" }
 "#;
@@ -3,13 +3,28 @@ pub const MODULE_NAME: &str = "aivi.net.sockets";
 pub const SOURCE: &str = r#"
 @no_prelude
 module aivi.net.sockets
-export Address, SocketError, Listener, Connection
+export Address, SocketError, Listener, Connection, UdpSocket, Datagram
+export TlsServerConfig, TlsClientConfig, ClientCertificate
 export listen, accept, connect, send, recv, close
+export listenTls, connectTls, defaultTlsClientConfig
+export listenUnix, connectUnix
+export bind, sendTo, recvFrom, joinMulticast, leaveMulticast
 
 use aivi
 
 Address = { host: Text, port: Int }
 SocketError = { message: Text }
+Datagram = { data: Bytes, from: Address }
+
+// PEM files. With `clientCaFile`, every client must present a certificate issued by that CA.
+TlsServerConfig = { certFile: Text, keyFile: Text, clientCaFile: Option Text }
+ClientCertificate = { certFile: Text, keyFile: Text }
+// `serverName` (SNI and certificate name) defaults to the host; `caFile` to the bundled
+// Mozilla roots.
+TlsClientConfig = { serverName: Option Text, caFile: Option Text, clientCert: Option ClientCertificate }
+
+defaultTlsClientConfig : TlsClientConfig
+defaultTlsClientConfig = { serverName: None, caFile: None, clientCert: None }
 
 listen : Address -> Resource SocketError Listener
 listen = address => resource {
@@ -18,18 +33,63 @@ listen = address => resource {
   _ <- sockets.closeListener listener
 }
 
+// Accepted connections complete the TLS handshake before `accept` returns them.
+listenTls : Address -> TlsServerConfig -> Resource SocketError Listener
+listenTls = address config => resource {
+  listener <- sockets.listenTls address config
+  yield listener
+  _ <- sockets.closeListener listener
+}
+
+// The socket file is removed when the listener is released.
+listenUnix : Text -> Resource SocketError Listener
+listenUnix = path => resource {
+  listener <- sockets.listenUnix path
+  yield listener
+  _ <- sockets.closeListener listener
+}
+
+// Waits for the next connection; cancelling the surrounding fiber stops the wait.
 accept : Listener -> Effect SocketError Connection
 accept = listener => sockets.accept listener
 
 connect : Address -> Effect SocketError Connection
 connect = address => sockets.connect address
 
-send : Connection -> List Int -> Effect SocketError Unit
+connectTls : Address -> TlsClientConfig -> Effect SocketError Connection
+connectTls = address config => sockets.connectTls address config
+
+connectUnix : Text -> Effect SocketError Connection
+connectUnix = path => sockets.connectUnix path
+
+send : Connection -> Bytes -> Effect SocketError Unit
 send = conn bytes => sockets.send conn bytes
 
-recv : Connection -> Effect SocketError (List Int)
+// Returns whatever arrived next, up to 4096 bytes; empty `Bytes` means the peer closed.
+recv : Connection -> Effect SocketError Bytes
 recv = conn => sockets.recv conn
 
 close : Connection -> Effect SocketError Unit
 close = conn => sockets.close conn
+
+// Port 0 picks a free port.
+bind : Address -> Resource SocketError UdpSocket
+bind = address => resource {
+  socket <- sockets.bind address
+  yield socket
+  _ <- sockets.closeUdp socket
+}
+
+sendTo : UdpSocket -> Address -> Bytes -> Effect SocketError Unit
+sendTo = socket address bytes => sockets.sendTo socket address bytes
+
+// Waits for the next datagram; cancelling the surrounding fiber stops the wait.
+recvFrom : UdpSocket -> Effect SocketError Datagram
+recvFrom = socket => sockets.recvFrom socket
+
+joinMulticast : UdpSocket -> Text -> Effect SocketError Unit
+joinMulticast = socket group => sockets.joinMulticast socket group
+
+leaveMulticast : UdpSocket -> Text -> Effect SocketError Unit
+leaveMulticast = socket group => sockets.leaveMulticast socket group
 "#;
@@ -4,18 +4,33 @@ pub const SOURCE: &str = r#"
 @no_prelude
 module aivi.net.streams
 export Stream, StreamError
-export fromSocket, toSocket, chunks
+export fromSocket, toSocket, chunks, fromChunks, fromChannel, readChunk, collect
 
 use aivi
 
 StreamError = { message: Text }
 
-fromSocket : Connection -> Stream (List Int)
+fromSocket : Connection -> Stream Bytes
 fromSocket = conn => streams.fromSocket conn
 
-toSocket : Connection -> Stream (List Int) -> Effect StreamError Unit
+toSocket : Connection -> Stream Bytes -> Effect StreamError Unit
 toSocket = conn stream => streams.toSocket conn stream
 
-chunks : Int -> Stream (List Int) -> Stream (List Int)
+chunks : Int -> Stream Bytes -> Stream Bytes
 chunks = size stream => streams.chunks size stream
+
+fromChunks : List Bytes -> Stream Bytes
+fromChunks = items => streams.fromChunks items
+
+// Yields every value sent on the channel; the stream ends when the sender is closed.
+fromChannel : Recv Bytes -> Stream Bytes
+fromChannel = receiver => streams.fromChannel receiver
+
+// Streams are consumed as they are read: `None` marks the end.
+readChunk : Stream Bytes -> Effect StreamError (Option Bytes)
+readChunk = stream => streams.readChunk stream
+
+// Reads the rest of the stream, failing once it holds more than `limit` bytes.
+collect : Int -> Stream Bytes -> Effect StreamError Bytes
+collect = limit stream => streams.collect limit stream
 "#;
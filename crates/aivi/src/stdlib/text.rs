@@ -5,7 +5,7 @@ pub const SOURCE: &str = r#"
 module aivi.text
 export Bytes, Encoding, TextError
 export Utf8, Utf16, Utf32, Latin1
-export ToText
+export ToText, ToJson
 export length, isEmpty, isDigit, isAlpha, isAlnum, isSpace, isUpper, isLower
 export contains, startsWith, endsWith, indexOf, lastIndexOf, count, compare
 export slice, split, splitLines, chunk
@@ -37,6 +37,11 @@ instance ToText {} = {
       | {} => text.toText value
 }
 
+// JSON encoding to `Text`; `deriving (ToJson)` generates instances structurally.
+class ToJson A = {
+  toJson: A -> Text
+}
+
 length : Text -> Int
 length = value => text.length value
 
@@ -3,22 +3,50 @@ pub const MODULE_NAME: &str = "aivi.ui";
 pub const SOURCE: &str = r#"
 @no_prelude
 module aivi.ui
-export VNode, Attr, PatchOp, Event, LiveConfig, LiveError
+export VNode, Attr, PatchOp, Event, KeyEvent, LiveConfig, LiveError
 export Element, TextNode, Keyed
 export Class, Id, Style, OnClick, OnInput
-export Replace, SetText, SetAttr, RemoveAttr
-export Click, Input
+export OnSubmit, OnChange, OnKeyDown, OnFocus, OnBlur, OnCheck
+export Value, Checked, Debounce, Throttle
+export Replace, SetText, SetAttr, RemoveAttr, SetProp, InsertChild, MoveChild, RemoveChild
+export Click, Input, Submit, Change, KeyDown, Focus, Blur, Check
 export vElement, vText, vKeyed
 export vClass, vId, vStyle, vAttr, vOnClick, vOnInput
+export vOnSubmit, vOnChange, vOnKeyDown, vOnFocus, vOnBlur, vOnCheck
+export vValue, vChecked, vDebounce, vThrottle
 export renderHtml, diff, patchToJson, eventFromJson
-export live
+export live, liveApp
+export Cmd, Sub
+export cmdNone, cmdBatch, cmdPerform, subEvery, subReceive
 
 use aivi
+use aivi.duration (Span)
 
 // A typed Virtual DOM. Rendering is backend/runtime-specific.
 type VNode msg = Element Text (List (Attr msg)) (List (VNode msg)) | TextNode Text | Keyed Text (VNode msg)
 
-type Attr msg = Class Text | Id Text | Style { } | OnClick msg | OnInput (Text -> msg) | Attr Text Text
+// `Value` and `Checked` are DOM properties: live diffs patch them with `SetProp` so they keep
+// tracking the model after the user edits the control. `Debounce`/`Throttle` (milliseconds)
+// rate-limit the events sent by the element they are attached to.
+type Attr msg =
+  | Class Text
+  | Id Text
+  | Style { }
+  | OnClick msg
+  | OnInput (Text -> msg)
+  | Attr Text Text
+  | OnSubmit ({ } -> msg)
+  | OnChange (Text -> msg)
+  | OnKeyDown (KeyEvent -> msg)
+  | OnFocus msg
+  | OnBlur msg
+  | OnCheck (Bool -> msg)
+  | Value Text
+  | Checked Bool
+  | Debounce Int
+  | Throttle Int
+
+type KeyEvent = { key: Text, ctrl: Bool, shift: Bool, alt: Bool, meta: Bool }
 
 // Helpers for tooling/lowerings. These avoid common names like `id` or `style`,
 // which are likely to appear in user code and other stdlib modules.
@@ -49,10 +77,58 @@ vOnClick = msg => OnClick msg
 vOnInput : (Text -> msg) -> Attr msg
 vOnInput = f => OnInput f
 
-// Patch operations for LiveView-like updates.
-type PatchOp = Replace Text Text | SetText Text Text | SetAttr Text Text Text | RemoveAttr Text Text
+// The form's named fields as a record of `Text` values.
+vOnSubmit : ({ } -> msg) -> Attr msg
+vOnSubmit = f => OnSubmit f
 
-type Event = Click Int | Input Int Text
+vOnChange : (Text -> msg) -> Attr msg
+vOnChange = f => OnChange f
+
+vOnKeyDown : (KeyEvent -> msg) -> Attr msg
+vOnKeyDown = f => OnKeyDown f
+
+vOnFocus : msg -> Attr msg
+vOnFocus = msg => OnFocus msg
+
+vOnBlur : msg -> Attr msg
+vOnBlur = msg => OnBlur msg
+
+vOnCheck : (Bool -> msg) -> Attr msg
+vOnCheck = f => OnCheck f
+
+vValue : Text -> Attr msg
+vValue = t => Value t
+
+vChecked : Bool -> Attr msg
+vChecked = b => Checked b
+
+vDebounce : Int -> Attr msg
+vDebounce = millis => Debounce millis
+
+vThrottle : Int -> Attr msg
+vThrottle = millis => Throttle millis
+
+// Patch operations for LiveView-like updates. The first field is always the target node id;
+// child ops address the parent, and their `Int` is the child position when the op is applied.
+type PatchOp =
+  | Replace Text Text
+  | SetText Text Text
+  | SetAttr Text Text Text
+  | RemoveAttr Text Text
+  | SetProp Text Text Text
+  | InsertChild Text Int Text
+  | MoveChild Text Text Int
+  | RemoveChild Text Text
+
+type Event =
+  | Click Int
+  | Input Int Text
+  | Submit Int { }
+  | Change Int Text
+  | KeyDown Int KeyEvent
+  | Focus Int
+  | Blur Int
+  | Check Int Bool
 
 type LiveConfig = { address: Text, path: Text, title: Text }
 type LiveError = { message: Text }
@@ -73,4 +149,30 @@ eventFromJson = text => ui.eventFromJson text
 // The client protocol is implemented by the runtime's embedded JS snippet.
 live : LiveConfig -> model -> (model -> VNode msg) -> (msg -> model -> model) -> Effect LiveError Server
 live = cfg initialModel view update => ui.live cfg initialModel view update
+
+// Elm-style live server. `update` also returns commands, whose effects run in the background and
+// report back as messages; `subscriptions` is re-evaluated after every update. Each browser
+// session starts from `init`, and its commands and subscriptions are cancelled when it closes.
+liveApp : LiveConfig -> (model, Cmd msg) -> (model -> VNode msg) -> (msg -> model -> (model, Cmd msg)) -> (model -> List (Sub msg)) -> Effect LiveError Server
+liveApp = cfg init view update subscriptions => ui.liveApp cfg init view update subscriptions
+
+// `Cmd msg` and `Sub msg` are opaque; build them with these helpers.
+cmdNone : Cmd msg
+cmdNone = ui.cmdNone
+
+cmdBatch : List (Cmd msg) -> Cmd msg
+cmdBatch = cmds => ui.cmdBatch cmds
+
+// Runs `task` and turns its outcome into a message.
+cmdPerform : (Result e a -> msg) -> Effect e a -> Cmd msg
+cmdPerform = toMsg task => ui.cmdPerform toMsg task
+
+// Sends `msg` every `interval`.
+subEvery : Span -> msg -> Sub msg
+subEvery = interval msg => ui.subEvery interval.millis msg
+
+// Turns every value received on the channel into a message. `Err Closed` is delivered once when
+// the channel closes.
+subReceive : Recv a -> (Result Closed a -> msg) -> Sub msg
+subReceive = receiver toMsg => ui.subReceive receiver toMsg
 "#;
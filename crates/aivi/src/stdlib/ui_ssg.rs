@@ -0,0 +1,59 @@
+pub const MODULE_NAME: &str = "aivi.ui.ssg";
+
+pub const SOURCE: &str = r#"
+@no_prelude
+module aivi.ui.ssg
+export Meta, Link, CssRule, Head, Page, SiteConfig, AuditIssue, SiteReport, SsgError
+export page, withHead, withMeta, withLink, withStyle
+export renderPage, auditPage, build
+
+use aivi
+use aivi.ui (VNode)
+
+type Meta = { name: Text, content: Text }
+type Link = { rel: Text, href: Text }
+
+// `style` uses the same CSS record shape as `Style`; it is inlined into the page's `<style>`.
+type CssRule = { selector: Text, style: { } }
+
+type Head = { title: Text, meta: List Meta, links: List Link, styles: List CssRule }
+
+// `route` is the URL path the page is served from: `/` and `/docs/` become `index.html`
+// files, `/about` becomes `about/index.html` and `/feed.xml` is written as is.
+type Page msg = { route: Text, head: Head, body: VNode msg }
+
+// `hydrate` is the WebSocket path (or `ws://` URL) of a `live` server. When set, every page
+// loads the live client, which takes over the static markup once the socket connects.
+type SiteConfig = { outDir: Text, hydrate: Option Text }
+
+type AuditIssue = { route: Text, message: Text }
+type SiteReport = { files: List Text, audit: List AuditIssue }
+type SsgError = { message: Text }
+
+page : Text -> Text -> VNode msg -> Page msg
+page = route title body => { route: route, head: { title: title, meta: [], links: [], styles: [] }, body: body }
+
+withHead : Head -> Page msg -> Page msg
+withHead = head p => { route: p.route, head: head, body: p.body }
+
+withMeta : Text -> Text -> Page msg -> Page msg
+withMeta = name content p => withHead { title: p.head.title, meta: [...p.head.meta, { name: name, content: content }], links: p.head.links, styles: p.head.styles } p
+
+withLink : Text -> Text -> Page msg -> Page msg
+withLink = rel href p => withHead { title: p.head.title, meta: p.head.meta, links: [...p.head.links, { rel: rel, href: href }], styles: p.head.styles } p
+
+withStyle : Text -> { } -> Page msg -> Page msg
+withStyle = selector style p => withHead { title: p.head.title, meta: p.head.meta, links: p.head.links, styles: [...p.head.styles, { selector: selector, style: style }] } p
+
+renderPage : Option Text -> Page msg -> Text
+renderPage = hydrate p => ui.renderPage hydrate p
+
+// Reports markup that would run script or escape its context (inline handlers, `javascript:`
+// URLs, `<script>` elements, unsafe selectors or CSS values). Text is always escaped.
+auditPage : Page msg -> List AuditIssue
+auditPage = p => ui.auditPage p
+
+// Writes one HTML file per page under `outDir` and reports the files and audit findings.
+build : SiteConfig -> List (Page msg) -> Effect SsgError SiteReport
+build = cfg pages => ui.buildSite cfg pages
+"#;
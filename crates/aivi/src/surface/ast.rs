@@ -6,7 +6,14 @@ pub struct SpannedName {
     pub span: Span,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl SpannedName {
+    /// `?name` stands for an expression still to be written; the type checker reports what fits.
+    pub fn is_typed_hole(&self) -> bool {
+        self.name.starts_with('?')
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ScopeItemKind {
     Value,
     Domain,
@@ -63,6 +70,8 @@ pub struct TypeDecl {
     pub name: SpannedName,
     pub params: Vec<SpannedName>,
     pub constructors: Vec<TypeCtor>,
+    /// Classes listed in a trailing `deriving (...)` clause.
+    pub deriving: Vec<SpannedName>,
     pub span: Span,
 }
 
@@ -72,6 +81,10 @@ pub struct TypeAlias {
     pub name: SpannedName,
     pub params: Vec<SpannedName>,
     pub aliased: TypeExpr,
+    /// Classes listed in a trailing `deriving (...)` clause.
+    pub deriving: Vec<SpannedName>,
+    /// `opaque type`: the representation is only visible inside the defining module.
+    pub opaque: bool,
     pub span: Span,
 }
 
@@ -87,9 +100,20 @@ pub struct ClassDecl {
     pub decorators: Vec<Decorator>,
     pub name: SpannedName,
     pub params: Vec<TypeExpr>,
+    pub fundeps: Vec<FunctionalDependency>,
     pub constraints: Vec<TypeVarConstraint>,
     pub supers: Vec<TypeExpr>,
     pub members: Vec<ClassMember>,
+    /// Default method bodies, for the class's own members or for members of its superclasses.
+    pub defaults: Vec<Def>,
+    pub span: Span,
+}
+
+/// `| A -> B` on a class head: the `from` parameters of an instance determine its `to` parameters.
+#[derive(Debug, Clone)]
+pub struct FunctionalDependency {
+    pub from: Vec<SpannedName>,
+    pub to: Vec<SpannedName>,
     pub span: Span,
 }
 
@@ -107,6 +131,25 @@ pub struct ClassMember {
     pub span: Span,
 }
 
+/// A user-defined effect: `effect Log = { log: Text -> Unit }`.
+///
+/// Each operation is performed as an `Effect` carrying the effect's label in its row and is
+/// interpreted by the nearest enclosing `expr with { ... }` handler.
+#[derive(Debug, Clone)]
+pub struct EffectDecl {
+    pub decorators: Vec<Decorator>,
+    pub name: SpannedName,
+    pub operations: Vec<EffectOperation>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectOperation {
+    pub name: SpannedName,
+    pub ty: TypeExpr,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct InstanceDecl {
     pub decorators: Vec<Decorator>,
@@ -142,6 +185,7 @@ pub enum ModuleItem {
     ClassDecl(ClassDecl),
     InstanceDecl(InstanceDecl),
     DomainDecl(DomainDecl),
+    EffectDecl(EffectDecl),
 }
 
 #[derive(Debug, Clone)]
@@ -174,6 +218,8 @@ pub enum TypeExpr {
     },
     Record {
         fields: Vec<(SpannedName, TypeExpr)>,
+        /// Row variable naming the remaining fields: `{ name: Text | r }`.
+        rest: Option<SpannedName>,
         span: Span,
     },
     Tuple {
@@ -299,6 +345,13 @@ pub enum Expr {
         items: Vec<BlockItem>,
         span: Span,
     },
+    /// `body with handler`: runs `body` with the operations named by the fields of `handler`
+    /// interpreted by those fields.
+    Handle {
+        body: Box<Expr>,
+        handler: Box<Expr>,
+        span: Span,
+    },
     Raw {
         text: String,
         span: Span,
@@ -29,6 +29,7 @@ fn wrap_in_pure(expr: Expr) -> Expr {
         | Expr::If { span, .. }
         | Expr::Binary { span, .. }
         | Expr::Block { span, .. }
+        | Expr::Handle { span, .. }
         | Expr::Raw { span, .. } => span.clone(),
     };
 
@@ -178,6 +179,15 @@ fn desugar_expr(expr: Expr) -> Expr {
             body: Box::new(desugar_expr(*body)),
             span,
         },
+        Expr::Handle {
+            body,
+            handler,
+            span,
+        } => Expr::Handle {
+            body: Box::new(desugar_expr(*body)),
+            handler: Box::new(desugar_expr(*handler)),
+            span,
+        },
         Expr::Match {
             scrutinee,
             arms,
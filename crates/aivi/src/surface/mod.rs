@@ -1,10 +1,13 @@
 mod ast;
 mod desugar;
 mod parser;
+mod recursion;
 
 pub use ast::*;
 pub use desugar::desugar_effect_sugars;
+pub(crate) use parser::parse_sigil_text;
 pub use parser::{parse_modules, parse_modules_from_tokens};
+pub(crate) use recursion::{expr_mentions, function_binding, recursive_binding_groups};
 
 #[cfg(test)]
 mod tests;
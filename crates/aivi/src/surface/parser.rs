@@ -1,4 +1,5 @@
 include!("parser/entrypoints.rs");
+include!("parser/deriving.rs");
 include!("parser/module.rs");
 include!("parser/declarations.rs");
 include!("parser/definitions.rs");
@@ -187,11 +187,13 @@ impl Parser {
             }
             self.consume_newlines();
         }
+        let deriving = self.parse_deriving_clause();
         let span = merge_span(
             name.span.clone(),
-            ctors
+            deriving
                 .last()
-                .map(|ctor| ctor.span.clone())
+                .map(|class| class.span.clone())
+                .or_else(|| ctors.last().map(|ctor| ctor.span.clone()))
                 .unwrap_or(name.span.clone()),
         );
         Some(TypeDecl {
@@ -199,6 +201,7 @@ impl Parser {
             name,
             params,
             constructors: ctors,
+            deriving,
             span,
         })
     }
@@ -214,16 +217,62 @@ impl Parser {
         let aliased = self.parse_type_expr().unwrap_or(TypeExpr::Unknown {
             span: name.span.clone(),
         });
-        let span = merge_span(name.span.clone(), type_span(&aliased));
+        let deriving = self.parse_deriving_clause();
+        let span = merge_span(
+            name.span.clone(),
+            deriving
+                .last()
+                .map(|class| class.span.clone())
+                .unwrap_or_else(|| type_span(&aliased)),
+        );
         Some(TypeAlias {
             decorators,
             name,
             params,
             aliased,
+            deriving,
+            opaque: false,
             span,
         })
     }
 
+    /// Parses an optional `deriving (C1, C2)` clause after a type declaration.
+    ///
+    /// The clause may sit on the same line or on a following line; when it is absent the
+    /// parser position is left untouched so the next module item starts cleanly.
+    fn parse_deriving_clause(&mut self) -> Vec<SpannedName> {
+        let checkpoint = self.pos;
+        self.consume_newlines();
+        if self.consume_ident_text("deriving").is_none() {
+            self.pos = checkpoint;
+            return Vec::new();
+        }
+        let mut classes = Vec::new();
+        if self
+            .expect_symbol("(", "expected '(' after 'deriving'")
+            .is_none()
+        {
+            return classes;
+        }
+        loop {
+            self.consume_newlines();
+            match self.consume_ident() {
+                Some(class) => classes.push(class),
+                None => {
+                    let span = self.peek_span().unwrap_or_else(|| self.previous_span());
+                    self.emit_diag("E1541", "expected a class name in 'deriving' clause", span);
+                    break;
+                }
+            }
+            self.consume_newlines();
+            if !self.consume_symbol(",") {
+                break;
+            }
+        }
+        self.expect_symbol(")", "expected ')' to close 'deriving' clause");
+        classes
+    }
+
     fn parse_class_decl(&mut self, decorators: Vec<Decorator>) -> Option<ClassDecl> {
         self.reject_debug_decorators(&decorators, "class declarations");
         let start = self.previous_span();
@@ -236,6 +285,11 @@ impl Parser {
                 break;
             }
         }
+        let fundeps = if self.consume_symbol("|") {
+            self.parse_class_fundeps(&params)
+        } else {
+            Vec::new()
+        };
         self.consume_newlines();
         self.expect_symbol("=", "expected '=' in class declaration");
         self.consume_newlines();
@@ -265,14 +319,22 @@ impl Parser {
                 .is_some_and(|tok| tok.kind == TokenKind::Symbol && tok.text == "(")
         }
 
+        fn peek_is_with_body(parser: &Parser) -> bool {
+            parser.peek_keyword("with")
+                && parser
+                    .tokens
+                    .get(parser.pos + 1)
+                    .is_some_and(|tok| tok.kind == TokenKind::Symbol && tok.text == "{")
+        }
+
         let mut body_opt: Option<TypeExpr> = None;
-        if !self.check_symbol("{") && !peek_is_with_constraints(self) {
+        if !self.check_symbol("{") && !peek_is_with_constraints(self) && !peek_is_with_body(self) {
             // Parse a `with`-separated chain, but stop before `with (...)` constraints.
             if let Some(first) = self.parse_type_pipe() {
                 let mut items = vec![first];
                 loop {
                     self.consume_newlines();
-                    if peek_is_with_constraints(self) {
+                    if peek_is_with_constraints(self) || peek_is_with_body(self) {
                         break;
                     }
                     if self.consume_ident_text("with").is_none() {
@@ -362,30 +424,15 @@ impl Parser {
             }
         }
 
-        // Parse optional trailing member record (`{ ... }` or `with { ... }`).
+        // Parse optional trailing member block (`{ ... }` or `with { ... }`).
         self.consume_newlines();
-        if self.peek_keyword("with")
-            && self
-                .tokens
-                .get(self.pos + 1)
-                .is_some_and(|tok| tok.kind == TokenKind::Symbol && tok.text == "{")
-        {
+        if peek_is_with_body(self) {
             let _ = self.consume_ident_text("with");
             self.consume_newlines();
         }
+        let mut defaults = Vec::new();
         if self.check_symbol("{") {
-            if let Some(TypeExpr::Record { fields, .. }) = self.parse_type_atom() {
-                for (field_name, field_ty) in fields {
-                    let span = merge_span(field_name.span.clone(), type_span(&field_ty));
-                    members.push(ClassMember {
-                        name: field_name,
-                        ty: field_ty,
-                        span,
-                    });
-                }
-            } else {
-                self.expect_symbol("{", "expected '{' to start class member set");
-            }
+            self.parse_class_body(&mut members, &mut defaults);
         }
 
         let span = merge_span(start, self.previous_span());
@@ -393,9 +440,149 @@ impl Parser {
             decorators,
             name,
             params,
+            fundeps,
             constraints,
             supers,
             members,
+            defaults,
+            span,
+        })
+    }
+
+    /// `| A -> B, A B -> C` after the class parameters.
+    fn parse_class_fundeps(&mut self, params: &[TypeExpr]) -> Vec<FunctionalDependency> {
+        let param_names: Vec<&str> = params
+            .iter()
+            .filter_map(|param| match param {
+                TypeExpr::Name(name) => Some(name.name.as_str()),
+                TypeExpr::Apply { base, .. } => match base.as_ref() {
+                    TypeExpr::Name(name) => Some(name.name.as_str()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        let mut fundeps = Vec::new();
+        loop {
+            let mut from = Vec::new();
+            while let Some(var) = self.consume_ident() {
+                from.push(var);
+            }
+            self.expect_symbol("->", "expected '->' in functional dependency");
+            let mut to = Vec::new();
+            while let Some(var) = self.consume_ident() {
+                to.push(var);
+            }
+            for var in from.iter().chain(to.iter()) {
+                if !param_names.contains(&var.name.as_str()) {
+                    self.emit_diag(
+                        "E1548",
+                        &format!(
+                            "functional dependency mentions '{}', which is not a class parameter",
+                            var.name
+                        ),
+                        var.span.clone(),
+                    );
+                }
+            }
+            if from.is_empty() || to.is_empty() {
+                self.emit_diag(
+                    "E1548",
+                    "a functional dependency needs parameters on both sides of '->'",
+                    self.previous_span(),
+                );
+            } else {
+                let span = merge_span(from[0].span.clone(), to[to.len() - 1].span.clone());
+                fundeps.push(FunctionalDependency { from, to, span });
+            }
+            if !self.consume_symbol(",") {
+                break;
+            }
+        }
+        fundeps
+    }
+
+    /// The member block of a class: `name: Type` declares a member, `name = expr` gives a default.
+    fn parse_class_body(&mut self, members: &mut Vec<ClassMember>, defaults: &mut Vec<Def>) {
+        self.expect_symbol("{", "expected '{' to start class member set");
+        while self.pos < self.tokens.len() {
+            self.consume_newlines();
+            if self.check_symbol("}") || self.pos >= self.tokens.len() {
+                break;
+            }
+            let checkpoint = self.pos;
+            if let Some(member_name) = self.consume_name() {
+                if self.consume_symbol(":") {
+                    self.consume_newlines();
+                    if let Some(ty) = self.parse_type_expr() {
+                        let span = merge_span(member_name.span.clone(), type_span(&ty));
+                        members.push(ClassMember {
+                            name: member_name,
+                            ty,
+                            span,
+                        });
+                    }
+                    self.consume_newlines();
+                    self.consume_symbol(",");
+                    continue;
+                }
+                self.pos = checkpoint;
+                if let Some(def) = self.parse_def(Vec::new()) {
+                    defaults.push(def);
+                    self.consume_newlines();
+                    self.consume_symbol(",");
+                    continue;
+                }
+            }
+            // Recovery: skip unexpected tokens inside the member block.
+            self.pos = checkpoint + 1;
+        }
+        self.expect_symbol("}", "expected '}' to close class member set");
+    }
+
+    fn parse_effect_decl(&mut self, decorators: Vec<Decorator>) -> Option<EffectDecl> {
+        self.reject_debug_decorators(&decorators, "effect declarations");
+        let start = self.previous_span();
+        let name = self.consume_ident()?;
+        self.consume_newlines();
+        self.expect_symbol("=", "expected '=' in effect declaration");
+        self.consume_newlines();
+        let mut operations = Vec::new();
+        match self.parse_type_atom() {
+            Some(TypeExpr::Record { fields, rest, .. }) => {
+                if let Some(rest) = rest {
+                    self.emit_diag(
+                        "E1544",
+                        "effect operations cannot name a row variable",
+                        rest.span,
+                    );
+                }
+                for (op_name, op_ty) in fields {
+                    let span = merge_span(op_name.span.clone(), type_span(&op_ty));
+                    operations.push(EffectOperation {
+                        name: op_name,
+                        ty: op_ty,
+                        span,
+                    });
+                }
+            }
+            other => {
+                let span = other
+                    .as_ref()
+                    .map(type_span)
+                    .unwrap_or_else(|| self.peek_span().unwrap_or_else(|| self.previous_span()));
+                self.emit_diag(
+                    "E1544",
+                    "expected '{ operation: Type, ... }' after '=' in effect declaration",
+                    span,
+                );
+            }
+        }
+        let span = merge_span(start, self.previous_span());
+        Some(EffectDecl {
+            decorators,
+            name,
+            operations,
             span,
         })
     }
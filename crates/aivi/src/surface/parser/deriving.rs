@@ -0,0 +1,868 @@
+/// Classes a `deriving (...)` clause can generate instances for.
+const DERIVABLE_CLASSES: [&str; 4] = ["Setoid", "Ord", "ToText", "ToJson"];
+
+/// The structure a derived instance is generated from: the constructors of an ADT or the
+/// fields of a closed record alias.
+#[derive(Clone)]
+enum DeriveShape {
+    Ctors(Vec<TypeCtor>),
+    Record(Vec<(SpannedName, TypeExpr)>),
+}
+
+#[derive(Clone)]
+struct DeriveTarget {
+    name: SpannedName,
+    params: Vec<SpannedName>,
+    shape: DeriveShape,
+    classes: Vec<SpannedName>,
+}
+
+/// One match case of a derived member: a constructor (or the record itself) and its fields.
+struct DeriveCase<'a> {
+    ctor: Option<&'a SpannedName>,
+    labels: Vec<&'a SpannedName>,
+    fields: Vec<&'a TypeExpr>,
+}
+
+struct DeriveContext<'a> {
+    /// `(class, type)` pairs with an instance in the module, hand-written or derived.
+    instances: &'a std::collections::HashSet<(String, String)>,
+    /// Constructors of the module's ADTs, used to parenthesise nested `toText` output.
+    adts: &'a std::collections::HashMap<String, Vec<TypeCtor>>,
+    span: Span,
+}
+
+/// Expands `deriving (...)` clauses into instance declarations appended to the module.
+///
+/// Derived instances carry a `@derived` decorator whose argument names the type, so the
+/// instance checker can attribute errors to the clause and `aivi desugar` shows the members.
+fn expand_deriving(modules: &mut [Module]) -> Vec<FileDiagnostic> {
+    use std::collections::{HashMap, HashSet};
+
+    let mut diags = Vec::new();
+    for module in modules {
+        let targets = collect_derive_targets(module, &mut diags);
+        if targets.is_empty() {
+            continue;
+        }
+
+        let declared: HashSet<(String, String)> = module
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::InstanceDecl(instance) => instance
+                    .params
+                    .first()
+                    .and_then(type_expr_head)
+                    .map(|head| (instance.name.name.clone(), head)),
+                _ => None,
+            })
+            .collect();
+        let adts: HashMap<String, Vec<TypeCtor>> = module
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::TypeDecl(decl) => {
+                    Some((decl.name.name.clone(), decl.constructors.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut instances = declared.clone();
+        let mut planned = Vec::new();
+        for target in &targets {
+            for class in &target.classes {
+                let key = (class.name.clone(), target.name.name.clone());
+                let problem = if declared.contains(&key) {
+                    Some((
+                        "E1543",
+                        format!(
+                            "cannot derive '{}' for '{}': an instance is already declared",
+                            class.name, target.name.name
+                        ),
+                    ))
+                } else if !instances.insert(key) {
+                    Some((
+                        "E1543",
+                        format!(
+                            "cannot derive '{}' for '{}': the class is listed twice",
+                            class.name, target.name.name
+                        ),
+                    ))
+                } else {
+                    let has_setoid = declared
+                        .contains(&("Setoid".to_string(), target.name.name.clone()))
+                        || target.classes.iter().any(|class| class.name == "Setoid");
+                    underivable(class, target, has_setoid)
+                };
+                match problem {
+                    Some((code, message)) => {
+                        instances.remove(&(class.name.clone(), target.name.name.clone()));
+                        diags.push(derive_diag(&module.path, code, message, class.span.clone()));
+                    }
+                    None => planned.push((target, class)),
+                }
+            }
+        }
+
+        for (target, class) in planned {
+            let ctx = DeriveContext {
+                instances: &instances,
+                adts: &adts,
+                span: class.span.clone(),
+            };
+            let instance = derive_instance(target, &class.name, &ctx);
+            module.items.push(ModuleItem::InstanceDecl(instance));
+        }
+    }
+    diags
+}
+
+fn collect_derive_targets(module: &Module, diags: &mut Vec<FileDiagnostic>) -> Vec<DeriveTarget> {
+    let mut targets = Vec::new();
+    for item in &module.items {
+        match item {
+            ModuleItem::TypeDecl(decl) if !decl.deriving.is_empty() => {
+                targets.push(DeriveTarget {
+                    name: decl.name.clone(),
+                    params: decl.params.clone(),
+                    shape: DeriveShape::Ctors(decl.constructors.clone()),
+                    classes: decl.deriving.clone(),
+                });
+            }
+            ModuleItem::TypeAlias(alias) if !alias.deriving.is_empty() => match &alias.aliased {
+                TypeExpr::Record {
+                    fields, rest: None, ..
+                } => targets.push(DeriveTarget {
+                    name: alias.name.clone(),
+                    params: alias.params.clone(),
+                    shape: DeriveShape::Record(fields.clone()),
+                    classes: alias.deriving.clone(),
+                }),
+                _ => diags.push(derive_diag(
+                    &module.path,
+                    "E1543",
+                    format!(
+                        "cannot derive instances for '{}': only ADTs and closed record types support 'deriving'",
+                        alias.name.name
+                    ),
+                    alias.name.span.clone(),
+                )),
+            },
+            _ => {}
+        }
+    }
+    targets
+}
+
+/// Returns why `class` has no derivable shape for `target`, if it has none.
+fn underivable(
+    class: &SpannedName,
+    target: &DeriveTarget,
+    has_setoid: bool,
+) -> Option<(&'static str, String)> {
+    let prefix = format!("cannot derive '{}' for '{}'", class.name, target.name.name);
+    if !DERIVABLE_CLASSES.contains(&class.name.as_str()) {
+        let hint = match class.name.as_str() {
+            "Eq" => " (did you mean 'Setoid'?)",
+            "Show" => " (did you mean 'ToText'?)",
+            "Json" => " (did you mean 'ToJson'?)",
+            _ => "",
+        };
+        return Some((
+            "E1542",
+            format!("{prefix}: only Setoid, Ord, ToText and ToJson can be derived{hint}"),
+        ));
+    }
+    if class.name == "Ord" && !has_setoid {
+        return Some((
+            "E1543",
+            format!("{prefix}: 'Ord' extends 'Setoid', so derive or declare 'Setoid' as well"),
+        ));
+    }
+    if class.name == "ToText" && matches!(target.shape, DeriveShape::Record(_)) {
+        return Some((
+            "E1543",
+            format!("{prefix}: records already convert to Text through the 'ToText {{}}' instance"),
+        ));
+    }
+    for field in shape_fields(&target.shape) {
+        if class.name != "ToText" && type_expr_has_func(field) {
+            let what = if class.name == "ToJson" {
+                "encoded as JSON"
+            } else {
+                "compared"
+            };
+            return Some((
+                "E1543",
+                format!("{prefix}: fields of function type cannot be {what}"),
+            ));
+        }
+        if matches!(class.name.as_str(), "Ord" | "ToJson") {
+            if let Some(param) = target
+                .params
+                .iter()
+                .find(|param| type_expr_mentions(field, &param.name))
+            {
+                return Some((
+                    "E1543",
+                    format!(
+                        "{prefix}: a field of type parameter '{}' would need a '{} {}' constraint, which instances cannot declare",
+                        param.name, class.name, param.name
+                    ),
+                ));
+            }
+        }
+    }
+    None
+}
+
+fn derive_diag(path: &str, code: &str, message: String, span: Span) -> FileDiagnostic {
+    FileDiagnostic {
+        path: path.to_string(),
+        diagnostic: Diagnostic {
+            code: code.to_string(),
+            severity: DiagnosticSeverity::Error,
+            message,
+            span,
+            labels: Vec::new(),
+        },
+    }
+}
+
+fn derive_instance(target: &DeriveTarget, class: &str, ctx: &DeriveContext<'_>) -> InstanceDecl {
+    let span = ctx.span.clone();
+    let cases = derive_cases(&target.shape);
+    let mut defs = Vec::new();
+    match class {
+        "Setoid" => defs.push(derive_member("equals", derive_equals(&cases, ctx), &span)),
+        "Ord" => defs.push(derive_member("lte", derive_lte(&cases, ctx), &span)),
+        "ToText" => defs.push(derive_member("toText", derive_to_text(&cases, ctx), &span)),
+        _ => defs.push(derive_member("toJson", derive_to_json(&cases, ctx), &span)),
+    }
+
+    let type_name = SpannedName {
+        name: target.name.name.clone(),
+        span: span.clone(),
+    };
+    let param = if target.params.is_empty() {
+        TypeExpr::Name(type_name.clone())
+    } else {
+        TypeExpr::Apply {
+            base: Box::new(TypeExpr::Name(type_name.clone())),
+            args: target
+                .params
+                .iter()
+                .map(|param| {
+                    TypeExpr::Name(SpannedName {
+                        name: param.name.clone(),
+                        span: span.clone(),
+                    })
+                })
+                .collect(),
+            span: span.clone(),
+        }
+    };
+    InstanceDecl {
+        decorators: vec![Decorator {
+            name: SpannedName {
+                name: "derived".to_string(),
+                span: span.clone(),
+            },
+            arg: Some(Expr::Ident(type_name)),
+            span: span.clone(),
+        }],
+        name: SpannedName {
+            name: class.to_string(),
+            span: span.clone(),
+        },
+        params: vec![param],
+        defs,
+        span,
+    }
+}
+
+fn derive_cases(shape: &DeriveShape) -> Vec<DeriveCase<'_>> {
+    match shape {
+        DeriveShape::Ctors(ctors) => ctors
+            .iter()
+            .map(|ctor| DeriveCase {
+                ctor: Some(&ctor.name),
+                labels: Vec::new(),
+                fields: ctor.args.iter().collect(),
+            })
+            .collect(),
+        DeriveShape::Record(fields) => vec![DeriveCase {
+            ctor: None,
+            labels: fields.iter().map(|(label, _)| label).collect(),
+            fields: fields.iter().map(|(_, ty)| ty).collect(),
+        }],
+    }
+}
+
+fn shape_fields(shape: &DeriveShape) -> Vec<&TypeExpr> {
+    match shape {
+        DeriveShape::Ctors(ctors) => ctors.iter().flat_map(|ctor| ctor.args.iter()).collect(),
+        DeriveShape::Record(fields) => fields.iter().map(|(_, ty)| ty).collect(),
+    }
+}
+
+fn derive_member(name: &str, expr: Expr, span: &Span) -> Def {
+    Def {
+        decorators: Vec::new(),
+        name: SpannedName {
+            name: name.to_string(),
+            span: span.clone(),
+        },
+        params: Vec::new(),
+        expr,
+        span: span.clone(),
+    }
+}
+
+/// `equals = left right => (left, right) ? | (C a0.., C b0..) => a0 == b0 && .. | (C _.., _) => False`
+///
+/// The mismatch arms are per constructor (never a bare `_`) so the clause still fails to match
+/// values of other types sharing the `equals` name at runtime.
+fn derive_equals(cases: &[DeriveCase<'_>], ctx: &DeriveContext<'_>) -> Expr {
+    let span = &ctx.span;
+    let mut arms = Vec::new();
+    for case in cases {
+        let checks: Vec<Expr> = case
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, ty)| derive_field_equals(ty, index, ctx))
+            .collect();
+        let body = checks
+            .into_iter()
+            .reduce(|acc, check| derive_binary("&&", acc, check, span))
+            .unwrap_or_else(|| derive_bool(true, span));
+        arms.push(derive_arm(
+            derive_pair_pattern(
+                derive_case_pattern(case, Some("a"), span),
+                derive_case_pattern(case, Some("b"), span),
+                span,
+            ),
+            body,
+            span,
+        ));
+    }
+    if cases.len() > 1 {
+        for case in cases {
+            arms.push(derive_arm(
+                derive_pair_pattern(
+                    derive_case_pattern(case, None, span),
+                    Pattern::Wildcard(span.clone()),
+                    span,
+                ),
+                derive_bool(false, span),
+                span,
+            ));
+        }
+    }
+    derive_pair_lambda(arms, span)
+}
+
+/// `lte = left right => ...` comparing constructors by declaration order, then fields
+/// lexicographically.
+fn derive_lte(cases: &[DeriveCase<'_>], ctx: &DeriveContext<'_>) -> Expr {
+    let span = &ctx.span;
+    let mut arms = Vec::new();
+    for case in cases {
+        arms.push(derive_arm(
+            derive_pair_pattern(
+                derive_case_pattern(case, Some("a"), span),
+                derive_case_pattern(case, Some("b"), span),
+                span,
+            ),
+            derive_lexicographic(&case.fields, 0, ctx),
+            span,
+        ));
+    }
+    if cases.len() == 1 {
+        return derive_pair_lambda(arms, span);
+    }
+
+    for (index, case) in cases.iter().enumerate() {
+        arms.push(derive_arm(
+            derive_pair_pattern(
+                derive_case_pattern(case, None, span),
+                Pattern::Ident(derive_name("right", span)),
+                span,
+            ),
+            derive_binary(
+                "<",
+                derive_int(index, span),
+                derive_call("rank", vec![derive_ident("right", span)], span),
+                span,
+            ),
+            span,
+        ));
+    }
+    let rank = Expr::Lambda {
+        params: vec![Pattern::Ident(derive_name("value", span))],
+        body: Box::new(Expr::Match {
+            scrutinee: Some(Box::new(derive_ident("value", span))),
+            arms: cases
+                .iter()
+                .enumerate()
+                .map(|(index, case)| {
+                    derive_arm(
+                        derive_case_pattern(case, None, span),
+                        derive_int(index, span),
+                        span,
+                    )
+                })
+                .collect(),
+            span: span.clone(),
+        }),
+        span: span.clone(),
+    };
+    let Expr::Lambda { params, body, .. } = derive_pair_lambda(arms, span) else {
+        unreachable!("derive_pair_lambda builds a lambda");
+    };
+    Expr::Lambda {
+        params,
+        body: Box::new(Expr::Block {
+            kind: BlockKind::Plain,
+            items: vec![
+                BlockItem::Let {
+                    pattern: Pattern::Ident(derive_name("rank", span)),
+                    expr: rank,
+                    span: span.clone(),
+                },
+                BlockItem::Expr {
+                    expr: *body,
+                    span: span.clone(),
+                },
+            ],
+            span: span.clone(),
+        }),
+        span: span.clone(),
+    }
+}
+
+fn derive_lexicographic(fields: &[&TypeExpr], index: usize, ctx: &DeriveContext<'_>) -> Expr {
+    let span = &ctx.span;
+    let Some(ty) = fields.get(index) else {
+        return derive_bool(true, span);
+    };
+    let left = derive_ident(&format!("a{index}"), span);
+    let right = derive_ident(&format!("b{index}"), span);
+    let compare = match type_expr_head(ty).as_deref() {
+        Some("Int") => derive_binary("<=", left, right, span),
+        Some("Text") => derive_binary(
+            "<=",
+            Expr::Call {
+                func: Box::new(derive_field_access("text", "compare", span)),
+                args: vec![left, right],
+                span: span.clone(),
+            },
+            derive_int(0, span),
+            span,
+        ),
+        Some("Bool") => Expr::If {
+            cond: Box::new(left),
+            then_branch: Box::new(right),
+            else_branch: Box::new(derive_bool(true, span)),
+            span: span.clone(),
+        },
+        _ => derive_call("lte", vec![left, right], span),
+    };
+    if index + 1 == fields.len() {
+        return compare;
+    }
+    Expr::If {
+        cond: Box::new(derive_field_equals(ty, index, ctx)),
+        then_branch: Box::new(derive_lexicographic(fields, index + 1, ctx)),
+        else_branch: Box::new(compare),
+        span: span.clone(),
+    }
+}
+
+/// Compares field `index` with `equals` when its type has a `Setoid` instance in this module and
+/// with structural `==` otherwise.
+fn derive_field_equals(ty: &TypeExpr, index: usize, ctx: &DeriveContext<'_>) -> Expr {
+    let span = &ctx.span;
+    let left = derive_ident(&format!("a{index}"), span);
+    let right = derive_ident(&format!("b{index}"), span);
+    let has_setoid = type_expr_head(ty)
+        .is_some_and(|head| ctx.instances.contains(&("Setoid".to_string(), head)));
+    if has_setoid {
+        derive_call("equals", vec![left, right], span)
+    } else {
+        derive_binary("==", left, right, span)
+    }
+}
+
+/// `toText = value => value ? | C a0 a1 => "C {a0} {a1}" | ..`
+///
+/// Only ADTs get here: records are rejected because `ToText {}` already covers them.
+fn derive_to_text(cases: &[DeriveCase<'_>], ctx: &DeriveContext<'_>) -> Expr {
+    let span = &ctx.span;
+    let arms = cases
+        .iter()
+        .filter_map(|case| {
+            let ctor = case.ctor?;
+            let mut parts = Vec::new();
+            derive_push_text(&mut parts, &ctor.name, span);
+            for (index, ty) in case.fields.iter().enumerate() {
+                derive_push_text(&mut parts, " ", span);
+                parts.push(derive_text_expr(derive_field_text(ty, index, ctx), span));
+            }
+            Some(derive_arm(
+                derive_case_pattern(case, Some("a"), span),
+                derive_text(parts, span),
+                span,
+            ))
+        })
+        .collect();
+    derive_value_lambda(arms, span)
+}
+
+/// Renders constructor argument `index` for `toText`: types with a `ToText` instance in this
+/// module go through `toText`, parenthesised when the value is itself a constructor application;
+/// everything else uses the interpolation rendering.
+fn derive_field_text(ty: &TypeExpr, index: usize, ctx: &DeriveContext<'_>) -> Expr {
+    let span = &ctx.span;
+    let value = derive_ident(&format!("a{index}"), span);
+    let Some(head) = type_expr_head(ty).filter(|head| {
+        ctx.instances
+            .contains(&("ToText".to_string(), head.clone()))
+    }) else {
+        return value;
+    };
+    let rendered = derive_call("toText", vec![value], span);
+    let Some(ctors) = ctx.adts.get(&head) else {
+        return rendered;
+    };
+    if ctors.iter().all(|ctor| ctor.args.is_empty()) {
+        return rendered;
+    }
+    Expr::Match {
+        scrutinee: Some(Box::new(derive_ident(&format!("a{index}"), span))),
+        arms: ctors
+            .iter()
+            .map(|ctor| {
+                let pattern = Pattern::Constructor {
+                    name: SpannedName {
+                        name: ctor.name.name.clone(),
+                        span: span.clone(),
+                    },
+                    args: ctor
+                        .args
+                        .iter()
+                        .map(|_| Pattern::Wildcard(span.clone()))
+                        .collect(),
+                    span: span.clone(),
+                };
+                let body = if ctor.args.is_empty() {
+                    rendered.clone()
+                } else {
+                    let mut parts = Vec::new();
+                    derive_push_text(&mut parts, "(", span);
+                    parts.push(derive_text_expr(rendered.clone(), span));
+                    derive_push_text(&mut parts, ")", span);
+                    derive_text(parts, span)
+                };
+                derive_arm(pattern, body, span)
+            })
+            .collect(),
+        span: span.clone(),
+    }
+}
+
+/// `toJson`: records become objects, nullary constructors strings and other constructors
+/// `{"tag":"C","values":[..]}`.
+fn derive_to_json(cases: &[DeriveCase<'_>], ctx: &DeriveContext<'_>) -> Expr {
+    let span = &ctx.span;
+    let arms = cases
+        .iter()
+        .map(|case| {
+            let mut parts = Vec::new();
+            match case.ctor {
+                Some(ctor) if case.fields.is_empty() => {
+                    derive_push_text(&mut parts, &format!("\"{}\"", ctor.name), span);
+                }
+                Some(ctor) => {
+                    derive_push_text(
+                        &mut parts,
+                        &format!("{{\"tag\":\"{}\",\"values\":[", ctor.name),
+                        span,
+                    );
+                    for (index, ty) in case.fields.iter().enumerate() {
+                        if index > 0 {
+                            derive_push_text(&mut parts, ",", span);
+                        }
+                        derive_push_json(&mut parts, ty, index, span);
+                    }
+                    derive_push_text(&mut parts, "]}", span);
+                }
+                None => {
+                    derive_push_text(&mut parts, "{", span);
+                    for (index, (label, ty)) in case.labels.iter().zip(&case.fields).enumerate() {
+                        let sep = if index == 0 { "" } else { "," };
+                        derive_push_text(&mut parts, &format!("{sep}\"{}\":", label.name), span);
+                        derive_push_json(&mut parts, ty, index, span);
+                    }
+                    derive_push_text(&mut parts, "}", span);
+                }
+            }
+            derive_arm(
+                derive_case_pattern(case, Some("a"), span),
+                derive_text(parts, span),
+                span,
+            )
+        })
+        .collect();
+    derive_value_lambda(arms, span)
+}
+
+fn derive_push_json(parts: &mut Vec<TextPart>, ty: &TypeExpr, index: usize, span: &Span) {
+    let value = derive_ident(&format!("a{index}"), span);
+    match type_expr_head(ty).as_deref() {
+        Some("Int") | Some("Float") => parts.push(derive_text_expr(value, span)),
+        Some("Bool") => parts.push(derive_text_expr(
+            Expr::If {
+                cond: Box::new(value),
+                then_branch: Box::new(derive_string("true", span)),
+                else_branch: Box::new(derive_string("false", span)),
+                span: span.clone(),
+            },
+            span,
+        )),
+        Some("Text") => {
+            let escaped = [("\\", "\\\\"), ("\"", "\\\""), ("\n", "\\n")]
+                .into_iter()
+                .fold(value, |acc, (needle, replacement)| Expr::Call {
+                    func: Box::new(derive_field_access("text", "replaceAll", span)),
+                    args: vec![
+                        acc,
+                        derive_string(needle, span),
+                        derive_string(replacement, span),
+                    ],
+                    span: span.clone(),
+                });
+            derive_push_text(parts, "\"", span);
+            parts.push(derive_text_expr(escaped, span));
+            derive_push_text(parts, "\"", span);
+        }
+        _ => parts.push(derive_text_expr(
+            derive_call("toJson", vec![value], span),
+            span,
+        )),
+    }
+}
+
+/// Pattern for one case: binds fields to `{prefix}0..` or ignores them when `prefix` is `None`.
+fn derive_case_pattern(case: &DeriveCase<'_>, prefix: Option<&str>, span: &Span) -> Pattern {
+    let field_pattern = |index: usize| match prefix {
+        Some(prefix) => Pattern::Ident(derive_name(&format!("{prefix}{index}"), span)),
+        None => Pattern::Wildcard(span.clone()),
+    };
+    match case.ctor {
+        Some(ctor) => Pattern::Constructor {
+            name: SpannedName {
+                name: ctor.name.clone(),
+                span: span.clone(),
+            },
+            args: (0..case.fields.len()).map(field_pattern).collect(),
+            span: span.clone(),
+        },
+        None => Pattern::Record {
+            fields: case
+                .labels
+                .iter()
+                .enumerate()
+                .map(|(index, label)| RecordPatternField {
+                    path: vec![SpannedName {
+                        name: label.name.clone(),
+                        span: span.clone(),
+                    }],
+                    pattern: field_pattern(index),
+                    span: span.clone(),
+                })
+                .collect(),
+            span: span.clone(),
+        },
+    }
+}
+
+fn derive_pair_pattern(left: Pattern, right: Pattern, span: &Span) -> Pattern {
+    Pattern::Tuple {
+        items: vec![left, right],
+        span: span.clone(),
+    }
+}
+
+/// `left right => (left, right) ? | ..`
+fn derive_pair_lambda(arms: Vec<MatchArm>, span: &Span) -> Expr {
+    Expr::Lambda {
+        params: vec![
+            Pattern::Ident(derive_name("left", span)),
+            Pattern::Ident(derive_name("right", span)),
+        ],
+        body: Box::new(Expr::Match {
+            scrutinee: Some(Box::new(Expr::Tuple {
+                items: vec![derive_ident("left", span), derive_ident("right", span)],
+                span: span.clone(),
+            })),
+            arms,
+            span: span.clone(),
+        }),
+        span: span.clone(),
+    }
+}
+
+/// `value => value ? | ..`
+fn derive_value_lambda(arms: Vec<MatchArm>, span: &Span) -> Expr {
+    Expr::Lambda {
+        params: vec![Pattern::Ident(derive_name("value", span))],
+        body: Box::new(Expr::Match {
+            scrutinee: Some(Box::new(derive_ident("value", span))),
+            arms,
+            span: span.clone(),
+        }),
+        span: span.clone(),
+    }
+}
+
+fn derive_arm(pattern: Pattern, body: Expr, span: &Span) -> MatchArm {
+    MatchArm {
+        pattern,
+        guard: None,
+        body,
+        span: span.clone(),
+    }
+}
+
+fn derive_name(name: &str, span: &Span) -> SpannedName {
+    SpannedName {
+        name: name.to_string(),
+        span: span.clone(),
+    }
+}
+
+fn derive_ident(name: &str, span: &Span) -> Expr {
+    Expr::Ident(derive_name(name, span))
+}
+
+fn derive_field_access(base: &str, field: &str, span: &Span) -> Expr {
+    Expr::FieldAccess {
+        base: Box::new(derive_ident(base, span)),
+        field: derive_name(field, span),
+        span: span.clone(),
+    }
+}
+
+fn derive_call(func: &str, args: Vec<Expr>, span: &Span) -> Expr {
+    Expr::Call {
+        func: Box::new(derive_ident(func, span)),
+        args,
+        span: span.clone(),
+    }
+}
+
+fn derive_binary(op: &str, left: Expr, right: Expr, span: &Span) -> Expr {
+    Expr::Binary {
+        op: op.to_string(),
+        left: Box::new(left),
+        right: Box::new(right),
+        span: span.clone(),
+    }
+}
+
+fn derive_bool(value: bool, span: &Span) -> Expr {
+    Expr::Literal(Literal::Bool {
+        value,
+        span: span.clone(),
+    })
+}
+
+fn derive_int(value: usize, span: &Span) -> Expr {
+    Expr::Literal(Literal::Number {
+        text: value.to_string(),
+        span: span.clone(),
+    })
+}
+
+fn derive_string(text: &str, span: &Span) -> Expr {
+    Expr::Literal(Literal::String {
+        text: text.to_string(),
+        span: span.clone(),
+    })
+}
+
+fn derive_text_expr(expr: Expr, span: &Span) -> TextPart {
+    TextPart::Expr {
+        expr: Box::new(expr),
+        span: span.clone(),
+    }
+}
+
+/// Appends literal text, merging with a preceding literal part.
+fn derive_push_text(parts: &mut Vec<TextPart>, text: &str, span: &Span) {
+    if let Some(TextPart::Text { text: last, .. }) = parts.last_mut() {
+        last.push_str(text);
+        return;
+    }
+    parts.push(TextPart::Text {
+        text: text.to_string(),
+        span: span.clone(),
+    });
+}
+
+fn derive_text(parts: Vec<TextPart>, span: &Span) -> Expr {
+    match parts.as_slice() {
+        [TextPart::Text { text, .. }] => derive_string(text, span),
+        _ => Expr::TextInterpolate {
+            parts,
+            span: span.clone(),
+        },
+    }
+}
+
+/// Name of the outermost type constructor: `Int` for `Int`, `List` for `List A`.
+fn type_expr_head(ty: &TypeExpr) -> Option<String> {
+    match ty {
+        TypeExpr::Name(name) => Some(name.name.clone()),
+        TypeExpr::Apply { base, .. } => type_expr_head(base),
+        _ => None,
+    }
+}
+
+fn type_expr_has_func(ty: &TypeExpr) -> bool {
+    match ty {
+        TypeExpr::Func { .. } => true,
+        TypeExpr::Name(_) | TypeExpr::Star { .. } | TypeExpr::Unknown { .. } => false,
+        TypeExpr::And { items, .. } | TypeExpr::Tuple { items, .. } => {
+            items.iter().any(type_expr_has_func)
+        }
+        TypeExpr::Apply { base, args, .. } => {
+            type_expr_has_func(base) || args.iter().any(type_expr_has_func)
+        }
+        TypeExpr::Record { fields, .. } => fields.iter().any(|(_, ty)| type_expr_has_func(ty)),
+    }
+}
+
+fn type_expr_mentions(ty: &TypeExpr, name: &str) -> bool {
+    match ty {
+        TypeExpr::Name(type_name) => type_name.name == name,
+        TypeExpr::Star { .. } | TypeExpr::Unknown { .. } => false,
+        TypeExpr::And { items, .. } | TypeExpr::Tuple { items, .. } => {
+            items.iter().any(|item| type_expr_mentions(item, name))
+        }
+        TypeExpr::Apply { base, args, .. } => {
+            type_expr_mentions(base, name) || args.iter().any(|arg| type_expr_mentions(arg, name))
+        }
+        TypeExpr::Func { params, result, .. } => {
+            params.iter().any(|param| type_expr_mentions(param, name))
+                || type_expr_mentions(result, name)
+        }
+        TypeExpr::Record { fields, .. } => {
+            fields.iter().any(|(_, ty)| type_expr_mentions(ty, name))
+        }
+    }
+}
@@ -16,6 +16,7 @@ pub fn parse_modules(path: &Path, content: &str) -> (Vec<Module>, Vec<FileDiagno
     inject_prelude_imports(&mut modules);
     expand_domain_exports(&mut modules);
     expand_module_aliases(&mut modules);
+    let mut deriving_diags = expand_deriving(&mut modules);
     let mut decorator_diags = apply_static_decorators(&mut modules);
     let mut diagnostics: Vec<FileDiagnostic> = lex_diags
         .into_iter()
@@ -25,6 +26,7 @@ pub fn parse_modules(path: &Path, content: &str) -> (Vec<Module>, Vec<FileDiagno
         })
         .collect();
     diagnostics.append(&mut parser.diagnostics);
+    diagnostics.append(&mut deriving_diags);
     diagnostics.append(&mut decorator_diags);
     (modules, diagnostics)
 }
@@ -38,7 +40,9 @@ pub fn parse_modules_from_tokens(
     let mut modules = parser.parse_modules();
     inject_prelude_imports(&mut modules);
     expand_domain_exports(&mut modules);
-    (modules, parser.diagnostics)
+    let mut diagnostics = parser.diagnostics;
+    diagnostics.append(&mut expand_deriving(&mut modules));
+    (modules, diagnostics)
 }
 
 fn inject_prelude_imports(modules: &mut [Module]) {
@@ -159,11 +163,12 @@ fn expand_module_aliases(modules: &mut [Module]) {
                 result: Box::new(rewrite_type_expr(*result, aliases)),
                 span,
             },
-            TypeExpr::Record { fields, span } => TypeExpr::Record {
+            TypeExpr::Record { fields, rest, span } => TypeExpr::Record {
                 fields: fields
                     .into_iter()
                     .map(|(label, ty)| (label, rewrite_type_expr(ty, aliases)))
                     .collect(),
+                rest,
                 span,
             },
             TypeExpr::Tuple { items, span } => TypeExpr::Tuple {
@@ -363,6 +368,15 @@ fn expand_module_aliases(modules: &mut [Module]) {
                     .collect(),
                 span,
             },
+            Expr::Handle {
+                body,
+                handler,
+                span,
+            } => Expr::Handle {
+                body: Box::new(rewrite_expr(*body, aliases)),
+                handler: Box::new(rewrite_expr(*handler, aliases)),
+                span,
+            },
             Expr::Ident(_) | Expr::Literal(_) | Expr::Raw { .. } | Expr::FieldSection { .. } => {
                 expr
             }
@@ -433,6 +447,9 @@ fn expand_module_aliases(modules: &mut [Module]) {
                     for member in class_decl.members.iter_mut() {
                         member.ty = rewrite_type_expr(member.ty.clone(), &aliases);
                     }
+                    for def in class_decl.defaults.iter_mut() {
+                        def.expr = rewrite_expr(def.expr.clone(), &aliases);
+                    }
                     class_decl.supers = class_decl
                         .supers
                         .iter()
@@ -440,6 +457,11 @@ fn expand_module_aliases(modules: &mut [Module]) {
                         .map(|super_expr| rewrite_type_expr(super_expr, &aliases))
                         .collect();
                 }
+                ModuleItem::EffectDecl(effect_decl) => {
+                    for operation in effect_decl.operations.iter_mut() {
+                        operation.ty = rewrite_type_expr(operation.ty.clone(), &aliases);
+                    }
+                }
             }
         }
     }
@@ -587,7 +609,8 @@ fn apply_static_decorators(modules: &mut [Module]) -> Vec<FileDiagnostic> {
                 ModuleItem::TypeSig(_)
                 | ModuleItem::TypeDecl(_)
                 | ModuleItem::TypeAlias(_)
-                | ModuleItem::ClassDecl(_) => {}
+                | ModuleItem::ClassDecl(_)
+                | ModuleItem::EffectDecl(_) => {}
             }
         }
     }
@@ -231,7 +231,17 @@ impl Parser {
         while let Some(pattern) = self.parse_pattern() {
             params.push(pattern);
         }
-        if !params.is_empty() && self.consume_symbol("=>") {
+        // In `map (n => ...)` the head stops inside the parentheses: that `=>` belongs to an
+        // argument, so this is an application rather than a lambda.
+        let open_groups = self.tokens[checkpoint..self.pos]
+            .iter()
+            .filter(|tok| tok.kind == TokenKind::Symbol)
+            .fold(0i32, |depth, tok| match tok.text.as_str() {
+                "(" | "[" | "{" => depth + 1,
+                ")" | "]" | "}" => depth - 1,
+                _ => depth,
+            });
+        if !params.is_empty() && open_groups <= 0 && self.consume_symbol("=>") {
             let body = self.parse_expr()?;
             let span = merge_span(pattern_span(&params[0]), expr_span(&body));
             return Some(Expr::Lambda {
@@ -247,6 +257,7 @@ impl Parser {
 
     fn parse_match_or_binary(&mut self) -> Option<Expr> {
         let expr = self.parse_binary(0)?;
+        let expr = self.parse_handler_suffix(expr);
         if self.consume_symbol("?") {
             let mut arms = Vec::new();
             loop {
@@ -289,6 +300,34 @@ impl Parser {
         }
         Some(expr)
     }
+    /// Parses `body with handler`, where the handler is a record literal of operation clauses
+    /// or any postfix expression evaluating to one.
+    fn parse_handler_suffix(&mut self, mut body: Expr) -> Expr {
+        while self.match_keyword("with") {
+            let with_span = self.previous_span();
+            self.consume_newlines();
+            let handler = self.parse_postfix().unwrap_or_else(|| {
+                let span = self.peek_span().unwrap_or_else(|| with_span.clone());
+                self.emit_diag(
+                    "E1545",
+                    "expected a handler after 'with' (e.g. `with { log: msg => ... }`)",
+                    span.clone(),
+                );
+                Expr::Raw {
+                    text: String::new(),
+                    span,
+                }
+            });
+            let span = merge_span(expr_span(&body), expr_span(&handler));
+            body = Expr::Handle {
+                body: Box::new(body),
+                handler: Box::new(handler),
+                span,
+            };
+        }
+        body
+    }
+
     fn parse_binary(&mut self, min_prec: u8) -> Option<Expr> {
         let mut left = self.parse_application()?;
         while let Some(op) = self.peek_symbol_text() {
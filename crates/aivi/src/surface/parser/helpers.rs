@@ -190,7 +190,8 @@ fn expr_span(expr: &Expr) -> Span {
         | Expr::Match { span, .. }
         | Expr::If { span, .. }
         | Expr::Binary { span, .. }
-        | Expr::Block { span, .. } => span.clone(),
+        | Expr::Block { span, .. }
+        | Expr::Handle { span, .. } => span.clone(),
         Expr::Raw { span, .. } => span.clone(),
     }
 }
@@ -230,7 +231,7 @@ fn literal_span(literal: &Literal) -> Span {
     }
 }
 
-fn parse_sigil_text(text: &str) -> Option<(String, String, String)> {
+pub(crate) fn parse_sigil_text(text: &str) -> Option<(String, String, String)> {
     let mut iter = text.chars();
     if iter.next()? != '~' {
         return None;
@@ -242,6 +242,47 @@ impl Parser {
                 continue;
             }
 
+            if self.peek_keyword("effect")
+                && self.tokens.get(self.pos + 1).is_some_and(|tok| {
+                    tok.kind == TokenKind::Ident
+                        && tok.text.starts_with(|ch: char| ch.is_ascii_uppercase())
+                })
+            {
+                self.pos += 1;
+                if let Some(effect_decl) = self.parse_effect_decl(decorators) {
+                    items.push(ModuleItem::EffectDecl(effect_decl));
+                }
+                continue;
+            }
+
+            if self.peek_keyword("opaque")
+                && self.tokens.get(self.pos + 1).is_some_and(|tok| {
+                    tok.kind == TokenKind::Ident
+                        && (tok.text == "type"
+                            || tok.text.starts_with(|ch: char| ch.is_ascii_uppercase()))
+                })
+            {
+                let opaque_span = self.peek_span().unwrap_or_else(|| self.previous_span());
+                self.pos += 1;
+                let _ = self.match_keyword("type");
+                match self.parse_type_decl_or_alias(decorators) {
+                    Some(ModuleItem::TypeAlias(mut alias)) => {
+                        alias.opaque = true;
+                        items.push(ModuleItem::TypeAlias(alias));
+                    }
+                    Some(item) => {
+                        self.emit_diag(
+                            "E1546",
+                            "`opaque` only applies to type aliases; keep an ADT abstract by not exporting its constructors",
+                            opaque_span,
+                        );
+                        items.push(item);
+                    }
+                    None => {}
+                }
+                continue;
+            }
+
             if self.match_keyword("type") {
                 if let Some(item) = self.parse_type_decl_or_alias(decorators) {
                     items.push(item);
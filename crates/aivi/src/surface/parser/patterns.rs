@@ -14,6 +14,14 @@ impl Parser {
             if ident.name == "_" {
                 return Some(Pattern::Wildcard(ident.span));
             }
+            if ident.is_typed_hole() {
+                self.emit_diag(
+                    "E1547",
+                    &format!("typed hole '{}' cannot be used as a pattern", ident.name),
+                    ident.span.clone(),
+                );
+                return Some(Pattern::Wildcard(ident.span));
+            }
             if ident
                 .name
                 .chars()
@@ -73,9 +81,10 @@ impl Parser {
             let mut rest = None;
             while !self.check_symbol("]") && self.pos < self.tokens.len() {
                 if self.consume_symbol("...") {
-                    if let Some(pattern) = self.parse_pattern() {
-                        rest = Some(Box::new(pattern));
-                    }
+                    // A bare `...` ignores the tail, like `..._`.
+                    let dots = self.previous_span();
+                    let pattern = self.parse_pattern().unwrap_or(Pattern::Wildcard(dots));
+                    rest = Some(Box::new(pattern));
                 } else if let Some(pattern) = self.parse_pattern() {
                     items.push(pattern);
                 }
@@ -292,6 +301,7 @@ impl Parser {
             name,
             params,
             constructors: ctors,
+            deriving: Vec::new(),
             span,
         })
     }
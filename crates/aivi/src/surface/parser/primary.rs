@@ -3,7 +3,7 @@ impl Parser {
         if let Some(token) = self.tokens.get(self.pos) {
             match token.kind {
                 TokenKind::Ident => {
-                    if token.text == "then" || token.text == "else" || token.text == "or" {
+                    if matches!(token.text.as_str(), "then" | "else" | "or" | "with") {
                         return false;
                     }
                     return true;
@@ -378,6 +378,42 @@ impl Parser {
                 ("style", HtmlAttrValue::Splice(expr)) => Some(call1("vStyle", expr)),
                 ("onClick", HtmlAttrValue::Splice(expr)) => Some(call1("vOnClick", expr)),
                 ("onInput", HtmlAttrValue::Splice(expr)) => Some(call1("vOnInput", expr)),
+                ("onSubmit", HtmlAttrValue::Splice(expr)) => Some(call1("vOnSubmit", expr)),
+                ("onChange", HtmlAttrValue::Splice(expr)) => Some(call1("vOnChange", expr)),
+                ("onKeyDown", HtmlAttrValue::Splice(expr)) => Some(call1("vOnKeyDown", expr)),
+                ("onFocus", HtmlAttrValue::Splice(expr)) => Some(call1("vOnFocus", expr)),
+                ("onBlur", HtmlAttrValue::Splice(expr)) => Some(call1("vOnBlur", expr)),
+                ("onCheck", HtmlAttrValue::Splice(expr)) => Some(call1("vOnCheck", expr)),
+                // `value` and `checked` are DOM properties, patched rather than set as attributes.
+                ("value", HtmlAttrValue::Text(v)) => Some(call1("vValue", mk_string(&v))),
+                ("value", HtmlAttrValue::Splice(expr)) => Some(call1("vValue", expr)),
+                ("checked", HtmlAttrValue::Splice(expr)) => Some(call1("vChecked", expr)),
+                ("checked", value) => {
+                    let checked = !matches!(&value, HtmlAttrValue::Text(v) if v == "false");
+                    Some(call1(
+                        "vChecked",
+                        Expr::Literal(Literal::Bool {
+                            value: checked,
+                            span: span.clone(),
+                        }),
+                    ))
+                }
+                ("debounce", HtmlAttrValue::Splice(expr)) => Some(call1("vDebounce", expr)),
+                ("throttle", HtmlAttrValue::Splice(expr)) => Some(call1("vThrottle", expr)),
+                ("debounce" | "throttle", HtmlAttrValue::Text(v)) if v.parse::<u32>().is_ok() => {
+                    let helper = if name == "debounce" {
+                        "vDebounce"
+                    } else {
+                        "vThrottle"
+                    };
+                    Some(call1(
+                        helper,
+                        Expr::Literal(Literal::Number {
+                            text: v,
+                            span: span.clone(),
+                        }),
+                    ))
+                }
                 ("key", _) => None, // handled separately
                 (_other, HtmlAttrValue::Text(v)) => {
                     Some(call2("vAttr", mk_string(&name), mk_string(&v)))
@@ -93,12 +93,27 @@ impl Parser {
         }
         if self.consume_symbol("{") {
             let mut fields = Vec::new();
+            let mut rest = None;
             self.consume_newlines();
             while !self.check_symbol("}") && self.pos < self.tokens.len() {
                 self.consume_newlines();
                 if self.check_symbol("}") {
                     break;
                 }
+                if self.consume_symbol("|") {
+                    // Row tail: `{ name: Text | r }`.
+                    self.consume_newlines();
+                    rest = self.consume_ident();
+                    if rest.is_none() {
+                        self.emit_diag(
+                            "E1540",
+                            "expected a row variable after '|' in record type",
+                            self.peek_span().unwrap_or_else(|| self.previous_span()),
+                        );
+                    }
+                    self.consume_newlines();
+                    break;
+                }
                 if let Some(name) = self.consume_ident() {
                     self.consume_newlines();
                     self.expect_symbol(":", "expected ':' in record type");
@@ -126,15 +141,16 @@ impl Parser {
                 .first()
                 .map(|field| field.0.span.clone())
                 .unwrap_or(self.previous_span());
-            return Some(TypeExpr::Record { fields, span });
+            return Some(TypeExpr::Record { fields, rest, span });
         }
         if self.consume_symbol("*") {
             let span = self.previous_span();
             return Some(TypeExpr::Star { span });
         }
         if let Some(name) = self.parse_dotted_name() {
-            if name.name == "with" {
-                // `with` is reserved in type position (composition operator).
+            if name.name == "with" || name.name == "deriving" {
+                // `with` (composition operator) and `deriving` (instance derivation clause)
+                // are reserved in type position.
                 self.pos -= 1;
                 return None;
             }
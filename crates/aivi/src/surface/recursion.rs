@@ -0,0 +1,160 @@
+use std::ops::Range;
+
+use super::{BlockItem, Expr, ListItem, Pattern, RecordField, SpannedName, TextPart};
+
+/// `name = params => body` (or `name = | arm ...`) inside a block.
+pub fn function_binding(item: &BlockItem) -> Option<(&SpannedName, &Expr)> {
+    let BlockItem::Let {
+        pattern: Pattern::Ident(name),
+        expr,
+        ..
+    } = item
+    else {
+        return None;
+    };
+    matches!(
+        expr,
+        Expr::Lambda { .. }
+            | Expr::Match {
+                scrutinee: None,
+                ..
+            }
+    )
+    .then_some((name, expr))
+}
+
+/// Finds the recursive binding groups of a block.
+///
+/// A run of consecutive function bindings is a group when one of its bodies mentions a name bound
+/// by the run; every name of a group is then in scope in all of its bodies. A run ends before a
+/// name it already binds, so a rebinding starts a new run instead of joining the old one. Other
+/// bindings keep their sequential scoping, so `n = n + 1` still refers to the previous `n`.
+pub fn recursive_binding_groups(items: &[BlockItem]) -> Vec<Range<usize>> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    while start < items.len() {
+        let mut names: Vec<&str> = Vec::new();
+        for item in &items[start..] {
+            match function_binding(item) {
+                Some((name, _)) if !names.contains(&name.name.as_str()) => {
+                    names.push(name.name.as_str())
+                }
+                _ => break,
+            }
+        }
+        let len = names.len();
+        if len == 0 {
+            start += 1;
+            continue;
+        }
+        let run = start..start + len;
+        let recursive = items[run.clone()]
+            .iter()
+            .filter_map(function_binding)
+            .any(|(_, expr)| expr_mentions(expr, &names));
+        if recursive {
+            groups.push(run);
+        }
+        start += len;
+    }
+    groups
+}
+
+/// Whether `expr` refers to one of `names`. Lambda parameters, match arm patterns and block
+/// bindings shadow the names they bind, so `f = f => f 1` does not mention the outer `f`.
+pub(crate) fn expr_mentions(expr: &Expr, names: &[&str]) -> bool {
+    let mentions = |expr: &Expr| expr_mentions(expr, names);
+    match expr {
+        Expr::Ident(name) => names.contains(&name.name.as_str()),
+        Expr::Literal(_) | Expr::FieldSection { .. } | Expr::Raw { .. } => false,
+        Expr::Suffixed { base, .. } | Expr::FieldAccess { base, .. } => mentions(base),
+        Expr::TextInterpolate { parts, .. } => parts.iter().any(|part| match part {
+            TextPart::Text { .. } => false,
+            TextPart::Expr { expr, .. } => mentions(expr),
+        }),
+        Expr::List { items, .. } => items.iter().any(|item: &ListItem| mentions(&item.expr)),
+        Expr::Tuple { items, .. } => items.iter().any(mentions),
+        Expr::Record { fields, .. } | Expr::PatchLit { fields, .. } => fields
+            .iter()
+            .any(|field: &RecordField| mentions(&field.value)),
+        Expr::Index { base, index, .. } => mentions(base) || mentions(index),
+        Expr::Call { func, args, .. } => mentions(func) || args.iter().any(mentions),
+        Expr::Lambda { params, body, .. } => expr_mentions(body, &unshadowed(names, params)),
+        Expr::Match {
+            scrutinee, arms, ..
+        } => {
+            scrutinee.as_deref().is_some_and(mentions)
+                || arms.iter().any(|arm| {
+                    let names = unshadowed(names, std::slice::from_ref(&arm.pattern));
+                    expr_mentions(&arm.body, &names)
+                        || arm
+                            .guard
+                            .as_ref()
+                            .is_some_and(|guard| expr_mentions(guard, &names))
+                })
+        }
+        Expr::If {
+            cond,
+            then_branch,
+            else_branch,
+            ..
+        } => mentions(cond) || mentions(then_branch) || mentions(else_branch),
+        Expr::Binary { left, right, .. } => mentions(left) || mentions(right),
+        Expr::Handle { body, handler, .. } => mentions(body) || mentions(handler),
+        Expr::Block { items, .. } => {
+            let mut names = names.to_vec();
+            for item in items {
+                // A function binding that mentions its own name refers to itself.
+                if let Some((name, _)) = function_binding(item) {
+                    names.retain(|candidate| *candidate != name.name);
+                }
+                let expr = match item {
+                    BlockItem::Bind { expr, .. }
+                    | BlockItem::Let { expr, .. }
+                    | BlockItem::Filter { expr, .. }
+                    | BlockItem::Yield { expr, .. }
+                    | BlockItem::Recurse { expr, .. }
+                    | BlockItem::Expr { expr, .. } => expr,
+                };
+                if expr_mentions(expr, &names) {
+                    return true;
+                }
+                if let BlockItem::Bind { pattern, .. } | BlockItem::Let { pattern, .. } = item {
+                    names = unshadowed(&names, std::slice::from_ref(pattern));
+                }
+                if names.is_empty() {
+                    return false;
+                }
+            }
+            false
+        }
+    }
+}
+
+/// `names` without the ones `patterns` bind.
+fn unshadowed<'a>(names: &[&'a str], patterns: &[Pattern]) -> Vec<&'a str> {
+    names
+        .iter()
+        .copied()
+        .filter(|name| !patterns.iter().any(|pattern| pattern_binds(pattern, name)))
+        .collect()
+}
+
+fn pattern_binds(pattern: &Pattern, name: &str) -> bool {
+    match pattern {
+        Pattern::Wildcard(_) | Pattern::Literal(_) => false,
+        Pattern::Ident(ident) => ident.name == name,
+        Pattern::Constructor { args: items, .. } | Pattern::Tuple { items, .. } => {
+            items.iter().any(|item| pattern_binds(item, name))
+        }
+        Pattern::List { items, rest, .. } => {
+            items.iter().any(|item| pattern_binds(item, name))
+                || rest
+                    .as_deref()
+                    .is_some_and(|rest| pattern_binds(rest, name))
+        }
+        Pattern::Record { fields, .. } => fields
+            .iter()
+            .any(|field| pattern_binds(&field.pattern, name)),
+    }
+}
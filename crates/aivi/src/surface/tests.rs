@@ -233,6 +233,9 @@ fn expr_contains_ident(expr: &Expr, target: &str) -> bool {
         Expr::Binary { left, right, .. } => {
             expr_contains_ident(left, target) || expr_contains_ident(right, target)
         }
+        Expr::Handle { body, handler, .. } => {
+            expr_contains_ident(body, target) || expr_contains_ident(handler, target)
+        }
         Expr::Block { items, .. } => items.iter().any(|item| match item {
             crate::surface::BlockItem::Bind { expr, .. }
             | crate::surface::BlockItem::Let { expr, .. }
@@ -697,3 +700,330 @@ value = (Ok 1) or
     let (_, diags) = parse_modules(Path::new("test.aivi"), src);
     assert!(diag_codes(&diags).contains(&"E1530".to_string()));
 }
+
+#[test]
+fn deriving_clause_expands_to_instances() {
+    let src = r#"
+module Example
+
+Shape = Circle Int | Dot
+  deriving (Setoid, Ord)
+"#;
+    let (modules, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert!(
+        diags.is_empty(),
+        "unexpected diagnostics: {:?}",
+        diag_codes(&diags)
+    );
+
+    let module = modules.first().expect("module");
+    let decl = module
+        .items
+        .iter()
+        .find_map(|item| match item {
+            ModuleItem::TypeDecl(decl) => Some(decl),
+            _ => None,
+        })
+        .expect("Shape decl");
+    let deriving: Vec<&str> = decl.deriving.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(deriving, ["Setoid", "Ord"]);
+    assert_eq!(decl.constructors.len(), 2);
+
+    let derived: Vec<(&str, Vec<&str>)> = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::InstanceDecl(instance)
+                if instance.decorators.iter().any(|d| d.name.name == "derived") =>
+            {
+                let members = instance.defs.iter().map(|d| d.name.name.as_str()).collect();
+                Some((instance.name.name.as_str(), members))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(derived, [("Setoid", vec!["equals"]), ("Ord", vec!["lte"])]);
+}
+
+#[test]
+fn deriving_rejects_classes_without_a_derivable_shape() {
+    let src = r#"
+module Example
+
+Color = Red | Green deriving (Eq, Ord)
+Name = Text deriving (Setoid)
+Handler = { run: Int -> Int } deriving (Setoid)
+Box A = Empty | Full A deriving (Setoid, ToJson)
+"#;
+    let (_, diags) = parse_modules(Path::new("test.aivi"), src);
+    let messages: Vec<&str> = diags
+        .iter()
+        .map(|d| d.diagnostic.message.as_str())
+        .collect();
+    for expected in [
+        "cannot derive 'Eq' for 'Color': only Setoid, Ord, ToText and ToJson can be derived (did you mean 'Setoid'?)",
+        "cannot derive 'Ord' for 'Color': 'Ord' extends 'Setoid', so derive or declare 'Setoid' as well",
+        "cannot derive instances for 'Name': only ADTs and closed record types support 'deriving'",
+        "cannot derive 'Setoid' for 'Handler': fields of function type cannot be compared",
+    ] {
+        assert!(
+            messages.contains(&expected),
+            "missing {expected:?}, got: {messages:?}"
+        );
+    }
+    assert!(
+        messages
+            .iter()
+            .any(|m| m
+                .starts_with("cannot derive 'ToJson' for 'Box': a field of type parameter 'A'")),
+        "got: {messages:?}"
+    );
+    assert!(
+        diag_codes(&diags).contains(&"E1542".to_string()),
+        "expected E1542, got: {:?}",
+        diag_codes(&diags)
+    );
+}
+
+#[test]
+fn parses_effect_declaration_and_handler() {
+    let src = r#"
+module Example
+
+effect Log = {
+  emit: Text -> Unit
+  level: Text
+}
+
+main = greet "Ada" with {
+  emit: msg => println msg
+  level: pure "info"
+}
+"#;
+    let (modules, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert!(
+        diags.is_empty(),
+        "unexpected diagnostics: {:?}",
+        diag_codes(&diags)
+    );
+
+    let module = modules.first().expect("module");
+    let effect = module
+        .items
+        .iter()
+        .find_map(|item| match item {
+            ModuleItem::EffectDecl(effect) => Some(effect),
+            _ => None,
+        })
+        .expect("Log effect");
+    assert_eq!(effect.name.name, "Log");
+    let ops: Vec<&str> = effect
+        .operations
+        .iter()
+        .map(|op| op.name.name.as_str())
+        .collect();
+    assert_eq!(ops, ["emit", "level"]);
+
+    let main = module
+        .items
+        .iter()
+        .find_map(|item| match item {
+            ModuleItem::Def(def) if def.name.name == "main" => Some(def),
+            _ => None,
+        })
+        .expect("main");
+    let Expr::Handle { body, handler, .. } = &main.expr else {
+        panic!("expected a handled expression, got {:?}", main.expr);
+    };
+    assert!(matches!(&**body, Expr::Call { .. }));
+    assert!(matches!(&**handler, Expr::Record { fields, .. } if fields.len() == 2));
+}
+
+#[test]
+fn rejects_effect_declaration_without_operations_record() {
+    let src = r#"
+module Example
+
+effect Log = Text
+"#;
+    let (_, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert!(
+        diag_codes(&diags).contains(&"E1544".to_string()),
+        "expected E1544, got: {:?}",
+        diag_codes(&diags)
+    );
+}
+
+#[test]
+fn finds_recursive_binding_groups_in_blocks() {
+    let src = r#"
+module Example
+
+main = {
+  n = 1
+  n = n + 1
+  isEven = k => if k == 0 then True else isOdd (k - 1)
+  isOdd = k => if k == 0 then False else isEven (k - 1)
+  double = k => k * 2
+  total = n
+  twice = k => double (double k)
+  isEven total
+}
+"#;
+    let (modules, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert!(
+        diags.is_empty(),
+        "unexpected diagnostics: {:?}",
+        diag_codes(&diags)
+    );
+
+    let module = modules.first().expect("module");
+    let Some(ModuleItem::Def(def)) = module.items.first() else {
+        panic!("expected main def");
+    };
+    let Expr::Block { items, .. } = &def.expr else {
+        panic!("expected block");
+    };
+    // `isEven`, `isOdd` and `double` form one run; `twice` is a run of its own that does not
+    // refer to itself, and the `n` bindings stay sequential.
+    assert_eq!(crate::surface::recursive_binding_groups(items), vec![2..5]);
+}
+
+#[test]
+fn recursive_binding_groups_end_at_a_rebound_name() {
+    let src = r#"
+module Example
+
+main = {
+  step = n => n + 1
+  step = n => step (step n)
+  isEven = k => if k == 0 then True else isOdd (k - 1)
+  isOdd = k => if k == 0 then False else isEven (k - 1)
+  isEven = k => k == 0
+  isEven 4
+}
+"#;
+    let (modules, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert!(
+        diags.is_empty(),
+        "unexpected diagnostics: {:?}",
+        diag_codes(&diags)
+    );
+
+    let module = modules.first().expect("module");
+    let Some(ModuleItem::Def(def)) = module.items.first() else {
+        panic!("expected main def");
+    };
+    let Expr::Block { items, .. } = &def.expr else {
+        panic!("expected block");
+    };
+    // The first `step` is a run of its own; the second starts a new run with `isEven` and
+    // `isOdd`, and the rebound `isEven` starts another one that does not recurse.
+    assert_eq!(crate::surface::recursive_binding_groups(items), vec![1..4]);
+}
+
+#[test]
+fn opaque_marks_type_aliases() {
+    let src = r#"
+module Example
+
+opaque type UserId = Text
+opaque Token = { value: Text }
+type Name = Text
+opaque type Color = Red | Green
+"#;
+    let (modules, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert_eq!(diag_codes(&diags), ["E1546"]);
+
+    let aliases: Vec<(&str, bool)> = modules
+        .first()
+        .expect("module")
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::TypeAlias(alias) => Some((alias.name.name.as_str(), alias.opaque)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        aliases,
+        [("UserId", true), ("Token", true), ("Name", false)]
+    );
+}
+
+#[test]
+fn typed_holes_parse_as_names_outside_patterns() {
+    let src = r#"
+module Example
+
+total = xs => add ?acc (?head xs)
+pick = opt => opt ?
+  | Some ?x => 1
+  | None    => 0
+"#;
+    let (modules, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert_eq!(diag_codes(&diags), ["E1547"]);
+
+    let module = modules.first().expect("module");
+    let ModuleItem::Def(total) = &module.items[0] else {
+        panic!("expected def");
+    };
+    assert!(expr_contains_ident(&total.expr, "?acc"));
+    assert!(expr_contains_ident(&total.expr, "?head"));
+}
+
+#[test]
+fn parses_class_defaults_and_functional_dependencies() {
+    let src = r#"
+module Example
+
+class Convert A B | A -> B = {
+  convert: A -> B
+  convertAll: List A -> List B
+  convertAll = xs => map convert xs
+}
+
+class Broken A B | A -> C = {
+  broken: A -> B
+}
+"#;
+    let (modules, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert_eq!(diag_codes(&diags), ["E1548"]);
+
+    let module = modules.first().expect("module");
+    let ModuleItem::ClassDecl(class_decl) = &module.items[0] else {
+        panic!("expected class decl");
+    };
+    assert_eq!(class_decl.fundeps.len(), 1);
+    assert_eq!(class_decl.fundeps[0].from[0].name, "A");
+    assert_eq!(class_decl.fundeps[0].to[0].name, "B");
+    let members: Vec<&str> = class_decl
+        .members
+        .iter()
+        .map(|member| member.name.name.as_str())
+        .collect();
+    assert_eq!(members, ["convert", "convertAll"]);
+    assert_eq!(class_decl.defaults.len(), 1);
+    assert_eq!(class_decl.defaults[0].name.name, "convertAll");
+}
+
+#[test]
+fn application_to_a_lambda_is_not_a_lambda_head() {
+    let src = r#"
+module Example
+
+bumped = map (n => n + 1) [1, 2]
+"#;
+    let (modules, diags) = parse_modules(Path::new("test.aivi"), src);
+    assert!(
+        diags.is_empty(),
+        "unexpected diagnostics: {:?}",
+        diag_codes(&diags)
+    );
+
+    let module = modules.first().expect("module");
+    let ModuleItem::Def(def) = &module.items[0] else {
+        panic!("expected def");
+    };
+    assert!(matches!(def.expr, Expr::Call { .. }), "got {:?}", def.expr);
+}
@@ -4,14 +4,14 @@ pub const KEYWORDS_CONTROL: &[&str] = &[
 ];
 
 pub const KEYWORDS_OTHER: &[&str] = &[
-    "module", "export", "use", "as", "hiding", "domain", "class", "instance", "type", "over",
-    "patch", "with",
+    "module", "export", "use", "as", "hiding", "domain", "class", "instance", "type", "opaque",
+    "over", "patch", "with", "deriving",
 ];
 
 pub const KEYWORDS_ALL: &[&str] = &[
     "do", "effect", "generate", "resource", "if", "then", "else", "when", "yield", "loop",
     "recurse", "pure", "module", "export", "use", "as", "hiding", "domain", "class", "instance",
-    "type", "over", "patch", "with",
+    "type", "opaque", "over", "patch", "with", "deriving",
 ];
 
 pub const BOOLEAN_LITERALS: &[&str] = &["True", "False"];
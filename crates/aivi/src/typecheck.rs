@@ -1,18 +1,19 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::diagnostics::FileDiagnostic;
-use crate::surface::{DomainItem, Module, ModuleItem, TypeExpr};
+use crate::surface::{Def, DomainItem, Module, ModuleItem, TypeExpr};
 
 mod builtins;
 mod checker;
 #[cfg(test)]
 mod class_constraints_tests;
+mod exhaustiveness;
 #[cfg(test)]
 mod expected_coercions_tests;
 mod types;
 
 use self::checker::TypeChecker;
-use self::types::{AliasInfo, Kind, Scheme};
+use self::types::{AliasInfo, Kind, Scheme, TypeEnv};
 
 fn collect_global_type_info(
     checker: &mut TypeChecker,
@@ -63,8 +64,12 @@ fn collect_global_type_info(
     let mut aliases = HashMap::new();
     for module in modules {
         for item in &module.items {
+            // Opaque aliases only expand inside their own module (`register_module_types`);
+            // everywhere else they stay abstract type constructors.
             if let ModuleItem::TypeAlias(alias) = item {
-                aliases.insert(alias.name.name.clone(), checker.alias_info(alias));
+                if !alias.opaque {
+                    aliases.insert(alias.name.name.clone(), checker.alias_info(alias));
+                }
             }
         }
     }
@@ -73,6 +78,85 @@ fn collect_global_type_info(
     (type_constructors, aliases)
 }
 
+/// Maps every declared effect to its operation names, so effect rows can name effects from any
+/// module.
+fn collect_global_effects(modules: &[Module]) -> HashMap<String, Vec<String>> {
+    let mut effects = HashMap::new();
+    for module in modules {
+        for item in &module.items {
+            if let ModuleItem::EffectDecl(effect) = item {
+                effects.insert(
+                    effect.name.name.clone(),
+                    effect
+                        .operations
+                        .iter()
+                        .map(|op| op.name.name.clone())
+                        .collect(),
+                );
+            }
+        }
+    }
+    effects
+}
+
+/// Constructors of every ADT declared in `modules`, so matches on imported types are checked
+/// against their full constructor set.
+fn collect_global_constructors(modules: &[Module]) -> exhaustiveness::ConstructorSets {
+    let mut sets = exhaustiveness::builtin_constructor_sets();
+    for module in modules {
+        for item in &module.items {
+            let type_decls: Vec<&crate::surface::TypeDecl> = match item {
+                ModuleItem::TypeDecl(type_decl) => vec![type_decl],
+                ModuleItem::DomainDecl(domain) => domain
+                    .items
+                    .iter()
+                    .filter_map(|item| match item {
+                        DomainItem::TypeAlias(type_decl) => Some(type_decl),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            for type_decl in type_decls {
+                if !type_decl.constructors.is_empty() {
+                    sets.insert(
+                        type_decl.name.name.clone(),
+                        exhaustiveness::constructor_set(type_decl),
+                    );
+                }
+            }
+        }
+    }
+    sets
+}
+
+/// Schemes of a module's exported values; exporting an effect exports its operations.
+fn collect_value_exports(module: &Module, env: &TypeEnv) -> HashMap<String, Scheme> {
+    let mut exports = HashMap::new();
+    for export in &module.exports {
+        if export.kind != crate::surface::ScopeItemKind::Value {
+            continue;
+        }
+        if let Some(scheme) = env.get(&export.name.name) {
+            exports.insert(export.name.name.clone(), scheme.clone());
+        }
+        for item in &module.items {
+            let ModuleItem::EffectDecl(effect) = item else {
+                continue;
+            };
+            if effect.name.name != export.name.name {
+                continue;
+            }
+            for op in &effect.operations {
+                if let Some(scheme) = env.get(&op.name.name) {
+                    exports.insert(op.name.name.clone(), scheme.clone());
+                }
+            }
+        }
+    }
+    exports
+}
+
 #[derive(Clone, Debug)]
 struct ClassDeclInfo {
     params: Vec<TypeExpr>,
@@ -82,12 +166,18 @@ struct ClassDeclInfo {
     // method names without introducing ambiguity from superclass expansion.
     direct_members: HashMap<String, TypeExpr>,
     members: HashMap<String, TypeExpr>,
+    // Default bodies by member name, including defaults inherited from superclasses.
+    defaults: HashMap<String, Def>,
+    // Functional dependencies as indices into `params`: `from` determines `to`.
+    fundeps: Vec<(Vec<usize>, Vec<usize>)>,
 }
 
 #[derive(Clone, Debug)]
 struct InstanceDeclInfo {
     class_name: String,
     params: Vec<TypeExpr>,
+    // Members the instance defines itself (as opposed to delegating or taking a default).
+    methods: Vec<String>,
 }
 
 fn collect_local_class_env(
@@ -108,6 +198,29 @@ fn collect_local_class_env(
                     .iter()
                     .map(|constraint| (constraint.var.name.clone(), constraint.class.name.clone()))
                     .collect();
+                let defaults = class_decl
+                    .defaults
+                    .iter()
+                    .map(|def| (def.name.name.clone(), def.clone()))
+                    .collect();
+                let param_index = |name: &str| {
+                    class_decl
+                        .params
+                        .iter()
+                        .position(|param| class_name_from_type_expr(param) == Some(name))
+                };
+                let fundeps = class_decl
+                    .fundeps
+                    .iter()
+                    .map(|fundep| {
+                        let indices = |vars: &[crate::surface::SpannedName]| {
+                            vars.iter()
+                                .filter_map(|var| param_index(&var.name))
+                                .collect::<Vec<_>>()
+                        };
+                        (indices(&fundep.from), indices(&fundep.to))
+                    })
+                    .collect();
                 classes.insert(
                     class_decl.name.name.clone(),
                     ClassDeclInfo {
@@ -116,6 +229,8 @@ fn collect_local_class_env(
                         constraints,
                         direct_members,
                         members,
+                        defaults,
+                        fundeps,
                     },
                 );
             }
@@ -123,6 +238,11 @@ fn collect_local_class_env(
                 instances.push(InstanceDeclInfo {
                     class_name: instance_decl.name.name.clone(),
                     params: instance_decl.params.clone(),
+                    methods: instance_decl
+                        .defs
+                        .iter()
+                        .map(|def| def.name.name.clone())
+                        .collect(),
                 });
             }
             _ => {}
@@ -142,12 +262,14 @@ fn class_name_from_type_expr(ty: &TypeExpr) -> Option<&str> {
     }
 }
 
-fn expand_class_members(
+/// Merges a per-member table (`members` or `defaults`) of `name` with its superclasses' tables.
+fn expand_class_members<T: Clone>(
     name: &str,
     classes: &HashMap<String, ClassDeclInfo>,
+    table: fn(&ClassDeclInfo) -> &HashMap<String, T>,
     visiting: &mut HashSet<String>,
-    cache: &mut HashMap<String, HashMap<String, TypeExpr>>,
-) -> HashMap<String, TypeExpr> {
+    cache: &mut HashMap<String, HashMap<String, T>>,
+) -> HashMap<String, T> {
     if let Some(members) = cache.get(name) {
         return members.clone();
     }
@@ -156,7 +278,7 @@ fn expand_class_members(
     };
     if !visiting.insert(name.to_string()) {
         // Cycle: stop expanding to avoid infinite recursion.
-        return info.members.clone();
+        return table(info).clone();
     }
 
     let mut merged = HashMap::new();
@@ -167,14 +289,14 @@ fn expand_class_members(
         if !classes.contains_key(super_name) {
             continue;
         };
-        let inherited = expand_class_members(super_name, classes, visiting, cache);
-        for (member, ty) in inherited {
-            merged.entry(member).or_insert(ty);
+        let inherited = expand_class_members(super_name, classes, table, visiting, cache);
+        for (member, value) in inherited {
+            merged.entry(member).or_insert(value);
         }
     }
     // Explicit members override inherited ones when names overlap.
-    for (member, ty) in &info.members {
-        merged.insert(member.clone(), ty.clone());
+    for (member, value) in table(info) {
+        merged.insert(member.clone(), value.clone());
     }
 
     visiting.remove(name);
@@ -184,12 +306,27 @@ fn expand_class_members(
 
 fn expand_classes(mut classes: HashMap<String, ClassDeclInfo>) -> HashMap<String, ClassDeclInfo> {
     let mut visiting = HashSet::new();
-    let mut cache: HashMap<String, HashMap<String, TypeExpr>> = HashMap::new();
+    let mut member_cache = HashMap::new();
+    let mut default_cache = HashMap::new();
     let names: Vec<String> = classes.keys().cloned().collect();
     for name in names {
-        let expanded = expand_class_members(&name, &classes, &mut visiting, &mut cache);
+        let members = expand_class_members(
+            &name,
+            &classes,
+            |info| &info.members,
+            &mut visiting,
+            &mut member_cache,
+        );
+        let defaults = expand_class_members(
+            &name,
+            &classes,
+            |info| &info.defaults,
+            &mut visiting,
+            &mut default_cache,
+        );
         if let Some(info) = classes.get_mut(&name) {
-            info.members = expanded;
+            info.members = members;
+            info.defaults = defaults;
         }
     }
     classes
@@ -220,9 +357,23 @@ fn collect_imported_class_env(
             if item.kind != crate::surface::ScopeItemKind::Value {
                 continue;
             }
-            if let Some(info) = class_exports.get(&item.name.name) {
-                classes.insert(item.name.name.clone(), info.clone());
-                imported_classes.insert(item.name.name.clone());
+            // Superclasses come along: the class's instances and defaults rely on them, while the
+            // resolver still decides which method names the module may use.
+            let mut pending = vec![item.name.name.clone()];
+            while let Some(name) = pending.pop() {
+                let Some(info) = class_exports.get(&name) else {
+                    continue;
+                };
+                if !imported_classes.insert(name.clone()) {
+                    continue;
+                }
+                pending.extend(
+                    info.supers
+                        .iter()
+                        .filter_map(class_name_from_type_expr)
+                        .map(str::to_string),
+                );
+                classes.insert(name, info.clone());
             }
         }
         if let Some(instance_exports) = module_instance_exports.get(&use_decl.module.name) {
@@ -392,6 +543,8 @@ pub fn check_types(modules: &[Module]) -> Vec<FileDiagnostic> {
     let (global_type_constructors, global_aliases) =
         collect_global_type_info(&mut checker, modules);
     checker.set_global_type_info(global_type_constructors, global_aliases);
+    checker.set_global_effects(collect_global_effects(modules));
+    checker.set_global_constructors(collect_global_constructors(modules));
 
     for module in ordered_modules(modules) {
         checker.reset_module_context(module);
@@ -419,16 +572,10 @@ pub fn check_types(modules: &[Module]) -> Vec<FileDiagnostic> {
         let mut module_diags = checker.check_module_defs(module, &sigs, &mut env);
         diagnostics.append(&mut module_diags);
 
-        let mut exports = HashMap::new();
-        for export in &module.exports {
-            if export.kind != crate::surface::ScopeItemKind::Value {
-                continue;
-            }
-            if let Some(scheme) = env.get(&export.name.name) {
-                exports.insert(export.name.name.clone(), scheme.clone());
-            }
-        }
-        module_exports.insert(module.name.name.clone(), exports);
+        module_exports.insert(
+            module.name.name.clone(),
+            collect_value_exports(module, &env),
+        );
 
         let mut domain_exports = HashMap::new();
         for export in &module.exports {
@@ -476,6 +623,8 @@ pub fn elaborate_expected_coercions(modules: &mut [Module]) -> Vec<FileDiagnosti
     let (global_type_constructors, global_aliases) =
         collect_global_type_info(&mut checker, modules);
     checker.set_global_type_info(global_type_constructors, global_aliases);
+    checker.set_global_effects(collect_global_effects(modules));
+    checker.set_global_constructors(collect_global_constructors(modules));
 
     for idx in ordered_module_indices(modules) {
         let module = &mut modules[idx];
@@ -542,16 +691,10 @@ pub fn elaborate_expected_coercions(modules: &mut [Module]) -> Vec<FileDiagnosti
             }
         }
 
-        let mut exports = HashMap::new();
-        for export in &module.exports {
-            if export.kind != crate::surface::ScopeItemKind::Value {
-                continue;
-            }
-            if let Some(scheme) = env.get(&export.name.name) {
-                exports.insert(export.name.name.clone(), scheme.clone());
-            }
-        }
-        module_exports.insert(module.name.name.clone(), exports);
+        module_exports.insert(
+            module.name.name.clone(),
+            collect_value_exports(module, &env),
+        );
 
         let mut domain_exports = HashMap::new();
         for export in &module.exports {
@@ -605,6 +748,8 @@ pub fn infer_value_types(
     let (global_type_constructors, global_aliases) =
         collect_global_type_info(&mut checker, modules);
     checker.set_global_type_info(global_type_constructors, global_aliases);
+    checker.set_global_effects(collect_global_effects(modules));
+    checker.set_global_constructors(collect_global_constructors(modules));
 
     for module in ordered_modules(modules) {
         checker.reset_module_context(module);
@@ -666,16 +811,10 @@ pub fn infer_value_types(
         }
         inferred.insert(module.name.name.clone(), module_types);
 
-        let mut exports = HashMap::new();
-        for export in &module.exports {
-            if export.kind != crate::surface::ScopeItemKind::Value {
-                continue;
-            }
-            if let Some(scheme) = env.get(&export.name.name) {
-                exports.insert(export.name.name.clone(), scheme.clone());
-            }
-        }
-        module_exports.insert(module.name.name.clone(), exports);
+        module_exports.insert(
+            module.name.name.clone(),
+            collect_value_exports(module, &env),
+        );
 
         let mut domain_exports = HashMap::new();
         for export in &module.exports {
@@ -44,6 +44,7 @@ impl TypeChecker {
             "FileStats",
             "Listener",
             "Connection",
+            "UdpSocket",
             "Stream",
             "Send",
             "Recv",
@@ -63,6 +64,11 @@ impl TypeChecker {
             .insert("List".to_string(), arrow(star.clone(), star.clone()));
         self.builtin_types
             .insert("Option".to_string(), arrow(star.clone(), star.clone()));
+        // `aivi.ui` commands and subscriptions are opaque runtime values.
+        self.builtin_types
+            .insert("Cmd".to_string(), arrow(star.clone(), star.clone()));
+        self.builtin_types
+            .insert("Sub".to_string(), arrow(star.clone(), star.clone()));
         // `Resource E A` mirrors `Effect E A`: acquisition may fail with `E`.
         self.builtin_types.insert(
             "Resource".to_string(),
@@ -1,5 +1,5 @@
 use super::TypeChecker;
-use crate::typecheck::types::{Scheme, Type, TypeEnv};
+use crate::typecheck::types::{Row, Scheme, Type, TypeEnv};
 
 pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
     let int_ty = Type::con("Int");
@@ -155,7 +155,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     let map_record_value = map_record.clone();
 
@@ -245,7 +245,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     let set_record_value = set_record.clone();
 
@@ -282,7 +282,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     let queue_record_value = queue_record.clone();
 
@@ -343,7 +343,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     let deque_record_value = deque_record.clone();
 
@@ -381,7 +381,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     let heap_record_value = heap_record.clone();
 
@@ -395,7 +395,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert(
         "collections".to_string(),
@@ -1,5 +1,5 @@
 use super::TypeChecker;
-use crate::typecheck::types::{Scheme, Type, TypeEnv};
+use crate::typecheck::types::{Row, Scheme, Type, TypeEnv};
 
 pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
     env.insert("Unit".to_string(), Scheme::mono(Type::con("Unit")));
@@ -209,7 +209,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("file".to_string(), Scheme::mono(file_record));
 
@@ -260,7 +260,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("channel".to_string(), Scheme::mono(channel_record));
 
@@ -309,7 +309,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("concurrent".to_string(), Scheme::mono(concurrent_record));
 
@@ -323,7 +323,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         )]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("clock".to_string(), Scheme::mono(clock_record));
 
@@ -340,7 +340,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         )]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("random".to_string(), Scheme::mono(random_record));
 
@@ -351,7 +351,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: false,
+        row: Row::Closed,
     };
     let request_ty = Type::Record {
         fields: vec![
@@ -361,18 +361,15 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
                 "headers".to_string(),
                 Type::con("List").app(vec![header_ty.clone()]),
             ),
+            ("body".to_string(), Type::con("Body")),
             (
-                "body".to_string(),
-                Type::con("List").app(vec![Type::con("Int")]),
-            ),
-            (
-                "remote_addr".to_string(),
+                "remoteAddr".to_string(),
                 Type::con("Option").app(vec![Type::con("Text")]),
             ),
         ]
         .into_iter()
         .collect(),
-        open: false,
+        row: Row::Closed,
     };
     let _response_ty = Type::Record {
         fields: vec![
@@ -381,20 +378,45 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
                 "headers".to_string(),
                 Type::con("List").app(vec![header_ty]),
             ),
+            ("body".to_string(), Type::con("Body")),
+        ]
+        .into_iter()
+        .collect(),
+        row: Row::Closed,
+    };
+    let tls_config_ty = Type::Record {
+        fields: vec![
+            ("certFile".to_string(), Type::con("Text")),
+            ("keyFile".to_string(), Type::con("Text")),
             (
-                "body".to_string(),
-                Type::con("List").app(vec![Type::con("Int")]),
+                "clientCaFile".to_string(),
+                Type::con("Option").app(vec![Type::con("Text")]),
             ),
         ]
         .into_iter()
         .collect(),
-        open: false,
+        row: Row::Closed,
     };
     let server_config_ty = Type::Record {
-        fields: vec![("address".to_string(), Type::con("Text"))]
-            .into_iter()
-            .collect(),
-        open: false,
+        fields: vec![
+            ("address".to_string(), Type::con("Text")),
+            (
+                "tls".to_string(),
+                Type::con("Option").app(vec![tls_config_ty]),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        row: Row::Closed,
+    };
+    let server_options_ty = Type::Record {
+        fields: vec![
+            ("maxBodyBytes".to_string(), Type::con("Int")),
+            ("streamRequestBodies".to_string(), Type::con("Bool")),
+        ]
+        .into_iter()
+        .collect(),
+        row: Row::Closed,
     };
     let server_ty = Type::con("Server");
     let ws_ty = Type::con("WebSocket");
@@ -402,20 +424,111 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
     let ws_error_ty = Type::con("WsError");
     let reply_ty = Type::con("ServerReply");
     let ws_message_ty = Type::con("WsMessage");
+    let request_alias = Type::con("Request");
+    let handler_ty = Type::Func(
+        Box::new(request_alias.clone()),
+        Box::new(Type::con("Effect").app(vec![http_error_ty.clone(), reply_ty.clone()])),
+    );
+    let fields_ty = Type::con("List").app(vec![Type::con("Field")]);
+    let option_text_ty = Type::con("Option").app(vec![Type::con("Text")]);
+    let request_fn = |result: Type| Type::Func(Box::new(request_alias.clone()), Box::new(result));
+    let middleware_fn = |config: Option<Type>| {
+        let middleware = Type::Func(Box::new(handler_ty.clone()), Box::new(handler_ty.clone()));
+        match config {
+            Some(config) => Type::Func(Box::new(config), Box::new(middleware)),
+            None => middleware,
+        }
+    };
+    let routing_fields = vec![
+        (
+            "route".to_string(),
+            Type::Func(
+                Box::new(Type::con("List").app(vec![Type::con("Route")])),
+                Box::new(handler_ty.clone()),
+            ),
+        ),
+        (
+            "serveStatic".to_string(),
+            Type::Func(
+                Box::new(Type::con("Text")),
+                Box::new(Type::Func(
+                    Box::new(Type::con("Text")),
+                    Box::new(handler_ty.clone()),
+                )),
+            ),
+        ),
+        ("logging".to_string(), middleware_fn(None)),
+        (
+            "cors".to_string(),
+            middleware_fn(Some(Type::con("CorsConfig"))),
+        ),
+        ("compression".to_string(), middleware_fn(None)),
+        (
+            "header".to_string(),
+            Type::Func(
+                Box::new(Type::con("Text")),
+                Box::new(request_fn(option_text_ty.clone())),
+            ),
+        ),
+        ("bearerToken".to_string(), request_fn(option_text_ty)),
+        ("requestPath".to_string(), request_fn(Type::con("Text"))),
+        ("query".to_string(), request_fn(fields_ty.clone())),
+        ("cookies".to_string(), request_fn(fields_ty.clone())),
+        (
+            "form".to_string(),
+            request_fn(Type::con("Result").app(vec![http_error_ty.clone(), fields_ty])),
+        ),
+        (
+            "setCookie".to_string(),
+            Type::Func(
+                Box::new(Type::con("SetCookie")),
+                Box::new(Type::con("Result").app(vec![http_error_ty.clone(), Type::con("Header")])),
+            ),
+        ),
+        (
+            "readBody".to_string(),
+            Type::Func(
+                Box::new(Type::con("Int")),
+                Box::new(request_fn(
+                    Type::con("Effect").app(vec![http_error_ty.clone(), Type::con("Bytes")]),
+                )),
+            ),
+        ),
+        (
+            "sseStream".to_string(),
+            Type::Func(
+                Box::new(Type::con("Recv").app(vec![Type::con("SseEvent")])),
+                Box::new(Type::con("Stream").app(vec![Type::con("Bytes")])),
+            ),
+        ),
+    ];
+    let raw_handler_ty = Type::Func(
+        Box::new(request_ty),
+        Box::new(Type::con("Effect").app(vec![http_error_ty.clone(), reply_ty])),
+    );
+    let listen_result = Type::con("Effect").app(vec![http_error_ty, server_ty.clone()]);
     let http_server_record = Type::Record {
         fields: vec![
             (
                 "listen".to_string(),
+                Type::Func(
+                    Box::new(server_config_ty.clone()),
+                    Box::new(Type::Func(
+                        Box::new(raw_handler_ty.clone()),
+                        Box::new(listen_result.clone()),
+                    )),
+                ),
+            ),
+            (
+                "listenWith".to_string(),
                 Type::Func(
                     Box::new(server_config_ty),
                     Box::new(Type::Func(
+                        Box::new(server_options_ty),
                         Box::new(Type::Func(
-                            Box::new(request_ty),
-                            Box::new(
-                                Type::con("Effect").app(vec![http_error_ty.clone(), reply_ty]),
-                            ),
+                            Box::new(raw_handler_ty),
+                            Box::new(listen_result),
                         )),
-                        Box::new(Type::con("Effect").app(vec![http_error_ty, server_ty.clone()])),
                     )),
                 ),
             ),
@@ -456,8 +569,9 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
             ),
         ]
         .into_iter()
+        .chain(routing_fields)
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("httpServer".to_string(), Scheme::mono(http_server_record));
 
@@ -468,7 +582,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         )]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("html".to_string(), Scheme::mono(html_record));
 
@@ -481,6 +595,12 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
     let event_ty = Type::con("Event");
     let live_error_ty = Type::con("LiveError");
     let live_config_ty = Type::con("LiveConfig");
+    let e = checker.fresh_var_id();
+    let a = checker.fresh_var_id();
+    let cmd_msg = Type::con("Cmd").app(vec![Type::Var(msg)]);
+    let sub_msg = Type::con("Sub").app(vec![Type::Var(msg)]);
+    let model_and_cmd = Type::Tuple(vec![Type::Var(model), cmd_msg.clone()]);
+    let page_msg = Type::con("Page").app(vec![Type::Var(msg)]);
 
     let ui_record = Type::Record {
         fields: vec![
@@ -509,10 +629,40 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
                     Box::new(Type::con("Result").app(vec![live_error_ty.clone(), event_ty])),
                 ),
             ),
+            (
+                "renderPage".to_string(),
+                Type::Func(
+                    Box::new(Type::con("Option").app(vec![Type::con("Text")])),
+                    Box::new(Type::Func(
+                        Box::new(page_msg.clone()),
+                        Box::new(Type::con("Text")),
+                    )),
+                ),
+            ),
+            (
+                "auditPage".to_string(),
+                Type::Func(
+                    Box::new(page_msg.clone()),
+                    Box::new(Type::con("List").app(vec![Type::con("AuditIssue")])),
+                ),
+            ),
+            (
+                "buildSite".to_string(),
+                Type::Func(
+                    Box::new(Type::con("SiteConfig")),
+                    Box::new(Type::Func(
+                        Box::new(Type::con("List").app(vec![page_msg])),
+                        Box::new(
+                            Type::con("Effect")
+                                .app(vec![Type::con("SsgError"), Type::con("SiteReport")]),
+                        ),
+                    )),
+                ),
+            ),
             (
                 "live".to_string(),
                 Type::Func(
-                    Box::new(live_config_ty),
+                    Box::new(live_config_ty.clone()),
                     Box::new(Type::Func(
                         Box::new(Type::Var(model)),
                         Box::new(Type::Func(
@@ -530,22 +680,102 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
                                 )),
                                 Box::new(
                                     Type::con("Effect")
-                                        .app(vec![live_error_ty, Type::con("Server")]),
+                                        .app(vec![live_error_ty.clone(), Type::con("Server")]),
                                 ),
                             )),
                         )),
                     )),
                 ),
             ),
+            (
+                "liveApp".to_string(),
+                Type::Func(
+                    Box::new(live_config_ty.clone()),
+                    Box::new(Type::Func(
+                        Box::new(model_and_cmd.clone()),
+                        Box::new(Type::Func(
+                            Box::new(Type::Func(
+                                Box::new(Type::Var(model)),
+                                Box::new(vnode_msg.clone()),
+                            )),
+                            Box::new(Type::Func(
+                                Box::new(Type::Func(
+                                    Box::new(Type::Var(msg)),
+                                    Box::new(Type::Func(
+                                        Box::new(Type::Var(model)),
+                                        Box::new(model_and_cmd),
+                                    )),
+                                )),
+                                Box::new(Type::Func(
+                                    Box::new(Type::Func(
+                                        Box::new(Type::Var(model)),
+                                        Box::new(Type::con("List").app(vec![sub_msg.clone()])),
+                                    )),
+                                    Box::new(
+                                        Type::con("Effect")
+                                            .app(vec![live_error_ty, Type::con("Server")]),
+                                    ),
+                                )),
+                            )),
+                        )),
+                    )),
+                ),
+            ),
+            ("cmdNone".to_string(), cmd_msg.clone()),
+            (
+                "cmdBatch".to_string(),
+                Type::Func(
+                    Box::new(Type::con("List").app(vec![cmd_msg.clone()])),
+                    Box::new(cmd_msg.clone()),
+                ),
+            ),
+            (
+                "cmdPerform".to_string(),
+                Type::Func(
+                    Box::new(Type::Func(
+                        Box::new(Type::con("Result").app(vec![Type::Var(e), Type::Var(a)])),
+                        Box::new(Type::Var(msg)),
+                    )),
+                    Box::new(Type::Func(
+                        Box::new(Type::con("Effect").app(vec![Type::Var(e), Type::Var(a)])),
+                        Box::new(cmd_msg),
+                    )),
+                ),
+            ),
+            (
+                "subEvery".to_string(),
+                Type::Func(
+                    Box::new(Type::con("Int")),
+                    Box::new(Type::Func(
+                        Box::new(Type::Var(msg)),
+                        Box::new(sub_msg.clone()),
+                    )),
+                ),
+            ),
+            (
+                "subReceive".to_string(),
+                Type::Func(
+                    Box::new(Type::con("Recv").app(vec![Type::Var(a)])),
+                    Box::new(Type::Func(
+                        Box::new(Type::Func(
+                            Box::new(
+                                Type::con("Result").app(vec![Type::con("Closed"), Type::Var(a)]),
+                            ),
+                            Box::new(Type::Var(msg)),
+                        )),
+                        Box::new(sub_msg),
+                    )),
+                ),
+            ),
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert(
         "ui".to_string(),
         Scheme {
-            vars: vec![msg, model],
+            vars: vec![msg, model, e, a],
             ty: ui_record,
         },
     );
@@ -1,5 +1,5 @@
 use super::TypeChecker;
-use crate::typecheck::types::{Scheme, Type, TypeEnv};
+use crate::typecheck::types::{Row, Scheme, Type, TypeEnv};
 
 pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
     let int_ty = Type::con("Int");
@@ -70,7 +70,7 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("decimal".to_string(), Scheme::mono(decimal_record));
 
@@ -91,7 +91,7 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("url".to_string(), Scheme::mono(url_record));
 
@@ -123,10 +123,17 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
                     Box::new(http_effect_ty.clone()),
                 ),
             ),
+            (
+                "client".to_string(),
+                Type::Func(
+                    Box::new(Type::con("ClientConfig")),
+                    Box::new(Type::con("Effect").app(vec![text_ty.clone(), Type::con("Client")])),
+                ),
+            ),
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("http".to_string(), Scheme::mono(http_record));
 
@@ -156,7 +163,7 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("https".to_string(), Scheme::mono(https_record));
 
@@ -167,13 +174,50 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     let socket_error_ty = Type::Record {
         fields: vec![("message".to_string(), Type::con("Text"))]
             .into_iter()
             .collect(),
-        open: true,
+        row: Row::Open,
+    };
+    let option_text_ty = Type::con("Option").app(vec![Type::con("Text")]);
+    let certificate_files = |extra: Vec<(String, Type)>| Type::Record {
+        fields: vec![
+            ("certFile".to_string(), Type::con("Text")),
+            ("keyFile".to_string(), Type::con("Text")),
+        ]
+        .into_iter()
+        .chain(extra)
+        .collect(),
+        row: Row::Open,
+    };
+    let tls_server_config_ty =
+        certificate_files(vec![("clientCaFile".to_string(), option_text_ty.clone())]);
+    let tls_client_config_ty = Type::Record {
+        fields: vec![
+            ("serverName".to_string(), option_text_ty.clone()),
+            ("caFile".to_string(), option_text_ty),
+            (
+                "clientCert".to_string(),
+                Type::con("Option").app(vec![certificate_files(Vec::new())]),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        row: Row::Open,
+    };
+    let socket_effect =
+        |result: Type| Type::con("Effect").app(vec![socket_error_ty.clone(), result]);
+    let datagram_ty = Type::Record {
+        fields: vec![
+            ("data".to_string(), Type::con("Bytes")),
+            ("from".to_string(), address_ty.clone()),
+        ]
+        .into_iter()
+        .collect(),
+        row: Row::Open,
     };
     let sockets_record = Type::Record {
         fields: vec![
@@ -187,6 +231,19 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
                     ),
                 ),
             ),
+            (
+                "listenTls".to_string(),
+                Type::Func(
+                    Box::new(address_ty.clone()),
+                    Box::new(Type::Func(
+                        Box::new(tls_server_config_ty),
+                        Box::new(
+                            Type::con("Effect")
+                                .app(vec![socket_error_ty.clone(), Type::con("Listener")]),
+                        ),
+                    )),
+                ),
+            ),
             (
                 "accept".to_string(),
                 Type::Func(
@@ -207,12 +264,25 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
                     ),
                 ),
             ),
+            (
+                "connectTls".to_string(),
+                Type::Func(
+                    Box::new(address_ty.clone()),
+                    Box::new(Type::Func(
+                        Box::new(tls_client_config_ty),
+                        Box::new(
+                            Type::con("Effect")
+                                .app(vec![socket_error_ty.clone(), Type::con("Connection")]),
+                        ),
+                    )),
+                ),
+            ),
             (
                 "send".to_string(),
                 Type::Func(
                     Box::new(Type::con("Connection")),
                     Box::new(Type::Func(
-                        Box::new(Type::con("List").app(vec![Type::con("Int")])),
+                        Box::new(Type::con("Bytes")),
                         Box::new(
                             Type::con("Effect")
                                 .app(vec![socket_error_ty.clone(), Type::con("Unit")]),
@@ -224,10 +294,9 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
                 "recv".to_string(),
                 Type::Func(
                     Box::new(Type::con("Connection")),
-                    Box::new(Type::con("Effect").app(vec![
-                        socket_error_ty.clone(),
-                        Type::con("List").app(vec![Type::con("Int")]),
-                    ])),
+                    Box::new(
+                        Type::con("Effect").app(vec![socket_error_ty.clone(), Type::con("Bytes")]),
+                    ),
                 ),
             ),
             (
@@ -248,10 +317,78 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
                     ),
                 ),
             ),
+            (
+                "listenUnix".to_string(),
+                Type::Func(
+                    Box::new(Type::con("Text")),
+                    Box::new(socket_effect(Type::con("Listener"))),
+                ),
+            ),
+            (
+                "connectUnix".to_string(),
+                Type::Func(
+                    Box::new(Type::con("Text")),
+                    Box::new(socket_effect(Type::con("Connection"))),
+                ),
+            ),
+            (
+                "bind".to_string(),
+                Type::Func(
+                    Box::new(address_ty.clone()),
+                    Box::new(socket_effect(Type::con("UdpSocket"))),
+                ),
+            ),
+            (
+                "sendTo".to_string(),
+                Type::Func(
+                    Box::new(Type::con("UdpSocket")),
+                    Box::new(Type::Func(
+                        Box::new(address_ty.clone()),
+                        Box::new(Type::Func(
+                            Box::new(Type::con("Bytes")),
+                            Box::new(socket_effect(Type::con("Unit"))),
+                        )),
+                    )),
+                ),
+            ),
+            (
+                "recvFrom".to_string(),
+                Type::Func(
+                    Box::new(Type::con("UdpSocket")),
+                    Box::new(socket_effect(datagram_ty)),
+                ),
+            ),
+            (
+                "joinMulticast".to_string(),
+                Type::Func(
+                    Box::new(Type::con("UdpSocket")),
+                    Box::new(Type::Func(
+                        Box::new(Type::con("Text")),
+                        Box::new(socket_effect(Type::con("Unit"))),
+                    )),
+                ),
+            ),
+            (
+                "leaveMulticast".to_string(),
+                Type::Func(
+                    Box::new(Type::con("UdpSocket")),
+                    Box::new(Type::Func(
+                        Box::new(Type::con("Text")),
+                        Box::new(socket_effect(Type::con("Unit"))),
+                    )),
+                ),
+            ),
+            (
+                "closeUdp".to_string(),
+                Type::Func(
+                    Box::new(Type::con("UdpSocket")),
+                    Box::new(socket_effect(Type::con("Unit"))),
+                ),
+            ),
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("sockets".to_string(), Scheme::mono(sockets_record));
 
@@ -259,10 +396,15 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
         fields: vec![("message".to_string(), Type::con("Text"))]
             .into_iter()
             .collect(),
-        open: true,
+        row: Row::Open,
+    };
+    let stream_bytes_ty = Type::con("Stream").app(vec![Type::con("Bytes")]);
+    let stream_effect = |result: Type| {
+        Type::Func(
+            Box::new(stream_bytes_ty.clone()),
+            Box::new(Type::con("Effect").app(vec![stream_error_ty.clone(), result])),
+        )
     };
-    let stream_bytes_ty =
-        Type::con("Stream").app(vec![Type::con("List").app(vec![Type::con("Int")])]);
     let streams_record = Type::Record {
         fields: vec![
             (
@@ -295,10 +437,35 @@ pub(super) fn register(_checker: &mut TypeChecker, env: &mut TypeEnv) {
                     )),
                 ),
             ),
+            (
+                "fromChunks".to_string(),
+                Type::Func(
+                    Box::new(Type::con("List").app(vec![Type::con("Bytes")])),
+                    Box::new(stream_bytes_ty.clone()),
+                ),
+            ),
+            (
+                "fromChannel".to_string(),
+                Type::Func(
+                    Box::new(Type::con("Recv").app(vec![Type::con("Bytes")])),
+                    Box::new(stream_bytes_ty.clone()),
+                ),
+            ),
+            (
+                "readChunk".to_string(),
+                stream_effect(Type::con("Option").app(vec![Type::con("Bytes")])),
+            ),
+            (
+                "collect".to_string(),
+                Type::Func(
+                    Box::new(Type::con("Int")),
+                    Box::new(stream_effect(Type::con("Bytes"))),
+                ),
+            ),
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("streams".to_string(), Scheme::mono(streams_record));
 }
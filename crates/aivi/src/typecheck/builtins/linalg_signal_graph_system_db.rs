@@ -1,5 +1,5 @@
 use super::TypeChecker;
-use crate::typecheck::types::{Scheme, Type, TypeEnv};
+use crate::typecheck::types::{Row, Scheme, Type, TypeEnv};
 
 pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
     let int_ty = Type::con("Int");
@@ -43,7 +43,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("linalg".to_string(), Scheme::mono(linalg_record));
 
@@ -70,7 +70,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("signal".to_string(), Scheme::mono(signal_record));
 
@@ -115,7 +115,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("graph".to_string(), Scheme::mono(graph_record));
 
@@ -198,7 +198,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("console".to_string(), Scheme::mono(console_record));
 
@@ -234,7 +234,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: false,
+        row: Row::Closed,
     };
     let system_record = Type::Record {
         fields: vec![
@@ -259,7 +259,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("system".to_string(), Scheme::mono(system_record));
 
@@ -335,7 +335,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("logger".to_string(), Scheme::mono(logger_record));
 
@@ -404,7 +404,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("database".to_string(), Scheme::mono(database_record));
 }
@@ -1,5 +1,5 @@
 use super::TypeChecker;
-use crate::typecheck::types::{Scheme, Type, TypeEnv};
+use crate::typecheck::types::{Row, Scheme, Type, TypeEnv};
 
 pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
     let int_ty = Type::con("Int");
@@ -389,11 +389,19 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("math".to_string(), Scheme::mono(math_record));
 
     let date_ty = Type::con("Date");
+    let text_ty = Type::con("Text");
+    let time_ty = Type::con("Time");
+    let local_ty = Type::con("LocalDateTime");
+    let instant_ty = Type::con("DateTime");
+    let zone_ty = Type::con("TimeZone");
+    let zoned_ty = Type::con("ZonedDateTime");
+    let parse_result = |ty: &Type| Type::con("Result").app(vec![text_ty.clone(), ty.clone()]);
+    let shift_var = checker.fresh_var_id();
     let calendar_record = Type::Record {
         fields: vec![
             (
@@ -438,12 +446,100 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
                     )),
                 ),
             ),
+            (
+                "shift".to_string(),
+                Type::Func(
+                    Box::new(Type::Var(shift_var)),
+                    Box::new(Type::Func(
+                        Box::new(Type::con("Delta")),
+                        Box::new(Type::Var(shift_var)),
+                    )),
+                ),
+            ),
+            (
+                "timeZone".to_string(),
+                Type::Func(Box::new(text_ty.clone()), Box::new(parse_result(&zone_ty))),
+            ),
+            (
+                "atZone".to_string(),
+                Type::Func(
+                    Box::new(zone_ty.clone()),
+                    Box::new(Type::Func(
+                        Box::new(instant_ty.clone()),
+                        Box::new(zoned_ty.clone()),
+                    )),
+                ),
+            ),
+            (
+                "fromLocal".to_string(),
+                Type::Func(
+                    Box::new(zone_ty.clone()),
+                    Box::new(Type::Func(
+                        Box::new(local_ty.clone()),
+                        Box::new(zoned_ty.clone()),
+                    )),
+                ),
+            ),
+            (
+                "toInstant".to_string(),
+                Type::Func(Box::new(zoned_ty.clone()), Box::new(instant_ty.clone())),
+            ),
+            (
+                "parseDate".to_string(),
+                Type::Func(Box::new(text_ty.clone()), Box::new(parse_result(&date_ty))),
+            ),
+            (
+                "parseTime".to_string(),
+                Type::Func(Box::new(text_ty.clone()), Box::new(parse_result(&time_ty))),
+            ),
+            (
+                "parseLocalDateTime".to_string(),
+                Type::Func(Box::new(text_ty.clone()), Box::new(parse_result(&local_ty))),
+            ),
+            (
+                "parseInstant".to_string(),
+                Type::Func(
+                    Box::new(text_ty.clone()),
+                    Box::new(parse_result(&instant_ty)),
+                ),
+            ),
+            (
+                "parseZoned".to_string(),
+                Type::Func(Box::new(text_ty.clone()), Box::new(parse_result(&zoned_ty))),
+            ),
+            (
+                "dateText".to_string(),
+                Type::Func(Box::new(date_ty.clone()), Box::new(text_ty.clone())),
+            ),
+            (
+                "timeText".to_string(),
+                Type::Func(Box::new(time_ty.clone()), Box::new(text_ty.clone())),
+            ),
+            (
+                "localDateTimeText".to_string(),
+                Type::Func(Box::new(local_ty.clone()), Box::new(text_ty.clone())),
+            ),
+            (
+                "instantText".to_string(),
+                Type::Func(Box::new(instant_ty.clone()), Box::new(text_ty.clone())),
+            ),
+            (
+                "zonedText".to_string(),
+                Type::Func(Box::new(zoned_ty.clone()), Box::new(text_ty.clone())),
+            ),
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
-    env.insert("calendar".to_string(), Scheme::mono(calendar_record));
+    // `shift` works on every calendar carrier, so its carrier variable is quantified.
+    env.insert(
+        "calendar".to_string(),
+        Scheme {
+            vars: vec![shift_var],
+            ty: calendar_record,
+        },
+    );
 
     let rgb_ty = Type::con("Rgb");
     let hsl_ty = Type::con("Hsl");
@@ -495,7 +591,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("color".to_string(), Scheme::mono(color_record));
 
@@ -542,7 +638,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("bigint".to_string(), Scheme::mono(bigint_record));
 
@@ -614,7 +710,7 @@ pub(super) fn register(checker: &mut TypeChecker, env: &mut TypeEnv) {
         ]
         .into_iter()
         .collect(),
-        open: true,
+        row: Row::Open,
     };
     env.insert("rational".to_string(), Scheme::mono(rational_record));
 }
@@ -4,6 +4,7 @@ include!("checker/instances.rs");
 include!("checker/elaboration.rs");
 include!("checker/infer_expr.rs");
 include!("checker/infer_effects_and_patches.rs");
+include!("checker/user_effects.rs");
 include!("checker/unify_and_substitute.rs");
 include!("checker/type_expr_and_rows.rs");
 include!("checker/unbound_names.rs");
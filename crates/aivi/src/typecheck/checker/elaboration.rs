@@ -88,7 +88,7 @@ impl TypeChecker {
             }
             Expr::Index { base, index, .. } => self.infer_index(base, index, env),
             Expr::Call { func, args, .. } => self.infer_call(func, args, env),
-            Expr::Lambda { params, body, .. } => self.infer_lambda(params, body, env),
+            Expr::Lambda { params, body, span } => self.infer_lambda(params, body, span, env),
             Expr::Match {
                 scrutinee,
                 arms,
@@ -104,6 +104,11 @@ impl TypeChecker {
             Expr::Binary {
                 op, left, right, ..
             } => self.infer_binary(op, left, right, env),
+            Expr::Handle {
+                body,
+                handler,
+                span,
+            } => self.infer_handle(body, handler, span, env),
             Expr::Block { kind, items, .. } => self.infer_block(kind, items, env),
             Expr::Raw { .. } => Ok(self.fresh_var()),
         }
@@ -290,7 +295,31 @@ impl TypeChecker {
             Expr::Block { kind, items, span } => {
                 let mut local_env = env.clone();
                 let mut new_items = Vec::new();
-                for item in items {
+                let groups = recursive_binding_groups(&items);
+                let group_items = if groups.is_empty() {
+                    Vec::new()
+                } else {
+                    items.clone()
+                };
+                for (idx, item) in items.into_iter().enumerate() {
+                    // A recursive group is typed up front so each body sees the generalised
+                    // types of all its names; its bindings must not rebind them monomorphically.
+                    if self.infer_recursive_group(&groups, &group_items, idx, &mut local_env)? {
+                        if let BlockItem::Let {
+                            pattern,
+                            expr,
+                            span,
+                        } = item
+                        {
+                            let (expr, _ty) = self.elab_expr(expr, None, &mut local_env)?;
+                            new_items.push(BlockItem::Let {
+                                pattern,
+                                expr,
+                                span,
+                            });
+                        }
+                        continue;
+                    }
                     match item {
                         BlockItem::Expr { expr, span } => {
                             let (expr, _ty) = self.elab_expr(expr, None, &mut local_env)?;
@@ -355,21 +384,22 @@ impl TypeChecker {
         expected: Option<Type>,
         env: &mut TypeEnv,
     ) -> Result<(Expr, Type), TypeError> {
-        // Method calls are inferred via a dedicated path; skip expected-type propagation.
-        if let Expr::Ident(name) = &func {
-            if env.get(&name.name).is_none() && self.method_to_classes.contains_key(&name.name) {
-                let mut new_args = Vec::new();
-                for arg in args {
-                    let (arg, _ty) = self.elab_expr(arg, None, env)?;
-                    new_args.push(arg);
-                }
-                let out = Expr::Call {
-                    func: Box::new(func),
-                    args: new_args,
-                    span: span.clone(),
-                };
-                return self.check_or_coerce(out, expected, env);
+        // Method calls and `aivi.i18n` renders are inferred via a dedicated path in `infer_call`;
+        // skip expected-type propagation.
+        let is_method = matches!(&func, Expr::Ident(name)
+            if env.get(&name.name).is_none() && self.method_to_classes.contains_key(&name.name));
+        if is_method || self.message_render_index(&func, env).is_some() {
+            let mut new_args = Vec::new();
+            for arg in args {
+                let (arg, _ty) = self.elab_expr(arg, None, env)?;
+                new_args.push(arg);
             }
+            let out = Expr::Call {
+                func: Box::new(func),
+                args: new_args,
+                span: span.clone(),
+            };
+            return self.check_or_coerce(out, expected, env);
         }
 
         let (func, _func_ty) = self.elab_expr(func, None, env)?;
@@ -18,8 +18,14 @@ impl TypeChecker {
     ) -> Result<Type, TypeError> {
         let mut local_env = env.clone();
         let err_ty = self.fresh_var();
+        // The effects performed by the block's statements.
+        let row = self.fresh_var();
         let mut result_ty = Type::con("Unit");
+        let groups = recursive_binding_groups(items);
         for (idx, item) in items.iter().enumerate() {
+            if self.infer_recursive_group(&groups, items, idx, &mut local_env)? {
+                continue;
+            }
             match item {
                 BlockItem::Bind { pattern, expr, .. } => {
                     let expr_ty = self.infer_expr(expr, &mut local_env)?;
@@ -29,7 +35,10 @@ impl TypeChecker {
                         err_ty.clone(),
                         expr_span(expr),
                     ) {
-                        Ok(value_ty) => value_ty,
+                        Ok(value_ty) => {
+                            self.join_effect_row_of(&row, &expr_ty, expr_span(expr))?;
+                            value_ty
+                        }
                         Err(_) => {
                             self.subst = snapshot;
                             expr_ty
@@ -74,18 +83,22 @@ impl TypeChecker {
                         result_ty = self.fresh_var();
                         let expected =
                             Type::con("Effect").app(vec![err_ty.clone(), result_ty.clone()]);
-                        self.unify_with_span(expr_ty, expected, expr_span(expr))?;
+                        self.unify_with_span(expr_ty.clone(), expected, expr_span(expr))?;
                     } else {
                         // Expression statements only auto-run effects when they return `Unit`.
                         // For non-`Unit` results, require an explicit `<-` bind.
-                        let value_ty =
-                            self.require_effect_value(expr_ty, err_ty.clone(), expr_span(expr))?;
+                        let value_ty = self.require_effect_value(
+                            expr_ty.clone(),
+                            err_ty.clone(),
+                            expr_span(expr),
+                        )?;
                         self.unify_with_span(value_ty, Type::con("Unit"), expr_span(expr))?;
                     }
+                    self.join_effect_row_of(&row, &expr_ty, expr_span(expr))?;
                 }
             }
         }
-        Ok(Type::con("Effect").app(vec![err_ty, result_ty]))
+        Ok(Type::con("Effect").app(vec![err_ty, result_ty, row]))
     }
 
     fn infer_generate_block(
@@ -96,7 +109,11 @@ impl TypeChecker {
         let mut local_env = env.clone();
         let yield_ty = self.fresh_var();
         let mut current_elem: Option<Type> = None;
-        for item in items {
+        let groups = recursive_binding_groups(items);
+        for (idx, item) in items.iter().enumerate() {
+            if self.infer_recursive_group(&groups, items, idx, &mut local_env)? {
+                continue;
+            }
             match item {
                 BlockItem::Bind { pattern, expr, .. } => {
                     let expr_ty = self.infer_expr(expr, &mut local_env)?;
@@ -145,7 +162,11 @@ impl TypeChecker {
         let mut local_env = env.clone();
         let err_ty = self.fresh_var();
         let yield_ty = self.fresh_var();
-        for item in items {
+        let groups = recursive_binding_groups(items);
+        for (idx, item) in items.iter().enumerate() {
+            if self.infer_recursive_group(&groups, items, idx, &mut local_env)? {
+                continue;
+            }
             match item {
                 BlockItem::Bind { pattern, expr, .. } => {
                     let expr_ty = self.infer_expr(expr, &mut local_env)?;
@@ -235,9 +256,10 @@ impl TypeChecker {
                     let field_ty = self.fresh_var();
                     let mut fields = BTreeMap::new();
                     fields.insert(name.name.clone(), field_ty.clone());
+                    let row = Row::Var(self.fresh_var_id());
                     self.unify_with_span(
                         current_ty,
-                        Type::Record { fields, open: true },
+                        Type::Record { fields, row },
                         name.span.clone(),
                     )?;
                     current_ty = field_ty;
@@ -348,7 +370,7 @@ impl TypeChecker {
                         entry_fields.insert("value".to_string(), value_ty.clone());
                         let entry_ty = Type::Record {
                             fields: entry_fields,
-                            open: true,
+                            row: Row::Open,
                         };
 
                         let param = "__it".to_string();
@@ -373,7 +395,7 @@ impl TypeChecker {
     ) -> Result<Type, TypeError> {
         let mut record_ty = Type::Record {
             fields: BTreeMap::new(),
-            open: true,
+            row: Row::Var(self.fresh_var_id()),
         };
         for field in fields {
             if field.spread {
@@ -465,7 +487,7 @@ impl TypeChecker {
     ) -> Result<Type, TypeError> {
         let mut record_ty = Type::Record {
             fields: BTreeMap::new(),
-            open: true,
+            row: Row::Var(self.fresh_var_id()),
         };
         for field in fields {
             let field_ty = self.infer_pattern(&field.pattern, env)?;
@@ -527,7 +549,8 @@ impl TypeChecker {
                 PathSegment::Field(name) => {
                     let mut fields = BTreeMap::new();
                     fields.insert(name.name.clone(), current);
-                    current = Type::Record { fields, open: true };
+                    let row = Row::Var(self.fresh_var_id());
+                    current = Type::Record { fields, row };
                 }
                 PathSegment::Index(_, _) | PathSegment::All(_) => {
                     current = Type::con("List").app(vec![current]);
@@ -542,7 +565,8 @@ impl TypeChecker {
         for segment in path.iter().rev() {
             let mut fields = BTreeMap::new();
             fields.insert(segment.name.clone(), current);
-            current = Type::Record { fields, open: true };
+            let row = Row::Var(self.fresh_var_id());
+            current = Type::Record { fields, row };
         }
         current
     }
@@ -554,10 +578,10 @@ impl TypeChecker {
         let right_clone = right.clone();
         match (left, right) {
             (
-                Type::Record { mut fields, open },
+                Type::Record { mut fields, row },
                 Type::Record {
                     fields: other,
-                    open: other_open,
+                    row: other_row,
                 },
             ) => {
                 for (name, ty) in other {
@@ -567,10 +591,13 @@ impl TypeChecker {
                         fields.insert(name, ty);
                     }
                 }
-                Ok(Type::Record {
-                    fields,
-                    open: open || other_open,
-                })
+                // A row variable on either side carries the fields neither side lists.
+                let row = match (row, other_row) {
+                    (Row::Var(var), _) | (_, Row::Var(var)) => Row::Var(var),
+                    (Row::Closed, Row::Closed) => Row::Closed,
+                    _ => Row::Open,
+                };
+                Ok(Type::Record { fields, row })
             }
             (Type::Var(var), other) => {
                 self.bind_var(var, other, span.clone())?;
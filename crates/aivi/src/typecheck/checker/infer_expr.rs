@@ -4,6 +4,16 @@ impl TypeChecker {
             Ok(self.instantiate(scheme))
         } else if name.name == "_" {
             Ok(self.fresh_var())
+        } else if name.is_typed_hole() {
+            let ty = self.fresh_var();
+            // Elaboration re-infers expressions; the latest inference of a hole is the one in use.
+            self.holes.retain(|hole| hole.name.span != name.span);
+            self.holes.push(TypedHole {
+                name: name.clone(),
+                ty: ty.clone(),
+                env: env.clone(),
+            });
+            Ok(ty)
         } else {
             Err(TypeError {
                 span: name.span.clone(),
@@ -73,7 +83,7 @@ impl TypeChecker {
         // record type.
         let mut record_ty = Type::Record {
             fields: BTreeMap::new(),
-            open: false,
+            row: Row::Closed,
         };
 
         fn closed_record_from_path(path: &[PathSegment], value: Type) -> Type {
@@ -83,7 +93,10 @@ impl TypeChecker {
                     PathSegment::Field(name) => {
                         let mut fields = BTreeMap::new();
                         fields.insert(name.name.clone(), current);
-                        current = Type::Record { fields, open: false };
+                        current = Type::Record {
+                            fields,
+                            row: Row::Closed,
+                        };
                     }
                     PathSegment::Index(_, _) | PathSegment::All(_) => {
                         current = Type::con("List").app(vec![current]);
@@ -175,20 +188,125 @@ impl TypeChecker {
             }
         }
 
+        let message_index = self.message_render_index(func, env);
         let mut func_ty = self.infer_expr(func, env)?;
-        for arg in args {
+        for (index, arg) in args.iter().enumerate() {
             let arg_ty = self.infer_expr(arg, env)?;
             let result_ty = self.fresh_var();
             self.unify_with_span(
                 func_ty,
-                Type::Func(Box::new(arg_ty), Box::new(result_ty.clone())),
+                Type::Func(Box::new(arg_ty.clone()), Box::new(result_ty.clone())),
                 expr_span(arg),
             )?;
+            if let Some(message_index) = message_index.filter(|&i| i + 1 == index) {
+                self.check_message_literal_args(&args[message_index], arg, arg_ty)?;
+            }
             func_ty = result_ty;
         }
         Ok(func_ty)
     }
 
+    /// Position of the message argument when `func` names `render` or `renderIn` imported from
+    /// `aivi.i18n` (and not shadowed by a local binding).
+    fn message_render_index(&self, func: &Expr, env: &TypeEnv) -> Option<usize> {
+        let Expr::Ident(name) = func else {
+            return None;
+        };
+        if env.origin(&name.name) != Some("aivi.i18n") {
+            return None;
+        }
+        match name.name.rsplit('.').next() {
+            Some("render") => Some(0),
+            Some("renderIn") => Some(1),
+            _ => None,
+        }
+    }
+
+    /// `render ~m"..." args` (and `renderIn locale ...`): when the message is a literal, check
+    /// the argument record's inferred field types against the template's placeholders. Messages
+    /// from catalogs or other bindings are opaque `Message` values and are only checked when
+    /// rendered.
+    fn check_message_literal_args(
+        &mut self,
+        message: &Expr,
+        record: &Expr,
+        record_ty: Type,
+    ) -> Result<(), TypeError> {
+        let Expr::Literal(Literal::Sigil { tag, body, .. }) = message else {
+            return Ok(());
+        };
+        if tag != "m" {
+            return Ok(());
+        }
+        let Type::Record {
+            fields: field_tys,
+            row,
+        } = self.apply(record_ty)
+        else {
+            return Ok(());
+        };
+        // Malformed templates are reported by the parser (E1515).
+        let Ok(parsed) = crate::i18n::parse_message_template(body) else {
+            return Ok(());
+        };
+        let mut requirements = Vec::new();
+        collect_message_requirements(&parsed.parts, &mut requirements);
+
+        // Point at the field itself when the record is written out in place.
+        let field_span = |name: &str| {
+            match record {
+            Expr::Record { fields, .. } => fields
+                .iter()
+                .find(|field| {
+                    !field.spread
+                        && matches!(field.path.as_slice(), [PathSegment::Field(f)] if f.name == name)
+                })
+                .map(|field| expr_span(&field.value)),
+            _ => None,
+        }
+        };
+        for (name, requirement) in requirements {
+            let Some(field_ty) = field_tys.get(&name).cloned() else {
+                if matches!(row, Row::Closed) {
+                    return Err(TypeError {
+                        span: expr_span(record),
+                        message: format!("missing message argument '{name}'"),
+                        expected: None,
+                        found: None,
+                    });
+                }
+                continue;
+            };
+            let span = field_span(&name).unwrap_or_else(|| expr_span(record));
+            match requirement {
+                MessageArgRequirement::Exact(ty) => {
+                    self.unify_with_span(field_ty, Type::con(&ty), span)?;
+                }
+                MessageArgRequirement::Numeric => {
+                    let field_ty = self.apply(field_ty);
+                    let numeric = match &field_ty {
+                        Type::Var(_) => true,
+                        Type::Con(found, args) => {
+                            args.is_empty() && matches!(found.as_str(), "Int" | "Float" | "Decimal")
+                        }
+                        _ => false,
+                    };
+                    if !numeric {
+                        return Err(TypeError {
+                            span,
+                            message: format!(
+                                "message argument '{name}' must be numeric (Int, Float or Decimal)"
+                            ),
+                            expected: None,
+                            found: Some(Box::new(field_ty)),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn infer_method_call(
         &mut self,
         method: &SpannedName,
@@ -212,7 +330,24 @@ impl TypeChecker {
         let base_subst = self.subst.clone();
         let mut candidates: Vec<(HashMap<TypeVarId, Type>, Type)> = Vec::new();
 
-        for class_name in classes.iter().cloned() {
+        // Instances of the declaring classes come first. Only when none of them fits, an instance
+        // of a subclass that defines the method or inherits a default for it stands in.
+        let mut subclasses: Vec<String> = self
+            .classes
+            .iter()
+            .filter(|(name, info)| {
+                !classes.contains(name) && info.members.contains_key(&method.name)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        subclasses.sort();
+        let passes = [(classes.clone(), false), (subclasses, true)];
+        for (class_name, via_subclass) in passes.into_iter().flat_map(|(names, via_subclass)| {
+            names.into_iter().map(move |name| (name, via_subclass))
+        }) {
+            if via_subclass && !candidates.is_empty() {
+                break;
+            }
             let Some(class_info) = self.classes.get(&class_name).cloned() else {
                 continue;
             };
@@ -220,10 +355,16 @@ impl TypeChecker {
                 continue;
             };
 
+            let provides_default = class_info.defaults.contains_key(&method.name);
             let instances: Vec<InstanceDeclInfo> = self
                 .instances
                 .iter()
-                .filter(|instance| instance.class_name == class_name)
+                .filter(|instance| {
+                    instance.class_name == class_name
+                        && (!via_subclass
+                            || provides_default
+                            || instance.methods.contains(&method.name))
+                })
                 .cloned()
                 .collect();
 
@@ -235,12 +376,13 @@ impl TypeChecker {
                 self.subst = base_subst.clone();
 
                 let mut ctx = TypeContext::new(&self.type_constructors);
+                let mut inst_ctx = TypeContext::new(&self.type_constructors);
                 let mut ok = true;
                 for (class_param, inst_param) in
                     class_info.params.iter().zip(instance.params.iter())
                 {
                     let class_ty = self.type_from_expr(class_param, &mut ctx);
-                    let inst_ty = self.type_from_expr(inst_param, &mut ctx);
+                    let inst_ty = self.type_from_expr(inst_param, &mut inst_ctx);
                     if self
                         .unify_with_span(class_ty, inst_ty, method.span.clone())
                         .is_err()
@@ -253,7 +395,11 @@ impl TypeChecker {
                     continue;
                 }
 
-                let member_ty = self.type_from_expr(&member_ty_expr, &mut ctx);
+                let member_ty =
+                    match self.member_type_in_class(&class_name, &method.name, &mut ctx, 0) {
+                        Some(member_ty) => member_ty,
+                        None => self.type_from_expr(&member_ty_expr, &mut ctx),
+                    };
                 let result_ty = self.fresh_var();
                 let mut expected = result_ty.clone();
                 for arg_ty in arg_tys.iter().rev() {
@@ -275,6 +421,9 @@ impl TypeChecker {
             self.subst = subst;
             return Ok(result);
         }
+        if let Some(result) = self.infer_method_call_by_fundeps(method, &classes, &arg_tys)? {
+            return Ok(result);
+        }
 
         // If instance selection fails due to polymorphism, allow the call when a matching class
         // constraint is in scope for one of the argument type variables.
@@ -354,10 +503,111 @@ impl TypeChecker {
         }
     }
 
+    /// Selects the instance for a method call through a functional dependency `A -> B`: once
+    /// the call fixes the types of `A`, the single instance they match determines `B`, and the
+    /// call is checked against that instance alone.
+    fn infer_method_call_by_fundeps(
+        &mut self,
+        method: &SpannedName,
+        classes: &[String],
+        arg_tys: &[Type],
+    ) -> Result<Option<Type>, TypeError> {
+        let base_subst = self.subst.clone();
+        for class_name in classes {
+            let Some(class_info) = self.classes.get(class_name).cloned() else {
+                continue;
+            };
+            let Some(member_ty_expr) = class_info.members.get(&method.name).cloned() else {
+                continue;
+            };
+            if class_info.fundeps.is_empty() {
+                continue;
+            }
+
+            let mut ctx = TypeContext::new(&self.type_constructors);
+            let class_tys: Vec<Type> = class_info
+                .params
+                .iter()
+                .map(|param| self.type_from_expr(param, &mut ctx))
+                .collect();
+            let member_ty = match self.member_type_in_class(class_name, &method.name, &mut ctx, 0) {
+                Some(member_ty) => member_ty,
+                None => self.type_from_expr(&member_ty_expr, &mut ctx),
+            };
+            let result_ty = self.fresh_var();
+            let mut expected = result_ty.clone();
+            for arg_ty in arg_tys.iter().rev() {
+                expected = Type::Func(Box::new(arg_ty.clone()), Box::new(expected));
+            }
+            if self
+                .unify_with_span(member_ty, expected, method.span.clone())
+                .is_err()
+            {
+                self.subst = base_subst.clone();
+                continue;
+            }
+            let call_subst = self.subst.clone();
+
+            let instances: Vec<InstanceDeclInfo> = self
+                .instances
+                .iter()
+                .filter(|instance| {
+                    instance.class_name == *class_name
+                        && instance.params.len() == class_info.params.len()
+                })
+                .cloned()
+                .collect();
+            for (from, _) in &class_info.fundeps {
+                if from
+                    .iter()
+                    .any(|&idx| !self.free_vars(&class_tys[idx]).is_empty())
+                {
+                    continue;
+                }
+                let mut matching = Vec::new();
+                for instance in &instances {
+                    let mut inst_ctx = TypeContext::new(&self.type_constructors);
+                    let inst_tys: Vec<Type> = instance
+                        .params
+                        .iter()
+                        .map(|param| self.type_from_expr(param, &mut inst_ctx))
+                        .collect();
+                    let matches = from.iter().all(|&idx| {
+                        self.unify(
+                            class_tys[idx].clone(),
+                            inst_tys[idx].clone(),
+                            method.span.clone(),
+                        )
+                        .is_ok()
+                    });
+                    self.subst = call_subst.clone();
+                    if matches {
+                        matching.push(inst_tys);
+                    }
+                }
+                let [inst_tys] = &matching[..] else {
+                    continue;
+                };
+                for (class_ty, inst_ty) in class_tys.iter().zip(inst_tys) {
+                    if let Err(err) =
+                        self.unify_with_span(inst_ty.clone(), class_ty.clone(), method.span.clone())
+                    {
+                        self.subst = base_subst;
+                        return Err(err);
+                    }
+                }
+                return Ok(Some(self.apply(result_ty)));
+            }
+            self.subst = base_subst.clone();
+        }
+        Ok(None)
+    }
+
     fn infer_lambda(
         &mut self,
         params: &[Pattern],
         body: &Expr,
+        span: &Span,
         env: &mut TypeEnv,
     ) -> Result<Type, TypeError> {
         let mut local_env = env.clone();
@@ -366,6 +616,7 @@ impl TypeChecker {
             let param_ty = self.infer_pattern(pattern, &mut local_env)?;
             param_tys.push(param_ty);
         }
+        self.check_lambda_patterns(params, &param_tys, span);
         let mut body_ty = self.infer_expr(body, &mut local_env)?;
         for param_ty in param_tys.into_iter().rev() {
             body_ty = Type::Func(Box::new(param_ty), Box::new(body_ty));
@@ -397,7 +648,7 @@ impl TypeChecker {
             let body_ty = self.infer_expr(&arm.body, &mut arm_env)?;
             self.unify_with_span(body_ty, result_ty.clone(), arm.span.clone())?;
         }
-        self.check_match_arms(scrutinee_ty.clone(), arms, match_span);
+        self.check_match_arms(arms, &scrutinee_ty, match_span);
         // A match without an explicit scrutinee is the multi-clause unary-function sugar:
         //   f =
         //     | Pat1 => expr1
@@ -409,111 +660,148 @@ impl TypeChecker {
         }
     }
 
+    /// The ADT a pattern column of type `ty` ranges over, if known.
+    fn column_type_name(&mut self, ty: &Type) -> Option<String> {
+        let applied = self.apply(ty.clone());
+        match self.expand_alias(applied) {
+            Type::Con(name, _) if self.adt_constructors.contains_key(&name) => Some(name),
+            _ => None,
+        }
+    }
+
     fn check_match_arms(
         &mut self,
-        scrutinee_ty: Type,
         arms: &[crate::surface::MatchArm],
+        scrutinee_ty: &Type,
         match_span: &Span,
     ) {
-        // Unreachable arms: catch-all without a guard makes later arms unreachable.
-        let mut has_catch_all: Option<Span> = None;
-        // `covered_ctors` tracks constructors that are fully covered by a previous arm
-        // (i.e. a constructor arm whose arguments are all wildcards/idents).
-        let mut covered_ctors: HashSet<String> = HashSet::new();
-        // `seen_ctors` tracks constructors that appear anywhere in the match, regardless of
-        // argument patterns, for basic exhaustiveness checking.
-        let mut seen_ctors: HashSet<String> = HashSet::new();
-
-        for arm in arms {
-            if has_catch_all.is_some() {
-                self.emit_extra_diag(
-                    "W3101",
-                    crate::diagnostics::DiagnosticSeverity::Warning,
-                    "unreachable match arm (previous arm matches everything)".to_string(),
-                    arm.span.clone(),
-                );
-                continue;
-            }
-
-            let guarded = arm.guard.is_some();
-            if matches!(arm.pattern, Pattern::Wildcard(_) | Pattern::Ident(_)) && !guarded {
-                has_catch_all = Some(arm.span.clone());
-                continue;
-            }
-
-            if let Pattern::Constructor { name, ref args, .. } = &arm.pattern {
-                let ctor_name = name.name.clone();
-                seen_ctors.insert(ctor_name.clone());
-
-                if guarded {
-                    continue;
-                }
-
-                let ctor_catch_all = args
-                    .iter()
-                    .all(|arg| matches!(arg, Pattern::Wildcard(_) | Pattern::Ident(_)));
-                if !ctor_catch_all {
-                    continue;
-                }
-
-                // If a previous arm already fully covered this constructor, this arm is unreachable.
-                if covered_ctors.contains(&ctor_name) {
-                    self.emit_extra_diag(
-                        "W3101",
-                        crate::diagnostics::DiagnosticSeverity::Warning,
-                        format!(
-                            "unreachable match arm (constructor '{}' already matched by a previous arm)",
-                            ctor_name
-                        ),
-                        arm.span.clone(),
-                    );
-                } else {
-                    covered_ctors.insert(ctor_name);
-                }
-            }
+        let rows: Vec<MatchRow<'_>> = arms
+            .iter()
+            .map(|arm| MatchRow {
+                patterns: vec![&arm.pattern],
+                guarded: arm.guard.is_some(),
+            })
+            .collect();
+        let types = [self.column_type_name(scrutinee_ty)];
+        let report = exhaustiveness::check_rows(&self.adt_constructors, &rows, &types);
+        for index in report.redundant {
+            self.emit_extra_diag(
+                "W3101",
+                crate::diagnostics::DiagnosticSeverity::Warning,
+                "unreachable match arm (previous arms already match every value it matches)"
+                    .to_string(),
+                arms[index].span.clone(),
+            );
         }
-
-        // Non-exhaustive matches are errors unless there is a catch-all arm.
-        if has_catch_all.is_some() {
-            return;
+        if !report.missing.is_empty() {
+            self.emit_extra_diag(
+                "E3100",
+                crate::diagnostics::DiagnosticSeverity::Error,
+                format!(
+                    "non-exhaustive match (missing: {})",
+                    report.missing.join(", ")
+                ),
+                match_span.clone(),
+            );
         }
+    }
 
-        let scrutinee_ty = self.apply(scrutinee_ty);
-        let scrutinee_ty = self.expand_alias(scrutinee_ty);
-        let expected_ctors: Option<Vec<String>> = match scrutinee_ty {
-            Type::Con(ref name, _) if name == "Bool" => {
-                Some(vec!["True".to_string(), "False".to_string()])
-            }
-            Type::Con(ref name, _) if name == "Option" => {
-                Some(vec!["None".to_string(), "Some".to_string()])
-            }
-            Type::Con(ref name, _) if name == "Result" => {
-                Some(vec!["Ok".to_string(), "Err".to_string()])
-            }
-            Type::Con(ref name, _) => self.adt_constructors.get(name).cloned(),
-            _ => None,
-        };
-
-        let Some(expected_ctors) = expected_ctors else {
+    /// Lambda parameters have no fallback, so a refutable parameter pattern must still cover
+    /// every value.
+    fn check_lambda_patterns(&mut self, params: &[Pattern], param_tys: &[Type], span: &Span) {
+        if self.clause_lambda_spans.contains(span) {
             return;
-        };
-
-        let mut missing = Vec::new();
-        for ctor in expected_ctors {
-            if !seen_ctors.contains(&ctor) {
-                missing.push(ctor);
-            }
         }
-        if !missing.is_empty() {
+        let rows = [MatchRow {
+            patterns: params.iter().collect(),
+            guarded: false,
+        }];
+        let types: Vec<Option<String>> = param_tys
+            .iter()
+            .map(|ty| self.column_type_name(ty))
+            .collect();
+        let report = exhaustiveness::check_rows(&self.adt_constructors, &rows, &types);
+        if !report.missing.is_empty() {
             self.emit_extra_diag(
                 "E3100",
                 crate::diagnostics::DiagnosticSeverity::Error,
-                format!("non-exhaustive match (missing: {})", missing.join(", ")),
-                match_span.clone(),
+                format!(
+                    "non-exhaustive lambda parameters (missing: {})",
+                    report.missing.join(", ")
+                ),
+                span.clone(),
             );
         }
     }
 
+    /// Checks the clauses of a function together, as one match over all parameters. Clauses
+    /// that are not lambdas take any argument, so such functions are skipped. The parameter
+    /// types come from the function's scheme in `env`.
+    fn check_def_clauses(&mut self, clauses: &[&Def], env: &TypeEnv) {
+        let mut rows = Vec::new();
+        let mut spans = Vec::new();
+        for def in clauses {
+            let patterns: Vec<&Pattern> = match &def.expr {
+                _ if !def.params.is_empty() => def.params.iter().collect(),
+                Expr::Lambda { params, span, .. } => {
+                    spans.push(span.clone());
+                    rows.push(MatchRow {
+                        patterns: params.iter().collect(),
+                        guarded: false,
+                    });
+                    continue;
+                }
+                _ => return,
+            };
+            spans.push(def.span.clone());
+            rows.push(MatchRow {
+                patterns,
+                guarded: false,
+            });
+        }
+        let name = &clauses[0].name.name;
+        let mut types = Vec::new();
+        let mut remaining = env.get(name).map(|scheme| scheme.ty.clone());
+        while let Some(ty) = remaining.take() {
+            let applied = self.apply(ty);
+            if let Type::Func(param, rest) = self.expand_alias(applied) {
+                types.push(self.column_type_name(&param));
+                remaining = Some(*rest);
+            }
+        }
+        let report = exhaustiveness::check_rows(&self.adt_constructors, &rows, &types);
+        for index in report.redundant {
+            self.emit_extra_diag(
+                "W3101",
+                crate::diagnostics::DiagnosticSeverity::Warning,
+                format!(
+                    "unreachable clause of '{name}' (previous clauses already match its arguments)"
+                ),
+                spans[index].clone(),
+            );
+        }
+        if report.missing.is_empty() {
+            return;
+        }
+        let message = if clauses.len() == 1 {
+            format!(
+                "non-exhaustive lambda parameters (missing: {})",
+                report.missing.join(", ")
+            )
+        } else {
+            format!(
+                "non-exhaustive clauses for '{name}' (missing: {})",
+                report.missing.join(", ")
+            )
+        };
+        self.emit_extra_diag(
+            "E3100",
+            crate::diagnostics::DiagnosticSeverity::Error,
+            message,
+            spans[0].clone(),
+        );
+    }
+
     fn infer_if(
         &mut self,
         cond: &Expr,
@@ -580,9 +868,9 @@ impl TypeChecker {
                         || matches!(right_applied, Type::Var(_));
                     let concrete_non_int = matches!(left_applied, Type::Con(ref name, _) if name != "Int")
                         || matches!(right_applied, Type::Con(ref name, _) if name != "Int");
-                    if let Some(scheme) = env.get(&op_name) {
+                    for scheme in env.operator_schemes(&op_name) {
                         let checkpoint_subst = self.subst.clone();
-                        let op_ty = self.instantiate(scheme);
+                        let op_ty = self.instantiate(&scheme);
                         let result_ty = self.fresh_var();
                         let expected = Type::Func(
                             Box::new(left_ty.clone()),
@@ -628,9 +916,9 @@ impl TypeChecker {
                         || matches!(right_applied, Type::Var(_));
                     let concrete_non_int = matches!(left_applied, Type::Con(ref name, _) if name != "Int")
                         || matches!(right_applied, Type::Con(ref name, _) if name != "Int");
-                    if let Some(scheme) = env.get(&op_name) {
+                    for scheme in env.operator_schemes(&op_name) {
                         let checkpoint_subst = self.subst.clone();
-                        let op_ty = self.instantiate(scheme);
+                        let op_ty = self.instantiate(&scheme);
                         let result_ty = self.fresh_var();
                         let expected = Type::Func(
                             Box::new(left_ty.clone()),
@@ -691,7 +979,11 @@ impl TypeChecker {
     ) -> Result<Type, TypeError> {
         let mut local_env = env.clone();
         let mut last_ty = Type::con("Unit");
-        for item in items {
+        let groups = recursive_binding_groups(items);
+        for (idx, item) in items.iter().enumerate() {
+            if self.infer_recursive_group(&groups, items, idx, &mut local_env)? {
+                continue;
+            }
             match item {
                 BlockItem::Bind { pattern, expr, .. } => {
                     let expr_ty = self.infer_expr(expr, &mut local_env)?;
@@ -713,4 +1005,90 @@ impl TypeChecker {
         }
         Ok(last_ty)
     }
+
+    /// Infers the recursive binding group that `items[idx]` belongs to, if any, when reaching
+    /// its first binding. Returns whether the item was part of a group.
+    fn infer_recursive_group(
+        &mut self,
+        groups: &[Range<usize>],
+        items: &[BlockItem],
+        idx: usize,
+        env: &mut TypeEnv,
+    ) -> Result<bool, TypeError> {
+        let Some(group) = groups.iter().find(|group| group.contains(&idx)) else {
+            return Ok(false);
+        };
+        if group.start != idx {
+            return Ok(true);
+        }
+        let outer_env = env.clone();
+        let bindings: Vec<(&SpannedName, &Expr, Type)> = items[group.clone()]
+            .iter()
+            .filter_map(function_binding)
+            .map(|(name, expr)| (name, expr, self.fresh_var()))
+            .collect();
+        for (name, _, ty) in &bindings {
+            env.insert(name.name.clone(), Scheme::mono(ty.clone()));
+        }
+        for (_, expr, ty) in &bindings {
+            let expr_ty = self.infer_expr(expr, env)?;
+            self.unify_with_span(ty.clone(), expr_ty, expr_span(expr))?;
+        }
+        // Like module-level definitions, the group is generalised once all of it is known.
+        for (name, _, ty) in bindings {
+            let scheme = self.generalize(ty, &outer_env);
+            env.insert(name.name.clone(), scheme);
+        }
+        Ok(true)
+    }
+}
+
+enum MessageArgRequirement {
+    Exact(String),
+    Numeric,
+}
+
+fn collect_message_requirements(
+    parts: &[crate::i18n::MessagePart],
+    out: &mut Vec<(String, MessageArgRequirement)>,
+) {
+    use crate::i18n::MessagePart;
+    for part in parts {
+        match part {
+            MessagePart::Lit(_) | MessagePart::Pound => {}
+            MessagePart::Hole { name, ty } => {
+                if let Some(ty) = ty {
+                    out.push((name.clone(), MessageArgRequirement::Exact(ty.clone())));
+                }
+            }
+            MessagePart::Plural { name, ty, arms, .. } => {
+                let requirement = match ty {
+                    Some(ty) => MessageArgRequirement::Exact(ty.clone()),
+                    None => MessageArgRequirement::Numeric,
+                };
+                out.push((name.clone(), requirement));
+                for arm in arms {
+                    collect_message_requirements(&arm.parts, out);
+                }
+            }
+            MessagePart::Select { name, ty, arms } => {
+                if let Some(ty) = ty {
+                    out.push((name.clone(), MessageArgRequirement::Exact(ty.clone())));
+                }
+                for arm in arms {
+                    collect_message_requirements(&arm.parts, out);
+                }
+            }
+            MessagePart::Format {
+                name, ty, format, ..
+            } => {
+                let requirement = match (ty, format.as_str()) {
+                    (Some(ty), _) => MessageArgRequirement::Exact(ty.clone()),
+                    (None, "number") => MessageArgRequirement::Numeric,
+                    (None, _) => MessageArgRequirement::Exact("DateTime".to_string()),
+                };
+                out.push((name.clone(), requirement));
+            }
+        }
+    }
 }
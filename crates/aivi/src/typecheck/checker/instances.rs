@@ -5,6 +5,62 @@ impl TypeChecker {
         env: &TypeEnv,
         module: &Module,
         diagnostics: &mut Vec<FileDiagnostic>,
+    ) {
+        let derived_for = instance.decorators.iter().find_map(|decorator| {
+            match (&decorator.name.name[..], &decorator.arg) {
+                ("derived", Some(Expr::Ident(type_name))) => Some(type_name.name.clone()),
+                _ => None,
+            }
+        });
+        let start = diagnostics.len();
+        self.check_instance_members(instance, derived_for.is_some(), env, module, diagnostics);
+
+        // Instances generated from a `deriving` clause point their spans at the clause; name the
+        // failed derivation so the error doesn't read like one about hand-written code.
+        if let Some(type_name) = derived_for {
+            for diag in &mut diagnostics[start..] {
+                diag.diagnostic.message = format!(
+                    "cannot derive '{}' for '{}': {}",
+                    instance.name.name, type_name, diag.diagnostic.message
+                );
+            }
+        }
+    }
+
+    fn check_class_defaults(
+        &mut self,
+        class_decl: &crate::surface::ClassDecl,
+        module: &Module,
+        diagnostics: &mut Vec<FileDiagnostic>,
+    ) {
+        let Some(class_info) = self.classes.get(&class_decl.name.name).cloned() else {
+            return;
+        };
+        for def in &class_decl.defaults {
+            if !class_info.members.contains_key(&def.name.name) {
+                diagnostics.push(self.error_to_diag(
+                    module,
+                    TypeError {
+                        span: def.name.span.clone(),
+                        message: format!(
+                            "default '{}' is not a member of class '{}' or its superclasses",
+                            def.name.name, class_decl.name.name
+                        ),
+                        expected: None,
+                        found: None,
+                    },
+                ));
+            }
+        }
+    }
+
+    fn check_instance_members(
+        &mut self,
+        instance: &crate::surface::InstanceDecl,
+        derived: bool,
+        env: &TypeEnv,
+        module: &Module,
+        diagnostics: &mut Vec<FileDiagnostic>,
     ) {
         let Some(class_info) = self.classes.get(&instance.name.name).cloned() else {
             diagnostics.push(self.error_to_diag(
@@ -52,42 +108,65 @@ impl TypeChecker {
             }
         }
 
-        for (member_name, member_sig) in class_info.members.iter() {
-            let Some(def) = defs_by_name.get(member_name).copied() else {
-                // If the member is inherited via a superclass constraint, allow the instance
-                // to omit it as long as a matching superclass instance is in scope.
-                if self.instance_method_satisfied_by_super(instance, &class_info, member_name) {
-                    continue;
-                }
+        self.check_instance_fundeps(instance, &class_info, module, diagnostics);
 
-                diagnostics.push(self.error_to_diag(
-                    module,
-                    TypeError {
-                        span: instance.span.clone(),
-                        message: format!("missing instance method '{}'", member_name),
-                        expected: None,
-                        found: None,
-                    },
-                ));
-                continue;
+        let mut member_names: Vec<&String> = class_info.members.keys().collect();
+        member_names.sort();
+        // If a member is inherited via a superclass constraint, the instance may omit it as long
+        // as a matching superclass instance is in scope.
+        let omitted: Vec<&String> = member_names
+            .iter()
+            .copied()
+            .filter(|member_name| {
+                !defs_by_name.contains_key(*member_name)
+                    && !self.instance_method_satisfied_by_super(instance, &class_info, member_name)
+            })
+            .collect();
+        let missing: Vec<String> = omitted
+            .iter()
+            .filter(|member_name| !class_info.defaults.contains_key(**member_name))
+            .map(|member_name| format!("'{member_name}'"))
+            .collect();
+        for member_name in member_names {
+            let member_sig = &class_info.members[member_name];
+            let (def, from_default) = match defs_by_name.get(member_name) {
+                Some(def) => (*def, false),
+                // A default that leans on a missing method would only repeat that error.
+                None if !missing.is_empty() || !omitted.contains(&member_name) => continue,
+                None => match class_info.defaults.get(member_name) {
+                    Some(def) => (def, true),
+                    None => continue,
+                },
             };
 
             let base_subst = self.subst.clone();
             let mut ctx = TypeContext::new(&self.type_constructors);
+            // The instance head gets its own scope: `instance Setoid (Tree A)` must not tie its
+            // `A` to the class parameter `A`.
+            let mut inst_ctx = TypeContext::new(&self.type_constructors);
             for (class_param, inst_param) in class_info.params.iter().zip(instance.params.iter()) {
                 let class_ty = self.type_from_expr(class_param, &mut ctx);
-                let inst_ty = self.type_from_expr(inst_param, &mut ctx);
+                let inst_ty = self.type_from_expr(inst_param, &mut inst_ctx);
                 if let Err(err) = self.unify_with_span(class_ty, inst_ty, instance.span.clone()) {
                     diagnostics.push(self.error_to_diag(module, err));
                     self.subst = base_subst;
                     return;
                 }
             }
-            let expected = self.type_from_expr(member_sig, &mut ctx);
+            let expected =
+                match self.member_type_in_class(&instance.name.name, member_name, &mut ctx, 0) {
+                    Some(expected) => expected,
+                    None => self.type_from_expr(member_sig, &mut ctx),
+                };
 
             let expr = crate::surface::desugar_effect_sugars(desugar_holes(def.expr.clone()));
+            // A hand-written member's recursive calls are at the instance type itself. Derived
+            // members call the member at their field types too, so those calls resolve through
+            // instance selection instead.
             let mut local_env = env.clone();
-            local_env.insert(def.name.name.clone(), Scheme::mono(expected.clone()));
+            if !derived {
+                local_env.insert(def.name.name.clone(), Scheme::mono(expected.clone()));
+            }
 
             let assumed_constraints: Vec<(String, TypeVarId)> = class_info
                 .constraints
@@ -100,6 +179,7 @@ impl TypeChecker {
                 .collect();
             let old_assumed =
                 std::mem::replace(&mut self.assumed_class_constraints, assumed_constraints);
+            let all_instances = from_default.then(|| self.restrict_instances_to_head(instance));
 
             let result: Result<(), TypeError> = (|| {
                 // Instance methods are often written as `name: x y => ...` (a lambda expression).
@@ -161,13 +241,47 @@ impl TypeChecker {
             })();
 
             self.assumed_class_constraints = old_assumed;
+            if let Some(all_instances) = all_instances {
+                self.instances = all_instances;
+            }
             if let Err(err) = result {
+                // A default body lives in the class declaration, possibly in another module;
+                // report the mismatch on the instance that picked it up.
+                let err = if from_default {
+                    TypeError {
+                        span: instance.span.clone(),
+                        message: format!(
+                            "default for '{}' does not fit this instance: {}",
+                            member_name, err.message
+                        ),
+                        ..err
+                    }
+                } else {
+                    err
+                };
                 diagnostics.push(self.error_to_diag(module, err));
             }
 
             self.subst = base_subst;
         }
 
+        if !missing.is_empty() {
+            let message = if missing.len() == 1 {
+                format!("missing instance method {}", missing[0])
+            } else {
+                format!("missing instance methods {}", missing.join(", "))
+            };
+            diagnostics.push(self.error_to_diag(
+                module,
+                TypeError {
+                    span: instance.span.clone(),
+                    message,
+                    expected: None,
+                    found: None,
+                },
+            ));
+        }
+
         for method_name in defs_by_name.keys() {
             if !class_info.members.contains_key(method_name) {
                 diagnostics.push(self.error_to_diag(
@@ -183,6 +297,103 @@ impl TypeChecker {
         }
     }
 
+    /// Reports the instances in scope that agree with `instance` on the determining parameters
+    /// of a functional dependency but not on the determined ones.
+    fn check_instance_fundeps(
+        &mut self,
+        instance: &crate::surface::InstanceDecl,
+        class_info: &ClassDeclInfo,
+        module: &Module,
+        diagnostics: &mut Vec<FileDiagnostic>,
+    ) {
+        if class_info.fundeps.is_empty() {
+            return;
+        }
+        // Only instances declared before this one: each conflict is reported once, on the later
+        // instance.
+        let head_text = |params: &[TypeExpr]| {
+            params
+                .iter()
+                .map(crate::doc_gen::type_expr_to_string)
+                .collect::<Vec<_>>()
+        };
+        let own_head = head_text(&instance.params);
+        let others: Vec<InstanceDeclInfo> = self
+            .instances
+            .iter()
+            .take_while(|other| {
+                other.class_name != instance.name.name || head_text(&other.params) != own_head
+            })
+            .filter(|other| {
+                other.class_name == instance.name.name
+                    && other.params.len() == instance.params.len()
+            })
+            .cloned()
+            .collect();
+        for (from, to) in &class_info.fundeps {
+            for other in &others {
+                let base_subst = self.subst.clone();
+                let mut inst_ctx = TypeContext::new(&self.type_constructors);
+                let mut other_ctx = TypeContext::new(&self.type_constructors);
+                let inst_tys: Vec<Type> = instance
+                    .params
+                    .iter()
+                    .map(|param| self.type_from_expr(param, &mut inst_ctx))
+                    .collect();
+                let other_tys: Vec<Type> = other
+                    .params
+                    .iter()
+                    .map(|param| self.type_from_expr(param, &mut other_ctx))
+                    .collect();
+                let agree = |checker: &mut Self, indices: &[usize]| {
+                    indices.iter().all(|&idx| {
+                        checker
+                            .unify(
+                                inst_tys[idx].clone(),
+                                other_tys[idx].clone(),
+                                instance.span.clone(),
+                            )
+                            .is_ok()
+                    })
+                };
+                let conflict = agree(self, from) && !agree(self, to);
+                self.subst = base_subst;
+                if conflict {
+                    let head = |params: &[TypeExpr]| {
+                        format!("{} {}", instance.name.name, head_text(params).join(" "))
+                    };
+                    let names = |indices: &[usize]| {
+                        indices
+                            .iter()
+                            .map(|&idx| {
+                                crate::doc_gen::type_expr_to_string(&class_info.params[idx])
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    };
+                    diagnostics.push(self.error_to_diag(
+                        module,
+                        TypeError {
+                            span: instance.span.clone(),
+                            message: format!(
+                                "instance '{}' conflicts with '{}': the functional dependency \
+                                 '{} -> {}' allows only one instance per '{}'",
+                                head(&instance.params),
+                                head(&other.params),
+                                names(from),
+                                names(to),
+                                names(from)
+                            ),
+                            expected: None,
+                            found: None,
+                        },
+                    ));
+                    return;
+                }
+            }
+        }
+    }
+
     fn instance_method_satisfied_by_super(
         &mut self,
         instance: &crate::surface::InstanceDecl,
@@ -206,9 +417,10 @@ impl TypeChecker {
             // concrete instance parameters, then applying the resulting substitution.
             let base_subst = self.subst.clone();
             let mut ctx = TypeContext::new(&self.type_constructors);
+            let mut inst_ctx = TypeContext::new(&self.type_constructors);
             for (class_param, inst_param) in class_info.params.iter().zip(instance.params.iter()) {
                 let class_ty = self.type_from_expr(class_param, &mut ctx);
-                let inst_ty = self.type_from_expr(inst_param, &mut ctx);
+                let inst_ty = self.type_from_expr(inst_param, &mut inst_ctx);
                 if self
                     .unify(class_ty, inst_ty, instance.span.clone())
                     .is_err()
@@ -282,6 +494,103 @@ impl TypeChecker {
         }
     }
 
+    /// The type of `member` as seen from `class_name`, whose parameters are named in `ctx`.
+    ///
+    /// Inherited members are declared in terms of their own class's parameters; those are tied
+    /// to the arguments the subclass passes to the superclass (`Functor (M *)`).
+    fn member_type_in_class(
+        &mut self,
+        class_name: &str,
+        member: &str,
+        ctx: &mut TypeContext,
+        depth: usize,
+    ) -> Option<Type> {
+        let class_info = self.classes.get(class_name)?.clone();
+        if let Some(member_ty) = class_info.direct_members.get(member) {
+            return Some(self.type_from_expr(member_ty, ctx));
+        }
+        if depth > 16 {
+            return None;
+        }
+        for super_expr in self.flatten_type_and_list(&class_info.supers) {
+            let Some((super_name, super_args)) = self.class_ref_from_type_expr(&super_expr) else {
+                continue;
+            };
+            let Some(super_info) = self.classes.get(super_name).cloned() else {
+                continue;
+            };
+            if !super_info.members.contains_key(member) {
+                continue;
+            }
+            let mut super_ctx = TypeContext::new(&self.type_constructors);
+            for (super_param, super_arg) in super_info.params.iter().zip(super_args.iter()) {
+                let param_ty = self.type_from_expr(super_param, &mut super_ctx);
+                let arg_ty = self.type_from_expr(super_arg, ctx);
+                let span = type_expr_span(super_arg);
+                let _ = self.unify(param_ty, arg_ty, span);
+            }
+            return self.member_type_in_class(super_name, member, &mut super_ctx, depth + 1);
+        }
+        None
+    }
+
+    /// Class names in `class_name`'s hierarchy: the class itself and all its superclasses.
+    fn class_and_supers(&self, class_name: &str) -> HashSet<String> {
+        let mut names = HashSet::new();
+        let mut pending = vec![class_name.to_string()];
+        while let Some(name) = pending.pop() {
+            if !names.insert(name.clone()) {
+                continue;
+            }
+            let Some(class_info) = self.classes.get(&name) else {
+                continue;
+            };
+            for super_expr in self.flatten_type_and_list(&class_info.supers) {
+                if let Some((super_name, _)) = self.class_ref_from_type_expr(&super_expr) {
+                    pending.push(super_name.to_string());
+                }
+            }
+        }
+        names
+    }
+
+    /// Drops the instances of `instance`'s class hierarchy that are for another head, so that the
+    /// methods a default body calls resolve to this instance, as dictionary passing would. Returns
+    /// the previous instance list.
+    fn restrict_instances_to_head(
+        &mut self,
+        instance: &crate::surface::InstanceDecl,
+    ) -> Vec<InstanceDeclInfo> {
+        let hierarchy = self.class_and_supers(&instance.name.name);
+        let all = self.instances.clone();
+        let mut kept = Vec::with_capacity(all.len());
+        for other in &all {
+            if !hierarchy.contains(&other.class_name) || other.params.len() != instance.params.len()
+            {
+                kept.push(other.clone());
+                continue;
+            }
+            let base_subst = self.subst.clone();
+            let mut inst_ctx = TypeContext::new(&self.type_constructors);
+            let mut other_ctx = TypeContext::new(&self.type_constructors);
+            let same_head =
+                instance
+                    .params
+                    .iter()
+                    .zip(other.params.iter())
+                    .all(|(inst_param, other_param)| {
+                        let inst_ty = self.type_from_expr(inst_param, &mut inst_ctx);
+                        let other_ty = self.type_from_expr(other_param, &mut other_ctx);
+                        self.unify(inst_ty, other_ty, instance.span.clone()).is_ok()
+                    });
+            self.subst = base_subst;
+            if same_head {
+                kept.push(other.clone());
+            }
+        }
+        std::mem::replace(&mut self.instances, kept)
+    }
+
     fn class_ref_from_type_expr<'a>(&self, ty: &'a TypeExpr) -> Option<(&'a str, Vec<TypeExpr>)> {
         match ty {
             TypeExpr::Name(name) => Some((name.name.as_str(), Vec::new())),
@@ -315,8 +624,9 @@ impl TypeChecker {
                     // can use the signature to constrain parameter types early. This avoids
                     // incorrectly selecting domain operators when operands start as unconstrained
                     // type variables (e.g. `a.x + b.x` inside a domain `(+)=...` implementation).
-                    if let Expr::Lambda { params, body, .. } = expr.clone() {
+                    if let Expr::Lambda { params, body, span } = expr.clone() {
                         let mut remaining = expected;
+                        let mut param_tys = Vec::new();
                         for param in &params {
                             let remaining_applied = self.apply(remaining);
                             let remaining_norm = self.expand_alias(remaining_applied);
@@ -329,9 +639,15 @@ impl TypeChecker {
                                 });
                             };
                             let pat_ty = self.infer_pattern(param, &mut local_env)?;
-                            self.unify_with_span(pat_ty, *expected_param, pattern_span(param))?;
+                            self.unify_with_span(
+                                pat_ty.clone(),
+                                *expected_param,
+                                pattern_span(param),
+                            )?;
+                            param_tys.push(pat_ty);
                             remaining = *expected_rest;
                         }
+                        self.check_lambda_patterns(&params, &param_tys, &span);
                         // Elaborate against the remaining expected return type so the typechecker
                         // can apply expected-type coercions in the body.
                         let (_elab, _ty) =
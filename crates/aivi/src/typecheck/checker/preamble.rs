@@ -1,15 +1,17 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Range;
 
-use crate::diagnostics::{Diagnostic, FileDiagnostic, Span};
+use crate::diagnostics::{Diagnostic, DiagnosticLabel, FileDiagnostic, Span};
 use crate::surface::{
-    BlockItem, BlockKind, Def, DomainItem, Expr, ListItem, Literal, Module, ModuleItem,
-    PathSegment, Pattern, RecordField, RecordPatternField, SpannedName, TextPart, TypeAlias,
-    TypeDecl, TypeExpr, TypeSig,
+    function_binding, recursive_binding_groups, BlockItem, BlockKind, Def, DomainItem, EffectDecl,
+    Expr, ListItem, Literal, Module, ModuleItem, PathSegment, Pattern, RecordField,
+    RecordPatternField, SpannedName, TextPart, TypeAlias, TypeDecl, TypeExpr, TypeSig,
 };
 
+use super::exhaustiveness::{self, ConstructorSets, MatchRow};
 use super::types::{
-    number_kind, split_suffixed_number, AliasInfo, Kind, NumberKind, Scheme, Type, TypeContext,
-    TypeEnv, TypeError, TypePrinter, TypeVarId,
+    number_kind, split_suffixed_number, AliasInfo, Kind, NumberKind, Row, Scheme, Type,
+    TypeContext, TypeEnv, TypeError, TypePrinter, TypeVarId,
 };
 use super::{ClassDeclInfo, InstanceDeclInfo};
 
@@ -29,7 +31,30 @@ pub(super) struct TypeChecker {
     assumed_class_constraints: Vec<(String, TypeVarId)>,
     current_module_path: String,
     extra_diagnostics: Vec<FileDiagnostic>,
-    adt_constructors: HashMap<String, Vec<String>>,
+    /// Constructors of the ADTs visible to the current module: every module's declarations,
+    /// with the current module's own taking precedence.
+    adt_constructors: ConstructorSets,
+    global_adt_constructors: ConstructorSets,
+    /// Spans of top-level definitions' lambdas; their patterns are checked per function by
+    /// `check_def_clauses` rather than one lambda at a time.
+    clause_lambda_spans: Vec<Span>,
+    /// Operation names of every declared effect, across all modules.
+    global_effects: HashMap<String, Vec<String>>,
+    /// The effect each registered operation belongs to, with its scheme. Kept across modules so
+    /// importers see the operations of effects declared earlier in dependency order.
+    effect_ops: HashMap<String, (String, Scheme)>,
+    /// Per-carrier signatures of the domain operators each module defines over several
+    /// carriers, keyed by module and operator. Kept across modules for importers.
+    domain_overloads: HashMap<(String, String), Vec<Scheme>>,
+    /// `?name` holes of the definition being checked; reported by `report_holes`.
+    holes: Vec<TypedHole>,
+}
+
+/// A typed hole met during inference, with the environment it appeared in.
+struct TypedHole {
+    name: SpannedName,
+    ty: Type,
+    env: TypeEnv,
 }
 
 impl TypeChecker {
@@ -51,6 +76,12 @@ impl TypeChecker {
             current_module_path: String::new(),
             extra_diagnostics: Vec::new(),
             adt_constructors: HashMap::new(),
+            global_adt_constructors: HashMap::new(),
+            clause_lambda_spans: Vec::new(),
+            global_effects: HashMap::new(),
+            effect_ops: HashMap::new(),
+            domain_overloads: HashMap::new(),
+            holes: Vec::new(),
         };
         checker.register_builtin_types();
         checker.register_builtin_aliases();
@@ -67,6 +98,15 @@ impl TypeChecker {
         self.global_aliases = aliases;
     }
 
+    pub(super) fn set_global_effects(&mut self, effects: HashMap<String, Vec<String>>) {
+        self.global_effects = effects;
+        self.effect_ops.clear();
+    }
+
+    pub(super) fn set_global_constructors(&mut self, constructors: ConstructorSets) {
+        self.global_adt_constructors = constructors;
+    }
+
     pub(super) fn reset_module_context(&mut self, _module: &Module) {
         self.subst.clear();
         self.type_constructors = self.builtin_type_constructors();
@@ -81,7 +121,8 @@ impl TypeChecker {
         self.method_to_classes.clear();
         self.assumed_class_constraints.clear();
         self.extra_diagnostics.clear();
-        self.adt_constructors.clear();
+        self.adt_constructors = self.global_adt_constructors.clone();
+        self.holes.clear();
         self.current_module_path = _module.path.clone();
     }
 
@@ -92,6 +133,15 @@ impl TypeChecker {
         message: String,
         span: Span,
     ) {
+        // Expressions can be inferred more than once (elaboration re-infers what it rebuilt), so
+        // the same finding may be reported repeatedly.
+        if self.extra_diagnostics.iter().any(|diag| {
+            diag.diagnostic.code == code
+                && diag.diagnostic.span == span
+                && diag.diagnostic.message == message
+        }) {
+            return;
+        }
         self.extra_diagnostics.push(FileDiagnostic {
             path: self.current_module_path.clone(),
             diagnostic: Diagnostic {
@@ -344,7 +394,7 @@ impl TypeChecker {
             ]
             .into_iter()
             .collect(),
-            open: true,
+            row: Row::Open,
         };
         env.insert("file".to_string(), Scheme::mono(file_record));
 
@@ -398,7 +448,7 @@ impl TypeChecker {
             ]
             .into_iter()
             .collect(),
-            open: true,
+            row: Row::Open,
         };
         env.insert("channel".to_string(), Scheme::mono(channel_record));
 
@@ -447,7 +497,7 @@ impl TypeChecker {
             ]
             .into_iter()
             .collect(),
-            open: true,
+            row: Row::Open,
         };
         env.insert("concurrent".to_string(), Scheme::mono(concurrent_record));
 
@@ -463,7 +513,7 @@ impl TypeChecker {
             )]
             .into_iter()
             .collect(),
-            open: true,
+            row: Row::Open,
         };
         env.insert("clock".to_string(), Scheme::mono(clock_record));
 
@@ -482,7 +532,7 @@ impl TypeChecker {
             )]
             .into_iter()
             .collect(),
-            open: true,
+            row: Row::Open,
         };
         env.insert("random".to_string(), Scheme::mono(random_record));
 
@@ -493,7 +543,7 @@ impl TypeChecker {
             )]
             .into_iter()
             .collect(),
-            open: true,
+            row: Row::Open,
         };
         env.insert("html".to_string(), Scheme::mono(html_record));
 
@@ -57,6 +57,11 @@ impl TypeChecker {
                 ModuleItem::TypeAlias(alias) => {
                     self.validate_type_expr(&alias.aliased, &mut errors);
                 }
+                ModuleItem::EffectDecl(effect) => {
+                    for op in &effect.operations {
+                        self.validate_type_expr(&op.ty, &mut errors);
+                    }
+                }
                 ModuleItem::DomainDecl(domain) => {
                     for domain_item in &domain.items {
                         match domain_item {
@@ -122,11 +127,7 @@ impl TypeChecker {
                     if !type_decl.constructors.is_empty() {
                         self.adt_constructors.insert(
                             type_decl.name.name.clone(),
-                            type_decl
-                                .constructors
-                                .iter()
-                                .map(|ctor| ctor.name.name.clone())
-                                .collect(),
+                            exhaustiveness::constructor_set(type_decl),
                         );
                     }
                     self.register_adt_constructors(type_decl, env);
@@ -137,11 +138,7 @@ impl TypeChecker {
                             if !type_decl.constructors.is_empty() {
                                 self.adt_constructors.insert(
                                     type_decl.name.name.clone(),
-                                    type_decl
-                                        .constructors
-                                        .iter()
-                                        .map(|ctor| ctor.name.name.clone())
-                                        .collect(),
+                                    exhaustiveness::constructor_set(type_decl),
                                 );
                             }
                             self.register_adt_constructors(type_decl, env);
@@ -181,6 +178,14 @@ impl TypeChecker {
         }
     }
 
+    /// Binds the per-carrier signatures `module_name` declares for the operator `name`, if any.
+    fn bind_domain_overloads(&self, module_name: &str, name: &str, env: &mut TypeEnv) {
+        let key = (module_name.to_string(), name.to_string());
+        if let Some(schemes) = self.domain_overloads.get(&key) {
+            env.insert_overloads(name.to_string(), schemes.clone());
+        }
+    }
+
     pub(super) fn register_imports(
         &mut self,
         module: &Module,
@@ -191,13 +196,16 @@ impl TypeChecker {
         for use_decl in &module.uses {
             if let Some(exports) = module_exports.get(&use_decl.module.name) {
                 let qualify = use_decl.alias.is_some();
+                let module_name = use_decl.module.name.as_str();
                 if use_decl.wildcard {
                     for (name, scheme) in exports {
-                        env.insert(name.clone(), scheme.clone());
+                        env.insert_imported(name.clone(), scheme.clone(), module_name);
+                        self.bind_domain_overloads(module_name, name, env);
                         if qualify {
-                            env.insert(
-                                format!("{}.{}", use_decl.module.name, name),
+                            env.insert_imported(
+                                format!("{module_name}.{name}"),
                                 scheme.clone(),
+                                module_name,
                             );
                         }
                     }
@@ -205,12 +213,21 @@ impl TypeChecker {
                     for item in &use_decl.items {
                         match item.kind {
                             crate::surface::ScopeItemKind::Value => {
-                                if let Some(scheme) = exports.get(&item.name.name) {
-                                    env.insert(item.name.name.clone(), scheme.clone());
+                                // Importing an effect brings its operations along.
+                                let ops = self.global_effects.get(&item.name.name);
+                                let names = std::iter::once(&item.name.name)
+                                    .chain(ops.into_iter().flatten());
+                                for name in names {
+                                    let Some(scheme) = exports.get(name) else {
+                                        continue;
+                                    };
+                                    env.insert_imported(name.clone(), scheme.clone(), module_name);
+                                    self.bind_domain_overloads(module_name, name, env);
                                     if qualify {
-                                        env.insert(
-                                            format!("{}.{}", use_decl.module.name, item.name.name),
+                                        env.insert_imported(
+                                            format!("{module_name}.{name}"),
                                             scheme.clone(),
+                                            module_name,
                                         );
                                     }
                                 }
@@ -226,6 +243,7 @@ impl TypeChecker {
                                 for member in members {
                                     if let Some(scheme) = exports.get(member) {
                                         env.insert(member.clone(), scheme.clone());
+                                        self.bind_domain_overloads(module_name, member, env);
                                         if qualify {
                                             env.insert(
                                                 format!("{}.{}", use_decl.module.name, member),
@@ -271,9 +289,43 @@ impl TypeChecker {
                         }
                     }
                 }
+                ModuleItem::EffectDecl(effect) => self.register_effect_operations(effect, env),
                 _ => {}
             }
         }
+        self.register_domain_overloads(module, env);
+    }
+
+    /// Operators declared by several `domain` blocks (one per carrier) keep every block's
+    /// signature, so uses pick the carrier that fits the operands.
+    fn register_domain_overloads(&mut self, module: &Module, env: &mut TypeEnv) {
+        let mut overloads: Vec<(String, Vec<Scheme>)> = Vec::new();
+        for item in &module.items {
+            let ModuleItem::DomainDecl(domain) = item else {
+                continue;
+            };
+            for domain_item in &domain.items {
+                let DomainItem::TypeSig(sig) = domain_item else {
+                    continue;
+                };
+                let scheme = self.scheme_from_sig(sig);
+                match overloads
+                    .iter_mut()
+                    .find(|(name, _)| *name == sig.name.name)
+                {
+                    Some((_, schemes)) => schemes.push(scheme),
+                    None => overloads.push((sig.name.name.clone(), vec![scheme])),
+                }
+            }
+        }
+        for (name, schemes) in overloads {
+            if schemes.len() < 2 {
+                continue;
+            }
+            env.insert_overloads(name.clone(), schemes.clone());
+            self.domain_overloads
+                .insert((module.name.name.clone(), name), schemes);
+        }
     }
 
     pub(super) fn check_module_defs(
@@ -283,28 +335,104 @@ impl TypeChecker {
         env: &mut TypeEnv,
     ) -> Vec<FileDiagnostic> {
         let mut diagnostics = Vec::new();
+        let clauses = module_clauses(module);
+        self.clause_lambda_spans = clauses
+            .iter()
+            .flatten()
+            .filter_map(|def| match &def.expr {
+                Expr::Lambda { span, .. } => Some(span.clone()),
+                _ => None,
+            })
+            .collect();
         for item in &module.items {
             match item {
                 ModuleItem::Def(def) => {
                     self.check_def(def, sigs, env, module, &mut diagnostics);
+                    if let Some(err) = self.unhandled_effect(def, sigs, env) {
+                        diagnostics.push(self.error_to_diag(module, err));
+                    }
                 }
                 ModuleItem::InstanceDecl(instance) => {
                     self.check_instance_decl(instance, env, module, &mut diagnostics);
                 }
+                ModuleItem::ClassDecl(class_decl) => {
+                    self.check_class_defaults(class_decl, module, &mut diagnostics);
+                }
                 ModuleItem::DomainDecl(domain) => {
+                    // Members are checked against the nearest signature above them, so an
+                    // operator can have one signature per operand type.
+                    let mut member_sigs = sigs.clone();
                     for domain_item in &domain.items {
                         match domain_item {
+                            DomainItem::TypeSig(sig) => {
+                                let scheme = self.scheme_from_sig(sig);
+                                member_sigs.insert(sig.name.name.clone(), scheme);
+                            }
                             DomainItem::Def(def) | DomainItem::LiteralDef(def) => {
-                                self.check_def(def, sigs, env, module, &mut diagnostics);
+                                self.check_def(def, &member_sigs, env, module, &mut diagnostics);
+                                // Checking rebinds the name to a single signature.
+                                self.bind_domain_overloads(&module.name.name, &def.name.name, env);
                             }
-                            DomainItem::TypeAlias(_) | DomainItem::TypeSig(_) => {}
+                            DomainItem::TypeAlias(_) => {}
                         }
                     }
                 }
                 _ => {}
             }
+            let current = match item {
+                ModuleItem::Def(def) => Some(def.name.name.as_str()),
+                _ => None,
+            };
+            self.report_holes(module, current, env, &mut diagnostics);
+        }
+        for group in &clauses {
+            self.check_def_clauses(group, env);
         }
+        self.clause_lambda_spans.clear();
         diagnostics.append(&mut self.extra_diagnostics);
         diagnostics
     }
 }
+
+/// Groups a module's definitions into functions. Repeated definitions of a name form one
+/// multi-clause function (`f = (Some x) => ...` then `f = None => ...`); domain members are
+/// grouped per signature, since each signature of a domain operator is a separate overload.
+fn module_clauses(module: &Module) -> Vec<Vec<&Def>> {
+    /// The domain and signature a member belongs to; `None` for top-level definitions.
+    type Owner<'a> = Option<(&'a str, usize)>;
+
+    fn push<'a>(groups: &mut Vec<(Owner<'a>, Vec<&'a Def>)>, owner: Owner<'a>, def: &'a Def) {
+        match groups.iter_mut().find(|(group_owner, group)| {
+            *group_owner == owner && group[0].name.name == def.name.name
+        }) {
+            Some((_, group)) => group.push(def),
+            None => groups.push((owner, vec![def])),
+        }
+    }
+    let mut groups = Vec::new();
+    let mut domain_sigs = 0;
+    let mut nearest_sig: HashMap<&str, usize> = HashMap::new();
+    for item in &module.items {
+        match item {
+            ModuleItem::Def(def) => push(&mut groups, None, def),
+            ModuleItem::DomainDecl(domain) => {
+                for domain_item in &domain.items {
+                    match domain_item {
+                        DomainItem::TypeSig(sig) => {
+                            domain_sigs += 1;
+                            nearest_sig.insert(sig.name.name.as_str(), domain_sigs);
+                        }
+                        DomainItem::Def(def) | DomainItem::LiteralDef(def) => {
+                            let sig = nearest_sig.get(def.name.name.as_str()).copied();
+                            let owner = (domain.name.name.as_str(), sig.unwrap_or(0));
+                            push(&mut groups, Some(owner), def);
+                        }
+                        DomainItem::TypeAlias(_) => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    groups.into_iter().map(|(_, group)| group).collect()
+}
@@ -16,11 +16,25 @@ fn expr_span(expr: &Expr) -> Span {
         | Expr::Match { span, .. }
         | Expr::If { span, .. }
         | Expr::Binary { span, .. }
+        | Expr::Handle { span, .. }
         | Expr::Block { span, .. } => span.clone(),
         Expr::Raw { span, .. } => span.clone(),
     }
 }
 
+fn type_expr_span(ty: &TypeExpr) -> Span {
+    match ty {
+        TypeExpr::Name(name) => name.span.clone(),
+        TypeExpr::And { span, .. }
+        | TypeExpr::Apply { span, .. }
+        | TypeExpr::Func { span, .. }
+        | TypeExpr::Record { span, .. }
+        | TypeExpr::Tuple { span, .. }
+        | TypeExpr::Star { span }
+        | TypeExpr::Unknown { span } => span.clone(),
+    }
+}
+
 fn pattern_span(pattern: &Pattern) -> Span {
     match pattern {
         Pattern::Wildcard(span) => span.clone(),
@@ -198,6 +212,15 @@ fn desugar_holes_inner(expr: Expr, is_root: bool) -> Expr {
             right: Box::new(desugar_holes_inner(*right, false)),
             span,
         },
+        Expr::Handle {
+            body,
+            handler,
+            span,
+        } => Expr::Handle {
+            body: Box::new(desugar_holes_inner(*body, false)),
+            handler: Box::new(desugar_holes_inner(*handler, false)),
+            span,
+        },
         Expr::Block { kind, items, span } => {
             let items = items
                 .into_iter()
@@ -286,6 +309,7 @@ fn contains_hole(expr: &Expr) -> bool {
             ..
         } => contains_hole(cond) || contains_hole(then_branch) || contains_hole(else_branch),
         Expr::Binary { left, right, .. } => contains_hole(left) || contains_hole(right),
+        Expr::Handle { body, handler, .. } => contains_hole(body) || contains_hole(handler),
         Expr::Block { items, .. } => items.iter().any(|item| match item {
             BlockItem::Bind { expr, .. } => contains_hole(expr),
             BlockItem::Let { expr, .. } => contains_hole(expr),
@@ -466,6 +490,15 @@ fn replace_holes_inner(expr: Expr, counter: &mut u32, params: &mut Vec<String>)
             right: Box::new(replace_holes_inner(*right, counter, params)),
             span,
         },
+        Expr::Handle {
+            body,
+            handler,
+            span,
+        } => Expr::Handle {
+            body: Box::new(replace_holes_inner(*body, counter, params)),
+            handler: Box::new(replace_holes_inner(*handler, counter, params)),
+            span,
+        },
         Expr::Block { kind, items, span } => Expr::Block {
             kind,
             items: items
@@ -511,3 +544,149 @@ fn replace_holes_inner(expr: Expr, counter: &mut u32, params: &mut Vec<String>)
         },
     }
 }
+
+/// How many bindings that fit a typed hole are listed.
+const MAX_HOLE_CANDIDATES: usize = 8;
+
+impl TypeChecker {
+    /// Reports the `?name` holes of the item just checked (`E3200`): the type expected there, the
+    /// local bindings in scope, and the bindings and class methods whose type fits. `current` is
+    /// the definition being checked, which is never suggested for its own holes.
+    fn report_holes(
+        &mut self,
+        module: &Module,
+        current: Option<&str>,
+        env: &TypeEnv,
+        diagnostics: &mut Vec<FileDiagnostic>,
+    ) {
+        for hole in std::mem::take(&mut self.holes) {
+            let ty = self.apply(hole.ty);
+            let mut printer = TypePrinter::new();
+            let expected = printer.print(&ty);
+            let label = |message: String| DiagnosticLabel {
+                message,
+                span: hole.name.span.clone(),
+            };
+
+            let mut locals: Vec<(&String, &Scheme)> = hole
+                .env
+                .iter()
+                .filter(|(name, _)| env.get(name).is_none())
+                .collect();
+            locals.sort_by(|a, b| a.0.cmp(b.0));
+            let mut labels = Vec::new();
+            for (name, scheme) in &locals {
+                let local_ty = self.apply(scheme.ty.clone());
+                labels.push(label(format!(
+                    "in scope: {name} : {}",
+                    printer.print(&local_ty)
+                )));
+            }
+
+            // Anything fits a hole whose type is still unconstrained.
+            if !matches!(ty, Type::Var(_)) {
+                let mut globals: Vec<(&String, &Scheme)> = env
+                    .iter()
+                    .filter(|(name, _)| {
+                        name.starts_with(|c: char| c.is_alphabetic())
+                            && Some(name.as_str()) != current
+                    })
+                    .collect();
+                globals.sort_by(|a, b| a.0.cmp(b.0));
+                let mut candidates = Vec::new();
+                for (name, scheme) in locals.into_iter().chain(globals) {
+                    if self.hole_fits(&ty, scheme, &hole.name.span) {
+                        let candidate_ty = self.apply(scheme.ty.clone());
+                        let candidate_ty = TypePrinter::new().print(&candidate_ty);
+                        candidates.push((name.clone(), candidate_ty));
+                    }
+                }
+                for (name, method_ty) in self.hole_fitting_methods(&ty, &hole.name.span) {
+                    if env.get(&name).is_none() {
+                        candidates.push((name, method_ty));
+                    }
+                }
+                for (name, candidate_ty) in candidates.into_iter().take(MAX_HOLE_CANDIDATES) {
+                    labels.push(label(format!("candidate: {name} : {candidate_ty}")));
+                }
+            }
+
+            diagnostics.push(FileDiagnostic {
+                path: module.path.clone(),
+                diagnostic: Diagnostic {
+                    code: "E3200".to_string(),
+                    severity: crate::diagnostics::DiagnosticSeverity::Error,
+                    message: format!("hole '{}' has type {expected}", hole.name.name),
+                    span: hole.name.span.clone(),
+                    labels,
+                },
+            });
+        }
+    }
+
+    /// Whether a binding of type `scheme` could fill a hole of type `hole`; leaves the
+    /// substitution untouched. Bindings whose type is still unconstrained fit anywhere and are
+    /// not suggested.
+    fn hole_fits(&mut self, hole: &Type, scheme: &Scheme, span: &Span) -> bool {
+        let saved = self.subst.clone();
+        let candidate = self.instantiate(scheme);
+        if matches!(self.apply(candidate.clone()), Type::Var(_)) {
+            self.subst = saved;
+            return false;
+        }
+        let fits = self.unify(candidate, hole.clone(), span.clone()).is_ok();
+        self.subst = saved;
+        fits
+    }
+
+    /// Class methods with an instance whose member type fits a hole of type `hole`, with their
+    /// class-level types.
+    fn hole_fitting_methods(&mut self, hole: &Type, span: &Span) -> Vec<(String, String)> {
+        let mut methods: Vec<(String, Vec<String>)> = self
+            .method_to_classes
+            .iter()
+            .map(|(name, classes)| (name.clone(), classes.clone()))
+            .collect();
+        methods.sort();
+        let saved = self.subst.clone();
+        let mut fitting = Vec::new();
+        for (method, classes) in methods {
+            let fits = classes.iter().find_map(|class_name| {
+                let class_info = self.classes.get(class_name)?.clone();
+                let member_ty_expr = class_info.members.get(&method)?.clone();
+                let instances: Vec<InstanceDeclInfo> = self
+                    .instances
+                    .iter()
+                    .filter(|instance| {
+                        instance.class_name == *class_name
+                            && instance.params.len() == class_info.params.len()
+                    })
+                    .cloned()
+                    .collect();
+                for instance in instances {
+                    self.subst = saved.clone();
+                    let mut ctx = TypeContext::new(&self.type_constructors);
+                    let mut inst_ctx = TypeContext::new(&self.type_constructors);
+                    let member_ty = self.type_from_expr(&member_ty_expr, &mut ctx);
+                    let printed = TypePrinter::new().print(&member_ty);
+                    let params_match = class_info.params.iter().zip(&instance.params).all(
+                        |(class_param, inst_param)| {
+                            let class_ty = self.type_from_expr(class_param, &mut ctx);
+                            let inst_ty = self.type_from_expr(inst_param, &mut inst_ctx);
+                            self.unify(class_ty, inst_ty, span.clone()).is_ok()
+                        },
+                    );
+                    if params_match && self.unify(member_ty, hole.clone(), span.clone()).is_ok() {
+                        return Some(printed);
+                    }
+                }
+                None
+            });
+            if let Some(method_ty) = fits {
+                fitting.push((method, method_ty));
+            }
+        }
+        self.subst = saved;
+        fitting
+    }
+}
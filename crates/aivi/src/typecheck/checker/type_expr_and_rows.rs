@@ -16,25 +16,29 @@ impl TypeChecker {
                 // v0.1: `A with B` is record/type composition. For now we only support composing records;
                 // other compositions fall back to an unconstrained fresh type variable.
                 let mut merged = BTreeMap::new();
-                let mut open = true;
+                let mut row = Row::Open;
                 for item in items {
                     let item_ty = self.type_from_expr(item, ctx);
                     let item_ty = self.expand_alias(item_ty);
                     let Type::Record {
                         fields: item_fields,
-                        open: item_open,
+                        row: item_row,
                     } = item_ty
                     else {
                         return self.fresh_var();
                     };
-                    open &= item_open;
+                    row = match (row, item_row) {
+                        (Row::Var(var), _) | (_, Row::Var(var)) => Row::Var(var),
+                        (Row::Open, Row::Open) => Row::Open,
+                        _ => Row::Closed,
+                    };
                     for (name, ty) in item_fields {
                         merged.entry(name).or_insert(ty);
                     }
                 }
                 Type::Record {
                     fields: merged,
-                    open,
+                    row,
                 }
             }
             TypeExpr::Apply { base, args, .. } => {
@@ -99,15 +103,28 @@ impl TypeChecker {
                 }
                 result_ty
             }
-            TypeExpr::Record { fields, .. } => {
+            TypeExpr::Record { fields, rest, .. } => {
                 let mut field_map = BTreeMap::new();
                 for (name, ty) in fields {
                     let field_ty = self.type_from_expr(ty, ctx);
                     field_map.insert(name.name.clone(), field_ty);
                 }
+                // `{ name: Text | r }` names the remaining fields; without a tail the record
+                // stays open to extra fields without tracking them.
+                let row = match rest {
+                    Some(rest) => Row::Var(match ctx.type_vars.get(&rest.name) {
+                        Some(var) => *var,
+                        None => {
+                            let var = self.fresh_var_id();
+                            ctx.type_vars.insert(rest.name.clone(), var);
+                            var
+                        }
+                    }),
+                    None => Row::Open,
+                };
                 Type::Record {
                     fields: field_map,
-                    open: true,
+                    row,
                 }
             }
             TypeExpr::Tuple { items, .. } => {
@@ -134,6 +151,7 @@ impl TypeChecker {
             "Required" => self.row_required(args, ctx),
             "Rename" => self.row_rename(args, ctx),
             "Defaulted" => self.row_defaulted(args, ctx),
+            "Effect" => self.effect_with_row(args, ctx),
             _ => None,
         }
     }
@@ -150,6 +168,13 @@ impl TypeChecker {
                     if Self::is_row_op_name(&base_name.name) {
                         self.validate_row_op(base_name, args, errors);
                     }
+                    if base_name.name == "Effect" && args.len() == 3 {
+                        self.validate_effect_labels(&args[0], errors);
+                        for arg in &args[1..] {
+                            self.validate_type_expr(arg, errors);
+                        }
+                        return;
+                    }
                 }
                 self.validate_type_expr(base, errors);
                 for arg in args {
@@ -193,7 +218,7 @@ impl TypeChecker {
         }
 
         let mut ctx = TypeContext::new(&self.type_constructors);
-        let Some((source_fields, _open)) = self.record_from_type_expr(&args[1], &mut ctx) else {
+        let Some((source_fields, _row)) = self.record_from_type_expr(&args[1], &mut ctx) else {
             return;
         };
         let source_names: HashSet<String> = source_fields.keys().cloned().collect();
@@ -295,14 +320,19 @@ impl TypeChecker {
             return None;
         }
         let fields = self.row_fields_from_expr(&args[0]);
-        let (source_fields, open) = self.record_from_type_expr(&args[1], ctx)?;
+        let (source_fields, row) = self.record_from_type_expr(&args[1], ctx)?;
         let mut out = BTreeMap::new();
         for name in fields {
             if let Some(ty) = source_fields.get(&name) {
                 out.insert(name, ty.clone());
             }
         }
-        Some(Type::Record { fields: out, open })
+        // Only the picked fields survive, so a source row variable must not bring the rest back.
+        let row = match row {
+            Row::Var(_) => Row::Open,
+            row => row,
+        };
+        Some(Type::Record { fields: out, row })
     }
 
     fn row_omit(&mut self, args: &[TypeExpr], ctx: &mut TypeContext) -> Option<Type> {
@@ -311,14 +341,14 @@ impl TypeChecker {
         }
         let fields = self.row_fields_from_expr(&args[0]);
         let omit: HashSet<String> = fields.into_iter().collect();
-        let (source_fields, open) = self.record_from_type_expr(&args[1], ctx)?;
+        let (source_fields, row) = self.record_from_type_expr(&args[1], ctx)?;
         let mut out = BTreeMap::new();
         for (name, ty) in source_fields {
             if !omit.contains(&name) {
                 out.insert(name, ty);
             }
         }
-        Some(Type::Record { fields: out, open })
+        Some(Type::Record { fields: out, row })
     }
 
     fn row_optional(&mut self, args: &[TypeExpr], ctx: &mut TypeContext) -> Option<Type> {
@@ -326,7 +356,7 @@ impl TypeChecker {
             return None;
         }
         let fields = self.row_fields_from_expr(&args[0]);
-        let (mut source_fields, open) = self.record_from_type_expr(&args[1], ctx)?;
+        let (mut source_fields, row) = self.record_from_type_expr(&args[1], ctx)?;
         for name in fields {
             if let Some(ty) = source_fields.get_mut(&name) {
                 *ty = self.wrap_option_type(ty.clone());
@@ -334,7 +364,7 @@ impl TypeChecker {
         }
         Some(Type::Record {
             fields: source_fields,
-            open,
+            row,
         })
     }
 
@@ -343,7 +373,7 @@ impl TypeChecker {
             return None;
         }
         let fields = self.row_fields_from_expr(&args[0]);
-        let (mut source_fields, open) = self.record_from_type_expr(&args[1], ctx)?;
+        let (mut source_fields, row) = self.record_from_type_expr(&args[1], ctx)?;
         for name in fields {
             if let Some(ty) = source_fields.get_mut(&name) {
                 *ty = self.unwrap_option_type(ty.clone());
@@ -351,7 +381,7 @@ impl TypeChecker {
         }
         Some(Type::Record {
             fields: source_fields,
-            open,
+            row,
         })
     }
 
@@ -360,7 +390,7 @@ impl TypeChecker {
             return None;
         }
         let rename_map = self.row_rename_map_from_expr(&args[0]);
-        let (source_fields, open) = self.record_from_type_expr(&args[1], ctx)?;
+        let (source_fields, row) = self.record_from_type_expr(&args[1], ctx)?;
         let mut out = BTreeMap::new();
         for (name, ty) in source_fields {
             let new_name = rename_map.get(&name).cloned().unwrap_or(name);
@@ -369,7 +399,7 @@ impl TypeChecker {
             }
             out.insert(new_name, ty);
         }
-        Some(Type::Record { fields: out, open })
+        Some(Type::Record { fields: out, row })
     }
 
     fn row_defaulted(&mut self, args: &[TypeExpr], ctx: &mut TypeContext) -> Option<Type> {
@@ -380,7 +410,7 @@ impl TypeChecker {
         if fields.is_empty() {
             fields = self.row_fields_from_record_expr(&args[0]);
         }
-        let (mut source_fields, open) = self.record_from_type_expr(&args[1], ctx)?;
+        let (mut source_fields, row) = self.record_from_type_expr(&args[1], ctx)?;
         for name in fields {
             if let Some(ty) = source_fields.get_mut(&name) {
                 *ty = self.wrap_option_type(ty.clone());
@@ -388,7 +418,7 @@ impl TypeChecker {
         }
         Some(Type::Record {
             fields: source_fields,
-            open,
+            row,
         })
     }
 
@@ -396,11 +426,11 @@ impl TypeChecker {
         &mut self,
         expr: &TypeExpr,
         ctx: &mut TypeContext,
-    ) -> Option<(BTreeMap<String, Type>, bool)> {
+    ) -> Option<(BTreeMap<String, Type>, Row)> {
         let ty = self.type_from_expr(expr, ctx);
         let ty = self.expand_alias(ty);
         match ty {
-            Type::Record { fields, open } => Some((fields, open)),
+            Type::Record { fields, row } => Some((fields, row)),
             _ => None,
         }
     }
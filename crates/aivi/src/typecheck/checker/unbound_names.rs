@@ -38,7 +38,7 @@ fn collect_unbound_names(expr: &Expr, env: &TypeEnv) -> HashSet<String> {
     ) {
         match expr {
             Expr::Ident(name) => {
-                if name.name == "_" {
+                if name.name == "_" || name.is_typed_hole() {
                     return;
                 }
                 let reserved = matches!(name.name.as_str(), "key" | "value");
@@ -128,9 +128,19 @@ fn collect_unbound_names(expr: &Expr, env: &TypeEnv) -> HashSet<String> {
                 collect_expr(left, env, bound, out);
                 collect_expr(right, env, bound, out);
             }
+            Expr::Handle { body, handler, .. } => {
+                collect_expr(body, env, bound, out);
+                collect_expr(handler, env, bound, out);
+            }
             Expr::Block { items, .. } => {
                 let before = bound.len();
-                for item in items {
+                let groups = recursive_binding_groups(items);
+                for (idx, item) in items.iter().enumerate() {
+                    if let Some(group) = groups.iter().find(|group| group.start == idx) {
+                        for (name, _) in items[group.clone()].iter().filter_map(function_binding) {
+                            bound.push(name.name.clone());
+                        }
+                    }
                     match item {
                         BlockItem::Bind { pattern, expr, .. }
                         | BlockItem::Let { pattern, expr, .. } => {
@@ -330,6 +340,19 @@ fn rewrite_implicit_field_vars(
             right: Box::new(rewrite_implicit_field_vars(*right, implicit_param, unbound)),
             span,
         },
+        Expr::Handle {
+            body,
+            handler,
+            span,
+        } => Expr::Handle {
+            body: Box::new(rewrite_implicit_field_vars(*body, implicit_param, unbound)),
+            handler: Box::new(rewrite_implicit_field_vars(
+                *handler,
+                implicit_param,
+                unbound,
+            )),
+            span,
+        },
         Expr::Block { kind, items, span } => Expr::Block {
             kind,
             items: items
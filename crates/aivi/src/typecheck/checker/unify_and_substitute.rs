@@ -11,6 +11,11 @@ impl TypeChecker {
         match (left, right) {
             (Type::Var(a), Type::Var(b)) if a == b => Ok(()),
             (Type::Var(var), ty) | (ty, Type::Var(var)) => self.bind_var(var, ty, span),
+            (Type::Con(name_a, args_a), Type::Con(name_b, args_b))
+                if name_a == "Effect" && name_b == "Effect" =>
+            {
+                self.unify_effects(args_a, args_b, span)
+            }
             (Type::Con(name_a, args_a), Type::Con(name_b, args_b)) => {
                 if name_a != name_b || args_a.len() != args_b.len() {
                     return Err(TypeError {
@@ -41,6 +46,9 @@ impl TypeChecker {
                 Ok(())
             }
             (Type::App(base_a, args_a), Type::Con(name_b, args_b)) => {
+                // An effect row is not a type argument: `m a` matches `Effect e a` whatever
+                // effects it performs.
+                let args_b = strip_effect_row(&name_b, args_b);
                 // Allow unifying a type application with a fully-applied constructor by splitting
                 // constructor args into a "prefix" (applied to the base) and a "suffix"
                 // corresponding to this application.
@@ -66,6 +74,7 @@ impl TypeChecker {
                 Ok(())
             }
             (Type::Con(name_a, args_a), Type::App(base_b, args_b)) => {
+                let args_a = strip_effect_row(&name_a, args_a);
                 if args_b.len() > args_a.len() {
                     return Err(TypeError {
                         span,
@@ -108,58 +117,13 @@ impl TypeChecker {
             (
                 Type::Record {
                     fields: a,
-                    open: open_a,
+                    row: row_a,
                 },
                 Type::Record {
                     fields: b,
-                    open: open_b,
+                    row: row_b,
                 },
-            ) => {
-                let mut all_fields: HashSet<String> = a.keys().cloned().collect();
-                all_fields.extend(b.keys().cloned());
-
-                for field in &all_fields {
-                    match (a.get(field), b.get(field)) {
-                        (Some(ta), Some(tb)) => {
-                            self.unify(ta.clone(), tb.clone(), span.clone())?;
-                        }
-                        (Some(_), None) => {
-                            if !open_b {
-                                return Err(TypeError {
-                                    span: span.clone(),
-                                    message: format!("missing field '{}'", field),
-                                    expected: Some(Box::new(Type::Record {
-                                        fields: a.clone(),
-                                        open: open_a,
-                                    })),
-                                    found: Some(Box::new(Type::Record {
-                                        fields: b.clone(),
-                                        open: open_b,
-                                    })),
-                                });
-                            }
-                        }
-                        (None, Some(_)) => {
-                            if !open_a {
-                                return Err(TypeError {
-                                    span: span.clone(),
-                                    message: format!("missing field '{}'", field),
-                                    expected: Some(Box::new(Type::Record {
-                                        fields: a.clone(),
-                                        open: open_a,
-                                    })),
-                                    found: Some(Box::new(Type::Record {
-                                        fields: b.clone(),
-                                        open: open_b,
-                                    })),
-                                });
-                            }
-                        }
-                        (None, None) => {}
-                    }
-                }
-                Ok(())
-            }
+            ) => self.unify_records(a, row_a, b, row_b, span),
             (a, b) => Err(TypeError {
                 span,
                 message: "type mismatch".to_string(),
@@ -169,6 +133,83 @@ impl TypeChecker {
         }
     }
 
+    /// Unifies two record types field by field, then settles the labels each side lacks against
+    /// the other side's row: a row variable is bound to the missing fields plus a fresh shared
+    /// tail, an open row absorbs them, and a closed row reports them.
+    fn unify_records(
+        &mut self,
+        a: BTreeMap<String, Type>,
+        row_a: Row,
+        b: BTreeMap<String, Type>,
+        row_b: Row,
+        span: Span,
+    ) -> Result<(), TypeError> {
+        let only_a: BTreeMap<String, Type> = a
+            .iter()
+            .filter(|(name, _)| !b.contains_key(*name))
+            .map(|(name, ty)| (name.clone(), ty.clone()))
+            .collect();
+        let only_b: BTreeMap<String, Type> = b
+            .iter()
+            .filter(|(name, _)| !a.contains_key(*name))
+            .map(|(name, ty)| (name.clone(), ty.clone()))
+            .collect();
+        let same_row = matches!((&row_a, &row_b), (Row::Var(x), Row::Var(y)) if x == y);
+        let mut problems = Vec::new();
+        if !only_a.is_empty() && (matches!(row_b, Row::Closed) || same_row) {
+            problems.push(describe_labels("missing", &only_a));
+        }
+        if !only_b.is_empty() && (matches!(row_a, Row::Closed) || same_row) {
+            problems.push(describe_labels("unexpected", &only_b));
+        }
+        if !problems.is_empty() {
+            return Err(TypeError {
+                span,
+                message: problems.join("; "),
+                expected: Some(Box::new(Type::Record {
+                    fields: a,
+                    row: row_a,
+                })),
+                found: Some(Box::new(Type::Record {
+                    fields: b,
+                    row: row_b,
+                })),
+            });
+        }
+
+        for (name, ta) in &a {
+            if let Some(tb) = b.get(name) {
+                self.unify(ta.clone(), tb.clone(), span.clone())?;
+            }
+        }
+
+        match (row_a, row_b) {
+            (Row::Var(x), Row::Var(y)) if x == y => Ok(()),
+            (Row::Var(x), Row::Var(y)) => {
+                let tail = Row::Var(self.fresh_var_id());
+                self.bind_row(x, only_b, tail.clone(), span.clone())?;
+                self.bind_row(y, only_a, tail, span)
+            }
+            (Row::Var(x), tail) => self.bind_row(x, only_b, tail, span),
+            (tail, Row::Var(y)) => self.bind_row(y, only_a, tail, span),
+            _ => Ok(()),
+        }
+    }
+
+    fn bind_row(
+        &mut self,
+        var: TypeVarId,
+        fields: BTreeMap<String, Type>,
+        tail: Row,
+        span: Span,
+    ) -> Result<(), TypeError> {
+        let ty = match tail {
+            Row::Var(tail) if fields.is_empty() => Type::Var(tail),
+            row => Type::Record { fields, row },
+        };
+        self.bind_var(var, ty, span)
+    }
+
     fn bind_var(&mut self, var: TypeVarId, ty: Type, span: Span) -> Result<(), TypeError> {
         if let Type::Var(other) = &ty {
             if *other == var {
@@ -196,7 +237,10 @@ impl TypeChecker {
             }
             Type::Func(a, b) => self.occurs(var, &a) || self.occurs(var, &b),
             Type::Tuple(items) => items.iter().any(|item| self.occurs(var, item)),
-            Type::Record { fields, .. } => fields.values().any(|field| self.occurs(var, field)),
+            Type::Record { fields, row } => {
+                matches!(row, Row::Var(id) if id == var)
+                    || fields.values().any(|field| self.occurs(var, field))
+            }
         }
     }
 
@@ -234,8 +278,13 @@ impl TypeChecker {
                 vars
             }
             Type::Tuple(items) => items.iter().flat_map(|item| self.free_vars(item)).collect(),
-            Type::Record { fields, .. } => {
-                fields.values().flat_map(|f| self.free_vars(f)).collect()
+            Type::Record { fields, row } => {
+                let mut vars: HashSet<TypeVarId> =
+                    fields.values().flat_map(|f| self.free_vars(f)).collect();
+                if let Row::Var(id) = row {
+                    vars.insert(id);
+                }
+                vars
             }
         }
     }
@@ -273,13 +322,25 @@ impl TypeChecker {
                     .map(|item| Self::substitute(item, mapping))
                     .collect(),
             ),
-            Type::Record { fields, open } => Type::Record {
-                fields: fields
+            Type::Record { fields, row } => {
+                let fields = fields
                     .iter()
                     .map(|(k, v)| (k.clone(), Self::substitute(v, mapping)))
-                    .collect(),
-                open: *open,
-            },
+                    .collect();
+                match row {
+                    Row::Var(id) => match mapping.get(id) {
+                        Some(rest) => splice_row(fields, rest.clone()),
+                        None => Type::Record {
+                            fields,
+                            row: Row::Var(*id),
+                        },
+                    },
+                    row => Type::Record {
+                        fields,
+                        row: row.clone(),
+                    },
+                }
+            }
         }
     }
 
@@ -305,13 +366,19 @@ impl TypeChecker {
             Type::Tuple(items) => {
                 Type::Tuple(items.into_iter().map(|item| self.apply(item)).collect())
             }
-            Type::Record { fields, open } => Type::Record {
-                fields: fields
+            Type::Record { fields, row } => {
+                let fields = fields
                     .into_iter()
                     .map(|(k, v)| (k, self.apply(v)))
-                    .collect(),
-                open,
-            },
+                    .collect();
+                match row {
+                    Row::Var(id) => {
+                        let rest = self.apply(Type::Var(id));
+                        splice_row(fields, rest)
+                    }
+                    row => Type::Record { fields, row },
+                }
+            }
         }
     }
 
@@ -328,3 +395,38 @@ impl TypeChecker {
         ty
     }
 }
+
+/// Extends `fields` with whatever a row variable stands for: another record's fields and row, or
+/// another row variable.
+fn splice_row(mut fields: BTreeMap<String, Type>, rest: Type) -> Type {
+    match rest {
+        Type::Var(id) => Type::Record {
+            fields,
+            row: Row::Var(id),
+        },
+        Type::Record {
+            fields: rest_fields,
+            row,
+        } => {
+            for (name, ty) in rest_fields {
+                fields.entry(name).or_insert(ty);
+            }
+            Type::Record { fields, row }
+        }
+        _ => Type::Record {
+            fields,
+            row: Row::Open,
+        },
+    }
+}
+
+/// `missing field 'a'` / `unexpected fields 'b', 'c'`.
+fn describe_labels(what: &str, fields: &BTreeMap<String, Type>) -> String {
+    let labels = fields
+        .keys()
+        .map(|name| format!("'{name}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let noun = if fields.len() == 1 { "field" } else { "fields" };
+    format!("{what} {noun} {labels}")
+}
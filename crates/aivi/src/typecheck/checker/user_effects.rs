@@ -0,0 +1,336 @@
+impl TypeChecker {
+    /// Gives each operation of `effect` the scheme `params -> Effect e R { Label | r }`.
+    fn register_effect_operations(&mut self, effect: &EffectDecl, env: &mut TypeEnv) {
+        for op in &effect.operations {
+            let mut ctx = TypeContext::new(&self.type_constructors);
+            let mut params = Vec::new();
+            let mut result = self.type_from_expr(&op.ty, &mut ctx);
+            while let Type::Func(param, rest) = result {
+                params.push(*param);
+                result = *rest;
+            }
+            let err = self.fresh_var_id();
+            let tail = self.fresh_var_id();
+            let row = effect_row([effect.name.name.clone()], Row::Var(tail));
+            let mut ty = Type::con("Effect").app(vec![Type::Var(err), result, row]);
+            for param in params.into_iter().rev() {
+                ty = Type::Func(Box::new(param), Box::new(ty));
+            }
+            let mut vars: Vec<TypeVarId> = ctx.type_vars.values().cloned().collect();
+            vars.extend([err, tail]);
+            let scheme = Scheme { vars, ty };
+            env.insert(op.name.name.clone(), scheme.clone());
+            self.effect_ops
+                .insert(op.name.name.clone(), (effect.name.name.clone(), scheme));
+        }
+    }
+
+    /// Lowers `Effect Log E A` / `Effect (Log, Clock) E A` to an effect with a closed row.
+    fn effect_with_row(&mut self, args: &[TypeExpr], ctx: &mut TypeContext) -> Option<Type> {
+        let [labels, err, value] = args else {
+            return None;
+        };
+        let row = effect_row(self.row_fields_from_expr(labels), Row::Closed);
+        let err = self.type_from_expr(err, ctx);
+        let value = self.type_from_expr(value, ctx);
+        Some(Type::con("Effect").app(vec![err, value, row]))
+    }
+
+    fn validate_effect_labels(&mut self, labels: &TypeExpr, errors: &mut Vec<TypeError>) {
+        let names = match labels {
+            TypeExpr::Tuple { items, .. } => items.iter().collect(),
+            other => vec![other],
+        };
+        for name in names {
+            let TypeExpr::Name(name) = name else {
+                errors.push(TypeError {
+                    span: type_expr_span(name),
+                    message: "expected an effect name".to_string(),
+                    expected: None,
+                    found: None,
+                });
+                continue;
+            };
+            if !self.global_effects.contains_key(&name.name) {
+                errors.push(TypeError {
+                    span: name.span.clone(),
+                    message: format!("unknown effect '{}'", name.name),
+                    expected: None,
+                    found: None,
+                });
+            }
+        }
+    }
+
+    /// Unifies two `Effect` types. Rows are compared only when both sides track one; an
+    /// `Effect E A` without a row says nothing about the effects performed.
+    fn unify_effects(
+        &mut self,
+        args_a: Vec<Type>,
+        args_b: Vec<Type>,
+        span: Span,
+    ) -> Result<(), TypeError> {
+        let arity_ok = args_a.len() == args_b.len()
+            || (args_a.len().min(args_b.len()) == 2 && args_a.len().max(args_b.len()) == 3);
+        if !arity_ok {
+            return Err(TypeError {
+                span,
+                message: "type mismatch".to_string(),
+                expected: Some(Box::new(Type::Con("Effect".to_string(), args_a))),
+                found: Some(Box::new(Type::Con("Effect".to_string(), args_b))),
+            });
+        }
+        for (a, b) in args_a.iter().zip(args_b.iter()).take(2) {
+            self.unify(a.clone(), b.clone(), span.clone())?;
+        }
+        if args_a.len() != 3 || args_b.len() != 3 {
+            return Ok(());
+        }
+        if self
+            .unify(args_a[2].clone(), args_b[2].clone(), span.clone())
+            .is_ok()
+        {
+            return Ok(());
+        }
+        let left = effect_labels(&self.apply(args_a[2].clone()));
+        let right = effect_labels(&self.apply(args_b[2].clone()));
+        let differing: Vec<String> = left
+            .symmetric_difference(&right)
+            .map(|label| format!("'{label}'"))
+            .collect();
+        let expected = self.apply(Type::Con("Effect".to_string(), args_a));
+        let found = self.apply(Type::Con("Effect".to_string(), args_b));
+        Err(TypeError {
+            span,
+            message: format!("effects do not match: {}", differing.join(", ")),
+            expected: Some(Box::new(expected)),
+            found: Some(Box::new(found)),
+        })
+    }
+
+    /// The effect row of an `Effect` type, when it tracks one.
+    fn effect_row_of(&mut self, ty: &Type) -> Option<Type> {
+        let ty = self.apply(ty.clone());
+        match self.expand_alias(ty) {
+            Type::Con(name, mut args) if name == "Effect" && args.len() == 3 => args.pop(),
+            _ => None,
+        }
+    }
+
+    /// Adds the effects of `row` to the row `acc` accumulated for a block or handler.
+    fn join_effect_row(&mut self, acc: &Type, row: Type, span: Span) -> Result<(), TypeError> {
+        let row = match self.apply(row) {
+            Type::Record {
+                fields,
+                row: Row::Closed | Row::Open,
+            } => Type::Record {
+                fields,
+                row: Row::Var(self.fresh_var_id()),
+            },
+            other => other,
+        };
+        self.unify(acc.clone(), row, span)
+    }
+
+    fn join_effect_row_of(&mut self, acc: &Type, ty: &Type, span: Span) -> Result<(), TypeError> {
+        match self.effect_row_of(ty) {
+            Some(row) => self.join_effect_row(acc, row, span),
+            None => Ok(()),
+        }
+    }
+
+    fn infer_handle(
+        &mut self,
+        body: &Expr,
+        handler: &Expr,
+        span: &Span,
+        env: &mut TypeEnv,
+    ) -> Result<Type, TypeError> {
+        let body_ty = self.infer_expr(body, env)?;
+        let err_ty = self.fresh_var();
+        let value_ty = self.fresh_var();
+        let untracked = Type::con("Effect").app(vec![err_ty.clone(), value_ty.clone()]);
+        self.unify_with_span(body_ty.clone(), untracked, expr_span(body))?;
+        let body_row = self.effect_row_of(&body_ty);
+
+        let handler_ty = self.infer_expr(handler, env)?;
+        let handler_ty = self.apply(handler_ty);
+        let Type::Record { fields, .. } = self.expand_alias(handler_ty.clone()) else {
+            return Err(TypeError {
+                span: expr_span(handler),
+                message: "a handler must be a record of effect operation clauses".to_string(),
+                expected: None,
+                found: Some(Box::new(handler_ty)),
+            });
+        };
+
+        let mut handled: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut clause_rows = Vec::new();
+        for (name, clause_ty) in fields {
+            let field_span = handler_field_span(handler, &name);
+            let Some((effect, scheme)) = self.effect_ops.get(&name).cloned() else {
+                return Err(TypeError {
+                    span: field_span,
+                    message: format!("'{name}' is not an effect operation"),
+                    expected: None,
+                    found: None,
+                });
+            };
+            handled.entry(effect).or_default().push(name);
+
+            // A clause takes the operation's arguments and produces its result as an effect
+            // that may fail like the handled body.
+            let mut params = Vec::new();
+            let mut result = self.instantiate(&scheme);
+            while let Type::Func(param, rest) = result {
+                params.push(*param);
+                result = *rest;
+            }
+            let Type::Con(_, mut args) = result else {
+                continue;
+            };
+            args.truncate(2);
+            let clause_row = self.fresh_var();
+            let mut expected = Type::con("Effect").app(vec![
+                err_ty.clone(),
+                args.pop().unwrap_or_else(|| Type::con("Unit")),
+                clause_row.clone(),
+            ]);
+            for param in params.into_iter().rev() {
+                expected = Type::Func(Box::new(param), Box::new(expected));
+            }
+            self.unify_with_span(clause_ty, expected, field_span)?;
+            clause_rows.push(clause_row);
+        }
+
+        for (effect, ops) in &handled {
+            let declared = self.global_effects.get(effect).cloned().unwrap_or_default();
+            if let Some(missing) = declared.iter().find(|op| !ops.contains(op)) {
+                return Err(TypeError {
+                    span: expr_span(handler),
+                    message: format!("handler for '{effect}' is missing operation '{missing}'"),
+                    expected: None,
+                    found: None,
+                });
+            }
+        }
+
+        // Handled effects leave the row; whatever the clauses perform joins it.
+        let result_row = match body_row.map(|row| self.apply(row)) {
+            Some(Type::Record { mut fields, row }) => {
+                fields.retain(|label, _| !handled.contains_key(label));
+                Type::Record { fields, row }
+            }
+            Some(other) => other,
+            None => self.fresh_var(),
+        };
+        for clause_row in clause_rows {
+            self.join_effect_row(&result_row, clause_row, span.clone())?;
+        }
+        Ok(Type::con("Effect").app(vec![err_ty, value_ty, result_row]))
+    }
+
+    /// `main` runs without enclosing handlers, so every effect it performs must be handled. So
+    /// must the effects of a definition whose signature returns an untracked `Effect E A`:
+    /// callers could not see them.
+    fn unhandled_effect(
+        &mut self,
+        def: &Def,
+        sigs: &HashMap<String, Scheme>,
+        env: &TypeEnv,
+    ) -> Option<TypeError> {
+        if self.global_effects.is_empty() {
+            return None;
+        }
+        let is_main = def.name.name == "main";
+        let params = if is_main {
+            0
+        } else {
+            let scheme = sigs.get(&def.name.name)?;
+            self.untracked_effect_params(scheme.ty.clone())?
+        };
+        let expr = crate::surface::desugar_effect_sugars(desugar_holes(def.expr.clone()));
+        let snapshot = self.subst.clone();
+        let mut ty = self.infer_expr(&expr, &mut env.clone()).ok();
+        for _ in 0..params {
+            ty = match ty.map(|ty| self.apply(ty)) {
+                Some(Type::Func(_, result)) => Some(*result),
+                _ => None,
+            };
+        }
+        let row = ty.and_then(|ty| self.effect_row_of(&ty));
+        let labels = row.map(|row| effect_labels(&self.apply(row)));
+        self.subst = snapshot;
+        let label = labels?.into_iter().next()?;
+        let name = &def.name.name;
+        let message = if is_main {
+            format!("unhandled effect '{label}' in 'main'")
+        } else {
+            format!(
+                "unhandled effect '{label}' in '{name}' (`Effect E A` performs no effects; write `Effect {label} E A`)"
+            )
+        };
+        Some(TypeError {
+            span: def.name.span.clone(),
+            message,
+            expected: None,
+            found: None,
+        })
+    }
+
+    /// The number of arguments before `ty` returns an `Effect E A` without an effect row.
+    fn untracked_effect_params(&mut self, mut ty: Type) -> Option<usize> {
+        let mut params = 0;
+        loop {
+            match self.expand_alias(ty) {
+                Type::Func(_, result) => {
+                    params += 1;
+                    ty = *result;
+                }
+                Type::Con(name, args) if name == "Effect" && args.len() == 2 => {
+                    return Some(params)
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// An effect row: effect names as the labels of a record of `Unit`s.
+fn effect_row(labels: impl IntoIterator<Item = String>, row: Row) -> Type {
+    Type::Record {
+        fields: labels
+            .into_iter()
+            .map(|label| (label, Type::con("Unit")))
+            .collect(),
+        row,
+    }
+}
+
+fn effect_labels(row: &Type) -> BTreeSet<String> {
+    match row {
+        Type::Record { fields, .. } => fields.keys().cloned().collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+/// Drops the row of a tracked `Effect` so it lines up with `m a` applications.
+fn strip_effect_row(name: &str, mut args: Vec<Type>) -> Vec<Type> {
+    if name == "Effect" && args.len() == 3 {
+        args.pop();
+    }
+    args
+}
+
+fn handler_field_span(handler: &Expr, name: &str) -> Span {
+    if let Expr::Record { fields, .. } = handler {
+        for field in fields {
+            if let [PathSegment::Field(field_name)] = field.path.as_slice() {
+                if field_name.name == name {
+                    return field_name.span.clone();
+                }
+            }
+        }
+    }
+    expr_span(handler)
+}
@@ -0,0 +1,555 @@
+//! Pattern-matrix exhaustiveness and redundancy checking.
+//!
+//! This follows Maranget's usefulness algorithm ("Warnings for pattern matching", JFP 2007): a
+//! pattern vector is *useful* against a matrix of earlier rows if some value matches it but no
+//! earlier row. A match is exhaustive when the all-wildcard row is not useful, and an arm is
+//! redundant when its own row is not useful against the arms above it. When usefulness holds, the
+//! algorithm also produces example values (witnesses), which become the `missing:` list of
+//! `E3100`.
+//!
+//! Each column carries the name of its type when the checker knows it. The constructors of that
+//! type form the column's signature; ADT constructors come from the type declarations of every
+//! module, so imported types are covered too. Without a known type, the constructors in the column
+//! pick the family, and only a single matching family counts. Lists have `[]` and `[x, ...xs]`,
+//! and tuples and records have a single constructor. A column of unknown constructors is treated
+//! as complete so that missing type information never causes a false positive. Number, text and
+//! other literals have infinitely many values, so they are only exhaustive together with a
+//! wildcard.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::surface::{Literal, Pattern, RecordPatternField, TypeDecl, TypeExpr};
+
+/// Witnesses beyond this many are dropped; the diagnostic only needs a few examples.
+const MAX_WITNESSES: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Head {
+    Ctor(String),
+    Tuple(usize),
+    /// Field names, sorted. Every record pattern in a column is widened to the union of the
+    /// fields mentioned anywhere in that column.
+    Record(Vec<String>),
+    Nil,
+    Cons,
+    Literal(String),
+}
+
+#[derive(Debug, Clone)]
+enum Pat {
+    Wild,
+    Con(Head, Vec<Pat>),
+    /// A record pattern before it is widened to its column's field set.
+    Record(Vec<(String, Pat)>),
+}
+
+/// ADT constructors with the type names of their arguments, keyed by type name. An argument
+/// whose type has no name to look up (a type parameter, function, record, ...) is `None`.
+pub(super) type ConstructorSets = HashMap<String, Vec<(String, Vec<Option<String>>)>>;
+
+/// The constructors of `type_decl`, as registered in `ConstructorSets`.
+pub(super) fn constructor_set(type_decl: &TypeDecl) -> Vec<(String, Vec<Option<String>>)> {
+    let type_name = |ty: &TypeExpr| {
+        let mut head = ty;
+        while let TypeExpr::Apply { base, .. } = head {
+            head = base;
+        }
+        match head {
+            TypeExpr::Name(name)
+                if !type_decl.params.iter().any(|param| param.name == name.name) =>
+            {
+                Some(name.name.clone())
+            }
+            _ => None,
+        }
+    };
+    type_decl
+        .constructors
+        .iter()
+        .map(|ctor| {
+            (
+                ctor.name.name.clone(),
+                ctor.args.iter().map(type_name).collect(),
+            )
+        })
+        .collect()
+}
+
+/// `Bool`, `Option` and `Result`, which have no declarations.
+pub(super) fn builtin_constructor_sets() -> ConstructorSets {
+    let set = |ctors: &[(&str, usize)]| {
+        ctors
+            .iter()
+            .map(|(name, arity)| (name.to_string(), vec![None; *arity]))
+            .collect()
+    };
+    HashMap::from([
+        ("Bool".to_string(), set(&[("True", 0), ("False", 0)])),
+        ("Option".to_string(), set(&[("None", 0), ("Some", 1)])),
+        ("Result".to_string(), set(&[("Ok", 1), ("Err", 1)])),
+    ])
+}
+
+pub(super) struct MatchRow<'a> {
+    pub(super) patterns: Vec<&'a Pattern>,
+    pub(super) guarded: bool,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct MatchReport {
+    /// Example values no arm matches, rendered as patterns (one entry per missing case).
+    pub(super) missing: Vec<String>,
+    /// Indices of rows that can never be selected.
+    pub(super) redundant: Vec<usize>,
+}
+
+/// Checks rows of patterns, one column per scrutinee (a single column for `?` matches, one per
+/// parameter for function clauses). `types` names each column's type where it is known. Guarded
+/// rows may fail, so they never cover later rows or count towards exhaustiveness; they are still
+/// reported when earlier rows make them unreachable.
+pub(super) fn check_rows(
+    adts: &ConstructorSets,
+    rows: &[MatchRow<'_>],
+    types: &[Option<String>],
+) -> MatchReport {
+    let Some(width) = rows.first().map(|row| row.patterns.len()) else {
+        return MatchReport::default();
+    };
+    if rows.iter().any(|row| row.patterns.len() != width) {
+        return MatchReport::default();
+    }
+    let mut types = types.to_vec();
+    types.resize(width, None);
+    let checker = Matrix { adts };
+    let mut report = MatchReport::default();
+    let mut covering: Vec<Vec<Pat>> = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        let lowered: Vec<Pat> = row.patterns.iter().map(|pat| lower(pat)).collect();
+        if checker.useful(&covering, &lowered, &types, 1).is_empty() {
+            report.redundant.push(index);
+        }
+        if !row.guarded {
+            covering.push(lowered);
+        }
+    }
+    report.missing = checker
+        .useful(&covering, &vec![Pat::Wild; width], &types, MAX_WITNESSES)
+        .iter()
+        .map(|witness| render_row(witness))
+        .collect();
+    report
+}
+
+fn lower(pattern: &Pattern) -> Pat {
+    match pattern {
+        Pattern::Wildcard(_) | Pattern::Ident(_) => Pat::Wild,
+        Pattern::Literal(Literal::Bool { value, .. }) => {
+            let name = if *value { "True" } else { "False" };
+            Pat::Con(Head::Ctor(name.to_string()), Vec::new())
+        }
+        Pattern::Literal(literal) => Pat::Con(Head::Literal(literal_key(literal)), Vec::new()),
+        Pattern::Constructor { name, args, .. } => Pat::Con(
+            Head::Ctor(name.name.clone()),
+            args.iter().map(lower).collect(),
+        ),
+        Pattern::Tuple { items, .. } => {
+            Pat::Con(Head::Tuple(items.len()), items.iter().map(lower).collect())
+        }
+        Pattern::List { items, rest, .. } => {
+            let tail = match rest {
+                Some(rest) => lower(rest),
+                None => Pat::Con(Head::Nil, Vec::new()),
+            };
+            items.iter().rev().fold(tail, |tail, item| {
+                Pat::Con(Head::Cons, vec![lower(item), tail])
+            })
+        }
+        Pattern::Record { fields, .. } => lower_record(fields),
+    }
+}
+
+fn lower_record(fields: &[RecordPatternField]) -> Pat {
+    let mut out: Vec<(String, Pat)> = Vec::new();
+    for field in fields {
+        // `{ a.b: p }` is `{ a: { b: p } }`.
+        let mut pat = lower(&field.pattern);
+        for segment in field.path.iter().skip(1).rev() {
+            pat = Pat::Record(vec![(segment.name.clone(), pat)]);
+        }
+        let Some(first) = field.path.first() else {
+            continue;
+        };
+        match out.iter_mut().find(|(name, _)| *name == first.name) {
+            Some((_, Pat::Record(existing))) => {
+                if let Pat::Record(more) = pat {
+                    existing.extend(more);
+                }
+            }
+            Some(_) => {}
+            None => out.push((first.name.clone(), pat)),
+        }
+    }
+    Pat::Record(out)
+}
+
+fn literal_key(literal: &Literal) -> String {
+    match literal {
+        Literal::Number { text, .. } => text.clone(),
+        Literal::String { text, .. } => format!("{text:?}"),
+        Literal::Sigil {
+            tag, body, flags, ..
+        } => format!("~{tag}({body}){flags}"),
+        Literal::Bool { value, .. } => if *value { "True" } else { "False" }.to_string(),
+        Literal::DateTime { text, .. } => text.clone(),
+    }
+}
+
+struct Matrix<'a> {
+    adts: &'a ConstructorSets,
+}
+
+impl Matrix<'_> {
+    /// Returns up to `limit` witnesses: value vectors matched by `row` but by none of `rows`.
+    /// An empty result means `row` is not useful. `types` holds the type name of each column.
+    fn useful(
+        &self,
+        rows: &[Vec<Pat>],
+        row: &[Pat],
+        types: &[Option<String>],
+        limit: usize,
+    ) -> Vec<Vec<Pat>> {
+        let Some((first, rest)) = row.split_first() else {
+            return if rows.is_empty() {
+                vec![Vec::new()]
+            } else {
+                Vec::new()
+            };
+        };
+        let ty = types.first().cloned().flatten();
+        let column: Vec<&Pat> = rows.iter().map(|row| &row[0]).collect();
+        if let Some(head) = self.head_of(first, &column) {
+            let arity = self.arity(ty.as_deref(), &head, first);
+            return self
+                .useful_specialized(rows, row, types, &head, arity, limit)
+                .into_iter()
+                .map(|witness| rebuild(&head, arity, witness))
+                .collect();
+        }
+
+        let seen = self.column_heads(&column);
+        match self.signature(ty.as_deref(), &seen, &column) {
+            Some(signature) if signature.iter().all(|(head, _)| seen.contains(head)) => {
+                let mut witnesses = Vec::new();
+                for (head, arity) in signature {
+                    let found = self.useful_specialized(rows, row, types, &head, arity, limit);
+                    witnesses.extend(
+                        found
+                            .into_iter()
+                            .map(|witness| rebuild(&head, arity, witness)),
+                    );
+                    if witnesses.len() >= limit {
+                        witnesses.truncate(limit);
+                        break;
+                    }
+                }
+                witnesses
+            }
+            signature => {
+                let defaults: Vec<Vec<Pat>> = rows
+                    .iter()
+                    .filter(|row| matches!(row[0], Pat::Wild))
+                    .map(|row| row[1..].to_vec())
+                    .collect();
+                let found = self.useful(&defaults, rest, &types[1..], limit);
+                if found.is_empty() {
+                    return found;
+                }
+                // Name the constructors nobody matched; `_` stands for "any other value" when
+                // the column has no constructors or its values cannot be enumerated.
+                let missing: Vec<Pat> = match signature {
+                    Some(signature) if !seen.is_empty() => signature
+                        .into_iter()
+                        .filter(|(head, _)| !seen.contains(head))
+                        .map(|(head, arity)| Pat::Con(head, vec![Pat::Wild; arity]))
+                        .collect(),
+                    _ => vec![Pat::Wild],
+                };
+                let mut witnesses = Vec::new();
+                'outer: for head in &missing {
+                    for witness in &found {
+                        let mut out = Vec::with_capacity(witness.len() + 1);
+                        out.push(head.clone());
+                        out.extend(witness.iter().cloned());
+                        witnesses.push(out);
+                        if witnesses.len() >= limit {
+                            break 'outer;
+                        }
+                    }
+                }
+                witnesses
+            }
+        }
+    }
+
+    fn useful_specialized(
+        &self,
+        rows: &[Vec<Pat>],
+        row: &[Pat],
+        types: &[Option<String>],
+        head: &Head,
+        arity: usize,
+        limit: usize,
+    ) -> Vec<Vec<Pat>> {
+        let specialized: Vec<Vec<Pat>> = rows
+            .iter()
+            .filter_map(|row| specialize(row, head, arity))
+            .collect();
+        // The argument columns take the constructor's argument types.
+        let mut arg_types = match head {
+            Head::Ctor(name) => self
+                .family(types[0].as_deref(), std::slice::from_ref(name))
+                .and_then(|family| family.into_iter().find(|(ctor, _)| ctor == name))
+                .map(|(_, args)| args)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        arg_types.resize(arity, None);
+        arg_types.extend(types[1..].iter().cloned());
+        match specialize(row, head, arity) {
+            Some(row) => self.useful(&specialized, &row, &arg_types, limit),
+            None => Vec::new(),
+        }
+    }
+
+    /// The head a non-wildcard pattern is specialized by. Records take their column's field set.
+    fn head_of(&self, pat: &Pat, column: &[&Pat]) -> Option<Head> {
+        match pat {
+            Pat::Wild => None,
+            Pat::Con(head, _) => Some(head.clone()),
+            Pat::Record(_) => Some(record_head(
+                std::iter::once(pat).chain(column.iter().copied()),
+            )),
+        }
+    }
+
+    fn arity(&self, ty: Option<&str>, head: &Head, pat: &Pat) -> usize {
+        match head {
+            Head::Ctor(name) => self
+                .family(ty, std::slice::from_ref(name))
+                .and_then(|family| {
+                    family
+                        .into_iter()
+                        .find(|(ctor, _)| ctor == name)
+                        .map(|(_, args)| args.len())
+                })
+                .unwrap_or(match pat {
+                    Pat::Con(_, args) => args.len(),
+                    _ => 0,
+                }),
+            Head::Tuple(arity) => *arity,
+            Head::Record(fields) => fields.len(),
+            Head::Cons => 2,
+            Head::Nil | Head::Literal(_) => 0,
+        }
+    }
+
+    fn column_heads(&self, column: &[&Pat]) -> Vec<Head> {
+        let mut heads: Vec<Head> = Vec::new();
+        for pat in column {
+            let head = match pat {
+                Pat::Wild => continue,
+                Pat::Con(head, _) => head.clone(),
+                Pat::Record(_) => record_head(column.iter().copied()),
+            };
+            if !heads.contains(&head) {
+                heads.push(head);
+            }
+        }
+        heads
+    }
+
+    /// Every constructor of the column's type with its arity, or `None` when the values cannot be
+    /// enumerated (literals).
+    fn signature(
+        &self,
+        ty: Option<&str>,
+        seen: &[Head],
+        column: &[&Pat],
+    ) -> Option<Vec<(Head, usize)>> {
+        match seen.first()? {
+            Head::Ctor(_) => {
+                let names: Vec<String> = seen
+                    .iter()
+                    .filter_map(|head| match head {
+                        Head::Ctor(name) => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                let family = self.family(ty, &names).map(|family| {
+                    family
+                        .into_iter()
+                        .map(|(name, args)| (name, args.len()))
+                        .collect::<Vec<_>>()
+                });
+                let family = family.unwrap_or_else(|| {
+                    // Unknown constructors: assume the ones in use are all there is, taking the
+                    // widest application seen as the arity.
+                    names
+                        .iter()
+                        .map(|name| {
+                            let arity = column
+                                .iter()
+                                .filter_map(|pat| match pat {
+                                    Pat::Con(Head::Ctor(ctor), args) if ctor == name => {
+                                        Some(args.len())
+                                    }
+                                    _ => None,
+                                })
+                                .max()
+                                .unwrap_or(0);
+                            (name.clone(), arity)
+                        })
+                        .collect()
+                });
+                Some(
+                    family
+                        .into_iter()
+                        .map(|(name, arity)| (Head::Ctor(name), arity))
+                        .collect(),
+                )
+            }
+            Head::Tuple(arity) => Some(vec![(Head::Tuple(*arity), *arity)]),
+            Head::Record(fields) => Some(vec![(Head::Record(fields.clone()), fields.len())]),
+            Head::Nil | Head::Cons => Some(vec![(Head::Nil, 0), (Head::Cons, 2)]),
+            Head::Literal(_) => None,
+        }
+    }
+
+    /// The constructor family containing all of `names`: the constructors of `ty` when the
+    /// column's type is known, otherwise the only declared type that has them all.
+    fn family(
+        &self,
+        ty: Option<&str>,
+        names: &[String],
+    ) -> Option<Vec<(String, Vec<Option<String>>)>> {
+        let contains_all = |ctors: &[(String, Vec<Option<String>>)]| {
+            names
+                .iter()
+                .all(|name| ctors.iter().any(|(ctor, _)| ctor == name))
+        };
+        if let Some(ctors) = ty.and_then(|ty| self.adts.get(ty)) {
+            return contains_all(ctors).then(|| ctors.clone());
+        }
+        let mut candidates = self.adts.values().filter(|ctors| contains_all(ctors));
+        match (candidates.next(), candidates.next()) {
+            (Some(ctors), None) => Some(ctors.clone()),
+            _ => None,
+        }
+    }
+}
+
+fn record_head<'a>(pats: impl Iterator<Item = &'a Pat>) -> Head {
+    let mut fields = BTreeSet::new();
+    for pat in pats {
+        if let Pat::Record(entries) = pat {
+            fields.extend(entries.iter().map(|(name, _)| name.clone()));
+        }
+    }
+    Head::Record(fields.into_iter().collect())
+}
+
+/// The rows of the specialized matrix: `row` with its first pattern replaced by the arguments
+/// it has under `head`, or `None` if it cannot match `head`.
+fn specialize(row: &[Pat], head: &Head, arity: usize) -> Option<Vec<Pat>> {
+    let (first, rest) = row.split_first()?;
+    let mut out: Vec<Pat> = match (first, head) {
+        (Pat::Wild, _) => vec![Pat::Wild; arity],
+        (Pat::Con(own, args), _) if own == head => {
+            let mut args = args.clone();
+            args.resize(arity, Pat::Wild);
+            args
+        }
+        (Pat::Record(entries), Head::Record(fields)) => fields
+            .iter()
+            .map(|field| {
+                entries
+                    .iter()
+                    .find(|(name, _)| name == field)
+                    .map(|(_, pat)| pat.clone())
+                    .unwrap_or(Pat::Wild)
+            })
+            .collect(),
+        _ => return None,
+    };
+    out.extend(rest.iter().cloned());
+    Some(out)
+}
+
+/// Inverse of `specialize` for witnesses: folds the first `arity` patterns back under `head`.
+fn rebuild(head: &Head, arity: usize, mut witness: Vec<Pat>) -> Vec<Pat> {
+    let rest = witness.split_off(arity.min(witness.len()));
+    let pat = match head {
+        Head::Record(fields) => Pat::Record(fields.iter().cloned().zip(witness).collect()),
+        _ => Pat::Con(head.clone(), witness),
+    };
+    let mut out = Vec::with_capacity(rest.len() + 1);
+    out.push(pat);
+    out.extend(rest);
+    out
+}
+
+fn render_row(row: &[Pat]) -> String {
+    row.iter()
+        .map(|pat| render(pat, row.len() > 1))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Renders a witness as source pattern syntax. `nested` adds parentheses where a constructor
+/// application would otherwise run into its neighbours.
+fn render(pat: &Pat, nested: bool) -> String {
+    match pat {
+        Pat::Wild => "_".to_string(),
+        Pat::Con(Head::Ctor(name), args) if args.is_empty() => name.clone(),
+        Pat::Con(Head::Ctor(name), args) => {
+            let args: Vec<String> = args.iter().map(|arg| render(arg, true)).collect();
+            let text = format!("{name} {}", args.join(" "));
+            if nested {
+                format!("({text})")
+            } else {
+                text
+            }
+        }
+        Pat::Con(Head::Tuple(_), items) => {
+            let items: Vec<String> = items.iter().map(|item| render(item, false)).collect();
+            format!("({})", items.join(", "))
+        }
+        Pat::Con(Head::Nil, _) => "[]".to_string(),
+        Pat::Con(Head::Cons, _) => {
+            let mut items = Vec::new();
+            let mut tail = pat;
+            while let Pat::Con(Head::Cons, parts) = tail {
+                items.push(render(&parts[0], false));
+                tail = &parts[1];
+            }
+            if !matches!(tail, Pat::Con(Head::Nil, _)) {
+                items.push(format!("...{}", render(tail, false)));
+            }
+            format!("[{}]", items.join(", "))
+        }
+        Pat::Con(Head::Literal(text), _) => text.clone(),
+        Pat::Con(Head::Record(_), _) => "_".to_string(),
+        Pat::Record(entries) => {
+            let fields: Vec<String> = entries
+                .iter()
+                .filter(|(_, pat)| !matches!(pat, Pat::Wild))
+                .map(|(name, pat)| format!("{name}: {}", render(pat, false)))
+                .collect();
+            if fields.is_empty() {
+                "_".to_string()
+            } else {
+                format!("{{ {} }}", fields.join(", "))
+            }
+        }
+    }
+}
@@ -68,6 +68,9 @@ fn hir_contains_var(expr: &HirExpr, name: &str) -> bool {
             crate::hir::HirBlockItem::Yield { expr } => hir_contains_var(expr, name),
             crate::hir::HirBlockItem::Recurse { expr } => hir_contains_var(expr, name),
             crate::hir::HirBlockItem::Expr { expr } => hir_contains_var(expr, name),
+            crate::hir::HirBlockItem::LetRec { bindings } => bindings
+                .iter()
+                .any(|binding| hir_contains_var(&binding.expr, name)),
         }),
         HirExpr::Raw { .. }
         | HirExpr::LitNumber { .. }
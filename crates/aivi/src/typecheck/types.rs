@@ -34,10 +34,22 @@ pub(super) enum Type {
     Tuple(Vec<Type>),
     Record {
         fields: BTreeMap<String, Type>,
-        open: bool,
+        row: Row,
     },
 }
 
+/// The part of a record type beyond its listed `fields`.
+#[derive(Clone, Debug)]
+pub(super) enum Row {
+    /// No other fields: record literals.
+    Closed,
+    /// Any other fields, untracked: record annotations and builtin signatures.
+    Open,
+    /// The fields of a row variable (`{ name: Text | r }`). Unification binds the variable to a
+    /// record type holding the remaining fields, and `apply` splices those back in.
+    Var(TypeVarId),
+}
+
 #[derive(Clone, Debug)]
 pub(super) struct Scheme {
     pub(super) vars: Vec<TypeVarId>,
@@ -53,17 +65,51 @@ pub(super) struct AliasInfo {
 #[derive(Clone, Debug, Default)]
 pub(super) struct TypeEnv {
     values: HashMap<String, Scheme>,
+    /// Module each imported name comes from; dropped once a local binding shadows the name.
+    origins: HashMap<String, String>,
+    /// Every carrier's signature of a domain operator defined over several carriers
+    /// (`domain Vector over Vec2`, `domain Vector over Vec3`), in declaration order.
+    overloads: HashMap<String, Vec<Scheme>>,
 }
 
 impl TypeEnv {
     pub(super) fn insert(&mut self, name: String, scheme: Scheme) {
+        self.origins.remove(&name);
+        self.overloads.remove(&name);
         self.values.insert(name, scheme);
     }
 
+    pub(super) fn insert_imported(&mut self, name: String, scheme: Scheme, module: &str) {
+        self.overloads.remove(&name);
+        self.values.insert(name.clone(), scheme);
+        self.origins.insert(name, module.to_string());
+    }
+
+    /// Records the per-carrier signatures of an operator already bound under `name`.
+    pub(super) fn insert_overloads(&mut self, name: String, schemes: Vec<Scheme>) {
+        self.overloads.insert(name, schemes);
+    }
+
+    /// The signatures to try for an operator: one per carrier, or just its binding.
+    pub(super) fn operator_schemes(&self, name: &str) -> Vec<Scheme> {
+        match self.overloads.get(name) {
+            Some(schemes) => schemes.clone(),
+            None => self.get(name).cloned().into_iter().collect(),
+        }
+    }
+
+    pub(super) fn origin(&self, name: &str) -> Option<&str> {
+        self.origins.get(name).map(String::as_str)
+    }
+
     pub(super) fn get(&self, name: &str) -> Option<&Scheme> {
         self.values.get(name)
     }
 
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&String, &Scheme)> {
+        self.values.iter()
+    }
+
     pub(super) fn free_vars(&self, checker: &mut TypeChecker) -> HashSet<TypeVarId> {
         let mut vars = HashSet::new();
         for scheme in self.values.values() {
@@ -227,6 +273,20 @@ impl TypePrinter {
     pub(super) fn print(&mut self, ty: &Type) -> String {
         match ty {
             Type::Var(id) => self.name_for(*id),
+            Type::Con(name, args) if name == "Effect" && args.len() == 3 => {
+                // Effect rows print as the list of effects performed, and not at all when empty.
+                let mut parts = Vec::new();
+                if let Type::Record { fields, .. } = &args[2] {
+                    let labels: Vec<&str> = fields.keys().map(String::as_str).collect();
+                    match labels.as_slice() {
+                        [] => {}
+                        [label] => parts.push(label.to_string()),
+                        labels => parts.push(format!("({})", labels.join(", "))),
+                    }
+                }
+                parts.extend(args[..2].iter().map(|arg| self.print(arg)));
+                format!("Effect {}", parts.join(" "))
+            }
             Type::Con(name, args) => {
                 if args.is_empty() {
                     name.clone()
@@ -259,15 +319,20 @@ impl TypePrinter {
                     .collect::<Vec<_>>();
                 format!("({})", items_str.join(", "))
             }
-            Type::Record { fields, open } => {
+            Type::Record { fields, row } => {
                 let mut parts = Vec::new();
                 for (name, ty) in fields {
                     parts.push(format!("{}: {}", name, self.print(ty)));
                 }
-                if *open {
-                    parts.push("..".to_string());
+                match row {
+                    Row::Closed => format!("{{ {} }}", parts.join(", ")),
+                    Row::Open => {
+                        parts.push("..".to_string());
+                        format!("{{ {} }}", parts.join(", "))
+                    }
+                    Row::Var(id) if parts.is_empty() => format!("{{ | {} }}", self.name_for(*id)),
+                    Row::Var(id) => format!("{{ {} | {} }}", parts.join(", "), self.name_for(*id)),
                 }
-                format!("{{ {} }}", parts.join(", "))
             }
         }
     }
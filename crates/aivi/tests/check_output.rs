@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+fn aivi_check(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .arg("check")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("run aivi check")
+}
+
+fn stdout_json(output: &Output) -> Value {
+    serde_json::from_slice(&output.stdout).expect("json output")
+}
+
+const UNCLOSED: &str = "module app.main\n\nvalue = { x: 1\n";
+
+const UNUSED_IMPORT: &str =
+    "module app.main\n\nexport value\n\nuse aivi.text (length)\n\nvalue = 1\n";
+
+#[test]
+fn check_reports_json_with_quick_fixes() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    fs::write(temp.path().join("main.aivi"), UNCLOSED).expect("write source");
+
+    let output = aivi_check(temp.path(), &["--format", "json", "main.aivi"]);
+    assert!(!output.status.success());
+    let report = stdout_json(&output);
+    let diagnostics = report["diagnostics"].as_array().expect("diagnostics");
+    let unclosed = diagnostics
+        .iter()
+        .find(|diag| diag["code"] == "E1004")
+        .unwrap_or_else(|| panic!("expected E1004: {report}"));
+    assert_eq!(unclosed["path"], "main.aivi");
+    assert_eq!(unclosed["severity"], "error");
+    assert_eq!(unclosed["span"]["start"]["line"], 3);
+    let edit = &unclosed["fixes"][0]["edits"][0];
+    assert_eq!(edit["newText"], "}");
+    assert_eq!(edit["span"]["start"]["line"], 4);
+    assert_eq!(edit["span"]["start"]["column"], 1);
+    assert!(report["errors"].as_u64().is_some_and(|count| count > 0));
+}
+
+#[test]
+fn check_reports_sarif() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    fs::write(temp.path().join("main.aivi"), UNCLOSED).expect("write source");
+
+    let output = aivi_check(temp.path(), &["--format", "sarif", "main.aivi"]);
+    assert!(!output.status.success());
+    let log = stdout_json(&output);
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    let results = run["results"].as_array().expect("results");
+    let result = results
+        .iter()
+        .find(|result| result["ruleId"] == "E1004")
+        .unwrap_or_else(|| panic!("expected E1004: {log}"));
+    assert_eq!(result["level"], "error");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "main.aivi");
+    assert_eq!(location["region"]["startLine"], 3);
+    let replacement = &result["fixes"][0]["artifactChanges"][0]["replacements"][0];
+    assert_eq!(replacement["insertedContent"]["text"], "}");
+
+    let rule = &run["tool"]["driver"]["rules"][result["ruleIndex"].as_u64().unwrap() as usize];
+    assert_eq!(rule["id"], "E1004");
+    assert!(rule["helpUri"].as_str().unwrap().ends_with("#e1004"));
+}
+
+#[test]
+fn check_reports_short_lines() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    fs::write(temp.path().join("main.aivi"), UNCLOSED).expect("write source");
+
+    let output = aivi_check(temp.path(), &["--format", "short", "main.aivi"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.starts_with("main.aivi:3:") && line.contains(": error[E1004] ")),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn check_deny_and_allow_override_warnings() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    fs::write(temp.path().join("main.aivi"), UNUSED_IMPORT).expect("write source");
+
+    let output = aivi_check(temp.path(), &["--format", "json", "main.aivi"]);
+    assert!(output.status.success(), "{output:?}");
+    let report = stdout_json(&output);
+    assert_eq!(report["diagnostics"][0]["code"], "W2100");
+    assert_eq!(report["diagnostics"][0]["severity"], "warning");
+
+    let output = aivi_check(
+        temp.path(),
+        &["--format", "json", "--deny", "W2100", "main.aivi"],
+    );
+    assert!(!output.status.success());
+    let report = stdout_json(&output);
+    assert_eq!(report["diagnostics"][0]["severity"], "error");
+    assert_eq!(report["errors"], 1);
+
+    let output = aivi_check(
+        temp.path(),
+        &[
+            "--format",
+            "json",
+            "--deny",
+            "warnings",
+            "--allow",
+            "w2100",
+            "main.aivi",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let report = stdout_json(&output);
+    assert_eq!(report["diagnostics"].as_array().map(Vec::len), Some(0));
+}
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use aivi::{check_types, parse_modules};
+use aivi::{check_types, infer_value_types, parse_modules};
 
 #[test]
 fn class_inheritance_uses_type_and_combinator() {
@@ -100,3 +100,218 @@ instance Sub Int = {
         "expected missing 'foo' diagnostic, got: {type_diags:#?}"
     );
 }
+
+#[test]
+fn class_defaults_fill_own_and_superclass_members() {
+    let lib = r#"
+module Lib
+export Describe, Shape
+
+class Describe A = {
+  describe: A -> Text
+  headline: A -> Text
+  headline = a => describe a
+}
+
+class Shape A = Describe A with {
+  sides: A -> Int
+  describe = a => "shape with {sides a} sides"
+}
+"#;
+    let app = r#"
+module App
+export label
+
+use Lib (Shape)
+
+Polygon = Triangle | Square
+
+instance Shape Polygon = {
+  sides: p => p ?
+    | Triangle => 3
+    | Square   => 4
+}
+
+label : Text
+label = headline Square
+"#;
+
+    let mut modules = Vec::new();
+    for (path, src) in [("lib.aivi", lib), ("app.aivi", app)] {
+        let (mut parsed, parse_diags) = parse_modules(Path::new(path), src);
+        assert!(
+            parse_diags.is_empty(),
+            "unexpected parse diagnostics: {parse_diags:#?}"
+        );
+        modules.append(&mut parsed);
+    }
+
+    let type_diags = check_types(&modules);
+    assert!(
+        type_diags.is_empty(),
+        "unexpected type diagnostics: {type_diags:#?}"
+    );
+}
+
+#[test]
+fn missing_instance_methods_are_reported_once() {
+    let src = r#"
+module Test
+
+class Shape A = {
+  area: A -> Int
+  name: A -> Text
+  sides: A -> Int
+  label: A -> Text
+  label = a => name a
+}
+
+instance Shape Bool = {
+  area: b => 1
+}
+"#;
+
+    let (modules, parse_diags) = parse_modules(Path::new("instance_missing.aivi"), src);
+    assert!(
+        parse_diags.is_empty(),
+        "unexpected parse diagnostics: {parse_diags:#?}"
+    );
+
+    let type_diags = check_types(&modules);
+    let messages: Vec<&str> = type_diags
+        .iter()
+        .map(|d| d.diagnostic.message.as_str())
+        .collect();
+    assert_eq!(messages, ["missing instance methods 'name', 'sides'"]);
+}
+
+#[test]
+fn class_defaults_are_checked_against_each_instance() {
+    let src = r#"
+module Test
+
+class Describe A = {
+  describe: A -> Text
+  size: A -> Int
+  size = a => describe a
+  whisper = a => describe a
+}
+
+instance Describe Int = {
+  describe: n => "int"
+}
+"#;
+
+    let (modules, parse_diags) = parse_modules(Path::new("class_default_errors.aivi"), src);
+    assert!(
+        parse_diags.is_empty(),
+        "unexpected parse diagnostics: {parse_diags:#?}"
+    );
+
+    let type_diags = check_types(&modules);
+    assert!(
+        type_diags.iter().any(|d| d
+            .diagnostic
+            .message
+            .contains("default 'whisper' is not a member of class 'Describe'")),
+        "expected unknown default diagnostic, got: {type_diags:#?}"
+    );
+    assert!(
+        type_diags.iter().any(|d| d
+            .diagnostic
+            .message
+            .starts_with("default for 'size' does not fit this instance")),
+        "expected default mismatch diagnostic, got: {type_diags:#?}"
+    );
+}
+
+#[test]
+fn functional_dependencies_reject_conflicting_instances() {
+    let src = r#"
+module Test
+export label
+
+class Convert A B | A -> B = {
+  convert: A -> B
+}
+
+instance Convert Int Text = {
+  convert: n => "int"
+}
+
+instance Convert Int Bool = {
+  convert: n => True
+}
+
+instance Convert Bool Int = {
+  convert: b => 1
+}
+
+label : Int
+label = convert True
+"#;
+
+    let (modules, parse_diags) = parse_modules(Path::new("fundeps.aivi"), src);
+    assert!(
+        parse_diags.is_empty(),
+        "unexpected parse diagnostics: {parse_diags:#?}"
+    );
+
+    let type_diags = check_types(&modules);
+    let messages: Vec<&str> = type_diags
+        .iter()
+        .map(|d| d.diagnostic.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        ["instance 'Convert Int Bool' conflicts with 'Convert Int Text': the functional dependency 'A -> B' allows only one instance per 'A'"]
+    );
+}
+
+#[test]
+fn functional_dependencies_select_the_instance_from_determining_params() {
+    let src = r#"
+module Test
+export label, count, total, bad
+
+class Convert A B | A -> B = {
+  convert: A -> B
+  convertOr: B -> A -> B
+}
+
+instance Convert Int Text = {
+  convert: n => "int"
+  convertOr: fallback n => fallback
+}
+
+instance Convert Bool Int = {
+  convert: b => 1
+  convertOr: fallback b => fallback
+}
+
+label = convert 1
+count = convert True
+total = count + 1
+
+bad = convertOr True 1
+"#;
+
+    let (modules, parse_diags) = parse_modules(Path::new("fundeps_infer.aivi"), src);
+    assert!(
+        parse_diags.is_empty(),
+        "unexpected parse diagnostics: {parse_diags:#?}"
+    );
+
+    let (type_diags, types) = infer_value_types(&modules);
+    let messages: Vec<&str> = type_diags
+        .iter()
+        .map(|d| d.diagnostic.message.as_str())
+        .collect();
+    // `A` fixes the instance, so `True` is checked against its `B` rather than reported as a
+    // missing instance.
+    assert_eq!(messages, ["type mismatch (expected Text, found Bool)"]);
+    let types = &types["Test"];
+    assert_eq!(types["label"], "Text");
+    assert_eq!(types["count"], "Int");
+    assert_eq!(types["total"], "Int");
+}
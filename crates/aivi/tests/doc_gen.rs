@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use aivi::{
+    collect_doctests, doc_target, generate_docs, module_doc, parse_modules, run_doctests, DocFormat,
+};
+
+const SHAPES: &str = r#"// Geometry helpers.
+module demo.shapes
+export Shape, Circle, Square, area, double, domain Metric
+
+// A 2D shape.
+type Shape = Circle Float | Square Float
+
+// Area of a shape.
+//
+// >>> area (Square 2.0)
+// 4.0
+area : Shape -> Float
+area = shape => shape ?
+  | Circle r => 3.0 * r * r
+  | Square s => s * s
+
+// Doubles a number.
+// >>> double 21
+// 42
+@deprecated "use times 2"
+double = x => x * 2
+
+helper = 1
+
+domain Metric over Float = {
+  (+) : Float -> Float -> Float
+  (+) = a b => a
+}
+"#;
+
+#[test]
+fn module_doc_lists_exports_types_and_domains() {
+    let (modules, diags) = parse_modules(Path::new("shapes.aivi"), SHAPES);
+    assert!(diags.is_empty(), "unexpected diagnostics: {diags:?}");
+    let doc = module_doc(&modules[0], SHAPES, None);
+
+    assert_eq!(doc.name, "demo.shapes");
+    assert_eq!(doc.doc.as_deref(), Some("Geometry helpers."));
+
+    let names: Vec<&str> = doc.values.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["area", "double"],
+        "unexported helper must be hidden"
+    );
+    assert_eq!(doc.values[0].signature.as_deref(), Some("Shape -> Float"));
+    assert_eq!(doc.values[1].deprecated.as_deref(), Some("use times 2"));
+    assert!(doc.values[1]
+        .doc
+        .as_deref()
+        .is_some_and(|d| d.starts_with("Doubles a number.")));
+
+    assert_eq!(doc.types.len(), 1);
+    assert_eq!(doc.types[0].header, "type Shape");
+    assert_eq!(
+        doc.types[0].constructors,
+        vec!["Circle Float", "Square Float"]
+    );
+    assert_eq!(doc.types[0].doc.as_deref(), Some("A 2D shape."));
+
+    assert_eq!(doc.domains.len(), 1);
+    assert_eq!(doc.domains[0].over, "Float");
+    assert_eq!(doc.domains[0].operators[0].name, "(+)");
+}
+
+#[test]
+fn doctests_are_collected_and_run() {
+    let (modules, _) = parse_modules(Path::new("shapes.aivi"), SHAPES);
+    let doctests = collect_doctests(&modules[0], SHAPES);
+    assert_eq!(doctests.len(), 2);
+    assert_eq!(doctests[0].expr, "area (Square 2.0)");
+    assert_eq!(doctests[0].expected.as_deref(), Some("4.0"));
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("shapes.aivi");
+    std::fs::write(&path, SHAPES).expect("write file");
+    let report = run_doctests(path.to_str().expect("utf-8 path")).expect("run doctests");
+    assert_eq!(report.passed, 2, "failures: {:?}", report.failures);
+    assert_eq!(report.failed, 0);
+
+    std::fs::write(&path, SHAPES.replace("// 42", "// 43")).expect("write file");
+    let report = run_doctests(path.to_str().expect("utf-8 path")).expect("run doctests");
+    assert_eq!(report.passed, 1);
+    assert_eq!(report.failed, 1);
+    assert!(report.failures[0].name.ends_with("doctest_double_1"));
+}
+
+#[test]
+fn doc_target_renders_markdown_and_html_pages() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("shapes.aivi");
+    std::fs::write(&path, SHAPES).expect("write file");
+    let target = path.to_str().expect("utf-8 path");
+
+    let pages = doc_target(target, DocFormat::Markdown).expect("markdown docs");
+    let paths: Vec<String> = pages.iter().map(|p| p.path.display().to_string()).collect();
+    assert_eq!(paths, vec!["index.md", "demo.shapes.md"]);
+    let page = &pages[1].contents;
+    assert!(page.contains("### `area : Shape -> Float`"));
+    assert!(page.contains("> **Deprecated:** use times 2"));
+    assert!(page.contains("```aivi\n>>> double 21\n42\n```"));
+
+    let pages = doc_target(target, DocFormat::Html).expect("html docs");
+    let page = &pages[1].contents;
+    assert!(page.contains("<code>area : Shape -&gt; Float</code>"));
+    assert!(!page.contains("id=\"helper\""));
+}
+
+#[test]
+fn generate_docs_uses_the_given_source_text() {
+    let (modules, _) = parse_modules(Path::new("<memory>/shapes.aivi"), SHAPES);
+    let sources = HashMap::from([(modules[0].path.clone(), SHAPES.to_string())]);
+    let pages = generate_docs(&modules, &sources, DocFormat::Markdown).expect("docs");
+    assert!(pages[1].contents.contains("Area of a shape."));
+
+    let err = generate_docs(&modules, &HashMap::new(), DocFormat::Markdown)
+        .expect_err("a module without source text must be reported");
+    assert!(err.to_string().contains("demo.shapes"), "{err}");
+}
+
+#[test]
+fn markdown_tables_escape_pipes_in_types() {
+    let source = r#"module demo.rows
+export Named
+
+class Named A = {
+  rename: { name: Text | r } -> { name: Text | r }
+}
+"#;
+    let (modules, diags) = parse_modules(Path::new("rows.aivi"), source);
+    assert!(diags.is_empty(), "unexpected diagnostics: {diags:?}");
+    let sources = HashMap::from([(modules[0].path.clone(), source.to_string())]);
+    let pages = generate_docs(&modules, &sources, DocFormat::Markdown).expect("docs");
+    let row = pages[1]
+        .contents
+        .lines()
+        .find(|line| line.starts_with("| `rename`"))
+        .expect("member row");
+    assert_eq!(
+        row,
+        "| `rename` | `{ name: Text \\| r } -> { name: Text \\| r }` |"
+    );
+}
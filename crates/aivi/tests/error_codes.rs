@@ -0,0 +1,116 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use aivi::{error_code_info, error_codes_markdown, ERROR_CODES};
+use walkdir::WalkDir;
+
+fn workspace_root() -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir
+        .parent()
+        .and_then(|path| path.parent())
+        .expect("workspace root")
+        .to_path_buf()
+}
+
+fn bless_enabled() -> bool {
+    std::env::var("AIVI_BLESS").is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+}
+
+/// Collects every `"E1234"` / `"W1234"` string literal in the Rust sources under `dir`.
+fn emitted_codes(dir: &Path, codes: &mut BTreeSet<String>) {
+    for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+            continue;
+        }
+        // The registry itself mentions codes in prose and examples.
+        if path.file_name().and_then(|name| name.to_str()) == Some("error_codes.rs") {
+            continue;
+        }
+        let text = fs::read_to_string(path).expect("read source");
+        let bytes = text.as_bytes();
+        for (idx, window) in bytes.windows(7).enumerate() {
+            let is_code = window[0] == b'"'
+                && matches!(window[1], b'E' | b'W')
+                && window[2..6].iter().all(u8::is_ascii_digit)
+                && window[6] == b'"';
+            if is_code {
+                codes.insert(text[idx + 1..idx + 6].to_string());
+            }
+        }
+    }
+}
+
+#[test]
+fn every_emitted_code_is_registered() {
+    let root = workspace_root();
+    let mut codes = BTreeSet::new();
+    emitted_codes(&root.join("crates/aivi/src"), &mut codes);
+    emitted_codes(&root.join("crates/aivi_lsp/src"), &mut codes);
+    assert!(!codes.is_empty(), "no diagnostic codes found in sources");
+
+    let missing: Vec<_> = codes
+        .iter()
+        .filter(|code| error_code_info(code).is_none())
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "codes emitted but missing from ERROR_CODES: {missing:?}"
+    );
+}
+
+#[test]
+fn registry_entries_are_unique_and_complete() {
+    let mut seen = BTreeSet::new();
+    for info in ERROR_CODES {
+        assert!(seen.insert(info.code), "duplicate entry for {}", info.code);
+        assert!(!info.title.is_empty(), "{} has no title", info.code);
+        assert!(
+            !info.explanation.is_empty(),
+            "{} has no explanation",
+            info.code
+        );
+        assert!(!info.bad.is_empty(), "{} has no example", info.code);
+        assert!(!info.good.is_empty(), "{} has no fixed example", info.code);
+        assert!(!info.fixes.is_empty(), "{} has no fixes", info.code);
+    }
+}
+
+#[test]
+fn error_codes_page_is_up_to_date() {
+    let path = workspace_root().join("specs/07_tools/05_error_codes.md");
+    let expected = error_codes_markdown();
+    if bless_enabled() {
+        fs::write(&path, &expected).expect("write error codes page");
+        return;
+    }
+    let actual = fs::read_to_string(&path).expect("read error codes page");
+    assert!(
+        actual.replace("\r\n", "\n") == expected,
+        "{} is stale; rerun with AIVI_BLESS=1",
+        path.display()
+    );
+}
+
+#[test]
+fn explain_prints_registry_entry() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .args(["explain", "e3100"])
+        .output()
+        .expect("run aivi explain");
+    assert!(output.status.success(), "aivi explain failed: {output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("E3100: Non-exhaustive match"), "{stdout}");
+    assert!(stdout.contains("How to fix:"), "{stdout}");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aivi"))
+        .args(["explain", "E9999"])
+        .output()
+        .expect("run aivi explain");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown error code"), "{stderr}");
+}
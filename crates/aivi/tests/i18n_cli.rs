@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn write_file(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent dirs");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn aivi_exe() -> &'static str {
+    env!("CARGO_BIN_EXE_aivi")
+}
+
+#[test]
+fn i18n_extract_then_check_reports_missing_translations() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    write_file(
+        &root.join("aivi.toml"),
+        r#"[project]
+kind = "bin"
+entry = "main.aivi"
+
+[i18n]
+catalogs = "locales"
+source_locale = "en"
+"#,
+    );
+    write_file(
+        &root.join("src/main.aivi"),
+        r#"module app.main
+
+title = ~k"app.title"
+greeting = (~k"app.greeting", ~m"Hello, {name:Text}!")
+"#,
+    );
+
+    let output = Command::new(aivi_exe())
+        .current_dir(root)
+        .args(["i18n", "extract", "src/...", "--locale", "de"])
+        .output()
+        .expect("run aivi i18n extract");
+    assert!(
+        output.status.success(),
+        "extract failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let en = fs::read_to_string(root.join("locales/en.properties")).expect("read en catalog");
+    assert!(en.contains("app.greeting = Hello, {name:Text}!"), "{en}");
+    assert!(en.contains("app.title ="), "{en}");
+    let de = fs::read_to_string(root.join("locales/de.properties")).expect("read de catalog");
+    assert!(de.contains("app.greeting ="), "{de}");
+
+    // Skeleton entries are still untranslated.
+    let output = Command::new(aivi_exe())
+        .current_dir(root)
+        .args(["i18n", "check", "src/..."])
+        .output()
+        .expect("run aivi i18n check");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("W2600"), "{stderr}");
+
+    write_file(
+        &root.join("locales/en.properties"),
+        "app.title = Welcome\napp.greeting = Hello, {name:Text}!\n",
+    );
+    write_file(
+        &root.join("locales/de.properties"),
+        "app.title = Willkommen\napp.greeting = Hallo, {user:Text}!\n",
+    );
+    let output = Command::new(aivi_exe())
+        .current_dir(root)
+        .args(["i18n", "check", "src/..."])
+        .output()
+        .expect("run aivi i18n check");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("E2602"), "{stderr}");
+    assert!(!stderr.contains("W2600"), "{stderr}");
+
+    write_file(
+        &root.join("locales/de.properties"),
+        "app.title = Willkommen\napp.greeting = Hallo, {name:Text}!\n",
+    );
+    let output = Command::new(aivi_exe())
+        .current_dir(root)
+        .args(["i18n", "check", "src/..."])
+        .output()
+        .expect("run aivi i18n check");
+    assert!(
+        output.status.success(),
+        "check failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -1,6 +1,9 @@
 use std::path::Path;
 
-use aivi::{generate_i18n_module_from_properties, parse_file, parse_modules};
+use aivi::{
+    check_types, embedded_stdlib_modules, generate_i18n_module_from_properties, parse_file,
+    parse_modules,
+};
 
 #[test]
 fn i18n_sigil_key_is_validated_at_parse_time() {
@@ -72,3 +75,254 @@ fn i18n_codegen_module_parses() {
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn i18n_sigil_plural_without_other_arm_is_rejected() {
+    let dummy = Path::new("plural.aivi");
+    let (_modules, diags) = parse_modules(
+        dummy,
+        r#"
+module test.badPlural
+use aivi
+x = ~m"{count, plural, one {# item}}"
+"#,
+    );
+    assert!(
+        diags
+            .iter()
+            .any(|d| d.diagnostic.code == "E1515" && d.diagnostic.message.contains("'other'")),
+        "expected E1515 diagnostic, got: {:?}",
+        diags
+            .iter()
+            .map(|d| (&d.diagnostic.code, &d.diagnostic.message))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn i18n_plural_placeholder_requires_numeric_argument() {
+    let type_errors = |source: &str| {
+        let (mut user_modules, diags) = parse_modules(Path::new("plural.aivi"), source);
+        assert!(diags.is_empty(), "parse diagnostics: {diags:?}");
+        let mut modules = embedded_stdlib_modules();
+        modules.append(&mut user_modules);
+        check_types(&modules)
+            .into_iter()
+            .filter(|d| d.path == "plural.aivi")
+            .map(|d| d.diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+
+    let ok = type_errors(
+        r#"
+module test.plural
+use aivi
+use aivi.i18n (render)
+x = render ~m"{count, plural, one {# item} other {# items}}" { count: 3 }
+"#,
+    );
+    assert!(ok.is_empty(), "unexpected type errors: {ok:?}");
+
+    let bad = type_errors(
+        r#"
+module test.plural
+use aivi
+use aivi.i18n (render)
+x = render ~m"{count, plural, one {# item} other {# items}}" { count: "three" }
+"#,
+    );
+    assert!(
+        bad.iter().any(|msg| msg.contains("must be numeric")),
+        "expected a numeric plural argument error, got: {bad:?}"
+    );
+}
+
+#[test]
+fn i18n_message_arguments_are_checked_through_bound_records() {
+    let type_errors = |source: &str| {
+        let (mut user_modules, diags) = parse_modules(Path::new("bound.aivi"), source);
+        assert!(diags.is_empty(), "parse diagnostics: {diags:?}");
+        let mut modules = embedded_stdlib_modules();
+        modules.append(&mut user_modules);
+        check_types(&modules)
+            .into_iter()
+            .filter(|d| d.path == "bound.aivi")
+            .map(|d| d.diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+
+    let ok = type_errors(
+        r#"
+module test.bound
+use aivi
+use aivi.i18n (render)
+args = { count: 3 }
+x = render ~m"{count, plural, one {# item} other {# items}}" args
+"#,
+    );
+    assert!(ok.is_empty(), "unexpected type errors: {ok:?}");
+
+    let bound = type_errors(
+        r#"
+module test.bound
+use aivi
+use aivi.i18n (render)
+args = { count: "three" }
+x = render ~m"{count, plural, one {# item} other {# items}}" args
+"#,
+    );
+    assert!(
+        bound.iter().any(|msg| msg.contains("must be numeric")),
+        "expected a numeric plural argument error, got: {bound:?}"
+    );
+
+    let missing = type_errors(
+        r#"
+module test.bound
+use aivi
+use aivi.i18n (render)
+args = { total: 3 }
+x = render ~m"{count, plural, one {# item} other {# items}}" args
+"#,
+    );
+    assert!(
+        missing
+            .iter()
+            .any(|msg| msg.contains("missing message argument 'count'")),
+        "expected a missing argument error, got: {missing:?}"
+    );
+
+    let function = type_errors(
+        r#"
+module test.bound
+use aivi
+use aivi.i18n (render)
+x = render ~m"{count, plural, one {# item} other {# items}}" { count: n => n }
+"#,
+    );
+    assert!(
+        function.iter().any(|msg| msg.contains("must be numeric")),
+        "expected a numeric plural argument error, got: {function:?}"
+    );
+}
+
+#[test]
+fn i18n_format_placeholders_check_argument_types() {
+    let type_errors = |source: &str| {
+        let (mut user_modules, diags) = parse_modules(Path::new("format.aivi"), source);
+        assert!(diags.is_empty(), "parse diagnostics: {diags:?}");
+        let mut modules = embedded_stdlib_modules();
+        modules.append(&mut user_modules);
+        check_types(&modules)
+            .into_iter()
+            .filter(|d| d.path == "format.aivi")
+            .map(|d| d.diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+
+    let ok = type_errors(
+        r#"
+module test.format
+use aivi
+use aivi.i18n (render)
+x = render ~m"{total, number, percent} due {when, date, long}" { total: 0.5, when: 2024-05-21T12:00:00Z }
+"#,
+    );
+    assert!(ok.is_empty(), "unexpected type errors: {ok:?}");
+
+    let bad = type_errors(
+        r#"
+module test.format
+use aivi
+use aivi.i18n (render)
+x = render ~m"{total, number}" { total: "lots" }
+"#,
+    );
+    assert!(
+        bad.iter().any(|msg| msg.contains("must be numeric")),
+        "expected a numeric format argument error, got: {bad:?}"
+    );
+
+    let bad = type_errors(
+        r#"
+module test.format
+use aivi
+use aivi.i18n (render)
+x = render ~m"{when, time}" { when: 3 }
+"#,
+    );
+    assert!(!bad.is_empty(), "expected a DateTime argument error");
+}
+
+#[test]
+fn i18n_argument_checks_only_apply_to_aivi_i18n_render() {
+    let type_errors = |source: &str| {
+        let (mut user_modules, diags) = parse_modules(Path::new("render.aivi"), source);
+        assert!(diags.is_empty(), "parse diagnostics: {diags:?}");
+        let mut modules = embedded_stdlib_modules();
+        modules.append(&mut user_modules);
+        check_types(&modules)
+            .into_iter()
+            .filter(|d| d.path == "render.aivi")
+            .map(|d| d.diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+
+    let own = type_errors(
+        r#"
+module test.render
+use aivi
+use aivi.i18n (Message)
+
+render : Message -> { count: Text } -> Text
+render = msg args => args.count
+
+x = render ~m"{count, plural, one {# item} other {# items}}" { count: "three" }
+"#,
+    );
+    assert!(own.is_empty(), "unexpected type errors: {own:?}");
+
+    let shadowed = type_errors(
+        r#"
+module test.render
+use aivi
+use aivi.i18n (Message, render)
+
+x = (render => render ~m"{count, plural, one {# item} other {# items}}" { count: "three" }) (msg args => args.count)
+"#,
+    );
+    assert!(shadowed.is_empty(), "unexpected type errors: {shadowed:?}");
+
+    let wildcard = type_errors(
+        r#"
+module test.render
+use aivi
+use aivi.i18n
+
+x = render ~m"{count, plural, one {# item} other {# items}}" { count: "three" }
+"#,
+    );
+    assert!(
+        wildcard.iter().any(|msg| msg.contains("must be numeric")),
+        "expected a numeric plural argument error, got: {wildcard:?}"
+    );
+}
+
+#[test]
+fn i18n_codegen_accepts_plural_messages() {
+    let source = generate_i18n_module_from_properties(
+        "app.i18n.ru",
+        "ru",
+        "app.files = {count, plural, one {# файл} few {# файла} other {# файлов}}\n",
+    )
+    .expect("generate module");
+    assert!(source.contains("i18n.renderIn locale msg args"));
+
+    let err = generate_i18n_module_from_properties(
+        "app.i18n.ru",
+        "ru",
+        "app.files = {count, plural, one {# файл}}\n",
+    )
+    .expect_err("missing 'other' arm");
+    assert!(err.starts_with("line 1:"), "unexpected error: {err}");
+}
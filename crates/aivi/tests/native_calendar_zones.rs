@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use aivi::{compile_rust_native, desugar_target};
+use tempfile::tempdir;
+
+#[test]
+fn native_codegen_converts_between_time_zones() {
+    let dir = tempdir().expect("tempdir");
+    let source_path = dir.path().join("main.aivi");
+    std::fs::write(
+        &source_path,
+        r#"module app.main
+main : Effect Text Unit
+main = effect {
+  newYork = calendar.timeZone "America/New_York" or { id: "UTC" }
+  standup = calendar.fromLocal newYork { date: { year: 2024, month: 3, day: 9 }, time: { hour: 9, minute: 0, second: 0 } }
+  skipped = calendar.fromLocal newYork { date: { year: 2024, month: 3, day: 10 }, time: { hour: 2, minute: 30, second: 0 } }
+  _ <- println (calendar.zonedText standup)
+  _ <- println (calendar.zonedText skipped)
+  _ <- println (calendar.instantText (calendar.toInstant standup))
+  tokyo = calendar.timeZone "Asia/Tokyo" or { id: "UTC" }
+  _ <- println (calendar.zonedText (calendar.atZone tokyo 2024-05-21T12:00:00Z))
+  _ <- println (calendar.localDateTimeText (calendar.parseLocalDateTime "2024-02-29 07:05" or standup.dateTime))
+  pure Unit
+}
+"#,
+    )
+    .expect("write aivi source");
+
+    let source_path_str = source_path.to_string_lossy().to_string();
+    let program = desugar_target(&source_path_str).expect("desugar");
+    let rust = compile_rust_native(program).expect("compile_rust_native");
+
+    let cargo_toml = format!(
+        "[package]\nname = \"aivi-native-calendar-zones\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\naivi_native_runtime = {{ path = {:?} }}\n",
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../aivi_native_runtime")
+            .display()
+            .to_string()
+    );
+    std::fs::write(dir.path().join("Cargo.toml"), cargo_toml).expect("write Cargo.toml");
+    let src_dir = dir.path().join("src");
+    std::fs::create_dir_all(&src_dir).expect("create src dir");
+    std::fs::write(src_dir.join("main.rs"), rust).expect("write main.rs");
+
+    let output = Command::new("cargo")
+        .arg("run")
+        .arg("--quiet")
+        .arg("--offline")
+        .env("RUSTFLAGS", "-Awarnings")
+        .current_dir(dir.path())
+        .output()
+        .expect("cargo run");
+    assert!(
+        output.status.success(),
+        "cargo run failed\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for want in [
+        "2024-03-09T09:00:00-05:00[America/New_York]",
+        "2024-03-10T03:30:00-04:00[America/New_York]",
+        "2024-03-09T14:00:00Z",
+        "2024-05-21T21:00:00+09:00[Asia/Tokyo]",
+        "2024-02-29T07:05:00",
+    ] {
+        assert!(
+            stdout.lines().any(|l| l.trim() == want),
+            "stdout missing line {want:?}\nstdout:\n{stdout}"
+        );
+    }
+}
@@ -4,21 +4,10 @@ use std::process::Command;
 use aivi::{compile_rust_native, desugar_target};
 use tempfile::tempdir;
 
-#[test]
-fn native_codegen_smoke_compiles_and_runs() {
+fn compile_and_run(source: &str) -> String {
     let dir = tempdir().expect("tempdir");
     let source_path = dir.path().join("main.aivi");
-    std::fs::write(
-        &source_path,
-        r#"module app.main
-main : Effect Text Unit
-main = effect {
-  _ <- print "Hello from AIVI!"
-  pure Unit
-}
-"#,
-    )
-    .expect("write aivi source");
+    std::fs::write(&source_path, source).expect("write aivi source");
 
     let source_path_str = source_path.to_string_lossy().to_string();
     let program = desugar_target(&source_path_str).expect("desugar");
@@ -52,9 +41,52 @@ main = effect {
         String::from_utf8_lossy(&output.stdout),
         String::from_utf8_lossy(&output.stderr),
     );
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn native_codegen_smoke_compiles_and_runs() {
+    let stdout = compile_and_run(
+        r#"module app.main
+main : Effect Text Unit
+main = effect {
+  _ <- print "Hello from AIVI!"
+  pure Unit
+}
+"#,
+    );
     assert!(
         stdout.contains("Hello from AIVI!"),
         "unexpected stdout: {stdout}"
     );
 }
+
+#[test]
+fn native_codegen_runs_recursive_block_bindings() {
+    let stdout = compile_and_run(
+        r#"module app.main
+
+partials = generate {
+  upTo = n => if n == 0 then 0 else n + upTo (n - 1)
+  yield upTo 3
+  yield upTo 4
+}
+
+main : Effect Text Unit
+main = effect {
+  go = n => if n > 10 then 0 else n + go (n + 1)
+  isEven = n => if n == 0 then True else isOdd (n - 1)
+  isOdd = n => if n == 0 then False else isEven (n - 1)
+  parity = if isEven 10 && isOdd 7 then "ok" else "wrong"
+  add = acc => x => acc + x
+  total = partials add 0
+  _ <- print "sum={go 1} parity={parity} partials={total}"
+  pure Unit
+}
+"#,
+    );
+    assert!(
+        stdout.contains("sum=55 parity=ok partials=16"),
+        "unexpected stdout: {stdout}"
+    );
+}
@@ -16,7 +16,16 @@ main = effect {
   k = ~k"app.welcome"
   msg = ~m"Hello, {name:Text}!"
   rendered = i18n.render msg { name: "Alice" } or "ERR"
+  files = ~m"{count, plural, =0 {no files} one {# file} other {# files}}"
+  ru = { language: "ru", region: None, variants: [], tag: "ru" }
+  days = ~m"{n, plural, one {# день} few {# дня} many {# дней} other {# дня}}"
   _ <- println rendered
+  _ <- println (i18n.render files { count: 0 } or "ERR")
+  _ <- println (i18n.render files { count: 2 } or "ERR")
+  _ <- println (i18n.renderIn ru days { n: 3 } or "ERR")
+  de = { language: "de", region: None, variants: [], tag: "de" }
+  price = ~m"Preis: {amount, number, currency/EUR}"
+  _ <- println (i18n.renderIn de price { amount: 1234.5 } or "ERR")
   _ <- println (k.body)
   pure Unit
 }
@@ -56,7 +65,14 @@ main = effect {
     );
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    for want in ["Hello, Alice!", "app.welcome"] {
+    for want in [
+        "Hello, Alice!",
+        "no files",
+        "2 files",
+        "3 дня",
+        "Preis: 1.234,50\u{a0}€",
+        "app.welcome",
+    ] {
         assert!(
             stdout.lines().any(|l| l.trim() == want),
             "stdout missing line {want:?}\nstdout:\n{stdout}"
@@ -0,0 +1,260 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn write_file(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create parent dirs");
+    }
+    fs::write(path, contents).expect("write file");
+}
+
+fn aivi_exe() -> &'static str {
+    env!("CARGO_BIN_EXE_aivi")
+}
+
+fn write_project_with_path_dep(temp: &Path) -> std::path::PathBuf {
+    let root = temp.join("app");
+    let dep = temp.join("greeting");
+
+    write_file(
+        &root.join("aivi.toml"),
+        r#"[project]
+kind = "bin"
+entry = "main.aivi"
+language_version = "0.1"
+"#,
+    );
+    write_file(
+        &root.join("Cargo.toml"),
+        &format!(
+            r#"[package]
+name = "demo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+greeting = {{ path = {:?} }}
+"#,
+            dep.display().to_string()
+        ),
+    );
+    write_file(&root.join("src/lib.rs"), "");
+    write_file(
+        &root.join("src/main.aivi"),
+        r#"module app.main
+use greeting.hello (hello)
+
+main : Text
+main = hello
+"#,
+    );
+
+    write_file(
+        &dep.join("Cargo.toml"),
+        r#"[package]
+name = "greeting"
+version = "0.2.0"
+edition = "2021"
+
+[package.metadata.aivi]
+language_version = "0.1"
+kind = "lib"
+entry = "src/lib.aivi"
+"#,
+    );
+    write_file(&dep.join("src/lib.rs"), "");
+    write_file(
+        &dep.join("src/lib.aivi"),
+        r#"module greeting.hello
+export hello
+
+hello : Text
+hello = "hi"
+"#,
+    );
+    root
+}
+
+#[test]
+fn hash_aivi_sources_is_stable_and_content_sensitive() {
+    let temp = TempDir::new().expect("tempdir");
+    let dir = temp.path().join("src");
+    write_file(&dir.join("a.aivi"), "module a\n");
+    write_file(&dir.join("nested/b.aivi"), "module b\n");
+    write_file(&dir.join("ignored.rs"), "fn main() {}\n");
+
+    let first = aivi::hash_aivi_sources(&dir).expect("hash");
+    let second = aivi::hash_aivi_sources(&dir).expect("hash");
+    assert_eq!(first, second);
+    assert_eq!(first.len(), 64);
+
+    write_file(&dir.join("ignored.rs"), "fn other() {}\n");
+    assert_eq!(aivi::hash_aivi_sources(&dir).expect("hash"), first);
+
+    write_file(&dir.join("nested/b.aivi"), "module b\n\nx = 1\n");
+    assert_ne!(aivi::hash_aivi_sources(&dir).expect("hash"), first);
+}
+
+#[test]
+fn verify_locked_dependencies_rejects_unvendored_and_tampered_sources() {
+    let temp = TempDir::new().expect("tempdir");
+    let root = temp.path();
+    let vendored = root.join("vendor/aivi/dep");
+    write_file(&vendored.join("lib.aivi"), "module dep\n");
+    let hash = aivi::hash_aivi_sources(&vendored).expect("hash");
+
+    let mut lock = aivi::AiviLock {
+        version: 1,
+        language_version: Some("0.1".to_string()),
+        cargo_vendored: None,
+        packages: vec![aivi::AiviLockPackage {
+            name: "dep".to_string(),
+            version: "1.0.0".to_string(),
+            language_version: "0.1".to_string(),
+            source: "path+/elsewhere/dep".to_string(),
+            sources_sha256: hash,
+            vendored: None,
+        }],
+    };
+    let lock_path = root.join(aivi::AIVI_LOCK_FILE);
+    aivi::write_aivi_lock(&lock_path, &lock).expect("write lock");
+    assert!(aivi::verify_locked_dependencies(root, Some("0.1")).is_err());
+
+    lock.packages[0].vendored = Some("vendor/aivi/dep".to_string());
+    aivi::write_aivi_lock(&lock_path, &lock).expect("write lock");
+    assert_eq!(aivi::read_aivi_lock(&lock_path).expect("read lock"), lock);
+    let dirs = aivi::verify_locked_dependencies(root, Some("0.1")).expect("verified");
+    assert_eq!(dirs, vec![vendored.clone()]);
+
+    assert!(aivi::verify_locked_dependencies(root, Some("0.2")).is_err());
+
+    write_file(&vendored.join("lib.aivi"), "module dep\n\nx = 1\n");
+    assert!(aivi::verify_locked_dependencies(root, Some("0.1")).is_err());
+}
+
+#[test]
+fn vendor_then_offline_check_uses_vendored_sources() {
+    let temp = TempDir::new().expect("tempdir");
+    let root = write_project_with_path_dep(temp.path());
+
+    let exe = aivi_exe();
+
+    let output = Command::new(exe)
+        .arg("check")
+        .arg("--offline")
+        .arg("src/main.aivi")
+        .current_dir(&root)
+        .output()
+        .expect("run aivi check");
+    assert!(
+        !output.status.success(),
+        "check without aivi.lock must fail"
+    );
+
+    let output = Command::new(exe)
+        .arg("vendor")
+        .current_dir(&root)
+        .output()
+        .expect("run aivi vendor");
+    assert!(
+        output.status.success(),
+        "vendor failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(root.join("vendor/aivi/greeting/lib.aivi").exists());
+
+    let lock = aivi::read_aivi_lock(&root.join("aivi.lock")).expect("read lock");
+    assert_eq!(lock.language_version.as_deref(), Some("0.1"));
+    assert_eq!(lock.packages.len(), 1);
+    assert_eq!(lock.packages[0].name, "greeting");
+    assert_eq!(lock.packages[0].version, "0.2.0");
+    assert_eq!(lock.packages[0].source, "path+../greeting");
+    assert_eq!(
+        lock.packages[0].vendored.as_deref(),
+        Some("vendor/aivi/greeting")
+    );
+    assert_eq!(lock.cargo_vendored.as_deref(), Some("vendor/cargo"));
+    let cargo_args = aivi::locked_cargo_source_args(&root).expect("cargo source args");
+    assert!(cargo_args
+        .iter()
+        .any(|arg| arg == "source.crates-io.replace-with=\"aivi-vendored\""));
+    assert!(cargo_args
+        .iter()
+        .any(|arg| arg.starts_with("source.aivi-vendored.directory=")
+            && arg.contains("vendor/cargo")));
+
+    // The original dependency is no longer needed once vendored.
+    fs::remove_dir_all(temp.path().join("greeting")).expect("remove dep");
+
+    let output = Command::new(exe)
+        .arg("check")
+        .arg("--offline")
+        .arg("src/main.aivi")
+        .current_dir(&root)
+        .output()
+        .expect("run aivi check");
+    assert!(
+        output.status.success(),
+        "offline check failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // `build --offline` compiles against the vendored modules; `--help` stops Cargo before it
+    // compiles the generated crate.
+    let output = Command::new(exe)
+        .args(["build", "--offline", "--", "--help"])
+        .current_dir(&root)
+        .output()
+        .expect("run aivi build");
+    assert!(
+        output.status.success(),
+        "offline build failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let generated =
+        fs::read_to_string(root.join("target/aivi-gen/src/main.rs")).expect("generated main.rs");
+    assert!(generated.contains("hi"));
+
+    write_file(
+        &root.join("vendor/aivi/greeting/lib.aivi"),
+        "module greeting.hello\nexport hello\n\nhello : Text\nhello = \"changed\"\n",
+    );
+    let output = Command::new(exe)
+        .arg("check")
+        .arg("--locked")
+        .arg("src/main.aivi")
+        .current_dir(&root)
+        .output()
+        .expect("run aivi check");
+    assert!(!output.status.success(), "tampered vendor dir must fail");
+}
+
+#[test]
+fn locked_cargo_source_args_write_the_vendor_dir_as_toml() {
+    let temp = TempDir::new().expect("tempdir");
+    let root = temp.path().join("we\"ird\\app");
+    fs::create_dir_all(root.join("vendor/cargo")).expect("vendor dir");
+    let lock = aivi::AiviLock {
+        version: 1,
+        language_version: None,
+        cargo_vendored: Some("vendor/cargo".to_string()),
+        packages: Vec::new(),
+    };
+    aivi::write_aivi_lock(&root.join(aivi::AIVI_LOCK_FILE), &lock).expect("write lock");
+
+    let args = aivi::locked_cargo_source_args(&root).expect("cargo source args");
+    let directory = args
+        .iter()
+        .find_map(|arg| arg.strip_prefix("source.aivi-vendored.directory="))
+        .expect("directory arg");
+    let parsed: toml::Value =
+        toml::from_str(&format!("directory = {directory}")).expect("TOML value");
+    let expected = root.join("vendor/cargo").canonicalize().expect("canonical");
+    assert_eq!(
+        parsed["directory"].as_str(),
+        Some(expected.to_string_lossy().as_ref())
+    );
+}
@@ -180,6 +180,90 @@ updated = addWeek { year: 2024, month: 9, day: 1 }"#;
     check_ok(source);
 }
 
+#[test]
+fn typecheck_calendar_deltas_and_zones() {
+    let source = r#"
+module test.calendar
+export nextStandup, lateLunch, later, deadline, stamp, local
+
+use aivi.calendar
+
+newYork : TimeZone
+newYork = timeZone "America/New_York" or utc
+
+standup : ZonedDateTime
+standup = fromLocal newYork { date: { year: 2024, month: 3, day: 9 }, time: { hour: 9, minute: 0, second: 0 } }
+
+nextStandup : ZonedDateTime
+nextStandup = standup + 1d
+
+lateLunch : Time
+lateLunch = { hour: 12, minute: 30, second: 0 } + 90min
+
+later : LocalDateTime
+later = standup.dateTime - 2h
+
+deadline : Instant
+deadline = fromDateTime 2024-01-01T00:00:00Z + 36h
+
+stamp : Text
+stamp = zonedText (withZone utc nextStandup)
+
+local : Result Text LocalDateTime
+local = parseLocalDateTime "2025-01-31T23:30:00""#;
+    check_ok_with_embedded(source, &["aivi", "aivi.calendar"]);
+}
+
+#[test]
+fn typecheck_calendar_deltas_reject_non_calendar_operands() {
+    for body in ["x : Int\nx = 5 + 1d", "x = \"hello\" + 1h", "x = 2.5 - 1m"] {
+        let source = format!("module test.calendarBad\nexport x\n\nuse aivi.calendar\n\n{body}");
+        check_err_with_embedded(&source, &["aivi", "aivi.calendar"]);
+    }
+}
+
+#[test]
+fn typecheck_instant_is_distinct_from_date_time() {
+    let source = r#"
+module test.instantOpaque
+export start
+
+use aivi.calendar
+
+start : Instant
+start = 2024-01-01T00:00:00Z"#;
+    check_err_with_embedded(source, &["aivi", "aivi.calendar"]);
+}
+
+#[test]
+fn typecheck_calendar_shift_is_polymorphic_per_use() {
+    let source = r#"
+module test.calendarShift
+export tomorrow, later
+
+use aivi.calendar
+
+tomorrow = calendar.shift { year: 2024, month: 3, day: 9 } 1d
+later = calendar.shift 2024-01-01T00:00:00Z 36h"#;
+    check_ok_with_embedded(source, &["aivi", "aivi.calendar"]);
+}
+
+#[test]
+fn typecheck_domain_members_follow_their_own_signature() {
+    let source = r#"
+module test.durationSigs
+export later, total
+
+use aivi.duration
+
+later : Span
+later = { millis: 500 } + 2s
+
+total : Span
+total = later + { millis: 250 }"#;
+    check_ok_with_embedded(source, &["aivi", "aivi.duration"]);
+}
+
 #[test]
 fn typecheck_html_sigil_vdom_and_layout_units() {
     let source = r#"
@@ -195,7 +279,10 @@ node =
       <span>{ TextNode "1" }</span>
     </div>
   <~html"#;
-    check_ok_with_embedded(source, &["aivi", "aivi.ui", "aivi.ui.layout"]);
+    check_ok_with_embedded(
+        source,
+        &["aivi", "aivi.duration", "aivi.ui", "aivi.ui.layout"],
+    );
 }
 
 #[test]
@@ -213,7 +300,334 @@ node =
       { "hallo" }
     </div>
   <~html"#;
-    check_ok_with_embedded(source, &["aivi", "aivi.ui", "aivi.ui.layout"]);
+    check_ok_with_embedded(
+        source,
+        &["aivi", "aivi.duration", "aivi.ui", "aivi.ui.layout"],
+    );
+}
+
+#[test]
+fn typecheck_live_app_commands_and_subscriptions() {
+    let source = r#"
+module test.ui_live_app
+export main
+
+use aivi
+use aivi.ui
+
+Msg = Loaded (Result Text Int) | Tick | Refresh
+Model = { count: Int, ticking: Bool }
+
+fetchCount : Effect Text Int
+fetchCount = pure 41
+
+init : (Model, Cmd Msg)
+init = ({ count: 0, ticking: True }, cmdPerform Loaded fetchCount)
+
+view : Model -> VNode Msg
+view = model => vElement "button" [vOnClick Refresh] [vText "{model.count}"]
+
+update : Msg -> Model -> (Model, Cmd Msg)
+update = msg model => msg ?
+  | Loaded (Ok n)  => ({ count: n, ticking: model.ticking }, cmdNone)
+  | Loaded (Err _) => (model, cmdNone)
+  | Tick           => ({ count: model.count + 1, ticking: model.count < 45 }, cmdNone)
+  | Refresh        => (model, cmdBatch [cmdPerform Loaded fetchCount])
+
+subscriptions : Model -> List (Sub Msg)
+subscriptions = model => model.ticking ?
+  | True  => [subEvery { millis: 1000 } Tick]
+  | False => []
+
+main : Effect LiveError Server
+main = liveApp { address: "127.0.0.1:3000", path: "/", title: "Counter" } init view update subscriptions"#;
+    check_ok_with_embedded(source, &["aivi", "aivi.duration", "aivi.ui"]);
+}
+
+#[test]
+fn typecheck_live_form_events() {
+    let source = r#"
+module test.ui_form
+export view
+
+use aivi
+use aivi.ui
+
+Msg = Save { } | Edit Text | Toggle Bool | Key KeyEvent | Focused | Left
+
+view : { title: Text, done: Bool } -> VNode Msg
+view = draft => ~html~>
+  <form onSubmit={ fields => Save fields }>
+    <input name="title" value={ draft.title } onInput={ Edit } onChange={ Edit } debounce="250" />
+    <input type="checkbox" checked={ draft.done } onCheck={ Toggle } throttle={ 100 } />
+    <input onKeyDown={ Key } onFocus={ Focused } onBlur={ Left } checked />
+  </form>
+<~html"#;
+    check_ok_with_embedded(source, &["aivi", "aivi.duration", "aivi.ui"]);
+}
+
+#[test]
+fn typecheck_static_site_pages() {
+    let source = r#"
+module test.ui_ssg
+export site
+
+use aivi
+use aivi.ui
+use aivi.ui.ssg
+
+home : Page Unit
+home = withStyle ".hero" { color: "red" } (withMeta "description" "Docs" (page "/" "Home" (vText "hi")))
+
+site : Effect SsgError (List Text)
+site = effect {
+  report <- build { outDir: "site", hydrate: Some "/live" } [home, page "/about" "About" (vText "about")]
+  pure report.files
+}
+"#;
+    check_ok_with_embedded(source, &["aivi", "aivi.duration", "aivi.ui", "aivi.ui.ssg"]);
+}
+
+#[test]
+fn typecheck_http_server_routing() {
+    let source = r#"
+module test.http_routing
+export app
+
+use aivi
+use aivi.net.http_server
+
+showUser : List PathParam -> Handler
+showUser = params req => paramInt "id" params ?
+  | Some id => pure (Http { status: 200, headers: [{ name: "x-user", value: "{id}" }], body: emptyBody })
+  | None => pure (Http { status: 400, headers: [], body: emptyBody })
+
+search : List PathParam -> Handler
+search = params req => pure (Http { status: 200, headers: query req, body: emptyBody })
+
+userName : List Field -> Text
+userName = fields => lookupField "name" fields ?
+  | Some name => name
+  | None => ""
+
+signIn : Text -> Effect HttpError ServerReply
+signIn = name => setCookie (sessionCookie "user" name) ?
+  | Ok header => pure (Http { status: 204, headers: [header], body: emptyBody })
+  | Err e => fail e
+
+login : List PathParam -> Handler
+login = params req => form req ?
+  | Ok fields => signIn (userName fields)
+  | Err e => pure (Http { status: 400, headers: [], body: emptyBody })
+
+signedIn : Request -> Effect HttpError Bool
+signedIn = req => pure (cookie "user" req != None)
+
+corsConfig : CorsConfig
+corsConfig = { origins: ["*"], methods: ["GET", "POST"], headers: [], credentials: False, maxAge: None }
+
+app : Handler
+app = middleware [logging, cors corsConfig, compression, auth signedIn] (router [onGet "/users/:id:Int" showUser, onGet "/search" search, onPost "/login" login, staticDir "/assets" "public"])
+"#;
+    check_ok_with_embedded(
+        source,
+        &[
+            "aivi",
+            "aivi.text",
+            "aivi.net.sockets",
+            "aivi.net.http_server",
+        ],
+    );
+}
+
+#[test]
+fn typecheck_http_server_streaming_bodies() {
+    let source = r#"
+module test.http_streaming
+export serve, publish
+
+use aivi
+use aivi.text (toBytes, Utf8)
+use aivi.net.http_server
+use aivi.net.streams
+
+upload : List PathParam -> Handler
+upload = params req => effect {
+  body <- readBody 1048576 req
+  pure (Http { status: 200, headers: [], body: Buffered body })
+}
+
+echo : List PathParam -> Handler
+echo = params req => pure (Http { status: 200, headers: [], body: req.body })
+
+download : List PathParam -> Handler
+download = params req => pure (Http { status: 200, headers: [], body: Streamed (chunks 4096 (fromChunks [toBytes Utf8 "a", toBytes Utf8 "b"])) })
+
+publish : Send SseEvent -> Effect Text Unit
+publish = tx => channel.send tx { event: Some "tick", data: "1", id: None, retry: None }
+
+events : Recv SseEvent -> List PathParam -> Handler
+events = rx params req => pure (Http (sse rx))
+
+options : ServerOptions
+options = { maxBodyBytes: 1048576, streamRequestBodies: True }
+
+serve : Recv SseEvent -> Resource HttpError Server
+serve = rx => listenWith { address: "127.0.0.1:8080" } options (router [onPost "/upload" upload, onPost "/echo" echo, onGet "/download" download, onGet "/events" (events rx)])
+"#;
+    check_ok_with_embedded(
+        source,
+        &[
+            "aivi",
+            "aivi.text",
+            "aivi.net.sockets",
+            "aivi.net.http_server",
+            "aivi.net.streams",
+        ],
+    );
+}
+
+#[test]
+fn typecheck_tls_server_and_sockets() {
+    let source = r#"
+module test.tls
+
+use aivi
+use aivi.text
+use aivi.net.sockets
+use aivi.net.http_server
+
+certs : TlsServerConfig
+certs = { certFile: "cert.pem", keyFile: "key.pem", clientCaFile: Some "ca.pem" }
+
+hello : Handler
+hello = req => pure (Http { status: 200, headers: [], body: textBody "hi" })
+
+https : Resource HttpError Server
+https = listenHttps { address: "0.0.0.0:8443" } certs defaultServerOptions hello
+
+secureListener : Resource SocketError Listener
+secureListener = listenTls { host: "0.0.0.0", port: 9443 } certs
+
+client : TlsClientConfig
+client = { serverName: Some "example.test", caFile: Some "ca.pem", clientCert: Some { certFile: "client.pem", keyFile: "client-key.pem" } }
+
+dial : Effect SocketError Connection
+dial = connectTls { host: "127.0.0.1", port: 9443 } client
+
+dialPublic : Effect SocketError Connection
+dialPublic = connectTls { host: "93.184.215.14", port: 443 } (defaultTlsClientConfig <| { serverName: Some "example.com" })
+"#;
+    check_ok_with_embedded(
+        source,
+        &[
+            "aivi",
+            "aivi.text",
+            "aivi.net.sockets",
+            "aivi.net.http_server",
+        ],
+    );
+}
+
+#[test]
+fn typecheck_http_client() {
+    let source = r#"
+module test.http_client
+
+use aivi
+use aivi.text
+use aivi.url
+use aivi.net.http
+use aivi.database.pool (Exponential)
+
+config : ClientConfig
+config = defaultClientConfig <| {
+  timeout: { millis: 5000 }
+  retry: { maxRetries: 3, backoff: Exponential { base: { millis: 100 }, max: { millis: 2000 } } }
+  userAgent: Some "aivi-test"
+}
+
+upload : Url -> ClientRequest
+upload = target => request "POST" target <| {
+  body: MultipartBody [{ name: "file", fileName: Some "a.bin", contentType: None, data: toBytes Utf8 "abc" }]
+  timeout: Some { millis: 1000 }
+}
+
+login : Url -> ClientRequest
+login = target => request "POST" target <| { body: FormBody [{ name: "user", value: "ada" }] }
+
+run : Url -> Effect Text (Result Error ClientResponse)
+run = target => effect {
+  c <- client config
+  _ <- execute c (login target)
+  jar <- cookiesFor c target
+  execute c (upload target)
+}
+"#;
+    // The embedded `aivi.duration` domain does not check on its own, so, as in `aivi check`,
+    // only diagnostics from the test module are considered.
+    let mut modules = Vec::new();
+    for module_name in [
+        "aivi",
+        "aivi.text",
+        "aivi.url",
+        "aivi.duration",
+        "aivi.database",
+        "aivi.database.pool",
+        "aivi.net.http",
+    ] {
+        let embedded_source = embedded_stdlib_source(module_name).expect(module_name);
+        let (mut embedded_modules, _) = parse_modules(
+            Path::new(&format!("<embedded:{module_name}>")),
+            embedded_source,
+        );
+        modules.append(&mut embedded_modules);
+    }
+    let (mut user_modules, diagnostics) = parse_modules(Path::new("test.aivi"), source);
+    assert!(diagnostics.is_empty(), "parse diagnostics: {diagnostics:?}");
+    modules.append(&mut user_modules);
+    let mut module_diags = check_modules(&modules);
+    module_diags.extend(check_types(&modules));
+    module_diags.retain(|diag| !diag.path.starts_with("<embedded:"));
+    assert!(
+        !file_diagnostics_have_errors(&module_diags),
+        "unexpected errors: {module_diags:?}"
+    );
+}
+
+#[test]
+fn typecheck_sockets_udp_unix_and_bytes() {
+    let source = r#"
+module test.sockets
+
+use aivi
+use aivi.text
+use aivi.net.sockets
+
+statsd : Address
+statsd = { host: "127.0.0.1", port: 8125 }
+
+emit : Text -> Effect SocketError Unit
+emit = line => effect {
+  socket <- bind { host: "0.0.0.0", port: 0 }
+  _ <- joinMulticast socket "239.1.2.3"
+  sendTo socket statsd (toBytes Utf8 line)
+}
+
+receive : UdpSocket -> Effect SocketError Address
+receive = socket => effect {
+  datagram <- recvFrom socket
+  pure datagram.from
+}
+
+ipc : Text -> Effect SocketError Bytes
+ipc = path => effect {
+  conn <- connectUnix path
+  _ <- send conn (toBytes Utf8 "ping")
+  recv conn
+}
+"#;
+    check_ok_with_embedded(source, &["aivi", "aivi.text", "aivi.net.sockets"]);
 }
 
 #[test]
@@ -578,6 +992,63 @@ badRename = { name: 1 }"#;
     check_err(source);
 }
 
+#[test]
+fn typecheck_row_variables_keep_extra_fields() {
+    let source = r#"
+module test.row_vars
+export email, age, label, shortName, names
+
+withAge : { name: Text | r } -> { name: Text, age: Int | r }
+withAge = u => { ...u, age: 30 }
+
+nameOf = u => u.name
+bump = u => u <| { age: _ + 1 }
+
+short : Omit (email) { name: Text, email: Text | r } -> { name: Text | r }
+short = u => u
+
+full = withAge { name: "Ada", email: "ada@example.com" }
+email = full.email
+age = full.age
+label = (bump { age: 2, label: "x" }).label
+shortName = (short { name: "Ada", email: "a", id: 1 }).id
+names = [nameOf { name: "x", id: 1 }, nameOf { name: "y" }]"#;
+    check_ok(source);
+}
+
+#[test]
+fn typecheck_row_mismatch_names_missing_and_extra_fields() {
+    let source = r#"
+module test.row_errors
+
+withAge : { name: Text | r } -> { name: Text, age: Int | r }
+withAge = u => { ...u, age: 30 }
+
+lost = (withAge { name: "Ada" }).email
+people = [{ name: "Ada", age: 36 }, { name: "Alan", agee: 41 }]"#;
+    let (modules, diagnostics) = parse_modules(Path::new("test.aivi"), source);
+    assert!(diagnostics.is_empty(), "parse diagnostics: {diagnostics:?}");
+
+    let mut module_diags = check_modules(&modules);
+    module_diags.extend(check_types(&modules));
+    let messages: Vec<&str> = module_diags
+        .iter()
+        .map(|d| d.diagnostic.message.as_str())
+        .collect();
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.starts_with("unexpected field 'email' (expected { age: Int, name: Text }")),
+        "expected the extra label to be named, got: {messages:?}"
+    );
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.starts_with("missing field 'agee'; unexpected field 'age'")),
+        "expected both labels to be named, got: {messages:?}"
+    );
+}
+
 #[test]
 fn typecheck_type_classes_missing_instance_errors() {
     let source = r#"
@@ -666,6 +1137,222 @@ value = Some 1 ?
     );
 }
 
+fn match_diagnostics(source: &str) -> Vec<(String, String)> {
+    let (modules, diagnostics) = parse_modules(Path::new("test.aivi"), source);
+    assert!(
+        !file_diagnostics_have_errors(&diagnostics),
+        "parse errors: {diagnostics:?}"
+    );
+    let mut module_diags = check_modules(&modules);
+    module_diags.extend(check_types(&modules));
+    module_diags
+        .into_iter()
+        .filter(|d| d.diagnostic.code == "E3100" || d.diagnostic.code == "W3101")
+        .map(|d| (d.diagnostic.code, d.diagnostic.message))
+        .collect()
+}
+
+#[test]
+fn typecheck_exhaustiveness_reports_nested_missing_patterns() {
+    let source = r#"
+module test.match_nested
+
+Option A = None | Some A
+Result E A = Err E | Ok A
+
+nested = x => x ?
+  | Some (Ok n) => n
+  | None => 0
+
+pairs = p => p ?
+  | (True, _) => 1
+  | (_, False) => 2
+
+lists =
+  | [] => 0
+  | [x] => x
+
+literals = n => n ?
+  | 0 => 1
+  | 1 => 2
+
+guarded =
+  | (Some n) when n > 0 => n
+  | None => 0
+
+fromSome = (Some x) => x
+"#;
+    let messages: Vec<String> = match_diagnostics(source)
+        .into_iter()
+        .map(|(code, message)| format!("{code} {message}"))
+        .collect();
+    for expected in [
+        "E3100 non-exhaustive match (missing: Some (Err _))",
+        "E3100 non-exhaustive match (missing: (False, True))",
+        "E3100 non-exhaustive match (missing: [_, _, ..._])",
+        "E3100 non-exhaustive match (missing: _)",
+        "E3100 non-exhaustive match (missing: Some _)",
+        "E3100 non-exhaustive lambda parameters (missing: None)",
+    ] {
+        assert!(
+            messages.iter().any(|message| message == expected),
+            "expected `{expected}`, got: {messages:?}"
+        );
+    }
+    assert_eq!(messages.len(), 6, "{messages:?}");
+}
+
+#[test]
+fn typecheck_exhaustiveness_accepts_complete_nested_matches() {
+    let source = r#"
+module test.match_complete
+
+Option A = None | Some A
+Result E A = Err E | Ok A
+
+nested = x => x ?
+  | Some (Ok n) => n
+  | Some (Err _) => 0
+  | None => 0
+
+zipped = p => p ?
+  | ([], _) => 0
+  | (_, []) => 0
+  | ([x, ...], [y, ...]) => x + y
+
+records = r => r ?
+  | { flag: True, n } => n
+  | { flag: False } => 0
+"#;
+    let found = match_diagnostics(source);
+    assert!(found.is_empty(), "unexpected diagnostics: {found:?}");
+}
+
+#[test]
+fn typecheck_redundant_nested_arm_is_warning() {
+    let source = r#"
+module test.match_redundant
+
+Option A = None | Some A
+
+value = x => x ?
+  | Some _ => 1
+  | None => 0
+  | Some 3 => 2
+
+records = r => r ?
+  | { kind: "a" } => 1
+  | { kind: _ } => 2
+  | { kind: "b" } => 3
+"#;
+    let found = match_diagnostics(source);
+    assert_eq!(
+        found.iter().filter(|(code, _)| code == "W3101").count(),
+        2,
+        "{found:?}"
+    );
+    assert!(!found.iter().any(|(code, _)| code == "E3100"), "{found:?}");
+}
+
+#[test]
+fn typecheck_exhaustiveness_checks_clauses_together() {
+    let source = r#"
+module test.match_clauses
+
+Shape = Circle Int | Square Int | Triangle Int
+
+area = (Circle r) => r * r * 3
+area = (Square s) => s * s
+area = (Triangle b) => b
+
+perimeter = (Circle r) => r * 6
+perimeter = (Square s) => s * 4
+perimeter = (Circle _) => 0
+"#;
+    let found = match_diagnostics(source);
+    let messages: Vec<String> = found
+        .iter()
+        .map(|(code, message)| format!("{code} {message}"))
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "W3101 unreachable clause of 'perimeter' (previous clauses already match its arguments)"
+                .to_string(),
+            "E3100 non-exhaustive clauses for 'perimeter' (missing: Triangle _)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn typecheck_exhaustiveness_knows_constructors_of_imported_types() {
+    let lib = r#"
+module test.shapes
+export Shape, Circle, Square, Triangle
+
+Shape = Circle Int | Square Int | Triangle Int
+"#;
+    let app = r#"
+module test.app
+export area
+
+use test.shapes
+
+area : Shape -> Int
+area = shape => shape ?
+  | Circle r => r * r * 3
+  | Square s => s * s
+"#;
+    let mut modules = Vec::new();
+    for (path, source) in [("shapes.aivi", lib), ("app.aivi", app)] {
+        let (parsed, diagnostics) = parse_modules(Path::new(path), source);
+        assert!(
+            !file_diagnostics_have_errors(&diagnostics),
+            "parse errors: {diagnostics:?}"
+        );
+        modules.extend(parsed);
+    }
+    let mut module_diags = check_modules(&modules);
+    module_diags.extend(check_types(&modules));
+    let messages: Vec<String> = module_diags
+        .into_iter()
+        .filter(|d| d.diagnostic.code == "E3100" || d.diagnostic.code == "W3101")
+        .map(|d| format!("{} {}", d.diagnostic.code, d.diagnostic.message))
+        .collect();
+    assert_eq!(
+        messages,
+        vec!["E3100 non-exhaustive match (missing: Triangle _)".to_string()]
+    );
+}
+
+#[test]
+fn typecheck_exhaustiveness_uses_the_scrutinee_type_for_shared_constructor_names() {
+    let source = r#"
+module test.match_shared_names
+
+Light = Red | Green
+Paint = Blue | Green | Red
+
+light : Light -> Int
+light = l => l ?
+  | Red => 0
+  | Green => 1
+
+paint : Paint -> Int
+paint = p => p ?
+  | Red => 0
+  | Green => 1
+"#;
+    let messages: Vec<String> = match_diagnostics(source)
+        .into_iter()
+        .map(|(code, message)| format!("{code} {message}"))
+        .collect();
+    assert_eq!(
+        messages,
+        vec!["E3100 non-exhaustive match (missing: Blue)".to_string()]
+    );
+}
+
 #[test]
 fn typecheck_reports_missing_domain_operator_for_concrete_non_int_operands() {
     let source = r#"
@@ -693,3 +1380,402 @@ bad = True + False
         "expected missing domain operator message, got: {module_diags:?}"
     );
 }
+
+#[test]
+fn typecheck_deriving_generates_instances() {
+    let source = r#"
+module test.deriving
+export Shape, Point, Tree, same, before, rendered, encoded
+
+class Setoid A = {
+  equals: A -> A -> Bool
+}
+
+class Ord A = Setoid A with {
+  lte: A -> A -> Bool
+}
+
+class ToText A = {
+  toText: A -> Text
+}
+
+class ToJson A = {
+  toJson: A -> Text
+}
+
+Color = Red | Blue deriving (Setoid, Ord, ToText, ToJson)
+
+Shape = Circle Int | Tinted Color Shape | Dot
+  deriving (Setoid, Ord, ToText, ToJson)
+
+Point = { x: Int, name: Text, visible: Bool } deriving (Setoid, Ord, ToJson)
+
+Tree A = Leaf | Node (Tree A) A (Tree A) deriving (Setoid, ToText)
+
+same = equals (Node Leaf 1 Leaf) (Node Leaf 1 Leaf)
+before = lte { x: 1, name: "a", visible: True } { x: 1, name: "b", visible: False }
+rendered = toText (Tinted Red Dot)
+encoded = toJson (Circle 3)"#;
+    check_ok(source);
+}
+
+#[test]
+fn typecheck_hand_written_instance_methods_can_recurse() {
+    let source = r#"
+module test.recursive_instance
+export Nat, Tree, same, sameTree
+
+class Setoid A = {
+  equals: A -> A -> Bool
+}
+
+Nat = Zero | Succ Nat
+
+Tree = Leaf | Node Tree Int Tree
+
+instance Setoid Nat = {
+  equals: a b => (a, b) ?
+    | (Zero, Zero) => True
+    | (Succ x, Succ y) => equals x y
+    | _ => False
+}
+
+instance Setoid Tree = {
+  equals: a b => (a, b) ?
+    | (Leaf, Leaf) => True
+    | (Node l x r, Node l2 y r2) => equals l l2 && x == y && equals r r2
+    | _ => False
+}
+
+same = equals (Succ Zero) (Succ Zero)
+sameTree = equals (Node Leaf 1 Leaf) Leaf"#;
+    check_ok(source);
+}
+
+#[test]
+fn typecheck_deriving_reports_underivable_fields() {
+    let source = r#"
+module test.deriving_errors
+
+class Setoid A = {
+  equals: A -> A -> Bool
+}
+
+class Ord A = Setoid A with {
+  lte: A -> A -> Bool
+}
+
+Measure = { size: Float } deriving (Setoid, Ord)"#;
+    let (modules, diagnostics) = parse_modules(Path::new("test.aivi"), source);
+    assert!(diagnostics.is_empty(), "parse diagnostics: {diagnostics:?}");
+
+    let mut module_diags = check_modules(&modules);
+    module_diags.extend(check_types(&modules));
+    let messages: Vec<&str> = module_diags
+        .iter()
+        .map(|d| d.diagnostic.message.as_str())
+        .collect();
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.starts_with("cannot derive 'Ord' for 'Measure': no instance found")),
+        "expected the derivation to be named, got: {messages:?}"
+    );
+}
+
+#[test]
+fn typecheck_user_effects_track_and_discharge_rows() {
+    let source = r#"
+module test.user_effects
+export main
+
+effect Log = {
+  emit: Text -> Unit
+  level: Text
+}
+
+effect Clock = {
+  tick: Int
+}
+
+greet : Text -> Effect Log Text Unit
+greet = name => effect {
+  lvl <- level
+  emit "{lvl}: hello {name}"
+}
+
+stamped : Effect (Log, Clock) Text Unit
+stamped = effect {
+  t <- tick
+  greet "at {t}"
+}
+
+withClock : Effect Log Text Unit
+withClock = stamped with {
+  tick: pure 42
+}
+
+main = withClock with {
+  emit: msg => println msg
+  level: pure "info"
+}"#;
+    check_ok(source);
+}
+
+#[test]
+fn typecheck_user_effects_report_unhandled_and_mismatched_effects() {
+    let source = r#"
+module test.user_effects_errors
+export main
+
+effect Log = {
+  emit: Text -> Unit
+  level: Text
+}
+
+effect Clock = {
+  tick: Int
+}
+
+quiet : Effect Log Text Unit
+quiet = effect {
+  t <- tick
+  emit "x"
+}
+
+partial = (emit "a") with { emit: msg => println msg }
+
+stray = (emit "a") with { emit: msg => println msg, level: pure "x", other: 1 }
+
+bogus : Effect Nope Text Unit
+bogus = pure Unit
+
+helper : Effect Text Unit
+helper = effect {
+  _ <- emit "x"
+  pure Unit
+}
+
+shout : Text -> Effect Text Unit
+shout = msg => emit msg
+
+main = effect {
+  emit "hi"
+}"#;
+    let (modules, diagnostics) = parse_modules(Path::new("test.aivi"), source);
+    assert!(diagnostics.is_empty(), "parse diagnostics: {diagnostics:?}");
+
+    let mut module_diags = check_modules(&modules);
+    module_diags.extend(check_types(&modules));
+    let messages: Vec<&str> = module_diags
+        .iter()
+        .map(|d| d.diagnostic.message.as_str())
+        .collect();
+    for expected in [
+        "unknown effect 'Nope'",
+        "effects do not match: 'Clock'",
+        "handler for 'Log' is missing operation 'level'",
+        "'other' is not an effect operation",
+        "unhandled effect 'Log' in 'main'",
+        "unhandled effect 'Log' in 'helper'",
+        "unhandled effect 'Log' in 'shout'",
+    ] {
+        assert!(
+            messages.iter().any(|m| m.starts_with(expected)),
+            "expected {expected:?}, got: {messages:?}"
+        );
+    }
+}
+
+#[test]
+fn typecheck_recursive_block_bindings_generalize() {
+    let source = r#"
+module test.local_recursion
+export lengths, parity
+
+lengths : (Int, Int)
+lengths = {
+  len =
+    | []           => 0
+    | [_, ...rest] => 1 + len rest
+  (len [1, 2, 3], len ["a", "b"])
+}
+
+parity : Effect Text Bool
+parity = effect {
+  isEven = n => if n == 0 then True else isOdd (n - 1)
+  isOdd = n => if n == 0 then False else isEven (n - 1)
+  pure (isEven 10 && isOdd 3)
+}"#;
+    check_ok(source);
+}
+
+#[test]
+fn typecheck_recursive_block_bindings_check_bodies() {
+    let source = r#"
+module test.local_recursion_errors
+export bad
+
+bad = {
+  spin = n => if spin n then 1 else 2
+  spin 0
+}"#;
+    check_err(source);
+}
+
+#[test]
+fn typecheck_opaque_types_hide_their_representation() {
+    let users = r#"
+module test.users
+export UserId, userId, userIdText
+
+opaque type UserId = Text
+
+userId : Text -> UserId
+userId = raw => raw
+
+userIdText : UserId -> Text
+userIdText = id => id"#;
+    let check = |client: &str| {
+        let mut modules = Vec::new();
+        for (path, source) in [("users.aivi", users), ("client.aivi", client)] {
+            let (mut parsed, diagnostics) = parse_modules(Path::new(path), source);
+            assert!(
+                !file_diagnostics_have_errors(&diagnostics),
+                "parse errors: {diagnostics:?}"
+            );
+            modules.append(&mut parsed);
+        }
+        let mut module_diags = check_modules(&modules);
+        module_diags.extend(check_types(&modules));
+        module_diags
+            .into_iter()
+            .filter(|d| d.path == "client.aivi")
+            .map(|d| d.diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+
+    let ok = check(
+        r#"
+module test.client
+export name
+
+use test.users
+
+name : Text
+name = userIdText (userId "ada")"#,
+    );
+    assert!(ok.is_empty(), "unexpected diagnostics: {ok:?}");
+
+    let forged = check(
+        r#"
+module test.client
+export name
+
+use test.users
+
+name : Text
+name = userIdText "root""#,
+    );
+    assert!(
+        forged.iter().any(|m| m.starts_with("type mismatch")),
+        "expected a type mismatch, got: {forged:?}"
+    );
+}
+
+#[test]
+fn typecheck_typed_holes_report_type_locals_and_candidates() {
+    let source = r#"
+module test.holes
+export pick, step
+
+Option A = None | Some A
+
+class Bump A = {
+  bump: A -> A
+}
+
+instance Bump Int = {
+  bump: x => x + 1
+}
+
+pick : Option Int -> Int -> Int
+pick = opt fallback => opt ?
+  | Some x => ?found
+  | None   => fallback
+
+step : Int -> Int
+step = ?next"#;
+    let (modules, diagnostics) = parse_modules(Path::new("test.aivi"), source);
+    assert!(
+        !file_diagnostics_have_errors(&diagnostics),
+        "parse errors: {diagnostics:?}"
+    );
+    let mut module_diags = check_modules(&modules);
+    module_diags.extend(check_types(&modules));
+    let holes: Vec<_> = module_diags
+        .into_iter()
+        .filter(|d| d.diagnostic.code == "E3200")
+        .map(|d| d.diagnostic)
+        .collect();
+    assert_eq!(holes.len(), 2, "expected two holes: {holes:?}");
+
+    let found = &holes[0];
+    assert_eq!(found.message, "hole '?found' has type Int");
+    let labels: Vec<&str> = found.labels.iter().map(|l| l.message.as_str()).collect();
+    assert!(labels.contains(&"in scope: opt : Option Int"), "{labels:?}");
+    assert!(labels.contains(&"candidate: fallback : Int"), "{labels:?}");
+    assert!(labels.contains(&"candidate: x : Int"), "{labels:?}");
+    assert!(
+        !labels.iter().any(|l| l.starts_with("candidate: opt")),
+        "{labels:?}"
+    );
+
+    let next = &holes[1];
+    assert_eq!(next.message, "hole '?next' has type Int -> Int");
+    let labels: Vec<&str> = next.labels.iter().map(|l| l.message.as_str()).collect();
+    assert!(
+        labels.iter().any(|l| l.starts_with("candidate: bump :")),
+        "{labels:?}"
+    );
+    assert!(
+        !labels.iter().any(|l| l.starts_with("candidate: step")),
+        "{labels:?}"
+    );
+}
+
+#[test]
+fn resolver_allows_type_and_domain_exports_with_the_same_name() {
+    check_ok(
+        r#"
+@no_prelude
+module test.paths
+export domain Path
+export Path
+
+Path = { segments: List Text }
+
+domain Path over Path = {
+  (/) : Path -> Path -> Path
+  (/) = base other => { segments: other.segments }
+}
+"#,
+    );
+
+    let source = r#"
+@no_prelude
+module test.paths
+export Path
+export Path
+
+Path = { segments: List Text }
+"#;
+    let (modules, _) = parse_modules(Path::new("test.aivi"), source);
+    let diags = check_modules(&modules);
+    assert!(
+        diags
+            .iter()
+            .any(|d| d.diagnostic.message == "duplicate export 'Path'"),
+        "expected duplicate export, got: {diags:?}"
+    );
+}
@@ -4,32 +4,105 @@ use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
+use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
-use http_body_util::{BodyExt, Full};
-use hyper::body::{Bytes, Incoming};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited, StreamBody};
+use hyper::body::{Frame, Incoming};
 use hyper::service::service_fn;
 use hyper::{Request, Response, StatusCode};
 use hyper_tungstenite::HyperWebsocketStream;
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use hyper_util::server::conn::auto;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpListener;
 use tokio::runtime::{Handle, Runtime};
-use tokio::sync::{oneshot, Mutex as TokioMutex};
+use tokio::sync::{mpsc, oneshot, Mutex as TokioMutex};
+use tokio_rustls::TlsAcceptor;
+
+pub use hyper::body::Bytes;
+pub use tls::{TlsClientSettings, TlsServerSettings};
+
+pub mod middleware;
+pub mod net;
+pub mod routing;
+pub mod sse;
+pub mod static_files;
+pub mod tls;
 
 pub struct AiviRequest {
     pub method: String,
     pub path: String,
     pub headers: Vec<(String, String)>,
-    pub body: Vec<u8>,
+    pub body: Body,
     pub remote_addr: Option<String>,
 }
 
 pub struct AiviResponse {
     pub status: u16,
     pub headers: Vec<(String, String)>,
-    pub body: Vec<u8>,
+    pub body: Body,
+}
+
+/// Pulls the next chunk of a streamed body, blocking until it is available. `Ok(None)` ends the
+/// body. Sources are always polled from blocking threads, never from the server's reactor.
+pub type ChunkSource = Box<dyn FnMut() -> Result<Option<Bytes>, AiviHttpError> + Send>;
+
+pub enum Body {
+    Full(Bytes),
+    /// Sent with chunked transfer encoding unless the response sets `content-length`.
+    Stream(ChunkSource),
+}
+
+impl Body {
+    pub fn empty() -> Self {
+        Body::Full(Bytes::new())
+    }
+
+    /// The buffered bytes, or `None` for streamed bodies.
+    pub fn as_full(&self) -> Option<&Bytes> {
+        match self {
+            Body::Full(bytes) => Some(bytes),
+            Body::Stream(_) => None,
+        }
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(bytes: Vec<u8>) -> Self {
+        Body::Full(Bytes::from(bytes))
+    }
+}
+
+impl From<Bytes> for Body {
+    fn from(bytes: Bytes) -> Self {
+        Body::Full(bytes)
+    }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct ServerOptions {
+    /// Requests whose body exceeds this many bytes are answered with 413 (buffered bodies) or
+    /// fail the body stream (streamed bodies).
+    pub max_body_bytes: u64,
+    /// Hand request bodies to the handler as a stream instead of buffering them first.
+    pub stream_request_bodies: bool,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 16 * 1024 * 1024,
+            stream_request_bodies: false,
+        }
+    }
+}
+
+type HttpBody = UnsyncBoxBody<Bytes, AiviHttpError>;
+
+// Streamed bodies run ahead of the client by at most this many chunks.
+const STREAM_BUFFER_CHUNKS: usize = 4;
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("{message}")]
 pub struct AiviHttpError {
@@ -86,23 +159,30 @@ impl ServerHandle {
 #[derive(Clone)]
 pub struct WebSocketHandle {
     runtime: Handle,
-    socket: Arc<TokioMutex<HyperWebsocketStream>>,
+    // Reads and writes lock separate halves so a pending `recv` never blocks `send`.
+    sink: Arc<TokioMutex<WsSink>>,
+    stream: Arc<TokioMutex<WsStream>>,
 }
 
+type WsSink = SplitSink<HyperWebsocketStream, hyper_tungstenite::tungstenite::Message>;
+type WsStream = SplitStream<HyperWebsocketStream>;
+
 impl WebSocketHandle {
     fn new(runtime: Handle, socket: HyperWebsocketStream) -> Self {
+        let (sink, stream) = socket.split();
         Self {
             runtime,
-            socket: Arc::new(TokioMutex::new(socket)),
+            sink: Arc::new(TokioMutex::new(sink)),
+            stream: Arc::new(TokioMutex::new(stream)),
         }
     }
 
     pub fn recv(&self) -> Result<AiviWsMessage, AiviHttpError> {
-        let socket = self.socket.clone();
+        let stream = self.stream.clone();
         let handle = self.runtime.clone();
         handle.block_on(async move {
-            let mut socket = socket.lock().await;
-            match socket.next().await {
+            let mut stream = stream.lock().await;
+            match stream.next().await {
                 Some(Ok(msg)) => Ok(map_ws_message(msg)),
                 Some(Err(err)) => Err(AiviHttpError {
                     message: err.to_string(),
@@ -113,24 +193,23 @@ impl WebSocketHandle {
     }
 
     pub fn send(&self, msg: AiviWsMessage) -> Result<(), AiviHttpError> {
-        let socket = self.socket.clone();
+        let sink = self.sink.clone();
         let handle = self.runtime.clone();
         handle.block_on(async move {
-            let mut socket = socket.lock().await;
+            let mut sink = sink.lock().await;
             let msg = to_ws_message(msg);
-            socket.send(msg).await.map_err(|err| AiviHttpError {
+            sink.send(msg).await.map_err(|err| AiviHttpError {
                 message: err.to_string(),
             })
         })
     }
 
     pub fn close(&self) -> Result<(), AiviHttpError> {
-        let socket = self.socket.clone();
+        let sink = self.sink.clone();
         let handle = self.runtime.clone();
         handle.block_on(async move {
-            let mut socket = socket.lock().await;
-            socket
-                .send(hyper_tungstenite::tungstenite::Message::Close(None))
+            let mut sink = sink.lock().await;
+            sink.send(hyper_tungstenite::tungstenite::Message::Close(None))
                 .await
                 .map_err(|err| AiviHttpError {
                     message: err.to_string(),
@@ -140,6 +219,38 @@ impl WebSocketHandle {
 }
 
 pub fn start_server(addr: SocketAddr, handler: Handler) -> Result<ServerHandle, AiviHttpError> {
+    start_server_with(addr, ServerOptions::default(), handler)
+}
+
+pub fn start_server_with(
+    addr: SocketAddr,
+    options: ServerOptions,
+    handler: Handler,
+) -> Result<ServerHandle, AiviHttpError> {
+    spawn_server(addr, options, None, handler)
+}
+
+/// Like [`start_server_with`], terminating TLS on every connection. Certificate problems are
+/// reported here rather than when the first client connects. Connections negotiate HTTP/2 or
+/// HTTP/1.1 through ALPN.
+pub fn start_tls_server(
+    addr: SocketAddr,
+    options: ServerOptions,
+    tls: &TlsServerSettings,
+    handler: Handler,
+) -> Result<ServerHandle, AiviHttpError> {
+    let mut config = tls::server_config(tls)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    spawn_server(addr, options, Some(acceptor), handler)
+}
+
+fn spawn_server(
+    addr: SocketAddr,
+    options: ServerOptions,
+    acceptor: Option<TlsAcceptor>,
+    handler: Handler,
+) -> Result<ServerHandle, AiviHttpError> {
     let worker_threads = std::thread::available_parallelism()
         .map(|value| value.get())
         .unwrap_or(1);
@@ -173,18 +284,23 @@ pub fn start_server(addr: SocketAddr, handler: Handler) -> Result<ServerHandle,
                             Ok(value) => value,
                             Err(_) => continue,
                         };
-                        let handler = handler.clone();
-                        let runtime_handle = runtime_handle.clone();
-                        let service = service_fn(move |req| {
-                            let handler = handler.clone();
-                            let runtime_handle = runtime_handle.clone();
-                            async move { handle_request(req, remote_addr, handler, runtime_handle).await }
-                        });
+                        let connection = Connection {
+                            remote_addr,
+                            options,
+                            handler: handler.clone(),
+                            runtime_handle: runtime_handle.clone(),
+                        };
+                        let acceptor = acceptor.clone();
                         tokio::spawn(async move {
-                            let mut builder = auto::Builder::new(TokioExecutor::new());
-                            builder.http1().keep_alive(true);
-                            let conn = builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
-                            let _ = conn.await;
+                            match acceptor {
+                                Some(acceptor) => {
+                                    // A failed handshake only drops this connection.
+                                    if let Ok(stream) = acceptor.accept(stream).await {
+                                        connection.serve(stream).await;
+                                    }
+                                }
+                                None => connection.serve(stream).await,
+                            }
                         });
                     }
                 }
@@ -201,60 +317,102 @@ pub fn start_server(addr: SocketAddr, handler: Handler) -> Result<ServerHandle,
     })
 }
 
+struct Connection {
+    remote_addr: SocketAddr,
+    options: ServerOptions,
+    handler: Handler,
+    runtime_handle: Handle,
+}
+
+impl Connection {
+    async fn serve<S>(self, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let service = service_fn(move |req| {
+            let handler = self.handler.clone();
+            let runtime_handle = self.runtime_handle.clone();
+            handle_request(req, self.remote_addr, self.options, handler, runtime_handle)
+        });
+        let mut builder = auto::Builder::new(TokioExecutor::new());
+        builder.http1().keep_alive(true);
+        let conn = builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
+        let _ = conn.await;
+    }
+}
+
 async fn handle_request(
     req: Request<Incoming>,
     remote_addr: SocketAddr,
+    options: ServerOptions,
     handler: Handler,
     runtime_handle: Handle,
-) -> Result<Response<Full<Bytes>>, hyper::Error> {
+) -> Result<Response<HttpBody>, hyper::Error> {
     let is_upgrade = hyper_tungstenite::is_upgrade_request(&req);
     let (parts, body) = req.into_parts();
 
-    let (body_bytes, upgrade_body) = if is_upgrade {
-        (Bytes::new(), Some(body))
+    let declared_len = parts
+        .headers
+        .get(hyper::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if declared_len.is_some_and(|len| len > options.max_body_bytes) {
+        return Ok(error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "request body too large",
+        ));
+    }
+
+    let (request_body, upgrade_body) = if is_upgrade {
+        (Body::empty(), Some(body))
+    } else if options.stream_request_bodies {
+        let source = request_body_source(body, runtime_handle.clone(), options.max_body_bytes);
+        (Body::Stream(source), None)
     } else {
-        (body.collect().await?.to_bytes(), None)
+        match Limited::new(body, options.max_body_bytes as usize)
+            .collect()
+            .await
+        {
+            Ok(collected) => (Body::Full(collected.to_bytes()), None),
+            Err(err) if err.is::<LengthLimitError>() => {
+                return Ok(error_response(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "request body too large",
+                ))
+            }
+            Err(err) => return Ok(error_response(StatusCode::BAD_REQUEST, err.to_string())),
+        }
     };
 
-    let request = match build_request(&parts, body_bytes, Some(remote_addr.to_string())) {
+    let request = match build_request(&parts, request_body, Some(remote_addr.to_string())) {
         Ok(value) => value,
-        Err(err) => {
-            let mut response = Response::new(Full::from(Bytes::from(err.message)));
-            *response.status_mut() = StatusCode::BAD_REQUEST;
-            return Ok(response);
-        }
+        Err(err) => return Ok(error_response(StatusCode::BAD_REQUEST, err.message)),
     };
     let reply = match handler(request).await {
         Ok(value) => value,
         Err(err) => {
-            let mut response = Response::new(Full::from(Bytes::from(err.message)));
-            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
-            return Ok(response);
+            return Ok(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                err.message,
+            ))
         }
     };
 
     match reply {
         ServerReply::Http(response) => match convert_response(response) {
             Ok(response) => Ok(response),
-            Err(err) => {
-                let mut response = Response::new(Full::from(Bytes::from(err.message)));
-                *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
-                Ok(response)
-            }
+            Err(err) => Ok(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                err.message,
+            )),
         },
         ServerReply::Ws(ws_handler) => {
             if !is_upgrade {
-                let mut response = Response::new(Full::from(Bytes::from("upgrade required")));
-                *response.status_mut() = StatusCode::BAD_REQUEST;
-                return Ok(response);
+                return Ok(error_response(StatusCode::BAD_REQUEST, "upgrade required"));
             }
             let body = match upgrade_body {
                 Some(value) => value,
-                None => {
-                    let mut response = Response::new(Full::from(Bytes::from("upgrade required")));
-                    *response.status_mut() = StatusCode::BAD_REQUEST;
-                    return Ok(response);
-                }
+                None => return Ok(error_response(StatusCode::BAD_REQUEST, "upgrade required")),
             };
             let mut req = Request::from_parts(parts, body);
             match hyper_tungstenite::upgrade(&mut req, None) {
@@ -266,21 +424,75 @@ async fn handle_request(
                             let _ = ws_handler(ws_handle).await;
                         }
                     });
-                    Ok(response)
-                }
-                Err(_) => {
-                    let mut response = Response::new(Full::from(Bytes::from("upgrade failed")));
-                    *response.status_mut() = StatusCode::BAD_REQUEST;
-                    Ok(response)
+                    Ok(response.map(full_body))
                 }
+                Err(_) => Ok(error_response(StatusCode::BAD_REQUEST, "upgrade failed")),
             }
         }
     }
 }
 
+fn error_response(status: StatusCode, message: impl Into<Bytes>) -> Response<HttpBody> {
+    let mut response = Response::new(full_body(Full::new(message.into())));
+    *response.status_mut() = status;
+    response
+}
+
+fn full_body(body: Full<Bytes>) -> HttpBody {
+    body.map_err(|never| match never {}).boxed_unsync()
+}
+
+/// Reads the request body frame by frame on the caller's (blocking) thread, failing once more
+/// than `limit` bytes have arrived.
+fn request_body_source(mut body: Incoming, runtime: Handle, limit: u64) -> ChunkSource {
+    let mut received = 0u64;
+    Box::new(move || loop {
+        let frame = match runtime.block_on(body.frame()) {
+            None => return Ok(None),
+            Some(Err(err)) => {
+                return Err(AiviHttpError {
+                    message: err.to_string(),
+                })
+            }
+            Some(Ok(frame)) => frame,
+        };
+        // Trailers carry no data.
+        let Ok(data) = frame.into_data() else {
+            continue;
+        };
+        received += data.len() as u64;
+        if received > limit {
+            return Err(AiviHttpError {
+                message: format!("request body exceeds {limit} bytes"),
+            });
+        }
+        if !data.is_empty() {
+            return Ok(Some(data));
+        }
+    })
+}
+
+/// Pumps `source` on a blocking thread into a bounded channel the connection drains. The pump
+/// stops at the end of the body, on the first error, or once the client has gone away.
+fn stream_body(mut source: ChunkSource) -> HttpBody {
+    let (tx, mut rx) = mpsc::channel(STREAM_BUFFER_CHUNKS);
+    tokio::task::spawn_blocking(move || loop {
+        let (frame, last) = match source() {
+            Ok(Some(chunk)) => (Ok(Frame::data(chunk)), false),
+            Ok(None) => break,
+            Err(err) => (Err(err), true),
+        };
+        if tx.blocking_send(frame).is_err() || last {
+            break;
+        }
+    });
+    let frames = futures_util::stream::poll_fn(move |cx| rx.poll_recv(cx));
+    StreamBody::new(frames).boxed_unsync()
+}
+
 fn build_request(
     parts: &hyper::http::request::Parts,
-    body: Bytes,
+    body: Body,
     remote_addr: Option<String>,
 ) -> Result<AiviRequest, AiviHttpError> {
     let method = parts.method.as_str().to_string();
@@ -294,7 +506,7 @@ fn build_request(
         method,
         path,
         headers,
-        body: body.to_vec(),
+        body,
         remote_addr,
     })
 }
@@ -312,7 +524,7 @@ fn headers_to_vec(
     Ok(out)
 }
 
-fn convert_response(response: AiviResponse) -> Result<Response<Full<Bytes>>, AiviHttpError> {
+fn convert_response(response: AiviResponse) -> Result<Response<HttpBody>, AiviHttpError> {
     let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
     let mut builder = Response::builder().status(status);
     {
@@ -332,11 +544,13 @@ fn convert_response(response: AiviResponse) -> Result<Response<Full<Bytes>>, Aiv
             headers.append(name, value);
         }
     }
-    builder
-        .body(Full::from(Bytes::from(response.body)))
-        .map_err(|_| AiviHttpError {
-            message: "invalid response body".to_string(),
-        })
+    let body = match response.body {
+        Body::Full(bytes) => full_body(Full::new(bytes)),
+        Body::Stream(source) => stream_body(source),
+    };
+    builder.body(body).map_err(|_| AiviHttpError {
+        message: "invalid response body".to_string(),
+    })
 }
 
 fn map_ws_message(msg: hyper_tungstenite::tungstenite::Message) -> AiviWsMessage {
@@ -0,0 +1,178 @@
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::routing::header_value;
+use crate::{AiviResponse, Body};
+
+pub struct CorsConfig {
+    /// Allowed origins; `*` allows any origin.
+    pub origins: Vec<String>,
+    pub methods: Vec<String>,
+    pub headers: Vec<String>,
+    pub credentials: bool,
+    pub max_age: Option<i64>,
+}
+
+impl CorsConfig {
+    fn allowed_origin(&self, request_headers: &[(String, String)]) -> Option<String> {
+        let origin = header_value(request_headers, "origin")?;
+        if self.origins.iter().any(|allowed| allowed == origin) {
+            return Some(origin.to_string());
+        }
+        if self.origins.iter().any(|allowed| allowed == "*") {
+            // Browsers reject `*` on credentialed requests, so echo the origin instead.
+            return Some(if self.credentials {
+                origin.to_string()
+            } else {
+                "*".to_string()
+            });
+        }
+        None
+    }
+}
+
+/// Answers a CORS preflight (`OPTIONS` with `Access-Control-Request-Method`). Preflights from
+/// origins that are not allowed get a 403 without CORS headers.
+pub fn cors_preflight(
+    config: &CorsConfig,
+    method: &str,
+    request_headers: &[(String, String)],
+) -> Option<AiviResponse> {
+    if !method.eq_ignore_ascii_case("OPTIONS")
+        || header_value(request_headers, "origin").is_none()
+        || header_value(request_headers, "access-control-request-method").is_none()
+    {
+        return None;
+    }
+    let Some(origin) = config.allowed_origin(request_headers) else {
+        return Some(AiviResponse {
+            status: 403,
+            headers: Vec::new(),
+            body: Body::empty(),
+        });
+    };
+    let mut headers = origin_headers(config, origin);
+    headers.push((
+        "access-control-allow-methods".to_string(),
+        config.methods.join(", "),
+    ));
+    let allow_headers = if config.headers.is_empty() {
+        header_value(request_headers, "access-control-request-headers")
+            .unwrap_or("")
+            .to_string()
+    } else {
+        config.headers.join(", ")
+    };
+    if !allow_headers.is_empty() {
+        headers.push(("access-control-allow-headers".to_string(), allow_headers));
+    }
+    if let Some(max_age) = config.max_age {
+        headers.push(("access-control-max-age".to_string(), max_age.to_string()));
+    }
+    Some(AiviResponse {
+        status: 204,
+        headers,
+        body: Body::empty(),
+    })
+}
+
+/// Adds the CORS response headers for an allowed origin.
+pub fn apply_cors(
+    config: &CorsConfig,
+    request_headers: &[(String, String)],
+    response: &mut AiviResponse,
+) {
+    if let Some(origin) = config.allowed_origin(request_headers) {
+        response.headers.extend(origin_headers(config, origin));
+    }
+}
+
+fn origin_headers(config: &CorsConfig, origin: String) -> Vec<(String, String)> {
+    let vary = origin != "*";
+    let mut headers = vec![("access-control-allow-origin".to_string(), origin)];
+    if config.credentials {
+        headers.push((
+            "access-control-allow-credentials".to_string(),
+            "true".to_string(),
+        ));
+    }
+    if vary {
+        headers.push(("vary".to_string(), "Origin".to_string()));
+    }
+    headers
+}
+
+// Smaller bodies rarely get smaller once the gzip framing is added.
+const MIN_COMPRESS_LEN: usize = 1024;
+
+/// Gzips a successful response when the client accepts it and the content type is textual.
+/// Strong `ETag`s are weakened because the encoded bytes differ from the original ones. Streamed
+/// bodies are sent as they are.
+pub fn compress_response(request_headers: &[(String, String)], response: &mut AiviResponse) {
+    let Some(original) = response.body.as_full() else {
+        return;
+    };
+    if response.status != 200
+        || original.len() < MIN_COMPRESS_LEN
+        || !accepts_gzip(request_headers)
+        || header_value(&response.headers, "content-encoding").is_some()
+        || !header_value(&response.headers, "content-type").is_some_and(is_compressible)
+    {
+        return;
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let Ok(body) = encoder.write_all(original).and_then(|_| encoder.finish()) else {
+        return;
+    };
+    response.body = Body::from(body);
+    response
+        .headers
+        .retain(|(name, _)| !name.eq_ignore_ascii_case("content-length"));
+    for (name, value) in response.headers.iter_mut() {
+        if name.eq_ignore_ascii_case("etag") && !value.starts_with("W/") {
+            *value = format!("W/{value}");
+        }
+    }
+    response
+        .headers
+        .push(("content-encoding".to_string(), "gzip".to_string()));
+    response
+        .headers
+        .push(("vary".to_string(), "Accept-Encoding".to_string()));
+}
+
+fn accepts_gzip(request_headers: &[(String, String)]) -> bool {
+    let Some(accept) = header_value(request_headers, "accept-encoding") else {
+        return false;
+    };
+    accept.split(',').any(|item| {
+        let mut parts = item.split(';');
+        let coding = parts.next().unwrap_or("").trim();
+        let rejected = parts.any(|param| {
+            param
+                .trim()
+                .strip_prefix("q=")
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+        });
+        (coding.eq_ignore_ascii_case("gzip") || coding == "*") && !rejected
+    })
+}
+
+fn is_compressible(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || matches!(
+            mime.as_str(),
+            "application/json" | "application/javascript" | "application/xml" | "image/svg+xml"
+        )
+}
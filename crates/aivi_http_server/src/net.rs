@@ -0,0 +1,236 @@
+//! Blocking stream sockets for `aivi.net.sockets`: TCP, optionally wrapped in TLS, and Unix
+//! domain sockets. Listeners never block; callers poll `try_accept` so they can give up when
+//! cancelled.
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, ServerConfig, ServerConnection, StreamOwned};
+
+use crate::TlsClientSettings;
+
+/// A TCP connection, optionally wrapped in TLS, or a Unix domain stream. TLS handshakes finish
+/// before a stream is handed out, so certificate errors surface from `connect`/`try_accept`
+/// rather than the first read.
+pub enum NetStream {
+    Plain(TcpStream),
+    TlsClient(Box<StreamOwned<ClientConnection, TcpStream>>),
+    TlsServer(Box<StreamOwned<ServerConnection, TcpStream>>),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+#[cfg(not(unix))]
+fn unix_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Unix domain sockets are not supported on this platform",
+    )
+}
+
+impl NetStream {
+    pub fn connect(addr: SocketAddr) -> io::Result<Self> {
+        TcpStream::connect(addr).map(NetStream::Plain)
+    }
+
+    /// Connects and completes a TLS handshake. `host` is used for SNI and certificate
+    /// verification unless the settings name a server explicitly.
+    pub fn connect_tls(
+        addr: SocketAddr,
+        host: &str,
+        config: Arc<ClientConfig>,
+        settings: &TlsClientSettings,
+    ) -> io::Result<Self> {
+        let name = settings.server_name.as_deref().unwrap_or(host);
+        let server_name = ServerName::try_from(name.to_string()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid TLS server name `{name}`"),
+            )
+        })?;
+        let mut conn = ClientConnection::new(config, server_name).map_err(io::Error::other)?;
+        let mut sock = TcpStream::connect(addr)?;
+        while conn.is_handshaking() {
+            conn.complete_io(&mut sock)?;
+        }
+        Ok(NetStream::TlsClient(Box::new(StreamOwned::new(conn, sock))))
+    }
+
+    #[cfg(unix)]
+    pub fn connect_unix(path: &Path) -> io::Result<Self> {
+        UnixStream::connect(path).map(NetStream::Unix)
+    }
+
+    #[cfg(not(unix))]
+    pub fn connect_unix(_path: &Path) -> io::Result<Self> {
+        Err(unix_unsupported())
+    }
+
+    /// Sends a TLS `close_notify` where applicable, then shuts down both directions.
+    pub fn shutdown(&mut self) -> io::Result<()> {
+        match self {
+            NetStream::Plain(sock) => sock.shutdown(Shutdown::Both),
+            NetStream::TlsClient(stream) => {
+                stream.conn.send_close_notify();
+                while stream.conn.wants_write() {
+                    stream.conn.write_tls(&mut stream.sock)?;
+                }
+                stream.sock.shutdown(Shutdown::Both)
+            }
+            NetStream::TlsServer(stream) => {
+                stream.conn.send_close_notify();
+                while stream.conn.wants_write() {
+                    stream.conn.write_tls(&mut stream.sock)?;
+                }
+                stream.sock.shutdown(Shutdown::Both)
+            }
+            #[cfg(unix)]
+            NetStream::Unix(sock) => sock.shutdown(Shutdown::Both),
+        }
+    }
+}
+
+impl Read for NetStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            NetStream::Plain(sock) => sock.read(buf),
+            NetStream::TlsClient(stream) => stream.read(buf),
+            NetStream::TlsServer(stream) => stream.read(buf),
+            #[cfg(unix)]
+            NetStream::Unix(sock) => sock.read(buf),
+        }
+    }
+}
+
+impl Write for NetStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            NetStream::Plain(sock) => sock.write(buf),
+            NetStream::TlsClient(stream) => stream.write(buf),
+            NetStream::TlsServer(stream) => stream.write(buf),
+            #[cfg(unix)]
+            NetStream::Unix(sock) => sock.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            NetStream::Plain(sock) => sock.flush(),
+            NetStream::TlsClient(stream) => stream.flush(),
+            NetStream::TlsServer(stream) => stream.flush(),
+            #[cfg(unix)]
+            NetStream::Unix(sock) => sock.flush(),
+        }
+    }
+}
+
+enum ListenerKind {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        path: PathBuf,
+    },
+}
+
+/// A non-blocking TCP or Unix domain listener. Accepted TCP connections are optionally
+/// wrapped in TLS.
+pub struct NetListener {
+    kind: ListenerKind,
+    tls: Option<Arc<ServerConfig>>,
+}
+
+impl NetListener {
+    pub fn bind(addr: SocketAddr, tls: Option<Arc<ServerConfig>>) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            kind: ListenerKind::Tcp(listener),
+            tls,
+        })
+    }
+
+    /// Binds a Unix domain socket at `path`. The socket file is removed when the listener is
+    /// dropped.
+    #[cfg(unix)]
+    pub fn bind_unix(path: &Path) -> io::Result<Self> {
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            kind: ListenerKind::Unix {
+                listener,
+                path: path.to_path_buf(),
+            },
+            tls: None,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn bind_unix(_path: &Path) -> io::Result<Self> {
+        Err(unix_unsupported())
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        match &self.kind {
+            ListenerKind::Tcp(listener) => listener.local_addr(),
+            #[cfg(unix)]
+            ListenerKind::Unix { .. } => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Unix domain listeners have no socket address",
+            )),
+        }
+    }
+
+    /// Takes the next pending connection, or `Ok(None)` when there is none yet. Accepted
+    /// streams are blocking. With TLS the handshake completes before returning; a failed
+    /// handshake is returned as an error and the listener itself stays usable.
+    pub fn try_accept(&self) -> io::Result<Option<NetStream>> {
+        let sock = match &self.kind {
+            ListenerKind::Tcp(listener) => match listener.accept() {
+                Ok((sock, _)) => sock,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(None),
+                Err(err) => return Err(err),
+            },
+            #[cfg(unix)]
+            ListenerKind::Unix { listener, .. } => {
+                return match listener.accept() {
+                    Ok((sock, _)) => {
+                        sock.set_nonblocking(false)?;
+                        Ok(Some(NetStream::Unix(sock)))
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+                    Err(err) => Err(err),
+                };
+            }
+        };
+        // Some platforms hand out accepted sockets with the listener's non-blocking flag.
+        sock.set_nonblocking(false)?;
+        let Some(config) = &self.tls else {
+            return Ok(Some(NetStream::Plain(sock)));
+        };
+        let mut sock = sock;
+        let mut conn = ServerConnection::new(config.clone()).map_err(io::Error::other)?;
+        while conn.is_handshaking() {
+            conn.complete_io(&mut sock)?;
+        }
+        Ok(Some(NetStream::TlsServer(Box::new(StreamOwned::new(
+            conn, sock,
+        )))))
+    }
+}
+
+impl Drop for NetListener {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let ListenerKind::Unix { path, .. } = &self.kind {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+
+use crate::AiviHttpError;
+
+/// A route pattern such as `/users/:id:Int/posts/*rest`.
+///
+/// - literal segments match exactly,
+/// - `:name` captures one segment as text, `:name:Int` only matches a decimal integer,
+/// - a trailing `*name` captures the remaining path (possibly empty).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePattern {
+    segments: Vec<PatternSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternSegment {
+    Literal(String),
+    Param(String, ParamKind),
+    Rest(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParamKind {
+    Text,
+    Int,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamValue {
+    Text(String),
+    Int(i64),
+}
+
+impl RoutePattern {
+    pub fn parse(pattern: &str) -> Result<Self, AiviHttpError> {
+        let invalid = |reason: &str| AiviHttpError {
+            message: format!("invalid route pattern `{pattern}`: {reason}"),
+        };
+        if !pattern.starts_with('/') {
+            return Err(invalid("must start with '/'"));
+        }
+        let raw: Vec<&str> = pattern.split('/').filter(|seg| !seg.is_empty()).collect();
+        let mut segments = Vec::with_capacity(raw.len());
+        for (idx, seg) in raw.iter().enumerate() {
+            if let Some(name) = seg.strip_prefix('*') {
+                if idx + 1 != raw.len() {
+                    return Err(invalid("`*` must be the last segment"));
+                }
+                segments.push(PatternSegment::Rest(
+                    param_name(name).ok_or_else(|| invalid("`*` needs a parameter name"))?,
+                ));
+            } else if let Some(spec) = seg.strip_prefix(':') {
+                let (name, kind) = match spec.split_once(':') {
+                    Some((name, "Int")) => (name, ParamKind::Int),
+                    Some((name, "Text")) => (name, ParamKind::Text),
+                    Some((_, other)) => {
+                        return Err(invalid(&format!("unknown parameter type `{other}`")))
+                    }
+                    None => (spec, ParamKind::Text),
+                };
+                let name = param_name(name).ok_or_else(|| invalid("`:` needs a parameter name"))?;
+                if segments
+                    .iter()
+                    .any(|seg| segment_name(seg) == Some(name.as_str()))
+                {
+                    return Err(invalid(&format!("parameter `{name}` appears twice")));
+                }
+                segments.push(PatternSegment::Param(name, kind));
+            } else {
+                segments.push(PatternSegment::Literal(seg.to_string()));
+            }
+        }
+        Ok(Self { segments })
+    }
+
+    /// Matches a request path (without query string). Path segments are percent-decoded
+    /// before they are compared or captured.
+    pub fn matches(&self, path: &str) -> Option<Vec<(String, ParamValue)>> {
+        let decoded: Vec<String> = path
+            .split('/')
+            .filter(|seg| !seg.is_empty())
+            .map(|seg| {
+                percent_decode_str(seg)
+                    .decode_utf8()
+                    .map(|s| s.into_owned())
+            })
+            .collect::<Result<_, _>>()
+            .ok()?;
+        let mut params = Vec::new();
+        let mut rest = decoded.as_slice();
+        for segment in &self.segments {
+            match segment {
+                PatternSegment::Rest(name) => {
+                    params.push((name.clone(), ParamValue::Text(rest.join("/"))));
+                    return Some(params);
+                }
+                PatternSegment::Literal(literal) => {
+                    let (head, tail) = rest.split_first()?;
+                    if head != literal {
+                        return None;
+                    }
+                    rest = tail;
+                }
+                PatternSegment::Param(name, kind) => {
+                    let (head, tail) = rest.split_first()?;
+                    let value = match kind {
+                        ParamKind::Text => ParamValue::Text(head.clone()),
+                        ParamKind::Int => ParamValue::Int(parse_int_segment(head)?),
+                    };
+                    params.push((name.clone(), value));
+                    rest = tail;
+                }
+            }
+        }
+        rest.is_empty().then_some(params)
+    }
+}
+
+fn param_name(name: &str) -> Option<String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    valid.then(|| name.to_string())
+}
+
+fn segment_name(segment: &PatternSegment) -> Option<&str> {
+    match segment {
+        PatternSegment::Param(name, _) | PatternSegment::Rest(name) => Some(name),
+        PatternSegment::Literal(_) => None,
+    }
+}
+
+fn parse_int_segment(text: &str) -> Option<i64> {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// `GET` routes also answer `HEAD`; `*` matches every method.
+pub fn method_matches(route_method: &str, request_method: &str) -> bool {
+    route_method == "*"
+        || route_method.eq_ignore_ascii_case(request_method)
+        || (route_method.eq_ignore_ascii_case("GET") && request_method.eq_ignore_ascii_case("HEAD"))
+}
+
+/// Splits a request target into its path and query string.
+pub fn split_target(target: &str) -> (&str, &str) {
+    let target = target.split_once('#').map_or(target, |(head, _)| head);
+    target.split_once('?').unwrap_or((target, ""))
+}
+
+pub fn parse_query(query: &str) -> Vec<(String, String)> {
+    form_urlencoded::parse(query.as_bytes())
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect()
+}
+
+/// Parses every `Cookie` header. Values are unquoted and percent-decoded, mirroring
+/// [`format_set_cookie`].
+pub fn parse_cookies(headers: &[(String, String)]) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    for (name, value) in headers {
+        if !name.eq_ignore_ascii_case("cookie") {
+            continue;
+        }
+        for pair in value.split(';') {
+            let Some((name, value)) = pair.split_once('=') else {
+                continue;
+            };
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            let value = percent_decode_str(value).decode_utf8_lossy().into_owned();
+            out.push((name.to_string(), value));
+        }
+    }
+    out
+}
+
+/// Parses an `application/x-www-form-urlencoded` body.
+pub fn parse_form(
+    headers: &[(String, String)],
+    body: &[u8],
+) -> Result<Vec<(String, String)>, AiviHttpError> {
+    let content_type = header_value(headers, "content-type").unwrap_or("");
+    let mime = content_type.split(';').next().unwrap_or("").trim();
+    if !mime.eq_ignore_ascii_case("application/x-www-form-urlencoded") {
+        return Err(AiviHttpError {
+            message: format!(
+                "expected an application/x-www-form-urlencoded body, got `{content_type}`"
+            ),
+        });
+    }
+    Ok(form_urlencoded::parse(body)
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect())
+}
+
+pub fn header_value<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+pub struct SetCookie {
+    pub name: String,
+    pub value: String,
+    pub path: Option<String>,
+    pub domain: Option<String>,
+    pub max_age: Option<i64>,
+    pub http_only: bool,
+    pub secure: bool,
+    pub same_site: Option<String>,
+}
+
+// Everything outside the RFC 6265 `cookie-octet` set.
+const COOKIE_VALUE: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b',')
+    .add(b';')
+    .add(b'\\')
+    .add(b'%');
+
+/// Formats a `Set-Cookie` header value. The cookie value is percent-encoded where needed;
+/// names and attributes that would break the header are rejected.
+pub fn format_set_cookie(cookie: &SetCookie) -> Result<String, AiviHttpError> {
+    let is_token = |text: &str| {
+        !text.is_empty()
+            && text
+                .bytes()
+                .all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&b))
+    };
+    if !is_token(&cookie.name) {
+        return Err(AiviHttpError {
+            message: format!("invalid cookie name `{}`", cookie.name),
+        });
+    }
+    let attribute = |label: &str, value: &str| {
+        if value.is_empty() || value.contains([';', '\r', '\n']) {
+            Err(AiviHttpError {
+                message: format!("invalid cookie {label} `{value}`"),
+            })
+        } else {
+            Ok(())
+        }
+    };
+    let mut out = format!(
+        "{}={}",
+        cookie.name,
+        utf8_percent_encode(&cookie.value, COOKIE_VALUE)
+    );
+    if let Some(path) = &cookie.path {
+        attribute("path", path)?;
+        out.push_str(&format!("; Path={path}"));
+    }
+    if let Some(domain) = &cookie.domain {
+        attribute("domain", domain)?;
+        out.push_str(&format!("; Domain={domain}"));
+    }
+    if let Some(max_age) = cookie.max_age {
+        out.push_str(&format!("; Max-Age={max_age}"));
+    }
+    if let Some(same_site) = &cookie.same_site {
+        let same_site = match same_site.to_ascii_lowercase().as_str() {
+            "strict" => "Strict",
+            "lax" => "Lax",
+            "none" => "None",
+            _ => {
+                return Err(AiviHttpError {
+                    message: format!("invalid cookie sameSite `{same_site}`"),
+                })
+            }
+        };
+        out.push_str(&format!("; SameSite={same_site}"));
+    }
+    if cookie.http_only {
+        out.push_str("; HttpOnly");
+    }
+    if cookie.secure {
+        out.push_str("; Secure");
+    }
+    Ok(out)
+}
@@ -0,0 +1,34 @@
+/// One Server-Sent Event, as defined by the HTML `text/event-stream` format.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+    pub id: Option<String>,
+    /// Reconnection delay in milliseconds.
+    pub retry: Option<i64>,
+}
+
+pub const CONTENT_TYPE: &str = "text/event-stream";
+
+/// Formats one event, terminated by the blank line that dispatches it. Multi-line data becomes
+/// several `data:` lines; line breaks in `event` and `id` would start new fields, so they are
+/// dropped.
+pub fn format_event(event: &SseEvent) -> String {
+    let single_line = |text: &str| text.replace(['\r', '\n'], "");
+    let mut out = String::new();
+    if let Some(name) = &event.event {
+        out.push_str(&format!("event: {}\n", single_line(name)));
+    }
+    if let Some(id) = &event.id {
+        out.push_str(&format!("id: {}\n", single_line(id)));
+    }
+    if let Some(retry) = event.retry.filter(|retry| *retry >= 0) {
+        out.push_str(&format!("retry: {retry}\n"));
+    }
+    let data = event.data.replace("\r\n", "\n").replace('\r', "\n");
+    for line in data.split('\n') {
+        out.push_str(&format!("data: {line}\n"));
+    }
+    out.push('\n');
+    out
+}
@@ -0,0 +1,231 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::routing::header_value;
+use crate::{AiviHttpError, AiviResponse, Body, Bytes, ChunkSource};
+
+/// Serves `relative` from below `root`, answering conditional (`If-None-Match`,
+/// `If-Modified-Since`) and single-range (`Range`, `If-Range`) requests. Directories serve
+/// their `index.html`. Paths that would leave `root` are answered with 404. Bodies larger than
+/// 64 KiB are streamed from disk instead of being read into memory.
+pub fn serve_file(
+    root: &Path,
+    relative: &str,
+    method: &str,
+    headers: &[(String, String)],
+) -> AiviResponse {
+    let Some(path) = resolve(root, relative) else {
+        return plain(404, "not found");
+    };
+    let (mut file, metadata) = match open_file(&path) {
+        Some(value) => value,
+        None => return plain(404, "not found"),
+    };
+    let len = metadata.len();
+    let modified = metadata.modified().ok();
+    let etag = entity_tag(len, modified);
+
+    let mut response_headers = vec![
+        ("content-type".to_string(), content_type(&path).to_string()),
+        ("etag".to_string(), etag.clone()),
+        ("accept-ranges".to_string(), "bytes".to_string()),
+    ];
+    if let Some(modified) = modified {
+        response_headers.push((
+            "last-modified".to_string(),
+            httpdate::fmt_http_date(modified),
+        ));
+    }
+
+    if not_modified(headers, &etag, modified) {
+        response_headers.retain(|(name, _)| name != "content-type");
+        return AiviResponse {
+            status: 304,
+            headers: response_headers,
+            body: Body::empty(),
+        };
+    }
+
+    let range = header_value(headers, "range")
+        .filter(|_| header_value(headers, "if-range").is_none_or(|tag| tag.trim() == etag.as_str()))
+        .map(|value| parse_range(value, len));
+    let (status, start, end) = match range {
+        Some(RangeRequest::Satisfiable(start, end)) => {
+            response_headers.push((
+                "content-range".to_string(),
+                format!("bytes {start}-{end}/{len}"),
+            ));
+            (206, start, end + 1)
+        }
+        Some(RangeRequest::Unsatisfiable) => {
+            return AiviResponse {
+                status: 416,
+                headers: vec![("content-range".to_string(), format!("bytes */{len}"))],
+                body: Body::empty(),
+            };
+        }
+        Some(RangeRequest::Ignored) | None => (200, 0, len),
+    };
+
+    let length = end - start;
+    let body = if method.eq_ignore_ascii_case("HEAD") {
+        response_headers.push(("content-length".to_string(), length.to_string()));
+        Body::empty()
+    } else if file.seek(SeekFrom::Start(start)).is_err() {
+        return plain(500, "failed to read file");
+    } else if length > STREAM_THRESHOLD {
+        response_headers.push(("content-length".to_string(), length.to_string()));
+        Body::Stream(file_chunks(file.take(length)))
+    } else {
+        let mut body = Vec::new();
+        if file.take(length).read_to_end(&mut body).is_err() {
+            return plain(500, "failed to read file");
+        }
+        Body::from(body)
+    };
+    AiviResponse {
+        status,
+        headers: response_headers,
+        body,
+    }
+}
+
+const STREAM_THRESHOLD: u64 = 64 * 1024;
+
+fn file_chunks(mut file: std::io::Take<File>) -> ChunkSource {
+    Box::new(move || {
+        let mut chunk = vec![0u8; STREAM_THRESHOLD as usize];
+        let count = file.read(&mut chunk).map_err(|err| AiviHttpError {
+            message: err.to_string(),
+        })?;
+        chunk.truncate(count);
+        Ok((count > 0).then(|| Bytes::from(chunk)))
+    })
+}
+
+fn resolve(root: &Path, relative: &str) -> Option<PathBuf> {
+    let mut path = root.to_path_buf();
+    for seg in relative.split('/').filter(|seg| !seg.is_empty()) {
+        if seg == "." || seg == ".." || seg.contains(['\\', '\0', ':']) {
+            return None;
+        }
+        path.push(seg);
+    }
+    if path.is_dir() {
+        path.push("index.html");
+    }
+    Some(path)
+}
+
+fn open_file(path: &Path) -> Option<(File, std::fs::Metadata)> {
+    let file = File::open(path).ok()?;
+    let metadata = file.metadata().ok()?;
+    metadata.is_file().then_some((file, metadata))
+}
+
+fn entity_tag(len: u64, modified: Option<SystemTime>) -> String {
+    let nanos = modified
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_nanos());
+    format!("\"{len:x}-{nanos:x}\"")
+}
+
+fn not_modified(headers: &[(String, String)], etag: &str, modified: Option<SystemTime>) -> bool {
+    if let Some(tags) = header_value(headers, "if-none-match") {
+        // Weak comparison: `W/"x"` matches `"x"`.
+        return tags.split(',').any(|tag| {
+            let tag = tag.trim();
+            tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag
+        });
+    }
+    let since = header_value(headers, "if-modified-since")
+        .and_then(|value| httpdate::parse_http_date(value).ok());
+    match (since, modified) {
+        // HTTP dates have second precision.
+        (Some(since), Some(modified)) => modified
+            .duration_since(since)
+            .map_or(true, |newer| newer.as_secs() == 0),
+        _ => false,
+    }
+}
+
+enum RangeRequest {
+    Satisfiable(u64, u64),
+    Unsatisfiable,
+    Ignored,
+}
+
+// Only single byte ranges are served; anything else gets the whole file, which RFC 9110
+// allows.
+fn parse_range(value: &str, len: u64) -> RangeRequest {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Ignored;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Ignored;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Ignored;
+    };
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", "") => return RangeRequest::Ignored,
+        ("", suffix) => match suffix.parse::<u64>() {
+            Ok(0) => return RangeRequest::Unsatisfiable,
+            Ok(suffix) => (len.saturating_sub(suffix), len.saturating_sub(1)),
+            Err(_) => return RangeRequest::Ignored,
+        },
+        (start, "") => match start.parse::<u64>() {
+            Ok(start) => (start, len.saturating_sub(1)),
+            Err(_) => return RangeRequest::Ignored,
+        },
+        (start, end) => match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Ok(start), Ok(end)) if start <= end => (start, end.min(len.saturating_sub(1))),
+            _ => return RangeRequest::Ignored,
+        },
+    };
+    if len == 0 || start >= len {
+        RangeRequest::Unsatisfiable
+    } else {
+        RangeRequest::Satisfiable(start, end)
+    }
+}
+
+fn content_type(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("html" | "htm") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js" | "mjs") => "text/javascript; charset=utf-8",
+        Some("json" | "map") => "application/json",
+        Some("txt" | "md") => "text/plain; charset=utf-8",
+        Some("csv") => "text/csv; charset=utf-8",
+        Some("xml") => "application/xml",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("ico") => "image/x-icon",
+        Some("wasm") => "application/wasm",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+fn plain(status: u16, message: &str) -> AiviResponse {
+    AiviResponse {
+        status,
+        headers: vec![(
+            "content-type".to_string(),
+            "text/plain; charset=utf-8".to_string(),
+        )],
+        body: Body::from(message.as_bytes().to_vec()),
+    }
+}
@@ -0,0 +1,117 @@
+//! TLS for the HTTP server and for plain sockets, backed by rustls with the `ring` provider.
+//! Certificates and keys are read from PEM files.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{ClientConfig, RootCertStore, ServerConfig};
+
+use crate::AiviHttpError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsServerSettings {
+    /// PEM certificate chain, leaf first.
+    pub cert_file: PathBuf,
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1).
+    pub key_file: PathBuf,
+    /// When set, every client must present a certificate issued by one of these CAs.
+    pub client_ca_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsClientSettings {
+    /// Sent as SNI and checked against the server certificate. Defaults to the host connected
+    /// to.
+    pub server_name: Option<String>,
+    /// PEM trust anchors. The bundled Mozilla roots are used when unset.
+    pub ca_file: Option<PathBuf>,
+    /// Certificate chain and key presented to servers that ask for a client certificate.
+    pub identity: Option<(PathBuf, PathBuf)>,
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn tls_error(context: &str, err: impl std::fmt::Display) -> AiviHttpError {
+    AiviHttpError {
+        message: format!("{context}: {err}"),
+    }
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, AiviHttpError> {
+    let context = format!("failed to read certificates from {}", path.display());
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| tls_error(&context, err))?;
+    if certs.is_empty() {
+        return Err(tls_error(&context, "no certificates found"));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>, AiviHttpError> {
+    PrivateKeyDer::from_pem_file(path).map_err(|err| {
+        tls_error(
+            &format!("failed to read private key from {}", path.display()),
+            err,
+        )
+    })
+}
+
+fn root_store(path: &Path) -> Result<RootCertStore, AiviHttpError> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        roots
+            .add(cert)
+            .map_err(|err| tls_error(&format!("invalid CA in {}", path.display()), err))?;
+    }
+    Ok(roots)
+}
+
+/// Builds a server configuration without ALPN protocols; the HTTP server adds its own.
+pub fn server_config(settings: &TlsServerSettings) -> Result<ServerConfig, AiviHttpError> {
+    let certs = load_certs(&settings.cert_file)?;
+    let key = load_key(&settings.key_file)?;
+    let builder = ServerConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(|err| tls_error("invalid TLS configuration", err))?;
+    let builder = match &settings.client_ca_file {
+        Some(path) => {
+            let verifier = WebPkiClientVerifier::builder_with_provider(
+                Arc::new(root_store(path)?),
+                provider(),
+            )
+            .build()
+            .map_err(|err| tls_error("invalid client CA", err))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    builder
+        .with_single_cert(certs, key)
+        .map_err(|err| tls_error("invalid certificate or key", err))
+}
+
+pub fn client_config(settings: &TlsClientSettings) -> Result<ClientConfig, AiviHttpError> {
+    let roots = match &settings.ca_file {
+        Some(path) => root_store(path)?,
+        None => RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        },
+    };
+    let builder = ClientConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(|err| tls_error("invalid TLS configuration", err))?
+        .with_root_certificates(roots);
+    match &settings.identity {
+        Some((cert_file, key_file)) => builder
+            .with_client_auth_cert(load_certs(cert_file)?, load_key(key_file)?)
+            .map_err(|err| tls_error("invalid client certificate or key", err)),
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
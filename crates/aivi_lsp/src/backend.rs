@@ -3,9 +3,9 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use aivi::{
-    BlockItem, ClassDecl, Def, DomainDecl, DomainItem, Expr, InstanceDecl, ListItem, MatchArm,
-    Module, ModuleItem, PathSegment, Pattern, RecordField, RecordPatternField, Span, SpannedName,
-    TypeAlias, TypeCtor, TypeDecl, TypeExpr, UseDecl,
+    BlockItem, ClassDecl, Def, DomainDecl, DomainItem, EffectDecl, Expr, InstanceDecl, ListItem,
+    MatchArm, Module, ModuleItem, PathSegment, Pattern, RecordField, RecordPatternField, Span,
+    SpannedName, TypeAlias, TypeCtor, TypeDecl, TypeExpr, UseDecl,
 };
 use tokio::sync::Mutex;
 use tower_lsp::lsp_types::{Location, Position, Range, TextEdit, Url};
@@ -29,6 +29,17 @@ impl Backend {
         )
     }
 
+    pub(super) fn range_to_span(range: Range) -> Span {
+        let position = |line: u32, column: u32| aivi::Position {
+            line: line as usize + 1,
+            column: column as usize,
+        };
+        Span {
+            start: position(range.start.line, range.start.character + 1),
+            end: position(range.end.line, range.end.character),
+        }
+    }
+
     pub(super) fn offset_at(text: &str, position: Position) -> usize {
         let mut offset = 0usize;
         for (line, chunk) in text.split_inclusive('\n').enumerate() {
@@ -242,6 +242,21 @@ impl Backend {
                     }
                 }
             }
+            ModuleItem::EffectDecl(effect) => {
+                if effect.name.name == ident {
+                    return Some(format!("`effect {}`", effect.name.name));
+                }
+                for op in effect.operations.iter() {
+                    if matches(&op.name.name) {
+                        return Some(format!(
+                            "`{}` : `{}` (effect `{}`)",
+                            op.name.name,
+                            Self::type_expr_to_string(&op.ty),
+                            effect.name.name
+                        ));
+                    }
+                }
+            }
             ModuleItem::InstanceDecl(instance_decl) => {
                 if instance_decl.name.name == ident {
                     return Some(format!("`{}`", Self::format_instance_decl(instance_decl)));
@@ -68,6 +68,15 @@ impl Backend {
                     locations,
                 );
             }
+            ModuleItem::EffectDecl(effect_decl) => {
+                Self::collect_effect_references(
+                    effect_decl,
+                    ident,
+                    uri,
+                    include_declaration,
+                    locations,
+                );
+            }
         }
     }
 
@@ -170,6 +179,30 @@ impl Backend {
         }
     }
 
+    fn collect_effect_references(
+        effect_decl: &EffectDecl,
+        ident: &str,
+        uri: &Url,
+        include_declaration: bool,
+        locations: &mut Vec<Location>,
+    ) {
+        if include_declaration && effect_decl.name.name == ident {
+            locations.push(Location::new(
+                uri.clone(),
+                Self::span_to_range(effect_decl.name.span.clone()),
+            ));
+        }
+        for op in effect_decl.operations.iter() {
+            if include_declaration && op.name.name == ident {
+                locations.push(Location::new(
+                    uri.clone(),
+                    Self::span_to_range(op.name.span.clone()),
+                ));
+            }
+            Self::collect_type_expr_references(&op.ty, ident, uri, locations);
+        }
+    }
+
     fn collect_instance_references(
         instance_decl: &InstanceDecl,
         ident: &str,
@@ -529,6 +562,10 @@ impl Backend {
                     Self::collect_block_item_references(item, ident, text, uri, locations);
                 }
             }
+            Expr::Handle { body, handler, .. } => {
+                Self::collect_expr_references(body, ident, text, uri, locations);
+                Self::collect_expr_references(handler, ident, text, uri, locations);
+            }
             Expr::Raw { .. } => {}
         }
     }
@@ -82,17 +82,35 @@ impl Backend {
             .map(Self::format_type_ctor)
             .collect::<Vec<_>>()
             .join(" | ");
+        let deriving = Self::format_deriving(&decl.deriving);
         if ctors.is_empty() {
-            format!("type {}{}", decl.name.name, params)
+            format!("type {}{}{}", decl.name.name, params, deriving)
         } else {
-            format!("type {}{} = {}", decl.name.name, params, ctors)
+            format!("type {}{} = {}{}", decl.name.name, params, ctors, deriving)
         }
     }
 
     fn format_type_alias(alias: &TypeAlias) -> String {
         let params = Self::format_type_params(&alias.params);
         let aliased = Self::type_expr_to_string(&alias.aliased);
-        format!("type {}{} = {}", alias.name.name, params, aliased)
+        let deriving = Self::format_deriving(&alias.deriving);
+        let keyword = if alias.opaque { "opaque type" } else { "type" };
+        format!(
+            "{keyword} {}{} = {}{}",
+            alias.name.name, params, aliased, deriving
+        )
+    }
+
+    fn format_deriving(classes: &[SpannedName]) -> String {
+        if classes.is_empty() {
+            return String::new();
+        }
+        let classes = classes
+            .iter()
+            .map(|class| class.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(" deriving ({classes})")
     }
 
     #[allow(unused)]
@@ -193,13 +211,16 @@ impl Backend {
                     format!("{} -> {}", params_str, result_str)
                 }
             }
-            TypeExpr::Record { fields, .. } => {
+            TypeExpr::Record { fields, rest, .. } => {
                 let fields_str = fields
                     .iter()
                     .map(|(name, ty)| format!("{}: {}", name.name, Self::type_expr_to_string(ty)))
                     .collect::<Vec<_>>()
                     .join(", ");
-                format!("{{{}}}", fields_str)
+                match rest {
+                    Some(rest) => format!("{{{} | {}}}", fields_str, rest.name),
+                    None => format!("{{{}}}", fields_str),
+                }
             }
             TypeExpr::Tuple { items, .. } => {
                 let items_str = items
@@ -253,6 +274,16 @@ impl Backend {
                         }
                     }
                 }
+                ModuleItem::EffectDecl(effect_decl) => {
+                    if effect_decl.name.name == ident {
+                        return Some(Self::span_to_range(effect_decl.name.span.clone()));
+                    }
+                    for op in effect_decl.operations.iter() {
+                        if op.name.name == ident {
+                            return Some(Self::span_to_range(op.name.span.clone()));
+                        }
+                    }
+                }
                 ModuleItem::InstanceDecl(instance_decl) => {
                     if instance_decl.name.name == ident {
                         return Some(Self::span_to_range(instance_decl.name.span.clone()));
@@ -171,6 +171,9 @@ impl Backend {
             ModuleItem::DomainDecl(domain_decl) => {
                 Some((domain_decl.name.name, CompletionItemKind::MODULE))
             }
+            ModuleItem::EffectDecl(effect_decl) => {
+                Some((effect_decl.name.name, CompletionItemKind::INTERFACE))
+            }
         }
     }
 
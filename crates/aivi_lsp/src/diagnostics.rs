@@ -1,14 +1,16 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use aivi::{check_modules, check_types, embedded_stdlib_modules, parse_modules};
 use tower_lsp::lsp_types::{
-    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, DiagnosticRelatedInformation,
-    DiagnosticSeverity, Location, NumberOrString, Position, Range, TextEdit, Url, WorkspaceEdit,
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeDescription, Diagnostic,
+    DiagnosticRelatedInformation, DiagnosticSeverity, Hover, HoverContents, Location,
+    MarkupContent, MarkupKind, NumberOrString, Position, TextEdit, Url, WorkspaceEdit,
 };
 
 use crate::backend::Backend;
-use crate::state::IndexedModule;
+use crate::state::{IndexedModule, ProjectCatalogs};
 use crate::strict::{build_strict_diagnostics, StrictConfig};
 
 impl Backend {
@@ -24,10 +26,15 @@ impl Backend {
 
     #[cfg(test)]
     pub(super) fn build_diagnostics(text: &str, uri: &Url) -> Vec<Diagnostic> {
+        let path = PathBuf::from(Self::path_from_uri(uri));
+        let catalogs = Self::i18n_project_root(&path)
+            .map(|root| Self::load_project_catalogs(&root).catalogs)
+            .unwrap_or_default();
         Self::build_diagnostics_with_workspace(
             text,
             uri,
             &HashMap::new(),
+            &catalogs,
             false,
             &StrictConfig::default(),
         )
@@ -39,13 +46,14 @@ impl Backend {
         uri: &Url,
         strict: &StrictConfig,
     ) -> Vec<Diagnostic> {
-        Self::build_diagnostics_with_workspace(text, uri, &HashMap::new(), false, strict)
+        Self::build_diagnostics_with_workspace(text, uri, &HashMap::new(), &[], false, strict)
     }
 
     pub(super) fn build_diagnostics_with_workspace(
         text: &str,
         uri: &Url,
         workspace_modules: &HashMap<String, IndexedModule>,
+        i18n_catalogs: &[aivi::I18nCatalog],
         include_specs_snippets: bool,
         strict: &StrictConfig,
     ) -> Vec<Diagnostic> {
@@ -104,9 +112,56 @@ impl Backend {
             workspace_modules,
         ));
 
+        out.extend(Self::build_i18n_diagnostics(
+            text,
+            uri,
+            &path,
+            i18n_catalogs,
+        ));
+
         out
     }
 
+    /// Missing-translation warnings on `~k` keys, checked against the catalogs of the enclosing
+    /// `aivi.toml` project (`[i18n] catalogs`). Projects without catalogs get nothing.
+    fn build_i18n_diagnostics(
+        text: &str,
+        uri: &Url,
+        path: &Path,
+        catalogs: &[aivi::I18nCatalog],
+    ) -> Vec<Diagnostic> {
+        if catalogs.is_empty() {
+            return Vec::new();
+        }
+        let usages = aivi::collect_i18n_key_usages(&path.to_string_lossy(), text);
+        aivi::missing_i18n_translations(&usages, catalogs)
+            .into_iter()
+            .map(|file_diag| Self::file_diag_to_lsp(uri, file_diag))
+            .collect()
+    }
+
+    pub(super) fn i18n_project_root(path: &Path) -> Option<PathBuf> {
+        path.ancestors()
+            .find(|dir| dir.join("aivi.toml").is_file())
+            .map(Path::to_path_buf)
+    }
+
+    /// Reads `aivi.toml` below `root` and loads its catalog directory. Unreadable catalogs count as
+    /// none; `aivi i18n check` reports them.
+    pub(super) fn load_project_catalogs(root: &Path) -> ProjectCatalogs {
+        let config = aivi::read_aivi_toml(&root.join("aivi.toml"))
+            .map(|toml| toml.i18n)
+            .unwrap_or_default();
+        let dir = root.join(config.catalogs);
+        let catalogs = aivi::load_i18n_catalogs(&dir)
+            .map(|(catalogs, _)| catalogs)
+            .unwrap_or_default();
+        ProjectCatalogs {
+            dir,
+            catalogs: Arc::new(catalogs),
+        }
+    }
+
     fn file_diag_to_lsp(uri: &Url, file_diag: aivi::FileDiagnostic) -> Diagnostic {
         let related_information = (!file_diag.diagnostic.labels.is_empty()).then(|| {
             file_diag
@@ -131,7 +186,9 @@ impl Backend {
                 aivi::DiagnosticSeverity::Warning => DiagnosticSeverity::WARNING,
             }),
             code: Some(NumberOrString::String(code.clone())),
-            code_description: None,
+            code_description: aivi::error_code_info(&code)
+                .and_then(|info| Url::parse(&aivi::error_code_docs_url(info.code)).ok())
+                .map(|href| CodeDescription { href }),
             source: Some(format!("aivi.{}", category_for_code(&code))),
             message: file_diag.diagnostic.message,
             related_information,
@@ -140,45 +197,58 @@ impl Backend {
         }
     }
 
-    pub(super) fn end_position(text: &str) -> Position {
-        let mut line = 0u32;
-        let mut column = 0u32;
-        for ch in text.chars() {
-            if ch == '\n' {
-                line += 1;
-                column = 0;
-            } else {
-                column += 1;
-            }
-        }
-        Position::new(line, column)
-    }
-
-    fn end_of_line_position(text: &str, line: u32) -> Position {
-        let parts: Vec<&str> = text.split('\n').collect();
-        let column = parts
-            .get(line as usize)
-            .map(|line| line.chars().count() as u32)
-            .unwrap_or(0);
-        Position::new(line, column)
-    }
-
-    fn closing_for(open: char) -> Option<char> {
-        match open {
-            '{' => Some('}'),
-            '(' => Some(')'),
-            '[' => Some(']'),
-            _ => None,
-        }
+    /// Registry explanations for the codes of the diagnostics under `position`.
+    pub(super) fn explain_diagnostics_at(
+        diagnostics: &[Diagnostic],
+        position: Position,
+    ) -> Option<String> {
+        let mut codes: Vec<&str> = diagnostics
+            .iter()
+            .filter(|diag| diag.range.start <= position && position <= diag.range.end)
+            .filter_map(|diag| match diag.code.as_ref() {
+                Some(NumberOrString::String(code)) => Some(code.as_str()),
+                _ => None,
+            })
+            .collect();
+        codes.sort_unstable();
+        codes.dedup();
+        let sections: Vec<String> = codes
+            .into_iter()
+            .filter_map(aivi::error_code_info)
+            .map(|info| {
+                format!(
+                    "**{}**: {}\n\n{}\n[Documentation]({})",
+                    info.code,
+                    info.title,
+                    info.render_markdown(),
+                    aivi::error_code_docs_url(info.code)
+                )
+            })
+            .collect();
+        (!sections.is_empty()).then(|| sections.join("\n\n---\n\n"))
     }
 
-    fn unclosed_open_delimiter(message: &str) -> Option<char> {
-        let start = message.find('\'')?;
-        let rest = &message[start + 1..];
-        let mut chars = rest.chars();
-        let open = chars.next()?;
-        let end = chars.next()?;
-        (end == '\'').then_some(open)
+    /// Appends `markdown` below the contents of `hover`.
+    pub(super) fn append_hover_markdown(
+        hover: Option<Hover>,
+        markdown: Option<String>,
+    ) -> Option<Hover> {
+        let Some(markdown) = markdown else {
+            return hover;
+        };
+        let value = match hover.map(|hover| hover.contents) {
+            Some(HoverContents::Markup(existing)) => {
+                format!("{}\n\n---\n\n{markdown}", existing.value)
+            }
+            _ => markdown,
+        };
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        })
     }
 
     pub(super) fn build_code_actions(
@@ -194,88 +264,50 @@ impl Backend {
                 out.extend(actions);
             }
 
-            let code = match diagnostic.code.as_ref() {
-                Some(NumberOrString::String(code)) => code.as_str(),
-                Some(NumberOrString::Number(_)) => continue,
-                None => continue,
+            let Some(NumberOrString::String(code)) = diagnostic.code.as_ref() else {
+                continue;
             };
-
-            match code {
-                "E1004" => {
-                    let Some(open) = Self::unclosed_open_delimiter(&diagnostic.message) else {
-                        continue;
-                    };
-                    let Some(close) = Self::closing_for(open) else {
-                        continue;
-                    };
-                    let position = Self::end_position(text);
-                    let range = Range::new(position, position);
-                    let edit = TextEdit {
-                        range,
-                        new_text: close.to_string(),
-                    };
-                    out.push(CodeActionOrCommand::CodeAction(CodeAction {
-                        title: format!("Insert missing '{close}'"),
-                        kind: Some(CodeActionKind::QUICKFIX),
-                        diagnostics: Some(vec![diagnostic.clone()]),
-                        edit: Some(WorkspaceEdit {
-                            changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
-                            document_changes: None,
-                            change_annotations: None,
-                        }),
-                        command: None,
-                        is_preferred: Some(true),
-                        disabled: None,
-                        data: None,
-                    }));
-                }
-                "E1002" => {
-                    out.push(CodeActionOrCommand::CodeAction(CodeAction {
-                        title: "Remove unmatched closing delimiter".to_string(),
-                        kind: Some(CodeActionKind::QUICKFIX),
-                        diagnostics: Some(vec![diagnostic.clone()]),
-                        edit: Some(WorkspaceEdit {
-                            changes: Some(HashMap::from([(
-                                uri.clone(),
-                                vec![TextEdit {
-                                    range: diagnostic.range,
-                                    new_text: String::new(),
-                                }],
-                            )])),
-                            document_changes: None,
-                            change_annotations: None,
-                        }),
-                        command: None,
-                        is_preferred: Some(true),
-                        disabled: None,
-                        data: None,
-                    }));
-                }
-                "E1001" => {
-                    let position = Self::end_of_line_position(text, diagnostic.range.end.line);
-                    let range = Range::new(position, position);
-                    out.push(CodeActionOrCommand::CodeAction(CodeAction {
-                        title: "Insert missing closing quote".to_string(),
-                        kind: Some(CodeActionKind::QUICKFIX),
-                        diagnostics: Some(vec![diagnostic.clone()]),
-                        edit: Some(WorkspaceEdit {
-                            changes: Some(HashMap::from([(
-                                uri.clone(),
-                                vec![TextEdit {
-                                    range,
-                                    new_text: "\"".to_string(),
-                                }],
-                            )])),
-                            document_changes: None,
-                            change_annotations: None,
-                        }),
-                        command: None,
-                        is_preferred: Some(true),
-                        disabled: None,
-                        data: None,
-                    }));
-                }
-                _ => {}
+            let core = aivi::Diagnostic {
+                code: code.clone(),
+                severity: match diagnostic.severity {
+                    Some(DiagnosticSeverity::WARNING) => aivi::DiagnosticSeverity::Warning,
+                    _ => aivi::DiagnosticSeverity::Error,
+                },
+                message: diagnostic.message.clone(),
+                span: Self::range_to_span(diagnostic.range),
+                labels: diagnostic
+                    .related_information
+                    .iter()
+                    .flatten()
+                    .map(|info| aivi::DiagnosticLabel {
+                        message: info.message.clone(),
+                        span: Self::range_to_span(info.location.range),
+                    })
+                    .collect(),
+            };
+            for fix in aivi::diagnostic_fixes(text, &core) {
+                let edits = fix
+                    .edits
+                    .into_iter()
+                    .map(|edit| TextEdit {
+                        range: Self::span_to_range(edit.span),
+                        new_text: edit.new_text,
+                    })
+                    .collect();
+                out.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: fix.title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), edits)])),
+                        document_changes: None,
+                        change_annotations: None,
+                    }),
+                    command: None,
+                    is_preferred: Some(fix.is_preferred),
+                    disabled: None,
+                    data: None,
+                }));
             }
         }
         out
@@ -284,6 +316,9 @@ impl Backend {
 
 fn category_for_code(code: &str) -> &'static str {
     // Keep the mapping coarse and stable; strict-mode uses its own source.
+    if code.get(1..3) == Some("26") {
+        return "I18n";
+    }
     if code.starts_with('E') {
         match &code.get(1..2) {
             Some("1") => "Syntax",
@@ -128,6 +128,36 @@ impl Backend {
                     children: None,
                 })
             }
+            ModuleItem::EffectDecl(effect_decl) => {
+                let range = Self::span_to_range(effect_decl.span);
+                let children = effect_decl
+                    .operations
+                    .into_iter()
+                    .map(|op| {
+                        let range = Self::span_to_range(op.span);
+                        DocumentSymbol {
+                            name: op.name.name,
+                            detail: Some("effect operation".to_string()),
+                            kind: SymbolKind::FUNCTION,
+                            tags: None,
+                            deprecated: None,
+                            range,
+                            selection_range: range,
+                            children: None,
+                        }
+                    })
+                    .collect();
+                Some(DocumentSymbol {
+                    name: effect_decl.name.name,
+                    detail: Some("effect".to_string()),
+                    kind: SymbolKind::INTERFACE,
+                    tags: None,
+                    deprecated: None,
+                    range,
+                    selection_range: range,
+                    children: Some(children),
+                })
+            }
             ModuleItem::DomainDecl(domain_decl) => {
                 let mut children = Vec::new();
                 for domain_item in domain_decl.items {
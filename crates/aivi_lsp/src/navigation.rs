@@ -106,6 +106,10 @@ impl Backend {
                 Self::find_record_field_name_at_position(left, position)
                     .or_else(|| Self::find_record_field_name_at_position(right, position))
             }
+            Expr::Handle { body, handler, .. } => {
+                Self::find_record_field_name_at_position(body, position)
+                    .or_else(|| Self::find_record_field_name_at_position(handler, position))
+            }
             Expr::Block { items, .. } => items.iter().find_map(|item| match item {
                 aivi::BlockItem::Bind { expr, .. }
                 | aivi::BlockItem::Let { expr, .. }
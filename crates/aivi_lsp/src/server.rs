@@ -209,6 +209,7 @@ impl LanguageServer for Backend {
         let version = params.text_document.version;
         self.update_document(uri.clone(), text).await;
         let workspace = self.workspace_modules_for(&uri).await;
+        let catalogs = self.i18n_catalogs_for(&uri).await;
         let (include_specs_snippets, strict) = {
             let state = self.state.lock().await;
             (state.diagnostics_in_specs_snippets, state.strict.clone())
@@ -219,12 +220,14 @@ impl LanguageServer for Backend {
                     content,
                     &uri,
                     &workspace,
+                    &catalogs,
                     include_specs_snippets,
                     &strict,
                 )
             })
             .await
         {
+            self.set_document_diagnostics(&uri, &diagnostics).await;
             self.client
                 .publish_diagnostics(uri, diagnostics, Some(version))
                 .await;
@@ -237,6 +240,7 @@ impl LanguageServer for Backend {
         if let Some(change) = params.content_changes.into_iter().next() {
             self.update_document(uri.clone(), change.text).await;
             let workspace = self.workspace_modules_for(&uri).await;
+            let catalogs = self.i18n_catalogs_for(&uri).await;
             let (include_specs_snippets, strict) = {
                 let state = self.state.lock().await;
                 (state.diagnostics_in_specs_snippets, state.strict.clone())
@@ -247,12 +251,14 @@ impl LanguageServer for Backend {
                         content,
                         &uri,
                         &workspace,
+                        &catalogs,
                         include_specs_snippets,
                         &strict,
                     )
                 })
                 .await
             {
+                self.set_document_diagnostics(&uri, &diagnostics).await;
                 self.client
                     .publish_diagnostics(uri, diagnostics, Some(version))
                     .await;
@@ -273,6 +279,7 @@ impl LanguageServer for Backend {
             let Ok(path) = change.uri.to_file_path() else {
                 continue;
             };
+            self.invalidate_i18n_catalogs_for_path(&path).await;
             match change.typ {
                 FileChangeType::CREATED | FileChangeType::CHANGED => {
                     if path.extension().and_then(|e| e.to_str()) == Some("aivi") {
@@ -400,7 +407,14 @@ impl LanguageServer for Backend {
             }
             None => None,
         };
-        Ok(hover)
+        let explanation = {
+            let state = self.state.lock().await;
+            state
+                .documents
+                .get(&uri)
+                .and_then(|doc| Self::explain_diagnostics_at(&doc.diagnostics, position))
+        };
+        Ok(Self::append_hover_markdown(hover, explanation))
     }
 
     async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
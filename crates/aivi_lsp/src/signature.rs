@@ -232,6 +232,8 @@ impl Backend {
                 .or_else(|| Self::find_call_info(else_branch, position)),
             Expr::Binary { left, right, .. } => Self::find_call_info(left, position)
                 .or_else(|| Self::find_call_info(right, position)),
+            Expr::Handle { body, handler, .. } => Self::find_call_info(body, position)
+                .or_else(|| Self::find_call_info(handler, position)),
             Expr::Block { items, .. } => items.iter().find_map(|item| match item {
                 BlockItem::Bind { expr, .. } => Self::find_call_info(expr, position),
                 BlockItem::Let { expr, .. } => Self::find_call_info(expr, position),
@@ -299,6 +301,7 @@ impl Backend {
             | Expr::Match { span, .. }
             | Expr::If { span, .. }
             | Expr::Binary { span, .. }
+            | Expr::Handle { span, .. }
             | Expr::Block { span, .. }
             | Expr::Raw { span, .. } => span,
         }
@@ -2,8 +2,8 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use aivi::Module;
-use tower_lsp::lsp_types::Url;
+use aivi::{I18nCatalog, Module};
+use tower_lsp::lsp_types::{Diagnostic, Url};
 
 use crate::doc_index::{DocIndex, DOC_INDEX_JSON};
 use crate::strict::StrictConfig;
@@ -11,6 +11,16 @@ use crate::strict::StrictConfig;
 #[derive(Default)]
 pub(super) struct DocumentState {
     pub(super) text: String,
+    /// Last diagnostics published for the document; hovers explain their codes.
+    pub(super) diagnostics: Vec<Diagnostic>,
+}
+
+/// `[i18n] catalogs` of one `aivi.toml` project, loaded once and dropped when a file below
+/// `dir` (or the project's `aivi.toml`) changes.
+#[derive(Debug, Clone)]
+pub(super) struct ProjectCatalogs {
+    pub(super) dir: PathBuf,
+    pub(super) catalogs: Arc<Vec<I18nCatalog>>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -26,6 +36,8 @@ pub(super) struct BackendState {
     pub(super) open_modules_by_uri: HashMap<Url, Vec<String>>,
     pub(super) open_module_index: HashMap<String, IndexedModule>,
     pub(super) disk_indexes: HashMap<PathBuf, DiskIndex>,
+    /// Keyed by the directory holding `aivi.toml`.
+    pub(super) i18n_catalogs: HashMap<PathBuf, ProjectCatalogs>,
     pub(super) format_options: aivi::FormatOptions,
     pub(super) format_options_from_config: bool,
     pub(super) diagnostics_in_specs_snippets: bool,
@@ -43,6 +55,7 @@ impl Default for BackendState {
             open_modules_by_uri: HashMap::new(),
             open_module_index: HashMap::new(),
             disk_indexes: HashMap::new(),
+            i18n_catalogs: HashMap::new(),
             format_options: aivi::FormatOptions::default(),
             format_options_from_config: false,
             diagnostics_in_specs_snippets: false,
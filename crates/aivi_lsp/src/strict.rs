@@ -11,9 +11,10 @@ use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, TextE
 
 use crate::backend::Backend;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) enum StrictLevel {
+    #[default]
     Off = 0,
     LexicalStructural = 1,
     NamesImports = 2,
@@ -35,12 +36,6 @@ impl StrictLevel {
     }
 }
 
-impl Default for StrictLevel {
-    fn default() -> Self {
-        StrictLevel::Off
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct StrictConfig {
@@ -68,12 +63,10 @@ impl Default for StrictConfig {
 pub(crate) enum StrictCategory {
     Syntax,
     Import,
-    NameResolution,
     Pipe,
     Pattern,
     Effect,
     Generator,
-    Sigil,
     Style,
     Kernel,
     Domain,
@@ -85,12 +78,10 @@ impl StrictCategory {
         match self {
             StrictCategory::Syntax => "Syntax",
             StrictCategory::Import => "Import",
-            StrictCategory::NameResolution => "NameResolution",
             StrictCategory::Pipe => "Pipe",
             StrictCategory::Pattern => "Pattern",
             StrictCategory::Effect => "Effect",
             StrictCategory::Generator => "Generator",
-            StrictCategory::Sigil => "Sigil",
             StrictCategory::Style => "Style",
             StrictCategory::Kernel => "Kernel",
             StrictCategory::Domain => "Domain",
@@ -130,6 +121,7 @@ fn expr_span(expr: &aivi::Expr) -> aivi::Span {
         | aivi::Expr::Match { span, .. }
         | aivi::Expr::If { span, .. }
         | aivi::Expr::Binary { span, .. }
+        | aivi::Expr::Handle { span, .. }
         | aivi::Expr::Block { span, .. }
         | aivi::Expr::Raw { span, .. } => span.clone(),
     }
@@ -507,34 +499,35 @@ fn strict_tuple_intent(file_modules: &[Module], out: &mut Vec<Diagnostic>) {
                 for item in items {
                     // Strict: `(a b, c)` often means `(a, b, c)`; surface parse sees `a b` as a call.
                     if let aivi::Expr::Call { func, args, span } = item {
-                        if matches!(&**func, aivi::Expr::Ident(_)) && args.len() == 1 {
-                            if matches!(&args[0], aivi::Expr::Ident(_)) {
-                                let func_span = expr_span(func);
-                                let insert_at = aivi::Span {
-                                    start: func_span.end.clone(),
-                                    end: func_span.end.clone(),
-                                };
-                                let edit = TextEdit {
-                                    range: Backend::span_to_range(insert_at.clone()),
-                                    new_text: ",".to_string(),
-                                };
-                                let message = format!(
+                        if matches!(&**func, aivi::Expr::Ident(_))
+                            && args.len() == 1
+                            && matches!(&args[0], aivi::Expr::Ident(_))
+                        {
+                            let func_span = expr_span(func);
+                            let insert_at = aivi::Span {
+                                start: func_span.end.clone(),
+                                end: func_span.end.clone(),
+                            };
+                            let edit = TextEdit {
+                                range: Backend::span_to_range(insert_at.clone()),
+                                new_text: ",".to_string(),
+                            };
+                            let message = format!(
                                     "AIVI-S020 [{}]\nSuspicious tuple element.\nFound: function application inside a tuple element.\nHint: If you meant a 3-tuple, use commas.\nFix: Insert ',' after the first name.",
                                     StrictCategory::Syntax.as_str(),
                                 );
-                                out.push(diag_with_fix(
-                                    "AIVI-S020",
-                                    StrictCategory::Syntax,
-                                    DiagnosticSeverity::WARNING,
-                                    message,
-                                    Backend::span_to_range(span.clone()),
-                                    Some(StrictFix {
-                                        title: "Insert missing comma".to_string(),
-                                        edits: vec![edit],
-                                        is_preferred: false,
-                                    }),
-                                ));
-                            }
+                            out.push(diag_with_fix(
+                                "AIVI-S020",
+                                StrictCategory::Syntax,
+                                DiagnosticSeverity::WARNING,
+                                message,
+                                Backend::span_to_range(span.clone()),
+                                Some(StrictFix {
+                                    title: "Insert missing comma".to_string(),
+                                    edits: vec![edit],
+                                    is_preferred: false,
+                                }),
+                            ));
                         }
                     }
                     walk_expr(item, out);
@@ -584,6 +577,10 @@ fn strict_tuple_intent(file_modules: &[Module], out: &mut Vec<Diagnostic>) {
                 walk_expr(left, out);
                 walk_expr(right, out);
             }
+            aivi::Expr::Handle { body, handler, .. } => {
+                walk_expr(body, out);
+                walk_expr(handler, out);
+            }
             aivi::Expr::Block { items, .. } => {
                 for item in items {
                     match item {
@@ -734,6 +731,10 @@ fn strict_pipe_discipline(file_modules: &[Module], out: &mut Vec<Diagnostic>) {
                 walk_expr(left, out);
                 walk_expr(right, out);
             }
+            aivi::Expr::Handle { body, handler, .. } => {
+                walk_expr(body, out);
+                walk_expr(handler, out);
+            }
             aivi::Expr::Block { items, .. } => {
                 for item in items {
                     match item {
@@ -786,12 +787,10 @@ fn strict_record_field_access(file_modules: &[Module], out: &mut Vec<Diagnostic>
                 if let aivi::Expr::Record { fields, .. } = &**base {
                     let mut has = false;
                     for f in fields {
-                        if let Some(seg) = f.path.last() {
-                            if let aivi::PathSegment::Field(name) = seg {
-                                if name.name == field.name {
-                                    has = true;
-                                    break;
-                                }
+                        if let Some(aivi::PathSegment::Field(name)) = f.path.last() {
+                            if name.name == field.name {
+                                has = true;
+                                break;
                             }
                         }
                     }
@@ -870,6 +869,10 @@ fn strict_record_field_access(file_modules: &[Module], out: &mut Vec<Diagnostic>
                 walk_expr(left, out);
                 walk_expr(right, out);
             }
+            aivi::Expr::Handle { body, handler, .. } => {
+                walk_expr(body, out);
+                walk_expr(handler, out);
+            }
             aivi::Expr::Block { items, .. } => {
                 for item in items {
                     match item {
@@ -979,6 +982,10 @@ fn strict_pattern_discipline(file_modules: &[Module], out: &mut Vec<Diagnostic>)
                 walk_expr(left, out);
                 walk_expr(right, out);
             }
+            aivi::Expr::Handle { body, handler, .. } => {
+                walk_expr(body, out);
+                walk_expr(handler, out);
+            }
             aivi::Expr::Block { items, .. } => {
                 for item in items {
                     match item {
@@ -1093,6 +1100,9 @@ fn strict_block_shape(file_modules: &[Module], out: &mut Vec<Diagnostic>) {
             aivi::Expr::Binary { left, right, .. } => {
                 expr_uses_name(left, name) || expr_uses_name(right, name)
             }
+            aivi::Expr::Handle { body, handler, .. } => {
+                expr_uses_name(body, name) || expr_uses_name(handler, name)
+            }
             aivi::Expr::Block { items, .. } => items.iter().any(|item| match item {
                 aivi::BlockItem::Bind { expr, .. }
                 | aivi::BlockItem::Let { expr, .. }
@@ -1197,21 +1207,18 @@ fn strict_block_shape(file_modules: &[Module], out: &mut Vec<Diagnostic>) {
     }
 
     fn walk_expr(expr: &aivi::Expr, out: &mut Vec<Diagnostic>) {
-        match expr {
-            aivi::Expr::Block { kind, items, .. } => {
-                check_block(kind.clone(), items, out);
-                for item in items {
-                    match item {
-                        aivi::BlockItem::Bind { expr, .. }
-                        | aivi::BlockItem::Let { expr, .. }
-                        | aivi::BlockItem::Filter { expr, .. }
-                        | aivi::BlockItem::Yield { expr, .. }
-                        | aivi::BlockItem::Recurse { expr, .. }
-                        | aivi::BlockItem::Expr { expr, .. } => walk_expr(expr, out),
-                    }
+        if let aivi::Expr::Block { kind, items, .. } = expr {
+            check_block(kind.clone(), items, out);
+            for item in items {
+                match item {
+                    aivi::BlockItem::Bind { expr, .. }
+                    | aivi::BlockItem::Let { expr, .. }
+                    | aivi::BlockItem::Filter { expr, .. }
+                    | aivi::BlockItem::Yield { expr, .. }
+                    | aivi::BlockItem::Recurse { expr, .. }
+                    | aivi::BlockItem::Expr { expr, .. } => walk_expr(expr, out),
                 }
             }
-            _ => {}
         }
         // Keep it small: other passes already walk expressions.
     }
@@ -1314,6 +1321,10 @@ fn strict_missing_import_suggestions(
                 collect_idents(left, out);
                 collect_idents(right, out);
             }
+            aivi::Expr::Handle { body, handler, .. } => {
+                collect_idents(body, out);
+                collect_idents(handler, out);
+            }
             aivi::Expr::Block { items, .. } => {
                 for item in items {
                     match item {
@@ -1534,6 +1545,10 @@ fn strict_expected_type_coercions(
                 collect_calls(left, out);
                 collect_calls(right, out);
             }
+            aivi::Expr::Handle { body, handler, .. } => {
+                collect_calls(body, out);
+                collect_calls(handler, out);
+            }
             aivi::Expr::Block { items, .. } => {
                 for item in items {
                     match item {
@@ -1804,6 +1819,11 @@ fn strict_kernel_consistency(
                         | aivi::KernelBlockItem::Expr { expr } => {
                             walk_expr(expr, seen_ids, span_hint, out);
                         }
+                        aivi::KernelBlockItem::LetRec { bindings } => {
+                            for binding in bindings {
+                                walk_expr(&binding.expr, seen_ids, span_hint, out);
+                            }
+                        }
                     }
                 }
             }
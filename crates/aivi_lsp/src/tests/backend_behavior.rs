@@ -37,6 +37,36 @@ value = Some 1 ?
     }));
 }
 
+#[test]
+fn diagnostics_link_and_explain_their_codes() {
+    let text = r#"module demo
+
+Option A = None | Some A
+
+value = Some 1 ?
+  | Some _ => 1
+"#;
+    let uri = sample_uri();
+    let diagnostics = Backend::build_diagnostics(text, &uri);
+    let diag = diagnostics
+        .iter()
+        .find(|diag| {
+            matches!(diag.code.as_ref(), Some(NumberOrString::String(code)) if code == "E3100")
+        })
+        .expect("E3100 diagnostic");
+    let href = &diag
+        .code_description
+        .as_ref()
+        .expect("code description")
+        .href;
+    assert!(href.as_str().ends_with("#e3100"), "{href}");
+
+    let explanation =
+        Backend::explain_diagnostics_at(&diagnostics, diag.range.start).expect("hover explanation");
+    assert!(explanation.contains("Non-exhaustive match"));
+    assert!(explanation.contains(href.as_str()));
+}
+
 #[test]
 #[ignore]
 fn diagnostics_report_missing_list_comma() {
@@ -101,7 +131,7 @@ fn code_actions_offer_quick_fix_for_unclosed_delimiter() {
     let uri = sample_uri();
     let diagnostics = Backend::build_diagnostics(text, &uri);
     let actions = Backend::build_code_actions(text, &uri, &diagnostics);
-    let expected_pos = Backend::end_position(text);
+    let expected_pos = Backend::full_document_range(text).end;
 
     let mut saw_fix = false;
     for action in actions {
@@ -133,6 +163,45 @@ fn code_actions_offer_quick_fix_for_unclosed_delimiter() {
     assert!(saw_fix);
 }
 
+#[test]
+fn code_actions_fill_typed_holes_with_candidates() {
+    let text = r#"module demo
+
+export pick
+
+Option A = None | Some A
+
+pick : Option Int -> Int -> Int
+pick = opt fallback => opt ?
+  | Some x => ?found
+  | None   => fallback
+"#;
+    let uri = sample_uri();
+    let diagnostics = Backend::build_diagnostics(text, &uri);
+    let hole = diagnostics
+        .iter()
+        .find(|diag| {
+            matches!(diag.code.as_ref(), Some(NumberOrString::String(code)) if code == "E3200")
+        })
+        .expect("typed hole diagnostic");
+    assert_eq!(hole.message, "hole '?found' has type Int");
+
+    let actions = Backend::build_code_actions(text, &uri, &diagnostics);
+    let fill = actions
+        .into_iter()
+        .find_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) if action.title == "Fill hole with `x`" => {
+                Some(action)
+            }
+            _ => None,
+        })
+        .expect("fill action");
+    let edits = &fill.edit.expect("edit").changes.expect("changes")[&uri];
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range, hole.range);
+    assert_eq!(edits[0].new_text, "x");
+}
+
 #[test]
 fn strict_mode_reports_split_arrow_and_offers_fix() {
     let text = "module demo\n\nid = x = > x\n";
@@ -155,6 +224,84 @@ fn strict_mode_reports_split_arrow_and_offers_fix() {
     }));
 }
 
+#[test]
+fn diagnostics_warn_about_missing_translations() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        dir.path().join("aivi.toml"),
+        "[project]\nkind = \"bin\"\nentry = \"main.aivi\"\n",
+    )
+    .expect("write aivi.toml");
+    std::fs::create_dir_all(dir.path().join("i18n")).expect("create i18n dir");
+    std::fs::write(
+        dir.path().join("i18n/en.properties"),
+        "app.title = Title\napp.body = Body\n",
+    )
+    .expect("write en catalog");
+    std::fs::write(
+        dir.path().join("i18n/de.properties"),
+        "app.title = Titel\napp.body =\n",
+    )
+    .expect("write de catalog");
+    let path = dir.path().join("main.aivi");
+    let text = "module app.main\n\ntitle = ~k\"app.title\"\nbody = ~k\"app.body\"\n";
+    let uri = Url::from_file_path(&path).expect("file uri");
+
+    let diagnostics = Backend::build_diagnostics(text, &uri);
+    let missing: Vec<_> = diagnostics
+        .iter()
+        .filter(|diag| {
+            matches!(diag.code.as_ref(), Some(NumberOrString::String(code)) if code == "W2600")
+        })
+        .collect();
+    assert_eq!(missing.len(), 1, "diagnostics: {diagnostics:?}");
+    assert_eq!(missing[0].severity, Some(DiagnosticSeverity::WARNING));
+    assert_eq!(missing[0].range.start.line, 3);
+    assert!(missing[0].message.contains("'app.body' in de"));
+}
+
+#[test]
+fn i18n_catalogs_are_cached_until_a_catalog_file_changes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        dir.path().join("aivi.toml"),
+        "[project]\nkind = \"bin\"\nentry = \"main.aivi\"\n",
+    )
+    .expect("write aivi.toml");
+    std::fs::create_dir_all(dir.path().join("i18n")).expect("create i18n dir");
+    let en = dir.path().join("i18n/en.properties");
+    std::fs::write(&en, "app.title = Title\n").expect("write en catalog");
+    let uri = Url::from_file_path(dir.path().join("main.aivi")).expect("file uri");
+
+    let (service, _socket) = tower_lsp::LspService::new(|client| Backend {
+        client,
+        state: Default::default(),
+    });
+    let backend = service.inner();
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    runtime.block_on(async {
+        let first = backend.i18n_catalogs_for(&uri).await;
+        assert_eq!(first.len(), 1);
+
+        std::fs::write(dir.path().join("i18n/de.properties"), "app.title = Titel\n")
+            .expect("write de catalog");
+        let cached = backend.i18n_catalogs_for(&uri).await;
+        assert!(std::sync::Arc::ptr_eq(&first, &cached));
+
+        backend
+            .invalidate_i18n_catalogs_for_path(&dir.path().join("main.aivi"))
+            .await;
+        assert!(std::sync::Arc::ptr_eq(
+            &first,
+            &backend.i18n_catalogs_for(&uri).await
+        ));
+
+        backend.invalidate_i18n_catalogs_for_path(&en).await;
+        let reloaded = backend.i18n_catalogs_for(&uri).await;
+        assert_eq!(reloaded.len(), 2);
+    });
+}
+
 #[test]
 fn document_symbols_include_module_and_children() {
     let text = sample_text();
@@ -171,6 +171,7 @@ fn examples_open_without_lsp_errors() {
             &text,
             &uri,
             &workspace,
+            &[],
             false,
             &crate::strict::StrictConfig::default(),
         );
@@ -221,6 +222,7 @@ fn specs_snippets_open_without_lsp_diagnostics() {
             &text,
             &uri,
             &HashMap::new(),
+            &[],
             false,
             &crate::strict::StrictConfig::default(),
         );
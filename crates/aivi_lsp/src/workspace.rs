@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use aivi::{embedded_stdlib_modules, parse_modules};
-use tower_lsp::lsp_types::Url;
+use aivi::{embedded_stdlib_modules, parse_modules, I18nCatalog};
+use tower_lsp::lsp_types::{Diagnostic, Url};
 
 use crate::backend::Backend;
 use crate::state::{DiskIndex, DocumentState, IndexedModule};
@@ -122,6 +123,41 @@ impl Backend {
         state.disk_indexes.remove(&root);
     }
 
+    /// Catalogs of the project enclosing `uri`, loaded on first use and cached until a watched
+    /// file below the catalog directory changes.
+    pub(super) async fn i18n_catalogs_for(&self, uri: &Url) -> Arc<Vec<I18nCatalog>> {
+        let path = PathBuf::from(Self::path_from_uri(uri));
+        let Some(root) = Self::i18n_project_root(&path) else {
+            return Arc::default();
+        };
+        let existing = {
+            let state = self.state.lock().await;
+            state.i18n_catalogs.get(&root).cloned()
+        };
+        if let Some(existing) = existing {
+            return existing.catalogs;
+        }
+        let root_clone = root.clone();
+        let Ok(loaded) =
+            tokio::task::spawn_blocking(move || Self::load_project_catalogs(&root_clone)).await
+        else {
+            return Arc::default();
+        };
+        let catalogs = loaded.catalogs.clone();
+        let mut state = self.state.lock().await;
+        state.i18n_catalogs.insert(root, loaded);
+        catalogs
+    }
+
+    /// Drops cached catalogs when `path` is a project's `aivi.toml` or lies in its catalog
+    /// directory.
+    pub(super) async fn invalidate_i18n_catalogs_for_path(&self, path: &Path) {
+        let mut state = self.state.lock().await;
+        state.i18n_catalogs.retain(|root, project| {
+            !path.starts_with(&project.dir) && path != root.join("aivi.toml")
+        });
+    }
+
     pub(super) async fn refresh_disk_index_file(&self, path: &Path) {
         let workspace_folders = {
             let state = self.state.lock().await;
@@ -286,7 +322,13 @@ impl Backend {
             );
         }
         state.open_modules_by_uri.insert(uri.clone(), module_names);
-        state.documents.insert(uri, DocumentState { text });
+        state.documents.insert(
+            uri,
+            DocumentState {
+                text,
+                diagnostics: Vec::new(),
+            },
+        );
     }
 
     pub(super) async fn remove_document(&self, uri: &Url) {
@@ -299,6 +341,13 @@ impl Backend {
         }
     }
 
+    pub(super) async fn set_document_diagnostics(&self, uri: &Url, diagnostics: &[Diagnostic]) {
+        let mut state = self.state.lock().await;
+        if let Some(doc) = state.documents.get_mut(uri) {
+            doc.diagnostics = diagnostics.to_vec();
+        }
+    }
+
     pub(super) async fn with_document_text<F, R>(&self, uri: &Url, f: F) -> Option<R>
     where
         F: FnOnce(&str) -> R,
@@ -1,9 +1,13 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use chrono::{Datelike, Duration as ChronoDuration, NaiveDate};
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, FixedOffset, LocalResult, NaiveDate,
+    NaiveDateTime, NaiveTime, Offset, SecondsFormat, TimeZone as _, Timelike, Utc,
+};
+use chrono_tz::Tz;
 
-use super::util::{builtin, expect_int};
+use super::util::{builtin, expect_int, expect_text, make_err, make_ok};
 use crate::{RuntimeError, Value};
 
 pub(super) fn build_calendar_record() -> Value {
@@ -67,8 +71,526 @@ pub(super) fn build_calendar_record() -> Value {
             Ok(date_to_value(next))
         }),
     );
+    fields.insert(
+        "timeZone".to_string(),
+        builtin("calendar.timeZone", 1, |mut args, _| {
+            let id = expect_text(args.pop().unwrap(), "calendar.timeZone")?;
+            Ok(match Zone::parse(&id) {
+                Ok(zone) => make_ok(zone.to_value()),
+                Err(msg) => make_err(Value::Text(msg)),
+            })
+        }),
+    );
+    fields.insert(
+        "atZone".to_string(),
+        builtin("calendar.atZone", 2, |mut args, _| {
+            let instant = instant_from_value(args.pop().unwrap(), "calendar.atZone")?;
+            let zone = zone_from_value(args.pop().unwrap(), "calendar.atZone")?;
+            Ok(zoned_to_value(&zone, instant))
+        }),
+    );
+    fields.insert(
+        "fromLocal".to_string(),
+        builtin("calendar.fromLocal", 2, |mut args, _| {
+            let local = local_from_value(args.pop().unwrap(), "calendar.fromLocal")?;
+            let zone = zone_from_value(args.pop().unwrap(), "calendar.fromLocal")?;
+            Ok(zoned_to_value(&zone, zone.resolve(local, None)))
+        }),
+    );
+    fields.insert(
+        "toInstant".to_string(),
+        builtin("calendar.toInstant", 1, |mut args, _| {
+            let (_, instant) = zoned_from_value(args.pop().unwrap(), "calendar.toInstant")?;
+            Ok(instant_to_value(instant))
+        }),
+    );
+    fields.insert(
+        "shift".to_string(),
+        builtin("calendar.shift", 2, |mut args, _| {
+            let delta = delta_from_value(args.pop().unwrap(), "calendar.shift")?;
+            shift_value(args.pop().unwrap(), delta)
+        }),
+    );
+    fields.insert(
+        "parseDate".to_string(),
+        builtin("calendar.parseDate", 1, |mut args, _| {
+            let text = expect_text(args.pop().unwrap(), "calendar.parseDate")?;
+            Ok(parse_result(
+                NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+                    .map(date_to_value)
+                    .map_err(|_| format!("invalid ISO 8601 date: {text}")),
+            ))
+        }),
+    );
+    fields.insert(
+        "parseTime".to_string(),
+        builtin("calendar.parseTime", 1, |mut args, _| {
+            let text = expect_text(args.pop().unwrap(), "calendar.parseTime")?;
+            Ok(parse_result(parse_time(&text).map(time_to_value)))
+        }),
+    );
+    fields.insert(
+        "parseLocalDateTime".to_string(),
+        builtin("calendar.parseLocalDateTime", 1, |mut args, _| {
+            let text = expect_text(args.pop().unwrap(), "calendar.parseLocalDateTime")?;
+            Ok(parse_result(parse_local(&text).map(local_to_value)))
+        }),
+    );
+    fields.insert(
+        "parseInstant".to_string(),
+        builtin("calendar.parseInstant", 1, |mut args, _| {
+            let text = expect_text(args.pop().unwrap(), "calendar.parseInstant")?;
+            Ok(parse_result(
+                DateTime::parse_from_rfc3339(text.trim())
+                    .map(|value| instant_to_value(value.with_timezone(&Utc)))
+                    .map_err(|_| format!("invalid RFC 3339 timestamp: {text}")),
+            ))
+        }),
+    );
+    fields.insert(
+        "parseZoned".to_string(),
+        builtin("calendar.parseZoned", 1, |mut args, _| {
+            let text = expect_text(args.pop().unwrap(), "calendar.parseZoned")?;
+            Ok(parse_result(
+                parse_zoned(&text).map(|(zone, instant)| zoned_to_value(&zone, instant)),
+            ))
+        }),
+    );
+    fields.insert(
+        "dateText".to_string(),
+        builtin("calendar.dateText", 1, |mut args, _| {
+            let date = date_from_value(args.pop().unwrap(), "calendar.dateText")?;
+            Ok(Value::Text(date.format("%Y-%m-%d").to_string()))
+        }),
+    );
+    fields.insert(
+        "timeText".to_string(),
+        builtin("calendar.timeText", 1, |mut args, _| {
+            let time = time_from_value(args.pop().unwrap(), "calendar.timeText")?;
+            Ok(Value::Text(time.format("%H:%M:%S").to_string()))
+        }),
+    );
+    fields.insert(
+        "localDateTimeText".to_string(),
+        builtin("calendar.localDateTimeText", 1, |mut args, _| {
+            let local = local_from_value(args.pop().unwrap(), "calendar.localDateTimeText")?;
+            Ok(Value::Text(local.format("%Y-%m-%dT%H:%M:%S").to_string()))
+        }),
+    );
+    fields.insert(
+        "instantText".to_string(),
+        builtin("calendar.instantText", 1, |mut args, _| {
+            let instant = instant_from_value(args.pop().unwrap(), "calendar.instantText")?;
+            Ok(Value::Text(instant_text(instant)))
+        }),
+    );
+    fields.insert(
+        "zonedText".to_string(),
+        builtin("calendar.zonedText", 1, |mut args, _| {
+            let (zone, instant) = zoned_from_value(args.pop().unwrap(), "calendar.zonedText")?;
+            Ok(Value::Text(zoned_text(&zone, instant)))
+        }),
+    );
     Value::Record(Arc::new(fields))
 }
+
+/// A time zone: either an IANA zone from the bundled tz database or a fixed UTC offset.
+#[derive(Clone)]
+enum Zone {
+    Iana(Tz),
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    fn parse(id: &str) -> Result<Zone, String> {
+        let id = id.trim();
+        if id == "UTC" || id == "Z" {
+            return Ok(Zone::Fixed(FixedOffset::east_opt(0).expect("zero offset")));
+        }
+        if id.starts_with('+') || id.starts_with('-') {
+            return parse_offset(id)
+                .map(Zone::Fixed)
+                .ok_or_else(|| format!("invalid UTC offset: {id}"));
+        }
+        id.parse::<Tz>()
+            .map(Zone::Iana)
+            .map_err(|_| format!("unknown time zone: {id}"))
+    }
+
+    fn id(&self) -> String {
+        match self {
+            Zone::Iana(tz) => tz.name().to_string(),
+            Zone::Fixed(offset) if offset.local_minus_utc() == 0 => "UTC".to_string(),
+            Zone::Fixed(offset) => offset_text(offset.local_minus_utc()),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let mut map = HashMap::new();
+        map.insert("id".to_string(), Value::Text(self.id()));
+        Value::Record(Arc::new(map))
+    }
+
+    fn offset_at(&self, instant: DateTime<Utc>) -> FixedOffset {
+        match self {
+            Zone::Iana(tz) => tz.offset_from_utc_datetime(&instant.naive_utc()).fix(),
+            Zone::Fixed(offset) => *offset,
+        }
+    }
+
+    /// Maps a wall-clock time to an instant. Ambiguous times (DST fall-back) keep `prefer` when
+    /// it is one of the candidates and otherwise take the earlier offset; times in a DST gap
+    /// are pushed forward by the length of the gap.
+    fn resolve(&self, local: NaiveDateTime, prefer: Option<FixedOffset>) -> DateTime<Utc> {
+        let tz = match self {
+            Zone::Iana(tz) => tz,
+            Zone::Fixed(offset) => return (local - offset_duration(*offset)).and_utc(),
+        };
+        match tz.from_local_datetime(&local) {
+            LocalResult::Single(value) => value.with_timezone(&Utc),
+            LocalResult::Ambiguous(earlier, later) => {
+                if prefer == Some(later.offset().fix()) {
+                    later.with_timezone(&Utc)
+                } else {
+                    earlier.with_timezone(&Utc)
+                }
+            }
+            LocalResult::None => {
+                let before = tz
+                    .offset_from_utc_datetime(&(local - ChronoDuration::days(1)))
+                    .fix();
+                (local - offset_duration(before)).and_utc()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Delta {
+    Day(i64),
+    Month(i64),
+    Year(i64),
+    Hour(i64),
+    Minute(i64),
+    End,
+}
+
+fn delta_from_value(value: Value, ctx: &str) -> Result<Delta, RuntimeError> {
+    let Value::Constructor { name, args } = value else {
+        return Err(RuntimeError::Message(format!("{ctx} expects Delta")));
+    };
+    let amount = |args: Vec<Value>| match args.into_iter().next() {
+        Some(value) => expect_int(value, ctx),
+        None => Err(RuntimeError::Message(format!("{ctx} expects {name} Int"))),
+    };
+    match name.as_str() {
+        "Day" => Ok(Delta::Day(amount(args)?)),
+        "Month" => Ok(Delta::Month(amount(args)?)),
+        "Year" => Ok(Delta::Year(amount(args)?)),
+        "Hour" => Ok(Delta::Hour(amount(args)?)),
+        "Minute" => Ok(Delta::Minute(amount(args)?)),
+        "End" => Ok(Delta::End),
+        _ => Err(RuntimeError::Message(format!("{ctx} expects Delta"))),
+    }
+}
+
+/// Applies a `Calendar` delta to whichever calendar value the operand is. Day, month and year
+/// deltas follow the wall clock; hour and minute deltas are elapsed time, so on a
+/// `ZonedDateTime` they cross DST transitions exactly.
+fn shift_value(value: Value, delta: Delta) -> Result<Value, RuntimeError> {
+    const CTX: &str = "calendar.shift";
+    match &value {
+        Value::DateTime(_) => {
+            let instant = instant_from_value(value, CTX)?;
+            let utc = Zone::Fixed(FixedOffset::east_opt(0).expect("zero offset"));
+            Ok(instant_to_value(shift_zoned(&utc, instant, delta)?))
+        }
+        Value::Record(fields) if fields.contains_key("dateTime") => {
+            let (zone, instant) = zoned_from_value(value, CTX)?;
+            let shifted = shift_zoned(&zone, instant, delta)?;
+            Ok(zoned_to_value(&zone, shifted))
+        }
+        Value::Record(fields) if fields.contains_key("date") => {
+            let local = local_from_value(value, CTX)?;
+            Ok(local_to_value(shift_local(local, delta)?))
+        }
+        Value::Record(fields) if fields.contains_key("hour") => {
+            let time = time_from_value(value, CTX)?;
+            let anchor = NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid anchor date");
+            let shifted = shift_local(anchor.and_time(time), delta)?;
+            Ok(time_to_value(shifted.time()))
+        }
+        _ => {
+            let date = date_from_value(value, CTX)?;
+            let shifted = shift_local(date.and_time(NaiveTime::MIN), delta)?;
+            Ok(date_to_value(shifted.date()))
+        }
+    }
+}
+
+fn shift_local(local: NaiveDateTime, delta: Delta) -> Result<NaiveDateTime, RuntimeError> {
+    let overflow = || RuntimeError::Message("calendar.shift overflow".to_string());
+    let date = local.date();
+    let shifted = match delta {
+        Delta::Day(n) => {
+            local.checked_add_signed(ChronoDuration::try_days(n).ok_or_else(overflow)?)
+        }
+        Delta::Hour(n) => {
+            local.checked_add_signed(ChronoDuration::try_hours(n).ok_or_else(overflow)?)
+        }
+        Delta::Minute(n) => {
+            local.checked_add_signed(ChronoDuration::try_minutes(n).ok_or_else(overflow)?)
+        }
+        Delta::Month(n) => Some(add_months(date, n).and_time(local.time())),
+        Delta::Year(n) => {
+            Some(add_months(date, n.checked_mul(12).ok_or_else(overflow)?).and_time(local.time()))
+        }
+        Delta::End => NaiveDate::from_ymd_opt(
+            date.year(),
+            date.month(),
+            days_in_month(date.year(), date.month()),
+        )
+        .map(|end| end.and_time(local.time())),
+    };
+    shifted.ok_or_else(overflow)
+}
+
+fn shift_zoned(
+    zone: &Zone,
+    instant: DateTime<Utc>,
+    delta: Delta,
+) -> Result<DateTime<Utc>, RuntimeError> {
+    match delta {
+        Delta::Hour(n) => ChronoDuration::try_hours(n)
+            .and_then(|step| instant.checked_add_signed(step))
+            .ok_or_else(|| RuntimeError::Message("calendar.shift overflow".to_string())),
+        Delta::Minute(n) => ChronoDuration::try_minutes(n)
+            .and_then(|step| instant.checked_add_signed(step))
+            .ok_or_else(|| RuntimeError::Message("calendar.shift overflow".to_string())),
+        _ => {
+            let offset = zone.offset_at(instant);
+            let local = instant.naive_utc() + offset_duration(offset);
+            Ok(zone.resolve(shift_local(local, delta)?, Some(offset)))
+        }
+    }
+}
+
+fn parse_result(result: Result<Value, String>) -> Value {
+    match result {
+        Ok(value) => make_ok(value),
+        Err(msg) => make_err(Value::Text(msg)),
+    }
+}
+
+fn parse_time(text: &str) -> Result<NaiveTime, String> {
+    let trimmed = text.trim();
+    NaiveTime::parse_from_str(trimmed, "%H:%M:%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(trimmed, "%H:%M"))
+        .map(|time| time.with_nanosecond(0).expect("zero nanoseconds"))
+        .map_err(|_| format!("invalid ISO 8601 time: {text}"))
+}
+
+fn parse_local(text: &str) -> Result<NaiveDateTime, String> {
+    let trimmed = text.trim();
+    let (date, time) = trimmed
+        .split_once(['T', ' '])
+        .ok_or_else(|| format!("invalid ISO 8601 date-time: {text}"))?;
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("invalid ISO 8601 date-time: {text}"))?;
+    let time = parse_time(time).map_err(|_| format!("invalid ISO 8601 date-time: {text}"))?;
+    Ok(date.and_time(time))
+}
+
+/// Parses RFC 3339 text with an optional bracketed zone suffix, e.g.
+/// `2024-03-10T03:30:00-04:00[America/New_York]`. Without a suffix the offset becomes a
+/// fixed-offset zone; with a suffix the offset may be omitted and is then resolved in the zone.
+fn parse_zoned(text: &str) -> Result<(Zone, DateTime<Utc>), String> {
+    let trimmed = text.trim();
+    let invalid = || format!("invalid zoned date-time: {text}");
+    let (stamp, zone) = match trimmed.strip_suffix(']') {
+        Some(rest) => {
+            let (stamp, id) = rest.split_once('[').ok_or_else(invalid)?;
+            (stamp, Some(Zone::parse(id)?))
+        }
+        None => (trimmed, None),
+    };
+    match (DateTime::parse_from_rfc3339(stamp), zone) {
+        (Ok(value), Some(zone)) => {
+            let instant = value.with_timezone(&Utc);
+            if zone.offset_at(instant) != *value.offset() {
+                return Err(format!(
+                    "offset {} does not match {} at {stamp}",
+                    offset_text(value.offset().local_minus_utc()),
+                    zone.id()
+                ));
+            }
+            Ok((zone, instant))
+        }
+        (Ok(value), None) => {
+            let zone = Zone::Fixed(*value.offset());
+            Ok((zone, value.with_timezone(&Utc)))
+        }
+        (Err(_), Some(zone)) => {
+            let local = parse_local(stamp).map_err(|_| invalid())?;
+            let instant = zone.resolve(local, None);
+            Ok((zone, instant))
+        }
+        (Err(_), None) => Err(invalid()),
+    }
+}
+
+fn parse_offset(text: &str) -> Option<FixedOffset> {
+    let (sign, rest) = match text.as_bytes().first()? {
+        b'+' => (1, &text[1..]),
+        b'-' => (-1, &text[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn offset_text(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    format!("{sign}{:02}:{:02}", seconds / 3600, seconds % 3600 / 60)
+}
+
+fn offset_duration(offset: FixedOffset) -> ChronoDuration {
+    ChronoDuration::seconds(offset.local_minus_utc() as i64)
+}
+
+fn instant_text(instant: DateTime<Utc>) -> String {
+    instant.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn zoned_text(zone: &Zone, instant: DateTime<Utc>) -> String {
+    let offset = zone.offset_at(instant);
+    let local = instant.with_timezone(&offset);
+    match zone {
+        Zone::Iana(tz) => format!(
+            "{}[{}]",
+            local.to_rfc3339_opts(SecondsFormat::Secs, true),
+            tz.name()
+        ),
+        Zone::Fixed(_) => local.to_rfc3339_opts(SecondsFormat::Secs, true),
+    }
+}
+
+fn instant_from_value(value: Value, ctx: &str) -> Result<DateTime<Utc>, RuntimeError> {
+    let Value::DateTime(text) = value else {
+        return Err(RuntimeError::Message(format!("{ctx} expects Instant")));
+    };
+    DateTime::parse_from_rfc3339(&text)
+        .map(|value| value.with_timezone(&Utc))
+        .map_err(|_| RuntimeError::Message(format!("{ctx} expects RFC 3339 Instant, got {text}")))
+}
+
+fn instant_to_value(instant: DateTime<Utc>) -> Value {
+    Value::DateTime(instant_text(instant))
+}
+
+fn zone_from_value(value: Value, ctx: &str) -> Result<Zone, RuntimeError> {
+    let Value::Record(fields) = value else {
+        return Err(RuntimeError::Message(format!("{ctx} expects TimeZone")));
+    };
+    let id = fields
+        .get("id")
+        .cloned()
+        .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects TimeZone.id")))?;
+    let id = expect_text(id, ctx)?;
+    Zone::parse(&id).map_err(|msg| RuntimeError::Message(format!("{ctx}: {msg}")))
+}
+
+/// Reads a `ZonedDateTime`; the instant comes from its wall-clock time and `offsetSeconds`.
+fn zoned_from_value(value: Value, ctx: &str) -> Result<(Zone, DateTime<Utc>), RuntimeError> {
+    let Value::Record(fields) = value else {
+        return Err(RuntimeError::Message(format!(
+            "{ctx} expects ZonedDateTime"
+        )));
+    };
+    let field = |name: &str| {
+        fields
+            .get(name)
+            .cloned()
+            .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects ZonedDateTime.{name}")))
+    };
+    let local = local_from_value(field("dateTime")?, ctx)?;
+    let zone = zone_from_value(field("zone")?, ctx)?;
+    let offset = expect_int(field("offsetSeconds")?, ctx)?;
+    let offset = i32::try_from(offset)
+        .ok()
+        .and_then(FixedOffset::east_opt)
+        .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects valid offsetSeconds")))?;
+    Ok((zone, (local - offset_duration(offset)).and_utc()))
+}
+
+fn zoned_to_value(zone: &Zone, instant: DateTime<Utc>) -> Value {
+    let offset = zone.offset_at(instant);
+    let local = instant.naive_utc() + offset_duration(offset);
+    let mut map = HashMap::new();
+    map.insert("dateTime".to_string(), local_to_value(local));
+    map.insert("zone".to_string(), zone.to_value());
+    map.insert(
+        "offsetSeconds".to_string(),
+        Value::Int(offset.local_minus_utc() as i64),
+    );
+    Value::Record(Arc::new(map))
+}
+
+fn local_from_value(value: Value, ctx: &str) -> Result<NaiveDateTime, RuntimeError> {
+    let Value::Record(fields) = value else {
+        return Err(RuntimeError::Message(format!(
+            "{ctx} expects LocalDateTime"
+        )));
+    };
+    let date = fields
+        .get("date")
+        .cloned()
+        .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects LocalDateTime.date")))?;
+    let time = fields
+        .get("time")
+        .cloned()
+        .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects LocalDateTime.time")))?;
+    Ok(date_from_value(date, ctx)?.and_time(time_from_value(time, ctx)?))
+}
+
+fn local_to_value(local: NaiveDateTime) -> Value {
+    let mut map = HashMap::new();
+    map.insert("date".to_string(), date_to_value(local.date()));
+    map.insert("time".to_string(), time_to_value(local.time()));
+    Value::Record(Arc::new(map))
+}
+
+fn time_from_value(value: Value, ctx: &str) -> Result<NaiveTime, RuntimeError> {
+    let Value::Record(fields) = value else {
+        return Err(RuntimeError::Message(format!("{ctx} expects Time")));
+    };
+    let mut parts = [0u32; 3];
+    for (slot, name) in parts.iter_mut().zip(["hour", "minute", "second"]) {
+        let value = fields
+            .get(name)
+            .cloned()
+            .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects Time.{name}")))?;
+        *slot = u32::try_from(expect_int(value, ctx)?)
+            .map_err(|_| RuntimeError::Message(format!("{ctx} expects valid Time")))?;
+    }
+    NaiveTime::from_hms_opt(parts[0], parts[1], parts[2])
+        .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects valid Time")))
+}
+
+fn time_to_value(time: NaiveTime) -> Value {
+    let mut map = HashMap::new();
+    map.insert("hour".to_string(), Value::Int(time.hour() as i64));
+    map.insert("minute".to_string(), Value::Int(time.minute() as i64));
+    map.insert("second".to_string(), Value::Int(time.second() as i64));
+    Value::Record(Arc::new(map))
+}
+
 fn date_from_value(value: Value, ctx: &str) -> Result<NaiveDate, RuntimeError> {
     let Value::Record(fields) = value else {
         return Err(RuntimeError::Message(format!("{ctx} expects Date")));
@@ -152,6 +152,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                 func: Arc::new(move |runtime| {
                     let cancel = CancelToken::child(runtime.cancel.clone());
                     let mut child = Runtime::with_cancel(ctx.clone(), cancel.clone());
+                    child.effect_handlers = runtime.effect_handlers.clone();
                     let result = child.run_effect_value(effect.clone());
                     cancel.cancel();
                     result
@@ -177,6 +178,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                         ctx.clone(),
                         left_cancel.clone(),
                         tx.clone(),
+                        runtime.effect_handlers.clone(),
                     );
                     spawn_effect(
                         1,
@@ -184,6 +186,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                         ctx.clone(),
                         right_cancel.clone(),
                         tx.clone(),
+                        runtime.effect_handlers.clone(),
                     );
 
                     let mut left_result = None;
@@ -250,6 +253,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                         ctx.clone(),
                         left_cancel.clone(),
                         tx.clone(),
+                        runtime.effect_handlers.clone(),
                     );
                     spawn_effect(
                         1,
@@ -257,6 +261,7 @@ pub(crate) fn build_concurrent_record() -> Value {
                         ctx.clone(),
                         right_cancel.clone(),
                         tx.clone(),
+                        runtime.effect_handlers.clone(),
                     );
 
                     let mut cancelled = false;
@@ -308,7 +313,14 @@ pub(crate) fn build_concurrent_record() -> Value {
                         .unwrap_or_else(|| runtime.cancel.clone());
                     let cancel = CancelToken::child(parent);
                     let (tx, _rx) = mpsc::channel();
-                    spawn_effect(0, effect_value.clone(), ctx.clone(), cancel, tx);
+                    spawn_effect(
+                        0,
+                        effect_value.clone(),
+                        ctx.clone(),
+                        cancel,
+                        tx,
+                        runtime.effect_handlers.clone(),
+                    );
                     Ok(Value::Unit)
                 }),
             };
@@ -324,9 +336,11 @@ fn spawn_effect(
     ctx: Arc<RuntimeContext>,
     cancel: Arc<CancelToken>,
     sender: mpsc::Sender<(usize, Result<Value, RuntimeError>)>,
+    handlers: Vec<Arc<std::collections::HashMap<String, Value>>>,
 ) {
     std::thread::spawn(move || {
         let mut runtime = Runtime::with_cancel(ctx, cancel);
+        runtime.effect_handlers = handlers;
         let result = runtime.run_effect_value(effect);
         let _ = sender.send((id, result));
     });
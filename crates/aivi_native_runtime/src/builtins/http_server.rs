@@ -3,12 +3,14 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use aivi_http_server::{
-    AiviHttpError, AiviRequest, AiviResponse, AiviWsMessage, Handler, ServerReply, WebSocketHandle,
-    WsHandlerFuture,
+    AiviHttpError, AiviRequest, AiviResponse, AiviWsMessage, Body, Bytes, Handler, ServerOptions,
+    ServerReply, TlsServerSettings, WebSocketHandle, WsHandlerFuture,
 };
 
+use super::sockets::tls_server_settings_from_value;
+use super::streams::{channel_stream, collect_stream, next_chunk, stream_value};
 use super::util::{builtin, expect_record};
-use crate::values::CancelToken;
+use crate::values::{CancelToken, StreamState};
 use crate::{format_value, EffectValue, Runtime, RuntimeContext, RuntimeError, Value};
 
 pub(super) fn build_http_server_record() -> Value {
@@ -17,42 +19,22 @@ pub(super) fn build_http_server_record() -> Value {
         "listen".to_string(),
         builtin("httpServer.listen", 2, |mut args, runtime| {
             let handler = args.pop().unwrap();
-            let config = args.pop().unwrap();
-            let addr = parse_server_config(config)?;
-            let ctx = runtime.ctx.clone();
-            let handler_value = handler.clone();
-            let effect = EffectValue::Thunk {
-                func: Arc::new(move |_| {
-                    let handler_value_clone = handler_value.clone();
-                    let ctx_clone = ctx.clone();
-                    let handler: Handler = Arc::new(move |req: AiviRequest| {
-                        let handler_value = handler_value_clone.clone();
-                        let ctx = ctx_clone.clone();
-                        Box::pin(async move {
-                            let req_value = request_to_value(req);
-                            let ctx_for_reply = ctx.clone();
-                            let result = tokio::task::spawn_blocking(move || {
-                                let cancel = CancelToken::root();
-                                let mut runtime = Runtime::with_cancel(ctx.clone(), cancel);
-                                let applied = runtime.apply(handler_value, req_value)?;
-                                runtime.run_effect_value(applied)
-                            })
-                            .await
-                            .map_err(|err| AiviHttpError {
-                                message: err.to_string(),
-                            })?;
-                            match result {
-                                Ok(value) => server_reply_from_value(value, ctx_for_reply),
-                                Err(err) => Err(runtime_error_to_http_error(err)),
-                            }
-                        })
-                    });
-                    let server = aivi_http_server::start_server(addr, handler)
-                        .map_err(|err| RuntimeError::Error(http_error_value(err.message)))?;
-                    Ok(Value::HttpServer(Arc::new(server)))
-                }),
-            };
-            Ok(Value::Effect(Arc::new(effect)))
+            let config = parse_server_config(args.pop().unwrap())?;
+            Ok(listen_effect(
+                config,
+                ServerOptions::default(),
+                handler,
+                runtime.ctx.clone(),
+            ))
+        }),
+    );
+    fields.insert(
+        "listenWith".to_string(),
+        builtin("httpServer.listenWith", 3, |mut args, runtime| {
+            let handler = args.pop().unwrap();
+            let options = parse_server_options(args.pop().unwrap())?;
+            let config = parse_server_config(args.pop().unwrap())?;
+            Ok(listen_effect(config, options, handler, runtime.ctx.clone()))
         }),
     );
     fields.insert(
@@ -149,10 +131,61 @@ pub(super) fn build_http_server_record() -> Value {
             Ok(Value::Effect(Arc::new(effect)))
         }),
     );
+    insert_routing_builtins(&mut fields);
     Value::Record(Arc::new(fields))
 }
 
-fn parse_server_config(value: Value) -> Result<SocketAddr, RuntimeError> {
+include!("http_server/routing.rs");
+
+struct ListenConfig {
+    addr: SocketAddr,
+    tls: Option<TlsServerSettings>,
+}
+
+fn listen_effect(
+    config: ListenConfig,
+    options: ServerOptions,
+    handler_value: Value,
+    ctx: Arc<RuntimeContext>,
+) -> Value {
+    let effect = EffectValue::Thunk {
+        func: Arc::new(move |_| {
+            let handler_value_clone = handler_value.clone();
+            let ctx_clone = ctx.clone();
+            let handler: Handler = Arc::new(move |req: AiviRequest| {
+                let handler_value = handler_value_clone.clone();
+                let ctx = ctx_clone.clone();
+                Box::pin(async move {
+                    let req_value = request_to_value(req);
+                    let ctx_for_reply = ctx.clone();
+                    let result = tokio::task::spawn_blocking(move || {
+                        let cancel = CancelToken::root();
+                        let mut runtime = Runtime::with_cancel(ctx.clone(), cancel);
+                        let applied = runtime.apply(handler_value, req_value)?;
+                        runtime.run_effect_value(applied)
+                    })
+                    .await
+                    .map_err(|err| AiviHttpError {
+                        message: err.to_string(),
+                    })?;
+                    match result {
+                        Ok(value) => server_reply_from_value(value, ctx_for_reply),
+                        Err(err) => Err(runtime_error_to_http_error(err)),
+                    }
+                })
+            });
+            let server = match &config.tls {
+                Some(tls) => aivi_http_server::start_tls_server(config.addr, options, tls, handler),
+                None => aivi_http_server::start_server_with(config.addr, options, handler),
+            }
+            .map_err(|err| RuntimeError::Error(http_error_value(err.message)))?;
+            Ok(Value::HttpServer(Arc::new(server)))
+        }),
+    };
+    Value::Effect(Arc::new(effect))
+}
+
+fn parse_server_config(value: Value) -> Result<ListenConfig, RuntimeError> {
     let record = expect_record(value, "httpServer.listen expects ServerConfig")?;
     let address = match record.get("address") {
         Some(Value::Text(text)) => text.clone(),
@@ -162,8 +195,42 @@ fn parse_server_config(value: Value) -> Result<SocketAddr, RuntimeError> {
             ))
         }
     };
-    address.parse().map_err(|_| {
+    let addr = address.parse().map_err(|_| {
         RuntimeError::Message("httpServer.listen address must be host:port".to_string())
+    })?;
+    // `tls: None`, or no `tls` field at all, serves plain HTTP.
+    let tls = match record.get("tls") {
+        Some(Value::Constructor { name, args }) if name == "Some" && args.len() == 1 => Some(
+            tls_server_settings_from_value(args[0].clone(), "httpServer.listen ServerConfig.tls")?,
+        ),
+        _ => None,
+    };
+    Ok(ListenConfig { addr, tls })
+}
+
+fn parse_server_options(value: Value) -> Result<ServerOptions, RuntimeError> {
+    let record = expect_record(value, "httpServer.listenWith")?;
+    let max_body_bytes = match record.get("maxBodyBytes") {
+        Some(Value::Int(value)) if *value >= 0 => *value as u64,
+        other => {
+            return Err(RuntimeError::Message(format!(
+                "ServerOptions.maxBodyBytes must be a non-negative Int, got {}",
+                other.map(format_value).unwrap_or_default()
+            )))
+        }
+    };
+    let stream_request_bodies = match record.get("streamRequestBodies") {
+        Some(Value::Bool(value)) => *value,
+        other => {
+            return Err(RuntimeError::Message(format!(
+                "ServerOptions.streamRequestBodies must be Bool, got {}",
+                other.map(format_value).unwrap_or_default()
+            )))
+        }
+    };
+    Ok(ServerOptions {
+        max_body_bytes,
+        stream_request_bodies,
     })
 }
 
@@ -172,7 +239,7 @@ fn request_to_value(req: AiviRequest) -> Value {
     fields.insert("method".to_string(), Value::Text(req.method));
     fields.insert("path".to_string(), Value::Text(req.path));
     fields.insert("headers".to_string(), headers_to_value(req.headers));
-    fields.insert("body".to_string(), bytes_to_list_value(req.body));
+    fields.insert("body".to_string(), body_to_value(req.body));
     fields.insert(
         "remoteAddr".to_string(),
         match req.remote_addr {
@@ -265,10 +332,10 @@ fn response_from_value(value: Value) -> Result<AiviResponse, AiviHttpError> {
         }
     };
     let body = match record.get("body") {
-        Some(Value::List(items)) => list_value_to_bytes(items.as_ref())?,
-        _ => {
+        Some(body) => body_from_value(body.clone())?,
+        None => {
             return Err(AiviHttpError {
-                message: "Response.body must be List".to_string(),
+                message: "Response.body must be Body".to_string(),
             })
         }
     };
@@ -329,6 +396,52 @@ fn headers_from_value(values: &[Value]) -> Result<Vec<(String, String)>, AiviHtt
     Ok(out)
 }
 
+fn body_to_value(body: Body) -> Value {
+    match body {
+        Body::Full(bytes) => Value::Constructor {
+            name: "Buffered".to_string(),
+            args: vec![Value::Bytes(Arc::new(bytes.to_vec()))],
+        },
+        Body::Stream(source) => Value::Constructor {
+            name: "Streamed".to_string(),
+            args: vec![stream_value(StreamState::Body { source })],
+        },
+    }
+}
+
+fn body_from_value(value: Value) -> Result<Body, AiviHttpError> {
+    match value {
+        Value::Constructor { name, mut args } if name == "Buffered" && args.len() == 1 => {
+            match args.pop().unwrap() {
+                Value::Bytes(bytes) => Ok(Body::from(
+                    Arc::try_unwrap(bytes).unwrap_or_else(|bytes| bytes.as_ref().clone()),
+                )),
+                other => Err(AiviHttpError {
+                    message: format!("Buffered expects Bytes, got {}", format_value(&other)),
+                }),
+            }
+        }
+        Value::Constructor { name, mut args } if name == "Streamed" && args.len() == 1 => {
+            match args.pop().unwrap() {
+                Value::Stream(handle) => Ok(Body::Stream(Box::new(move || {
+                    next_chunk(&handle)
+                        .map(|chunk| chunk.map(Bytes::from))
+                        .map_err(runtime_error_to_http_error)
+                }))),
+                other => Err(AiviHttpError {
+                    message: format!("Streamed expects a Stream, got {}", format_value(&other)),
+                }),
+            }
+        }
+        other => Err(AiviHttpError {
+            message: format!(
+                "expected Body (Buffered|Streamed), got {}",
+                format_value(&other)
+            ),
+        }),
+    }
+}
+
 fn bytes_to_list_value(bytes: Vec<u8>) -> Value {
     Value::List(Arc::new(
         bytes.into_iter().map(|b| Value::Int(b as i64)).collect(),
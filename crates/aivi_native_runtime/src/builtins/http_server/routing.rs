@@ -0,0 +1,567 @@
+// Routing, middleware and request parsing for `aivi.net.http_server`. The HTTP logic lives in
+// `aivi_http_server`; these builtins convert between its types and AIVI values and run the
+// wrapped handlers.
+
+use aivi_http_server::middleware::{apply_cors, compress_response, cors_preflight, CorsConfig};
+use aivi_http_server::routing::{
+    format_set_cookie, header_value, method_matches, parse_cookies, parse_form, parse_query,
+    split_target, ParamValue, RoutePattern, SetCookie,
+};
+use aivi_http_server::sse::{format_event, SseEvent};
+
+struct RequestView {
+    method: String,
+    target: String,
+    headers: Vec<(String, String)>,
+    /// `None` while the body is still a stream.
+    body: Option<Arc<Vec<u8>>>,
+    remote_addr: Option<String>,
+}
+
+fn insert_routing_builtins(fields: &mut HashMap<String, Value>) {
+    fields.insert(
+        "route".to_string(),
+        builtin("httpServer.route", 2, |mut args, _| {
+            let request = args.pop().unwrap();
+            let routes = expect_routes(args.pop().unwrap())?;
+            let view = request_view(&request)?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |runtime| {
+                    let (path, _) = split_target(&view.target);
+                    let mut allowed: Vec<String> = Vec::new();
+                    for (method, pattern, handler) in &routes {
+                        let Some(params) = pattern.matches(path) else {
+                            continue;
+                        };
+                        if !method_matches(method, &view.method) {
+                            let method = method.to_ascii_uppercase();
+                            if !allowed.contains(&method) {
+                                allowed.push(method);
+                            }
+                            continue;
+                        }
+                        let applied = runtime.apply(handler.clone(), path_params_value(params))?;
+                        let applied = runtime.apply(applied, request.clone())?;
+                        return runtime.run_effect_value(applied);
+                    }
+                    if allowed.is_empty() {
+                        return Ok(http_reply(text_response(404, "not found")));
+                    }
+                    if allowed.iter().any(|method| method == "GET")
+                        && !allowed.iter().any(|method| method == "HEAD")
+                    {
+                        allowed.push("HEAD".to_string());
+                    }
+                    let mut response = text_response(405, "method not allowed");
+                    response
+                        .headers
+                        .push(("allow".to_string(), allowed.join(", ")));
+                    Ok(http_reply(response))
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "serveStatic".to_string(),
+        builtin("httpServer.serveStatic", 3, |mut args, _| {
+            let view = request_view(&args.pop().unwrap())?;
+            let relative = expect_text_arg(args.pop().unwrap(), "httpServer.serveStatic")?;
+            let root = expect_text_arg(args.pop().unwrap(), "httpServer.serveStatic")?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| {
+                    let response = aivi_http_server::static_files::serve_file(
+                        std::path::Path::new(&root),
+                        &relative,
+                        &view.method,
+                        &view.headers,
+                    );
+                    Ok(http_reply(response))
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "logging".to_string(),
+        builtin("httpServer.logging", 2, |mut args, _| {
+            let request = args.pop().unwrap();
+            let next = args.pop().unwrap();
+            let view = request_view(&request)?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |runtime| {
+                    let started = std::time::Instant::now();
+                    let result = runtime
+                        .apply(next.clone(), request.clone())
+                        .and_then(|applied| runtime.run_effect_value(applied));
+                    let status = match &result {
+                        Ok(Value::Constructor { name, args }) if name == "Http" => args
+                            .first()
+                            .and_then(|response| match response {
+                                Value::Record(fields) => match fields.get("status") {
+                                    Some(Value::Int(status)) => Some(*status),
+                                    _ => None,
+                                },
+                                _ => None,
+                            })
+                            .unwrap_or(500),
+                        Ok(_) => 101,
+                        Err(_) => 500,
+                    };
+                    let (path, _) = split_target(&view.target);
+                    let line = serde_json::json!({
+                        "level": if status >= 500 { "error" } else { "info" },
+                        "message": format!("{} {} {}", view.method, path, status),
+                        "context": {
+                            "method": view.method,
+                            "path": path,
+                            "status": status.to_string(),
+                            "durationMs": format!("{:.3}", started.elapsed().as_secs_f64() * 1000.0),
+                            "remoteAddr": view.remote_addr.clone().unwrap_or_default(),
+                        },
+                    });
+                    if status >= 500 {
+                        eprintln!("{line}");
+                    } else {
+                        println!("{line}");
+                    }
+                    result
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "cors".to_string(),
+        builtin("httpServer.cors", 3, |mut args, _| {
+            let request = args.pop().unwrap();
+            let next = args.pop().unwrap();
+            let config = expect_cors_config(args.pop().unwrap())?;
+            let view = request_view(&request)?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |runtime| {
+                    if let Some(response) = cors_preflight(&config, &view.method, &view.headers) {
+                        return Ok(http_reply(response));
+                    }
+                    let applied = runtime.apply(next.clone(), request.clone())?;
+                    let reply = runtime.run_effect_value(applied)?;
+                    map_http_reply(reply, |response| {
+                        apply_cors(&config, &view.headers, response)
+                    })
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "compression".to_string(),
+        builtin("httpServer.compression", 2, |mut args, _| {
+            let request = args.pop().unwrap();
+            let next = args.pop().unwrap();
+            let view = request_view(&request)?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |runtime| {
+                    let applied = runtime.apply(next.clone(), request.clone())?;
+                    let reply = runtime.run_effect_value(applied)?;
+                    map_http_reply(reply, |response| compress_response(&view.headers, response))
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "header".to_string(),
+        builtin("httpServer.header", 2, |mut args, _| {
+            let view = request_view(&args.pop().unwrap())?;
+            let name = expect_text_arg(args.pop().unwrap(), "httpServer.header")?;
+            Ok(option_text(
+                header_value(&view.headers, &name).map(str::to_string),
+            ))
+        }),
+    );
+    fields.insert(
+        "bearerToken".to_string(),
+        builtin("httpServer.bearerToken", 1, |mut args, _| {
+            let view = request_view(&args.pop().unwrap())?;
+            let token = header_value(&view.headers, "authorization").and_then(|value| {
+                let (scheme, token) = value.trim().split_once(' ')?;
+                let token = token.trim();
+                (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty())
+                    .then(|| token.to_string())
+            });
+            Ok(option_text(token))
+        }),
+    );
+    fields.insert(
+        "requestPath".to_string(),
+        builtin("httpServer.requestPath", 1, |mut args, _| {
+            let view = request_view(&args.pop().unwrap())?;
+            Ok(Value::Text(split_target(&view.target).0.to_string()))
+        }),
+    );
+    fields.insert(
+        "query".to_string(),
+        builtin("httpServer.query", 1, |mut args, _| {
+            let view = request_view(&args.pop().unwrap())?;
+            Ok(fields_value(parse_query(split_target(&view.target).1)))
+        }),
+    );
+    fields.insert(
+        "cookies".to_string(),
+        builtin("httpServer.cookies", 1, |mut args, _| {
+            let view = request_view(&args.pop().unwrap())?;
+            Ok(fields_value(parse_cookies(&view.headers)))
+        }),
+    );
+    fields.insert(
+        "form".to_string(),
+        builtin("httpServer.form", 1, |mut args, _| {
+            let view = request_view(&args.pop().unwrap())?;
+            let Some(body) = view.body else {
+                return Ok(result_value(
+                    "Err",
+                    http_error_value(
+                        "form needs a buffered body; read it with readBody".to_string(),
+                    ),
+                ));
+            };
+            Ok(match parse_form(&view.headers, &body) {
+                Ok(fields) => result_value("Ok", fields_value(fields)),
+                Err(err) => result_value("Err", http_error_value(err.message)),
+            })
+        }),
+    );
+    fields.insert(
+        "readBody".to_string(),
+        builtin("httpServer.readBody", 2, |mut args, _| {
+            let body = match args.pop().unwrap() {
+                Value::Record(fields) => fields.get("body").cloned(),
+                _ => None,
+            };
+            let limit = match args.pop().unwrap() {
+                Value::Int(limit) if limit >= 0 => limit as usize,
+                _ => {
+                    return Err(RuntimeError::Message(
+                        "httpServer.readBody expects a non-negative limit".to_string(),
+                    ))
+                }
+            };
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| match body.clone() {
+                    Some(Value::Constructor { name, args }) if name == "Buffered" => {
+                        match args.first() {
+                            Some(Value::Bytes(bytes)) if bytes.len() > limit => {
+                                Err(RuntimeError::Error(http_error_value(format!(
+                                    "request body exceeds {limit} bytes"
+                                ))))
+                            }
+                            Some(bytes @ Value::Bytes(_)) => Ok(bytes.clone()),
+                            _ => Err(RuntimeError::Message("Buffered expects Bytes".to_string())),
+                        }
+                    }
+                    Some(Value::Constructor { name, args }) if name == "Streamed" => {
+                        match args.first() {
+                            Some(Value::Stream(handle)) => collect_stream(handle, limit)
+                                .map(|bytes| Value::Bytes(Arc::new(bytes))),
+                            _ => Err(RuntimeError::Message(
+                                "Streamed expects a Stream".to_string(),
+                            )),
+                        }
+                    }
+                    _ => Err(RuntimeError::Message(
+                        "httpServer.readBody expects a Request with a Body".to_string(),
+                    )),
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "sseStream".to_string(),
+        builtin("httpServer.sseStream", 1, |mut args, _| {
+            let Value::ChannelRecv(receiver) = args.pop().unwrap() else {
+                return Err(RuntimeError::Message(
+                    "httpServer.sseStream expects a recv handle".to_string(),
+                ));
+            };
+            Ok(channel_stream(receiver, sse_event_chunk))
+        }),
+    );
+    fields.insert(
+        "setCookie".to_string(),
+        builtin("httpServer.setCookie", 1, |mut args, _| {
+            let cookie = expect_set_cookie(args.pop().unwrap())?;
+            Ok(match format_set_cookie(&cookie) {
+                Ok(value) => result_value("Ok", header_record("set-cookie".to_string(), value)),
+                Err(err) => result_value("Err", http_error_value(err.message)),
+            })
+        }),
+    );
+}
+
+fn request_view(value: &Value) -> Result<RequestView, RuntimeError> {
+    let Value::Record(fields) = value else {
+        return Err(RuntimeError::Message("expected Request record".to_string()));
+    };
+    let text = |name: &str| match fields.get(name) {
+        Some(Value::Text(text)) => Ok(text.clone()),
+        _ => Err(RuntimeError::Message(format!(
+            "Request.{name} must be Text"
+        ))),
+    };
+    let headers = match fields.get("headers") {
+        Some(Value::List(items)) => {
+            headers_from_value(items).map_err(|err| RuntimeError::Message(err.message))?
+        }
+        _ => {
+            return Err(RuntimeError::Message(
+                "Request.headers must be List".to_string(),
+            ))
+        }
+    };
+    let body = match fields.get("body") {
+        Some(Value::Constructor { name, args }) if name == "Buffered" => match args.first() {
+            Some(Value::Bytes(bytes)) => Some(bytes.clone()),
+            _ => return Err(RuntimeError::Message("Buffered expects Bytes".to_string())),
+        },
+        Some(Value::Constructor { name, .. }) if name == "Streamed" => None,
+        _ => {
+            return Err(RuntimeError::Message(
+                "Request.body must be Body".to_string(),
+            ))
+        }
+    };
+    let remote_addr = match fields.get("remoteAddr") {
+        Some(Value::Constructor { name, args }) if name == "Some" => match args.first() {
+            Some(Value::Text(text)) => Some(text.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+    Ok(RequestView {
+        method: text("method")?,
+        target: text("path")?,
+        headers,
+        body,
+        remote_addr,
+    })
+}
+
+fn sse_event_chunk(value: Value) -> Result<Vec<u8>, RuntimeError> {
+    let record = expect_record(value, "httpServer.sseStream expects SseEvent")?;
+    let option_text = |name: &str| match record.get(name) {
+        Some(Value::Constructor { name, args }) if name == "Some" => match args.first() {
+            Some(Value::Text(text)) => Some(text.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let data = match record.get("data") {
+        Some(Value::Text(text)) => text.clone(),
+        _ => {
+            return Err(RuntimeError::Message(
+                "SseEvent.data must be Text".to_string(),
+            ))
+        }
+    };
+    let event = SseEvent {
+        event: option_text("event"),
+        data,
+        id: option_text("id"),
+        retry: option_int_field(&record, "retry"),
+    };
+    Ok(format_event(&event).into_bytes())
+}
+
+fn expect_routes(value: Value) -> Result<Vec<(String, RoutePattern, Value)>, RuntimeError> {
+    let Value::List(items) = value else {
+        return Err(RuntimeError::Message(
+            "httpServer.route expects List Route".to_string(),
+        ));
+    };
+    items
+        .iter()
+        .map(|item| {
+            let Value::Record(fields) = item else {
+                return Err(RuntimeError::Message("Route must be a record".to_string()));
+            };
+            let (Some(Value::Text(method)), Some(Value::Text(pattern)), Some(handler)) = (
+                fields.get("method"),
+                fields.get("pattern"),
+                fields.get("handler"),
+            ) else {
+                return Err(RuntimeError::Message(
+                    "Route expects method, pattern and handler".to_string(),
+                ));
+            };
+            let pattern = RoutePattern::parse(pattern)
+                .map_err(|err| RuntimeError::Error(http_error_value(err.message)))?;
+            Ok((method.clone(), pattern, handler.clone()))
+        })
+        .collect()
+}
+
+fn expect_cors_config(value: Value) -> Result<CorsConfig, RuntimeError> {
+    let record = expect_record(value, "httpServer.cors expects CorsConfig")?;
+    let texts = |name: &str| match record.get(name) {
+        Some(Value::List(items)) => items
+            .iter()
+            .map(|item| match item {
+                Value::Text(text) => Ok(text.clone()),
+                _ => Err(RuntimeError::Message(format!(
+                    "CorsConfig.{name} must be List Text"
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>(),
+        _ => Err(RuntimeError::Message(format!(
+            "CorsConfig.{name} must be List Text"
+        ))),
+    };
+    Ok(CorsConfig {
+        origins: texts("origins")?,
+        methods: texts("methods")?,
+        headers: texts("headers")?,
+        credentials: matches!(record.get("credentials"), Some(Value::Bool(true))),
+        max_age: option_int_field(&record, "maxAge"),
+    })
+}
+
+fn expect_set_cookie(value: Value) -> Result<SetCookie, RuntimeError> {
+    let record = expect_record(value, "httpServer.setCookie expects SetCookie")?;
+    let text = |name: &str| match record.get(name) {
+        Some(Value::Text(text)) => Ok(text.clone()),
+        _ => Err(RuntimeError::Message(format!(
+            "SetCookie.{name} must be Text"
+        ))),
+    };
+    let option_text = |name: &str| match record.get(name) {
+        Some(Value::Constructor { name, args }) if name == "Some" => match args.first() {
+            Some(Value::Text(text)) => Some(text.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+    Ok(SetCookie {
+        name: text("name")?,
+        value: text("value")?,
+        path: option_text("path"),
+        domain: option_text("domain"),
+        max_age: option_int_field(&record, "maxAge"),
+        http_only: matches!(record.get("httpOnly"), Some(Value::Bool(true))),
+        secure: matches!(record.get("secure"), Some(Value::Bool(true))),
+        same_site: option_text("sameSite"),
+    })
+}
+
+fn option_int_field(record: &HashMap<String, Value>, name: &str) -> Option<i64> {
+    match record.get(name) {
+        Some(Value::Constructor { name, args }) if name == "Some" => match args.first() {
+            Some(Value::Int(value)) => Some(*value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn expect_text_arg(value: Value, ctx: &str) -> Result<String, RuntimeError> {
+    match value {
+        Value::Text(text) => Ok(text),
+        _ => Err(RuntimeError::Message(format!("{ctx} expects Text"))),
+    }
+}
+
+fn path_params_value(params: Vec<(String, ParamValue)>) -> Value {
+    let items = params
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                ParamValue::Int(value) => Value::Constructor {
+                    name: "IntParam".to_string(),
+                    args: vec![Value::Int(value)],
+                },
+                ParamValue::Text(text) => Value::Constructor {
+                    name: "TextParam".to_string(),
+                    args: vec![Value::Text(text)],
+                },
+            };
+            let mut fields = HashMap::new();
+            fields.insert("name".to_string(), Value::Text(name));
+            fields.insert("value".to_string(), value);
+            Value::Record(Arc::new(fields))
+        })
+        .collect();
+    Value::List(Arc::new(items))
+}
+
+fn fields_value(pairs: Vec<(String, String)>) -> Value {
+    headers_to_value(pairs)
+}
+
+fn header_record(name: String, value: String) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert("name".to_string(), Value::Text(name));
+    fields.insert("value".to_string(), Value::Text(value));
+    Value::Record(Arc::new(fields))
+}
+
+fn option_text(value: Option<String>) -> Value {
+    match value {
+        Some(text) => Value::Constructor {
+            name: "Some".to_string(),
+            args: vec![Value::Text(text)],
+        },
+        None => Value::Constructor {
+            name: "None".to_string(),
+            args: Vec::new(),
+        },
+    }
+}
+
+fn result_value(name: &str, value: Value) -> Value {
+    Value::Constructor {
+        name: name.to_string(),
+        args: vec![value],
+    }
+}
+
+fn text_response(status: u16, message: &str) -> AiviResponse {
+    AiviResponse {
+        status,
+        headers: vec![(
+            "content-type".to_string(),
+            "text/plain; charset=utf-8".to_string(),
+        )],
+        body: Body::from(message.as_bytes().to_vec()),
+    }
+}
+
+fn response_to_value(response: AiviResponse) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert("status".to_string(), Value::Int(response.status as i64));
+    fields.insert("headers".to_string(), headers_to_value(response.headers));
+    fields.insert("body".to_string(), body_to_value(response.body));
+    Value::Record(Arc::new(fields))
+}
+
+fn http_reply(response: AiviResponse) -> Value {
+    Value::Constructor {
+        name: "Http".to_string(),
+        args: vec![response_to_value(response)],
+    }
+}
+
+// Rewrites `Http` replies; WebSocket upgrades pass through untouched.
+fn map_http_reply(
+    reply: Value,
+    update: impl FnOnce(&mut AiviResponse),
+) -> Result<Value, RuntimeError> {
+    match reply {
+        Value::Constructor { name, mut args } if name == "Http" && args.len() == 1 => {
+            let mut response = response_from_value(args.pop().unwrap())
+                .map_err(|err| RuntimeError::Message(err.message))?;
+            update(&mut response);
+            Ok(http_reply(response))
+        }
+        other => Ok(other),
+    }
+}
@@ -3,6 +3,10 @@ use std::sync::Arc;
 
 use im::HashMap as ImHashMap;
 
+use super::i18n_format::{
+    format_currency, format_date, format_date_time, format_integer, format_number, format_percent,
+    format_time, locale_formats, DateStyle, FormatNumber, LocaleFormats,
+};
 use super::util::{builtin, expect_record, expect_text, list_value, make_err, make_ok, make_some};
 use crate::values::KeyValue;
 use crate::{format_value, RuntimeError, Value};
@@ -52,26 +56,99 @@ pub(super) fn build_i18n_record() -> Value {
         builtin("i18n.render", 2, |mut args, _| {
             let args_rec = expect_record(args.pop().unwrap(), "i18n.render")?;
             let msg = expect_record(args.pop().unwrap(), "i18n.render")?;
-            let body = match msg.get("body") {
-                Some(Value::Text(text)) => text.clone(),
+            let locale = RenderLocale {
+                language: DEFAULT_PLURAL_LANGUAGE,
+                formats: None,
+            };
+            render_message(&msg, &args_rec, locale, "i18n.render")
+        }),
+    );
+
+    fields.insert(
+        "renderIn".to_string(),
+        builtin("i18n.renderIn", 3, |mut args, _| {
+            let args_rec = expect_record(args.pop().unwrap(), "i18n.renderIn")?;
+            let msg = expect_record(args.pop().unwrap(), "i18n.renderIn")?;
+            let locale = expect_record(args.pop().unwrap(), "i18n.renderIn")?;
+            let language = match locale.get("language") {
+                Some(Value::Text(language)) => language.clone(),
                 _ => {
                     return Err(RuntimeError::Message(
-                        "i18n.render expects Message with field 'body : Text'".to_string(),
+                        "i18n.renderIn expects Locale with field 'language : Text'".to_string(),
                     ))
                 }
             };
+            let formats = locale_formats(&locale_tag(&locale, "i18n.renderIn")?);
+            render_message(
+                &msg,
+                &args_rec,
+                RenderLocale {
+                    language: &language,
+                    formats: Some(formats),
+                },
+                "i18n.renderIn",
+            )
+        }),
+    );
 
-            let rendered = if let Some(Value::List(items)) = msg.get("parts") {
-                render_compiled_parts(items, &args_rec)?
-            } else {
-                let parts = parse_message_template(&body).map_err(RuntimeError::Message)?;
-                render_parts(&parts, &args_rec)?
-            };
+    for (name, format) in [
+        (
+            "formatNumber",
+            format_number as fn(&LocaleFormats, FormatNumber) -> String,
+        ),
+        ("formatInt", format_integer),
+        ("formatDecimal", format_number),
+        ("formatPercent", format_percent),
+    ] {
+        let ctx = format!("i18n.{name}");
+        fields.insert(
+            name.to_string(),
+            builtin(&ctx.clone(), 2, move |mut args, _| {
+                let number = expect_number(args.pop().unwrap(), &ctx)?;
+                let locale = expect_record(args.pop().unwrap(), &ctx)?;
+                let formats = locale_formats(&locale_tag(&locale, &ctx)?);
+                Ok(Value::Text(format(formats, number)))
+            }),
+        );
+    }
 
-            Ok(make_ok(Value::Text(rendered)))
+    fields.insert(
+        "formatCurrency".to_string(),
+        builtin("i18n.formatCurrency", 3, |mut args, _| {
+            let number = expect_number(args.pop().unwrap(), "i18n.formatCurrency")?;
+            let code = expect_text(args.pop().unwrap(), "i18n.formatCurrency")?;
+            let locale = expect_record(args.pop().unwrap(), "i18n.formatCurrency")?;
+            let formats = locale_formats(&locale_tag(&locale, "i18n.formatCurrency")?);
+            Ok(Value::Text(format_currency(formats, &code, number)))
         }),
     );
 
+    for (name, format) in [
+        (
+            "formatDate",
+            format_date as fn(&LocaleFormats, DateStyle, &str) -> Result<String, String>,
+        ),
+        ("formatTime", format_time),
+        ("formatDateTime", format_date_time),
+    ] {
+        let ctx = format!("i18n.{name}");
+        fields.insert(
+            name.to_string(),
+            builtin(&ctx.clone(), 3, move |mut args, _| {
+                let value = args.pop().unwrap();
+                let style = expect_date_style(args.pop().unwrap(), &ctx)?;
+                let locale = expect_record(args.pop().unwrap(), &ctx)?;
+                let formats = locale_formats(&locale_tag(&locale, &ctx)?);
+                let Value::DateTime(text) = value else {
+                    return Err(RuntimeError::Message(format!("{ctx} expects DateTime")));
+                };
+                format(formats, style, &text)
+                    .map(Value::Text)
+                    .map_err(|msg| RuntimeError::Message(format!("{ctx}: {msg}")))
+            }),
+        );
+    }
+
     fields.insert(
         "bundleFromProperties".to_string(),
         builtin("i18n.bundleFromProperties", 2, |mut args, _| {
@@ -208,202 +285,739 @@ fn locale_value(parts: LocaleParts) -> Value {
 #[derive(Debug, Clone)]
 enum MessagePart {
     Lit(String),
-    Hole { name: String, ty: Option<String> },
+    Hole {
+        name: String,
+        ty: Option<String>,
+    },
+    /// `#` inside a plural arm: the number being pluralized.
+    Pound,
+    /// `{name, plural, ...}` or `{name, selectordinal, ...}` (`ordinal = true`).
+    Plural {
+        name: String,
+        ty: Option<String>,
+        ordinal: bool,
+        arms: Vec<MessageArm>,
+    },
+    /// `{name, select, ...}`.
+    Select {
+        name: String,
+        ty: Option<String>,
+        arms: Vec<MessageArm>,
+    },
+    /// `{name, number}`, `{name, date, long}`, ...: locale-formatted value.
+    Format {
+        name: String,
+        ty: Option<String>,
+        format: String,
+        style: Option<String>,
+    },
+}
+
+/// One `selector {message}` arm of a plural/select placeholder.
+#[derive(Debug, Clone)]
+struct MessageArm {
+    /// `=N`, a CLDR plural category, or a select key.
+    selector: String,
+    parts: Vec<MessagePart>,
 }
 
 fn parse_message_template(text: &str) -> Result<Vec<MessagePart>, String> {
-    // Same minimal grammar as the compiler-side helper:
+    // Same grammar as the compiler-side helper:
     // - '{{' and '}}' for literal braces
     // - placeholders: {name} or {name:Type}
-    let mut parts = Vec::new();
-    let mut buf = String::new();
+    // - ICU-style plural/selectordinal/select forms with nested arms and '#'
+    // - formatted values: {name, number[, style]} and {name, date|time[, style]}
+    let mut parser = TemplateParser {
+        chars: text.chars().collect(),
+        pos: 0,
+    };
+    parser.parse_parts(false, false)
+}
 
-    let mut chars = text.chars().peekable();
-    while let Some(ch) = chars.next() {
-        match ch {
-            '{' => {
-                if matches!(chars.peek(), Some('{')) {
-                    chars.next();
+struct TemplateParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl TemplateParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        self.chars.get(self.pos + 1).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(ch) if ch.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn take_until(&mut self, stops: &[char]) -> String {
+        let mut out = String::new();
+        while let Some(ch) = self.peek() {
+            if stops.contains(&ch) {
+                break;
+            }
+            out.push(ch);
+            self.pos += 1;
+        }
+        out
+    }
+
+    fn parse_parts(&mut self, in_arm: bool, in_plural: bool) -> Result<Vec<MessagePart>, String> {
+        let mut parts = Vec::new();
+        let mut buf = String::new();
+        while let Some(ch) = self.peek() {
+            match ch {
+                '{' if self.peek_next() == Some('{') => {
+                    self.pos += 2;
                     buf.push('{');
-                    continue;
                 }
-                if !buf.is_empty() {
-                    parts.push(MessagePart::Lit(std::mem::take(&mut buf)));
+                '{' => {
+                    if !buf.is_empty() {
+                        parts.push(MessagePart::Lit(std::mem::take(&mut buf)));
+                    }
+                    self.pos += 1;
+                    parts.push(self.parse_placeholder(in_plural)?);
+                }
+                '}' if in_arm => break,
+                '}' if self.peek_next() == Some('}') => {
+                    self.pos += 2;
+                    buf.push('}');
                 }
-                let mut inner = String::new();
-                let mut closed = false;
-                for next in chars.by_ref() {
-                    if next == '}' {
-                        closed = true;
-                        break;
+                '}' => return Err("unexpected '}' (use '}}' to escape)".to_string()),
+                '#' if in_plural => {
+                    if !buf.is_empty() {
+                        parts.push(MessagePart::Lit(std::mem::take(&mut buf)));
                     }
-                    inner.push(next);
+                    self.pos += 1;
+                    parts.push(MessagePart::Pound);
                 }
-                if !closed {
-                    return Err("unclosed '{' in message template".to_string());
+                other => {
+                    self.pos += 1;
+                    buf.push(other);
                 }
-                let inner = inner.trim();
-                if inner.is_empty() {
-                    return Err("empty placeholder '{}'".to_string());
+            }
+        }
+        if !buf.is_empty() {
+            parts.push(MessagePart::Lit(buf));
+        }
+        Ok(parts)
+    }
+
+    fn parse_placeholder(&mut self, in_plural: bool) -> Result<MessagePart, String> {
+        let head = self.take_until(&['}', ',']);
+        if self.peek().is_none() {
+            return Err("unclosed '{' in message template".to_string());
+        }
+        let head = head.trim();
+        if head.is_empty() {
+            return Err(if self.peek() == Some('}') {
+                "empty placeholder '{}'".to_string()
+            } else {
+                "placeholder name is empty".to_string()
+            });
+        }
+        let (name, ty) = match head.split_once(':') {
+            Some((n, t)) => (n.trim(), Some(t.trim())),
+            None => (head, None),
+        };
+        validate_placeholder_name(name)?;
+        let ty = ty
+            .filter(|t| !t.is_empty())
+            .map(|t| validate_placeholder_type(t).map(|_| t.to_string()))
+            .transpose()?;
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(MessagePart::Hole {
+                name: name.to_string(),
+                ty,
+            });
+        }
+
+        // ICU-style form: `{name, kind, arms...}` or `{name, format[, style]}`.
+        self.pos += 1;
+        let kind = self.take_until(&['}', ',']);
+        let kind = kind.trim();
+        if matches!(kind, "number" | "date" | "time") {
+            return self.parse_format(name, ty, kind);
+        }
+        if self.peek() != Some(',') {
+            return Err(format!("placeholder '{name}' is missing arms for '{kind}'"));
+        }
+        self.pos += 1;
+        match kind {
+            "plural" | "selectordinal" => {
+                if let Some(ty) = ty.as_deref() {
+                    if !matches!(ty, "Int" | "Float" | "Decimal") {
+                        return Err(format!(
+                            "{kind} placeholder '{name}' must be numeric, got {ty}"
+                        ));
+                    }
                 }
-                let (name, ty) = match inner.split_once(':') {
-                    Some((n, t)) => (n.trim(), Some(t.trim())),
-                    None => (inner, None),
-                };
-                validate_placeholder_name(name)?;
-                let ty = ty
-                    .filter(|t| !t.is_empty())
-                    .map(|t| validate_placeholder_type(t).map(|_| t.to_string()))
-                    .transpose()?;
-                parts.push(MessagePart::Hole {
+                let arms = self.parse_arms(name, kind, true)?;
+                Ok(MessagePart::Plural {
                     name: name.to_string(),
                     ty,
-                });
+                    ordinal: kind == "selectordinal",
+                    arms,
+                })
             }
-            '}' => {
-                if matches!(chars.peek(), Some('}')) {
-                    chars.next();
-                    buf.push('}');
-                    continue;
+            "select" => {
+                if let Some(ty) = ty.as_deref() {
+                    if ty != "Text" {
+                        return Err(format!(
+                            "select placeholder '{name}' must be Text, got {ty}"
+                        ));
+                    }
                 }
-                return Err("unexpected '}' (use '}}' to escape)".to_string());
+                let arms = self.parse_arms(name, kind, in_plural)?;
+                Ok(MessagePart::Select {
+                    name: name.to_string(),
+                    ty,
+                    arms,
+                })
             }
-            other => buf.push(other),
+            other => Err(format!(
+                "unsupported placeholder format '{other}' (expected plural, select, selectordinal, number, date or time)"
+            )),
         }
     }
-    if !buf.is_empty() {
-        parts.push(MessagePart::Lit(buf));
+
+    fn parse_format(
+        &mut self,
+        name: &str,
+        ty: Option<String>,
+        format: &str,
+    ) -> Result<MessagePart, String> {
+        let style = match self.peek() {
+            Some(',') => {
+                self.pos += 1;
+                let style = self.take_until(&['}', ',']);
+                Some(style.trim().to_string())
+            }
+            _ => None,
+        };
+        if self.peek() != Some('}') {
+            return Err(format!("unclosed {format} placeholder '{name}'"));
+        }
+        self.pos += 1;
+        let (allowed_types, style_ok): (&[&str], bool) = match format {
+            "number" => (
+                &["Int", "Float", "Decimal"],
+                style.as_deref().is_none_or(|style| {
+                    matches!(style, "integer" | "percent")
+                        || style.strip_prefix("currency/").is_some_and(|code| {
+                            code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic())
+                        })
+                }),
+            ),
+            _ => (
+                &["DateTime"],
+                style
+                    .as_deref()
+                    .is_none_or(|style| matches!(style, "short" | "medium" | "long")),
+            ),
+        };
+        if let Some(ty) = ty.as_deref() {
+            if !allowed_types.contains(&ty) {
+                return Err(format!(
+                    "{format} placeholder '{name}' must be {}, got {ty}",
+                    allowed_types.join(" or ")
+                ));
+            }
+        }
+        if !style_ok {
+            return Err(format!(
+                "unsupported {format} style '{}' in placeholder '{name}'",
+                style.unwrap_or_default()
+            ));
+        }
+        Ok(MessagePart::Format {
+            name: name.to_string(),
+            ty,
+            format: format.to_string(),
+            style,
+        })
+    }
+
+    fn parse_arms(
+        &mut self,
+        name: &str,
+        kind: &str,
+        in_plural: bool,
+    ) -> Result<Vec<MessageArm>, String> {
+        let mut arms: Vec<MessageArm> = Vec::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                None => return Err(format!("unclosed {kind} placeholder '{name}'")),
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(_) => {}
+            }
+            let selector = self.take_until(&['{', '}']);
+            let selector = selector.trim().to_string();
+            if self.peek() != Some('{') {
+                return Err(format!(
+                    "{kind} placeholder '{name}': expected '{{' after selector '{selector}'"
+                ));
+            }
+            validate_arm_selector(kind, &selector)?;
+            if arms.iter().any(|arm| arm.selector == selector) {
+                return Err(format!(
+                    "{kind} placeholder '{name}': duplicate selector '{selector}'"
+                ));
+            }
+            self.pos += 1;
+            let parts = self.parse_parts(true, in_plural)?;
+            if self.peek() != Some('}') {
+                return Err(format!(
+                    "{kind} placeholder '{name}': unclosed arm '{selector}'"
+                ));
+            }
+            self.pos += 1;
+            arms.push(MessageArm { selector, parts });
+        }
+        if !arms.iter().any(|arm| arm.selector == "other") {
+            return Err(format!(
+                "{kind} placeholder '{name}' requires an 'other' arm"
+            ));
+        }
+        Ok(arms)
     }
-    Ok(parts)
 }
 
-fn message_value(body: String, parts: &[MessagePart]) -> Value {
-    let parts_value = list_value(
-        parts
-            .iter()
-            .map(|part| match part {
-                MessagePart::Lit(text) => Value::Record(Arc::new(HashMap::from([
-                    ("kind".to_string(), Value::Text("lit".to_string())),
-                    ("text".to_string(), Value::Text(text.clone())),
-                ]))),
-                MessagePart::Hole { name, ty } => Value::Record(Arc::new(HashMap::from([
-                    ("kind".to_string(), Value::Text("hole".to_string())),
-                    ("name".to_string(), Value::Text(name.clone())),
-                    (
-                        "ty".to_string(),
-                        match ty {
-                            Some(t) => make_some(Value::Text(t.clone())),
-                            None => Value::Constructor {
-                                name: "None".to_string(),
-                                args: Vec::new(),
-                            },
-                        },
-                    ),
-                ]))),
-            })
-            .collect(),
-    );
+fn validate_arm_selector(kind: &str, selector: &str) -> Result<(), String> {
+    if selector.is_empty() {
+        return Err(format!("{kind} arm is missing a selector"));
+    }
+    if kind == "select" {
+        if !selector
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+        {
+            return Err(format!("invalid select key '{selector}'"));
+        }
+        return Ok(());
+    }
+    if let Some(exact) = selector.strip_prefix('=') {
+        if exact.is_empty() || !exact.chars().all(|ch| ch.is_ascii_digit()) {
+            return Err(format!(
+                "invalid {kind} selector '{selector}': expected '=' followed by digits"
+            ));
+        }
+        return Ok(());
+    }
+    if PLURAL_CATEGORIES.contains(&selector) {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid {kind} selector '{selector}': expected one of zero, one, two, few, many, other or '=N'"
+        ))
+    }
+}
 
+fn message_value(body: String, parts: &[MessagePart]) -> Value {
     Value::Record(Arc::new(HashMap::from([
         ("tag".to_string(), Value::Text("m".to_string())),
         ("body".to_string(), Value::Text(body)),
         ("flags".to_string(), Value::Text(String::new())),
-        ("parts".to_string(), parts_value),
+        ("parts".to_string(), message_parts_value(parts)),
     ])))
 }
 
-fn render_compiled_parts(
-    parts: &Arc<Vec<Value>>,
-    args: &std::collections::HashMap<String, Value>,
-) -> Result<String, RuntimeError> {
+fn message_parts_value(parts: &[MessagePart]) -> Value {
+    Value::List(Arc::new(parts.iter().map(message_part_value).collect()))
+}
+
+fn message_part_value(part: &MessagePart) -> Value {
+    let text = |value: &str| Value::Text(value.to_string());
+    let ty_value = |ty: &Option<String>| match ty {
+        Some(t) => Value::Constructor {
+            name: "Some".to_string(),
+            args: vec![Value::Text(t.clone())],
+        },
+        None => Value::Constructor {
+            name: "None".to_string(),
+            args: Vec::new(),
+        },
+    };
+    let arms_value = |arms: &[MessageArm]| {
+        Value::List(Arc::new(
+            arms.iter()
+                .map(|arm| {
+                    Value::Record(Arc::new(HashMap::from([
+                        ("selector".to_string(), text(&arm.selector)),
+                        ("parts".to_string(), message_parts_value(&arm.parts)),
+                    ])))
+                })
+                .collect(),
+        ))
+    };
+    let fields = match part {
+        MessagePart::Lit(value) => vec![("kind", text("lit")), ("text", text(value))],
+        MessagePart::Hole { name, ty } => vec![
+            ("kind", text("hole")),
+            ("name", text(name)),
+            ("ty", ty_value(ty)),
+        ],
+        MessagePart::Pound => vec![("kind", text("pound"))],
+        MessagePart::Plural {
+            name,
+            ty,
+            ordinal,
+            arms,
+        } => vec![
+            ("kind", text("plural")),
+            ("name", text(name)),
+            ("ty", ty_value(ty)),
+            ("ordinal", Value::Bool(*ordinal)),
+            ("arms", arms_value(arms)),
+        ],
+        MessagePart::Select { name, ty, arms } => vec![
+            ("kind", text("select")),
+            ("name", text(name)),
+            ("ty", ty_value(ty)),
+            ("arms", arms_value(arms)),
+        ],
+        MessagePart::Format {
+            name,
+            ty,
+            format,
+            style,
+        } => vec![
+            ("kind", text("format")),
+            ("name", text(name)),
+            ("ty", ty_value(ty)),
+            ("format", text(format)),
+            ("style", ty_value(style)),
+        ],
+    };
+    Value::Record(Arc::new(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    ))
+}
+
+fn locale_tag(locale: &HashMap<String, Value>, ctx: &str) -> Result<String, RuntimeError> {
+    match (locale.get("tag"), locale.get("language")) {
+        (Some(Value::Text(tag)), _) | (None, Some(Value::Text(tag))) => Ok(tag.clone()),
+        _ => Err(RuntimeError::Message(format!(
+            "{ctx} expects Locale with field 'tag : Text'"
+        ))),
+    }
+}
+
+fn format_number_of(value: &Value) -> Option<FormatNumber> {
+    match value {
+        Value::Int(n) => Some(FormatNumber::Int(*n)),
+        Value::Float(n) => Some(FormatNumber::Float(*n)),
+        Value::Decimal(n) => Some(FormatNumber::Decimal(*n)),
+        _ => None,
+    }
+}
+
+fn expect_number(value: Value, ctx: &str) -> Result<FormatNumber, RuntimeError> {
+    format_number_of(&value).ok_or_else(|| {
+        RuntimeError::Message(format!(
+            "{ctx} expects a number, got {}",
+            value_type_name(&value)
+        ))
+    })
+}
+
+fn expect_date_style(value: Value, ctx: &str) -> Result<DateStyle, RuntimeError> {
+    match &value {
+        Value::Constructor { name, args } if args.is_empty() => DateStyle::parse(name),
+        _ => None,
+    }
+    .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects DateStyle")))
+}
+
+/// Plural rules used by `i18n.render`, which has no locale to go by.
+const DEFAULT_PLURAL_LANGUAGE: &str = "en";
+
+/// Locale conventions applied while rendering a message.
+#[derive(Clone, Copy)]
+struct RenderLocale<'a> {
+    language: &'a str,
+    /// `None` for `i18n.render`: typed holes keep their locale-neutral text form.
+    formats: Option<&'static LocaleFormats>,
+}
+
+fn render_message(
+    msg: &HashMap<String, Value>,
+    args: &HashMap<String, Value>,
+    locale: RenderLocale,
+    ctx: &str,
+) -> Result<Value, RuntimeError> {
+    let body = match msg.get("body") {
+        Some(Value::Text(text)) => text.clone(),
+        _ => {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects Message with field 'body : Text'"
+            )))
+        }
+    };
+
+    let parts = match msg.get("parts") {
+        // Trusted compilation output from `i18n.message`.
+        Some(Value::List(items)) => decode_parts(items, ctx)?,
+        _ => parse_message_template(&body).map_err(RuntimeError::Message)?,
+    };
     let mut out = String::new();
-    for part in parts.iter().cloned() {
-        let rec = expect_record(part, "i18n.render")?;
-        let kind = match rec.get("kind") {
-            Some(Value::Text(k)) => k.as_str(),
-            _ => {
-                return Err(RuntimeError::Message(
-                    "i18n.render expects compiled message parts".to_string(),
-                ))
+    render_parsed(&parts, args, locale, None, &mut out)?;
+    Ok(make_ok(Value::Text(out)))
+}
+
+fn decode_parts(parts: &[Value], ctx: &str) -> Result<Vec<MessagePart>, RuntimeError> {
+    parts.iter().map(|part| decode_part(part, ctx)).collect()
+}
+
+fn decode_part(part: &Value, ctx: &str) -> Result<MessagePart, RuntimeError> {
+    let rec = expect_record(part.clone(), ctx)?;
+    let text_field = |field: &str, kind: &str| match rec.get(field) {
+        Some(Value::Text(text)) => Ok(text.clone()),
+        _ => Err(RuntimeError::Message(format!(
+            "{ctx} expects {kind} part with '{field} : Text'"
+        ))),
+    };
+    let ty = match rec.get("ty") {
+        Some(Value::Constructor { name, args }) if name == "Some" && args.len() == 1 => {
+            match &args[0] {
+                Value::Text(t) => Some(t.clone()),
+                _ => None,
             }
+        }
+        _ => None,
+    };
+    let arms = || -> Result<Vec<MessageArm>, RuntimeError> {
+        let Some(Value::List(items)) = rec.get("arms") else {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects arms : List in plural/select parts"
+            )));
         };
-        match kind {
-            "lit" => match rec.get("text") {
-                Some(Value::Text(text)) => out.push_str(text),
-                _ => {
-                    return Err(RuntimeError::Message(
-                        "i18n.render expects lit part with 'text : Text'".to_string(),
-                    ))
+        items
+            .iter()
+            .map(|item| {
+                let arm = expect_record(item.clone(), ctx)?;
+                match (arm.get("selector"), arm.get("parts")) {
+                    (Some(Value::Text(selector)), Some(Value::List(parts))) => Ok(MessageArm {
+                        selector: selector.clone(),
+                        parts: decode_parts(parts, ctx)?,
+                    }),
+                    _ => Err(RuntimeError::Message(format!(
+                        "{ctx} expects arms with 'selector : Text' and 'parts : List'"
+                    ))),
                 }
-            },
-            "hole" => {
-                let name = match rec.get("name") {
-                    Some(Value::Text(name)) => name.as_str(),
-                    _ => {
-                        return Err(RuntimeError::Message(
-                            "i18n.render expects hole part with 'name : Text'".to_string(),
-                        ))
-                    }
-                };
-                let expected_ty = match rec.get("ty") {
-                    Some(Value::Constructor { name, args })
-                        if name == "Some" && args.len() == 1 =>
-                    {
-                        match &args[0] {
-                            Value::Text(t) => Some(t.as_str()),
-                            _ => None,
-                        }
-                    }
-                    _ => None,
-                };
-                let value = args.get(name).ok_or_else(|| {
-                    RuntimeError::Message(format!("missing message arg '{name}'"))
-                })?;
-                if let Some(ty) = expected_ty {
-                    if !matches_type(ty, value) {
-                        return Err(RuntimeError::Message(format!(
-                            "type mismatch for arg '{name}': expected {ty}, got {}",
-                            value_type_name(value)
-                        )));
+            })
+            .collect()
+    };
+    let kind = match rec.get("kind") {
+        Some(Value::Text(k)) => k.as_str(),
+        _ => {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects compiled message parts"
+            )))
+        }
+    };
+    match kind {
+        "lit" => Ok(MessagePart::Lit(text_field("text", kind)?)),
+        "hole" => Ok(MessagePart::Hole {
+            name: text_field("name", kind)?,
+            ty,
+        }),
+        "pound" => Ok(MessagePart::Pound),
+        "plural" => Ok(MessagePart::Plural {
+            name: text_field("name", kind)?,
+            ty,
+            ordinal: matches!(rec.get("ordinal"), Some(Value::Bool(true))),
+            arms: arms()?,
+        }),
+        "select" => Ok(MessagePart::Select {
+            name: text_field("name", kind)?,
+            ty,
+            arms: arms()?,
+        }),
+        "format" => Ok(MessagePart::Format {
+            name: text_field("name", kind)?,
+            ty,
+            format: text_field("format", kind)?,
+            style: match rec.get("style") {
+                Some(Value::Constructor { name, args }) if name == "Some" && args.len() == 1 => {
+                    match &args[0] {
+                        Value::Text(style) => Some(style.clone()),
+                        _ => None,
                     }
                 }
-                out.push_str(&format_value(value));
-            }
-            other => {
-                return Err(RuntimeError::Message(format!(
-                    "i18n.render: unknown part kind '{other}'"
-                )))
-            }
-        }
+                _ => None,
+            },
+        }),
+        other => Err(RuntimeError::Message(format!(
+            "{ctx}: unknown part kind '{other}'"
+        ))),
     }
-    Ok(out)
 }
 
-fn render_parts(
+fn render_parsed(
     parts: &[MessagePart],
-    args: &std::collections::HashMap<String, Value>,
-) -> Result<String, RuntimeError> {
-    let mut out = String::new();
+    args: &HashMap<String, Value>,
+    locale: RenderLocale,
+    pound: Option<&Value>,
+    out: &mut String,
+) -> Result<(), RuntimeError> {
     for part in parts {
         match part {
             MessagePart::Lit(text) => out.push_str(text),
             MessagePart::Hole { name, ty } => {
-                let value = args.get(name).ok_or_else(|| {
-                    RuntimeError::Message(format!("missing message arg '{name}'"))
-                })?;
-                if let Some(ty) = ty.as_deref() {
-                    if !matches_type(ty, value) {
+                let value = message_arg(args, name, ty.as_deref())?;
+                match (locale.formats, ty.as_deref(), value) {
+                    (Some(formats), Some(_), Value::DateTime(text)) => out
+                        .push_str(&format_date_time(formats, DateStyle::Medium, text).map_err(
+                            |msg| RuntimeError::Message(format!("arg '{name}': {msg}")),
+                        )?),
+                    (Some(formats), Some(_), value) => match format_number_of(value) {
+                        Some(number) => out.push_str(&format_number(formats, number)),
+                        None => out.push_str(&format_value(value)),
+                    },
+                    _ => out.push_str(&format_value(value)),
+                }
+            }
+            MessagePart::Pound => {
+                if let Some(value) = pound {
+                    match (locale.formats, format_number_of(value)) {
+                        (Some(formats), Some(number)) => {
+                            out.push_str(&format_number(formats, number))
+                        }
+                        _ => out.push_str(&format_value(value)),
+                    }
+                }
+            }
+            MessagePart::Plural {
+                name,
+                ty,
+                ordinal,
+                arms,
+            } => {
+                let value = message_arg(args, name, ty.as_deref())?;
+                let number = match value {
+                    Value::Int(n) => n.to_string(),
+                    Value::Float(n) => n.to_string(),
+                    Value::Decimal(n) => n.to_string(),
+                    other => {
+                        return Err(RuntimeError::Message(format!(
+                            "type mismatch for arg '{name}': expected a number, got {}",
+                            value_type_name(other)
+                        )))
+                    }
+                };
+                if let Some(arm) = select_plural_arm(arms, locale.language, &number, *ordinal) {
+                    render_parsed(&arm.parts, args, locale, Some(value), out)?;
+                }
+            }
+            MessagePart::Select { name, ty, arms } => {
+                let value = message_arg(args, name, ty.as_deref())?;
+                let key = match value {
+                    Value::Text(text) => text.clone(),
+                    Value::Bool(flag) => flag.to_string(),
+                    Value::Constructor { name, args } if args.is_empty() => name.clone(),
+                    other => {
                         return Err(RuntimeError::Message(format!(
-                            "type mismatch for arg '{name}': expected {ty}, got {}",
-                            value_type_name(value)
-                        )));
+                            "type mismatch for arg '{name}': expected Text, got {}",
+                            value_type_name(other)
+                        )))
                     }
+                };
+                if let Some(arm) = select_arm(arms, &key) {
+                    render_parsed(&arm.parts, args, locale, pound, out)?;
                 }
-                out.push_str(&format_value(value));
+            }
+            MessagePart::Format {
+                name,
+                ty,
+                format,
+                style,
+            } => {
+                let value = message_arg(args, name, ty.as_deref())?;
+                let formats = locale
+                    .formats
+                    .unwrap_or_else(|| locale_formats(DEFAULT_PLURAL_LANGUAGE));
+                out.push_str(&format_message_value(
+                    formats,
+                    format,
+                    style.as_deref(),
+                    name,
+                    value,
+                )?);
             }
         }
     }
-    Ok(out)
+    Ok(())
+}
+
+fn format_message_value(
+    formats: &LocaleFormats,
+    format: &str,
+    style: Option<&str>,
+    name: &str,
+    value: &Value,
+) -> Result<String, RuntimeError> {
+    let mismatch = |expected: &str| {
+        RuntimeError::Message(format!(
+            "type mismatch for arg '{name}': expected {expected}, got {}",
+            value_type_name(value)
+        ))
+    };
+    if format == "number" {
+        let number = format_number_of(value).ok_or_else(|| mismatch("a number"))?;
+        return Ok(match style {
+            Some("integer") => format_integer(formats, number),
+            Some("percent") => format_percent(formats, number),
+            Some(style) => match style.strip_prefix("currency/") {
+                Some(code) => format_currency(formats, code, number),
+                None => format_number(formats, number),
+            },
+            None => format_number(formats, number),
+        });
+    }
+    let Value::DateTime(text) = value else {
+        return Err(mismatch("DateTime"));
+    };
+    let style = style
+        .and_then(DateStyle::parse)
+        .unwrap_or(DateStyle::Medium);
+    let formatted = if format == "time" {
+        format_time(formats, style, text)
+    } else {
+        format_date(formats, style, text)
+    };
+    formatted.map_err(|msg| RuntimeError::Message(format!("arg '{name}': {msg}")))
+}
+
+fn message_arg<'a>(
+    args: &'a HashMap<String, Value>,
+    name: &str,
+    ty: Option<&str>,
+) -> Result<&'a Value, RuntimeError> {
+    let value = args
+        .get(name)
+        .ok_or_else(|| RuntimeError::Message(format!("missing message arg '{name}'")))?;
+    if let Some(ty) = ty {
+        if !matches_type(ty, value) {
+            return Err(RuntimeError::Message(format!(
+                "type mismatch for arg '{name}': expected {ty}, got {}",
+                value_type_name(value)
+            )));
+        }
+    }
+    Ok(value)
 }
 
 fn matches_type(expected: &str, value: &Value) -> bool {
@@ -451,6 +1065,7 @@ fn value_type_name(value: &Value) -> &'static str {
         Value::FileHandle(_) => "FileHandle",
         Value::Listener(_) => "Listener",
         Value::Connection(_) => "Connection",
+        Value::UdpSocket(_) => "UdpSocket",
         Value::Stream(_) => "Stream",
         Value::HttpServer(_) => "HttpServer",
         Value::WebSocket(_) => "WebSocket",
@@ -518,6 +1133,164 @@ fn validate_placeholder_type(ty: &str) -> Result<(), String> {
     }
 }
 
+/// CLDR plural categories accepted as plural/selectordinal selectors.
+const PLURAL_CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// CLDR plural operands, derived from the decimal text of a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PluralOperands {
+    /// Integer digits of the absolute value.
+    i: u64,
+    /// Number of visible fraction digits (with trailing zeros).
+    v: usize,
+    /// Visible fraction digits as an integer (with trailing zeros).
+    f: u64,
+}
+
+impl PluralOperands {
+    /// Parses operands from a plain decimal rendering such as `-12`, `1.50` or `3.25`.
+    fn from_decimal_text(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('-');
+        let (int_part, frac_part) = text.split_once('.').unwrap_or((text, ""));
+        if int_part.is_empty() || !int_part.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        if !frac_part.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        // Only the low digits matter for the modulo-based rules.
+        let low = |digits: &str| {
+            let start = digits.len().saturating_sub(18);
+            digits[start..].parse::<u64>().unwrap_or(0)
+        };
+        Some(Self {
+            i: low(int_part),
+            v: frac_part.len(),
+            f: low(frac_part),
+        })
+    }
+
+    fn integer(&self) -> Option<u64> {
+        (self.f == 0).then_some(self.i)
+    }
+}
+
+/// Returns the CLDR plural category for `operands` in `language`.
+///
+/// Rules are bundled for the locales we ship; other languages use the CLDR root rule (`other`).
+fn plural_category(language: &str, operands: PluralOperands, ordinal: bool) -> &'static str {
+    let PluralOperands { i, v, .. } = operands;
+    let n = operands.integer();
+    if ordinal {
+        return match language {
+            "en" => match (i % 10, i % 100) {
+                _ if n.is_none() => "other",
+                (1, r) if r != 11 => "one",
+                (2, r) if r != 12 => "two",
+                (3, r) if r != 13 => "few",
+                _ => "other",
+            },
+            "fr" if n == Some(1) => "one",
+            "it" if matches!(n, Some(11 | 8 | 80 | 800)) => "many",
+            "sv" if n.is_some() && matches!(i % 10, 1 | 2) && !matches!(i % 100, 11 | 12) => "one",
+            _ => "other",
+        };
+    }
+    let million = v == 0 && i != 0 && i % 1_000_000 == 0;
+    match language {
+        "en" | "de" | "nl" | "sv" | "nb" | "no" | "fi" | "et" | "ca" | "gl" => {
+            if i == 1 && v == 0 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        // `n = 1 or t != 0 and i = 0,1`: 1, 1.0, 0.5 and 1.5 are all `one`.
+        "da" => {
+            if i <= 1 && (n == Some(1) || n.is_none()) {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        "it" => match () {
+            _ if i == 1 && v == 0 => "one",
+            _ if million => "many",
+            _ => "other",
+        },
+        "es" => match () {
+            _ if n == Some(1) => "one",
+            _ if million => "many",
+            _ => "other",
+        },
+        "fr" | "pt" => match () {
+            _ if i <= 1 => "one",
+            _ if million => "many",
+            _ => "other",
+        },
+        "ru" | "uk" => match (v, i % 10, i % 100) {
+            (0, 1, r) if r != 11 => "one",
+            (0, 2..=4, r) if !(12..=14).contains(&r) => "few",
+            (0, _, _) => "many",
+            _ => "other",
+        },
+        "pl" => match (v, i % 10, i % 100) {
+            (0, _, _) if i == 1 => "one",
+            (0, 2..=4, r) if !(12..=14).contains(&r) => "few",
+            (0, _, _) => "many",
+            _ => "other",
+        },
+        "cs" | "sk" => match () {
+            _ if v != 0 => "many",
+            _ if i == 1 => "one",
+            _ if (2..=4).contains(&i) => "few",
+            _ => "other",
+        },
+        "ar" => match n {
+            Some(0) => "zero",
+            Some(1) => "one",
+            Some(2) => "two",
+            Some(n) if (3..=10).contains(&(n % 100)) => "few",
+            Some(n) if (11..=99).contains(&(n % 100)) => "many",
+            _ => "other",
+        },
+        _ => "other",
+    }
+}
+
+/// Picks the plural arm for a number given as decimal text: exact `=N` arms win over the
+/// locale's plural category, which falls back to `other`.
+fn select_plural_arm<'a>(
+    arms: &'a [MessageArm],
+    language: &str,
+    number: &str,
+    ordinal: bool,
+) -> Option<&'a MessageArm> {
+    let other = || arms.iter().find(|arm| arm.selector == "other");
+    let Some(operands) = PluralOperands::from_decimal_text(number) else {
+        return other();
+    };
+    if !number.trim().starts_with('-') {
+        if let Some(n) = operands.integer() {
+            let exact = format!("={n}");
+            if let Some(arm) = arms.iter().find(|arm| arm.selector == exact) {
+                return Some(arm);
+            }
+        }
+    }
+    let category = plural_category(language, operands, ordinal);
+    arms.iter()
+        .find(|arm| arm.selector == category)
+        .or_else(other)
+}
+
+/// Picks the select arm matching `key`, falling back to `other`.
+fn select_arm<'a>(arms: &'a [MessageArm], key: &str) -> Option<&'a MessageArm> {
+    arms.iter()
+        .find(|arm| arm.selector == key)
+        .or_else(|| arms.iter().find(|arm| arm.selector == "other"))
+}
+
 fn split_kv(line: &str) -> Option<(&str, &str)> {
     if let Some((k, v)) = line.split_once('=') {
         return Some((k, v));
@@ -0,0 +1,733 @@
+//! Locale-aware number, currency and date formatting backed by a small bundle of CLDR data.
+//! Mirrors the interpreter's `i18n_format` module.
+//!
+//! `en` (and `en-GB`) are always available; the remaining locales are compiled in through the
+//! `cldr-*` Cargo features (all enabled by default via `cldr-all`).
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Per-locale formatting conventions (a subset of CLDR).
+#[derive(Debug)]
+pub struct LocaleFormats {
+    /// Normalized locale tag (`en`, `en-GB`, `de`, ...).
+    pub tag: &'static str,
+    pub decimal: &'static str,
+    pub group: &'static str,
+    /// Minimum integer digits before grouping kicks in (CLDR `minimumGroupingDigits` + 3).
+    pub min_grouping: usize,
+    /// `{0}` is the number.
+    pub percent: &'static str,
+    /// `{0}` is the amount and `{s}` the currency symbol.
+    pub currency: &'static str,
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+    /// Short, medium and long date patterns.
+    pub date: [&'static str; 3],
+    /// Short, medium and long time patterns.
+    pub time: [&'static str; 3],
+    /// `{1}` is the date and `{0}` the time.
+    pub date_time: &'static str,
+    pub am_pm: [&'static str; 2],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateStyle {
+    Short,
+    Medium,
+    Long,
+}
+
+impl DateStyle {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "Short" | "short" => Some(Self::Short),
+            "Medium" | "medium" => Some(Self::Medium),
+            "Long" | "long" => Some(Self::Long),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::Short => 0,
+            Self::Medium => 1,
+            Self::Long => 2,
+        }
+    }
+}
+
+/// A number to format, independent of the runtime's value representation.
+#[derive(Debug, Clone, Copy)]
+pub enum FormatNumber {
+    Int(i64),
+    Float(f64),
+    Decimal(Decimal),
+}
+
+impl FormatNumber {
+    fn to_decimal(self) -> Option<Decimal> {
+        match self {
+            Self::Int(n) => Some(Decimal::from(n)),
+            Self::Float(n) => Decimal::from_f64(n),
+            Self::Decimal(n) => Some(n),
+        }
+    }
+}
+
+/// Looks up formatting data for a locale tag, falling back to the language and then to `en`.
+pub fn locale_formats(tag: &str) -> &'static LocaleFormats {
+    let find = |tag: &str| {
+        LOCALES
+            .iter()
+            .find(|locale| locale.tag.eq_ignore_ascii_case(tag))
+    };
+    find(tag)
+        .or_else(|| tag.split(['-', '_']).next().and_then(find))
+        .unwrap_or(&LOCALES[0])
+}
+
+/// Decimal format: grouping and at most three fraction digits for floats. Decimals keep their
+/// scale.
+pub fn format_number(locale: &LocaleFormats, number: FormatNumber) -> String {
+    let text = match number {
+        FormatNumber::Int(n) => n.to_string(),
+        FormatNumber::Decimal(n) => n.to_string(),
+        FormatNumber::Float(n) => match Decimal::from_f64(n) {
+            Some(value) => value
+                .round_dp_with_strategy(3, RoundingStrategy::MidpointAwayFromZero)
+                .normalize()
+                .to_string(),
+            None => return n.to_string(),
+        },
+    };
+    localize_digits(locale, &text)
+}
+
+/// Integer format: rounds half away from zero, then groups.
+pub fn format_integer(locale: &LocaleFormats, number: FormatNumber) -> String {
+    match number.to_decimal() {
+        Some(value) => {
+            let rounded = value.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+            localize_digits(locale, &rounded.normalize().to_string())
+        }
+        None => format_number(locale, number),
+    }
+}
+
+/// Percent format: `0.25` renders as `25%` (no fraction digits).
+pub fn format_percent(locale: &LocaleFormats, number: FormatNumber) -> String {
+    let digits = match number.to_decimal() {
+        Some(value) => {
+            let scaled = (value * Decimal::ONE_HUNDRED)
+                .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+            localize_digits(locale, &scaled.normalize().to_string())
+        }
+        None => format_number(locale, number),
+    };
+    locale.percent.replace("{0}", &digits)
+}
+
+/// Currency format for an ISO 4217 code, rounded to the currency's minor units.
+pub fn format_currency(locale: &LocaleFormats, code: &str, number: FormatNumber) -> String {
+    let code = code.trim().to_ascii_uppercase();
+    let amount = match number.to_decimal() {
+        Some(value) => {
+            let mut rounded = value.round_dp_with_strategy(
+                currency_digits(&code),
+                RoundingStrategy::MidpointNearestEven,
+            );
+            rounded.rescale(currency_digits(&code));
+            localize_digits(locale, &rounded.to_string())
+        }
+        None => format_number(locale, number),
+    };
+    let (sign, amount) = match amount.strip_prefix('-') {
+        Some(rest) => ("-", rest.to_string()),
+        None => ("", amount),
+    };
+    format!(
+        "{sign}{}",
+        locale
+            .currency
+            .replace("{s}", currency_symbol(&code))
+            .replace("{0}", &amount)
+    )
+}
+
+pub fn format_date(locale: &LocaleFormats, style: DateStyle, text: &str) -> Result<String, String> {
+    let value = parse_date_time(text)?;
+    Ok(apply_pattern(locale, locale.date[style.index()], &value))
+}
+
+pub fn format_time(locale: &LocaleFormats, style: DateStyle, text: &str) -> Result<String, String> {
+    let value = parse_date_time(text)?;
+    Ok(apply_pattern(locale, locale.time[style.index()], &value))
+}
+
+pub fn format_date_time(
+    locale: &LocaleFormats,
+    style: DateStyle,
+    text: &str,
+) -> Result<String, String> {
+    let value = parse_date_time(text)?;
+    let date = apply_pattern(locale, locale.date[style.index()], &value);
+    let time = apply_pattern(locale, locale.time[style.index()], &value);
+    Ok(locale.date_time.replace("{1}", &date).replace("{0}", &time))
+}
+
+fn localize_digits(locale: &LocaleFormats, text: &str) -> String {
+    let (sign, rest) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text),
+    };
+    let (int_part, frac_part) = match rest.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (rest, None),
+    };
+    let mut out = String::from(sign);
+    if int_part.len() >= locale.min_grouping {
+        let head = int_part.len() % 3;
+        for (idx, ch) in int_part.chars().enumerate() {
+            if idx > 0 && (idx + 3 - head) % 3 == 0 {
+                out.push_str(locale.group);
+            }
+            out.push(ch);
+        }
+    } else {
+        out.push_str(int_part);
+    }
+    if let Some(frac_part) = frac_part {
+        out.push_str(locale.decimal);
+        out.push_str(frac_part);
+    }
+    out
+}
+
+fn currency_digits(code: &str) -> u32 {
+    match code {
+        "JPY" | "KRW" | "CLP" | "ISK" | "VND" => 0,
+        _ => 2,
+    }
+}
+
+fn currency_symbol(code: &str) -> &str {
+    match code {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" => "¥",
+        "CNY" => "CN¥",
+        "INR" => "₹",
+        "RUB" => "₽",
+        "PLN" => "zł",
+        "CZK" => "Kč",
+        "SEK" => "kr",
+        "BRL" => "R$",
+        "CAD" => "CA$",
+        "AUD" => "A$",
+        other => other,
+    }
+}
+
+fn parse_date_time(text: &str) -> Result<NaiveDateTime, String> {
+    let text = text.trim();
+    if let Ok(value) = DateTime::parse_from_rfc3339(text) {
+        return Ok(value.naive_local());
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(value) = NaiveDateTime::parse_from_str(text.trim_end_matches('Z'), format) {
+            return Ok(value);
+        }
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .map_err(|_| format!("invalid DateTime '{text}'"))
+}
+
+/// Expands a CLDR date/time pattern (`y`, `yy`, `M`..`MMMM`, `d`, `dd`, `H`, `HH`, `h`, `hh`,
+/// `mm`, `ss`, `a`, and `'quoted'` literals).
+fn apply_pattern(locale: &LocaleFormats, pattern: &str, value: &NaiveDateTime) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != '\'' {
+                out.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+        if !ch.is_ascii_alphabetic() {
+            out.push(ch);
+            i += 1;
+            continue;
+        }
+        let mut width = 1;
+        while i + width < chars.len() && chars[i + width] == ch {
+            width += 1;
+        }
+        i += width;
+        let hour12 = match value.hour() % 12 {
+            0 => 12,
+            h => h,
+        };
+        let field = match (ch, width) {
+            ('y', 2) => format!("{:02}", value.year().rem_euclid(100)),
+            ('y', _) => value.year().to_string(),
+            ('M', 1) => value.month().to_string(),
+            ('M', 2) => format!("{:02}", value.month()),
+            ('M', 3) => locale.months_short[value.month0() as usize].to_string(),
+            ('M', _) => locale.months[value.month0() as usize].to_string(),
+            ('d', 1) => value.day().to_string(),
+            ('d', _) => format!("{:02}", value.day()),
+            ('H', 1) => value.hour().to_string(),
+            ('H', _) => format!("{:02}", value.hour()),
+            ('h', 1) => hour12.to_string(),
+            ('h', _) => format!("{hour12:02}"),
+            ('m', _) => format!("{:02}", value.minute()),
+            ('s', _) => format!("{:02}", value.second()),
+            ('a', _) => locale.am_pm[usize::from(value.hour() >= 12)].to_string(),
+            (other, _) => other.to_string().repeat(width),
+        };
+        out.push_str(&field);
+    }
+    out
+}
+
+const NBSP: &str = "\u{a0}";
+const NNBSP: &str = "\u{202f}";
+
+static LOCALES: &[LocaleFormats] = &[
+    LocaleFormats {
+        tag: "en",
+        decimal: ".",
+        group: ",",
+        min_grouping: 4,
+        percent: "{0}%",
+        currency: "{s}{0}",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_short: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        date: ["M/d/yy", "MMM d, y", "MMMM d, y"],
+        time: ["h:mm a", "h:mm:ss a", "h:mm:ss a"],
+        date_time: "{1}, {0}",
+        am_pm: ["AM", "PM"],
+    },
+    LocaleFormats {
+        tag: "en-GB",
+        decimal: ".",
+        group: ",",
+        min_grouping: 4,
+        percent: "{0}%",
+        currency: "{s}{0}",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_short: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sept", "Oct", "Nov", "Dec",
+        ],
+        date: ["dd/MM/y", "d MMM y", "d MMMM y"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1}, {0}",
+        am_pm: ["am", "pm"],
+    },
+    #[cfg(feature = "cldr-de")]
+    LocaleFormats {
+        tag: "de",
+        decimal: ",",
+        group: ".",
+        min_grouping: 4,
+        percent: "{0}\u{a0}%",
+        currency: "{0}\u{a0}{s}",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_short: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+            "Dez.",
+        ],
+        date: ["dd.MM.yy", "dd.MM.y", "d. MMMM y"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1}, {0}",
+        am_pm: ["AM", "PM"],
+    },
+    #[cfg(feature = "cldr-fr")]
+    LocaleFormats {
+        tag: "fr",
+        decimal: ",",
+        group: NNBSP,
+        min_grouping: 4,
+        percent: "{0}\u{a0}%",
+        currency: "{0}\u{a0}{s}",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_short: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        date: ["dd/MM/y", "d MMM y", "d MMMM y"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1} {0}",
+        am_pm: ["AM", "PM"],
+    },
+    #[cfg(feature = "cldr-es")]
+    LocaleFormats {
+        tag: "es",
+        decimal: ",",
+        group: ".",
+        min_grouping: 5,
+        percent: "{0}\u{a0}%",
+        currency: "{0}\u{a0}{s}",
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        months_short: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        date: ["d/M/yy", "d MMM y", "d 'de' MMMM 'de' y"],
+        time: ["H:mm", "H:mm:ss", "H:mm:ss"],
+        date_time: "{1}, {0}",
+        am_pm: ["a.\u{a0}m.", "p.\u{a0}m."],
+    },
+    #[cfg(feature = "cldr-it")]
+    LocaleFormats {
+        tag: "it",
+        decimal: ",",
+        group: ".",
+        min_grouping: 4,
+        percent: "{0}%",
+        currency: "{0}\u{a0}{s}",
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        months_short: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        date: ["dd/MM/yy", "d MMM y", "d MMMM y"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1}, {0}",
+        am_pm: ["AM", "PM"],
+    },
+    #[cfg(feature = "cldr-pt")]
+    LocaleFormats {
+        tag: "pt",
+        decimal: ",",
+        group: ".",
+        min_grouping: 4,
+        percent: "{0}%",
+        currency: "{s}\u{a0}{0}",
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        months_short: [
+            "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.",
+            "dez.",
+        ],
+        date: ["dd/MM/y", "d 'de' MMM 'de' y", "d 'de' MMMM 'de' y"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1} {0}",
+        am_pm: ["AM", "PM"],
+    },
+    #[cfg(feature = "cldr-nl")]
+    LocaleFormats {
+        tag: "nl",
+        decimal: ",",
+        group: ".",
+        min_grouping: 4,
+        percent: "{0}%",
+        currency: "{s}\u{a0}{0}",
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        months_short: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        date: ["dd-MM-y", "d MMM y", "d MMMM y"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1} {0}",
+        am_pm: ["a.m.", "p.m."],
+    },
+    #[cfg(feature = "cldr-sv")]
+    LocaleFormats {
+        tag: "sv",
+        decimal: ",",
+        group: NBSP,
+        min_grouping: 4,
+        percent: "{0}\u{a0}%",
+        currency: "{0}\u{a0}{s}",
+        months: [
+            "januari",
+            "februari",
+            "mars",
+            "april",
+            "maj",
+            "juni",
+            "juli",
+            "augusti",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        months_short: [
+            "jan.", "feb.", "mars", "apr.", "maj", "juni", "juli", "aug.", "sep.", "okt.", "nov.",
+            "dec.",
+        ],
+        date: ["y-MM-dd", "d MMM y", "d MMMM y"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1} {0}",
+        am_pm: ["fm", "em"],
+    },
+    #[cfg(feature = "cldr-pl")]
+    LocaleFormats {
+        tag: "pl",
+        decimal: ",",
+        group: NBSP,
+        min_grouping: 5,
+        percent: "{0}%",
+        currency: "{0}\u{a0}{s}",
+        months: [
+            "stycznia",
+            "lutego",
+            "marca",
+            "kwietnia",
+            "maja",
+            "czerwca",
+            "lipca",
+            "sierpnia",
+            "września",
+            "października",
+            "listopada",
+            "grudnia",
+        ],
+        months_short: [
+            "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+        ],
+        date: ["d.MM.y", "d MMM y", "d MMMM y"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1}, {0}",
+        am_pm: ["AM", "PM"],
+    },
+    #[cfg(feature = "cldr-ru")]
+    LocaleFormats {
+        tag: "ru",
+        decimal: ",",
+        group: NBSP,
+        min_grouping: 4,
+        percent: "{0}\u{a0}%",
+        currency: "{0}\u{a0}{s}",
+        months: [
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ],
+        months_short: [
+            "янв.",
+            "февр.",
+            "мар.",
+            "апр.",
+            "мая",
+            "июн.",
+            "июл.",
+            "авг.",
+            "сент.",
+            "окт.",
+            "нояб.",
+            "дек.",
+        ],
+        date: ["dd.MM.y", "d MMM y 'г'.", "d MMMM y 'г'."],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1}, {0}",
+        am_pm: ["AM", "PM"],
+    },
+    #[cfg(feature = "cldr-cs")]
+    LocaleFormats {
+        tag: "cs",
+        decimal: ",",
+        group: NBSP,
+        min_grouping: 4,
+        percent: "{0}\u{a0}%",
+        currency: "{0}\u{a0}{s}",
+        months: [
+            "ledna",
+            "února",
+            "března",
+            "dubna",
+            "května",
+            "června",
+            "července",
+            "srpna",
+            "září",
+            "října",
+            "listopadu",
+            "prosince",
+        ],
+        months_short: [
+            "led", "úno", "bře", "dub", "kvě", "čvn", "čvc", "srp", "zář", "říj", "lis", "pro",
+        ],
+        date: ["dd.MM.yy", "d. M. y", "d. MMMM y"],
+        time: ["H:mm", "H:mm:ss", "H:mm:ss"],
+        date_time: "{1} {0}",
+        am_pm: ["dop.", "odp."],
+    },
+    #[cfg(feature = "cldr-ja")]
+    LocaleFormats {
+        tag: "ja",
+        decimal: ".",
+        group: ",",
+        min_grouping: 4,
+        percent: "{0}%",
+        currency: "{s}{0}",
+        months: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        months_short: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        date: ["y/MM/dd", "y/MM/dd", "y年M月d日"],
+        time: ["H:mm", "H:mm:ss", "H:mm:ss"],
+        date_time: "{1} {0}",
+        am_pm: ["午前", "午後"],
+    },
+    #[cfg(feature = "cldr-zh")]
+    LocaleFormats {
+        tag: "zh",
+        decimal: ".",
+        group: ",",
+        min_grouping: 4,
+        percent: "{0}%",
+        currency: "{s}{0}",
+        months: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        months_short: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        date: ["y/M/d", "y年M月d日", "y年M月d日"],
+        time: ["HH:mm", "HH:mm:ss", "HH:mm:ss"],
+        date_time: "{1} {0}",
+        am_pm: ["上午", "下午"],
+    },
+];
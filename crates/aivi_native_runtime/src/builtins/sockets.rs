@@ -1,12 +1,22 @@
 use std::collections::HashMap;
-use std::io::{Read, Write};
-use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use super::util::{builtin, expect_int, expect_list, expect_record, list_value};
+use aivi_http_server::net::{NetListener, NetStream};
+use aivi_http_server::tls;
+use aivi_http_server::{TlsClientSettings, TlsServerSettings};
+
+use super::util::{builtin, expect_bytes, expect_record, expect_text};
 use crate::{EffectValue, RuntimeError, Value};
 
 const DEFAULT_RECV_CHUNK: usize = 4096;
+// Large enough for any UDP payload.
+const MAX_DATAGRAM: usize = 65536;
+// How often blocking socket calls wake up to check for cancellation.
+const CANCEL_POLL: Duration = Duration::from_millis(25);
 
 fn socket_error_value(message: impl Into<String>) -> Value {
     let mut fields = HashMap::new();
@@ -14,7 +24,11 @@ fn socket_error_value(message: impl Into<String>) -> Value {
     Value::Record(Arc::new(fields))
 }
 
-fn address_from_value(value: Value, ctx: &str) -> Result<SocketAddr, RuntimeError> {
+fn socket_error(err: io::Error) -> RuntimeError {
+    RuntimeError::Error(socket_error_value(err.to_string()))
+}
+
+fn address_fields(value: Value, ctx: &str) -> Result<(String, u16), RuntimeError> {
     let record = expect_record(value, ctx)?;
     let host = match record.get("host") {
         Some(Value::Text(text)) => text.clone(),
@@ -34,38 +48,184 @@ fn address_from_value(value: Value, ctx: &str) -> Result<SocketAddr, RuntimeErro
     };
     let port = u16::try_from(port)
         .map_err(|_| RuntimeError::Message(format!("{ctx} expects Address.port in 0..65535")))?;
-    let addr = format!("{host}:{port}");
-    addr.parse()
-        .map_err(|_| RuntimeError::Message(format!("{ctx} invalid address")))
+    Ok((host, port))
+}
+
+fn parse_address(host: &str, port: u16, ctx: &str) -> Result<SocketAddr, RuntimeError> {
+    let ip: IpAddr = host
+        .parse()
+        .map_err(|_| RuntimeError::Message(format!("{ctx} invalid address")))?;
+    Ok(SocketAddr::new(ip, port))
+}
+
+fn address_value(addr: SocketAddr) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert("host".to_string(), Value::Text(addr.ip().to_string()));
+    fields.insert("port".to_string(), Value::Int(addr.port() as i64));
+    Value::Record(Arc::new(fields))
+}
+
+fn address_from_value(value: Value, ctx: &str) -> Result<SocketAddr, RuntimeError> {
+    let (host, port) = address_fields(value, ctx)?;
+    parse_address(&host, port, ctx)
+}
+
+fn text_field(
+    record: &HashMap<String, Value>,
+    name: &str,
+    ctx: &str,
+) -> Result<String, RuntimeError> {
+    match record.get(name) {
+        Some(Value::Text(text)) => Ok(text.clone()),
+        _ => Err(RuntimeError::Message(format!("{ctx} expects {name} Text"))),
+    }
+}
+
+// A missing field reads as `None`.
+fn option_field(record: &HashMap<String, Value>, name: &str) -> Option<Value> {
+    match record.get(name) {
+        Some(Value::Constructor { name, args }) if name == "Some" => args.first().cloned(),
+        _ => None,
+    }
+}
+
+fn option_path_field(
+    record: &HashMap<String, Value>,
+    name: &str,
+    ctx: &str,
+) -> Result<Option<PathBuf>, RuntimeError> {
+    match option_field(record, name) {
+        Some(Value::Text(text)) => Ok(Some(PathBuf::from(text))),
+        Some(_) => Err(RuntimeError::Message(format!(
+            "{ctx} expects {name} Option Text"
+        ))),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn tls_server_settings_from_value(
+    value: Value,
+    ctx: &str,
+) -> Result<TlsServerSettings, RuntimeError> {
+    let record = expect_record(value, ctx)?;
+    Ok(TlsServerSettings {
+        cert_file: text_field(&record, "certFile", ctx)?.into(),
+        key_file: text_field(&record, "keyFile", ctx)?.into(),
+        client_ca_file: option_path_field(&record, "clientCaFile", ctx)?,
+    })
+}
+
+fn tls_client_settings_from_value(
+    value: Value,
+    ctx: &str,
+) -> Result<TlsClientSettings, RuntimeError> {
+    let record = expect_record(value, ctx)?;
+    let server_name = match option_field(&record, "serverName") {
+        Some(Value::Text(text)) => Some(text),
+        Some(_) => {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects serverName Option Text"
+            )))
+        }
+        None => None,
+    };
+    let identity = match option_field(&record, "clientCert") {
+        Some(value) => {
+            let cert = expect_record(value, ctx)?;
+            Some((
+                text_field(&cert, "certFile", ctx)?.into(),
+                text_field(&cert, "keyFile", ctx)?.into(),
+            ))
+        }
+        None => None,
+    };
+    Ok(TlsClientSettings {
+        server_name,
+        ca_file: option_path_field(&record, "caFile", ctx)?,
+        identity,
+    })
+}
+
+fn listen_effect(addr: SocketAddr, tls: Option<TlsServerSettings>) -> Value {
+    let effect = EffectValue::Thunk {
+        func: Arc::new(move |_| {
+            let config = match &tls {
+                Some(settings) => {
+                    Some(Arc::new(tls::server_config(settings).map_err(|err| {
+                        RuntimeError::Error(socket_error_value(err.message))
+                    })?))
+                }
+                None => None,
+            };
+            let listener = NetListener::bind(addr, config).map_err(socket_error)?;
+            Ok(Value::Listener(Arc::new(listener)))
+        }),
+    };
+    Value::Effect(Arc::new(effect))
 }
 
 pub(super) fn connection_from_value(
     value: Value,
     ctx: &str,
-) -> Result<Arc<Mutex<TcpStream>>, RuntimeError> {
+) -> Result<Arc<Mutex<NetStream>>, RuntimeError> {
     match value {
         Value::Connection(handle) => Ok(handle),
         _ => Err(RuntimeError::Message(format!("{ctx} expects a connection"))),
     }
 }
 
-fn listener_from_value(value: Value, ctx: &str) -> Result<Arc<TcpListener>, RuntimeError> {
+fn listener_from_value(value: Value, ctx: &str) -> Result<Arc<NetListener>, RuntimeError> {
     match value {
         Value::Listener(handle) => Ok(handle),
         _ => Err(RuntimeError::Message(format!("{ctx} expects a listener"))),
     }
 }
 
-fn list_int_to_bytes(value: Value, ctx: &str) -> Result<Vec<u8>, RuntimeError> {
-    let items = expect_list(value, ctx)?;
-    let mut out = Vec::with_capacity(items.len());
-    for item in items.iter() {
-        let value = expect_int(item.clone(), ctx)?;
-        let byte = u8::try_from(value)
-            .map_err(|_| RuntimeError::Message(format!("{ctx} expects bytes in 0..255")))?;
-        out.push(byte);
+fn udp_from_value(value: Value, ctx: &str) -> Result<Arc<UdpSocket>, RuntimeError> {
+    match value {
+        Value::UdpSocket(handle) => Ok(handle),
+        _ => Err(RuntimeError::Message(format!("{ctx} expects a UDP socket"))),
+    }
+}
+
+fn unit_effect(run: impl Fn() -> Result<(), RuntimeError> + Send + Sync + 'static) -> Value {
+    let effect = EffectValue::Thunk {
+        func: Arc::new(move |_| run().map(|_| Value::Unit)),
+    };
+    Value::Effect(Arc::new(effect))
+}
+
+fn multicast_effect(socket: Arc<UdpSocket>, group: IpAddr, join: bool) -> Value {
+    unit_effect(move || {
+        let result = match (group, join) {
+            (IpAddr::V4(group), true) => {
+                socket.join_multicast_v4(&group, &std::net::Ipv4Addr::UNSPECIFIED)
+            }
+            (IpAddr::V4(group), false) => {
+                socket.leave_multicast_v4(&group, &std::net::Ipv4Addr::UNSPECIFIED)
+            }
+            (IpAddr::V6(group), true) => socket.join_multicast_v6(&group, 0),
+            (IpAddr::V6(group), false) => socket.leave_multicast_v6(&group, 0),
+        };
+        result.map_err(socket_error)
+    })
+}
+
+fn multicast_group(value: Value, ctx: &str) -> Result<IpAddr, RuntimeError> {
+    let group = expect_text(value, ctx)?;
+    match group.parse::<IpAddr>() {
+        Ok(ip) if ip.is_multicast() => Ok(ip),
+        _ => Err(RuntimeError::Message(format!(
+            "{ctx} expects a multicast group address, got {group}"
+        ))),
     }
-    Ok(out)
+}
+
+fn is_timeout(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
 }
 
 pub(super) fn build_sockets_record() -> Value {
@@ -74,14 +234,15 @@ pub(super) fn build_sockets_record() -> Value {
         "listen".to_string(),
         builtin("sockets.listen", 1, |mut args, _| {
             let addr = address_from_value(args.pop().unwrap(), "sockets.listen")?;
-            let effect = EffectValue::Thunk {
-                func: Arc::new(move |_| {
-                    let listener = TcpListener::bind(addr)
-                        .map_err(|err| RuntimeError::Error(socket_error_value(err.to_string())))?;
-                    Ok(Value::Listener(Arc::new(listener)))
-                }),
-            };
-            Ok(Value::Effect(Arc::new(effect)))
+            Ok(listen_effect(addr, None))
+        }),
+    );
+    fields.insert(
+        "listenTls".to_string(),
+        builtin("sockets.listenTls", 2, |mut args, _| {
+            let tls = tls_server_settings_from_value(args.pop().unwrap(), "sockets.listenTls")?;
+            let addr = address_from_value(args.pop().unwrap(), "sockets.listenTls")?;
+            Ok(listen_effect(addr, Some(tls)))
         }),
     );
     fields.insert(
@@ -89,11 +250,12 @@ pub(super) fn build_sockets_record() -> Value {
         builtin("sockets.accept", 1, |mut args, _| {
             let listener = listener_from_value(args.pop().unwrap(), "sockets.accept")?;
             let effect = EffectValue::Thunk {
-                func: Arc::new(move |_| {
-                    let (stream, _) = listener
-                        .accept()
-                        .map_err(|err| RuntimeError::Error(socket_error_value(err.to_string())))?;
-                    Ok(Value::Connection(Arc::new(Mutex::new(stream))))
+                func: Arc::new(move |runtime| loop {
+                    runtime.check_cancelled()?;
+                    match listener.try_accept().map_err(socket_error)? {
+                        Some(stream) => return Ok(Value::Connection(Arc::new(Mutex::new(stream)))),
+                        None => std::thread::sleep(CANCEL_POLL),
+                    }
                 }),
             };
             Ok(Value::Effect(Arc::new(effect)))
@@ -105,8 +267,28 @@ pub(super) fn build_sockets_record() -> Value {
             let addr = address_from_value(args.pop().unwrap(), "sockets.connect")?;
             let effect = EffectValue::Thunk {
                 func: Arc::new(move |_| {
-                    let stream = TcpStream::connect(addr)
-                        .map_err(|err| RuntimeError::Error(socket_error_value(err.to_string())))?;
+                    let stream = NetStream::connect(addr).map_err(socket_error)?;
+                    Ok(Value::Connection(Arc::new(Mutex::new(stream))))
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "connectTls".to_string(),
+        builtin("sockets.connectTls", 2, |mut args, _| {
+            let settings =
+                tls_client_settings_from_value(args.pop().unwrap(), "sockets.connectTls")?;
+            let (host, port) = address_fields(args.pop().unwrap(), "sockets.connectTls")?;
+            let addr = parse_address(&host, port, "sockets.connectTls")?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| {
+                    let socket_error =
+                        |message: String| RuntimeError::Error(socket_error_value(message));
+                    let config =
+                        tls::client_config(&settings).map_err(|err| socket_error(err.message))?;
+                    let stream = NetStream::connect_tls(addr, &host, Arc::new(config), &settings)
+                        .map_err(|err| socket_error(err.to_string()))?;
                     Ok(Value::Connection(Arc::new(Mutex::new(stream))))
                 }),
             };
@@ -116,17 +298,14 @@ pub(super) fn build_sockets_record() -> Value {
     fields.insert(
         "send".to_string(),
         builtin("sockets.send", 2, |mut args, _| {
-            let bytes = args.pop().unwrap();
+            let bytes = expect_bytes(args.pop().unwrap(), "sockets.send")?;
             let conn = connection_from_value(args.pop().unwrap(), "sockets.send")?;
-            let bytes = list_int_to_bytes(bytes, "sockets.send")?;
             let effect = EffectValue::Thunk {
                 func: Arc::new(move |_| {
                     let mut stream = conn
                         .lock()
                         .map_err(|_| RuntimeError::Message("connection poisoned".to_string()))?;
-                    stream
-                        .write_all(&bytes)
-                        .map_err(|err| RuntimeError::Error(socket_error_value(err.to_string())))?;
+                    stream.write_all(&bytes).map_err(socket_error)?;
                     Ok(Value::Unit)
                 }),
             };
@@ -143,15 +322,9 @@ pub(super) fn build_sockets_record() -> Value {
                         .lock()
                         .map_err(|_| RuntimeError::Message("connection poisoned".to_string()))?;
                     let mut buffer = vec![0u8; DEFAULT_RECV_CHUNK];
-                    let count = stream
-                        .read(&mut buffer)
-                        .map_err(|err| RuntimeError::Error(socket_error_value(err.to_string())))?;
+                    let count = stream.read(&mut buffer).map_err(socket_error)?;
                     buffer.truncate(count);
-                    let items = buffer
-                        .into_iter()
-                        .map(|byte| Value::Int(byte as i64))
-                        .collect::<Vec<_>>();
-                    Ok(list_value(items))
+                    Ok(Value::Bytes(Arc::new(buffer)))
                 }),
             };
             Ok(Value::Effect(Arc::new(effect)))
@@ -163,10 +336,10 @@ pub(super) fn build_sockets_record() -> Value {
             let conn = connection_from_value(args.pop().unwrap(), "sockets.close")?;
             let effect = EffectValue::Thunk {
                 func: Arc::new(move |_| {
-                    let stream = conn
+                    let mut stream = conn
                         .lock()
                         .map_err(|_| RuntimeError::Message("connection poisoned".to_string()))?;
-                    let _ = stream.shutdown(Shutdown::Both);
+                    let _ = stream.shutdown();
                     Ok(Value::Unit)
                 }),
             };
@@ -183,5 +356,108 @@ pub(super) fn build_sockets_record() -> Value {
             Ok(Value::Effect(Arc::new(effect)))
         }),
     );
+    fields.insert(
+        "listenUnix".to_string(),
+        builtin("sockets.listenUnix", 1, |mut args, _| {
+            let path = PathBuf::from(expect_text(args.pop().unwrap(), "sockets.listenUnix")?);
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| {
+                    let listener = NetListener::bind_unix(&path).map_err(socket_error)?;
+                    Ok(Value::Listener(Arc::new(listener)))
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "connectUnix".to_string(),
+        builtin("sockets.connectUnix", 1, |mut args, _| {
+            let path = expect_text(args.pop().unwrap(), "sockets.connectUnix")?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| {
+                    let stream = NetStream::connect_unix(Path::new(&path)).map_err(socket_error)?;
+                    Ok(Value::Connection(Arc::new(Mutex::new(stream))))
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "bind".to_string(),
+        builtin("sockets.bind", 1, |mut args, _| {
+            let addr = address_from_value(args.pop().unwrap(), "sockets.bind")?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| {
+                    let socket = UdpSocket::bind(addr).map_err(socket_error)?;
+                    socket
+                        .set_read_timeout(Some(CANCEL_POLL))
+                        .map_err(socket_error)?;
+                    Ok(Value::UdpSocket(Arc::new(socket)))
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "sendTo".to_string(),
+        builtin("sockets.sendTo", 3, |mut args, _| {
+            let bytes = expect_bytes(args.pop().unwrap(), "sockets.sendTo")?;
+            let addr = address_from_value(args.pop().unwrap(), "sockets.sendTo")?;
+            let socket = udp_from_value(args.pop().unwrap(), "sockets.sendTo")?;
+            Ok(unit_effect(move || {
+                socket.send_to(&bytes, addr).map_err(socket_error)?;
+                Ok(())
+            }))
+        }),
+    );
+    fields.insert(
+        "recvFrom".to_string(),
+        builtin("sockets.recvFrom", 1, |mut args, _| {
+            let socket = udp_from_value(args.pop().unwrap(), "sockets.recvFrom")?;
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |runtime| {
+                    let mut buffer = vec![0u8; MAX_DATAGRAM];
+                    loop {
+                        runtime.check_cancelled()?;
+                        match socket.recv_from(&mut buffer) {
+                            Ok((count, from)) => {
+                                buffer.truncate(count);
+                                let mut fields = HashMap::new();
+                                fields.insert("data".to_string(), Value::Bytes(Arc::new(buffer)));
+                                fields.insert("from".to_string(), address_value(from));
+                                return Ok(Value::Record(Arc::new(fields)));
+                            }
+                            Err(err) if is_timeout(&err) => continue,
+                            Err(err) => return Err(socket_error(err)),
+                        }
+                    }
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    fields.insert(
+        "joinMulticast".to_string(),
+        builtin("sockets.joinMulticast", 2, |mut args, _| {
+            let group = multicast_group(args.pop().unwrap(), "sockets.joinMulticast")?;
+            let socket = udp_from_value(args.pop().unwrap(), "sockets.joinMulticast")?;
+            Ok(multicast_effect(socket, group, true))
+        }),
+    );
+    fields.insert(
+        "leaveMulticast".to_string(),
+        builtin("sockets.leaveMulticast", 2, |mut args, _| {
+            let group = multicast_group(args.pop().unwrap(), "sockets.leaveMulticast")?;
+            let socket = udp_from_value(args.pop().unwrap(), "sockets.leaveMulticast")?;
+            Ok(multicast_effect(socket, group, false))
+        }),
+    );
+    fields.insert(
+        "closeUdp".to_string(),
+        builtin("sockets.closeUdp", 1, |mut args, _| {
+            let _socket = udp_from_value(args.pop().unwrap(), "sockets.closeUdp")?;
+            Ok(unit_effect(|| Ok(())))
+        }),
+    );
     Value::Record(Arc::new(fields))
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::io::Write;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::UNIX_EPOCH;
 
 use super::util::{builtin, expect_record, expect_text, make_err, make_none, make_ok, make_some};
 use crate::{format_value, EffectValue, RuntimeError, Value};
@@ -216,10 +216,8 @@ pub(super) fn build_clock_record() -> Value {
         builtin("clock.now", 1, |_, _| {
             let effect = EffectValue::Thunk {
                 func: Arc::new(move |_| {
-                    let now = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap_or(Duration::from_secs(0));
-                    let text = format!("{}.{:09}Z", now.as_secs(), now.subsec_nanos());
+                    let text =
+                        chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true);
                     Ok(Value::DateTime(text))
                 }),
             };
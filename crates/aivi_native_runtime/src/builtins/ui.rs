@@ -1,2 +1,4 @@
 include!("ui/server_and_render.rs");
 include!("ui/vdom_diff.rs");
+include!("ui/live_session.rs");
+include!("ui/static_site.rs");
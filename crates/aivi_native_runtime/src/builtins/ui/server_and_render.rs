@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -15,7 +15,13 @@ use crate::{format_value, EffectValue, Runtime, RuntimeContext, RuntimeError, Va
 #[derive(Clone)]
 enum UiHandler {
     Click(Value),
-    Input(Value), // Text -> msg
+    Input(Value),   // Text -> msg
+    Change(Value),  // Text -> msg
+    Check(Value),   // Bool -> msg
+    KeyDown(Value), // KeyEvent -> msg
+    Submit(Value),  // { } -> msg
+    Focus(Value),
+    Blur(Value),
 }
 
 pub(super) fn build_ui_record() -> Value {
@@ -62,6 +68,33 @@ pub(super) fn build_ui_record() -> Value {
             }
         }),
     );
+    fields.insert(
+        "renderPage".to_string(),
+        builtin("ui.renderPage", 2, |mut args, _runtime| {
+            let page = expect_page(&args.pop().unwrap())?;
+            let hydrate = expect_hydrate(&args.pop().unwrap(), "ui.renderPage")?;
+            Ok(Value::Text(render_page(&page, hydrate.as_deref())))
+        }),
+    );
+    fields.insert(
+        "auditPage".to_string(),
+        builtin("ui.auditPage", 1, |mut args, _runtime| {
+            let page = expect_page(&args.pop().unwrap())?;
+            let issues = audit_page(&page)
+                .into_iter()
+                .map(|message| audit_issue(&page.route, message))
+                .collect();
+            Ok(Value::List(Arc::new(issues)))
+        }),
+    );
+    fields.insert(
+        "buildSite".to_string(),
+        builtin("ui.buildSite", 2, |mut args, _runtime| {
+            let pages = args.pop().unwrap();
+            let config = args.pop().unwrap();
+            build_site(config, pages)
+        }),
+    );
     fields.insert(
         "live".to_string(),
         builtin("ui.live", 4, |mut args, runtime| {
@@ -69,19 +102,86 @@ pub(super) fn build_ui_record() -> Value {
             let view = args.pop().unwrap();
             let initial_model = args.pop().unwrap();
             let cfg = args.pop().unwrap();
-            ui_live(cfg, initial_model, view, update, runtime)
+            let app = LiveApp {
+                model: initial_model,
+                init_cmd: None,
+                view,
+                update,
+                subscriptions: None,
+                commands: false,
+            };
+            ui_live(cfg, app, runtime)
+        }),
+    );
+    fields.insert(
+        "liveApp".to_string(),
+        builtin("ui.liveApp", 5, |mut args, runtime| {
+            let subscriptions = args.pop().unwrap();
+            let update = args.pop().unwrap();
+            let view = args.pop().unwrap();
+            let init = args.pop().unwrap();
+            let cfg = args.pop().unwrap();
+            let app = LiveApp::from_init(init, view, update, subscriptions)?;
+            ui_live(cfg, app, runtime)
+        }),
+    );
+    fields.insert(
+        "cmdNone".to_string(),
+        Value::Constructor {
+            name: "CmdNone".to_string(),
+            args: Vec::new(),
+        },
+    );
+    fields.insert(
+        "cmdBatch".to_string(),
+        builtin("ui.cmdBatch", 1, |mut args, _runtime| {
+            Ok(Value::Constructor {
+                name: "CmdBatch".to_string(),
+                args: vec![args.pop().unwrap()],
+            })
+        }),
+    );
+    fields.insert(
+        "cmdPerform".to_string(),
+        builtin("ui.cmdPerform", 2, |args, _runtime| {
+            Ok(Value::Constructor {
+                name: "CmdPerform".to_string(),
+                args,
+            })
+        }),
+    );
+    fields.insert(
+        "subEvery".to_string(),
+        builtin("ui.subEvery", 2, |args, _runtime| {
+            if !matches!(args[0], Value::Int(millis) if millis > 0) {
+                return Err(RuntimeError::Message(
+                    "ui.subEvery expects a positive number of milliseconds".to_string(),
+                ));
+            }
+            Ok(Value::Constructor {
+                name: "SubEvery".to_string(),
+                args,
+            })
+        }),
+    );
+    fields.insert(
+        "subReceive".to_string(),
+        builtin("ui.subReceive", 2, |args, _runtime| {
+            if !matches!(args[0], Value::ChannelRecv(_)) {
+                return Err(RuntimeError::Message(
+                    "ui.subReceive expects a recv handle".to_string(),
+                ));
+            }
+            Ok(Value::Constructor {
+                name: "SubReceive".to_string(),
+                args,
+            })
         }),
     );
     Value::Record(Arc::new(fields))
 }
 
-fn ui_live(
-    cfg: Value,
-    initial_model: Value,
-    view: Value,
-    update: Value,
-    runtime: &mut Runtime,
-) -> Result<Value, RuntimeError> {
+fn ui_live(cfg: Value, app: LiveApp, runtime: &mut Runtime) -> Result<Value, RuntimeError> {
     let record = expect_record(cfg, "ui.live expects LiveConfig record")?;
     let address = match record.get("address") {
         Some(Value::Text(t)) => t.clone(),
@@ -113,24 +213,17 @@ fn ui_live(
 
     let ws_path = live_ws_path(&path);
     let ctx = runtime.ctx.clone();
-    let view_value = view.clone();
-    let update_value = update.clone();
-    let initial_model_value = initial_model.clone();
 
     let effect = EffectValue::Thunk {
         func: Arc::new(move |_| {
-            let view_value = view_value.clone();
-            let update_value = update_value.clone();
-            let initial_model_value = initial_model_value.clone();
+            let app = app.clone();
             let ctx_clone = ctx.clone();
             let http_path = normalize_path(&path);
             let ws_path = ws_path.clone();
             let title = title.clone();
 
             let handler: Handler = Arc::new(move |req: AiviRequest| {
-                let view_value = view_value.clone();
-                let update_value = update_value.clone();
-                let initial_model_value = initial_model_value.clone();
+                let app = app.clone();
                 let ctx_for_req = ctx_clone.clone();
                 let http_path = http_path.clone();
                 let ws_path = ws_path.clone();
@@ -142,7 +235,7 @@ fn ui_live(
                         let html = tokio::task::spawn_blocking(move || {
                             let cancel = CancelToken::root();
                             let mut runtime = Runtime::with_cancel(ctx_for_req.clone(), cancel);
-                            let vnode = runtime.apply(view_value, initial_model_value)?;
+                            let vnode = runtime.apply(app.view, app.model)?;
                             let (body, _handlers) = render_vnode(&vnode, "root");
                             Ok::<_, RuntimeError>(live_html_page(&title, &ws_path, &body))
                         })
@@ -157,7 +250,7 @@ fn ui_live(
                         let resp = AiviResponse {
                             status: 200,
                             headers: vec![("content-type".to_string(), "text/html".to_string())],
-                            body: html.into_bytes(),
+                            body: html.into_bytes().into(),
                         };
                         return Ok(ServerReply::Http(resp));
                     }
@@ -166,18 +259,10 @@ fn ui_live(
                     if req.path == ws_path {
                         let ws_handler = Arc::new(move |socket| {
                             let ctx = ctx_for_req.clone();
-                            let view_value = view_value.clone();
-                            let update_value = update_value.clone();
-                            let initial_model_value = initial_model_value.clone();
+                            let app = app.clone();
                             let future: WsHandlerFuture = Box::pin(async move {
                                 let result = tokio::task::spawn_blocking(move || {
-                                    run_ws_session(
-                                        ctx,
-                                        socket,
-                                        initial_model_value,
-                                        view_value,
-                                        update_value,
-                                    )
+                                    run_ws_session(ctx, socket, app)
                                 })
                                 .await
                                 .map_err(|err| AiviHttpError {
@@ -196,7 +281,7 @@ fn ui_live(
                     let resp = AiviResponse {
                         status: 404,
                         headers: vec![("content-type".to_string(), "text/plain".to_string())],
-                        body: b"not found".to_vec(),
+                        body: b"not found".to_vec().into(),
                     };
                     Ok(ServerReply::Http(resp))
                 })
@@ -211,66 +296,6 @@ fn ui_live(
     Ok(Value::Effect(Arc::new(effect)))
 }
 
-fn run_ws_session(
-    ctx: Arc<RuntimeContext>,
-    socket: WebSocketHandle,
-    initial_model: Value,
-    view: Value,
-    update: Value,
-) -> Result<(), RuntimeError> {
-    let cancel = CancelToken::root();
-    let mut runtime = Runtime::with_cancel(ctx.clone(), cancel);
-
-    let mut model = initial_model;
-    let mut vnode = runtime.apply(view.clone(), model.clone())?;
-    let mut handlers = collect_handlers(&vnode, "root");
-
-    // No need to send an init message: the initial HTML is delivered via HTTP.
-    loop {
-        let msg = socket
-            .recv()
-            .map_err(|err| RuntimeError::Message(err.message))?;
-        let text = match msg {
-            AiviWsMessage::TextMsg(t) => t,
-            AiviWsMessage::Close => break,
-            _ => continue,
-        };
-        let event = decode_event_raw(&text).map_err(RuntimeError::Message)?;
-
-        let (event_id, payload) = match event {
-            DecodedEvent::Click(id) => (id, None),
-            DecodedEvent::Input(id, value) => (id, Some(value)),
-        };
-
-        let Some(handler) = handlers.get(&event_id).cloned() else {
-            continue;
-        };
-
-        let msg_value = match (handler, payload) {
-            (UiHandler::Click(msg), _) => msg,
-            (UiHandler::Input(f), Some(value)) => runtime.apply(f, Value::Text(value))?,
-            (UiHandler::Input(_), None) => continue,
-        };
-
-        let update_fn = runtime.apply(update.clone(), msg_value)?;
-        model = runtime.apply(update_fn, model)?; // update : msg -> model -> model (curried)
-
-        let new_vnode = runtime.apply(view.clone(), model.clone())?;
-        let mut ops = Vec::new();
-        diff_vnode(&vnode, &new_vnode, "root", &mut ops);
-        vnode = new_vnode;
-        handlers = collect_handlers(&vnode, "root");
-
-        let json_ops = patch_ops_to_json_text(&Value::List(Arc::new(ops)))?;
-        let payload = format!("{{\"t\":\"patch\",\"ops\":{}}}", json_ops);
-        socket
-            .send(AiviWsMessage::TextMsg(payload))
-            .map_err(|err| RuntimeError::Message(err.message))?;
-    }
-
-    Ok(())
-}
-
 fn runtime_error_to_text(err: RuntimeError) -> String {
     match err {
         RuntimeError::Cancelled => "cancelled".to_string(),
@@ -312,32 +337,76 @@ fn live_html_page(title: &str, ws_path: &str, body_html: &str) -> String {
 </body></html>",
         escape_html_text(title),
         body_html,
-        live_client_js(ws_path)
+        live_client_js(ws_path, false)
     )
 }
 
-fn live_client_js(ws_path: &str) -> String {
-    let ws_path = ws_path.replace('\\', "\\\\").replace('"', "\\\"");
+/// `ws_target` is a path on the page's host or a full `ws://`/`wss://` URL. Hydrating clients
+/// (static pages) keep reconnecting until a live server answers, and ask it for its current
+/// view on every connect since the static markup may be stale.
+fn live_client_js(ws_target: &str, hydrate: bool) -> String {
+    // The target lands in a JS string inside `<script>`.
+    let ws_target = ws_target
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('<', "\\u003c");
+    let ws_url = if ws_target.starts_with("ws://") || ws_target.starts_with("wss://") {
+        format!("\"{ws_target}\"")
+    } else {
+        format!("(location.protocol==='https:'?'wss://':'ws://')+location.host+\"{ws_target}\"")
+    };
     format!(
         "(function(){{\
-const wsUrl=(location.protocol==='https:'?'wss://':'ws://')+location.host+\"{ws_path}\";\
-const socket=new WebSocket(wsUrl);\
-function send(obj){{ try{{socket.send(JSON.stringify(obj));}}catch(_){{}} }}\
+const wsUrl={ws_url};\
+const hydrate={hydrate};\
+let socket=null; let retry=500;\
+function send(obj){{ try{{ if(socket&&socket.readyState===1) socket.send(JSON.stringify(obj)); }}catch(_){{}} }}\
 function closestWithAttr(el,attr){{ while(el&&el!==document.body){{ if(el.getAttribute&&el.getAttribute(attr)) return el; el=el.parentNode; }} return null; }}\
-document.addEventListener('click',function(ev){{ const el=closestWithAttr(ev.target,'data-aivi-onclick'); if(!el) return; const id=parseInt(el.getAttribute('data-aivi-onclick'),10); if(!isFinite(id)) return; send({{t:'click',id:id}}); }});\
-document.addEventListener('input',function(ev){{ const el=closestWithAttr(ev.target,'data-aivi-oninput'); if(!el) return; const id=parseInt(el.getAttribute('data-aivi-oninput'),10); if(!isFinite(id)) return; const v=('value'in ev.target)?String(ev.target.value):''; send({{t:'input',id:id,value:v}}); }});\
+const timers=new Map(); const pending=new Map(); const lastSent=new Map();\
+function millis(el,name){{ const v=parseInt(el.getAttribute('data-aivi-'+name)||'',10); return isFinite(v)&&v>0?v:0; }}\
+function dispatch(el,obj){{\
+  const key=obj.t+':'+obj.id; const debounce=millis(el,'debounce'); const throttle=millis(el,'throttle');\
+  if(debounce){{ clearTimeout(timers.get(key)); timers.set(key,setTimeout(function(){{ timers.delete(key); send(obj); }},debounce)); return; }}\
+  if(throttle){{\
+    const wait=(lastSent.get(key)||0)+throttle-Date.now(); pending.set(key,obj);\
+    if(wait>0){{ if(!timers.has(key)) timers.set(key,setTimeout(function(){{ timers.delete(key); lastSent.set(key,Date.now()); send(pending.get(key)); }},wait)); return; }}\
+    lastSent.set(key,Date.now());\
+  }}\
+  send(obj);\
+}}\
+function on(type,kind,payload,prevent){{ document.addEventListener(type,function(ev){{ const el=closestWithAttr(ev.target,'data-aivi-on'+kind); if(!el) return; const id=parseInt(el.getAttribute('data-aivi-on'+kind),10); if(!isFinite(id)) return; if(prevent) ev.preventDefault(); dispatch(el,Object.assign({{t:kind,id:id}},payload?payload(ev,el):{{}})); }}); }}\
+function valueOf(ev){{ return ('value'in ev.target)?String(ev.target.value):''; }}\
+on('click','click');\
+on('input','input',function(ev){{ return {{value:valueOf(ev)}}; }});\
+on('change','change',function(ev){{ return {{value:valueOf(ev)}}; }});\
+on('change','check',function(ev){{ return {{checked:!!ev.target.checked}}; }});\
+on('keydown','keydown',function(ev){{ return {{key:ev.key,ctrl:ev.ctrlKey,shift:ev.shiftKey,alt:ev.altKey,meta:ev.metaKey}}; }});\
+on('focusin','focus'); on('focusout','blur');\
+on('submit','submit',function(ev,el){{ const fields={{}}; new FormData(el).forEach(function(v,k){{ if(typeof v==='string') fields[k]=v; }}); return {{fields:fields}}; }},true);\
 function findNode(id){{ return document.querySelector('[data-aivi-node=\"'+CSS.escape(id)+'\"]'); }}\
 function applyOp(op){{\
   if(op.op==='replace'){{ const node=findNode(op.id); if(!node) return; node.outerHTML=op.html; return; }}\
   if(op.op==='setText'){{ const node=findNode(op.id); if(!node) return; node.textContent=op.text; return; }}\
   if(op.op==='setAttr'){{ const node=findNode(op.id); if(!node) return; node.setAttribute(op.name,op.value); return; }}\
   if(op.op==='removeAttr'){{ const node=findNode(op.id); if(!node) return; node.removeAttribute(op.name); return; }}\
+  if(op.op==='setProp'){{ const node=findNode(op.id); if(!node) return; node[op.name]=op.value; return; }}\
+  if(op.op==='insertChild'){{ const parent=findNode(op.id); if(!parent) return; const tpl=document.createElement('template'); tpl.innerHTML=op.html; const node=tpl.content.firstElementChild; if(node) parent.insertBefore(node,parent.children[op.index]||null); return; }}\
+  if(op.op==='moveChild'){{ const parent=findNode(op.id); const node=findNode(op.child); if(!parent||!node) return; node.remove(); parent.insertBefore(node,parent.children[op.index]||null); return; }}\
+  if(op.op==='removeChild'){{ const node=findNode(op.child); if(node) node.remove(); return; }}\
 }}\
-socket.addEventListener('message',function(ev){{\
+function onMessage(ev){{\
   let msg=null; try{{ msg=JSON.parse(ev.data); }}catch(_){{ return; }}\
   if(!msg||msg.t!=='patch'||!Array.isArray(msg.ops)) return;\
   for(const op of msg.ops) applyOp(op);\
-}});\
+}}\
+function connect(){{\
+  socket=new WebSocket(wsUrl);\
+  socket.addEventListener('message',onMessage);\
+  if(!hydrate) return;\
+  socket.addEventListener('open',function(){{ retry=500; send({{t:'hydrate'}}); }});\
+  socket.addEventListener('close',function(){{ setTimeout(connect,retry); retry=Math.min(retry*2,30000); }});\
+}}\
+connect();\
 }})();"
     )
 }
@@ -481,15 +550,30 @@ fn render_attrs(attrs: &Value, node_id: &str, state: &mut RenderState) -> String
                     }
                 }
             }
-            Value::Constructor { name, args } if name == "OnClick" && args.len() == 1 => {
-                let id = event_id("click", node_id);
-                state.handlers.insert(id, UiHandler::Click(args[0].clone()));
-                out.push_str(&format!(" data-aivi-onclick=\"{}\"", id));
+            Value::Constructor { name, args } if name == "Value" && args.len() == 1 => {
+                if let Value::Text(t) = &args[0] {
+                    out.push_str(&format!(" value=\"{}\"", escape_attr_value(t)));
+                }
             }
-            Value::Constructor { name, args } if name == "OnInput" && args.len() == 1 => {
-                let id = event_id("input", node_id);
-                state.handlers.insert(id, UiHandler::Input(args[0].clone()));
-                out.push_str(&format!(" data-aivi-oninput=\"{}\"", id));
+            Value::Constructor { name, args } if name == "Checked" && args.len() == 1 => {
+                if let Value::Bool(true) = &args[0] {
+                    out.push_str(" checked");
+                }
+            }
+            Value::Constructor { name, args }
+                if (name == "Debounce" || name == "Throttle") && args.len() == 1 =>
+            {
+                if let Value::Int(millis) = &args[0] {
+                    let kind = name.to_ascii_lowercase();
+                    out.push_str(&format!(" data-aivi-{kind}=\"{millis}\""));
+                }
+            }
+            Value::Constructor { name, args } if args.len() == 1 => {
+                if let Some((kind, handler)) = event_handler(name, &args[0]) {
+                    let id = event_id(kind, node_id);
+                    state.handlers.insert(id, handler);
+                    out.push_str(&format!(" data-aivi-on{kind}=\"{id}\""));
+                }
             }
             _ => {}
         }
@@ -497,6 +581,23 @@ fn render_attrs(attrs: &Value, node_id: &str, state: &mut RenderState) -> String
     out
 }
 
+/// Maps an `Attr` event constructor to its protocol event kind, which also names the
+/// `data-aivi-on{kind}` attribute the client looks for.
+fn event_handler(name: &str, handler: &Value) -> Option<(&'static str, UiHandler)> {
+    let handler = handler.clone();
+    Some(match name {
+        "OnClick" => ("click", UiHandler::Click(handler)),
+        "OnInput" => ("input", UiHandler::Input(handler)),
+        "OnChange" => ("change", UiHandler::Change(handler)),
+        "OnCheck" => ("check", UiHandler::Check(handler)),
+        "OnKeyDown" => ("keydown", UiHandler::KeyDown(handler)),
+        "OnSubmit" => ("submit", UiHandler::Submit(handler)),
+        "OnFocus" => ("focus", UiHandler::Focus(handler)),
+        "OnBlur" => ("blur", UiHandler::Blur(handler)),
+        _ => return None,
+    })
+}
+
 fn is_safe_attr_name(name: &str) -> bool {
     !name.is_empty()
         && name
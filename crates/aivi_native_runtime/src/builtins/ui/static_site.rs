@@ -0,0 +1,382 @@
+// Static site generation for `aivi.ui.ssg`: whole HTML documents rendered from `Page` records,
+// an escaping audit over the rendered content, and a build step writing one file per route.
+
+struct PageSpec {
+    route: String,
+    title: String,
+    meta: Vec<(String, String)>,
+    links: Vec<(String, String)>,
+    styles: Vec<(String, Value)>,
+    body: Value,
+}
+
+fn ssg_error(message: &str) -> RuntimeError {
+    RuntimeError::Error(live_error_value(message))
+}
+
+fn record_fields<'a>(
+    value: &'a Value,
+    ctx: &str,
+) -> Result<&'a HashMap<String, Value>, RuntimeError> {
+    match value {
+        Value::Record(fields) => Ok(fields.as_ref()),
+        _ => Err(RuntimeError::Message(format!("{ctx} must be a record"))),
+    }
+}
+
+fn text_field(
+    fields: &HashMap<String, Value>,
+    name: &str,
+    ctx: &str,
+) -> Result<String, RuntimeError> {
+    match fields.get(name) {
+        Some(Value::Text(text)) => Ok(text.clone()),
+        _ => Err(RuntimeError::Message(format!("{ctx}.{name} must be Text"))),
+    }
+}
+
+fn list_field<'a>(
+    fields: &'a HashMap<String, Value>,
+    name: &str,
+    ctx: &str,
+) -> Result<&'a [Value], RuntimeError> {
+    match fields.get(name) {
+        Some(Value::List(items)) => Ok(items.as_slice()),
+        _ => Err(RuntimeError::Message(format!(
+            "{ctx}.{name} must be a List"
+        ))),
+    }
+}
+
+fn text_pairs(
+    items: &[Value],
+    first: &str,
+    second: &str,
+    ctx: &str,
+) -> Result<Vec<(String, String)>, RuntimeError> {
+    items
+        .iter()
+        .map(|item| {
+            let fields = record_fields(item, ctx)?;
+            Ok((
+                text_field(fields, first, ctx)?,
+                text_field(fields, second, ctx)?,
+            ))
+        })
+        .collect()
+}
+
+fn expect_page(value: &Value) -> Result<PageSpec, RuntimeError> {
+    let fields = record_fields(value, "Page")?;
+    let route = text_field(fields, "route", "Page")?;
+    let head = record_fields(
+        fields
+            .get("head")
+            .ok_or_else(|| RuntimeError::Message("Page.head is missing".to_string()))?,
+        "Page.head",
+    )?;
+    let styles = list_field(head, "styles", "Head")?
+        .iter()
+        .map(|rule| {
+            let rule_fields = record_fields(rule, "CssRule")?;
+            let style = rule_fields
+                .get("style")
+                .cloned()
+                .ok_or_else(|| RuntimeError::Message("CssRule.style is missing".to_string()))?;
+            Ok((text_field(rule_fields, "selector", "CssRule")?, style))
+        })
+        .collect::<Result<Vec<_>, RuntimeError>>()?;
+    Ok(PageSpec {
+        route,
+        title: text_field(head, "title", "Head")?,
+        meta: text_pairs(list_field(head, "meta", "Head")?, "name", "content", "Meta")?,
+        links: text_pairs(list_field(head, "links", "Head")?, "rel", "href", "Link")?,
+        styles,
+        body: fields
+            .get("body")
+            .cloned()
+            .ok_or_else(|| RuntimeError::Message("Page.body is missing".to_string()))?,
+    })
+}
+
+fn expect_pages(value: Value) -> Result<Vec<PageSpec>, RuntimeError> {
+    match value {
+        Value::List(items) => items.iter().map(expect_page).collect(),
+        _ => Err(RuntimeError::Message(
+            "ui.buildSite expects List Page".to_string(),
+        )),
+    }
+}
+
+/// `None` disables hydration; `Some target` is a websocket path on the page's host or a full
+/// `ws://`/`wss://` URL.
+fn expect_hydrate(value: &Value, ctx: &str) -> Result<Option<String>, RuntimeError> {
+    match value {
+        Value::Constructor { name, args } if name == "None" && args.is_empty() => Ok(None),
+        Value::Constructor { name, args } if name == "Some" && args.len() == 1 => match &args[0] {
+            Value::Text(target) => Ok(Some(target.clone())),
+            _ => Err(RuntimeError::Message(format!("{ctx} expects Option Text"))),
+        },
+        _ => Err(RuntimeError::Message(format!("{ctx} expects Option Text"))),
+    }
+}
+
+fn render_page(page: &PageSpec, hydrate: Option<&str>) -> String {
+    let mut head = String::new();
+    head.push_str("<meta charset=\"utf-8\">");
+    head.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">");
+    head.push_str(&format!("<title>{}</title>", escape_html_text(&page.title)));
+    for (name, content) in &page.meta {
+        head.push_str(&format!(
+            "<meta name=\"{}\" content=\"{}\">",
+            escape_attr_value(name),
+            escape_attr_value(content)
+        ));
+    }
+    for (rel, href) in &page.links {
+        head.push_str(&format!(
+            "<link rel=\"{}\" href=\"{}\">",
+            escape_attr_value(rel),
+            escape_attr_value(href)
+        ));
+    }
+    let rules: Vec<String> = page
+        .styles
+        .iter()
+        .filter(|(selector, _)| is_safe_css_selector(selector))
+        .map(|(selector, style)| {
+            format!(
+                "{} {{ {} }}",
+                selector.trim(),
+                escape_style_text(&style_record_to_text(style))
+            )
+        })
+        .collect();
+    if !rules.is_empty() {
+        head.push_str(&format!("<style>{}</style>", rules.join("\n")));
+    }
+
+    let (body, _handlers) = render_vnode(&page.body, "root");
+    let script = match hydrate {
+        Some(target) => format!("<script>{}</script>", live_client_js(target, true)),
+        None => String::new(),
+    };
+    format!(
+        "<!doctype html>\n<html><head>{head}</head><body>\
+<div id=\"aivi-root\">{body}</div>{script}</body></html>\n"
+    )
+}
+
+fn is_safe_css_selector(selector: &str) -> bool {
+    !selector.trim().is_empty() && !selector.contains(['{', '}', '<', ';'])
+}
+
+// Keeps a rule's declarations inside its braces and the rule inside `<style>`.
+fn escape_style_text(css: &str) -> String {
+    css.replace('<', "\\3c ")
+        .replace('{', "\\7b ")
+        .replace('}', "\\7d ")
+}
+
+fn audit_issue(route: &str, message: String) -> Value {
+    let mut fields = HashMap::new();
+    fields.insert("route".to_string(), Value::Text(route.to_string()));
+    fields.insert("message".to_string(), Value::Text(message));
+    Value::Record(Arc::new(fields))
+}
+
+/// Reports content that the renderer drops, rewrites or emits verbatim in a way that is
+/// unsafe for a static page.
+fn audit_page(page: &PageSpec) -> Vec<String> {
+    let mut out = Vec::new();
+    for (rel, href) in &page.links {
+        if is_script_url(href) {
+            out.push(format!("<link rel=\"{rel}\"> uses a script URL"));
+        }
+    }
+    for (selector, style) in &page.styles {
+        if !is_safe_css_selector(selector) {
+            out.push(format!("CSS selector `{selector}` is dropped"));
+        }
+        audit_style(style, &format!("CSS rule `{}`", selector.trim()), &mut out);
+    }
+    audit_vnode(&page.body, "root", &mut out);
+    out
+}
+
+fn audit_style(style: &Value, place: &str, out: &mut Vec<String>) {
+    let Value::Record(fields) = style else {
+        out.push(format!("{place}: style is not a record and is dropped"));
+        return;
+    };
+    let mut keys: Vec<&String> = fields.keys().collect();
+    keys.sort();
+    for key in keys {
+        if !is_safe_css_prop(key) {
+            out.push(format!("{place}: CSS property `{key}` is dropped"));
+        } else if css_value_to_text(&fields[key]).contains(['<', '{', '}', ';']) {
+            out.push(format!(
+                "{place}: CSS property `{key}` has a value with markup or CSS syntax"
+            ));
+        }
+    }
+}
+
+fn audit_vnode(vnode: &Value, node_id: &str, out: &mut Vec<String>) {
+    match vnode {
+        Value::Constructor { name, args } if name == "Keyed" && args.len() == 2 => {
+            audit_vnode(&args[1], node_id, out);
+        }
+        Value::Constructor { name, args } if name == "Element" && args.len() == 3 => {
+            if let Value::Text(tag) = &args[0] {
+                if sanitize_tag(tag) != *tag {
+                    out.push(format!(
+                        "{node_id}: tag `{tag}` is not a valid name and renders as <div>"
+                    ));
+                } else if tag.eq_ignore_ascii_case("script") || tag.eq_ignore_ascii_case("style") {
+                    out.push(format!(
+                        "{node_id}: <{tag}> content is HTML-escaped and will not run as code"
+                    ));
+                }
+            }
+            if let Value::List(attrs) = &args[1] {
+                for attr in attrs.iter() {
+                    audit_attr(attr, node_id, out);
+                }
+            }
+            if let Value::List(children) = &args[2] {
+                for (idx, child) in children.iter().enumerate() {
+                    let child_id = format!("{}/{}", node_id, child_segment(child, idx));
+                    audit_vnode(child, &child_id, out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn audit_attr(attr: &Value, node_id: &str, out: &mut Vec<String>) {
+    match attr {
+        Value::Constructor { name, args } if name == "Attr" && args.len() == 2 => {
+            let (Value::Text(key), Value::Text(value)) = (&args[0], &args[1]) else {
+                return;
+            };
+            if !is_safe_attr_name(key) {
+                out.push(format!(
+                    "{node_id}: attribute `{key}` is not a valid name and is dropped"
+                ));
+            } else if key.len() > 2 && key[..2].eq_ignore_ascii_case("on") {
+                out.push(format!(
+                    "{node_id}: inline event handler `{key}` embeds script in the page"
+                ));
+            } else if is_url_attr(key) && is_script_url(value) {
+                out.push(format!("{node_id}: `{key}` uses a script URL"));
+            }
+        }
+        Value::Constructor { name, args } if name == "Style" && args.len() == 1 => {
+            audit_style(&args[0], &format!("{node_id}: style"), out);
+        }
+        _ => {}
+    }
+}
+
+fn is_url_attr(name: &str) -> bool {
+    matches!(
+        name.to_ascii_lowercase().as_str(),
+        "href" | "src" | "action" | "formaction" | "xlink:href" | "poster" | "cite"
+    )
+}
+
+fn is_script_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside the scheme.
+    let scheme: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .take(16)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    scheme.starts_with("javascript:")
+        || scheme.starts_with("vbscript:")
+        || scheme.starts_with("data:text/html")
+}
+
+/// Maps a route to a file below the output directory: `/` and `/docs/` become
+/// `index.html` files, a last segment with an extension (`/404.html`) is used as is.
+fn route_file(route: &str) -> Result<std::path::PathBuf, String> {
+    let Some(rest) = route.strip_prefix('/') else {
+        return Err(format!("route `{route}` must start with '/'"));
+    };
+    let mut path = std::path::PathBuf::new();
+    let segments: Vec<&str> = rest.split('/').filter(|seg| !seg.is_empty()).collect();
+    for seg in &segments {
+        if *seg == "." || *seg == ".." || seg.contains(['\\', '\0', ':']) {
+            return Err(format!("route `{route}` has an invalid segment `{seg}`"));
+        }
+        path.push(seg);
+    }
+    let is_file = !route.ends_with('/') && segments.last().is_some_and(|seg| seg.contains('.'));
+    if !is_file {
+        path.push("index.html");
+    }
+    Ok(path)
+}
+
+fn build_site(config: Value, pages: Value) -> Result<Value, RuntimeError> {
+    let config = record_fields(&config, "SiteConfig")?;
+    let out_dir = text_field(config, "outDir", "SiteConfig")?;
+    let hydrate = expect_hydrate(
+        config
+            .get("hydrate")
+            .ok_or_else(|| RuntimeError::Message("SiteConfig.hydrate is missing".to_string()))?,
+        "SiteConfig.hydrate",
+    )?;
+    let pages = expect_pages(pages)?;
+
+    let effect = EffectValue::Thunk {
+        func: Arc::new(move |_| {
+            let mut files = Vec::new();
+            let mut seen = HashSet::new();
+            for page in &pages {
+                let rel = route_file(&page.route).map_err(|msg| ssg_error(&msg))?;
+                if !seen.insert(rel.clone()) {
+                    return Err(ssg_error(&format!(
+                        "route `{}` writes {} more than once",
+                        page.route,
+                        rel.display()
+                    )));
+                }
+                files.push((rel, page));
+            }
+
+            let mut written = Vec::new();
+            let mut audit = Vec::new();
+            for (rel, page) in files {
+                let path = std::path::Path::new(&out_dir).join(&rel);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(|err| ssg_error(&format!("create {}: {err}", parent.display())))?;
+                }
+                let html = render_page(page, hydrate.as_deref());
+                std::fs::write(&path, html)
+                    .map_err(|err| ssg_error(&format!("write {}: {err}", path.display())))?;
+                let rel_text = rel
+                    .iter()
+                    .map(|seg| seg.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                written.push(Value::Text(rel_text));
+                audit.extend(
+                    audit_page(page)
+                        .into_iter()
+                        .map(|message| audit_issue(&page.route, message)),
+                );
+            }
+
+            let mut report = HashMap::new();
+            report.insert("files".to_string(), Value::List(Arc::new(written)));
+            report.insert("audit".to_string(), Value::List(Arc::new(audit)));
+            Ok(Value::Record(Arc::new(report)))
+        }),
+    };
+    Ok(Value::Effect(Arc::new(effect)))
+}
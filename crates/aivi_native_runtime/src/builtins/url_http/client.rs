@@ -0,0 +1,404 @@
+use std::io::Read;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use cookie_store::CookieStore;
+
+use super::util::{backoff_policy, backoff_wait, expect_bytes, span_millis, BackoffPolicy};
+
+struct ClientConfig {
+    timeout: Duration,
+    max_redirects: u32,
+    max_retries: usize,
+    backoff: BackoffPolicy,
+    cookies: bool,
+    user_agent: Option<String>,
+}
+
+/// A reusable HTTP client. The agent keeps connections alive between requests; `close` drops
+/// it, after which every send fails. Redirects are followed here rather than by ureq so the
+/// cookie jar sees the `Set-Cookie` headers of every hop.
+struct HttpClient {
+    agent: Mutex<Option<ureq::Agent>>,
+    jar: Option<Mutex<CookieStore>>,
+    config: ClientConfig,
+    mode: HttpClientMode,
+}
+
+fn client_builtin(mode: HttpClientMode) -> Value {
+    builtin("http.client", 1, move |mut args, _| {
+        let config = args.pop().unwrap();
+        let effect = EffectValue::Thunk {
+            func: Arc::new(move |_| {
+                let config = client_config_from_value(config.clone(), "http.client")?;
+                let mut builder = ureq::AgentBuilder::new()
+                    .timeout(config.timeout)
+                    .redirects(0);
+                if let Some(user_agent) = &config.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                Ok(client_value(Arc::new(HttpClient {
+                    agent: Mutex::new(Some(builder.build())),
+                    jar: config.cookies.then(|| Mutex::new(CookieStore::default())),
+                    config,
+                    mode,
+                })))
+            }),
+        };
+        Ok(Value::Effect(Arc::new(effect)))
+    })
+}
+
+fn client_value(client: Arc<HttpClient>) -> Value {
+    let mut fields = HashMap::new();
+    {
+        let client = client.clone();
+        fields.insert(
+            "send".to_string(),
+            builtin("http.client.send", 1, move |mut args, _| {
+                let request = args.pop().unwrap();
+                let client = client.clone();
+                let effect = EffectValue::Thunk {
+                    func: Arc::new(move |_| client_send(&client, request.clone())),
+                };
+                Ok(Value::Effect(Arc::new(effect)))
+            }),
+        );
+    }
+    {
+        let client = client.clone();
+        fields.insert(
+            "cookies".to_string(),
+            builtin("http.client.cookies", 1, move |mut args, _| {
+                let url = args.pop().unwrap();
+                let client = client.clone();
+                let effect = EffectValue::Thunk {
+                    func: Arc::new(move |_| {
+                        let url = url_from_value(url.clone(), "http.client.cookies")?;
+                        let cookies = match &client.jar {
+                            Some(jar) => jar
+                                .lock()
+                                .expect("http client cookie jar")
+                                .get_request_values(&url)
+                                .map(|(name, value)| (name.to_string(), value.to_string()))
+                                .collect(),
+                            None => Vec::new(),
+                        };
+                        Ok(headers_to_value(cookies))
+                    }),
+                };
+                Ok(Value::Effect(Arc::new(effect)))
+            }),
+        );
+    }
+    fields.insert(
+        "close".to_string(),
+        builtin("http.client.close", 1, move |_, _| {
+            let client = client.clone();
+            let effect = EffectValue::Thunk {
+                func: Arc::new(move |_| {
+                    client.agent.lock().expect("http client lock").take();
+                    Ok(Value::Unit)
+                }),
+            };
+            Ok(Value::Effect(Arc::new(effect)))
+        }),
+    );
+    Value::Record(Arc::new(fields))
+}
+
+fn client_send(client: &HttpClient, request: Value) -> Result<Value, RuntimeError> {
+    let ctx = "http.client.send";
+    let record = expect_record(request, &format!("{ctx} expects ClientRequest"))?;
+    let method = expect_text(record_field(&record, "method", ctx)?, ctx)?;
+    let url = url_from_value(record_field(&record, "url", ctx)?, ctx)?;
+    ensure_http_scheme(&url, client.mode, ctx)?;
+    let mut headers = headers_from_value(&record_field(&record, "headers", ctx)?, ctx)?;
+    let body = request_body_from_value(record_field(&record, "body", ctx)?, ctx)?;
+    if let Some((content_type, _)) = &body {
+        if !headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        {
+            headers.push(("content-type".to_string(), content_type.clone()));
+        }
+    }
+    let timeout = match record.get("timeout") {
+        Some(Value::Constructor { name, args }) if name == "Some" && args.len() == 1 => {
+            Duration::from_millis(span_millis(args[0].clone(), ctx)?.max(0) as u64)
+        }
+        Some(Value::Constructor { name, args }) if name == "None" && args.is_empty() => {
+            client.config.timeout
+        }
+        None => client.config.timeout,
+        Some(_) => {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects ClientRequest.timeout Option Span"
+            )))
+        }
+    };
+
+    let Some(agent) = client.agent.lock().expect("http client lock").clone() else {
+        return Ok(make_err(http_error_record(
+            "http client is closed".to_string(),
+        )));
+    };
+    // Only idempotent requests are retried; a POST may already have taken effect.
+    let attempts = if is_idempotent(&method) {
+        client.config.max_retries + 1
+    } else {
+        1
+    };
+    let body = body.map(|(_, bytes)| bytes);
+    let mut attempt = 0;
+    let outcome = loop {
+        let result = send_with_redirects(client, &agent, &method, &url, &headers, &body, timeout);
+        attempt += 1;
+        let retryable = match &result {
+            Ok(resp) => is_retryable_status(resp.status()),
+            Err(_) => true,
+        };
+        if !retryable || attempt >= attempts {
+            break result;
+        }
+        std::thread::sleep(backoff_wait(client.config.backoff, attempt - 1));
+    };
+    Ok(match outcome.and_then(client_response_to_value) {
+        Ok(response) => make_ok(response),
+        Err(message) => make_err(http_error_record(message)),
+    })
+}
+
+fn send_with_redirects(
+    client: &HttpClient,
+    agent: &ureq::Agent,
+    method: &str,
+    url: &Url,
+    headers: &[(String, String)],
+    body: &Option<Vec<u8>>,
+    timeout: Duration,
+) -> Result<ureq::Response, String> {
+    let mut method = method.to_string();
+    let mut url = url.clone();
+    let mut headers = headers.to_vec();
+    let mut body = body.as_deref();
+    let mut redirects = 0;
+    loop {
+        let mut pending = agent.request(&method, url.as_str()).timeout(timeout);
+        for (name, value) in &headers {
+            pending = pending.set(name, value);
+        }
+        if let Some(jar) = &client.jar {
+            let cookie = jar
+                .lock()
+                .expect("http client cookie jar")
+                .get_request_values(&url)
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<_>>()
+                .join("; ");
+            if !cookie.is_empty() {
+                pending = pending.set("cookie", &cookie);
+            }
+        }
+        let result = match body {
+            Some(bytes) => pending.send_bytes(bytes),
+            None => pending.call(),
+        };
+        // Error statuses are ordinary responses here; only transport failures are `Err`.
+        let resp = match result {
+            Ok(resp) | Err(UreqError::Status(_, resp)) => resp,
+            Err(UreqError::Transport(err)) => return Err(err.to_string()),
+        };
+        if let Some(jar) = &client.jar {
+            let mut jar = jar.lock().expect("http client cookie jar");
+            for value in resp.all("set-cookie") {
+                let _ = jar.parse(value, &url);
+            }
+        }
+        let status = resp.status();
+        let next = resp
+            .header("location")
+            .and_then(|location| url.join(location).ok());
+        let next = match next {
+            Some(next)
+                if matches!(status, 301 | 302 | 303 | 307 | 308)
+                    && redirects < client.config.max_redirects =>
+            {
+                next
+            }
+            _ => return Ok(resp),
+        };
+        redirects += 1;
+        // 307/308 replay the request as-is; the others become a bodiless GET, as browsers do.
+        if status == 303 || (matches!(status, 301 | 302) && method != "HEAD") {
+            method = "GET".to_string();
+            body = None;
+            headers.retain(|(name, _)| {
+                !name.eq_ignore_ascii_case("content-type")
+                    && !name.eq_ignore_ascii_case("content-length")
+            });
+        }
+        if next.origin() != url.origin() {
+            headers.retain(|(name, _)| {
+                !name.eq_ignore_ascii_case("authorization") && !name.eq_ignore_ascii_case("cookie")
+            });
+        }
+        url = next;
+    }
+}
+
+fn is_idempotent(method: &str) -> bool {
+    matches!(
+        method.to_ascii_uppercase().as_str(),
+        "GET" | "HEAD" | "OPTIONS" | "PUT" | "DELETE" | "TRACE"
+    )
+}
+
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 502 | 503 | 504)
+}
+
+fn client_response_to_value(resp: ureq::Response) -> Result<Value, String> {
+    let status = resp.status() as i64;
+    let url = Url::parse(resp.get_url()).map_err(|err| err.to_string())?;
+    let mut headers = Vec::new();
+    for name in resp.headers_names() {
+        for value in resp.all(&name) {
+            headers.push((name.clone(), value.to_string()));
+        }
+    }
+    let mut body = Vec::new();
+    resp.into_reader()
+        .read_to_end(&mut body)
+        .map_err(|err| err.to_string())?;
+    let mut fields = HashMap::new();
+    fields.insert("status".to_string(), Value::Int(status));
+    fields.insert("headers".to_string(), headers_to_value(headers));
+    fields.insert("body".to_string(), Value::Bytes(Arc::new(body)));
+    fields.insert(
+        "url".to_string(),
+        Value::Record(Arc::new(url_to_record(&url))),
+    );
+    Ok(Value::Record(Arc::new(fields)))
+}
+
+fn client_config_from_value(value: Value, ctx: &str) -> Result<ClientConfig, RuntimeError> {
+    let record = expect_record(value, &format!("{ctx} expects ClientConfig"))?;
+    let timeout = span_millis(record_field(&record, "timeout", ctx)?, ctx)?;
+    let max_redirects = expect_int(record_field(&record, "maxRedirects", ctx)?, ctx)?;
+    let retry = expect_record(record_field(&record, "retry", ctx)?, ctx)?;
+    let max_retries = expect_int(record_field(&retry, "maxRetries", ctx)?, ctx)?;
+    let backoff = backoff_policy(record_field(&retry, "backoff", ctx)?, ctx)?;
+    let cookies = match record_field(&record, "cookies", ctx)? {
+        Value::Bool(value) => value,
+        _ => {
+            return Err(RuntimeError::Message(format!(
+                "{ctx} expects ClientConfig.cookies Bool"
+            )))
+        }
+    };
+    let user_agent = match record.get("userAgent") {
+        Some(value) => text_option_from_value(value.clone(), ctx)?,
+        None => None,
+    };
+    Ok(ClientConfig {
+        timeout: Duration::from_millis(timeout.max(0) as u64),
+        max_redirects: max_redirects.clamp(0, u32::MAX as i64) as u32,
+        max_retries: max_retries.max(0) as usize,
+        backoff,
+        cookies,
+        user_agent,
+    })
+}
+
+fn record_field(
+    record: &HashMap<String, Value>,
+    name: &str,
+    ctx: &str,
+) -> Result<Value, RuntimeError> {
+    record
+        .get(name)
+        .cloned()
+        .ok_or_else(|| RuntimeError::Message(format!("{ctx} expects field {name}")))
+}
+
+/// Encodes a `RequestBody` constructor, returning the default content type with the bytes.
+fn request_body_from_value(
+    value: Value,
+    ctx: &str,
+) -> Result<Option<(String, Vec<u8>)>, RuntimeError> {
+    let Value::Constructor { name, args } = value else {
+        return Err(RuntimeError::Message(format!("{ctx} expects RequestBody")));
+    };
+    match (name.as_str(), args.as_slice()) {
+        ("NoBody", []) => Ok(None),
+        ("BytesBody", [bytes]) => Ok(Some((
+            "application/octet-stream".to_string(),
+            expect_bytes(bytes.clone(), ctx)?.to_vec(),
+        ))),
+        ("TextBody", [text]) => Ok(Some((
+            "text/plain; charset=utf-8".to_string(),
+            expect_text(text.clone(), ctx)?.into_bytes(),
+        ))),
+        ("FormBody", [fields]) => {
+            let mut form = url::form_urlencoded::Serializer::new(String::new());
+            for field in expect_list(fields.clone(), ctx)?.iter() {
+                let field = expect_record(field.clone(), ctx)?;
+                let name = expect_text(record_field(&field, "name", ctx)?, ctx)?;
+                let value = expect_text(record_field(&field, "value", ctx)?, ctx)?;
+                form.append_pair(&name, &value);
+            }
+            Ok(Some((
+                "application/x-www-form-urlencoded".to_string(),
+                form.finish().into_bytes(),
+            )))
+        }
+        ("MultipartBody", [parts]) => {
+            multipart_body(&expect_list(parts.clone(), ctx)?, ctx).map(Some)
+        }
+        _ => Err(RuntimeError::Message(format!(
+            "{ctx} expects RequestBody (NoBody|BytesBody|TextBody|FormBody|MultipartBody)"
+        ))),
+    }
+}
+
+fn multipart_body(parts: &[Value], ctx: &str) -> Result<(String, Vec<u8>), RuntimeError> {
+    let boundary = format!("aivi-{}", uuid::Uuid::new_v4().simple());
+    let mut body = Vec::new();
+    for part in parts {
+        let part = expect_record(part.clone(), ctx)?;
+        let name = expect_text(record_field(&part, "name", ctx)?, ctx)?;
+        let file_name = text_option_from_value(record_field(&part, "fileName", ctx)?, ctx)?;
+        let content_type = text_option_from_value(record_field(&part, "contentType", ctx)?, ctx)?;
+        let data = expect_bytes(record_field(&part, "data", ctx)?, ctx)?;
+        body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+        let mut disposition = format!(
+            "Content-Disposition: form-data; name=\"{}\"",
+            escape_disposition(&name)
+        );
+        if let Some(file_name) = file_name {
+            disposition.push_str(&format!(
+                "; filename=\"{}\"",
+                escape_disposition(&file_name)
+            ));
+        }
+        body.extend_from_slice(disposition.as_bytes());
+        body.extend_from_slice(b"\r\n");
+        if let Some(content_type) = content_type {
+            body.extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+        }
+        body.extend_from_slice(b"\r\n");
+        body.extend_from_slice(&data);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    Ok((format!("multipart/form-data; boundary={boundary}"), body))
+}
+
+// Percent-encodes the characters that would end a quoted parameter, as browsers do.
+fn escape_disposition(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
@@ -1,6 +1,5 @@
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-use std::net::{TcpListener, TcpStream};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
@@ -11,7 +10,8 @@ use num_rational::BigRational;
 use regex::Regex;
 use rust_decimal::Decimal;
 
-use aivi_http_server::{ServerHandle, WebSocketHandle};
+use aivi_http_server::net::{NetListener, NetStream};
+use aivi_http_server::{ChunkSource, ServerHandle, WebSocketHandle};
 
 #[derive(Clone)]
 pub enum RuntimeError {
@@ -95,8 +95,9 @@ pub enum Value {
     ChannelSend(Arc<ChannelSend>),
     ChannelRecv(Arc<ChannelRecv>),
     FileHandle(Arc<Mutex<std::fs::File>>),
-    Listener(Arc<TcpListener>),
-    Connection(Arc<Mutex<TcpStream>>),
+    Listener(Arc<NetListener>),
+    Connection(Arc<Mutex<NetStream>>),
+    UdpSocket(Arc<std::net::UdpSocket>),
     Stream(Arc<StreamHandle>),
     HttpServer(Arc<ServerHandle>),
     WebSocket(Arc<WebSocketHandle>),
@@ -153,7 +154,7 @@ pub struct StreamHandle {
 
 pub enum StreamState {
     Socket {
-        stream: Arc<Mutex<TcpStream>>,
+        stream: Arc<Mutex<NetStream>>,
         chunk_size: usize,
     },
     Chunks {
@@ -161,6 +162,15 @@ pub enum StreamState {
         size: usize,
         buffer: Vec<u8>,
     },
+    /// Chunks that are already in memory.
+    Buffered { chunks: VecDeque<Vec<u8>> },
+    /// Values received from a channel until it is closed, each encoded into one chunk.
+    Channel {
+        receiver: Arc<ChannelRecv>,
+        encode: fn(Value) -> Result<Vec<u8>, RuntimeError>,
+    },
+    /// A streamed HTTP request or response body.
+    Body { source: ChunkSource },
 }
 
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -313,6 +323,8 @@ pub struct Runtime {
     cancel_mask: usize,
     rng_state: u64,
     debug_stack: Vec<DebugFrame>,
+    /// Handlers installed by `with`, innermost last; each maps operation names to clauses.
+    pub(crate) effect_handlers: Vec<Arc<HashMap<String, Value>>>,
 }
 
 #[derive(Clone)]
@@ -330,6 +342,7 @@ impl Runtime {
             cancel_mask: 0,
             rng_state: seed_rng_state(),
             debug_stack: Vec::new(),
+            effect_handlers: Vec::new(),
         }
     }
 
@@ -340,6 +353,7 @@ impl Runtime {
             cancel_mask: 0,
             rng_state: seed_rng_state(),
             debug_stack: Vec::new(),
+            effect_handlers: Vec::new(),
         }
     }
 
@@ -296,6 +296,7 @@ fn debug_summary_json(value: &Value) -> serde_json::Value {
         Value::FileHandle(_) => ("File", None),
         Value::Listener(_) => ("Listener", None),
         Value::Connection(_) => ("Connection", None),
+        Value::UdpSocket(_) => ("UdpSocket", None),
         Value::Stream(_) => ("Stream", None),
         Value::HttpServer(_) => ("HttpServer", None),
         Value::WebSocket(_) => ("WebSocket", None),
@@ -379,6 +380,7 @@ pub fn format_value(value: &Value) -> String {
         Value::FileHandle(_) => "<file>".to_string(),
         Value::Listener(_) => "<listener>".to_string(),
         Value::Connection(_) => "<connection>".to_string(),
+        Value::UdpSocket(_) => "<udp-socket>".to_string(),
         Value::Stream(_) => "<stream>".to_string(),
         Value::HttpServer(_) => "<http-server>".to_string(),
         Value::WebSocket(_) => "<websocket>".to_string(),
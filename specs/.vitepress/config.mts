@@ -252,6 +252,7 @@ export default defineConfig({
           { text: 'LSP Server', link: '/07_tools/02_lsp_server' },
           { text: 'VSCode Extension', link: '/07_tools/03_vscode_extension' },
           { text: 'Packaging', link: '/07_tools/04_packaging' },
+          { text: 'Error Codes', link: '/07_tools/05_error_codes' },
         ]
       }
     ]
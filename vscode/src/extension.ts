@@ -61,6 +61,7 @@ export function activate(context: vscode.ExtensionContext) {
   const fileWatchers = [
     vscode.workspace.createFileSystemWatcher("**/*.aivi"),
     vscode.workspace.createFileSystemWatcher("**/aivi.toml"),
+    vscode.workspace.createFileSystemWatcher("**/*.properties"),
     vscode.workspace.createFileSystemWatcher("**/Cargo.toml"),
     vscode.workspace.createFileSystemWatcher("**/specs/**/*"),
     vscode.workspace.createFileSystemWatcher("**/.gemini/skills/**/*"),